@@ -0,0 +1,134 @@
+//! Cross-program invocation helpers for the ZK ElGamal proof program.
+//!
+//! On-chain programs that verify proofs through the proof program use these helpers to build
+//! the verification and `CloseContextState` instructions with the correct account metas, invoke
+//! them, and read back the verified proof context from a context-state account.
+
+use {
+    crate::{
+        instruction::{self, ContextStateInfo, ProofInstruction},
+        proof_data::{PodProofType, ProofType, ZkProofData},
+        state::ProofContextState,
+    },
+    alloc::vec,
+    bytemuck::Pod,
+    solana_account_info::AccountInfo,
+    solana_cpi::invoke_signed,
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+/// The accounts of a context state that is created or closed by a proof program instruction.
+#[derive(Clone, Copy)]
+pub struct ContextStateAccountInfos<'a, 'b> {
+    pub context_state_account: &'b AccountInfo<'a>,
+    pub context_state_authority: &'b AccountInfo<'a>,
+}
+
+impl ContextStateAccountInfos<'_, '_> {
+    fn context_state_info(&self) -> ContextStateInfo<'_> {
+        ContextStateInfo {
+            context_state_account: self.context_state_account.key,
+            context_state_authority: self.context_state_authority.key,
+        }
+    }
+}
+
+/// Invoke a proof verification instruction with the proof as instruction data.
+///
+/// If `context_state_accounts` is provided, the proof context is written to the context state
+/// account, which must already be allocated and assigned to the proof program.
+pub fn verify_proof<'a, T, U>(
+    proof_instruction: ProofInstruction,
+    proof_program: &AccountInfo<'a>,
+    context_state_accounts: Option<ContextStateAccountInfos<'a, '_>>,
+    proof_data: &T,
+) -> ProgramResult
+where
+    T: Pod + ZkProofData<U>,
+    U: Pod,
+{
+    let instruction = proof_instruction.encode_verify_proof(
+        context_state_accounts
+            .as_ref()
+            .map(ContextStateAccountInfos::context_state_info),
+        proof_data,
+    );
+
+    let mut account_infos = vec![proof_program.clone()];
+    if let Some(accounts) = context_state_accounts {
+        account_infos.push(accounts.context_state_account.clone());
+        account_infos.push(accounts.context_state_authority.clone());
+    }
+    invoke_signed(&instruction, &account_infos, &[])
+}
+
+/// Invoke a proof verification instruction with the proof read from `proof_account` at `offset`.
+pub fn verify_proof_from_account<'a>(
+    proof_instruction: ProofInstruction,
+    proof_program: &AccountInfo<'a>,
+    context_state_accounts: Option<ContextStateAccountInfos<'a, '_>>,
+    proof_account: &AccountInfo<'a>,
+    offset: u32,
+) -> ProgramResult {
+    let instruction = proof_instruction.encode_verify_proof_from_account(
+        context_state_accounts
+            .as_ref()
+            .map(ContextStateAccountInfos::context_state_info),
+        proof_account.key,
+        offset,
+    );
+
+    let mut account_infos = vec![proof_program.clone(), proof_account.clone()];
+    if let Some(accounts) = context_state_accounts {
+        account_infos.push(accounts.context_state_account.clone());
+        account_infos.push(accounts.context_state_authority.clone());
+    }
+    invoke_signed(&instruction, &account_infos, &[])
+}
+
+/// Invoke a `CloseContextState` instruction.
+///
+/// If the context state authority is a program-derived address of the calling program,
+/// `signer_seeds` must hold its seeds. Otherwise, the authority must have signed the transaction
+/// and `signer_seeds` is empty.
+pub fn close_context_state<'a>(
+    proof_program: &AccountInfo<'a>,
+    context_state_accounts: ContextStateAccountInfos<'a, '_>,
+    destination_account: &AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let instruction = instruction::close_context_state(
+        context_state_accounts.context_state_info(),
+        destination_account.key,
+    );
+
+    let account_infos = [
+        proof_program.clone(),
+        context_state_accounts.context_state_account.clone(),
+        destination_account.clone(),
+        context_state_accounts.context_state_authority.clone(),
+    ];
+    invoke_signed(&instruction, &account_infos, signer_seeds)
+}
+
+/// Read the proof context state of `proof_type` from a context state account.
+///
+/// The account must be owned by the proof program. A context state that expires is rejected
+/// after its expiry slot.
+pub fn read_proof_context_state<U: Pod>(
+    context_state_account: &AccountInfo,
+    proof_type: ProofType,
+    current_slot: u64,
+) -> Result<ProofContextState<U>, ProgramError> {
+    if !crate::check_id(context_state_account.owner) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = context_state_account.try_borrow_data()?;
+    let state = ProofContextState::<U>::try_from_bytes_unexpired(&data, current_slot)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if state.proof_type != PodProofType::from(proof_type) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(*state)
+}
@@ -32,17 +32,52 @@
 //! NOTE: A context-state account must be pre-allocated to the exact size of the context data that
 //! is expected for a proof type before it is included as part of a proof verification instruction.
 //!
+//! Hashed and expiring context states are not yet supported by the on-chain proof program, which
+//! decodes the instruction data with [`ProofInstruction::proof_data`] and rejects the trailing
+//! bytes that request them. Processors opt into them with separate decoders:
+//!
+//! - If a proof verification instruction is encoded with
+//!   [`ProofInstruction::encode_verify_proof_hashed`] and decoded with
+//!   [`ProofInstruction::proof_data_with_hashed_context`], only a hash of the context data is
+//!   stored in a [`HashedProofContextState`], and the context-state account is pre-allocated to
+//!   the size of a [`HashedProofContextState`]. Consumers of the account then supply the context
+//!   data again and check it with `HashedProofContextState::verify_proof_context`.
+//! - If a proof verification instruction is encoded with
+//!   [`ProofInstruction::encode_verify_proof_with_expiry`] and decoded with
+//!   [`ProofInstruction::proof_data_with_expiry`], the expiry slot is stored after the context
+//!   data, and the context-state account is pre-allocated with room for a
+//!   [`ProofContextStateExpiry`]. Consumers of the account reject it after the expiry slot with
+//!   [`ProofContextState::try_from_bytes_unexpired`].
+//!
+//! Neither mode can be requested by an instruction that reads its proof from an account.
+//!
+//! The proof verification instructions from `VerifyPercentageWithCapSplit` to `VerifyProofBatch`
+//! are not yet supported by the on-chain proof program, so they are only available with the
+//! `unstable` feature and their discriminants may still change. Only the in-memory processor of
+//! the `test-harness` feature of the ZK SDK emulates them. The `VerifyProofBatch` instruction
+//! verifies several proofs of the same type with a single multiscalar multiplication, and a
+//! proof batch does not create context-state accounts.
+//!
 //! The `CloseContextState` instruction closes a context state account. A transaction containing
 //! this instruction must be signed by the context account's owner. This instruction can be used by
 //! the account owner to reclaim lamports for storage.
 //!
 //! [`ZK ElGamal proof`]: https://docs.solanalabs.com/runtime/zk-token-proof
 //! [`context-state`]: https://docs.solanalabs.com/runtime/zk-token-proof#context-data
+//! [`ProofContextStateExpiry`]: crate::state::ProofContextStateExpiry
+//! [`ProofContextState::try_from_bytes_unexpired`]: crate::state::ProofContextState::try_from_bytes_unexpired
+//! [`HashedProofContextState`]: crate::state::HashedProofContextState
 
+#[cfg(feature = "unstable")]
+use crate::proof_data::{PodProofType, ProofBatchHeader, MAX_PROOF_BATCH_SIZE};
 use {
-    crate::proof_data::ZkProofData,
+    crate::{
+        proof_data::ZkProofData,
+        state::{ProofContextStateExpiry, HASHED_PROOF_CONTEXT_STATE_TAG},
+    },
     alloc::vec,
     bytemuck::{bytes_of, Pod},
+    core::mem::size_of,
     num_derive::{FromPrimitive, ToPrimitive},
     num_traits::{FromPrimitive, ToPrimitive},
     solana_address::Address,
@@ -461,6 +496,334 @@ pub enum ProofInstruction {
     ///   ii. `u32` byte offset if proof is provided as an account
     ///
     VerifyBatchedGroupedCiphertext3HandlesValidity,
+
+    /// Verify a percentage-with-cap fee-split proof.
+    ///
+    /// A percentage-with-cap fee-split proof certifies that a tuple of Pedersen commitments
+    /// satisfy a percentage relation and that a sequence of fee share commitments add up to the
+    /// fee commitment.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `PercentageWithCapSplitProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyPercentageWithCapSplit,
+
+    /// Verify a percentage-with-cap proof with a 128-bit maximum cap bound.
+    ///
+    /// The proof certifies the same relation as `VerifyPercentageWithCap`, but the maximum cap
+    /// bound is encoded as a 128-bit integer.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `PercentageWithCapU128ProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyPercentageWithCapU128,
+
+    /// Verify a grouped-ciphertext-ciphertext equality proof for a grouped ciphertext with 2
+    /// handles.
+    ///
+    /// A grouped-ciphertext-ciphertext equality proof certifies that the ciphertext component of
+    /// a grouped ElGamal ciphertext at a specified handle index and a regular twisted ElGamal
+    /// ciphertext encrypt the same message.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `GroupedCiphertext2HandlesCiphertextEqualityProofData` if proof is provided as
+    ///      instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyGroupedCiphertext2HandlesCiphertextEquality,
+
+    /// Verify a grouped-ciphertext-ciphertext equality proof for a grouped ciphertext with 3
+    /// handles.
+    ///
+    /// A grouped-ciphertext-ciphertext equality proof certifies that the ciphertext component of
+    /// a grouped ElGamal ciphertext at a specified handle index and a regular twisted ElGamal
+    /// ciphertext encrypt the same message.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `GroupedCiphertext3HandlesCiphertextEqualityProofData` if proof is provided as
+    ///      instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyGroupedCiphertext3HandlesCiphertextEquality,
+
+    /// Verify a grouped zero-ciphertext proof for a grouped ciphertext with 2 handles.
+    ///
+    /// A grouped zero-ciphertext proof certifies that a grouped ElGamal ciphertext encrypts the
+    /// value zero with respect to all of its decryption handles.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `GroupedZeroCiphertext2HandlesProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyGroupedZeroCiphertext2Handles,
+
+    /// Verify a grouped zero-ciphertext proof for a grouped ciphertext with 3 handles.
+    ///
+    /// A grouped zero-ciphertext proof certifies that a grouped ElGamal ciphertext encrypts the
+    /// value zero with respect to all of its decryption handles.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `GroupedZeroCiphertext3HandlesProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyGroupedZeroCiphertext3Handles,
+
+    /// Verify a lo/hi split consistency proof.
+    ///
+    /// A split consistency proof certifies that two ElGamal ciphertexts that encrypt the low 48
+    /// bits and the high 16 bits of an amount are consistent with an ElGamal ciphertext of the
+    /// full amount under the same public key.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `SplitConsistencyProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifySplitConsistency,
+
+    /// Verify an opening-based ciphertext-commitment equality proof.
+    ///
+    /// An opening-based ciphertext-commitment equality proof certifies that an ElGamal ciphertext
+    /// and a Pedersen commitment encrypt/encode the same message. Unlike
+    /// `VerifyCiphertextCommitmentEquality`, the proof is generated from the openings of the
+    /// ciphertext and the commitment rather than from the decryption key.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   There are four ways to structure the accounts, depending on whether the
+    ///   proof is provided as instruction data or in a separate account, and whether
+    ///   a proof context is created.
+    ///
+    ///   1. **Proof in instruction data, no context state:**
+    ///      - No accounts are required.
+    ///
+    ///   2. **Proof in instruction data, with context state:**
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    ///   3. **Proof in account, no context state:**
+    ///      - `[]` Account to read the proof from.
+    ///
+    ///   4. **Proof in account, with context state:**
+    ///      - `[]` Account to read the proof from.
+    ///      - `[writable]` The proof context account to create.
+    ///      - `[]` The proof context account owner.
+    ///
+    /// The instruction expects either:
+    ///   i. `CiphertextCommitmentOpeningEqualityProofData` if proof is provided as instruction data
+    ///   ii. `u32` byte offset if proof is provided as an account
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyCiphertextCommitmentOpeningEquality,
+
+    /// Verify a batch of proofs of the same type.
+    ///
+    /// The verification equations of all proofs in the batch are checked with a single
+    /// multiscalar multiplication. Only the proof types whose verification equations can be
+    /// combined are supported: `CiphertextCommitmentEquality`,
+    /// `BatchedGroupedCiphertext3HandlesValidity`, `BatchedRangeProofU64`, and
+    /// `BatchedRangeProofU128`.
+    ///
+    /// **Not yet supported by the on-chain proof program.** This instruction is only available
+    /// with the `unstable` feature, and only the in-memory processor of the `test-harness`
+    /// feature of the ZK SDK emulates it.
+    ///
+    /// Accounts expected by this instruction:
+    ///   None
+    ///
+    /// Data expected by this instruction:
+    ///   `ProofBatchHeader` followed by `num_proofs` proof data of the type specified in the
+    ///   header, each including its own context
+    ///
+    #[cfg(feature = "unstable")]
+    VerifyProofBatch,
 }
 
 /// Pubkeys associated with a context state account to be used as parameters to functions.
@@ -490,6 +853,37 @@ pub fn close_context_state(
     }
 }
 
+/// Create a `VerifyProofBatch` instruction.
+///
+/// Only available with the `unstable` feature; the proof program does not support proof batches
+/// yet.
+///
+/// Returns `None` if `proof_data` is empty or holds more than `MAX_PROOF_BATCH_SIZE` proofs.
+#[cfg(feature = "unstable")]
+pub fn verify_proof_batch<T, U>(proof_data: &[T]) -> Option<Instruction>
+where
+    T: Pod + ZkProofData<U>,
+    U: Pod,
+{
+    if proof_data.is_empty() || proof_data.len() > MAX_PROOF_BATCH_SIZE {
+        return None;
+    }
+    let header = ProofBatchHeader {
+        proof_type: T::PROOF_TYPE.into(),
+        num_proofs: proof_data.len() as u8,
+    };
+
+    let mut data = vec![ToPrimitive::to_u8(&ProofInstruction::VerifyProofBatch).unwrap()];
+    data.extend_from_slice(bytes_of(&header));
+    data.extend_from_slice(bytemuck::cast_slice(proof_data));
+
+    Some(Instruction {
+        program_id: crate::id(),
+        accounts: vec![],
+        data,
+    })
+}
+
 impl ProofInstruction {
     pub fn encode_verify_proof<T, U>(
         &self,
@@ -519,6 +913,59 @@ impl ProofInstruction {
         }
     }
 
+    /// Create a proof verification instruction that stores the proof context in a context state
+    /// account that expires after `expiry_slot`.
+    ///
+    /// **Not yet supported by the on-chain proof program**, which rejects the trailing expiry
+    /// slot. Only processors that decode instructions with
+    /// `ProofInstruction::proof_data_with_expiry`, such as the in-memory processor of the
+    /// `test-harness` feature of the ZK SDK, accept it.
+    pub fn encode_verify_proof_with_expiry<T, U>(
+        &self,
+        context_state_info: ContextStateInfo,
+        expiry_slot: u64,
+        proof_data: &T,
+    ) -> Instruction
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let mut instruction = self.encode_verify_proof(Some(context_state_info), proof_data);
+        instruction
+            .data
+            .extend_from_slice(bytes_of(&ProofContextStateExpiry::new(expiry_slot)));
+        instruction
+    }
+
+    /// Create a proof verification instruction that stores a hash of the proof context in a
+    /// `HashedProofContextState`.
+    ///
+    /// The instruction data is followed by `HASHED_PROOF_CONTEXT_STATE_TAG` to request the hashed
+    /// context state.
+    ///
+    /// **Not yet supported by the on-chain proof program**, which rejects the trailing tag. Only
+    /// processors that decode instructions with `ProofInstruction::proof_data_with_hashed_context`,
+    /// such as the in-memory processor of the `test-harness` feature of the ZK SDK, accept it.
+    pub fn encode_verify_proof_hashed<T, U>(
+        &self,
+        context_state_info: ContextStateInfo,
+        proof_data: &T,
+    ) -> Instruction
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let mut instruction = self.encode_verify_proof(Some(context_state_info), proof_data);
+        instruction.data.push(HASHED_PROOF_CONTEXT_STATE_TAG);
+        instruction
+    }
+
+    /// Create a proof verification instruction that reads the proof data from `proof_account` at
+    /// `offset`.
+    ///
+    /// The instruction data holds only the offset, so it cannot request an expiring or hashed
+    /// context state. A context state that is created from a proof in an account is always a
+    /// full `ProofContextState` without an expiry.
     pub fn encode_verify_proof_from_account(
         &self,
         context_state_info: Option<ContextStateInfo>,
@@ -551,6 +998,11 @@ impl ProofInstruction {
             .and_then(|instruction| FromPrimitive::from_u8(*instruction))
     }
 
+    /// The proof data of a proof verification instruction.
+    ///
+    /// The instruction data must hold exactly the proof data after the instruction discriminator,
+    /// so instructions that request an expiring or hashed context state are rejected. These are
+    /// decoded with `proof_data_with_expiry` and `proof_data_with_hashed_context` instead.
     pub fn proof_data<T, U>(input: &[u8]) -> Option<&T>
     where
         T: Pod + ZkProofData<U>,
@@ -560,4 +1012,80 @@ impl ProofInstruction {
             .get(1..)
             .and_then(|data| bytemuck::try_from_bytes(data).ok())
     }
+
+    /// The proof data of a proof verification instruction and the expiry of the context state
+    /// that it creates, if the instruction sets one.
+    ///
+    /// The proof program does not support expiring context states yet and decodes instructions
+    /// with `proof_data`. A processor opts into expiring context states by decoding instructions
+    /// with this function instead.
+    pub fn proof_data_with_expiry<T, U>(
+        input: &[u8],
+    ) -> Option<(&T, Option<&ProofContextStateExpiry>)>
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let (proof_data, expiry) = input.get(1..)?.split_at_checked(size_of::<T>())?;
+        let expiry = if expiry.is_empty() {
+            None
+        } else {
+            Some(bytemuck::try_from_bytes(expiry).ok()?)
+        };
+        Some((bytemuck::try_from_bytes(proof_data).ok()?, expiry))
+    }
+
+    /// The proof data of a proof verification instruction that requests a
+    /// `HashedProofContextState`.
+    ///
+    /// Returns `None` unless the proof data is followed by exactly
+    /// `HASHED_PROOF_CONTEXT_STATE_TAG`. The proof program does not support hashed context states
+    /// yet and decodes instructions with `proof_data`. A processor opts into hashed context states
+    /// by decoding instructions with this function first.
+    pub fn proof_data_with_hashed_context<T, U>(input: &[u8]) -> Option<&T>
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let (proof_data, tag) = input.get(1..)?.split_at_checked(size_of::<T>())?;
+        if tag != [HASHED_PROOF_CONTEXT_STATE_TAG] {
+            return None;
+        }
+        bytemuck::try_from_bytes(proof_data).ok()
+    }
+
+    /// The header of a `VerifyProofBatch` instruction.
+    #[cfg(feature = "unstable")]
+    pub fn proof_batch_header(input: &[u8]) -> Option<&ProofBatchHeader> {
+        input
+            .get(1..)
+            .and_then(|data| data.get(..size_of::<ProofBatchHeader>()))
+            .and_then(|header| bytemuck::try_from_bytes(header).ok())
+    }
+
+    /// The proof data of a `VerifyProofBatch` instruction.
+    ///
+    /// Returns `None` if the proof type in the header is not the proof type of `T` or if the
+    /// number of proofs in the header does not match the length of the instruction data.
+    #[cfg(feature = "unstable")]
+    pub fn proof_data_batch<T, U>(input: &[u8]) -> Option<&[T]>
+    where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        let header = Self::proof_batch_header(input)?;
+        if header.proof_type != PodProofType::from(T::PROOF_TYPE)
+            || header.num_proofs == 0
+            || header.num_proofs as usize > MAX_PROOF_BATCH_SIZE
+        {
+            return None;
+        }
+        let data = input
+            .get(1..)
+            .and_then(|data| data.get(size_of::<ProofBatchHeader>()..))?;
+        if data.len() != size_of::<T>().checked_mul(header.num_proofs as usize)? {
+            return None;
+        }
+        bytemuck::try_cast_slice(data).ok()
+    }
 }
@@ -16,6 +16,8 @@
 
 extern crate alloc;
 
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod instruction;
 pub mod proof_data;
 pub mod state;
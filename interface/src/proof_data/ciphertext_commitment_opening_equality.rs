@@ -0,0 +1,54 @@
+//! The opening-based ciphertext-commitment equality proof instruction.
+//!
+//! An opening-based ciphertext-commitment equality proof is defined with respect to a twisted
+//! ElGamal ciphertext and a Pedersen commitment. The proof certifies that a given ciphertext and a
+//! commitment pair encrypts/encodes the same message. To generate the proof, a prover must provide
+//! the Pedersen openings of the ciphertext and of the commitment, but not the decryption key.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::{
+            elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+            pedersen::PodPedersenCommitment,
+        },
+        sigma_proofs::PodCiphertextCommitmentOpeningEqualityProof,
+    },
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyCiphertextCommitmentOpeningEquality` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct CiphertextCommitmentOpeningEqualityProofData {
+    pub context: CiphertextCommitmentOpeningEqualityProofContext,
+    pub proof: PodCiphertextCommitmentOpeningEqualityProof,
+}
+
+/// The context data needed to verify an opening-based ciphertext-commitment equality proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct CiphertextCommitmentOpeningEqualityProofContext {
+    /// The ElGamal pubkey
+    pub pubkey: PodElGamalPubkey, // 32 bytes
+
+    /// The ciphertext encrypted under the ElGamal pubkey
+    pub ciphertext: PodElGamalCiphertext, // 64 bytes
+
+    /// The Pedersen commitment
+    pub commitment: PodPedersenCommitment, // 32 bytes
+}
+
+impl ZkProofData<CiphertextCommitmentOpeningEqualityProofContext>
+    for CiphertextCommitmentOpeningEqualityProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::CiphertextCommitmentOpeningEquality;
+
+    fn context_data(&self) -> &CiphertextCommitmentOpeningEqualityProofContext {
+        &self.context
+    }
+}
@@ -0,0 +1,59 @@
+//! The grouped-ciphertext-ciphertext equality proof instruction for grouped ciphertexts with two
+//! handles.
+//!
+//! A grouped-ciphertext-ciphertext equality proof is defined with respect to a grouped ElGamal
+//! ciphertext and a regular twisted ElGamal ciphertext. The proof certifies that the ciphertext
+//! component of the grouped ciphertext at a specified handle index and the regular ciphertext
+//! encrypt the same message. To generate the proof, a prover must provide the decryption key for
+//! the specified handle of the grouped ciphertext and the randomness used to generate the regular
+//! ciphertext.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::{
+            elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+            grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
+        },
+        sigma_proofs::PodCiphertextCiphertextEqualityProof,
+    },
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyGroupedCiphertext2HandlesCiphertextEquality` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedCiphertext2HandlesCiphertextEqualityProofData {
+    pub context: GroupedCiphertext2HandlesCiphertextEqualityProofContext,
+
+    pub proof: PodCiphertextCiphertextEqualityProof,
+}
+
+/// The context data needed to verify a grouped-ciphertext-ciphertext equality proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedCiphertext2HandlesCiphertextEqualityProofContext {
+    pub first_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub second_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub grouped_ciphertext: PodGroupedElGamalCiphertext2Handles, // 96 bytes
+
+    pub second_ciphertext: PodElGamalCiphertext, // 64 bytes
+
+    pub handle_index: u8, // 1 byte
+}
+
+impl ZkProofData<GroupedCiphertext2HandlesCiphertextEqualityProofContext>
+    for GroupedCiphertext2HandlesCiphertextEqualityProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::GroupedCiphertext2HandlesCiphertextEquality;
+
+    fn context_data(&self) -> &GroupedCiphertext2HandlesCiphertextEqualityProofContext {
+        &self.context
+    }
+}
@@ -0,0 +1,59 @@
+//! The grouped-ciphertext-ciphertext equality proof instruction for grouped ciphertexts with three
+//! handles.
+//!
+//! A grouped-ciphertext-ciphertext equality proof is defined with respect to a grouped ElGamal
+//! ciphertext and a regular twisted ElGamal ciphertext. The proof certifies that the ciphertext
+//! component of the grouped ciphertext at a specified handle index and the regular ciphertext
+//! encrypt the same message. To generate the proof, a prover must provide the decryption key for
+//! the specified handle of the grouped ciphertext and the randomness used to generate the regular
+//! ciphertext.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::{
+            elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+            grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
+        },
+        sigma_proofs::PodCiphertextCiphertextEqualityProof,
+    },
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyGroupedCiphertext3HandlesCiphertextEquality` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedCiphertext3HandlesCiphertextEqualityProofData {
+    pub context: GroupedCiphertext3HandlesCiphertextEqualityProofContext,
+
+    pub proof: PodCiphertextCiphertextEqualityProof,
+}
+
+/// The context data needed to verify a grouped-ciphertext-ciphertext equality proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedCiphertext3HandlesCiphertextEqualityProofContext {
+    pub first_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub second_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub grouped_ciphertext: PodGroupedElGamalCiphertext3Handles, // 128 bytes
+
+    pub second_ciphertext: PodElGamalCiphertext, // 64 bytes
+
+    pub handle_index: u8, // 1 byte
+}
+
+impl ZkProofData<GroupedCiphertext3HandlesCiphertextEqualityProofContext>
+    for GroupedCiphertext3HandlesCiphertextEqualityProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::GroupedCiphertext3HandlesCiphertextEquality;
+
+    fn context_data(&self) -> &GroupedCiphertext3HandlesCiphertextEqualityProofContext {
+        &self.context
+    }
+}
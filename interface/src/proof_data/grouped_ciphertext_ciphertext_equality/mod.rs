@@ -0,0 +1,13 @@
+mod handles_2;
+mod handles_3;
+
+pub use {
+    handles_2::{
+        GroupedCiphertext2HandlesCiphertextEqualityProofContext,
+        GroupedCiphertext2HandlesCiphertextEqualityProofData,
+    },
+    handles_3::{
+        GroupedCiphertext3HandlesCiphertextEqualityProofContext,
+        GroupedCiphertext3HandlesCiphertextEqualityProofData,
+    },
+};
@@ -0,0 +1,50 @@
+//! The grouped zero-ciphertext proof instruction for grouped ciphertexts with two handles.
+//!
+//! A grouped zero-ciphertext proof certifies that a grouped ElGamal ciphertext encrypts the
+//! message zero with respect to each of its decryption handles. To generate the proof, a prover
+//! must provide the Pedersen opening associated with the grouped ciphertext's commitment.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::{
+            elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
+        },
+        sigma_proofs::PodGroupedZeroCiphertext2HandlesProof,
+    },
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyGroupedZeroCiphertext2Handles` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedZeroCiphertext2HandlesProofData {
+    pub context: GroupedZeroCiphertext2HandlesProofContext,
+
+    pub proof: PodGroupedZeroCiphertext2HandlesProof,
+}
+
+/// The context data needed to verify a grouped zero-ciphertext proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedZeroCiphertext2HandlesProofContext {
+    pub first_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub second_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub grouped_ciphertext: PodGroupedElGamalCiphertext2Handles, // 96 bytes
+}
+
+impl ZkProofData<GroupedZeroCiphertext2HandlesProofContext>
+    for GroupedZeroCiphertext2HandlesProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::GroupedZeroCiphertext2Handles;
+
+    fn context_data(&self) -> &GroupedZeroCiphertext2HandlesProofContext {
+        &self.context
+    }
+}
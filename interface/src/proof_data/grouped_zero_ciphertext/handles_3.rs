@@ -0,0 +1,52 @@
+//! The grouped zero-ciphertext proof instruction for grouped ciphertexts with three handles.
+//!
+//! A grouped zero-ciphertext proof certifies that a grouped ElGamal ciphertext encrypts the
+//! message zero with respect to each of its decryption handles. To generate the proof, a prover
+//! must provide the Pedersen opening associated with the grouped ciphertext's commitment.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::{
+            elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
+        },
+        sigma_proofs::PodGroupedZeroCiphertext3HandlesProof,
+    },
+};
+
+/// The instruction data that is needed for the
+/// `ProofInstruction::VerifyGroupedZeroCiphertext3Handles` instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedZeroCiphertext3HandlesProofData {
+    pub context: GroupedZeroCiphertext3HandlesProofContext,
+
+    pub proof: PodGroupedZeroCiphertext3HandlesProof,
+}
+
+/// The context data needed to verify a grouped zero-ciphertext proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct GroupedZeroCiphertext3HandlesProofContext {
+    pub first_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub second_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub third_pubkey: PodElGamalPubkey, // 32 bytes
+
+    pub grouped_ciphertext: PodGroupedElGamalCiphertext3Handles, // 128 bytes
+}
+
+impl ZkProofData<GroupedZeroCiphertext3HandlesProofContext>
+    for GroupedZeroCiphertext3HandlesProofData
+{
+    const PROOF_TYPE: ProofType = ProofType::GroupedZeroCiphertext3Handles;
+
+    fn context_data(&self) -> &GroupedZeroCiphertext3HandlesProofContext {
+        &self.context
+    }
+}
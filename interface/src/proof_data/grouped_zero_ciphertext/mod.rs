@@ -0,0 +1,11 @@
+mod handles_2;
+mod handles_3;
+
+pub use {
+    handles_2::{
+        GroupedZeroCiphertext2HandlesProofContext, GroupedZeroCiphertext2HandlesProofData,
+    },
+    handles_3::{
+        GroupedZeroCiphertext3HandlesProofContext, GroupedZeroCiphertext3HandlesProofData,
+    },
+};
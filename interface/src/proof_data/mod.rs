@@ -9,15 +9,32 @@ pub mod batched_grouped_ciphertext_validity;
 pub mod batched_range_proof;
 pub mod ciphertext_ciphertext_equality;
 pub mod ciphertext_commitment_equality;
+#[cfg(feature = "unstable")]
+pub mod ciphertext_commitment_opening_equality;
+#[cfg(feature = "unstable")]
+pub mod grouped_ciphertext_ciphertext_equality;
 pub mod grouped_ciphertext_validity;
+#[cfg(feature = "unstable")]
+pub mod grouped_zero_ciphertext;
 pub mod percentage_with_cap;
+#[cfg(feature = "unstable")]
+pub mod percentage_with_cap_split;
+pub mod proof_batch;
 pub mod pubkey_validity;
+#[cfg(feature = "unstable")]
+pub mod split_consistency;
 pub mod zero_ciphertext;
 
 pub use {
     batched_grouped_ciphertext_validity::*, batched_range_proof::*,
     ciphertext_ciphertext_equality::*, ciphertext_commitment_equality::*,
-    grouped_ciphertext_validity::*, percentage_with_cap::*, pubkey_validity::*, zero_ciphertext::*,
+    grouped_ciphertext_validity::*, percentage_with_cap::*, proof_batch::*, pubkey_validity::*,
+    zero_ciphertext::*,
+};
+#[cfg(feature = "unstable")]
+pub use {
+    ciphertext_commitment_opening_equality::*, grouped_ciphertext_ciphertext_equality::*,
+    grouped_zero_ciphertext::*, percentage_with_cap_split::*, split_consistency::*,
 };
 
 #[derive(Clone, Copy, Debug, FromPrimitive, ToPrimitive, PartialEq, Eq)]
@@ -37,6 +54,22 @@ pub enum ProofType {
     BatchedGroupedCiphertext2HandlesValidity,
     GroupedCiphertext3HandlesValidity,
     BatchedGroupedCiphertext3HandlesValidity,
+    #[cfg(feature = "unstable")]
+    PercentageWithCapSplit,
+    #[cfg(feature = "unstable")]
+    PercentageWithCapU128,
+    #[cfg(feature = "unstable")]
+    GroupedCiphertext2HandlesCiphertextEquality,
+    #[cfg(feature = "unstable")]
+    GroupedCiphertext3HandlesCiphertextEquality,
+    #[cfg(feature = "unstable")]
+    GroupedZeroCiphertext2Handles,
+    #[cfg(feature = "unstable")]
+    GroupedZeroCiphertext3Handles,
+    #[cfg(feature = "unstable")]
+    SplitConsistency,
+    #[cfg(feature = "unstable")]
+    CiphertextCommitmentOpeningEquality,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
@@ -5,7 +5,13 @@
 //! certifies that either
 //! - the `percentage` amount is equal to a constant (referred to as the `max_value`)
 //! - the `delta` and `claimed` amounts are equal
+//!
+//! The `max_value` is encoded either as a 64-bit or a 128-bit integer. The encoding width is part
+//! of the proof context and is hashed into the proof transcript, so a proof for one width cannot be
+//! verified as a proof for the other.
 
+#[cfg(feature = "unstable")]
+use solana_zk_sdk_pod::primitive_types::PodU128;
 use {
     crate::proof_data::{ProofType, ZkProofData},
     bytemuck_derive::{Pod, Zeroable},
@@ -57,3 +63,44 @@ impl ZkProofData<PercentageWithCapProofContext> for PercentageWithCapProofData {
         &self.context
     }
 }
+
+/// The instruction data that is needed for the `ProofInstruction::VerifyPercentageWithCapU128`
+/// instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct PercentageWithCapU128ProofData {
+    pub context: PercentageWithCapU128ProofContext,
+
+    pub proof: PodPercentageWithCapProof,
+}
+
+/// The context data needed to verify a percentage-with-cap proof with a 128-bit maximum cap bound.
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct PercentageWithCapU128ProofContext {
+    /// The Pedersen commitment to the percentage amount.
+    pub percentage_commitment: PodPedersenCommitment,
+
+    /// The Pedersen commitment to the delta amount.
+    pub delta_commitment: PodPedersenCommitment,
+
+    /// The Pedersen commitment to the claimed amount.
+    pub claimed_commitment: PodPedersenCommitment,
+
+    /// The 128-bit maximum cap bound.
+    pub max_value: PodU128,
+}
+
+#[cfg(feature = "unstable")]
+impl ZkProofData<PercentageWithCapU128ProofContext> for PercentageWithCapU128ProofData {
+    const PROOF_TYPE: ProofType = ProofType::PercentageWithCapU128;
+
+    fn context_data(&self) -> &PercentageWithCapU128ProofContext {
+        &self.context
+    }
+}
@@ -0,0 +1,62 @@
+//! The percentage-with-cap fee-split proof instruction.
+//!
+//! The percentage-with-cap fee-split proof extends the percentage-with-cap proof with a sequence
+//! of Pedersen commitments that encode how the fee is split among multiple recipients. In addition
+//! to the percentage-with-cap relation, the proof certifies that the fee share commitments add up
+//! to the `percentage` commitment.
+//!
+//! The maximum number of fee shares that can be certified in a single proof is fixed at 4. Unused
+//! fee share commitments in the context must be zeroed.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::pedersen::PodPedersenCommitment, primitive_types::PodU64,
+        sigma_proofs::PodPercentageWithCapSplitProof,
+    },
+};
+
+/// The maximum number of fee shares that can be certified in a single fee-split proof.
+pub const MAX_FEE_SHARES: usize = 4;
+
+/// The instruction data that is needed for the `ProofInstruction::VerifyPercentageWithCapSplit`
+/// instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct PercentageWithCapSplitProofData {
+    pub context: PercentageWithCapSplitProofContext,
+
+    pub proof: PodPercentageWithCapSplitProof,
+}
+
+/// The context data needed to verify a percentage-with-cap fee-split proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct PercentageWithCapSplitProofContext {
+    /// The Pedersen commitment to the total fee amount.
+    pub percentage_commitment: PodPedersenCommitment,
+
+    /// The Pedersen commitment to the delta amount.
+    pub delta_commitment: PodPedersenCommitment,
+
+    /// The Pedersen commitment to the claimed amount.
+    pub claimed_commitment: PodPedersenCommitment,
+
+    /// The maximum cap bound.
+    pub max_value: PodU64,
+
+    /// The Pedersen commitments to the fee shares, padded with zeroed commitments.
+    pub share_commitments: [PodPedersenCommitment; MAX_FEE_SHARES],
+}
+
+impl ZkProofData<PercentageWithCapSplitProofContext> for PercentageWithCapSplitProofData {
+    const PROOF_TYPE: ProofType = ProofType::PercentageWithCapSplit;
+
+    fn context_data(&self) -> &PercentageWithCapSplitProofContext {
+        &self.context
+    }
+}
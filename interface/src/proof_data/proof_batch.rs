@@ -0,0 +1,29 @@
+//! The proof batch instruction.
+//!
+//! A proof batch carries several proofs of the same type, each with its own context, in a single
+//! `ProofInstruction::VerifyProofBatch` instruction. The verification equations of all proofs in
+//! the batch are checked with a single multiscalar multiplication, which amortizes the
+//! per-instruction overhead of verifying many proofs of the same type.
+//!
+//! The instruction data consists of a `ProofBatchHeader` followed by `num_proofs` proof data of
+//! the type specified in the header.
+
+use {
+    crate::proof_data::PodProofType,
+    bytemuck_derive::{Pod, Zeroable},
+};
+
+/// The maximum number of proofs that can be verified in a single proof batch.
+pub const MAX_PROOF_BATCH_SIZE: usize = 16;
+
+/// The header of the instruction data that is needed for the
+/// `ProofInstruction::VerifyProofBatch` instruction.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct ProofBatchHeader {
+    /// The proof type of all proofs in the batch
+    pub proof_type: PodProofType, // 1 byte
+
+    /// The number of proofs in the batch
+    pub num_proofs: u8, // 1 byte
+}
@@ -0,0 +1,59 @@
+//! The lo/hi split consistency proof instruction.
+//!
+//! A split consistency proof is defined with respect to two twisted ElGamal ciphertexts that
+//! encrypt the low 48 bits and the high 16 bits of an amount and a twisted ElGamal ciphertext of
+//! the full amount, all under the same public key. The proof certifies that the ciphertext of the
+//! full amount is consistent with the split, i.e. that `amount = amount_lo + 2^48 * amount_hi`. To
+//! generate the proof, a prover must provide the Pedersen openings of the three ciphertexts.
+//!
+//! The proof does not certify the bit lengths of the two halves. It must be accompanied by a range
+//! proof that the low bits fit in 48 bits and that the high bits fit in 16 bits.
+
+use {
+    crate::proof_data::{ProofType, ZkProofData},
+    bytemuck_derive::{Pod, Zeroable},
+    solana_zk_sdk_pod::{
+        encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+        sigma_proofs::PodSplitConsistencyProof,
+    },
+};
+
+/// The instruction data that is needed for the `ProofInstruction::VerifySplitConsistency`
+/// instruction.
+///
+/// It includes the cryptographic proof as well as the context data information needed to verify
+/// the proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct SplitConsistencyProofData {
+    /// The context data for the split consistency proof
+    pub context: SplitConsistencyProofContext, // 224 bytes
+
+    /// Proof that the ciphertext of the full amount is consistent with the split
+    pub proof: PodSplitConsistencyProof, // 96 bytes
+}
+
+/// The context data needed to verify a split consistency proof.
+#[derive(Clone, Copy, Pod, Zeroable, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct SplitConsistencyProofContext {
+    /// The ElGamal pubkey under which the three ciphertexts are encrypted
+    pub pubkey: PodElGamalPubkey, // 32 bytes
+
+    /// The ElGamal ciphertext of the low bits of the amount
+    pub ciphertext_lo: PodElGamalCiphertext, // 64 bytes
+
+    /// The ElGamal ciphertext of the high bits of the amount
+    pub ciphertext_hi: PodElGamalCiphertext, // 64 bytes
+
+    /// The ElGamal ciphertext of the full amount
+    pub ciphertext: PodElGamalCiphertext, // 64 bytes
+}
+
+impl ZkProofData<SplitConsistencyProofContext> for SplitConsistencyProofData {
+    const PROOF_TYPE: ProofType = ProofType::SplitConsistency;
+
+    fn context_data(&self) -> &SplitConsistencyProofContext {
+        &self.context
+    }
+}
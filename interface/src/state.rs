@@ -1,3 +1,5 @@
+#[cfg(feature = "hashed-context-state")]
+use sha2::{Digest, Sha256};
 use {
     crate::proof_data::{PodProofType, ProofType},
     alloc::vec::Vec,
@@ -6,6 +8,7 @@ use {
     num_traits::ToPrimitive,
     solana_address::Address,
     solana_instruction::error::{InstructionError, InstructionError::InvalidAccountData},
+    solana_zk_sdk_pod::primitive_types::PodU64,
 };
 
 /// The on-chain state for a verified zero-knowledge proof statement.
@@ -61,10 +64,186 @@ impl<T: Pod> ProofContextState<T> {
     /// in `ProofContextState` without a generic parameter, use
     /// `ProofContextStateMeta::try_from_bytes` instead.
     pub fn try_from_bytes(input: &[u8]) -> Result<&Self, InstructionError> {
-        bytemuck::try_from_bytes(input).map_err(|_| InvalidAccountData)
+        let state: &Self = bytemuck::try_from_bytes(input).map_err(|_| InvalidAccountData)?;
+        if state.proof_type.0 & HASHED_PROOF_CONTEXT_STATE_TAG != 0 {
+            return Err(InvalidAccountData);
+        }
+        Ok(state)
+    }
+
+    /// Encode a `ProofContextState` followed by an optional `ProofContextStateExpiry`.
+    pub fn encode_with_expiry(
+        context_state_authority: &Address,
+        proof_type: ProofType,
+        proof_context: &T,
+        expiry_slot: Option<u64>,
+    ) -> Vec<u8> {
+        let mut buf = Self::encode(context_state_authority, proof_type, proof_context);
+        if let Some(expiry_slot) = expiry_slot {
+            buf.extend_from_slice(bytes_of(&ProofContextStateExpiry::new(expiry_slot)));
+        }
+        buf
+    }
+
+    /// Interpret a slice as a `ProofContextState` that is optionally followed by a
+    /// `ProofContextStateExpiry`.
+    pub fn try_from_bytes_with_expiry(
+        input: &[u8],
+    ) -> Result<(&Self, Option<&ProofContextStateExpiry>), InstructionError> {
+        let (state, expiry) = input
+            .split_at_checked(size_of::<Self>())
+            .ok_or(InvalidAccountData)?;
+        let expiry = if expiry.is_empty() {
+            None
+        } else {
+            Some(bytemuck::try_from_bytes(expiry).map_err(|_| InvalidAccountData)?)
+        };
+        Ok((Self::try_from_bytes(state)?, expiry))
+    }
+
+    /// Interpret a slice as a `ProofContextState` that can be used at `current_slot`.
+    ///
+    /// A context state without an expiry never becomes stale. A context state with an expiry is
+    /// rejected with `InvalidAccountData` after its expiry slot.
+    pub fn try_from_bytes_unexpired(
+        input: &[u8],
+        current_slot: u64,
+    ) -> Result<&Self, InstructionError> {
+        let (state, expiry) = Self::try_from_bytes_with_expiry(input)?;
+        match expiry {
+            Some(expiry) if expiry.is_expired(current_slot) => Err(InvalidAccountData),
+            _ => Ok(state),
+        }
     }
 }
 
+/// The optional expiry of a proof context state.
+///
+/// A context state account that expires holds this struct immediately after its
+/// `ProofContextState`, so the layout of the `ProofContextState` itself is the same for accounts
+/// with and without an expiry. A verified statement in an account that never expires can be
+/// referenced for as long as the account exists; an expiry bounds the window in which the
+/// statement can be replayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
+#[repr(C)]
+pub struct ProofContextStateExpiry {
+    /// The last slot at which the context state can be used
+    pub expiry_slot: PodU64,
+}
+
+impl ProofContextStateExpiry {
+    pub fn new(expiry_slot: u64) -> Self {
+        Self {
+            expiry_slot: expiry_slot.into(),
+        }
+    }
+
+    /// Returns `true` if the context state can no longer be used at `current_slot`.
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        current_slot > u64::from(self.expiry_slot)
+    }
+}
+
+/// The byte length of the hash of a proof context.
+pub const PROOF_CONTEXT_HASH_LEN: usize = 32;
+
+/// The bit that is set in the proof type of a `HashedProofContextState`.
+///
+/// No `ProofType` has this bit set, so the proof type of an account tells a
+/// `HashedProofContextState` apart from a `ProofContextState` regardless of the size of the
+/// account.
+pub const HASHED_PROOF_CONTEXT_STATE_TAG: u8 = 0x80;
+
+/// The compact on-chain state for a verified zero-knowledge proof statement.
+///
+/// A `HashedProofContextState` stores a SHA-256 hash of the proof context in place of the proof
+/// context itself. Consumers of the account supply the full proof context again and check it
+/// against the stored hash with `verify_proof_context`. This shrinks the account, and therefore
+/// its rent-exempt balance, for proof types with large contexts.
+///
+/// A proof verification instruction writes a `HashedProofContextState` if it is encoded with
+/// `ProofInstruction::encode_verify_proof_hashed` and the processor decodes it with
+/// `ProofInstruction::proof_data_with_hashed_context`; the on-chain proof program does not support
+/// hashed context states yet. Hashing a proof context requires the `hashed-context-state`
+/// feature, which keeps SHA-256 out of the dependencies of programs that only read the
+/// authority and proof type of an account. The authority and proof type are laid out the
+/// same way in both states, so `ProofContextStateMeta::try_from_bytes` decodes either. The proof
+/// type of a `HashedProofContextState` has `HASHED_PROOF_CONTEXT_STATE_TAG` set, and
+/// `ProofContextStateMeta::is_hashed` tells the two states apart.
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
+#[repr(C)]
+pub struct HashedProofContextState {
+    /// The proof context authority that can close the account
+    pub context_state_authority: Address,
+    /// The proof type for the context data with `HASHED_PROOF_CONTEXT_STATE_TAG` set
+    pub proof_type: PodProofType,
+    /// The hash of the proof context data
+    pub proof_context_hash: [u8; PROOF_CONTEXT_HASH_LEN],
+}
+
+impl HashedProofContextState {
+    #[cfg(feature = "hashed-context-state")]
+    pub fn encode<T: Pod>(
+        context_state_authority: &Address,
+        proof_type: ProofType,
+        proof_context: &T,
+    ) -> Vec<u8> {
+        let PodProofType(proof_type_tag) = proof_type.into();
+        let state = Self {
+            context_state_authority: *context_state_authority,
+            proof_type: PodProofType(proof_type_tag | HASHED_PROOF_CONTEXT_STATE_TAG),
+            proof_context_hash: hash_proof_context(proof_type, proof_context),
+        };
+        bytes_of(&state).to_vec()
+    }
+
+    /// Interpret a slice as a `HashedProofContextState`.
+    pub fn try_from_bytes(input: &[u8]) -> Result<&Self, InstructionError> {
+        let state: &Self = bytemuck::try_from_bytes(input).map_err(|_| InvalidAccountData)?;
+        state.proof_type()?;
+        Ok(state)
+    }
+
+    /// The proof type of the proof context that was verified into this state.
+    pub fn proof_type(&self) -> Result<ProofType, InstructionError> {
+        let PodProofType(proof_type_tag) = self.proof_type;
+        if proof_type_tag & HASHED_PROOF_CONTEXT_STATE_TAG == 0 {
+            return Err(InvalidAccountData);
+        }
+        ProofType::try_from(PodProofType(
+            proof_type_tag & !HASHED_PROOF_CONTEXT_STATE_TAG,
+        ))
+        .map_err(|_| InvalidAccountData)
+    }
+
+    /// Check that `proof_context` is the proof context that was verified into this state.
+    #[cfg(feature = "hashed-context-state")]
+    pub fn verify_proof_context<T: Pod>(&self, proof_context: &T) -> Result<(), InstructionError> {
+        let proof_type = self.proof_type()?;
+        if hash_proof_context(proof_type, proof_context) == self.proof_context_hash {
+            Ok(())
+        } else {
+            Err(InvalidAccountData)
+        }
+    }
+}
+
+/// Hash a proof context for a `HashedProofContextState`.
+///
+/// The proof type is hashed along with the context, so contexts of different proof types with
+/// the same bytes hash to different values.
+#[cfg(feature = "hashed-context-state")]
+pub fn hash_proof_context<T: Pod>(
+    proof_type: ProofType,
+    proof_context: &T,
+) -> [u8; PROOF_CONTEXT_HASH_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"zk-elgamal-proof-context");
+    hasher.update([ToPrimitive::to_u8(&proof_type).unwrap()]);
+    hasher.update(bytes_of(proof_context));
+    hasher.finalize().into()
+}
+
 /// The `ProofContextState` without the proof context itself. This struct exists to facilitate the
 /// decoding of generic-independent fields in `ProofContextState`.
 #[derive(Clone, Copy, Debug, PartialEq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
@@ -83,4 +262,9 @@ impl ProofContextStateMeta {
             .and_then(|data| bytemuck::try_from_bytes(data).ok())
             .ok_or(InvalidAccountData)
     }
+
+    /// Returns `true` if the account holds a `HashedProofContextState`.
+    pub fn is_hashed(&self) -> bool {
+        self.proof_type.0 & HASHED_PROOF_CONTEXT_STATE_TAG != 0
+    }
 }
@@ -5,7 +5,7 @@ use crate::macros::impl_serde_base64;
 use {
     crate::{
         encryption::AE_CIPHERTEXT_LEN,
-        macros::{impl_from_bytes, impl_from_str, impl_nullable},
+        macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bytemuck::{Pod, Zeroable},
@@ -44,6 +44,8 @@ impl_from_str!(
     BASE64_LEN = AE_CIPHERTEXT_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodAeCiphertext, BYTES_LEN = AE_CIPHERTEXT_LEN);
+
 impl_from_bytes!(TYPE = PodAeCiphertext, BYTES_LEN = AE_CIPHERTEXT_LEN);
 
 impl_nullable!(TYPE = PodAeCiphertext, BYTES_LEN = AE_CIPHERTEXT_LEN);
@@ -4,8 +4,11 @@
 use crate::macros::impl_serde_base64;
 use {
     crate::{
-        encryption::{DECRYPT_HANDLE_LEN, ELGAMAL_CIPHERTEXT_LEN, ELGAMAL_PUBKEY_LEN},
-        macros::{impl_from_bytes, impl_from_str, impl_nullable},
+        encryption::{
+            pedersen::PodPedersenCommitment, DECRYPT_HANDLE_LEN, ELGAMAL_CIPHERTEXT_LEN,
+            ELGAMAL_PUBKEY_LEN, PEDERSEN_COMMITMENT_LEN,
+        },
+        macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bytemuck::Zeroable,
@@ -50,6 +53,11 @@ impl_from_str!(
     BASE64_LEN = ELGAMAL_CIPHERTEXT_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodElGamalCiphertext,
+    BYTES_LEN = ELGAMAL_CIPHERTEXT_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodElGamalCiphertext,
     BYTES_LEN = ELGAMAL_CIPHERTEXT_LEN
@@ -63,6 +71,48 @@ impl_nullable!(
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodElGamalCiphertext);
 
+// A ciphertext is the concatenation of a commitment and a decrypt handle
+const _: () = assert!(ELGAMAL_CIPHERTEXT_LEN == PEDERSEN_COMMITMENT_LEN + DECRYPT_HANDLE_LEN);
+
+impl PodElGamalCiphertext {
+    /// Join a commitment and a decrypt handle into a ciphertext.
+    pub fn from_parts(commitment: PodPedersenCommitment, handle: PodDecryptHandle) -> Self {
+        let mut ciphertext = Self::zeroed();
+        ciphertext.0[..PEDERSEN_COMMITMENT_LEN].copy_from_slice(&commitment.0);
+        ciphertext.0[PEDERSEN_COMMITMENT_LEN..].copy_from_slice(&handle.0);
+        ciphertext
+    }
+
+    /// Split the ciphertext into its commitment and decrypt handle components.
+    pub fn to_parts(&self) -> (PodPedersenCommitment, PodDecryptHandle) {
+        (self.commitment(), self.handle())
+    }
+
+    /// The commitment component of the ciphertext.
+    pub fn commitment(&self) -> PodPedersenCommitment {
+        // the length of the ciphertext is checked at compile time
+        PodPedersenCommitment(self.0[..PEDERSEN_COMMITMENT_LEN].try_into().unwrap())
+    }
+
+    /// The decrypt handle component of the ciphertext.
+    pub fn handle(&self) -> PodDecryptHandle {
+        // the length of the ciphertext is checked at compile time
+        PodDecryptHandle(self.0[PEDERSEN_COMMITMENT_LEN..].try_into().unwrap())
+    }
+}
+
+impl From<(PodPedersenCommitment, PodDecryptHandle)> for PodElGamalCiphertext {
+    fn from((commitment, handle): (PodPedersenCommitment, PodDecryptHandle)) -> Self {
+        Self::from_parts(commitment, handle)
+    }
+}
+
+impl From<PodElGamalCiphertext> for (PodPedersenCommitment, PodDecryptHandle) {
+    fn from(ciphertext: PodElGamalCiphertext) -> Self {
+        ciphertext.to_parts()
+    }
+}
+
 /// The `ElGamalPubkey` type as a `Pod`.
 #[derive(Clone, Copy, Default, bytemuck_derive::Pod, bytemuck_derive::Zeroable, PartialEq, Eq)]
 #[repr(transparent)]
@@ -86,6 +136,8 @@ impl_from_str!(
     BASE64_LEN = ELGAMAL_PUBKEY_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodElGamalPubkey, BYTES_LEN = ELGAMAL_PUBKEY_LEN);
+
 impl_from_bytes!(TYPE = PodElGamalPubkey, BYTES_LEN = ELGAMAL_PUBKEY_LEN);
 
 impl_nullable!(TYPE = PodElGamalPubkey, BYTES_LEN = ELGAMAL_PUBKEY_LEN);
@@ -116,6 +168,8 @@ impl_from_str!(
     BASE64_LEN = DECRYPT_HANDLE_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodDecryptHandle, BYTES_LEN = DECRYPT_HANDLE_LEN);
+
 impl_from_bytes!(TYPE = PodDecryptHandle, BYTES_LEN = DECRYPT_HANDLE_LEN);
 
 impl_nullable!(TYPE = PodDecryptHandle, BYTES_LEN = DECRYPT_HANDLE_LEN);
@@ -152,6 +206,53 @@ mod tests {
         assert_eq!(expected_elgamal_ciphertext, computed_elgamal_ciphertext);
     }
 
+    #[test]
+    fn elgamal_ciphertext_parts() {
+        let elgamal_keypair = ElGamalKeypair::new_rand();
+        let ciphertext = elgamal_keypair.pubkey().encrypt(77_u64);
+        let pod_ciphertext = PodElGamalCiphertext(ciphertext.to_bytes());
+
+        let (commitment, handle) = pod_ciphertext.to_parts();
+        assert_eq!(
+            commitment,
+            PodPedersenCommitment(ciphertext.commitment.to_bytes())
+        );
+        assert_eq!(handle, PodDecryptHandle(ciphertext.handle.to_bytes()));
+
+        assert_eq!(
+            PodElGamalCiphertext::from_parts(commitment, handle),
+            pod_ciphertext
+        );
+        assert_eq!(
+            PodElGamalCiphertext::from((commitment, handle)),
+            pod_ciphertext
+        );
+
+        let parts: (PodPedersenCommitment, PodDecryptHandle) = pod_ciphertext.into();
+        assert_eq!(parts, (commitment, handle));
+    }
+
+    #[test]
+    fn elgamal_ciphertext_byte_conversions() {
+        let elgamal_keypair = ElGamalKeypair::new_rand();
+        let ciphertext = elgamal_keypair.pubkey().encrypt(77_u64);
+        let pod_ciphertext = PodElGamalCiphertext(ciphertext.to_bytes());
+
+        assert_eq!(pod_ciphertext.as_ref(), &ciphertext.to_bytes());
+
+        let bytes: Vec<u8> = pod_ciphertext.into();
+        assert_eq!(bytes, ciphertext.to_bytes());
+        assert_eq!(
+            PodElGamalCiphertext::try_from(bytes).unwrap(),
+            pod_ciphertext
+        );
+
+        assert_eq!(
+            PodElGamalCiphertext::try_from(vec![0_u8; ELGAMAL_CIPHERTEXT_LEN - 1]).unwrap_err(),
+            crate::errors::ParseError::WrongSize
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_elgamal_pubkey_serde() {
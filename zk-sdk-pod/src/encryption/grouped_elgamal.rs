@@ -5,11 +5,13 @@ use crate::macros::impl_serde_base64;
 use {
     crate::{
         encryption::{
-            elgamal::PodElGamalCiphertext, pedersen::PodPedersenCommitment, DECRYPT_HANDLE_LEN,
-            ELGAMAL_CIPHERTEXT_LEN, PEDERSEN_COMMITMENT_LEN,
+            elgamal::{PodDecryptHandle, PodElGamalCiphertext},
+            pedersen::PodPedersenCommitment,
+            DECRYPT_HANDLE_LEN, ELGAMAL_CIPHERTEXT_LEN, GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN,
+            GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN, PEDERSEN_COMMITMENT_LEN,
         },
         errors::ParseError,
-        macros::{impl_from_bytes, impl_from_str, impl_nullable},
+        macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bytemuck::Zeroable,
@@ -23,55 +25,132 @@ const GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_MAX_BASE64_LEN: usize = 132;
 const GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_MAX_BASE64_LEN: usize = 176;
 
 macro_rules! impl_extract {
-    (TYPE = $type:ident) => {
+    (TYPE = $type:ident, HANDLES = $handles:expr) => {
+        // A grouped ciphertext is the concatenation of a commitment and the decrypt handles
+        const _: () = assert!(
+            core::mem::size_of::<$type>()
+                == PEDERSEN_COMMITMENT_LEN + $handles * DECRYPT_HANDLE_LEN
+        );
+
         impl $type {
+            /// Join a commitment and decrypt handles into a grouped ciphertext.
+            pub fn from_parts(
+                commitment: PodPedersenCommitment,
+                handles: [PodDecryptHandle; $handles],
+            ) -> Self {
+                let mut grouped_ciphertext = Self::zeroed();
+                grouped_ciphertext.0[..PEDERSEN_COMMITMENT_LEN].copy_from_slice(&commitment.0);
+
+                let handle_chunks = grouped_ciphertext.0[PEDERSEN_COMMITMENT_LEN..]
+                    .chunks_exact_mut(DECRYPT_HANDLE_LEN);
+                for (handle, chunk) in handles.iter().zip(handle_chunks) {
+                    chunk.copy_from_slice(&handle.0);
+                }
+                grouped_ciphertext
+            }
+
+            /// Split the grouped ciphertext into its commitment and decrypt handle components.
+            pub fn to_parts(&self) -> (PodPedersenCommitment, [PodDecryptHandle; $handles]) {
+                let mut handles = [PodDecryptHandle::zeroed(); $handles];
+                let handle_chunks =
+                    self.0[PEDERSEN_COMMITMENT_LEN..].chunks_exact(DECRYPT_HANDLE_LEN);
+                for (handle, chunk) in handles.iter_mut().zip(handle_chunks) {
+                    handle.0.copy_from_slice(chunk);
+                }
+                (self.extract_commitment(), handles)
+            }
+
             /// Extract the commitment component from a grouped ciphertext
             pub fn extract_commitment(&self) -> PodPedersenCommitment {
-                // `GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES` guaranteed to be at least `PEDERSEN_COMMITMENT_LEN`
+                // `GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN` guaranteed to be at least `PEDERSEN_COMMITMENT_LEN`
                 let commitment = self.0[..PEDERSEN_COMMITMENT_LEN].try_into().unwrap();
                 PodPedersenCommitment(commitment)
             }
 
+            /// Extract the decrypt handle at a specified index.
+            pub fn try_extract_handle(&self, index: usize) -> Result<PodDecryptHandle, ParseError> {
+                let handle_start = DECRYPT_HANDLE_LEN
+                    .checked_mul(index)
+                    .and_then(|n| n.checked_add(PEDERSEN_COMMITMENT_LEN))
+                    .ok_or(ParseError::WrongSize)?;
+                let handle_end = handle_start
+                    .checked_add(DECRYPT_HANDLE_LEN)
+                    .ok_or(ParseError::WrongSize)?;
+                let handle = self
+                    .0
+                    .get(handle_start..handle_end)
+                    .ok_or(ParseError::WrongSize)?
+                    .try_into()
+                    .unwrap();
+
+                Ok(PodDecryptHandle(handle))
+            }
+
             /// Extract a regular ElGamal ciphertext using the decrypt handle at a specified index.
             pub fn try_extract_ciphertext(
                 &self,
                 index: usize,
             ) -> Result<PodElGamalCiphertext, ParseError> {
+                let handle = self.try_extract_handle(index)?;
+
                 let mut ciphertext_bytes = [0u8; ELGAMAL_CIPHERTEXT_LEN];
                 ciphertext_bytes[..PEDERSEN_COMMITMENT_LEN]
                     .copy_from_slice(&self.0[..PEDERSEN_COMMITMENT_LEN]);
-
-                let handle_start = DECRYPT_HANDLE_LEN
-                    .checked_mul(index)
-                    .and_then(|n| n.checked_add(PEDERSEN_COMMITMENT_LEN))
-                    .ok_or(ParseError::WrongSize)?;
-                let handle_end = handle_start
-                    .checked_add(DECRYPT_HANDLE_LEN)
-                    .ok_or(ParseError::WrongSize)?;
-                ciphertext_bytes[PEDERSEN_COMMITMENT_LEN..].copy_from_slice(
-                    self.0
-                        .get(handle_start..handle_end)
-                        .ok_or(ParseError::WrongSize)?,
-                );
+                ciphertext_bytes[PEDERSEN_COMMITMENT_LEN..].copy_from_slice(&handle.0);
 
                 Ok(PodElGamalCiphertext(ciphertext_bytes))
             }
+
+            /// Combine regular ElGamal ciphertexts that share the same commitment into a grouped
+            /// ciphertext.
+            ///
+            /// The decrypt handle of the ciphertext at position `i` becomes the handle at index
+            /// `i` of the grouped ciphertext.
+            pub fn try_from_ciphertexts(
+                ciphertexts: &[PodElGamalCiphertext],
+            ) -> Result<Self, ParseError> {
+                if ciphertexts.len() != $handles {
+                    return Err(ParseError::WrongSize);
+                }
+
+                let mut grouped_ciphertext = Self::zeroed();
+
+                let commitment = &ciphertexts[0].0[..PEDERSEN_COMMITMENT_LEN];
+                grouped_ciphertext.0[..PEDERSEN_COMMITMENT_LEN].copy_from_slice(commitment);
+
+                let handle_chunks = grouped_ciphertext.0[PEDERSEN_COMMITMENT_LEN..]
+                    .chunks_exact_mut(DECRYPT_HANDLE_LEN);
+                for (ciphertext, handle) in ciphertexts.iter().zip(handle_chunks) {
+                    if ciphertext.0[..PEDERSEN_COMMITMENT_LEN] != *commitment {
+                        return Err(ParseError::InvalidArgument);
+                    }
+                    handle.copy_from_slice(&ciphertext.0[PEDERSEN_COMMITMENT_LEN..]);
+                }
+
+                Ok(grouped_ciphertext)
+            }
         }
-    };
-}
 
-/// Byte length of a grouped ElGamal ciphertext with 2 handles
-const GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES: usize =
-    PEDERSEN_COMMITMENT_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN;
+        impl From<(PodPedersenCommitment, [PodDecryptHandle; $handles])> for $type {
+            fn from(
+                (commitment, handles): (PodPedersenCommitment, [PodDecryptHandle; $handles]),
+            ) -> Self {
+                Self::from_parts(commitment, handles)
+            }
+        }
 
-/// Byte length of a grouped ElGamal ciphertext with 3 handles
-const GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES: usize =
-    PEDERSEN_COMMITMENT_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN;
+        impl From<$type> for (PodPedersenCommitment, [PodDecryptHandle; $handles]) {
+            fn from(grouped_ciphertext: $type) -> Self {
+                grouped_ciphertext.to_parts()
+            }
+        }
+    };
+}
 
 /// The `GroupedElGamalCiphertext` type with two decryption handles as a `Pod`
 #[derive(Clone, Copy, bytemuck_derive::Pod, bytemuck_derive::Zeroable, PartialEq, Eq)]
 #[repr(transparent)]
-pub struct PodGroupedElGamalCiphertext2Handles(pub [u8; GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES]);
+pub struct PodGroupedElGamalCiphertext2Handles(pub [u8; GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN]);
 
 impl fmt::Debug for PodGroupedElGamalCiphertext2Handles {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -93,21 +172,26 @@ impl fmt::Display for PodGroupedElGamalCiphertext2Handles {
 
 impl_from_str!(
     TYPE = PodGroupedElGamalCiphertext2Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES,
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN,
     BASE64_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodGroupedElGamalCiphertext2Handles,
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodGroupedElGamalCiphertext2Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN
 );
 
 impl_nullable!(
     TYPE = PodGroupedElGamalCiphertext2Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN
 );
 
-impl_extract!(TYPE = PodGroupedElGamalCiphertext2Handles);
+impl_extract!(TYPE = PodGroupedElGamalCiphertext2Handles, HANDLES = 2);
 
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodGroupedElGamalCiphertext2Handles);
@@ -115,7 +199,7 @@ impl_serde_base64!(TYPE = PodGroupedElGamalCiphertext2Handles);
 /// The `GroupedElGamalCiphertext` type with three decryption handles as a `Pod`
 #[derive(Clone, Copy, bytemuck_derive::Pod, bytemuck_derive::Zeroable, PartialEq, Eq)]
 #[repr(transparent)]
-pub struct PodGroupedElGamalCiphertext3Handles(pub [u8; GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES]);
+pub struct PodGroupedElGamalCiphertext3Handles(pub [u8; GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN]);
 
 impl fmt::Debug for PodGroupedElGamalCiphertext3Handles {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -137,24 +221,29 @@ impl fmt::Display for PodGroupedElGamalCiphertext3Handles {
 
 impl_from_str!(
     TYPE = PodGroupedElGamalCiphertext3Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES,
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN,
     BASE64_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodGroupedElGamalCiphertext3Handles,
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodGroupedElGamalCiphertext3Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN
 );
 
 impl_nullable!(
     TYPE = PodGroupedElGamalCiphertext3Handles,
-    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES
+    BYTES_LEN = GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN
 );
 
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodGroupedElGamalCiphertext3Handles);
 
-impl_extract!(TYPE = PodGroupedElGamalCiphertext3Handles);
+impl_extract!(TYPE = PodGroupedElGamalCiphertext3Handles, HANDLES = 3);
 
 #[cfg(test)]
 mod tests {
@@ -200,6 +289,59 @@ mod tests {
             .try_extract_ciphertext(2)
             .unwrap_err();
         assert_eq!(err, ParseError::WrongSize);
+
+        let expected_pod_handle_1 = PodDecryptHandle(grouped_ciphertext.handles[1].to_bytes());
+        let actual_pod_handle_1 = pod_grouped_ciphertext.try_extract_handle(1).unwrap();
+        assert_eq!(expected_pod_handle_1, actual_pod_handle_1);
+
+        let err = pod_grouped_ciphertext.try_extract_handle(2).unwrap_err();
+        assert_eq!(err, ParseError::WrongSize);
+
+        let err = pod_grouped_ciphertext
+            .try_extract_handle(usize::MAX)
+            .unwrap_err();
+        assert_eq!(err, ParseError::WrongSize);
+    }
+
+    #[test]
+    fn test_2_handles_ciphertext_from_ciphertexts() {
+        let elgamal_keypair_0 = ElGamalKeypair::new_rand();
+        let elgamal_keypair_1 = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 10;
+        let (_commitment, opening) = Pedersen::new(amount);
+
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(
+            [elgamal_keypair_0.pubkey(), elgamal_keypair_1.pubkey()],
+            amount,
+            &opening,
+        );
+        let pod_grouped_ciphertext =
+            PodGroupedElGamalCiphertext2Handles(grouped_ciphertext.to_bytes().try_into().unwrap());
+
+        let pod_ciphertext_0 = pod_grouped_ciphertext.try_extract_ciphertext(0).unwrap();
+        let pod_ciphertext_1 = pod_grouped_ciphertext.try_extract_ciphertext(1).unwrap();
+
+        let combined = PodGroupedElGamalCiphertext2Handles::try_from_ciphertexts(&[
+            pod_ciphertext_0,
+            pod_ciphertext_1,
+        ])
+        .unwrap();
+        assert_eq!(combined, pod_grouped_ciphertext);
+
+        // wrong number of ciphertexts
+        let err = PodGroupedElGamalCiphertext2Handles::try_from_ciphertexts(&[pod_ciphertext_0])
+            .unwrap_err();
+        assert_eq!(err, ParseError::WrongSize);
+
+        // mismatched commitments
+        let other_ciphertext = elgamal_keypair_1.pubkey().encrypt(amount);
+        let err = PodGroupedElGamalCiphertext2Handles::try_from_ciphertexts(&[
+            pod_ciphertext_0,
+            PodElGamalCiphertext(other_ciphertext.to_bytes()),
+        ])
+        .unwrap_err();
+        assert_eq!(err, ParseError::InvalidArgument);
     }
 
     #[test]
@@ -248,6 +390,45 @@ mod tests {
         assert_eq!(err, ParseError::WrongSize);
     }
 
+    #[test]
+    fn test_3_handles_ciphertext_parts() {
+        let elgamal_keypair_0 = ElGamalKeypair::new_rand();
+        let elgamal_keypair_1 = ElGamalKeypair::new_rand();
+        let elgamal_keypair_2 = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 10;
+        let (commitment, opening) = Pedersen::new(amount);
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(
+            [
+                elgamal_keypair_0.pubkey(),
+                elgamal_keypair_1.pubkey(),
+                elgamal_keypair_2.pubkey(),
+            ],
+            amount,
+            &opening,
+        );
+        let pod_grouped_ciphertext =
+            PodGroupedElGamalCiphertext3Handles(grouped_ciphertext.to_bytes().try_into().unwrap());
+
+        let (pod_commitment, pod_handles) = pod_grouped_ciphertext.to_parts();
+        assert_eq!(pod_commitment, PodPedersenCommitment(commitment.to_bytes()));
+        for (pod_handle, handle) in pod_handles.iter().zip(grouped_ciphertext.handles.iter()) {
+            assert_eq!(*pod_handle, PodDecryptHandle(handle.to_bytes()));
+        }
+
+        assert_eq!(
+            PodGroupedElGamalCiphertext3Handles::from_parts(pod_commitment, pod_handles),
+            pod_grouped_ciphertext
+        );
+        assert_eq!(
+            PodGroupedElGamalCiphertext3Handles::from((pod_commitment, pod_handles)),
+            pod_grouped_ciphertext
+        );
+
+        let parts: (PodPedersenCommitment, [PodDecryptHandle; 3]) = pod_grouped_ciphertext.into();
+        assert_eq!(parts, (pod_commitment, pod_handles));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_grouped_ciphertext_2_handles_serde() {
@@ -32,3 +32,11 @@ pub const PEDERSEN_OPENING_LEN: usize = SCALAR_LEN;
 
 /// Byte length of a Pedersen commitment.
 pub const PEDERSEN_COMMITMENT_LEN: usize = RISTRETTO_POINT_LEN;
+
+/// Byte length of a grouped ElGamal ciphertext with 2 handles
+pub const GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN: usize =
+    PEDERSEN_COMMITMENT_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN;
+
+/// Byte length of a grouped ElGamal ciphertext with 3 handles
+pub const GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN: usize =
+    PEDERSEN_COMMITMENT_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN + DECRYPT_HANDLE_LEN;
@@ -4,8 +4,8 @@
 use crate::macros::impl_serde_base64;
 use {
     crate::{
-        encryption::PEDERSEN_COMMITMENT_LEN,
-        macros::{impl_from_bytes, impl_from_str, impl_nullable},
+        encryption::{PEDERSEN_COMMITMENT_LEN, PEDERSEN_OPENING_LEN},
+        macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bytemuck_derive::{Pod, Zeroable},
@@ -15,6 +15,9 @@ use {
 /// Maximum length of a base-64 encoded ElGamal public key
 const PEDERSEN_COMMITMENT_MAX_BASE64_LEN: usize = 44;
 
+/// Maximum length of a base-64 encoded Pedersen opening
+const PEDERSEN_OPENING_MAX_BASE64_LEN: usize = 44;
+
 /// The `PedersenCommitment` type as a `Pod`.
 #[derive(Clone, Copy, Default, Pod, Zeroable, PartialEq, Eq)]
 #[repr(transparent)]
@@ -38,6 +41,11 @@ impl_from_str!(
     BASE64_LEN = PEDERSEN_COMMITMENT_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodPedersenCommitment,
+    BYTES_LEN = PEDERSEN_COMMITMENT_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodPedersenCommitment,
     BYTES_LEN = PEDERSEN_COMMITMENT_LEN
@@ -51,6 +59,41 @@ impl_nullable!(
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodPedersenCommitment);
 
+/// The `PedersenOpening` type as a `Pod`.
+///
+/// A Pedersen opening is secret data. Its `Debug` output is redacted, but its `Display` output is
+/// the base64 encoding of the opening so that it can be serialized.
+#[derive(Clone, Copy, Default, Pod, Zeroable, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PodPedersenOpening(pub [u8; PEDERSEN_OPENING_LEN]);
+
+impl fmt::Debug for PodPedersenOpening {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PodPedersenOpening")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+impl fmt::Display for PodPedersenOpening {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodPedersenOpening,
+    BYTES_LEN = PEDERSEN_OPENING_LEN,
+    BASE64_LEN = PEDERSEN_OPENING_MAX_BASE64_LEN
+);
+
+impl_len!(TYPE = PodPedersenOpening, BYTES_LEN = PEDERSEN_OPENING_LEN);
+
+impl_from_bytes!(TYPE = PodPedersenOpening, BYTES_LEN = PEDERSEN_OPENING_LEN);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodPedersenOpening);
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "serde")]
@@ -69,4 +112,21 @@ mod tests {
         let deserialized: PodPedersenCommitment = serde_json::from_str(&serialized).unwrap();
         assert_eq!(expected_commitment, deserialized);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pedersen_opening_serde() {
+        let (_commitment, opening) = Pedersen::new(10_u64);
+        let expected_opening = PodPedersenOpening(opening.to_bytes());
+
+        let serialized = serde_json::to_string(&expected_opening).unwrap();
+        assert_eq!(serialized, format!("\"{}\"", expected_opening));
+
+        let deserialized: PodPedersenOpening = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(expected_opening, deserialized);
+        assert_eq!(
+            format!("{:?}", deserialized),
+            "PodPedersenOpening(\"[REDACTED]\")"
+        );
+    }
 }
@@ -1,5 +1,7 @@
 #![cfg_attr(not(test), no_std)]
 
+extern crate alloc;
+
 pub mod encryption;
 pub mod errors;
 mod macros;
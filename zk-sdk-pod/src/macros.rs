@@ -29,10 +29,42 @@ macro_rules! impl_from_bytes {
                 Self(bytes)
             }
         }
+
+        impl core::convert::AsRef<[u8]> for $type {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl core::convert::From<$type> for alloc::vec::Vec<u8> {
+            fn from(pod: $type) -> Self {
+                pod.0.to_vec()
+            }
+        }
+
+        impl core::convert::TryFrom<alloc::vec::Vec<u8>> for $type {
+            type Error = crate::errors::ParseError;
+
+            fn try_from(bytes: alloc::vec::Vec<u8>) -> Result<Self, Self::Error> {
+                <[u8; $bytes_len]>::try_from(bytes)
+                    .map(Self)
+                    .map_err(|_| Self::Error::WrongSize)
+            }
+        }
     };
 }
 pub(crate) use impl_from_bytes;
 
+macro_rules! impl_len {
+    (TYPE = $type:ident, BYTES_LEN = $bytes_len:expr) => {
+        impl $type {
+            /// Byte length of the type.
+            pub const LEN: usize = $bytes_len;
+        }
+    };
+}
+pub(crate) use impl_len;
+
 macro_rules! impl_nullable {
     (TYPE = $type:ident, BYTES_LEN = $bytes_len:expr) => {
         impl solana_nullable::Nullable for $type {
@@ -27,3 +27,17 @@ impl From<PodU64> for u64 {
         Self::from_le_bytes(pod.0)
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+#[repr(transparent)]
+pub struct PodU128([u8; 16]);
+impl From<u128> for PodU128 {
+    fn from(n: u128) -> Self {
+        Self(n.to_le_bytes())
+    }
+}
+impl From<PodU128> for u128 {
+    fn from(pod: PodU128) -> Self {
+        Self::from_le_bytes(pod.0)
+    }
+}
@@ -4,7 +4,7 @@
 use crate::macros::impl_serde_base64;
 use {
     crate::{
-        macros::{impl_from_bytes, impl_from_str, impl_nullable},
+        macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
         RISTRETTO_POINT_LEN, SCALAR_LEN,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
@@ -56,6 +56,8 @@ impl_from_str!(
     BASE64_LEN = RANGE_PROOF_U64_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodRangeProofU64, BYTES_LEN = RANGE_PROOF_U64_LEN);
+
 impl_from_bytes!(TYPE = PodRangeProofU64, BYTES_LEN = RANGE_PROOF_U64_LEN);
 
 impl_nullable!(TYPE = PodRangeProofU64, BYTES_LEN = RANGE_PROOF_U64_LEN);
@@ -82,6 +84,8 @@ impl_from_str!(
     BASE64_LEN = RANGE_PROOF_U128_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodRangeProofU128, BYTES_LEN = RANGE_PROOF_U128_LEN);
+
 impl_from_bytes!(TYPE = PodRangeProofU128, BYTES_LEN = RANGE_PROOF_U128_LEN);
 
 impl_nullable!(TYPE = PodRangeProofU128, BYTES_LEN = RANGE_PROOF_U128_LEN);
@@ -108,6 +112,8 @@ impl_from_str!(
     BASE64_LEN = RANGE_PROOF_U256_MAX_BASE64_LEN
 );
 
+impl_len!(TYPE = PodRangeProofU256, BYTES_LEN = RANGE_PROOF_U256_LEN);
+
 impl_from_bytes!(TYPE = PodRangeProofU256, BYTES_LEN = RANGE_PROOF_U256_LEN);
 
 impl_nullable!(TYPE = PodRangeProofU256, BYTES_LEN = RANGE_PROOF_U256_LEN);
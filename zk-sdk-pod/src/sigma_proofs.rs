@@ -3,7 +3,7 @@
 #[cfg(feature = "serde")]
 use crate::macros::impl_serde_base64;
 use {
-    crate::macros::{impl_from_bytes, impl_from_str, impl_nullable},
+    crate::macros::{impl_from_bytes, impl_from_str, impl_len, impl_nullable},
     base64::{prelude::BASE64_STANDARD, Engine},
     bytemuck::{Pod, Zeroable},
     core::fmt,
@@ -30,12 +30,27 @@ pub const BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN: usize = 192;
 /// Byte length of a zero-ciphertext proof
 pub const ZERO_CIPHERTEXT_PROOF_LEN: usize = 96;
 
+/// Byte length of a grouped zero-ciphertext proof for 2 handles
+pub const GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN: usize = 128;
+
+/// Byte length of a grouped zero-ciphertext proof for 3 handles
+pub const GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN: usize = 160;
+
 /// Byte length of a percentage with cap proof
 pub const PERCENTAGE_WITH_CAP_PROOF_LEN: usize = 256;
 
+/// Byte length of a percentage with cap fee-split proof
+pub const PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN: usize = 320;
+
 /// Byte length of a public key validity proof
 pub const PUBKEY_VALIDITY_PROOF_LEN: usize = 64;
 
+/// Byte length of a lo/hi split consistency proof
+pub const SPLIT_CONSISTENCY_PROOF_LEN: usize = 96;
+
+/// Byte length of an opening-based ciphertext-commitment equality proof
+pub const CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN: usize = 192;
+
 /// The `CiphertextCommitmentEqualityProof` type as a `Pod`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(transparent)]
@@ -55,6 +70,11 @@ impl_from_str!(
     BASE64_LEN = CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodCiphertextCommitmentEqualityProof,
+    BYTES_LEN = CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodCiphertextCommitmentEqualityProof,
     BYTES_LEN = CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN
@@ -87,6 +107,11 @@ impl_from_str!(
     BASE64_LEN = CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodCiphertextCiphertextEqualityProof,
+    BYTES_LEN = CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodCiphertextCiphertextEqualityProof,
     BYTES_LEN = CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN
@@ -121,6 +146,11 @@ impl_from_str!(
     BASE64_LEN = GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodGroupedCiphertext2HandlesValidityProof,
+    BYTES_LEN = GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodGroupedCiphertext2HandlesValidityProof,
     BYTES_LEN = GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN
@@ -155,6 +185,11 @@ impl_from_str!(
     BASE64_LEN = GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodGroupedCiphertext3HandlesValidityProof,
+    BYTES_LEN = GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodGroupedCiphertext3HandlesValidityProof,
     BYTES_LEN = GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN
@@ -189,6 +224,11 @@ impl_from_str!(
     BASE64_LEN = BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodBatchedGroupedCiphertext2HandlesValidityProof,
+    BYTES_LEN = BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodBatchedGroupedCiphertext2HandlesValidityProof,
     BYTES_LEN = BATCHED_GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN
@@ -223,6 +263,11 @@ impl_from_str!(
     BASE64_LEN = BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodBatchedGroupedCiphertext3HandlesValidityProof,
+    BYTES_LEN = BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodBatchedGroupedCiphertext3HandlesValidityProof,
     BYTES_LEN = BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN
@@ -255,6 +300,11 @@ impl_from_str!(
     BASE64_LEN = ZERO_CIPHERTEXT_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodZeroCiphertextProof,
+    BYTES_LEN = ZERO_CIPHERTEXT_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodZeroCiphertextProof,
     BYTES_LEN = ZERO_CIPHERTEXT_PROOF_LEN
@@ -268,6 +318,84 @@ impl_nullable!(
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodZeroCiphertextProof);
 
+/// The `GroupedZeroCiphertextProof<2>` type as a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
+#[repr(transparent)]
+pub struct PodGroupedZeroCiphertext2HandlesProof(
+    pub [u8; GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN],
+);
+
+const GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_MAX_BASE64_LEN: usize = 172;
+
+impl fmt::Display for PodGroupedZeroCiphertext2HandlesProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodGroupedZeroCiphertext2HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN,
+    BASE64_LEN = GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_MAX_BASE64_LEN
+);
+
+impl_len!(
+    TYPE = PodGroupedZeroCiphertext2HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN
+);
+
+impl_from_bytes!(
+    TYPE = PodGroupedZeroCiphertext2HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN
+);
+
+impl_nullable!(
+    TYPE = PodGroupedZeroCiphertext2HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN
+);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodGroupedZeroCiphertext2HandlesProof);
+
+/// The `GroupedZeroCiphertextProof<3>` type as a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PodGroupedZeroCiphertext3HandlesProof(
+    pub [u8; GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN],
+);
+
+const GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_MAX_BASE64_LEN: usize = 216;
+
+impl fmt::Display for PodGroupedZeroCiphertext3HandlesProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodGroupedZeroCiphertext3HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN,
+    BASE64_LEN = GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_MAX_BASE64_LEN
+);
+
+impl_len!(
+    TYPE = PodGroupedZeroCiphertext3HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN
+);
+
+impl_from_bytes!(
+    TYPE = PodGroupedZeroCiphertext3HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN
+);
+
+impl_nullable!(
+    TYPE = PodGroupedZeroCiphertext3HandlesProof,
+    BYTES_LEN = GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN
+);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodGroupedZeroCiphertext3HandlesProof);
+
 /// The `PercentageWithCapProof` type as a `Pod`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
 #[repr(transparent)]
@@ -287,6 +415,11 @@ impl_from_str!(
     BASE64_LEN = PERCENTAGE_WITH_CAP_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodPercentageWithCapProof,
+    BYTES_LEN = PERCENTAGE_WITH_CAP_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodPercentageWithCapProof,
     BYTES_LEN = PERCENTAGE_WITH_CAP_PROOF_LEN
@@ -300,6 +433,43 @@ impl_nullable!(
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodPercentageWithCapProof);
 
+/// The `PercentageWithCapSplitProof` type as a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PodPercentageWithCapSplitProof(pub [u8; PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN]);
+
+const PERCENTAGE_WITH_CAP_SPLIT_PROOF_MAX_BASE64_LEN: usize = 428;
+
+impl fmt::Display for PodPercentageWithCapSplitProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodPercentageWithCapSplitProof,
+    BYTES_LEN = PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN,
+    BASE64_LEN = PERCENTAGE_WITH_CAP_SPLIT_PROOF_MAX_BASE64_LEN
+);
+
+impl_len!(
+    TYPE = PodPercentageWithCapSplitProof,
+    BYTES_LEN = PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN
+);
+
+impl_from_bytes!(
+    TYPE = PodPercentageWithCapSplitProof,
+    BYTES_LEN = PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN
+);
+
+impl_nullable!(
+    TYPE = PodPercentageWithCapSplitProof,
+    BYTES_LEN = PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN
+);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodPercentageWithCapSplitProof);
+
 /// The `PubkeyValidityProof` type as a `Pod`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
 #[repr(transparent)]
@@ -319,6 +489,11 @@ impl_from_str!(
     BASE64_LEN = PUBKEY_VALIDITY_PROOF_MAX_BASE64_LEN
 );
 
+impl_len!(
+    TYPE = PodPubkeyValidityProof,
+    BYTES_LEN = PUBKEY_VALIDITY_PROOF_LEN
+);
+
 impl_from_bytes!(
     TYPE = PodPubkeyValidityProof,
     BYTES_LEN = PUBKEY_VALIDITY_PROOF_LEN
@@ -332,6 +507,82 @@ impl_nullable!(
 #[cfg(feature = "serde")]
 impl_serde_base64!(TYPE = PodPubkeyValidityProof);
 
+/// The `SplitConsistencyProof` type as a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck_derive::Pod, bytemuck_derive::Zeroable)]
+#[repr(transparent)]
+pub struct PodSplitConsistencyProof(pub [u8; SPLIT_CONSISTENCY_PROOF_LEN]);
+
+const SPLIT_CONSISTENCY_PROOF_MAX_BASE64_LEN: usize = 88;
+
+impl fmt::Display for PodSplitConsistencyProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodSplitConsistencyProof,
+    BYTES_LEN = SPLIT_CONSISTENCY_PROOF_LEN,
+    BASE64_LEN = SPLIT_CONSISTENCY_PROOF_MAX_BASE64_LEN
+);
+
+impl_len!(
+    TYPE = PodSplitConsistencyProof,
+    BYTES_LEN = SPLIT_CONSISTENCY_PROOF_LEN
+);
+
+impl_from_bytes!(
+    TYPE = PodSplitConsistencyProof,
+    BYTES_LEN = SPLIT_CONSISTENCY_PROOF_LEN
+);
+
+impl_nullable!(
+    TYPE = PodSplitConsistencyProof,
+    BYTES_LEN = SPLIT_CONSISTENCY_PROOF_LEN
+);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodSplitConsistencyProof);
+
+/// The `CiphertextCommitmentOpeningEqualityProof` type as a `Pod`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PodCiphertextCommitmentOpeningEqualityProof(
+    pub [u8; CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN],
+);
+
+const CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_MAX_BASE64_LEN: usize = 256;
+
+impl fmt::Display for PodCiphertextCommitmentOpeningEqualityProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl_from_str!(
+    TYPE = PodCiphertextCommitmentOpeningEqualityProof,
+    BYTES_LEN = CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN,
+    BASE64_LEN = CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_MAX_BASE64_LEN
+);
+
+impl_len!(
+    TYPE = PodCiphertextCommitmentOpeningEqualityProof,
+    BYTES_LEN = CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN
+);
+
+impl_from_bytes!(
+    TYPE = PodCiphertextCommitmentOpeningEqualityProof,
+    BYTES_LEN = CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN
+);
+
+impl_nullable!(
+    TYPE = PodCiphertextCommitmentOpeningEqualityProof,
+    BYTES_LEN = CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN
+);
+
+#[cfg(feature = "serde")]
+impl_serde_base64!(TYPE = PodCiphertextCommitmentOpeningEqualityProof);
+
 // The sigma proof pod types are wrappers for byte arrays, which are both `Pod` and `Zeroable`. However,
 // the marker traits `bytemuck::Pod` and `bytemuck::Zeroable` can only be derived for power-of-two
 // length byte arrays. Directly implement these traits for the sigma proof pod types.
@@ -356,6 +607,15 @@ unsafe impl Pod for PodBatchedGroupedCiphertext3HandlesValidityProof {}
 unsafe impl Zeroable for PodZeroCiphertextProof {}
 unsafe impl Pod for PodZeroCiphertextProof {}
 
+unsafe impl Zeroable for PodGroupedZeroCiphertext3HandlesProof {}
+unsafe impl Pod for PodGroupedZeroCiphertext3HandlesProof {}
+
+unsafe impl Zeroable for PodPercentageWithCapSplitProof {}
+unsafe impl Pod for PodPercentageWithCapSplitProof {}
+
+unsafe impl Zeroable for PodCiphertextCommitmentOpeningEqualityProof {}
+unsafe impl Pod for PodCiphertextCommitmentOpeningEqualityProof {}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "serde")]
@@ -419,15 +679,45 @@ mod tests {
         ZERO_CIPHERTEXT_PROOF_LEN
     );
 
+    test_sigma_proof_serde!(
+        test_grouped_zero_ciphertext_2_handles_proof_serde,
+        PodGroupedZeroCiphertext2HandlesProof,
+        GROUPED_ZERO_CIPHERTEXT_2_HANDLES_PROOF_LEN
+    );
+
+    test_sigma_proof_serde!(
+        test_grouped_zero_ciphertext_3_handles_proof_serde,
+        PodGroupedZeroCiphertext3HandlesProof,
+        GROUPED_ZERO_CIPHERTEXT_3_HANDLES_PROOF_LEN
+    );
+
     test_sigma_proof_serde!(
         test_percentage_with_cap_proof_serde,
         PodPercentageWithCapProof,
         PERCENTAGE_WITH_CAP_PROOF_LEN
     );
 
+    test_sigma_proof_serde!(
+        test_percentage_with_cap_split_proof_serde,
+        PodPercentageWithCapSplitProof,
+        PERCENTAGE_WITH_CAP_SPLIT_PROOF_LEN
+    );
+
     test_sigma_proof_serde!(
         test_pubkey_validity_proof_serde,
         PodPubkeyValidityProof,
         PUBKEY_VALIDITY_PROOF_LEN
     );
+
+    test_sigma_proof_serde!(
+        test_split_consistency_proof_serde,
+        PodSplitConsistencyProof,
+        SPLIT_CONSISTENCY_PROOF_LEN
+    );
+
+    test_sigma_proof_serde!(
+        test_ciphertext_commitment_opening_equality_proof_serde,
+        PodCiphertextCommitmentOpeningEqualityProof,
+        CIPHERTEXT_COMMITMENT_OPENING_EQUALITY_PROOF_LEN
+    );
 }
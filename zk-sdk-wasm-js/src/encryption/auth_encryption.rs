@@ -1,4 +1,5 @@
 use {
+    crate::encryption::derivation::signature_from_uint8_array,
     js_sys::Uint8Array,
     solana_seed_derivable::SeedDerivable,
     solana_zk_sdk::encryption::auth_encryption,
@@ -51,6 +52,30 @@ impl AeKey {
         .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Returns the message a Solana signer must sign to derive an `AeKey`
+    /// via `fromSignatureLegacy`.
+    ///
+    /// The message is `b"AeKey" || public_seed`, matching the native
+    /// `AeKey::new_from_signer_legacy` derivation, so browser wallets can
+    /// recover keys for accounts provisioned under that scheme.
+    #[wasm_bindgen(js_name = "legacySignerMessage")]
+    pub fn legacy_signer_message(public_seed: Uint8Array) -> Vec<u8> {
+        [b"AeKey".as_ref(), &public_seed.to_vec()].concat()
+    }
+
+    /// Derives an `AeKey` from a 64-byte ed25519 signature over the message
+    /// returned by `legacySignerMessage`.
+    ///
+    /// New accounts should use `ConfidentialKeys.fromSignature` instead.
+    #[wasm_bindgen(js_name = "fromSignatureLegacy")]
+    #[allow(deprecated)]
+    pub fn from_signature_legacy(signature: Uint8Array) -> Result<AeKey, JsValue> {
+        let signature = signature_from_uint8_array(&signature)?;
+        auth_encryption::AeKey::new_from_signature_legacy(&signature)
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes an `AeKey` from a byte slice.
     #[wasm_bindgen(js_name = "fromBytes")]
     pub fn from_bytes(uint8_array: Uint8Array) -> Result<AeKey, JsValue> {
@@ -216,4 +241,19 @@ mod tests {
             AeKey::from_seed_phrase_and_passphrase(phrase, Some("pw".to_string())).unwrap();
         assert_ne!(different.to_bytes(), a.to_bytes());
     }
+
+    #[wasm_bindgen_test]
+    fn test_from_signature_legacy() {
+        let seed = [4u8; 32];
+        let message = AeKey::legacy_signer_message(Uint8Array::from(seed.as_ref()));
+        assert_eq!(message, [b"AeKey".as_ref(), seed.as_ref()].concat());
+
+        let signature = Uint8Array::from([3u8; 64].as_ref());
+        let key_a = AeKey::from_signature_legacy(signature.clone()).unwrap();
+        let key_b = AeKey::from_signature_legacy(signature).unwrap();
+        assert_eq!(key_a.to_bytes(), key_b.to_bytes());
+
+        assert!(AeKey::from_signature_legacy(Uint8Array::from([0u8; 64].as_ref())).is_err());
+        assert!(AeKey::from_signature_legacy(Uint8Array::from([3u8; 63].as_ref())).is_err());
+    }
 }
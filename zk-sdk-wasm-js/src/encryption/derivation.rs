@@ -1,10 +1,11 @@
 use {
     crate::encryption::{auth_encryption::AeKey, elgamal::ElGamalKeypair},
     js_sys::Uint8Array,
+    solana_address::Address,
     solana_signature::Signature,
     solana_zk_sdk::encryption::derivation::{
         confidential_derivation_message, derive_confidential_keys_from_ikm,
-        derive_confidential_keys_from_signature,
+        derive_confidential_keys_from_signature, derive_confidential_keys_from_wallet_signature,
         pda_wallet_public_seed as sdk_pda_wallet_public_seed, PDA_WALLET_PUBLIC_SEED_FIELD_LEN,
     },
     wasm_bindgen::prelude::{wasm_bindgen, JsValue},
@@ -34,6 +35,26 @@ fn copy_public_seed_field(
     Ok(bytes)
 }
 
+/// Parses a 64-byte ed25519 signature produced by a wallet, rejecting the
+/// all-zero default signature that some signers return on failure.
+pub(crate) fn signature_from_uint8_array(signature: &Uint8Array) -> Result<Signature, JsValue> {
+    if signature.length() as usize != SIGNATURE_LEN {
+        return Err(JsValue::from_str(&format!(
+            "Invalid signature length: expected {}, got {}",
+            SIGNATURE_LEN,
+            signature.length()
+        )));
+    }
+    let mut bytes = [0u8; SIGNATURE_LEN];
+    signature.copy_to(&mut bytes);
+
+    let signature = Signature::from(bytes);
+    if signature == Signature::default() {
+        return Err(JsValue::from_str("Rejecting default signature"));
+    }
+    Ok(signature)
+}
+
 /// Container returned by the unified confidential-balances key derivation.
 ///
 /// Both the ElGamal keypair and the AES (`decryptable_available_balance`
@@ -100,16 +121,7 @@ impl ConfidentialKeys {
     /// over the message returned by `signerMessage`.
     #[wasm_bindgen(js_name = "fromSignature")]
     pub fn from_signature(signature: Uint8Array) -> Result<ConfidentialKeys, JsValue> {
-        if signature.length() as usize != SIGNATURE_LEN {
-            return Err(JsValue::from_str(&format!(
-                "Invalid signature length: expected {}, got {}",
-                SIGNATURE_LEN,
-                signature.length()
-            )));
-        }
-        let mut bytes = [0u8; SIGNATURE_LEN];
-        signature.copy_to(&mut bytes);
-        let signature = Signature::from(bytes);
+        let signature = signature_from_uint8_array(&signature)?;
 
         derive_confidential_keys_from_signature(&signature)
             .map(|(elgamal, ae)| Self {
@@ -119,6 +131,34 @@ impl ConfidentialKeys {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Derives a `ConfidentialKeys` pair from the signature a wallet adapter
+    /// returned from `signMessage` over the message returned by
+    /// `signerMessage`.
+    ///
+    /// Unlike `fromSignature`, the signature is strictly verified against
+    /// `walletPubkey` and `publicSeed`, and malleable encodings are rejected,
+    /// so every wallet that signs the same message derives the same keys.
+    #[wasm_bindgen(js_name = "fromWalletSignature")]
+    pub fn from_wallet_signature(
+        wallet_pubkey: Uint8Array,
+        public_seed: Uint8Array,
+        signature: Uint8Array,
+    ) -> Result<ConfidentialKeys, JsValue> {
+        let wallet_pubkey = Address::from(copy_public_seed_field("wallet_pubkey", &wallet_pubkey)?);
+        let signature = signature_from_uint8_array(&signature)?;
+
+        derive_confidential_keys_from_wallet_signature(
+            &wallet_pubkey,
+            &public_seed.to_vec(),
+            &signature,
+        )
+        .map(|(elgamal, ae)| Self {
+            elgamal: elgamal.into(),
+            ae: ae.into(),
+        })
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Derives a `ConfidentialKeys` pair from raw input key material.
     ///
     /// Use this when the caller already produced 32 or more bytes of IKM
@@ -257,6 +297,24 @@ mod tests {
         assert_eq!(from_sig.ae().to_bytes(), from_ikm.ae().to_bytes());
     }
 
+    #[wasm_bindgen_test]
+    fn test_from_wallet_signature_rejects_unverified_signature() {
+        let wallet_pubkey = Uint8Array::from([1u8; 32].as_ref());
+        let public_seed = Uint8Array::from([2u8; 32].as_ref());
+        let signature = Uint8Array::from([3u8; 64].as_ref());
+        assert!(ConfidentialKeys::from_wallet_signature(
+            wallet_pubkey,
+            public_seed.clone(),
+            signature.clone()
+        )
+        .is_err());
+
+        let short_pubkey = Uint8Array::from([1u8; 31].as_ref());
+        assert!(
+            ConfidentialKeys::from_wallet_signature(short_pubkey, public_seed, signature).is_err()
+        );
+    }
+
     #[wasm_bindgen_test]
     fn test_from_ikm_rejects_short() {
         let too_short = vec![0u8; 31];
@@ -0,0 +1,108 @@
+use {
+    crate::encryption::elgamal::{ElGamalCiphertext, ElGamalSecretKey},
+    js_sys::Uint8Array,
+    solana_zk_sdk::encryption::discrete_log,
+    std::num::NonZeroUsize,
+    wasm_bindgen::prelude::{wasm_bindgen, JsValue},
+};
+
+/// A resumable discrete log search that decrypts an ElGamal ciphertext in chunks.
+///
+/// Each call to `search` runs a bounded number of steps, so a browser wallet can decrypt a balance
+/// without blocking the main thread for the whole search. The state can be sent to a web worker
+/// with `toBytes` and `fromBytes`, and a search can be divided among workers with `part`.
+#[wasm_bindgen]
+pub struct DiscreteLogSearch {
+    pub(crate) inner: discrete_log::DiscreteLogSearch,
+}
+
+crate::conversion::impl_inner_conversion!(DiscreteLogSearch, discrete_log::DiscreteLogSearch);
+
+#[wasm_bindgen]
+impl DiscreteLogSearch {
+    /// Starts a search for the amount that is encrypted in an ElGamal ciphertext.
+    #[wasm_bindgen(js_name = "fromCiphertext")]
+    pub fn from_ciphertext(secret_key: &ElGamalSecretKey, ciphertext: &ElGamalCiphertext) -> Self {
+        let target = secret_key.inner.decrypt(&ciphertext.inner).target;
+        Self {
+            inner: discrete_log::DiscreteLogSearch::new(target),
+        }
+    }
+
+    /// Returns the `index`-th of `parts` contiguous searches that together cover the remaining
+    /// steps of this search.
+    /// Throws an error if `parts` is zero or `index` is not less than `parts`.
+    pub fn part(&self, index: usize, parts: usize) -> Result<DiscreteLogSearch, JsValue> {
+        let parts = NonZeroUsize::new(parts)
+            .ok_or_else(|| JsValue::from_str("number of parts must be positive"))?;
+        self.inner
+            .split(parts)
+            .into_iter()
+            .nth(index)
+            .map(|inner| Self { inner })
+            .ok_or_else(|| JsValue::from_str("part index out of range"))
+    }
+
+    /// Runs at most `maxSteps` steps of the search.
+    /// Returns the decrypted amount if it is found, or `undefined` otherwise.
+    pub fn search(&mut self, max_steps: u32) -> Option<u64> {
+        self.inner.search(max_steps.into())
+    }
+
+    /// The decrypted amount, or `undefined` if it has not been found.
+    pub fn solution(&self) -> Option<u64> {
+        self.inner.solution()
+    }
+
+    /// Returns `true` if the amount is found or all steps are searched.
+    #[wasm_bindgen(js_name = "isFinished")]
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// The number of steps that are left to search.
+    #[wasm_bindgen(js_name = "stepsRemaining")]
+    pub fn steps_remaining(&self) -> u64 {
+        self.inner.steps_remaining()
+    }
+
+    /// Deserializes the state of a search from a byte slice.
+    /// Throws an error if the bytes are invalid.
+    #[wasm_bindgen(js_name = "fromBytes")]
+    pub fn from_bytes(uint8_array: Uint8Array) -> Result<DiscreteLogSearch, JsValue> {
+        discrete_log::DiscreteLogSearch::from_bytes(&uint8_array.to_vec())
+            .map(|inner| Self { inner })
+            .ok_or_else(|| JsValue::from_str("Invalid bytes for DiscreteLogSearch"))
+    }
+
+    /// Serializes the state of the search to a byte array.
+    #[wasm_bindgen(js_name = "toBytes")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair, wasm_bindgen_test::*};
+
+    #[wasm_bindgen_test]
+    fn test_discrete_log_search_in_chunks() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt_u64(4_294_967_295);
+
+        let search = DiscreteLogSearch::from_ciphertext(&keypair.secret(), &ciphertext);
+        let mut solutions = vec![];
+        for index in 0..4 {
+            let mut part = search.part(index, 4).unwrap();
+            while !part.is_finished() {
+                part.search(1024);
+                part = DiscreteLogSearch::from_bytes(Uint8Array::from(part.to_bytes().as_slice()))
+                    .unwrap();
+            }
+            solutions.extend(part.solution());
+        }
+        assert_eq!(solutions, vec![4_294_967_295]);
+        assert!(search.part(4, 4).is_err());
+    }
+}
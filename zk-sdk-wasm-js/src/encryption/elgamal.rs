@@ -1,5 +1,8 @@
 use {
-    crate::encryption::pedersen::{PedersenCommitment, PedersenOpening},
+    crate::encryption::{
+        derivation::signature_from_uint8_array,
+        pedersen::{PedersenCommitment, PedersenOpening},
+    },
     js_sys::Uint8Array,
     solana_seed_derivable::SeedDerivable,
     solana_zk_sdk::encryption::elgamal,
@@ -196,6 +199,30 @@ impl ElGamalKeypair {
         Ok(Self::from_secret_key(&secret))
     }
 
+    /// Returns the message a Solana signer must sign to derive an
+    /// `ElGamalKeypair` via `fromSignatureLegacy`.
+    ///
+    /// The message is `b"ElGamalSecretKey" || public_seed`, matching the
+    /// native `ElGamalKeypair::new_from_signer_legacy` derivation, so browser
+    /// wallets can recover keys for accounts provisioned under that scheme.
+    #[wasm_bindgen(js_name = "legacySignerMessage")]
+    pub fn legacy_signer_message(public_seed: Uint8Array) -> Vec<u8> {
+        [b"ElGamalSecretKey".as_ref(), &public_seed.to_vec()].concat()
+    }
+
+    /// Derives an `ElGamalKeypair` from a 64-byte ed25519 signature over the
+    /// message returned by `legacySignerMessage`.
+    ///
+    /// New accounts should use `ConfidentialKeys.fromSignature` instead.
+    #[wasm_bindgen(js_name = "fromSignatureLegacy")]
+    #[allow(deprecated)]
+    pub fn from_signature_legacy(signature: Uint8Array) -> Result<ElGamalKeypair, JsValue> {
+        let signature = signature_from_uint8_array(&signature)?;
+        elgamal::ElGamalKeypair::new_from_signature_legacy(&signature)
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Returns the public key of the keypair.
     pub fn pubkey(&self) -> ElGamalPubkey {
         ElGamalPubkey {
@@ -470,4 +497,26 @@ mod tests {
                 .unwrap();
         assert_ne!(different.to_bytes(), a.to_bytes());
     }
+
+    #[wasm_bindgen_test]
+    fn test_from_signature_legacy() {
+        let seed = [4u8; 32];
+        let message = ElGamalKeypair::legacy_signer_message(Uint8Array::from(seed.as_ref()));
+        assert_eq!(
+            message,
+            [b"ElGamalSecretKey".as_ref(), seed.as_ref()].concat()
+        );
+
+        let signature = Uint8Array::from([3u8; 64].as_ref());
+        let keypair_a = ElGamalKeypair::from_signature_legacy(signature.clone()).unwrap();
+        let keypair_b = ElGamalKeypair::from_signature_legacy(signature).unwrap();
+        assert_eq!(keypair_a.pubkey().to_bytes(), keypair_b.pubkey().to_bytes());
+
+        assert!(
+            ElGamalKeypair::from_signature_legacy(Uint8Array::from([0u8; 64].as_ref())).is_err()
+        );
+        assert!(
+            ElGamalKeypair::from_signature_legacy(Uint8Array::from([3u8; 63].as_ref())).is_err()
+        );
+    }
 }
@@ -5,15 +5,12 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_sdk::encryption::grouped_elgamal,
-    solana_zk_sdk_pod::encryption::{DECRYPT_HANDLE_LEN, PEDERSEN_COMMITMENT_LEN},
+    solana_zk_sdk_pod::encryption::{
+        GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN, GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN,
+    },
     wasm_bindgen::prelude::*,
 };
 
-const GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN: usize =
-    DECRYPT_HANDLE_LEN * 2 + PEDERSEN_COMMITMENT_LEN;
-const GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN: usize =
-    DECRYPT_HANDLE_LEN * 3 + PEDERSEN_COMMITMENT_LEN;
-
 #[wasm_bindgen]
 pub struct GroupedElGamalCiphertext2Handles {
     pub(crate) inner: grouped_elgamal::GroupedElGamalCiphertext<2>,
@@ -1,5 +1,6 @@
 pub mod auth_encryption;
 pub mod derivation;
+pub mod discrete_log;
 pub mod elgamal;
 pub mod grouped_elgamal;
 pub mod pedersen;
@@ -5,7 +5,13 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{
+            BatchedGroupedCiphertext3HandlesValidityStatement,
+            BatchedGroupedCiphertext3HandlesValidityWitness, ProofBuilder,
+        },
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -37,19 +43,48 @@ impl BatchedGroupedCiphertext3HandlesValidityProofData {
         opening_lo: &PedersenOpening,
         opening_hi: &PedersenOpening,
     ) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, JsValue> {
-        zk_elgamal_proof_program::build_batched_grouped_ciphertext_3_handles_validity_proof_data(
-            &first_pubkey.inner,
-            &second_pubkey.inner,
-            &third_pubkey.inner,
-            &grouped_ciphertext_lo.inner,
-            &grouped_ciphertext_hi.inner,
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
             amount_lo,
             amount_hi,
-            &opening_lo.inner,
-            &opening_hi.inner,
+            opening_lo,
+            opening_hi,
+            None,
+        )
+    }
+
+    /// Creates a new batched grouped ciphertext validity proof with three handles with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_context(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext_lo: &GroupedElGamalCiphertext3Handles,
+        grouped_ciphertext_hi: &GroupedElGamalCiphertext3Handles,
+        amount_lo: u64,
+        amount_hi: u64,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        transcript_context: &[u8],
+    ) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, JsValue> {
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            amount_lo,
+            amount_hi,
+            opening_lo,
+            opening_hi,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -67,6 +102,17 @@ impl BatchedGroupedCiphertext3HandlesValidityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the batched grouped ciphertext 3-handles validity proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a batched grouped ciphertext validity proof with three handles from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -104,6 +150,40 @@ impl BatchedGroupedCiphertext3HandlesValidityProofData {
     }
 }
 
+impl BatchedGroupedCiphertext3HandlesValidityProofData {
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext_lo: &GroupedElGamalCiphertext3Handles,
+        grouped_ciphertext_hi: &GroupedElGamalCiphertext3Handles,
+        amount_lo: u64,
+        amount_hi: u64,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(BatchedGroupedCiphertext3HandlesValidityStatement {
+                first_pubkey: &first_pubkey.inner,
+                second_pubkey: &second_pubkey.inner,
+                third_pubkey: &third_pubkey.inner,
+                grouped_ciphertext_lo: &grouped_ciphertext_lo.inner,
+                grouped_ciphertext_hi: &grouped_ciphertext_hi.inner,
+            })
+            .witness(BatchedGroupedCiphertext3HandlesValidityWitness {
+                amount_lo,
+                amount_hi,
+                opening_lo: &opening_lo.inner,
+                opening_hi: &opening_hi.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a batched grouped ciphertext 3-handles validity proof.
 #[wasm_bindgen]
 pub struct BatchedGroupedCiphertext3HandlesValidityProofContext {
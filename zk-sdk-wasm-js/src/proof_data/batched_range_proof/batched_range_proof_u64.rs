@@ -5,7 +5,10 @@ use {
     },
     js_sys::{BigUint64Array, Uint8Array},
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{BatchedRangeProofU64Statement, BatchedRangeProofU64Witness, ProofBuilder},
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -43,31 +46,28 @@ impl BatchedRangeProofU64Data {
         bit_lengths: Uint8Array,
         openings: Box<[PedersenOpening]>,
     ) -> Result<BatchedRangeProofU64Data, JsValue> {
-        // Check array lengths for early exit and clearer error messages
-        if commitments.len() != amounts.length() as usize
-            || commitments.len() != bit_lengths.length() as usize
-            || commitments.len() != openings.len()
-        {
-            return Err(JsValue::from_str("Mismatched lengths of input arrays"));
-        }
-
-        let commitments_inner: Vec<_> = commitments.iter().map(|c| &c.inner).collect();
-        let amounts_vec = amounts.to_vec();
-        let bit_lengths_vec: Vec<usize> = bit_lengths
-            .to_vec()
-            .into_iter()
-            .map(|x| x as usize)
-            .collect();
-        let openings_inner: Vec<_> = openings.iter().map(|o| &o.inner).collect();
+        Self::build(commitments, amounts, bit_lengths, openings, None)
+    }
 
-        zk_elgamal_proof_program::build_batched_range_proof_u64_data(
-            commitments_inner,
-            amounts_vec,
-            bit_lengths_vec,
-            openings_inner,
+    /// Creates a new 64-bit batched range proof with `transcript_context`
+    /// bound into the proof.
+    ///
+    /// The remaining arguments are the same as for the constructor.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        commitments: Box<[PedersenCommitment]>,
+        amounts: BigUint64Array,
+        bit_lengths: Uint8Array,
+        openings: Box<[PedersenOpening]>,
+        transcript_context: &[u8],
+    ) -> Result<BatchedRangeProofU64Data, JsValue> {
+        Self::build(
+            commitments,
+            amounts,
+            bit_lengths,
+            openings,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -84,6 +84,17 @@ impl BatchedRangeProofU64Data {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the 64-bit batched range proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a 64-bit batched range proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -113,6 +124,46 @@ impl BatchedRangeProofU64Data {
     }
 }
 
+impl BatchedRangeProofU64Data {
+    fn build(
+        commitments: Box<[PedersenCommitment]>,
+        amounts: BigUint64Array,
+        bit_lengths: Uint8Array,
+        openings: Box<[PedersenOpening]>,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<BatchedRangeProofU64Data, JsValue> {
+        // Check array lengths for early exit and clearer error messages
+        if commitments.len() != amounts.length() as usize
+            || commitments.len() != bit_lengths.length() as usize
+            || commitments.len() != openings.len()
+        {
+            return Err(JsValue::from_str("Mismatched lengths of input arrays"));
+        }
+
+        let commitments_inner: Vec<_> = commitments.iter().map(|c| &c.inner).collect();
+        let amounts_vec = amounts.to_vec();
+        let bit_lengths_vec: Vec<usize> = bit_lengths
+            .to_vec()
+            .into_iter()
+            .map(|x| x as usize)
+            .collect();
+        let openings_inner: Vec<_> = openings.iter().map(|o| &o.inner).collect();
+
+        ProofBuilder::new(transcript_context)
+            .statement(BatchedRangeProofU64Statement {
+                commitments: commitments_inner,
+                bit_lengths: bit_lengths_vec,
+            })
+            .witness(BatchedRangeProofU64Witness {
+                amounts: amounts_vec,
+                openings: openings_inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -5,7 +5,13 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{
+            CiphertextCiphertextEqualityStatement, CiphertextCiphertextEqualityWitness,
+            ProofBuilder,
+        },
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -34,16 +40,38 @@ impl CiphertextCiphertextEqualityProofData {
         second_opening: &PedersenOpening,
         amount: u64,
     ) -> Result<CiphertextCiphertextEqualityProofData, JsValue> {
-        zk_elgamal_proof_program::build_ciphertext_ciphertext_equality_proof_data(
-            &first_keypair.inner,
-            &second_pubkey.inner,
-            &first_ciphertext.inner,
-            &second_ciphertext.inner,
-            &second_opening.inner,
+        Self::build(
+            first_keypair,
+            second_pubkey,
+            first_ciphertext,
+            second_ciphertext,
+            second_opening,
             amount,
+            None,
+        )
+    }
+
+    /// Creates a new ciphertext-ciphertext equality proof with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        first_keypair: &ElGamalKeypair,
+        second_pubkey: &ElGamalPubkey,
+        first_ciphertext: &ElGamalCiphertext,
+        second_ciphertext: &ElGamalCiphertext,
+        second_opening: &PedersenOpening,
+        amount: u64,
+        transcript_context: &[u8],
+    ) -> Result<CiphertextCiphertextEqualityProofData, JsValue> {
+        Self::build(
+            first_keypair,
+            second_pubkey,
+            first_ciphertext,
+            second_ciphertext,
+            second_opening,
+            amount,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -61,6 +89,17 @@ impl CiphertextCiphertextEqualityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the ciphertext-ciphertext equality proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a ciphertext-ciphertext equality proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -93,6 +132,34 @@ impl CiphertextCiphertextEqualityProofData {
     }
 }
 
+impl CiphertextCiphertextEqualityProofData {
+    fn build(
+        first_keypair: &ElGamalKeypair,
+        second_pubkey: &ElGamalPubkey,
+        first_ciphertext: &ElGamalCiphertext,
+        second_ciphertext: &ElGamalCiphertext,
+        second_opening: &PedersenOpening,
+        amount: u64,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<CiphertextCiphertextEqualityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(CiphertextCiphertextEqualityStatement {
+                first_pubkey: first_keypair.inner.pubkey(),
+                second_pubkey: &second_pubkey.inner,
+                first_ciphertext: &first_ciphertext.inner,
+                second_ciphertext: &second_ciphertext.inner,
+            })
+            .witness(CiphertextCiphertextEqualityWitness {
+                first_keypair: &first_keypair.inner,
+                second_opening: &second_opening.inner,
+                amount,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a ciphertext-ciphertext equality proof.
 #[wasm_bindgen]
 pub struct CiphertextCiphertextEqualityProofContext {
@@ -5,7 +5,13 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{
+            CiphertextCommitmentEqualityStatement, CiphertextCommitmentEqualityWitness,
+            ProofBuilder,
+        },
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -33,15 +39,28 @@ impl CiphertextCommitmentEqualityProofData {
         opening: &PedersenOpening,
         amount: u64,
     ) -> Result<CiphertextCommitmentEqualityProofData, JsValue> {
-        zk_elgamal_proof_program::build_ciphertext_commitment_equality_proof_data(
-            &keypair.inner,
-            &ciphertext.inner,
-            &commitment.inner,
-            &opening.inner,
+        Self::build(keypair, ciphertext, commitment, opening, amount, None)
+    }
+
+    /// Creates a new ciphertext-commitment equality proof with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        opening: &PedersenOpening,
+        amount: u64,
+        transcript_context: &[u8],
+    ) -> Result<CiphertextCommitmentEqualityProofData, JsValue> {
+        Self::build(
+            keypair,
+            ciphertext,
+            commitment,
+            opening,
             amount,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -59,6 +78,17 @@ impl CiphertextCommitmentEqualityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the ciphertext-commitment equality proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a ciphertext-commitment equality proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -91,6 +121,32 @@ impl CiphertextCommitmentEqualityProofData {
     }
 }
 
+impl CiphertextCommitmentEqualityProofData {
+    fn build(
+        keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        opening: &PedersenOpening,
+        amount: u64,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<CiphertextCommitmentEqualityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(CiphertextCommitmentEqualityStatement {
+                pubkey: keypair.inner.pubkey(),
+                ciphertext: &ciphertext.inner,
+                commitment: &commitment.inner,
+            })
+            .witness(CiphertextCommitmentEqualityWitness {
+                keypair: &keypair.inner,
+                opening: &opening.inner,
+                amount,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a ciphertext-commitment equality proof.
 #[wasm_bindgen]
 pub struct CiphertextCommitmentEqualityProofContext {
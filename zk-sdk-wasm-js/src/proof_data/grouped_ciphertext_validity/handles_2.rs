@@ -5,7 +5,13 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{
+            GroupedCiphertext2HandlesValidityStatement, GroupedCiphertext2HandlesValidityWitness,
+            ProofBuilder,
+        },
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -32,15 +38,35 @@ impl GroupedCiphertext2HandlesValidityProofData {
         amount: u64,
         opening: &PedersenOpening,
     ) -> Result<GroupedCiphertext2HandlesValidityProofData, JsValue> {
-        zk_elgamal_proof_program::build_grouped_ciphertext_2_handles_validity_proof_data(
-            &first_pubkey.inner,
-            &second_pubkey.inner,
-            &grouped_ciphertext.inner,
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext,
             amount,
-            &opening.inner,
+            opening,
+            None,
+        )
+    }
+
+    /// Creates a new grouped ciphertext validity proof with two handles with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext2Handles,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript_context: &[u8],
+    ) -> Result<GroupedCiphertext2HandlesValidityProofData, JsValue> {
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -58,6 +84,17 @@ impl GroupedCiphertext2HandlesValidityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the grouped ciphertext 2-handles validity proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a grouped ciphertext validity proof with two handles from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -93,6 +130,31 @@ impl GroupedCiphertext2HandlesValidityProofData {
     }
 }
 
+impl GroupedCiphertext2HandlesValidityProofData {
+    fn build(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext2Handles,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<GroupedCiphertext2HandlesValidityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(GroupedCiphertext2HandlesValidityStatement {
+                first_pubkey: &first_pubkey.inner,
+                second_pubkey: &second_pubkey.inner,
+                grouped_ciphertext: &grouped_ciphertext.inner,
+            })
+            .witness(GroupedCiphertext2HandlesValidityWitness {
+                amount,
+                opening: &opening.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a grouped ciphertext 2-handles validity proof.
 #[wasm_bindgen]
 pub struct GroupedCiphertext2HandlesValidityProofContext {
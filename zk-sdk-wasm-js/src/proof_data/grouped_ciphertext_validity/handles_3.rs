@@ -5,7 +5,13 @@ use {
     },
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{
+            GroupedCiphertext3HandlesValidityStatement, GroupedCiphertext3HandlesValidityWitness,
+            ProofBuilder,
+        },
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -34,16 +40,38 @@ impl GroupedCiphertext3HandlesValidityProofData {
         amount: u64,
         opening: &PedersenOpening,
     ) -> Result<GroupedCiphertext3HandlesValidityProofData, JsValue> {
-        zk_elgamal_proof_program::build_grouped_ciphertext_3_handles_validity_proof_data(
-            &first_pubkey.inner,
-            &second_pubkey.inner,
-            &third_pubkey.inner,
-            &grouped_ciphertext.inner,
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+            None,
+        )
+    }
+
+    /// Creates a new grouped ciphertext validity proof with three handles with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext3Handles,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript_context: &[u8],
+    ) -> Result<GroupedCiphertext3HandlesValidityProofData, JsValue> {
+        Self::build(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
             amount,
-            &opening.inner,
+            opening,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -61,6 +89,17 @@ impl GroupedCiphertext3HandlesValidityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the grouped ciphertext 3-handles validity proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a grouped ciphertext validity proof with three handles from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -96,6 +135,33 @@ impl GroupedCiphertext3HandlesValidityProofData {
     }
 }
 
+impl GroupedCiphertext3HandlesValidityProofData {
+    fn build(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext3Handles,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<GroupedCiphertext3HandlesValidityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(GroupedCiphertext3HandlesValidityStatement {
+                first_pubkey: &first_pubkey.inner,
+                second_pubkey: &second_pubkey.inner,
+                third_pubkey: &third_pubkey.inner,
+                grouped_ciphertext: &grouped_ciphertext.inner,
+            })
+            .witness(GroupedCiphertext3HandlesValidityWitness {
+                amount,
+                opening: &opening.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a grouped ciphertext 3-handles validity proof.
 #[wasm_bindgen]
 pub struct GroupedCiphertext3HandlesValidityProofContext {
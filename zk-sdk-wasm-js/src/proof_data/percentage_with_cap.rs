@@ -2,7 +2,10 @@ use {
     crate::encryption::pedersen::{PedersenCommitment, PedersenOpening},
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{PercentageWithCapStatement, PercentageWithCapWitness, ProofBuilder},
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -34,19 +37,48 @@ impl PercentageWithCapProofData {
         claimed_opening: &PedersenOpening,
         max_value: u64,
     ) -> Result<PercentageWithCapProofData, JsValue> {
-        zk_elgamal_proof_program::build_percentage_with_cap_proof_data(
-            &percentage_commitment.inner,
-            &percentage_opening.inner,
+        Self::build(
+            percentage_commitment,
+            percentage_opening,
             percentage_amount,
-            &delta_commitment.inner,
-            &delta_opening.inner,
+            delta_commitment,
+            delta_opening,
             delta_amount,
-            &claimed_commitment.inner,
-            &claimed_opening.inner,
+            claimed_commitment,
+            claimed_opening,
             max_value,
+            None,
+        )
+    }
+
+    /// Creates a new percentage-with-cap proof with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_context(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u64,
+        transcript_context: &[u8],
+    ) -> Result<PercentageWithCapProofData, JsValue> {
+        Self::build(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            Some(transcript_context),
         )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Returns the context data associated with the proof.
@@ -64,6 +96,17 @@ impl PercentageWithCapProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the percentage-with-cap proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a percentage-with-cap proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -93,6 +136,40 @@ impl PercentageWithCapProofData {
     }
 }
 
+impl PercentageWithCapProofData {
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u64,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<PercentageWithCapProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(PercentageWithCapStatement {
+                percentage_commitment: &percentage_commitment.inner,
+                delta_commitment: &delta_commitment.inner,
+                claimed_commitment: &claimed_commitment.inner,
+                max_value,
+            })
+            .witness(PercentageWithCapWitness {
+                percentage_opening: &percentage_opening.inner,
+                percentage_amount,
+                delta_opening: &delta_opening.inner,
+                delta_amount,
+                claimed_opening: &claimed_opening.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a percentage-with-cap proof.
 #[wasm_bindgen]
 pub struct PercentageWithCapProofContext {
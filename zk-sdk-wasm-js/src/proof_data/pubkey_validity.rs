@@ -2,7 +2,10 @@ use {
     crate::encryption::elgamal::ElGamalKeypair,
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{ProofBuilder, PubkeyValidityStatement, PubkeyValidityWitness},
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -23,9 +26,17 @@ impl PubkeyValidityProofData {
     /// Creates a new public-key validity proof.
     #[wasm_bindgen(constructor)]
     pub fn new(keypair: &ElGamalKeypair) -> Result<PubkeyValidityProofData, JsValue> {
-        zk_elgamal_proof_program::build_pubkey_validity_proof_data(&keypair.inner)
-            .map(|inner| Self { inner })
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+        Self::build(keypair, None)
+    }
+
+    /// Creates a new public-key validity proof with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        keypair: &ElGamalKeypair,
+        transcript_context: &[u8],
+    ) -> Result<PubkeyValidityProofData, JsValue> {
+        Self::build(keypair, Some(transcript_context))
     }
 
     /// Returns the context data associated with the proof.
@@ -43,6 +54,17 @@ impl PubkeyValidityProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the public-key validity proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a pubkey validity proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -72,6 +94,24 @@ impl PubkeyValidityProofData {
     }
 }
 
+impl PubkeyValidityProofData {
+    fn build(
+        keypair: &ElGamalKeypair,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<PubkeyValidityProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(PubkeyValidityStatement {
+                pubkey: keypair.inner.pubkey(),
+            })
+            .witness(PubkeyValidityWitness {
+                keypair: &keypair.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a public-key validity proof.
 #[wasm_bindgen]
 pub struct PubkeyValidityProofContext {
@@ -122,6 +162,16 @@ mod tests {
         assert!(proof.verify().is_ok());
     }
 
+    #[wasm_bindgen_test]
+    fn test_pubkey_validity_proof_transcript_context() {
+        let keypair = ElGamalKeypair::new_rand();
+        let proof = PubkeyValidityProofData::new_with_context(&keypair, b"request-1").unwrap();
+
+        assert!(proof.verify_with_context(b"request-1").is_ok());
+        assert!(proof.verify_with_context(b"request-2").is_err());
+        assert!(proof.verify().is_err());
+    }
+
     #[wasm_bindgen_test]
     fn test_pubkey_validity_proof_bytes_roundtrip() {
         let keypair = ElGamalKeypair::new_rand();
@@ -2,7 +2,10 @@ use {
     crate::encryption::elgamal::{ElGamalCiphertext, ElGamalKeypair},
     js_sys::Uint8Array,
     solana_zk_elgamal_proof_interface::proof_data,
-    solana_zk_sdk::zk_elgamal_proof_program::{self, VerifyZkProof},
+    solana_zk_sdk::zk_elgamal_proof_program::{
+        builder::{ProofBuilder, ZeroCiphertextStatement, ZeroCiphertextWitness},
+        VerifyZkProof,
+    },
     wasm_bindgen::prelude::*,
 };
 
@@ -26,12 +29,18 @@ impl ZeroCiphertextProofData {
         keypair: &ElGamalKeypair,
         ciphertext: &ElGamalCiphertext,
     ) -> Result<ZeroCiphertextProofData, JsValue> {
-        zk_elgamal_proof_program::build_zero_ciphertext_proof_data(
-            &keypair.inner,
-            &ciphertext.inner,
-        )
-        .map(|inner| Self { inner })
-        .map_err(|e| JsValue::from_str(&e.to_string()))
+        Self::build(keypair, ciphertext, None)
+    }
+
+    /// Creates a new zero-ciphertext proof with `transcript_context`
+    /// bound into the proof.
+    #[wasm_bindgen(js_name = "newWithContext")]
+    pub fn new_with_context(
+        keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        transcript_context: &[u8],
+    ) -> Result<ZeroCiphertextProofData, JsValue> {
+        Self::build(keypair, ciphertext, Some(transcript_context))
     }
 
     /// Returns the context data associated with the proof.
@@ -49,6 +58,17 @@ impl ZeroCiphertextProofData {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
+    /// Verifies the zero-ciphertext proof with `transcript_context`
+    /// bound into the proof.
+    /// Throws an error if the proof is invalid or was generated with a different
+    /// transcript context.
+    #[wasm_bindgen(js_name = "verifyWithContext")]
+    pub fn verify_with_context(&self, transcript_context: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .verify_proof_with_statement_context(Some(transcript_context))
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Deserializes a zero-ciphertext proof from a byte slice.
     /// Throws an error if the bytes are invalid.
     #[wasm_bindgen(js_name = "fromBytes")]
@@ -78,6 +98,26 @@ impl ZeroCiphertextProofData {
     }
 }
 
+impl ZeroCiphertextProofData {
+    fn build(
+        keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        transcript_context: Option<&[u8]>,
+    ) -> Result<ZeroCiphertextProofData, JsValue> {
+        ProofBuilder::new(transcript_context)
+            .statement(ZeroCiphertextStatement {
+                pubkey: keypair.inner.pubkey(),
+                ciphertext: &ciphertext.inner,
+            })
+            .witness(ZeroCiphertextWitness {
+                keypair: &keypair.inner,
+            })
+            .build()
+            .map(|inner| Self { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
+
 /// The context data needed to verify a zero-ciphertext proof.
 #[wasm_bindgen]
 pub struct ZeroCiphertextProofContext {
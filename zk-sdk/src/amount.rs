@@ -0,0 +1,120 @@
+//! Amounts with a statically known bit length.
+//!
+//! Range proofs certify that an amount lies in the range `[0, 2^n)` for a bit length `n` that is
+//! fixed by the protocol. An [`Amount<BITS>`] can only be constructed from values in the range
+//! `[0, 2^BITS)`, so an amount that exceeds the provable range is rejected when the amount is
+//! constructed rather than producing a proof that verifies but violates the assumptions of the
+//! protocol.
+//!
+//! Amounts convert into `Scalar`, so they are accepted by the encryption functions and the proof
+//! constructors that take an amount `T: Into<Scalar>`. Batched range proofs can be generated for
+//! amounts of different bit lengths with
+//! [`BatchedRangeProofData::build_from_amounts`](crate::zk_elgamal_proof_program::BatchedRangeProofData::build_from_amounts).
+
+use {crate::errors::AmountError, curve25519_dalek::scalar::Scalar};
+
+/// An amount in the range `[0, 2^BITS)`.
+///
+/// `BITS` must be in the range `[1, 64]`. Constructing an amount with any other bit length fails
+/// to compile.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount<const BITS: u8>(u64);
+
+impl<const BITS: u8> Amount<BITS> {
+    const VALID_BIT_LENGTH: () = assert!(
+        BITS > 0 && BITS <= 64,
+        "amount bit length must be in the range [1, 64]"
+    );
+
+    /// The bit length of the amount.
+    pub const BIT_LENGTH: usize = BITS as usize;
+
+    /// The largest value of the amount.
+    pub const MAX: u64 = u64::MAX >> (u64::BITS - BITS as u32);
+
+    /// Creates an amount, returning an error if `value` does not fit in `BITS` bits.
+    pub fn new(value: u64) -> Result<Self, AmountError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_BIT_LENGTH;
+
+        if value > Self::MAX {
+            return Err(AmountError::ExceedsBitLength);
+        }
+        Ok(Self(value))
+    }
+
+    /// Returns the value of the amount.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<const BITS: u8> TryFrom<u64> for Amount<BITS> {
+    type Error = AmountError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const BITS: u8> From<Amount<BITS>> for u64 {
+    fn from(amount: Amount<BITS>) -> Self {
+        amount.0
+    }
+}
+
+impl<const BITS: u8> From<Amount<BITS>> for Scalar {
+    fn from(amount: Amount<BITS>) -> Self {
+        Scalar::from(amount.0)
+    }
+}
+
+/// An amount together with the bit length of the range that it lies in.
+///
+/// This trait is implemented by [`Amount<BITS>`] and allows amounts of different bit lengths to be
+/// combined in a single batched range proof.
+pub trait BoundedAmount {
+    /// The value of the amount.
+    fn value(&self) -> u64;
+
+    /// The bit length of the range that the amount lies in.
+    fn bit_length(&self) -> usize;
+}
+
+impl<const BITS: u8> BoundedAmount for Amount<BITS> {
+    fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn bit_length(&self) -> usize {
+        Self::BIT_LENGTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_bounds() {
+        assert_eq!(Amount::<16>::MAX, u16::MAX as u64);
+        assert_eq!(Amount::<48>::MAX, (1 << 48) - 1);
+        assert_eq!(Amount::<64>::MAX, u64::MAX);
+
+        assert_eq!(Amount::<16>::new(u16::MAX as u64).unwrap().get(), 65535);
+        assert_eq!(
+            Amount::<16>::new(1 << 16).unwrap_err(),
+            AmountError::ExceedsBitLength
+        );
+        assert_eq!(
+            Amount::<48>::try_from(1 << 48).unwrap_err(),
+            AmountError::ExceedsBitLength
+        );
+        assert!(Amount::<64>::try_from(u64::MAX).is_ok());
+
+        let amount = Amount::<32>::new(55).unwrap();
+        assert_eq!(u64::from(amount), 55);
+        assert_eq!(Scalar::from(amount), Scalar::from(55_u64));
+        assert_eq!(amount.bit_length(), 32);
+    }
+}
@@ -0,0 +1,365 @@
+//! ASCII-armored text encoding of keys and proof data.
+//!
+//! An armored value is a block of printable text that can be pasted into tickets or emails and
+//! decoded back into the original value. The format follows the OpenPGP armor of RFC 4880:
+//!
+//! ```text
+//! -----BEGIN SOLANA ZK ELGAMAL PUBKEY-----
+//! <base64 encoding of the value, wrapped at 64 columns>
+//! =<base64 encoding of the CRC-24 checksum of the value>
+//! -----END SOLANA ZK ELGAMAL PUBKEY-----
+//! ```
+//!
+//! The type tag in the header and footer identifies the kind of value, so a pubkey cannot be
+//! mistaken for a secret key or a proof. Surrounding whitespace and blank lines are ignored when
+//! decoding, and line endings may be either `\n` or `\r\n`.
+
+use {
+    crate::{
+        encryption::{
+            auth_encryption::AeKey,
+            elgamal::{ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey},
+        },
+        errors::ArmorError,
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_zk_elgamal_proof_interface::proof_data::*,
+    zeroize::Zeroizing,
+};
+
+const BEGIN_PREFIX: &str = "-----BEGIN SOLANA ZK ";
+const END_PREFIX: &str = "-----END SOLANA ZK ";
+const DASHES: &str = "-----";
+
+/// Number of base64 characters in each line of the armored body.
+const LINE_WIDTH: usize = 64;
+
+const CRC24_INIT: u32 = 0xB7_04CE;
+const CRC24_POLY: u32 = 0x186_4CFB;
+
+/// Types that can be encoded as ASCII-armored text.
+pub trait Armor: Sized {
+    /// The type tag that appears in the armor header and footer.
+    const ARMOR_TYPE_TAG: &'static str;
+
+    /// The byte encoding of the value that is placed in the armored body.
+    fn to_armor_bytes(&self) -> Zeroizing<Vec<u8>>;
+
+    /// Decodes a value from the bytes of an armored body.
+    fn from_armor_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// Encodes the value as ASCII-armored text.
+    fn to_armored_string(&self) -> Zeroizing<String> {
+        armor(Self::ARMOR_TYPE_TAG, &self.to_armor_bytes())
+    }
+
+    /// Decodes a value from ASCII-armored text.
+    ///
+    /// Returns an error if the armor is malformed, the checksum does not match, or the type tag
+    /// is not [`Self::ARMOR_TYPE_TAG`].
+    fn from_armored_str(text: &str) -> Result<Self, ArmorError> {
+        let (type_tag, bytes) = dearmor(text)?;
+        if type_tag != Self::ARMOR_TYPE_TAG {
+            return Err(ArmorError::UnexpectedTypeTag(type_tag));
+        }
+        Self::from_armor_bytes(&bytes).ok_or(ArmorError::InvalidData)
+    }
+}
+
+/// Encodes `bytes` as ASCII-armored text with the given type tag.
+pub fn armor(type_tag: &str, bytes: &[u8]) -> Zeroizing<String> {
+    let body = Zeroizing::new(BASE64_STANDARD.encode(bytes));
+    let checksum = BASE64_STANDARD.encode(&crc24(bytes).to_be_bytes()[1..]);
+
+    let mut text = Zeroizing::new(String::new());
+    text.push_str(BEGIN_PREFIX);
+    text.push_str(type_tag);
+    text.push_str(DASHES);
+    text.push('\n');
+    // the body is ASCII, so splitting it by bytes never splits a character
+    for line in body.as_bytes().chunks(LINE_WIDTH) {
+        text.push_str(std::str::from_utf8(line).unwrap());
+        text.push('\n');
+    }
+    text.push('=');
+    text.push_str(&checksum);
+    text.push('\n');
+    text.push_str(END_PREFIX);
+    text.push_str(type_tag);
+    text.push_str(DASHES);
+    text.push('\n');
+    text
+}
+
+/// Decodes ASCII-armored text into its type tag and body bytes.
+///
+/// Returns an error if the armor is malformed or the checksum does not match the body.
+pub fn dearmor(text: &str) -> Result<(String, Zeroizing<Vec<u8>>), ArmorError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    let type_tag = lines
+        .next()
+        .and_then(|line| line.strip_prefix(BEGIN_PREFIX))
+        .and_then(|line| line.strip_suffix(DASHES))
+        .ok_or(ArmorError::InvalidHeader)?;
+
+    // base64 lines are a multiple of four characters long, so padding never starts a line and
+    // the first line that starts with `=` is the checksum
+    let mut body = Zeroizing::new(String::new());
+    let checksum = loop {
+        let line = lines.next().ok_or(ArmorError::MissingChecksum)?;
+        if let Some(checksum) = line.strip_prefix('=') {
+            break checksum;
+        }
+        if line.starts_with(DASHES) {
+            return Err(ArmorError::MissingChecksum);
+        }
+        body.push_str(line);
+    };
+
+    let footer = lines
+        .next()
+        .and_then(|line| line.strip_prefix(END_PREFIX))
+        .and_then(|line| line.strip_suffix(DASHES));
+    if footer != Some(type_tag) || lines.next().is_some() {
+        return Err(ArmorError::InvalidFooter);
+    }
+
+    let bytes = Zeroizing::new(
+        BASE64_STANDARD
+            .decode(body.as_bytes())
+            .map_err(|_| ArmorError::InvalidBase64)?,
+    );
+    let checksum = BASE64_STANDARD
+        .decode(checksum)
+        .map_err(|_| ArmorError::InvalidBase64)?;
+    if checksum != crc24(&bytes).to_be_bytes()[1..] {
+        return Err(ArmorError::ChecksumMismatch);
+    }
+
+    Ok((type_tag.to_string(), bytes))
+}
+
+/// The CRC-24 checksum of RFC 4880.
+fn crc24(bytes: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    for byte in bytes {
+        crc ^= (*byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0xFF_FFFF
+}
+
+macro_rules! impl_armor_for_key {
+    (TYPE = $type:ident, TYPE_TAG = $type_tag:expr) => {
+        impl Armor for $type {
+            const ARMOR_TYPE_TAG: &'static str = $type_tag;
+
+            fn to_armor_bytes(&self) -> Zeroizing<Vec<u8>> {
+                let bytes = Zeroizing::new(<[u8; $type::LEN]>::from(self));
+                Zeroizing::new(bytes.to_vec())
+            }
+
+            fn from_armor_bytes(bytes: &[u8]) -> Option<Self> {
+                $type::try_from(bytes).ok()
+            }
+        }
+    };
+}
+
+impl_armor_for_key!(TYPE = ElGamalPubkey, TYPE_TAG = "ELGAMAL PUBKEY");
+impl_armor_for_key!(TYPE = ElGamalSecretKey, TYPE_TAG = "ELGAMAL SECRET KEY");
+impl_armor_for_key!(TYPE = ElGamalKeypair, TYPE_TAG = "ELGAMAL KEYPAIR");
+impl_armor_for_key!(TYPE = AeKey, TYPE_TAG = "AE KEY");
+
+macro_rules! impl_armor_for_proof_data {
+    (TYPE = $type:ident, TYPE_TAG = $type_tag:expr) => {
+        impl Armor for $type {
+            const ARMOR_TYPE_TAG: &'static str = $type_tag;
+
+            fn to_armor_bytes(&self) -> Zeroizing<Vec<u8>> {
+                Zeroizing::new(bytemuck::bytes_of(self).to_vec())
+            }
+
+            fn from_armor_bytes(bytes: &[u8]) -> Option<Self> {
+                bytemuck::try_pod_read_unaligned(bytes).ok()
+            }
+        }
+    };
+}
+
+impl_armor_for_proof_data!(
+    TYPE = ZeroCiphertextProofData,
+    TYPE_TAG = "ZERO CIPHERTEXT PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = CiphertextCiphertextEqualityProofData,
+    TYPE_TAG = "CIPHERTEXT CIPHERTEXT EQUALITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = CiphertextCommitmentEqualityProofData,
+    TYPE_TAG = "CIPHERTEXT COMMITMENT EQUALITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = PubkeyValidityProofData,
+    TYPE_TAG = "PUBKEY VALIDITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = PercentageWithCapProofData,
+    TYPE_TAG = "PERCENTAGE WITH CAP PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = PercentageWithCapU128ProofData,
+    TYPE_TAG = "PERCENTAGE WITH CAP U128 PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = PercentageWithCapSplitProofData,
+    TYPE_TAG = "PERCENTAGE WITH CAP SPLIT PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = BatchedRangeProofU64Data,
+    TYPE_TAG = "BATCHED RANGE PROOF U64 DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = BatchedRangeProofU128Data,
+    TYPE_TAG = "BATCHED RANGE PROOF U128 DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = BatchedRangeProofU256Data,
+    TYPE_TAG = "BATCHED RANGE PROOF U256 DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = GroupedCiphertext2HandlesValidityProofData,
+    TYPE_TAG = "GROUPED CIPHERTEXT 2 HANDLES VALIDITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = GroupedCiphertext3HandlesValidityProofData,
+    TYPE_TAG = "GROUPED CIPHERTEXT 3 HANDLES VALIDITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = BatchedGroupedCiphertext2HandlesValidityProofData,
+    TYPE_TAG = "BATCHED GROUPED CIPHERTEXT 2 HANDLES VALIDITY PROOF DATA"
+);
+impl_armor_for_proof_data!(
+    TYPE = BatchedGroupedCiphertext3HandlesValidityProofData,
+    TYPE_TAG = "BATCHED GROUPED CIPHERTEXT 3 HANDLES VALIDITY PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = GroupedCiphertext2HandlesCiphertextEqualityProofData,
+    TYPE_TAG = "GROUPED CIPHERTEXT 2 HANDLES CIPHERTEXT EQUALITY PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = GroupedCiphertext3HandlesCiphertextEqualityProofData,
+    TYPE_TAG = "GROUPED CIPHERTEXT 3 HANDLES CIPHERTEXT EQUALITY PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = GroupedZeroCiphertext2HandlesProofData,
+    TYPE_TAG = "GROUPED ZERO CIPHERTEXT 2 HANDLES PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = GroupedZeroCiphertext3HandlesProofData,
+    TYPE_TAG = "GROUPED ZERO CIPHERTEXT 3 HANDLES PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = SplitConsistencyProofData,
+    TYPE_TAG = "SPLIT CONSISTENCY PROOF DATA"
+);
+#[cfg(feature = "unstable")]
+impl_armor_for_proof_data!(
+    TYPE = CiphertextCommitmentOpeningEqualityProofData,
+    TYPE_TAG = "CIPHERTEXT COMMITMENT OPENING EQUALITY PROOF DATA"
+);
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::zk_elgamal_proof_program::{build_pubkey_validity_proof_data, VerifyZkProof},
+    };
+
+    #[test]
+    fn test_crc24() {
+        // check value of the OpenPGP CRC-24
+        assert_eq!(crc24(b"123456789"), 0x21_CF02);
+    }
+
+    #[test]
+    fn test_armor_round_trip() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let armored = keypair.pubkey().to_armored_string();
+        assert!(armored.starts_with("-----BEGIN SOLANA ZK ELGAMAL PUBKEY-----\n"));
+        assert_eq!(
+            ElGamalPubkey::from_armored_str(&armored).unwrap(),
+            *keypair.pubkey()
+        );
+
+        let armored = keypair.to_armored_string();
+        let decoded = ElGamalKeypair::from_armored_str(&armored).unwrap();
+        assert_eq!(decoded.pubkey(), keypair.pubkey());
+        assert_eq!(decoded.secret(), keypair.secret());
+
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+        let armored = proof_data.to_armored_string();
+        assert!(armored.lines().all(|line| line.len() <= LINE_WIDTH));
+
+        // text pasted into an email may pick up indentation and carriage returns
+        let pasted = armored
+            .lines()
+            .map(|line| format!("  {line}\r\n"))
+            .collect::<String>();
+        let decoded = PubkeyValidityProofData::from_armored_str(&pasted).unwrap();
+        assert_eq!(decoded, proof_data);
+        assert!(decoded.verify_proof().is_ok());
+    }
+
+    #[test]
+    fn test_armor_errors() {
+        let keypair = ElGamalKeypair::new_rand();
+        let armored = keypair.pubkey().to_armored_string();
+
+        // the type tag must match the decoded type
+        assert_eq!(
+            ElGamalSecretKey::from_armored_str(&armored).unwrap_err(),
+            ArmorError::UnexpectedTypeTag("ELGAMAL PUBKEY".to_string())
+        );
+
+        // a modified body fails the checksum
+        let body_start = armored.find('\n').unwrap() + 1;
+        let mut tampered = armored.to_string();
+        let replacement = if &tampered[body_start..body_start + 1] == "A" {
+            "B"
+        } else {
+            "A"
+        };
+        tampered.replace_range(body_start..body_start + 1, replacement);
+        assert_eq!(
+            ElGamalPubkey::from_armored_str(&tampered).unwrap_err(),
+            ArmorError::ChecksumMismatch
+        );
+
+        // the footer must match the header
+        let mismatched = armored.replace("END SOLANA ZK ELGAMAL PUBKEY", "END SOLANA ZK AE KEY");
+        assert_eq!(
+            ElGamalPubkey::from_armored_str(&mismatched).unwrap_err(),
+            ArmorError::InvalidFooter
+        );
+
+        assert_eq!(
+            ElGamalPubkey::from_armored_str("not armored").unwrap_err(),
+            ArmorError::InvalidHeader
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Decryption of grouped ElGamal ciphertexts by an auditor.
+//!
+//! By convention, the auditor of a grouped ElGamal ciphertext holds the last decrypt handle of the
+//! ciphertext. [`decrypt_as_auditor`] decrypts the auditor's view of a grouped ciphertext and
+//! cross-checks the handles of the ciphertext against its commitment before returning the amount.
+//!
+//! The handles of the other parties are checked only for consistency with the commitment: an
+//! honestly generated ciphertext either has an identity commitment and identity handles, or a
+//! non-identity commitment and non-identity handles. Whether a handle is encrypted under the
+//! public key of its party can only be established by a grouped ciphertext validity proof.
+
+use crate::{
+    encryption::{elgamal::ElGamalSecretKey, grouped_elgamal::GroupedElGamalCiphertext},
+    errors::AuditError,
+};
+
+/// Decrypts the amount of a grouped ElGamal ciphertext with the secret key of the auditor, which
+/// holds the last decrypt handle of the ciphertext.
+///
+/// The function returns an error if the ciphertext is not encrypted under an auditor public key,
+/// if any decrypt handle is inconsistent with the commitment, or if the auditor handle does not
+/// open the commitment to a positive 32-bit amount.
+///
+/// NOTE: This function is not constant time.
+pub fn decrypt_as_auditor<const N: usize>(
+    grouped_ciphertext: &GroupedElGamalCiphertext<N>,
+    auditor_secret: &ElGamalSecretKey,
+) -> Result<u64, AuditError> {
+    let auditor_index = N.checked_sub(1).ok_or(AuditError::MissingAuditorHandle)?;
+
+    for index in 0..N {
+        let ciphertext = grouped_ciphertext
+            .to_elgamal_ciphertext(index)
+            .map_err(|_| AuditError::InconsistentHandles)?;
+        if ciphertext.validate().is_err() {
+            return Err(if index == auditor_index {
+                AuditError::MissingAuditorHandle
+            } else {
+                AuditError::InconsistentHandles
+            });
+        }
+    }
+
+    grouped_ciphertext
+        .decrypt_u32(auditor_secret, auditor_index)
+        .map_err(|_| AuditError::MissingAuditorHandle)?
+        .ok_or(AuditError::UndecodableAmount)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::encryption::{
+            elgamal::{DecryptHandle, ElGamalKeypair},
+            grouped_elgamal::GroupedElGamal,
+        },
+    };
+
+    #[test]
+    fn test_decrypt_as_auditor() {
+        let source_keypair = ElGamalKeypair::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let amount = 55_u64;
+
+        let grouped_ciphertext = GroupedElGamal::encrypt(
+            [
+                source_keypair.pubkey(),
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+            ],
+            amount,
+        );
+
+        assert_eq!(
+            decrypt_as_auditor(&grouped_ciphertext, auditor_keypair.secret()),
+            Ok(amount)
+        );
+
+        // the secret key of another party does not open the auditor handle
+        assert_eq!(
+            decrypt_as_auditor(&grouped_ciphertext, source_keypair.secret()),
+            Err(AuditError::UndecodableAmount)
+        );
+
+        // a ciphertext that is not encrypted under an auditor public key
+        let mut unaudited_ciphertext = grouped_ciphertext;
+        unaudited_ciphertext.handles[2] = DecryptHandle::default();
+        assert_eq!(
+            decrypt_as_auditor(&unaudited_ciphertext, auditor_keypair.secret()),
+            Err(AuditError::MissingAuditorHandle)
+        );
+
+        // a ciphertext whose handle for another party is inconsistent with the commitment
+        let mut inconsistent_ciphertext = grouped_ciphertext;
+        inconsistent_ciphertext.handles[0] = DecryptHandle::default();
+        assert_eq!(
+            decrypt_as_auditor(&inconsistent_ciphertext, auditor_keypair.secret()),
+            Err(AuditError::InconsistentHandles)
+        );
+    }
+}
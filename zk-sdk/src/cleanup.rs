@@ -0,0 +1,344 @@
+//! Bulk cleanup of context state accounts.
+//!
+//! Every proof that is verified into a context state account locks up the rent-exempt balance of
+//! the account until the context state authority closes it. Operators that verify many proofs
+//! tend to accumulate context state accounts that are no longer referenced by any instruction.
+//!
+//! The helpers of this module reclaim the balances of these accounts in three steps:
+//!
+//! 1. Fetch the accounts of the proof program whose authority field matches the operator with
+//!    the `getProgramAccounts` RPC method and a `memcmp` filter at
+//!    [`CONTEXT_STATE_AUTHORITY_OFFSET`].
+//! 2. Decode the fetched accounts with [`ContextStateAccount::decode`] and select the ones to
+//!    close with a [`ContextStateFilter`].
+//! 3. Build the `CloseContextState` instructions with [`close_context_state_batches`], which
+//!    groups them into batches that each fit in a single transaction signed by the authority.
+//!
+//! The RPC client and the transactions themselves are outside the scope of this crate.
+
+use {
+    crate::errors::ClientError,
+    solana_address::Address,
+    solana_instruction::Instruction,
+    solana_zk_elgamal_proof_interface::{
+        instruction::{close_context_state, ContextStateInfo},
+        proof_data::*,
+        state::{ProofContextStateExpiry, ProofContextStateMeta},
+    },
+    std::mem::size_of,
+};
+
+/// The byte offset of the context state authority in a context state account.
+pub const CONTEXT_STATE_AUTHORITY_OFFSET: usize = 0;
+
+/// The byte offset of the proof type in a context state account.
+pub const CONTEXT_STATE_PROOF_TYPE_OFFSET: usize = 32;
+
+/// The maximum number of `CloseContextState` instructions that fit in a single transaction
+/// together with the signature of the context state authority.
+pub const MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION: usize = 20;
+
+/// A context state account as it is returned by an RPC node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContextStateAccount {
+    /// The address of the context state account
+    pub address: Address,
+    /// The lamports that are reclaimed by closing the account
+    pub lamports: u64,
+    /// The proof context authority that can close the account
+    pub context_state_authority: Address,
+    /// The proof type for the context data
+    pub proof_type: ProofType,
+    /// The last slot at which the context state can be used, if the context state expires
+    pub expiry_slot: Option<u64>,
+}
+
+impl ContextStateAccount {
+    /// Decodes a context state account from its address, lamports, and data.
+    pub fn decode(address: Address, lamports: u64, data: &[u8]) -> Result<Self, ClientError> {
+        let meta = ProofContextStateMeta::try_from_bytes(data)
+            .map_err(|_| ClientError::AccountDeserialization)?;
+        let proof_type = ProofType::try_from(meta.proof_type)
+            .map_err(|_| ClientError::AccountDeserialization)?;
+        if proof_type == ProofType::Uninitialized {
+            return Err(ClientError::AccountDeserialization);
+        }
+
+        let state_len = size_of::<ProofContextStateMeta>() + proof_context_len(proof_type);
+        let expiry_slot = match data.get(state_len..) {
+            Some(expiry) if expiry.len() == size_of::<ProofContextStateExpiry>() => {
+                let expiry: &ProofContextStateExpiry = bytemuck::try_from_bytes(expiry)
+                    .map_err(|_| ClientError::AccountDeserialization)?;
+                Some(u64::from(expiry.expiry_slot))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            address,
+            lamports,
+            context_state_authority: meta.context_state_authority,
+            proof_type,
+            expiry_slot,
+        })
+    }
+
+    /// Returns `true` if the context state can no longer be used at `current_slot`.
+    pub fn is_expired(&self, current_slot: u64) -> bool {
+        self.expiry_slot
+            .is_some_and(|expiry_slot| current_slot > expiry_slot)
+    }
+}
+
+/// The criteria for selecting the context state accounts to close.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextStateFilter {
+    /// The context state authority that closes the accounts
+    pub context_state_authority: Address,
+    /// The proof types of the accounts to close, or all proof types if empty
+    pub proof_types: Vec<ProofType>,
+    /// If set, only the accounts that have expired at this slot are closed
+    pub expired_at_slot: Option<u64>,
+}
+
+impl ContextStateFilter {
+    /// Creates a filter that selects every context state account of `context_state_authority`.
+    pub fn new(context_state_authority: Address) -> Self {
+        Self {
+            context_state_authority,
+            proof_types: vec![],
+            expired_at_slot: None,
+        }
+    }
+
+    /// Restricts the filter to the context state accounts of `proof_types`.
+    pub fn with_proof_types(mut self, proof_types: &[ProofType]) -> Self {
+        self.proof_types = proof_types.to_vec();
+        self
+    }
+
+    /// Restricts the filter to the context state accounts that have expired at `current_slot`.
+    pub fn with_expired_at_slot(mut self, current_slot: u64) -> Self {
+        self.expired_at_slot = Some(current_slot);
+        self
+    }
+
+    /// Returns `true` if `account` matches the filter.
+    pub fn matches(&self, account: &ContextStateAccount) -> bool {
+        account.context_state_authority == self.context_state_authority
+            && (self.proof_types.is_empty() || self.proof_types.contains(&account.proof_type))
+            && self
+                .expired_at_slot
+                .is_none_or(|current_slot| account.is_expired(current_slot))
+    }
+
+    /// Selects the accounts that match the filter.
+    pub fn select<'a>(&self, accounts: &'a [ContextStateAccount]) -> Vec<&'a ContextStateAccount> {
+        accounts
+            .iter()
+            .filter(|account| self.matches(account))
+            .collect()
+    }
+}
+
+/// Builds the `CloseContextState` instructions for `accounts`, grouped into batches of at most
+/// `MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION` instructions.
+///
+/// Each batch fits in a single transaction that is signed by the context state authority of the
+/// accounts. The lamports of all accounts are sent to `destination_account`.
+pub fn close_context_state_batches(
+    accounts: &[&ContextStateAccount],
+    destination_account: &Address,
+) -> Vec<Vec<Instruction>> {
+    accounts
+        .chunks(MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|account| {
+                    close_context_state(
+                        ContextStateInfo {
+                            context_state_account: &account.address,
+                            context_state_authority: &account.context_state_authority,
+                        },
+                        destination_account,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The byte length of the proof context of `proof_type`.
+fn proof_context_len(proof_type: ProofType) -> usize {
+    match proof_type {
+        ProofType::Uninitialized => 0,
+        ProofType::ZeroCiphertext => size_of::<ZeroCiphertextProofContext>(),
+        ProofType::CiphertextCiphertextEquality => {
+            size_of::<CiphertextCiphertextEqualityProofContext>()
+        }
+        ProofType::CiphertextCommitmentEquality => {
+            size_of::<CiphertextCommitmentEqualityProofContext>()
+        }
+        ProofType::PubkeyValidity => size_of::<PubkeyValidityProofContext>(),
+        ProofType::PercentageWithCap => size_of::<PercentageWithCapProofContext>(),
+        ProofType::BatchedRangeProofU64
+        | ProofType::BatchedRangeProofU128
+        | ProofType::BatchedRangeProofU256 => size_of::<BatchedRangeProofContext>(),
+        ProofType::GroupedCiphertext2HandlesValidity => {
+            size_of::<GroupedCiphertext2HandlesValidityProofContext>()
+        }
+        ProofType::BatchedGroupedCiphertext2HandlesValidity => {
+            size_of::<BatchedGroupedCiphertext2HandlesValidityProofContext>()
+        }
+        ProofType::GroupedCiphertext3HandlesValidity => {
+            size_of::<GroupedCiphertext3HandlesValidityProofContext>()
+        }
+        ProofType::BatchedGroupedCiphertext3HandlesValidity => {
+            size_of::<BatchedGroupedCiphertext3HandlesValidityProofContext>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapSplit => size_of::<PercentageWithCapSplitProofContext>(),
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapU128 => size_of::<PercentageWithCapU128ProofContext>(),
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext2HandlesCiphertextEquality => {
+            size_of::<GroupedCiphertext2HandlesCiphertextEqualityProofContext>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext3HandlesCiphertextEquality => {
+            size_of::<GroupedCiphertext3HandlesCiphertextEqualityProofContext>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext2Handles => {
+            size_of::<GroupedZeroCiphertext2HandlesProofContext>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext3Handles => {
+            size_of::<GroupedZeroCiphertext3HandlesProofContext>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::SplitConsistency => size_of::<SplitConsistencyProofContext>(),
+        #[cfg(feature = "unstable")]
+        ProofType::CiphertextCommitmentOpeningEquality => {
+            size_of::<CiphertextCommitmentOpeningEqualityProofContext>()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair,
+            zk_elgamal_proof_program::{
+                build_pubkey_validity_proof_data, build_zero_ciphertext_proof_data,
+            },
+        },
+        bytemuck::Zeroable,
+        solana_zk_elgamal_proof_interface::state::ProofContextState,
+    };
+
+    #[test]
+    fn test_context_state_cleanup() {
+        let authority = Address::new_unique();
+        let other_authority = Address::new_unique();
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_context = build_pubkey_validity_proof_data(&keypair).unwrap().context;
+        let zero_ciphertext_context =
+            build_zero_ciphertext_proof_data(&keypair, &keypair.pubkey().encrypt(0_u64))
+                .unwrap()
+                .context;
+
+        let encode = |authority: &Address, expiry_slot: Option<u64>| {
+            ProofContextState::encode_with_expiry(
+                authority,
+                ProofType::PubkeyValidity,
+                &proof_context,
+                expiry_slot,
+            )
+        };
+        let accounts = [
+            encode(&authority, None),
+            encode(&authority, Some(100)),
+            encode(&other_authority, Some(100)),
+            ProofContextState::encode(
+                &authority,
+                ProofType::ZeroCiphertext,
+                &zero_ciphertext_context,
+            ),
+        ]
+        .iter()
+        .map(|data| ContextStateAccount::decode(Address::new_unique(), 1_000, data).unwrap())
+        .collect::<Vec<_>>();
+
+        assert_eq!(accounts[0].expiry_slot, None);
+        assert_eq!(accounts[1].expiry_slot, Some(100));
+        assert_eq!(accounts[3].proof_type, ProofType::ZeroCiphertext);
+
+        let filter = ContextStateFilter::new(authority);
+        assert_eq!(filter.select(&accounts).len(), 3);
+
+        let filter = filter.with_proof_types(&[ProofType::PubkeyValidity]);
+        assert_eq!(filter.select(&accounts).len(), 2);
+
+        // only the accounts that have expired are selected
+        assert_eq!(
+            filter
+                .clone()
+                .with_expired_at_slot(100)
+                .select(&accounts)
+                .len(),
+            0
+        );
+        let selected = filter.with_expired_at_slot(101).select(&accounts);
+        assert_eq!(selected, vec![&accounts[1]]);
+
+        let destination = Address::new_unique();
+        let batches = close_context_state_batches(&selected, &destination);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0][0],
+            close_context_state(
+                ContextStateInfo {
+                    context_state_account: &accounts[1].address,
+                    context_state_authority: &authority,
+                },
+                &destination,
+            )
+        );
+    }
+
+    #[test]
+    fn test_close_context_state_batches() {
+        let data = ProofContextState::encode(
+            &Address::new_unique(),
+            ProofType::PubkeyValidity,
+            &PubkeyValidityProofContext::zeroed(),
+        );
+        let accounts = (0..MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION * 2 + 1)
+            .map(|_| ContextStateAccount::decode(Address::new_unique(), 1_000, &data).unwrap())
+            .collect::<Vec<_>>();
+        let accounts = accounts.iter().collect::<Vec<_>>();
+
+        let batches = close_context_state_batches(&accounts, &Address::new_unique());
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![
+                MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION,
+                MAX_CLOSE_INSTRUCTIONS_PER_TRANSACTION,
+                1
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_account() {
+        assert!(ContextStateAccount::decode(Address::new_unique(), 0, &[0; 16]).is_err());
+
+        // an uninitialized context state account is not closed
+        let data = [0; size_of::<ProofContextStateMeta>()];
+        assert!(ContextStateAccount::decode(Address::new_unique(), 0, &data).is_err());
+    }
+}
@@ -0,0 +1,2135 @@
+//! High-level helpers for the confidential balance operations of a token account.
+//!
+//! A confidential account holds an encrypted available balance, a copy of the available balance
+//! that is encrypted under the owner's authenticated encryption key, and a pending balance that
+//! is split into low and high bits together with a counter of the credits that it has received.
+//! [`ConfidentialAccountState`] holds these components as they are returned by an RPC node.
+//!
+//! Given the account state and the owner's keys, the functions of this module compute the new
+//! encrypted balances for the balance operations and generate every zero-knowledge proof
+//! that the operations require:
+//!
+//! * [`ConfidentialAccountState::deposit`] credits a public amount to the pending balance.
+//! * [`ConfidentialAccountState::apply_pending_balance`] merges the pending balance into the
+//!   available balance.
+//! * [`ConfidentialAccountState::withdraw`] debits a public amount from the available balance.
+//! * [`ConfidentialAccountState::transfer`] debits an encrypted amount from the available balance
+//!   and encrypts it for a destination and an auditor.
+//! * [`ConfidentialAccountState::burn`] debits an encrypted amount from the available balance and
+//!   encrypts it for the supply and an auditor.
+//!
+//! [`EncryptedSupplyState::mint`] credits an encrypted amount to a destination and adds the same
+//! amount to the encrypted supply of the mint.
+//!
+//! Clients that do not hold a full [`ConfidentialAccountState`] can compute the values of an apply
+//! pending balance instruction directly with [`compute_apply_pending_balance`], which also checks
+//! that the pending balance is consistent with the credit counter that is put in the instruction.
+//!
+//! Deposits and mints that credit an encrypted amount, rather than a public amount, use
+//! [`encrypt_deposit_amount`] to encrypt the amount under the owner and auditor public keys and to
+//! generate the matching ciphertext validity proof in one call.
+//!
+//! The proofs of a withdraw or a transfer are too large to fit in a single transaction together
+//! with the token instruction. They are verified ahead of time by proof instructions that store
+//! their contexts in context state accounts, which are then referenced by the token instruction
+//! and closed afterwards. [`ProofInstructionSet`] holds the verification instructions, the close
+//! instructions, and the byte lengths that the context state accounts must be allocated with. The
+//! token program instructions themselves are outside the scope of this crate.
+//!
+//! The proofs of an operation can be bound to the token accounts that it involves by generating
+//! them with a [`StatementContext`], for instance with
+//! [`ConfidentialAccountState::transfer_in_context`]. The proofs then verify only with the same
+//! context, for instance with [`TransferData::verify_all_in_context`], so they cannot be replayed
+//! for another account whose encrypted balances happen to coincide. The proofs of an operation
+//! are moreover bound to their positions in the operation through a [`ProtocolTranscript`], so a
+//! proof of one operation never verifies in place of another proof. The proof program verifies
+//! proofs without a statement context, so these proofs are returned without verification
+//! instructions and must be verified by the program that consumes them.
+
+use {
+    crate::{
+        encryption::{
+            auth_encryption::{AeCiphertext, AeKey},
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+            grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
+            pedersen::{Pedersen, PedersenOpening},
+        },
+        errors::ClientError,
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBuffer},
+        transcript::{ProtocolTranscript, StatementContext},
+        zk_elgamal_proof_program::{
+            build_batched_grouped_ciphertext_2_handles_validity_proof_data,
+            build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context,
+            build_batched_range_proof_u128_data_with_statement_context,
+            build_batched_range_proof_u64_data_with_statement_context,
+            build_ciphertext_commitment_equality_proof_data_with_statement_context,
+            errors::ProofVerificationError, AppendVerificationTerms, CombinedProofVerifier,
+        },
+    },
+    bytemuck::Pod,
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::Identity},
+    solana_address::Address,
+    solana_instruction::Instruction,
+    solana_zk_elgamal_proof_interface::{
+        instruction::{close_context_state, ContextStateInfo, ProofInstruction},
+        proof_data::{
+            BatchedGroupedCiphertext2HandlesValidityProofData,
+            BatchedGroupedCiphertext3HandlesValidityProofData, BatchedRangeProofU128Data,
+            BatchedRangeProofU64Data, CiphertextCommitmentEqualityProofData, ZkProofData,
+        },
+        state::ProofContextState,
+    },
+    solana_zk_sdk_pod::encryption::{
+        auth_encryption::PodAeCiphertext, elgamal::PodElGamalCiphertext,
+    },
+};
+
+/// Bit length of the low bits of a pending balance credit.
+pub const PENDING_BALANCE_LO_BIT_LENGTH: u32 = 16;
+
+/// Bit length of the high bits of a pending balance credit.
+pub const PENDING_BALANCE_HI_BIT_LENGTH: u32 = 32;
+
+/// Bit length of the low bits of a transfer amount.
+pub const TRANSFER_AMOUNT_LO_BIT_LENGTH: u32 = 16;
+
+/// Bit length of the high bits of a transfer amount.
+pub const TRANSFER_AMOUNT_HI_BIT_LENGTH: u32 = 32;
+
+/// Bit length of the padding commitment of a transfer range proof.
+const TRANSFER_RANGE_PADDING_BIT_LENGTH: u32 = 16;
+
+/// Bit length of an available balance.
+const AVAILABLE_BALANCE_BIT_LENGTH: u32 = 64;
+
+/// The maximum amount that can be deposited or transferred in a single operation.
+pub const MAXIMUM_DEPOSIT_TRANSFER_AMOUNT: u64 =
+    (1 << (TRANSFER_AMOUNT_LO_BIT_LENGTH + TRANSFER_AMOUNT_HI_BIT_LENGTH)) - 1;
+
+/// The maximum number of multiscalar multiplication terms in the proofs of a withdraw.
+const WITHDRAW_VERIFICATION_TERMS: usize =
+    CiphertextCommitmentEqualityProofData::MAX_VERIFICATION_TERMS
+        + BatchedRangeProofU64Data::MAX_VERIFICATION_TERMS;
+
+/// The maximum number of multiscalar multiplication terms in the proofs of a transfer, a burn, or
+/// a mint.
+const GROUPED_AMOUNT_VERIFICATION_TERMS: usize =
+    CiphertextCommitmentEqualityProofData::MAX_VERIFICATION_TERMS
+        + BatchedGroupedCiphertext3HandlesValidityProofData::MAX_VERIFICATION_TERMS
+        + BatchedRangeProofU128Data::MAX_VERIFICATION_TERMS;
+
+/// The labels of the proofs of a withdraw, in the order in which the proofs are generated.
+const WITHDRAW_PROOF_LABELS: [&[u8]; 2] = [
+    b"ciphertext-commitment-equality",
+    b"batched-range-proof-u64",
+];
+
+/// The labels of the proofs of a transfer, a burn, or a mint, in the order in which the proofs
+/// are generated.
+const GROUPED_AMOUNT_PROOF_LABELS: [&[u8]; 3] = [
+    b"ciphertext-commitment-equality",
+    b"batched-grouped-ciphertext-3-handles-validity",
+    b"batched-range-proof-u128",
+];
+
+/// The confidential balance state of a token account.
+#[derive(Clone, Debug)]
+pub struct ConfidentialAccountState {
+    /// The available balance encrypted under the owner's ElGamal public key
+    pub available_balance: ElGamalCiphertext,
+    /// The available balance encrypted under the owner's authenticated encryption key
+    pub decryptable_available_balance: AeCiphertext,
+    /// The low bits of the pending balance encrypted under the owner's ElGamal public key
+    pub pending_balance_lo: ElGamalCiphertext,
+    /// The high bits of the pending balance encrypted under the owner's ElGamal public key
+    pub pending_balance_hi: ElGamalCiphertext,
+    /// The number of credits to the pending balance since it was last applied
+    pub pending_balance_credit_counter: u64,
+}
+
+/// The values that are carried by an apply pending balance instruction.
+#[derive(Clone, Debug)]
+pub struct ApplyPendingBalanceInstructionData {
+    /// The credit counter that the token program checks against the account
+    pub expected_pending_balance_credit_counter: u64,
+    /// The new available balance encrypted under the owner's authenticated encryption key
+    pub new_decryptable_available_balance: AeCiphertext,
+}
+
+/// The new balances of an account after its pending balance is applied.
+#[derive(Clone, Debug)]
+pub struct ApplyPendingBalanceData {
+    /// The credit counter that the token program checks against the account
+    pub expected_pending_balance_credit_counter: u64,
+    /// The new available balance encrypted under the owner's authenticated encryption key
+    pub new_decryptable_available_balance: AeCiphertext,
+    /// The account state after the pending balance is applied
+    pub new_state: ConfidentialAccountState,
+}
+
+/// The new balances and proofs of a withdraw.
+#[derive(Clone, Debug)]
+pub struct WithdrawData {
+    /// The new available balance encrypted under the owner's authenticated encryption key
+    pub new_decryptable_available_balance: AeCiphertext,
+    /// The account state after the withdraw
+    pub new_state: ConfidentialAccountState,
+    /// Proof that the new available balance ciphertext and commitment hold the same amount
+    pub equality_proof_data: CiphertextCommitmentEqualityProofData,
+    /// Proof that the new available balance is non-negative
+    pub range_proof_data: BatchedRangeProofU64Data,
+    /// The proof verification and context state instructions, or `None` if the proofs are bound
+    /// to a statement context
+    pub instructions: Option<ProofInstructionSet>,
+}
+
+/// The new balances, transfer amount ciphertexts, and proofs of a transfer.
+#[derive(Clone, Debug)]
+pub struct TransferData {
+    /// The low bits of the transfer amount encrypted for the source, destination, and auditor
+    pub transfer_amount_lo: GroupedElGamalCiphertext<3>,
+    /// The high bits of the transfer amount encrypted for the source, destination, and auditor
+    pub transfer_amount_hi: GroupedElGamalCiphertext<3>,
+    /// The new available balance encrypted under the owner's authenticated encryption key
+    pub new_decryptable_available_balance: AeCiphertext,
+    /// The source account state after the transfer
+    pub new_state: ConfidentialAccountState,
+    /// Proof that the new available balance ciphertext and commitment hold the same amount
+    pub equality_proof_data: CiphertextCommitmentEqualityProofData,
+    /// Proof that the transfer amount ciphertexts are well-formed
+    pub ciphertext_validity_proof_data: BatchedGroupedCiphertext3HandlesValidityProofData,
+    /// Proof that the new available balance and the transfer amount are in range
+    pub range_proof_data: BatchedRangeProofU128Data,
+    /// The proof verification and context state instructions, or `None` if the proofs are bound
+    /// to a statement context
+    pub instructions: Option<ProofInstructionSet>,
+}
+
+/// The encrypted amount and ciphertext validity proof of a deposit or a mint.
+#[derive(Clone, Debug)]
+pub struct DepositData {
+    /// The low bits of the amount encrypted for the owner and the auditor
+    pub amount_lo: GroupedElGamalCiphertext<2>,
+    /// The high bits of the amount encrypted for the owner and the auditor
+    pub amount_hi: GroupedElGamalCiphertext<2>,
+    /// The Pedersen opening of the low bits of the amount
+    pub opening_lo: PedersenOpening,
+    /// The Pedersen opening of the high bits of the amount
+    pub opening_hi: PedersenOpening,
+    /// Proof that the amount ciphertexts are well-formed
+    pub ciphertext_validity_proof_data: BatchedGroupedCiphertext2HandlesValidityProofData,
+}
+
+impl WithdrawData {
+    /// Verifies the proofs of the withdraw with a single multiscalar multiplication.
+    ///
+    /// The verification terms of all proofs are collected in a single buffer on the stack, so
+    /// this is faster than verifying each proof on its own. This function is randomized. It uses
+    /// `OsRng` internally to generate the weights that combine the proofs.
+    pub fn verify_all(&self) -> Result<(), ProofVerificationError> {
+        self.verify_all_with_statement_contexts(&ProofStatementContexts(None))
+    }
+
+    /// Verifies the proofs of the withdraw under the token accounts that they were generated for.
+    ///
+    /// The proofs must have been generated by [`ConfidentialAccountState::withdraw_in_context`]
+    /// with `context`.
+    pub fn verify_all_in_context(
+        &self,
+        context: &StatementContext,
+    ) -> Result<(), ProofVerificationError> {
+        self.verify_all_with_statement_contexts(&ProofStatementContexts::new(
+            b"withdraw",
+            context,
+            WITHDRAW_PROOF_LABELS,
+        ))
+    }
+
+    fn verify_all_with_statement_contexts(
+        &self,
+        statement_contexts: &ProofStatementContexts<2>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut scalars = [Scalar::ZERO; WITHDRAW_VERIFICATION_TERMS];
+        let mut points = [RistrettoPoint::identity(); WITHDRAW_VERIFICATION_TERMS];
+        let mut verifier =
+            CombinedProofVerifier::new(MultiscalarMulBuffer::new(&mut scalars, &mut points));
+        verifier
+            .append_with_statement_context(&self.equality_proof_data, statement_contexts.get(0))?;
+        verifier
+            .append_with_statement_context(&self.range_proof_data, statement_contexts.get(1))?;
+        verifier.verify(&CpuMultiscalarMulBackend)
+    }
+}
+
+impl TransferData {
+    /// Verifies the proofs of the transfer with a single multiscalar multiplication.
+    ///
+    /// See [`WithdrawData::verify_all`].
+    pub fn verify_all(&self) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts(None),
+        )
+    }
+
+    /// Verifies the proofs of the transfer under the token accounts that they were generated for.
+    ///
+    /// See [`WithdrawData::verify_all_in_context`].
+    pub fn verify_all_in_context(
+        &self,
+        context: &StatementContext,
+    ) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts::new(b"transfer", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+}
+
+impl DepositData {
+    /// Creates the instruction that verifies the ciphertext validity proof.
+    ///
+    /// The proof fits in a single instruction, so a context state account is optional.
+    pub fn verify_instruction(&self, context_state_info: Option<ContextStateInfo>) -> Instruction {
+        ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity
+            .encode_verify_proof(context_state_info, &self.ciphertext_validity_proof_data)
+    }
+}
+
+/// The new balances, burn amount ciphertexts, and proofs of a burn.
+#[derive(Clone, Debug)]
+pub struct BurnProofBundle {
+    /// The low bits of the burn amount encrypted for the source, supply, and auditor
+    pub burn_amount_lo: GroupedElGamalCiphertext<3>,
+    /// The high bits of the burn amount encrypted for the source, supply, and auditor
+    pub burn_amount_hi: GroupedElGamalCiphertext<3>,
+    /// The new available balance encrypted under the owner's authenticated encryption key
+    pub new_decryptable_available_balance: AeCiphertext,
+    /// The source account state after the burn
+    pub new_state: ConfidentialAccountState,
+    /// Proof that the new available balance ciphertext and commitment hold the same amount
+    pub equality_proof_data: CiphertextCommitmentEqualityProofData,
+    /// Proof that the burn amount ciphertexts are well-formed
+    pub ciphertext_validity_proof_data: BatchedGroupedCiphertext3HandlesValidityProofData,
+    /// Proof that the new available balance and the burn amount are in range
+    pub range_proof_data: BatchedRangeProofU128Data,
+    /// The proof verification and context state instructions, or `None` if the proofs are bound
+    /// to a statement context
+    pub instructions: Option<ProofInstructionSet>,
+}
+
+impl BurnProofBundle {
+    /// Verifies the proofs of the burn with a single multiscalar multiplication.
+    ///
+    /// See [`WithdrawData::verify_all`].
+    pub fn verify_all(&self) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts(None),
+        )
+    }
+
+    /// Verifies the proofs of the burn under the token accounts that they were generated for.
+    ///
+    /// See [`WithdrawData::verify_all_in_context`].
+    pub fn verify_all_in_context(
+        &self,
+        context: &StatementContext,
+    ) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts::new(b"burn", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+}
+
+/// The encrypted supply of a mint.
+#[derive(Clone, Debug)]
+pub struct EncryptedSupplyState {
+    /// The supply encrypted under the supply ElGamal public key
+    pub current_supply: ElGamalCiphertext,
+    /// The supply encrypted under the supply authority's authenticated encryption key
+    pub decryptable_supply: AeCiphertext,
+}
+
+/// The new supply, mint amount ciphertexts, and proofs of a mint.
+#[derive(Clone, Debug)]
+pub struct MintProofBundle {
+    /// The low bits of the mint amount encrypted for the destination, supply, and auditor
+    pub mint_amount_lo: GroupedElGamalCiphertext<3>,
+    /// The high bits of the mint amount encrypted for the destination, supply, and auditor
+    pub mint_amount_hi: GroupedElGamalCiphertext<3>,
+    /// The new supply encrypted under the supply authority's authenticated encryption key
+    pub new_decryptable_supply: AeCiphertext,
+    /// The supply state after the mint
+    pub new_supply_state: EncryptedSupplyState,
+    /// Proof that the new supply ciphertext and commitment hold the same amount
+    pub equality_proof_data: CiphertextCommitmentEqualityProofData,
+    /// Proof that the mint amount ciphertexts are well-formed
+    pub ciphertext_validity_proof_data: BatchedGroupedCiphertext3HandlesValidityProofData,
+    /// Proof that the new supply and the mint amount are in range
+    pub range_proof_data: BatchedRangeProofU128Data,
+    /// The proof verification and context state instructions, or `None` if the proofs are bound
+    /// to a statement context
+    pub instructions: Option<ProofInstructionSet>,
+}
+
+impl MintProofBundle {
+    /// Verifies the proofs of the mint with a single multiscalar multiplication.
+    ///
+    /// See [`WithdrawData::verify_all`].
+    pub fn verify_all(&self) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts(None),
+        )
+    }
+
+    /// Verifies the proofs of the mint under the token accounts that they were generated for.
+    ///
+    /// See [`WithdrawData::verify_all_in_context`].
+    pub fn verify_all_in_context(
+        &self,
+        context: &StatementContext,
+    ) -> Result<(), ProofVerificationError> {
+        verify_grouped_amount_proofs(
+            &self.equality_proof_data,
+            &self.ciphertext_validity_proof_data,
+            &self.range_proof_data,
+            &ProofStatementContexts::new(b"mint", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+}
+
+/// The context state accounts of the proofs of a withdraw.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WithdrawContextStateAccounts<'a> {
+    pub equality_proof: ContextStateInfo<'a>,
+    pub range_proof: ContextStateInfo<'a>,
+}
+
+/// The context state accounts of the proofs of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransferContextStateAccounts<'a> {
+    pub equality_proof: ContextStateInfo<'a>,
+    pub ciphertext_validity_proof: ContextStateInfo<'a>,
+    pub range_proof: ContextStateInfo<'a>,
+}
+
+/// The context state accounts of the proofs of a burn, which are the same as those of a transfer.
+pub type BurnContextStateAccounts<'a> = TransferContextStateAccounts<'a>;
+
+/// The context state accounts of the proofs of a mint, which are the same as those of a transfer.
+pub type MintContextStateAccounts<'a> = TransferContextStateAccounts<'a>;
+
+/// The instructions that verify the proofs of an operation into context state accounts.
+///
+/// The context state accounts must be created with the byte lengths in `context_state_lens` and
+/// assigned to the proof program before the verification instructions are processed. The close
+/// instructions must be signed by the context state authorities.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProofInstructionSet {
+    /// The proof verification instructions, one for each proof
+    pub verify_instructions: Vec<Instruction>,
+    /// The instructions that close the context state accounts
+    pub close_instructions: Vec<Instruction>,
+    /// The byte lengths of the context state accounts, in the order of `verify_instructions`
+    pub context_state_lens: Vec<usize>,
+}
+
+impl ProofInstructionSet {
+    fn push<T, U>(
+        &mut self,
+        proof_instruction: ProofInstruction,
+        context_state_info: ContextStateInfo,
+        close_destination: &Address,
+        proof_data: &T,
+    ) where
+        T: Pod + ZkProofData<U>,
+        U: Pod,
+    {
+        self.verify_instructions
+            .push(proof_instruction.encode_verify_proof(Some(context_state_info), proof_data));
+        self.close_instructions
+            .push(close_context_state(context_state_info, close_destination));
+        self.context_state_lens
+            .push(size_of::<ProofContextState<U>>());
+    }
+}
+
+impl ConfidentialAccountState {
+    /// Decodes an account state from the pod representations that are returned by an RPC node.
+    pub fn from_pod(
+        available_balance: &PodElGamalCiphertext,
+        decryptable_available_balance: &PodAeCiphertext,
+        pending_balance_lo: &PodElGamalCiphertext,
+        pending_balance_hi: &PodElGamalCiphertext,
+        pending_balance_credit_counter: u64,
+    ) -> Result<Self, ClientError> {
+        Ok(Self {
+            available_balance: (*available_balance).try_into()?,
+            decryptable_available_balance: (*decryptable_available_balance)
+                .try_into()
+                .map_err(|_| ClientError::AccountDeserialization)?,
+            pending_balance_lo: (*pending_balance_lo).try_into()?,
+            pending_balance_hi: (*pending_balance_hi).try_into()?,
+            pending_balance_credit_counter,
+        })
+    }
+
+    /// Decrypts the available balance with the owner's authenticated encryption key.
+    pub fn decrypt_available_balance(&self, aes_key: &AeKey) -> Result<u64, ClientError> {
+        self.decryptable_available_balance
+            .decrypt(aes_key)
+            .ok_or(ClientError::DecryptionFailed)
+    }
+
+    /// Decrypts the pending balance with the owner's ElGamal keypair.
+    ///
+    /// Returns an error if the pending balance is inconsistent with the credit counter.
+    pub fn decrypt_pending_balance(&self, keypair: &ElGamalKeypair) -> Result<u64, ClientError> {
+        decrypt_pending_balance(
+            keypair,
+            &self.pending_balance_lo,
+            &self.pending_balance_hi,
+            self.pending_balance_credit_counter,
+        )
+    }
+
+    /// Computes the account state after a public amount is deposited into the pending balance.
+    ///
+    /// The token program credits the low and high bits of the amount to the pending balance in
+    /// the clear, so no proof is needed.
+    pub fn deposit(&self, amount: u64) -> Result<Self, ClientError> {
+        if amount > MAXIMUM_DEPOSIT_TRANSFER_AMOUNT {
+            return Err(ClientError::AmountTooLarge);
+        }
+        let (amount_lo, amount_hi) = split_amount(amount, PENDING_BALANCE_LO_BIT_LENGTH);
+
+        Ok(Self {
+            pending_balance_lo: self.pending_balance_lo.add_amount(amount_lo),
+            pending_balance_hi: self.pending_balance_hi.add_amount(amount_hi),
+            pending_balance_credit_counter: self
+                .pending_balance_credit_counter
+                .checked_add(1)
+                .ok_or(ClientError::BalanceOverflow)?,
+            ..self.clone()
+        })
+    }
+
+    /// Computes the new balances of the account after its pending balance is applied.
+    pub fn apply_pending_balance(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+    ) -> Result<ApplyPendingBalanceData, ClientError> {
+        let ApplyPendingBalanceInstructionData {
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+        } = compute_apply_pending_balance(
+            keypair,
+            aes_key,
+            &self.decryptable_available_balance,
+            &self.pending_balance_lo,
+            &self.pending_balance_hi,
+            self.pending_balance_credit_counter,
+            self.pending_balance_credit_counter,
+        )?;
+
+        let pending_balance_ciphertext = combine_lo_hi_ciphertexts(
+            &self.pending_balance_lo,
+            &self.pending_balance_hi,
+            PENDING_BALANCE_LO_BIT_LENGTH,
+        );
+        let zero_ciphertext = ElGamalCiphertext::default();
+
+        Ok(ApplyPendingBalanceData {
+            expected_pending_balance_credit_counter,
+            new_decryptable_available_balance,
+            new_state: Self {
+                available_balance: &self.available_balance + &pending_balance_ciphertext,
+                decryptable_available_balance: new_decryptable_available_balance,
+                pending_balance_lo: zero_ciphertext,
+                pending_balance_hi: zero_ciphertext,
+                pending_balance_credit_counter: 0,
+            },
+        })
+    }
+
+    /// Computes the new balances and generates the proofs of a withdraw.
+    ///
+    /// * `keypair` - The owner's ElGamal keypair
+    /// * `aes_key` - The owner's authenticated encryption key
+    /// * `amount` - The amount to withdraw
+    /// * `context_state_accounts` - The context state accounts of the proofs
+    /// * `close_destination` - The account that receives the lamports of the closed context states
+    pub fn withdraw(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        context_state_accounts: WithdrawContextStateAccounts,
+        close_destination: &Address,
+    ) -> Result<WithdrawData, ClientError> {
+        let mut withdraw = self.withdraw_with_statement_contexts(
+            keypair,
+            aes_key,
+            amount,
+            &ProofStatementContexts(None),
+        )?;
+
+        let mut instructions = ProofInstructionSet::default();
+        instructions.push(
+            ProofInstruction::VerifyCiphertextCommitmentEquality,
+            context_state_accounts.equality_proof,
+            close_destination,
+            &withdraw.equality_proof_data,
+        );
+        instructions.push(
+            ProofInstruction::VerifyBatchedRangeProofU64,
+            context_state_accounts.range_proof,
+            close_destination,
+            &withdraw.range_proof_data,
+        );
+        withdraw.instructions = Some(instructions);
+        Ok(withdraw)
+    }
+
+    /// Computes the new balances and generates the proofs of a withdraw that are bound to the
+    /// token accounts of `context`.
+    ///
+    /// See [`ConfidentialAccountState::withdraw`]. The proofs verify only with
+    /// [`WithdrawData::verify_all_in_context`] for the same context. The proof program cannot
+    /// verify them, so they are returned without instructions.
+    pub fn withdraw_in_context(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        context: &StatementContext,
+    ) -> Result<WithdrawData, ClientError> {
+        self.withdraw_with_statement_contexts(
+            keypair,
+            aes_key,
+            amount,
+            &ProofStatementContexts::new(b"withdraw", context, WITHDRAW_PROOF_LABELS),
+        )
+    }
+
+    fn withdraw_with_statement_contexts(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        statement_contexts: &ProofStatementContexts<2>,
+    ) -> Result<WithdrawData, ClientError> {
+        let available_balance = self.decrypt_available_balance(aes_key)?;
+        let remaining_balance = available_balance
+            .checked_sub(amount)
+            .ok_or(ClientError::InsufficientFunds)?;
+
+        let new_available_balance = self.available_balance.subtract_amount(amount);
+        let (remaining_commitment, remaining_opening) = Pedersen::new(remaining_balance);
+
+        let equality_proof_data =
+            build_ciphertext_commitment_equality_proof_data_with_statement_context(
+                keypair,
+                &new_available_balance,
+                &remaining_commitment,
+                &remaining_opening,
+                remaining_balance,
+                statement_contexts.get(0),
+            )?;
+        let range_proof_data = build_batched_range_proof_u64_data_with_statement_context(
+            vec![&remaining_commitment],
+            vec![remaining_balance],
+            vec![AVAILABLE_BALANCE_BIT_LENGTH as usize],
+            vec![&remaining_opening],
+            statement_contexts.get(1),
+        )?;
+
+        let new_decryptable_available_balance = aes_key.encrypt(remaining_balance);
+        Ok(WithdrawData {
+            new_decryptable_available_balance,
+            new_state: Self {
+                available_balance: new_available_balance,
+                decryptable_available_balance: new_decryptable_available_balance,
+                ..self.clone()
+            },
+            equality_proof_data,
+            range_proof_data,
+            instructions: None,
+        })
+    }
+
+    /// Computes the new balances and generates the proofs of a transfer.
+    ///
+    /// * `keypair` - The owner's ElGamal keypair
+    /// * `aes_key` - The owner's authenticated encryption key
+    /// * `amount` - The amount to transfer
+    /// * `destination_pubkey` - The ElGamal public key of the destination account
+    /// * `auditor_pubkey` - The ElGamal public key of the auditor of the mint
+    /// * `context_state_accounts` - The context state accounts of the proofs
+    /// * `close_destination` - The account that receives the lamports of the closed context states
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context_state_accounts: TransferContextStateAccounts,
+        close_destination: &Address,
+    ) -> Result<TransferData, ClientError> {
+        let mut transfer = self.debit_grouped_amount(
+            keypair,
+            aes_key,
+            amount,
+            destination_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts(None),
+        )?;
+        transfer.instructions = Some(grouped_amount_instructions(
+            &transfer.equality_proof_data,
+            &transfer.ciphertext_validity_proof_data,
+            &transfer.range_proof_data,
+            context_state_accounts,
+            close_destination,
+        ));
+        Ok(transfer)
+    }
+
+    /// Computes the new balances and generates the proofs of a transfer that are bound to the
+    /// token accounts of `context`.
+    ///
+    /// See [`ConfidentialAccountState::transfer`]. The proofs verify only with
+    /// [`TransferData::verify_all_in_context`] for the same context. The proof program cannot
+    /// verify them, so they are returned without instructions.
+    pub fn transfer_in_context(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context: &StatementContext,
+    ) -> Result<TransferData, ClientError> {
+        self.debit_grouped_amount(
+            keypair,
+            aes_key,
+            amount,
+            destination_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts::new(b"transfer", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+
+    /// Computes the new balances and generates the proofs of a burn.
+    ///
+    /// The burn amount is encrypted for the source, the supply, and the auditor, in that order.
+    ///
+    /// * `keypair` - The owner's ElGamal keypair
+    /// * `aes_key` - The owner's authenticated encryption key
+    /// * `amount` - The amount to burn
+    /// * `supply_pubkey` - The ElGamal public key of the encrypted supply of the mint
+    /// * `auditor_pubkey` - The ElGamal public key of the auditor of the mint
+    /// * `context_state_accounts` - The context state accounts of the proofs
+    /// * `close_destination` - The account that receives the lamports of the closed context states
+    #[allow(clippy::too_many_arguments)]
+    pub fn burn(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        supply_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context_state_accounts: BurnContextStateAccounts,
+        close_destination: &Address,
+    ) -> Result<BurnProofBundle, ClientError> {
+        let mut burn = self.burn_with_statement_contexts(
+            keypair,
+            aes_key,
+            amount,
+            supply_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts(None),
+        )?;
+        burn.instructions = Some(grouped_amount_instructions(
+            &burn.equality_proof_data,
+            &burn.ciphertext_validity_proof_data,
+            &burn.range_proof_data,
+            context_state_accounts,
+            close_destination,
+        ));
+        Ok(burn)
+    }
+
+    /// Computes the new balances and generates the proofs of a burn that are bound to the token
+    /// accounts of `context`.
+    ///
+    /// See [`ConfidentialAccountState::burn`]. The proofs verify only with
+    /// [`BurnProofBundle::verify_all_in_context`] for the same context. The proof program cannot
+    /// verify them, so they are returned without instructions.
+    pub fn burn_in_context(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        supply_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context: &StatementContext,
+    ) -> Result<BurnProofBundle, ClientError> {
+        self.burn_with_statement_contexts(
+            keypair,
+            aes_key,
+            amount,
+            supply_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts::new(b"burn", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+
+    fn burn_with_statement_contexts(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        supply_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        statement_contexts: &ProofStatementContexts<3>,
+    ) -> Result<BurnProofBundle, ClientError> {
+        let TransferData {
+            transfer_amount_lo,
+            transfer_amount_hi,
+            new_decryptable_available_balance,
+            new_state,
+            equality_proof_data,
+            ciphertext_validity_proof_data,
+            range_proof_data,
+            instructions,
+        } = self.debit_grouped_amount(
+            keypair,
+            aes_key,
+            amount,
+            supply_pubkey,
+            auditor_pubkey,
+            statement_contexts,
+        )?;
+
+        Ok(BurnProofBundle {
+            burn_amount_lo: transfer_amount_lo,
+            burn_amount_hi: transfer_amount_hi,
+            new_decryptable_available_balance,
+            new_state,
+            equality_proof_data,
+            ciphertext_validity_proof_data,
+            range_proof_data,
+            instructions,
+        })
+    }
+
+    /// Debits an amount that is encrypted for the source and two other parties from the available
+    /// balance.
+    ///
+    /// The source handles of the grouped ciphertexts are at index 0, followed by the handles of
+    /// `second_pubkey` and `third_pubkey`.
+    fn debit_grouped_amount(
+        &self,
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        amount: u64,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        statement_contexts: &ProofStatementContexts<3>,
+    ) -> Result<TransferData, ClientError> {
+        let available_balance = self.decrypt_available_balance(aes_key)?;
+        let remaining_balance = available_balance
+            .checked_sub(amount)
+            .ok_or(ClientError::InsufficientFunds)?;
+
+        let pubkeys = [keypair.pubkey(), second_pubkey, third_pubkey];
+        let grouped_amount = GroupedAmount::encrypt(amount, pubkeys)?;
+
+        // the source handles of the grouped ciphertexts are at index 0
+        let new_available_balance = &self.available_balance - &grouped_amount.ciphertext(0)?;
+
+        let proofs = GroupedAmountProofs::new(
+            keypair,
+            &new_available_balance,
+            remaining_balance,
+            pubkeys,
+            &grouped_amount,
+            statement_contexts,
+        )?;
+
+        let new_decryptable_available_balance = aes_key.encrypt(remaining_balance);
+        Ok(TransferData {
+            transfer_amount_lo: grouped_amount.ciphertext_lo,
+            transfer_amount_hi: grouped_amount.ciphertext_hi,
+            new_decryptable_available_balance,
+            new_state: Self {
+                available_balance: new_available_balance,
+                decryptable_available_balance: new_decryptable_available_balance,
+                ..self.clone()
+            },
+            equality_proof_data: proofs.equality_proof_data,
+            ciphertext_validity_proof_data: proofs.ciphertext_validity_proof_data,
+            range_proof_data: proofs.range_proof_data,
+            instructions: None,
+        })
+    }
+}
+
+impl EncryptedSupplyState {
+    /// Decrypts the supply with the supply authority's authenticated encryption key.
+    pub fn decrypt_supply(&self, aes_key: &AeKey) -> Result<u64, ClientError> {
+        self.decryptable_supply
+            .decrypt(aes_key)
+            .ok_or(ClientError::DecryptionFailed)
+    }
+
+    /// Computes the new supply and generates the proofs of a mint.
+    ///
+    /// The mint amount is encrypted for the destination, the supply, and the auditor, in that
+    /// order. The destination is credited and the supply is updated with the same grouped
+    /// ciphertexts, and the ciphertext validity proof certifies that all handles of the grouped
+    /// ciphertexts encrypt the same amount.
+    ///
+    /// * `supply_keypair` - The ElGamal keypair of the encrypted supply
+    /// * `supply_aes_key` - The authenticated encryption key of the supply authority
+    /// * `amount` - The amount to mint
+    /// * `destination_pubkey` - The ElGamal public key of the destination account
+    /// * `auditor_pubkey` - The ElGamal public key of the auditor of the mint
+    /// * `context_state_accounts` - The context state accounts of the proofs
+    /// * `close_destination` - The account that receives the lamports of the closed context states
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint(
+        &self,
+        supply_keypair: &ElGamalKeypair,
+        supply_aes_key: &AeKey,
+        amount: u64,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context_state_accounts: MintContextStateAccounts,
+        close_destination: &Address,
+    ) -> Result<MintProofBundle, ClientError> {
+        let mut mint = self.mint_with_statement_contexts(
+            supply_keypair,
+            supply_aes_key,
+            amount,
+            destination_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts(None),
+        )?;
+        mint.instructions = Some(grouped_amount_instructions(
+            &mint.equality_proof_data,
+            &mint.ciphertext_validity_proof_data,
+            &mint.range_proof_data,
+            context_state_accounts,
+            close_destination,
+        ));
+        Ok(mint)
+    }
+
+    /// Computes the new supply and generates the proofs of a mint that are bound to the token
+    /// accounts of `context`.
+    ///
+    /// See [`EncryptedSupplyState::mint`]. The proofs verify only with
+    /// [`MintProofBundle::verify_all_in_context`] for the same context. The proof program cannot
+    /// verify them, so they are returned without instructions.
+    pub fn mint_in_context(
+        &self,
+        supply_keypair: &ElGamalKeypair,
+        supply_aes_key: &AeKey,
+        amount: u64,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        context: &StatementContext,
+    ) -> Result<MintProofBundle, ClientError> {
+        self.mint_with_statement_contexts(
+            supply_keypair,
+            supply_aes_key,
+            amount,
+            destination_pubkey,
+            auditor_pubkey,
+            &ProofStatementContexts::new(b"mint", context, GROUPED_AMOUNT_PROOF_LABELS),
+        )
+    }
+
+    fn mint_with_statement_contexts(
+        &self,
+        supply_keypair: &ElGamalKeypair,
+        supply_aes_key: &AeKey,
+        amount: u64,
+        destination_pubkey: &ElGamalPubkey,
+        auditor_pubkey: &ElGamalPubkey,
+        statement_contexts: &ProofStatementContexts<3>,
+    ) -> Result<MintProofBundle, ClientError> {
+        let supply = self.decrypt_supply(supply_aes_key)?;
+        let new_supply = supply
+            .checked_add(amount)
+            .ok_or(ClientError::BalanceOverflow)?;
+
+        let pubkeys = [destination_pubkey, supply_keypair.pubkey(), auditor_pubkey];
+        let grouped_amount = GroupedAmount::encrypt(amount, pubkeys)?;
+
+        // the supply handles of the grouped ciphertexts are at index 1
+        let new_current_supply = &self.current_supply + &grouped_amount.ciphertext(1)?;
+
+        let proofs = GroupedAmountProofs::new(
+            supply_keypair,
+            &new_current_supply,
+            new_supply,
+            pubkeys,
+            &grouped_amount,
+            statement_contexts,
+        )?;
+
+        let new_decryptable_supply = supply_aes_key.encrypt(new_supply);
+        Ok(MintProofBundle {
+            mint_amount_lo: grouped_amount.ciphertext_lo,
+            mint_amount_hi: grouped_amount.ciphertext_hi,
+            new_decryptable_supply,
+            new_supply_state: Self {
+                current_supply: new_current_supply,
+                decryptable_supply: new_decryptable_supply,
+            },
+            equality_proof_data: proofs.equality_proof_data,
+            ciphertext_validity_proof_data: proofs.ciphertext_validity_proof_data,
+            range_proof_data: proofs.range_proof_data,
+            instructions: None,
+        })
+    }
+}
+
+/// The statement contexts of the proofs of an operation, in the order in which the proofs are
+/// generated, or `None` if the proofs are not bound to a statement context.
+struct ProofStatementContexts<const N: usize>(Option<[Vec<u8>; N]>);
+
+impl<const N: usize> ProofStatementContexts<N> {
+    /// Derives the statement contexts of the proofs of `operation` under `context`.
+    ///
+    /// Each statement context is derived from a [`ProtocolTranscript`] over `context`, so it binds
+    /// the proof to the operation, to its position in the operation, and to its label.
+    fn new(
+        operation: &'static [u8],
+        context: &StatementContext,
+        proof_labels: [&'static [u8]; N],
+    ) -> Self {
+        let statement_context = context.to_statement_context();
+        let mut protocol_transcript =
+            ProtocolTranscript::new(operation, &[statement_context.as_slice(); N]);
+        Self(Some(proof_labels.map(|proof_label| {
+            let mut transcript = protocol_transcript
+                .next_proof_transcript(proof_label)
+                .expect("a proof transcript is derived for each statement");
+            let mut proof_statement_context = vec![0; 32];
+            transcript.challenge_bytes(b"statement-context", &mut proof_statement_context);
+            proof_statement_context
+        })))
+    }
+
+    /// The statement context of the proof at `index`, if any.
+    fn get(&self, index: usize) -> Option<&[u8]> {
+        self.0
+            .as_ref()
+            .map(|statement_contexts| statement_contexts[index].as_slice())
+    }
+}
+
+/// An amount that is split into its low and high bits and encrypted for three parties.
+struct GroupedAmount {
+    amount_lo: u64,
+    amount_hi: u64,
+    ciphertext_lo: GroupedElGamalCiphertext<3>,
+    ciphertext_hi: GroupedElGamalCiphertext<3>,
+    opening_lo: PedersenOpening,
+    opening_hi: PedersenOpening,
+}
+
+impl GroupedAmount {
+    fn encrypt(amount: u64, pubkeys: [&ElGamalPubkey; 3]) -> Result<Self, ClientError> {
+        if amount > MAXIMUM_DEPOSIT_TRANSFER_AMOUNT {
+            return Err(ClientError::AmountTooLarge);
+        }
+        let (amount_lo, amount_hi) = split_amount(amount, TRANSFER_AMOUNT_LO_BIT_LENGTH);
+        let opening_lo = PedersenOpening::new_rand();
+        let opening_hi = PedersenOpening::new_rand();
+
+        Ok(Self {
+            amount_lo,
+            amount_hi,
+            ciphertext_lo: GroupedElGamal::encrypt_with(pubkeys, amount_lo, &opening_lo),
+            ciphertext_hi: GroupedElGamal::encrypt_with(pubkeys, amount_hi, &opening_hi),
+            opening_lo,
+            opening_hi,
+        })
+    }
+
+    /// The ciphertext of the full amount under the handles at `index`.
+    fn ciphertext(&self, index: usize) -> Result<ElGamalCiphertext, ClientError> {
+        Ok(combine_lo_hi_ciphertexts(
+            &self.ciphertext_lo.to_elgamal_ciphertext(index)?,
+            &self.ciphertext_hi.to_elgamal_ciphertext(index)?,
+            TRANSFER_AMOUNT_LO_BIT_LENGTH,
+        ))
+    }
+}
+
+/// The proofs that an encrypted balance was updated by a grouped amount.
+struct GroupedAmountProofs {
+    equality_proof_data: CiphertextCommitmentEqualityProofData,
+    ciphertext_validity_proof_data: BatchedGroupedCiphertext3HandlesValidityProofData,
+    range_proof_data: BatchedRangeProofU128Data,
+}
+
+impl GroupedAmountProofs {
+    /// Generates the proofs that `new_balance_ciphertext`, which is encrypted under `keypair`,
+    /// encrypts `new_balance`, that the grouped amount is well-formed, and that the new balance
+    /// and the amount are in range.
+    fn new(
+        keypair: &ElGamalKeypair,
+        new_balance_ciphertext: &ElGamalCiphertext,
+        new_balance: u64,
+        pubkeys: [&ElGamalPubkey; 3],
+        grouped_amount: &GroupedAmount,
+        statement_contexts: &ProofStatementContexts<3>,
+    ) -> Result<Self, ClientError> {
+        let (new_balance_commitment, new_balance_opening) = Pedersen::new(new_balance);
+
+        let equality_proof_data =
+            build_ciphertext_commitment_equality_proof_data_with_statement_context(
+                keypair,
+                new_balance_ciphertext,
+                &new_balance_commitment,
+                &new_balance_opening,
+                new_balance,
+                statement_contexts.get(0),
+            )?;
+        let [first_pubkey, second_pubkey, third_pubkey] = pubkeys;
+        let ciphertext_validity_proof_data =
+            build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+                first_pubkey,
+                second_pubkey,
+                third_pubkey,
+                &grouped_amount.ciphertext_lo,
+                &grouped_amount.ciphertext_hi,
+                grouped_amount.amount_lo,
+                grouped_amount.amount_hi,
+                &grouped_amount.opening_lo,
+                &grouped_amount.opening_hi,
+                statement_contexts.get(1),
+            )?;
+
+        // the bit lengths of the range proof must sum to 128, so the proof is padded with a
+        // commitment to zero
+        let (padding_commitment, padding_opening) = Pedersen::new(0_u64);
+        let range_proof_data = build_batched_range_proof_u128_data_with_statement_context(
+            vec![
+                &new_balance_commitment,
+                &grouped_amount.ciphertext_lo.commitment,
+                &grouped_amount.ciphertext_hi.commitment,
+                &padding_commitment,
+            ],
+            vec![
+                new_balance,
+                grouped_amount.amount_lo,
+                grouped_amount.amount_hi,
+                0,
+            ],
+            vec![
+                AVAILABLE_BALANCE_BIT_LENGTH as usize,
+                TRANSFER_AMOUNT_LO_BIT_LENGTH as usize,
+                TRANSFER_AMOUNT_HI_BIT_LENGTH as usize,
+                TRANSFER_RANGE_PADDING_BIT_LENGTH as usize,
+            ],
+            vec![
+                &new_balance_opening,
+                &grouped_amount.opening_lo,
+                &grouped_amount.opening_hi,
+                &padding_opening,
+            ],
+            statement_contexts.get(2),
+        )?;
+
+        Ok(Self {
+            equality_proof_data,
+            ciphertext_validity_proof_data,
+            range_proof_data,
+        })
+    }
+}
+
+/// Creates the instructions that verify the proofs of a transfer, a burn, or a mint into context
+/// state accounts.
+fn grouped_amount_instructions(
+    equality_proof_data: &CiphertextCommitmentEqualityProofData,
+    ciphertext_validity_proof_data: &BatchedGroupedCiphertext3HandlesValidityProofData,
+    range_proof_data: &BatchedRangeProofU128Data,
+    context_state_accounts: TransferContextStateAccounts,
+    close_destination: &Address,
+) -> ProofInstructionSet {
+    let mut instructions = ProofInstructionSet::default();
+    instructions.push(
+        ProofInstruction::VerifyCiphertextCommitmentEquality,
+        context_state_accounts.equality_proof,
+        close_destination,
+        equality_proof_data,
+    );
+    instructions.push(
+        ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity,
+        context_state_accounts.ciphertext_validity_proof,
+        close_destination,
+        ciphertext_validity_proof_data,
+    );
+    instructions.push(
+        ProofInstruction::VerifyBatchedRangeProofU128,
+        context_state_accounts.range_proof,
+        close_destination,
+        range_proof_data,
+    );
+    instructions
+}
+
+/// Verifies the proofs of a transfer, a burn, or a mint with a single multiscalar multiplication.
+fn verify_grouped_amount_proofs(
+    equality_proof_data: &CiphertextCommitmentEqualityProofData,
+    ciphertext_validity_proof_data: &BatchedGroupedCiphertext3HandlesValidityProofData,
+    range_proof_data: &BatchedRangeProofU128Data,
+    statement_contexts: &ProofStatementContexts<3>,
+) -> Result<(), ProofVerificationError> {
+    let mut scalars = [Scalar::ZERO; GROUPED_AMOUNT_VERIFICATION_TERMS];
+    let mut points = [RistrettoPoint::identity(); GROUPED_AMOUNT_VERIFICATION_TERMS];
+    let mut verifier =
+        CombinedProofVerifier::new(MultiscalarMulBuffer::new(&mut scalars, &mut points));
+    verifier.append_with_statement_context(equality_proof_data, statement_contexts.get(0))?;
+    verifier
+        .append_with_statement_context(ciphertext_validity_proof_data, statement_contexts.get(1))?;
+    verifier.append_with_statement_context(range_proof_data, statement_contexts.get(2))?;
+    verifier.verify(&CpuMultiscalarMulBackend)
+}
+
+/// Computes the values of an apply pending balance instruction.
+///
+/// The pending balance ciphertexts and `pending_balance_credit_counter` must be read from the same
+/// snapshot of the account. `expected_pending_balance_credit_counter` is the counter that the
+/// caller intends to put in the instruction, and it must match the counter of the snapshot, since
+/// the new decryptable available balance only accounts for the credits that are in the snapshot.
+///
+/// Each credit adds less than `2^16` to the low bits and less than `2^32` to the high bits of the
+/// pending balance. A pending balance that exceeds these bounds for the credit counter was not
+/// read from the same snapshot as the counter, and is rejected.
+///
+/// * `keypair` - The owner's ElGamal keypair
+/// * `aes_key` - The owner's authenticated encryption key
+/// * `decryptable_available_balance` - The current decryptable available balance
+/// * `pending_balance_lo` - The low bits of the pending balance
+/// * `pending_balance_hi` - The high bits of the pending balance
+/// * `pending_balance_credit_counter` - The credit counter of the account
+/// * `expected_pending_balance_credit_counter` - The credit counter to put in the instruction
+pub fn compute_apply_pending_balance(
+    keypair: &ElGamalKeypair,
+    aes_key: &AeKey,
+    decryptable_available_balance: &AeCiphertext,
+    pending_balance_lo: &ElGamalCiphertext,
+    pending_balance_hi: &ElGamalCiphertext,
+    pending_balance_credit_counter: u64,
+    expected_pending_balance_credit_counter: u64,
+) -> Result<ApplyPendingBalanceInstructionData, ClientError> {
+    if pending_balance_credit_counter != expected_pending_balance_credit_counter {
+        return Err(ClientError::PendingBalanceCreditCounterMismatch);
+    }
+
+    let available_balance = decryptable_available_balance
+        .decrypt(aes_key)
+        .ok_or(ClientError::DecryptionFailed)?;
+    let pending_balance = decrypt_pending_balance(
+        keypair,
+        pending_balance_lo,
+        pending_balance_hi,
+        pending_balance_credit_counter,
+    )?;
+    let new_available_balance = available_balance
+        .checked_add(pending_balance)
+        .ok_or(ClientError::BalanceOverflow)?;
+
+    Ok(ApplyPendingBalanceInstructionData {
+        expected_pending_balance_credit_counter,
+        new_decryptable_available_balance: aes_key.encrypt(new_available_balance),
+    })
+}
+
+/// Decrypts the low and high bits of a pending balance and checks them against the credit
+/// counter.
+fn decrypt_pending_balance(
+    keypair: &ElGamalKeypair,
+    pending_balance_lo: &ElGamalCiphertext,
+    pending_balance_hi: &ElGamalCiphertext,
+    pending_balance_credit_counter: u64,
+) -> Result<u64, ClientError> {
+    let pending_balance_lo = pending_balance_lo
+        .decrypt_u32(keypair.secret())
+        .ok_or(ClientError::DecryptionFailed)?;
+    let pending_balance_hi = pending_balance_hi
+        .decrypt_u32(keypair.secret())
+        .ok_or(ClientError::DecryptionFailed)?;
+
+    let exceeds_credits = |amount: u64, bit_length: u32| {
+        pending_balance_credit_counter
+            .checked_mul((1 << bit_length) - 1)
+            .is_some_and(|max_amount| amount > max_amount)
+    };
+    if exceeds_credits(pending_balance_lo, PENDING_BALANCE_LO_BIT_LENGTH)
+        || exceeds_credits(pending_balance_hi, PENDING_BALANCE_HI_BIT_LENGTH)
+    {
+        return Err(ClientError::PendingBalanceCreditCounterMismatch);
+    }
+
+    pending_balance_hi
+        .checked_shl(PENDING_BALANCE_LO_BIT_LENGTH)
+        .and_then(|hi| hi.checked_add(pending_balance_lo))
+        .ok_or(ClientError::BalanceOverflow)
+}
+
+/// Encrypts a public deposit or mint amount under the owner and auditor public keys and generates
+/// the matching ciphertext validity proof.
+///
+/// The amount is split into its low 16 bits and high 32 bits, in the same way as a transfer
+/// amount. The first handle of each grouped ciphertext pertains to the owner and the second handle
+/// to the auditor.
+pub fn encrypt_deposit_amount(
+    amount: u64,
+    owner_pubkey: &ElGamalPubkey,
+    auditor_pubkey: &ElGamalPubkey,
+) -> Result<DepositData, ClientError> {
+    if amount > MAXIMUM_DEPOSIT_TRANSFER_AMOUNT {
+        return Err(ClientError::AmountTooLarge);
+    }
+    let (amount_lo, amount_hi) = split_amount(amount, TRANSFER_AMOUNT_LO_BIT_LENGTH);
+
+    let pubkeys = [owner_pubkey, auditor_pubkey];
+    let opening_lo = PedersenOpening::new_rand();
+    let opening_hi = PedersenOpening::new_rand();
+    let grouped_amount_lo = GroupedElGamal::encrypt_with(pubkeys, amount_lo, &opening_lo);
+    let grouped_amount_hi = GroupedElGamal::encrypt_with(pubkeys, amount_hi, &opening_hi);
+
+    let ciphertext_validity_proof_data =
+        build_batched_grouped_ciphertext_2_handles_validity_proof_data(
+            owner_pubkey,
+            auditor_pubkey,
+            &grouped_amount_lo,
+            &grouped_amount_hi,
+            amount_lo,
+            amount_hi,
+            &opening_lo,
+            &opening_hi,
+        )?;
+
+    Ok(DepositData {
+        amount_lo: grouped_amount_lo,
+        amount_hi: grouped_amount_hi,
+        opening_lo,
+        opening_hi,
+        ciphertext_validity_proof_data,
+    })
+}
+
+/// Splits an amount into its low `lo_bit_length` bits and the remaining high bits.
+fn split_amount(amount: u64, lo_bit_length: u32) -> (u64, u64) {
+    let lo = amount & ((1 << lo_bit_length) - 1);
+    let hi = amount >> lo_bit_length;
+    (lo, hi)
+}
+
+/// Combines the ciphertexts of the low and high bits of an amount into a ciphertext of the amount.
+fn combine_lo_hi_ciphertexts(
+    ciphertext_lo: &ElGamalCiphertext,
+    ciphertext_hi: &ElGamalCiphertext,
+    lo_bit_length: u32,
+) -> ElGamalCiphertext {
+    let shift = 1_u64 << lo_bit_length;
+    ciphertext_lo + &(ciphertext_hi * &shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::zk_elgamal_proof_program::{errors::ProofGenerationError, VerifyZkProof},
+    };
+
+    fn new_account(
+        keypair: &ElGamalKeypair,
+        aes_key: &AeKey,
+        balance: u64,
+    ) -> ConfidentialAccountState {
+        let zero_ciphertext = ElGamalCiphertext::default();
+        ConfidentialAccountState {
+            available_balance: keypair.pubkey().encrypt(balance),
+            decryptable_available_balance: aes_key.encrypt(balance),
+            pending_balance_lo: zero_ciphertext,
+            pending_balance_hi: zero_ciphertext,
+            pending_balance_credit_counter: 0,
+        }
+    }
+
+    #[test]
+    fn test_deposit_and_apply_pending_balance() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let account = new_account(&keypair, &aes_key, 10);
+
+        let account = account.deposit(70_000).unwrap().deposit(5).unwrap();
+        assert_eq!(account.pending_balance_credit_counter, 2);
+        assert_eq!(account.decrypt_pending_balance(&keypair).unwrap(), 70_005);
+
+        let apply = account.apply_pending_balance(&keypair, &aes_key).unwrap();
+        assert_eq!(apply.expected_pending_balance_credit_counter, 2);
+        assert_eq!(
+            apply.new_decryptable_available_balance.decrypt(&aes_key),
+            Some(70_015)
+        );
+        assert_eq!(
+            apply
+                .new_state
+                .available_balance
+                .decrypt_u32(keypair.secret()),
+            Some(70_015)
+        );
+        assert_eq!(apply.new_state.pending_balance_credit_counter, 0);
+
+        assert_eq!(
+            account
+                .deposit(MAXIMUM_DEPOSIT_TRANSFER_AMOUNT + 1)
+                .unwrap_err(),
+            ClientError::AmountTooLarge
+        );
+    }
+
+    #[test]
+    fn test_compute_apply_pending_balance() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let account = new_account(&keypair, &aes_key, 10)
+            .deposit(0xffff)
+            .unwrap()
+            .deposit(0xffff)
+            .unwrap();
+
+        let data = compute_apply_pending_balance(
+            &keypair,
+            &aes_key,
+            &account.decryptable_available_balance,
+            &account.pending_balance_lo,
+            &account.pending_balance_hi,
+            2,
+            2,
+        )
+        .unwrap();
+        assert_eq!(data.expected_pending_balance_credit_counter, 2);
+        assert_eq!(
+            data.new_decryptable_available_balance.decrypt(&aes_key),
+            Some(10 + 2 * 0xffff)
+        );
+
+        // the expected counter does not match the counter of the account
+        let result = compute_apply_pending_balance(
+            &keypair,
+            &aes_key,
+            &account.decryptable_available_balance,
+            &account.pending_balance_lo,
+            &account.pending_balance_hi,
+            2,
+            1,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ClientError::PendingBalanceCreditCounterMismatch
+        );
+
+        // the pending balance holds more credits than the counter accounts for
+        let result = compute_apply_pending_balance(
+            &keypair,
+            &aes_key,
+            &account.decryptable_available_balance,
+            &account.pending_balance_lo,
+            &account.pending_balance_hi,
+            1,
+            1,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ClientError::PendingBalanceCreditCounterMismatch
+        );
+    }
+
+    #[test]
+    fn test_encrypt_deposit_amount() {
+        let owner_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let amount = 123_456_789_u64;
+
+        let deposit =
+            encrypt_deposit_amount(amount, owner_keypair.pubkey(), auditor_keypair.pubkey())
+                .unwrap();
+        assert!(deposit
+            .ciphertext_validity_proof_data
+            .verify_proof()
+            .is_ok());
+        assert!(deposit.verify_instruction(None).accounts.is_empty());
+
+        // the auditor can decrypt the amount from its handles
+        let auditor_lo = deposit
+            .amount_lo
+            .to_elgamal_ciphertext(1)
+            .unwrap()
+            .decrypt_u32(auditor_keypair.secret())
+            .unwrap();
+        let auditor_hi = deposit
+            .amount_hi
+            .to_elgamal_ciphertext(1)
+            .unwrap()
+            .decrypt_u32(auditor_keypair.secret())
+            .unwrap();
+        assert_eq!(
+            auditor_lo + (auditor_hi << TRANSFER_AMOUNT_LO_BIT_LENGTH),
+            amount
+        );
+
+        assert_eq!(
+            encrypt_deposit_amount(
+                MAXIMUM_DEPOSIT_TRANSFER_AMOUNT + 1,
+                owner_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+            )
+            .unwrap_err(),
+            ClientError::AmountTooLarge
+        );
+    }
+
+    #[test]
+    fn test_context_state_expiry() {
+        use solana_zk_elgamal_proof_interface::{
+            proof_data::{BatchedGroupedCiphertext2HandlesValidityProofContext, ProofType},
+            state::ProofContextStateExpiry,
+        };
+
+        let owner_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let deposit =
+            encrypt_deposit_amount(55, owner_keypair.pubkey(), auditor_keypair.pubkey()).unwrap();
+        let proof_data = &deposit.ciphertext_validity_proof_data;
+        type State = ProofContextState<BatchedGroupedCiphertext2HandlesValidityProofContext>;
+
+        let context_state_account = Address::new_unique();
+        let context_state_authority = Address::new_unique();
+        let context_state_info = ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        };
+
+        // only the expiry decoder accepts the trailing expiry slot
+        let instruction = ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity
+            .encode_verify_proof_with_expiry(context_state_info, 100, proof_data);
+        assert_eq!(
+            ProofInstruction::proof_data::<
+                BatchedGroupedCiphertext2HandlesValidityProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofContext,
+            >(&instruction.data),
+            None
+        );
+        assert_eq!(
+            ProofInstruction::proof_data_with_expiry::<
+                BatchedGroupedCiphertext2HandlesValidityProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofContext,
+            >(&instruction.data),
+            Some((proof_data, Some(&ProofContextStateExpiry::new(100))))
+        );
+        let instruction = deposit.verify_instruction(Some(context_state_info));
+        assert_eq!(
+            ProofInstruction::proof_data_with_expiry::<
+                BatchedGroupedCiphertext2HandlesValidityProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofContext,
+            >(&instruction.data),
+            Some((proof_data, None))
+        );
+
+        // a malformed expiry is rejected
+        let mut data = instruction.data.clone();
+        data.extend_from_slice(&[0; 3]);
+        assert_eq!(
+            ProofInstruction::proof_data_with_expiry::<
+                BatchedGroupedCiphertext2HandlesValidityProofData,
+                BatchedGroupedCiphertext2HandlesValidityProofContext,
+            >(&data),
+            None
+        );
+
+        // a context state is rejected only after its expiry slot
+        let expiring = State::encode_with_expiry(
+            &context_state_authority,
+            ProofType::BatchedGroupedCiphertext2HandlesValidity,
+            &proof_data.context,
+            Some(100),
+        );
+        assert!(State::try_from_bytes_unexpired(&expiring, 100).is_ok());
+        assert!(State::try_from_bytes_unexpired(&expiring, 101).is_err());
+
+        let non_expiring = State::encode_with_expiry(
+            &context_state_authority,
+            ProofType::BatchedGroupedCiphertext2HandlesValidity,
+            &proof_data.context,
+            None,
+        );
+        assert!(State::try_from_bytes_unexpired(&non_expiring, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_hashed_context_state() {
+        use solana_zk_elgamal_proof_interface::{
+            proof_data::{BatchedGroupedCiphertext2HandlesValidityProofContext, ProofType},
+            state::{HashedProofContextState, ProofContextStateMeta},
+        };
+
+        let owner_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let deposit =
+            encrypt_deposit_amount(55, owner_keypair.pubkey(), auditor_keypair.pubkey()).unwrap();
+        let proof_context = &deposit.ciphertext_validity_proof_data.context;
+        type State = ProofContextState<BatchedGroupedCiphertext2HandlesValidityProofContext>;
+        let context_state_authority = Address::new_unique();
+
+        let encoded = HashedProofContextState::encode(
+            &context_state_authority,
+            ProofType::BatchedGroupedCiphertext2HandlesValidity,
+            proof_context,
+        );
+        assert!(encoded.len() < size_of::<State>());
+        let state = HashedProofContextState::try_from_bytes(&encoded).unwrap();
+        assert!(state.verify_proof_context(proof_context).is_ok());
+
+        // the generic-independent fields are decoded the same way as in a full context state
+        let meta = ProofContextStateMeta::try_from_bytes(&encoded).unwrap();
+        assert_eq!(meta.context_state_authority, context_state_authority);
+        assert!(meta.is_hashed());
+        assert_eq!(
+            state.proof_type(),
+            Ok(ProofType::BatchedGroupedCiphertext2HandlesValidity)
+        );
+
+        // a hashed context state is not decoded as a full context state
+        assert!(State::try_from_bytes(&encoded).is_err());
+
+        // a different context is rejected
+        let other_deposit =
+            encrypt_deposit_amount(56, owner_keypair.pubkey(), auditor_keypair.pubkey()).unwrap();
+        assert!(state
+            .verify_proof_context(&other_deposit.ciphertext_validity_proof_data.context)
+            .is_err());
+
+        // only the hashed decoder accepts the trailing tag
+        let context_state_account = Address::new_unique();
+        let context_state_info = ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        };
+        let proof_data = &deposit.ciphertext_validity_proof_data;
+        let mut instruction = ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity
+            .encode_verify_proof_hashed(context_state_info, proof_data);
+        type Data = BatchedGroupedCiphertext2HandlesValidityProofData;
+        type Context = BatchedGroupedCiphertext2HandlesValidityProofContext;
+        assert_eq!(
+            ProofInstruction::proof_data::<Data, Context>(&instruction.data),
+            None
+        );
+        assert_eq!(
+            ProofInstruction::proof_data_with_expiry::<Data, Context>(&instruction.data),
+            None
+        );
+        assert_eq!(
+            ProofInstruction::proof_data_with_hashed_context::<Data, Context>(&instruction.data),
+            Some(proof_data)
+        );
+        *instruction.data.last_mut().unwrap() = 0;
+        assert_eq!(
+            ProofInstruction::proof_data_with_hashed_context::<Data, Context>(&instruction.data),
+            None
+        );
+    }
+
+    #[test]
+    fn test_withdraw() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let account = new_account(&keypair, &aes_key, 100);
+
+        let context_state_authority = Address::new_unique();
+        let equality_account = Address::new_unique();
+        let range_account = Address::new_unique();
+        let context_state_accounts = WithdrawContextStateAccounts {
+            equality_proof: ContextStateInfo {
+                context_state_account: &equality_account,
+                context_state_authority: &context_state_authority,
+            },
+            range_proof: ContextStateInfo {
+                context_state_account: &range_account,
+                context_state_authority: &context_state_authority,
+            },
+        };
+
+        let withdraw = account
+            .withdraw(
+                &keypair,
+                &aes_key,
+                40,
+                context_state_accounts,
+                &context_state_authority,
+            )
+            .unwrap();
+
+        assert!(withdraw.equality_proof_data.verify_proof().is_ok());
+        assert!(withdraw.range_proof_data.verify_proof().is_ok());
+        assert!(withdraw.verify_all().is_ok());
+        assert_eq!(
+            withdraw
+                .instructions
+                .as_ref()
+                .unwrap()
+                .verify_instructions
+                .len(),
+            2
+        );
+        assert_eq!(
+            withdraw
+                .instructions
+                .as_ref()
+                .unwrap()
+                .close_instructions
+                .len(),
+            2
+        );
+        assert_eq!(
+            withdraw
+                .new_state
+                .available_balance
+                .decrypt_u32(keypair.secret()),
+            Some(60)
+        );
+        assert_eq!(
+            withdraw.new_state.decrypt_available_balance(&aes_key),
+            Ok(60)
+        );
+
+        assert_eq!(
+            account
+                .withdraw(
+                    &keypair,
+                    &aes_key,
+                    101,
+                    context_state_accounts,
+                    &context_state_authority,
+                )
+                .unwrap_err(),
+            ClientError::InsufficientFunds
+        );
+    }
+
+    #[test]
+    fn test_transfer() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let account = new_account(&keypair, &aes_key, 200_000);
+
+        let context_state_authority = Address::new_unique();
+        let equality_account = Address::new_unique();
+        let validity_account = Address::new_unique();
+        let range_account = Address::new_unique();
+        let context_state_accounts = TransferContextStateAccounts {
+            equality_proof: ContextStateInfo {
+                context_state_account: &equality_account,
+                context_state_authority: &context_state_authority,
+            },
+            ciphertext_validity_proof: ContextStateInfo {
+                context_state_account: &validity_account,
+                context_state_authority: &context_state_authority,
+            },
+            range_proof: ContextStateInfo {
+                context_state_account: &range_account,
+                context_state_authority: &context_state_authority,
+            },
+        };
+
+        let transfer = account
+            .transfer(
+                &keypair,
+                &aes_key,
+                120_000,
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+                context_state_accounts,
+                &context_state_authority,
+            )
+            .unwrap();
+
+        assert!(transfer.equality_proof_data.verify_proof().is_ok());
+        assert!(transfer
+            .ciphertext_validity_proof_data
+            .verify_proof()
+            .is_ok());
+        assert!(transfer.range_proof_data.verify_proof().is_ok());
+        assert!(transfer.verify_all().is_ok());
+
+        // a single invalid proof makes the combined verification fail
+        let mut tampered = transfer.clone();
+        tampered.equality_proof_data.context.ciphertext = keypair.pubkey().encrypt(1_u64).into();
+        assert_eq!(
+            tampered.verify_all().unwrap_err(),
+            ProofVerificationError::CombinedAlgebraicRelation
+        );
+        assert_eq!(
+            transfer
+                .instructions
+                .as_ref()
+                .unwrap()
+                .verify_instructions
+                .len(),
+            3
+        );
+        assert_eq!(
+            transfer
+                .new_state
+                .available_balance
+                .decrypt_u32(keypair.secret()),
+            Some(80_000)
+        );
+
+        // the destination can decrypt the transfer amount from its handles
+        let destination_lo = transfer
+            .transfer_amount_lo
+            .to_elgamal_ciphertext(1)
+            .unwrap()
+            .decrypt_u32(destination_keypair.secret())
+            .unwrap();
+        let destination_hi = transfer
+            .transfer_amount_hi
+            .to_elgamal_ciphertext(1)
+            .unwrap()
+            .decrypt_u32(destination_keypair.secret())
+            .unwrap();
+        assert_eq!(
+            destination_lo + (destination_hi << TRANSFER_AMOUNT_LO_BIT_LENGTH),
+            120_000
+        );
+
+        // a wrong keypair fails proof generation
+        let result = account.transfer(
+            &ElGamalKeypair::new_rand(),
+            &aes_key,
+            1,
+            destination_keypair.pubkey(),
+            auditor_keypair.pubkey(),
+            context_state_accounts,
+            &context_state_authority,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ClientError::ProofGeneration(ProofGenerationError::InconsistentInput)
+        );
+    }
+
+    #[test]
+    fn test_transfer_in_statement_context() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let account = new_account(&keypair, &aes_key, 1_000);
+
+        let context = StatementContext::new(
+            Address::new_unique(),
+            Address::new_unique(),
+            Address::new_unique(),
+        )
+        .with_auditor(*auditor_keypair.pubkey());
+        let transfer = account
+            .transfer_in_context(
+                &keypair,
+                &aes_key,
+                100,
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+                &context,
+            )
+            .unwrap();
+
+        assert!(transfer.verify_all_in_context(&context).is_ok());
+        assert!(transfer.verify_all().is_err());
+
+        // the proof program cannot verify the proofs, so no instructions are returned
+        assert!(transfer.instructions.is_none());
+
+        // the proofs do not verify for another destination account
+        let other_context = StatementContext {
+            destination: Address::new_unique(),
+            ..context
+        };
+        assert!(transfer.verify_all_in_context(&other_context).is_err());
+        assert!(transfer
+            .verify_all_in_context(&context.with_nonce(1))
+            .is_err());
+    }
+
+    #[cfg(feature = "test-harness")]
+    #[test]
+    fn test_instructions_in_harness() {
+        use crate::test_harness::ProofProgramHarness;
+
+        fn process(harness: &mut ProofProgramHarness, instructions: &ProofInstructionSet) {
+            for (instruction, len) in instructions
+                .verify_instructions
+                .iter()
+                .zip(&instructions.context_state_lens)
+            {
+                // the context state account is the first account of a verify instruction
+                harness.create_context_state_account(instruction.accounts[0].pubkey, *len, 1_000);
+                assert_eq!(harness.process_instruction(instruction), Ok(()));
+            }
+            for instruction in &instructions.close_instructions {
+                assert_eq!(harness.process_instruction(instruction), Ok(()));
+            }
+        }
+
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let account = new_account(&keypair, &aes_key, 1_000);
+
+        let context_state_authority = Address::new_unique();
+        let equality_account = Address::new_unique();
+        let validity_account = Address::new_unique();
+        let range_account = Address::new_unique();
+        let equality_proof = ContextStateInfo {
+            context_state_account: &equality_account,
+            context_state_authority: &context_state_authority,
+        };
+        let range_proof = ContextStateInfo {
+            context_state_account: &range_account,
+            context_state_authority: &context_state_authority,
+        };
+        let close_destination = Address::new_unique();
+
+        let mut harness = ProofProgramHarness::new();
+
+        let withdraw = account
+            .withdraw(
+                &keypair,
+                &aes_key,
+                100,
+                WithdrawContextStateAccounts {
+                    equality_proof,
+                    range_proof,
+                },
+                &close_destination,
+            )
+            .unwrap();
+        process(&mut harness, withdraw.instructions.as_ref().unwrap());
+
+        let transfer = account
+            .transfer(
+                &keypair,
+                &aes_key,
+                100,
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+                TransferContextStateAccounts {
+                    equality_proof,
+                    ciphertext_validity_proof: ContextStateInfo {
+                        context_state_account: &validity_account,
+                        context_state_authority: &context_state_authority,
+                    },
+                    range_proof,
+                },
+                &close_destination,
+            )
+            .unwrap();
+        process(&mut harness, transfer.instructions.as_ref().unwrap());
+
+        assert_eq!(harness.account(&close_destination).unwrap().lamports, 5_000);
+    }
+
+    #[test]
+    fn test_burn() {
+        let keypair = ElGamalKeypair::new_rand();
+        let aes_key = AeKey::new_rand();
+        let supply_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let account = new_account(&keypair, &aes_key, 500);
+
+        let context_state_authority = Address::new_unique();
+        let equality_account = Address::new_unique();
+        let validity_account = Address::new_unique();
+        let range_account = Address::new_unique();
+        let context_state_accounts = BurnContextStateAccounts {
+            equality_proof: ContextStateInfo {
+                context_state_account: &equality_account,
+                context_state_authority: &context_state_authority,
+            },
+            ciphertext_validity_proof: ContextStateInfo {
+                context_state_account: &validity_account,
+                context_state_authority: &context_state_authority,
+            },
+            range_proof: ContextStateInfo {
+                context_state_account: &range_account,
+                context_state_authority: &context_state_authority,
+            },
+        };
+
+        let burn = account
+            .burn(
+                &keypair,
+                &aes_key,
+                300,
+                supply_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+                context_state_accounts,
+                &context_state_authority,
+            )
+            .unwrap();
+
+        assert!(burn.equality_proof_data.verify_proof().is_ok());
+        assert!(burn.ciphertext_validity_proof_data.verify_proof().is_ok());
+        assert!(burn.range_proof_data.verify_proof().is_ok());
+        assert!(burn.verify_all().is_ok());
+        assert_eq!(
+            burn.instructions
+                .as_ref()
+                .unwrap()
+                .verify_instructions
+                .len(),
+            3
+        );
+        assert_eq!(burn.new_state.decrypt_available_balance(&aes_key), Ok(200));
+
+        // the supply handle is at index 1
+        assert_eq!(
+            burn.burn_amount_lo
+                .to_elgamal_ciphertext(1)
+                .unwrap()
+                .decrypt_u32(supply_keypair.secret()),
+            Some(300)
+        );
+
+        assert_eq!(
+            account
+                .burn(
+                    &keypair,
+                    &aes_key,
+                    501,
+                    supply_keypair.pubkey(),
+                    auditor_keypair.pubkey(),
+                    context_state_accounts,
+                    &context_state_authority,
+                )
+                .unwrap_err(),
+            ClientError::InsufficientFunds
+        );
+    }
+
+    #[test]
+    fn test_mint() {
+        let supply_keypair = ElGamalKeypair::new_rand();
+        let supply_aes_key = AeKey::new_rand();
+        let destination_keypair = ElGamalKeypair::new_rand();
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let supply = EncryptedSupplyState {
+            current_supply: supply_keypair.pubkey().encrypt(1_000_u64),
+            decryptable_supply: supply_aes_key.encrypt(1_000),
+        };
+
+        let context_state_authority = Address::new_unique();
+        let equality_account = Address::new_unique();
+        let validity_account = Address::new_unique();
+        let range_account = Address::new_unique();
+        let context_state_accounts = MintContextStateAccounts {
+            equality_proof: ContextStateInfo {
+                context_state_account: &equality_account,
+                context_state_authority: &context_state_authority,
+            },
+            ciphertext_validity_proof: ContextStateInfo {
+                context_state_account: &validity_account,
+                context_state_authority: &context_state_authority,
+            },
+            range_proof: ContextStateInfo {
+                context_state_account: &range_account,
+                context_state_authority: &context_state_authority,
+            },
+        };
+
+        let mint = supply
+            .mint(
+                &supply_keypair,
+                &supply_aes_key,
+                70_000,
+                destination_keypair.pubkey(),
+                auditor_keypair.pubkey(),
+                context_state_accounts,
+                &context_state_authority,
+            )
+            .unwrap();
+
+        assert!(mint.equality_proof_data.verify_proof().is_ok());
+        assert!(mint.ciphertext_validity_proof_data.verify_proof().is_ok());
+        assert!(mint.range_proof_data.verify_proof().is_ok());
+        assert!(mint.verify_all().is_ok());
+        assert_eq!(
+            mint.instructions
+                .as_ref()
+                .unwrap()
+                .verify_instructions
+                .len(),
+            3
+        );
+        assert_eq!(
+            mint.new_supply_state.decrypt_supply(&supply_aes_key),
+            Ok(71_000)
+        );
+        assert_eq!(
+            mint.new_supply_state
+                .current_supply
+                .decrypt_u32(supply_keypair.secret()),
+            Some(71_000)
+        );
+
+        // the destination is credited with the same amount that is added to the supply
+        let destination_lo = mint
+            .mint_amount_lo
+            .to_elgamal_ciphertext(0)
+            .unwrap()
+            .decrypt_u32(destination_keypair.secret())
+            .unwrap();
+        let destination_hi = mint
+            .mint_amount_hi
+            .to_elgamal_ciphertext(0)
+            .unwrap()
+            .decrypt_u32(destination_keypair.secret())
+            .unwrap();
+        assert_eq!(
+            destination_lo + (destination_hi << TRANSFER_AMOUNT_LO_BIT_LENGTH),
+            70_000
+        );
+    }
+}
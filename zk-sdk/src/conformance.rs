@@ -0,0 +1,381 @@
+//! Conformance checks for proof bytes that are produced by other implementations.
+//!
+//! [`check`] runs a proof and its statement (the proof context) through the same stages that the
+//! ZK ElGamal proof program goes through when it verifies an instruction, and reports the first
+//! stage that fails:
+//!
+//! 1. [`ConformanceStage::Parsing`]: the proof and the statement have the byte lengths of the
+//!    proof type.
+//! 2. [`ConformanceStage::Canonicality`]: every point in the proof is a valid Ristretto encoding
+//!    and every scalar in the proof is canonically reduced.
+//! 3. [`ConformanceStage::Transcript`]: the statement decodes into the public keys, ciphertexts,
+//!    and commitments that are hashed into the transcript labeled with
+//!    [`ConformanceReport::transcript_label`].
+//! 4. [`ConformanceStage::Verification`]: the proof verifies against the statement.
+//!
+//! The transcript is reconstructed as part of verification, so a statement that cannot be
+//! decoded is reported from the verification error that it causes.
+
+#[cfg(feature = "unstable")]
+use crate::sigma_proofs::{
+    ciphertext_commitment_opening_equality::CiphertextCommitmentOpeningEqualityProof,
+    grouped_zero_ciphertext::GroupedZeroCiphertextProof,
+    percentage_with_cap_split::PercentageWithCapSplitProof,
+    split_consistency::SplitConsistencyProof,
+};
+use {
+    crate::{
+        range_proof::range::RangeProof,
+        sigma_proofs::{
+            batched_grouped_ciphertext_validity::{
+                BatchedGroupedCiphertext2HandlesValidityProof,
+                BatchedGroupedCiphertext3HandlesValidityProof,
+            },
+            ciphertext_ciphertext_equality::CiphertextCiphertextEqualityProof,
+            ciphertext_commitment_equality::CiphertextCommitmentEqualityProof,
+            grouped_ciphertext_validity::{
+                GroupedCiphertext2HandlesValidityProof, GroupedCiphertext3HandlesValidityProof,
+            },
+            percentage_with_cap::PercentageWithCapProof,
+            pubkey_validity::PubkeyValidityProof,
+            zero_ciphertext::ZeroCiphertextProof,
+        },
+        zk_elgamal_proof_program::{errors::ProofVerificationError, VerifyZkProof},
+    },
+    bytemuck::Pod,
+    solana_zk_elgamal_proof_interface::proof_data::*,
+    std::mem::size_of,
+};
+
+/// A stage of the conformance check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConformanceStage {
+    Parsing,
+    Canonicality,
+    Transcript,
+    Verification,
+}
+
+/// The stage at which a conformance check failed and the reason for the failure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConformanceFailure {
+    pub stage: ConformanceStage,
+    pub reason: String,
+}
+
+/// The result of a conformance check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// The proof type that the bytes were checked against
+    pub proof_type: ProofType,
+    /// The label of the transcript that the proof type is verified under
+    pub transcript_label: Option<&'static [u8]>,
+    /// The stages that passed, in order
+    pub passed: Vec<ConformanceStage>,
+    /// The stage that failed, if any
+    pub failure: Option<ConformanceFailure>,
+}
+
+impl ConformanceReport {
+    /// Returns `true` if every stage passed.
+    pub fn is_conforming(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Checks that `proof_bytes` is a valid proof of type `proof_type` for the statement encoded by
+/// `statement_bytes`.
+///
+/// The statement is the byte encoding of the proof context of the proof type. The proof data of
+/// an instruction is the statement followed by the proof.
+pub fn check(
+    proof_type: ProofType,
+    proof_bytes: &[u8],
+    statement_bytes: &[u8],
+) -> ConformanceReport {
+    let mut report = ConformanceReport {
+        proof_type,
+        transcript_label: transcript_label(proof_type),
+        passed: vec![],
+        failure: None,
+    };
+
+    macro_rules! check_as {
+        ($data:ty, $proof:ty) => {
+            check_as::<$data, _>(&mut report, proof_bytes, statement_bytes, |data| {
+                <$proof>::try_from(data.proof)
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            })
+        };
+    }
+
+    match proof_type {
+        ProofType::Uninitialized => report.fail(
+            ConformanceStage::Parsing,
+            "uninitialized proof type has no proof data".to_string(),
+        ),
+        ProofType::ZeroCiphertext => check_as!(ZeroCiphertextProofData, ZeroCiphertextProof),
+        ProofType::CiphertextCiphertextEquality => check_as!(
+            CiphertextCiphertextEqualityProofData,
+            CiphertextCiphertextEqualityProof
+        ),
+        ProofType::CiphertextCommitmentEquality => check_as!(
+            CiphertextCommitmentEqualityProofData,
+            CiphertextCommitmentEqualityProof
+        ),
+        ProofType::PubkeyValidity => check_as!(PubkeyValidityProofData, PubkeyValidityProof),
+        ProofType::PercentageWithCap => {
+            check_as!(PercentageWithCapProofData, PercentageWithCapProof)
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapU128 => {
+            check_as!(PercentageWithCapU128ProofData, PercentageWithCapProof)
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapSplit => {
+            check_as!(PercentageWithCapSplitProofData, PercentageWithCapSplitProof)
+        }
+        ProofType::BatchedRangeProofU64 => check_as!(BatchedRangeProofU64Data, RangeProof),
+        ProofType::BatchedRangeProofU128 => check_as!(BatchedRangeProofU128Data, RangeProof),
+        ProofType::BatchedRangeProofU256 => check_as!(BatchedRangeProofU256Data, RangeProof),
+        ProofType::GroupedCiphertext2HandlesValidity => check_as!(
+            GroupedCiphertext2HandlesValidityProofData,
+            GroupedCiphertext2HandlesValidityProof
+        ),
+        ProofType::GroupedCiphertext3HandlesValidity => check_as!(
+            GroupedCiphertext3HandlesValidityProofData,
+            GroupedCiphertext3HandlesValidityProof
+        ),
+        ProofType::BatchedGroupedCiphertext2HandlesValidity => check_as!(
+            BatchedGroupedCiphertext2HandlesValidityProofData,
+            BatchedGroupedCiphertext2HandlesValidityProof
+        ),
+        ProofType::BatchedGroupedCiphertext3HandlesValidity => check_as!(
+            BatchedGroupedCiphertext3HandlesValidityProofData,
+            BatchedGroupedCiphertext3HandlesValidityProof
+        ),
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext2HandlesCiphertextEquality => check_as!(
+            GroupedCiphertext2HandlesCiphertextEqualityProofData,
+            CiphertextCiphertextEqualityProof
+        ),
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext3HandlesCiphertextEquality => check_as!(
+            GroupedCiphertext3HandlesCiphertextEqualityProofData,
+            CiphertextCiphertextEqualityProof
+        ),
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext2Handles => check_as!(
+            GroupedZeroCiphertext2HandlesProofData,
+            GroupedZeroCiphertextProof<2>
+        ),
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext3Handles => check_as!(
+            GroupedZeroCiphertext3HandlesProofData,
+            GroupedZeroCiphertextProof<3>
+        ),
+        #[cfg(feature = "unstable")]
+        ProofType::SplitConsistency => {
+            check_as!(SplitConsistencyProofData, SplitConsistencyProof)
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::CiphertextCommitmentOpeningEquality => check_as!(
+            CiphertextCommitmentOpeningEqualityProofData,
+            CiphertextCommitmentOpeningEqualityProof
+        ),
+    }
+
+    report
+}
+
+/// The label of the transcript that a proof type is verified under.
+pub fn transcript_label(proof_type: ProofType) -> Option<&'static [u8]> {
+    let label: &'static [u8] = match proof_type {
+        ProofType::Uninitialized => return None,
+        ProofType::ZeroCiphertext => b"zero-ciphertext-instruction",
+        ProofType::CiphertextCiphertextEquality => b"ciphertext-ciphertext-equality-instruction",
+        ProofType::CiphertextCommitmentEquality => b"ciphertext-commitment-equality-instruction",
+        ProofType::PubkeyValidity => b"pubkey-validity-instruction",
+        ProofType::PercentageWithCap => b"percentage-with-cap-instruction",
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapU128 => b"percentage-with-cap-u128-instruction",
+        #[cfg(feature = "unstable")]
+        ProofType::PercentageWithCapSplit => b"percentage-with-cap-split-instruction",
+        ProofType::BatchedRangeProofU64
+        | ProofType::BatchedRangeProofU128
+        | ProofType::BatchedRangeProofU256 => b"batched-range-proof-instruction",
+        ProofType::GroupedCiphertext2HandlesValidity => {
+            b"grouped-ciphertext-validity-2-handles-instruction"
+        }
+        ProofType::GroupedCiphertext3HandlesValidity => {
+            b"grouped-ciphertext-validity-3-handles-instruction"
+        }
+        ProofType::BatchedGroupedCiphertext2HandlesValidity => {
+            b"batched-grouped-ciphertext-validity-2-handles-instruction"
+        }
+        ProofType::BatchedGroupedCiphertext3HandlesValidity => {
+            b"batched-grouped-ciphertext-validity-3-handles-instruction"
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext2HandlesCiphertextEquality => {
+            b"grouped-ciphertext-2-handles-ciphertext-equality-instruction"
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedCiphertext3HandlesCiphertextEquality => {
+            b"grouped-ciphertext-3-handles-ciphertext-equality-instruction"
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext2Handles => {
+            b"grouped-zero-ciphertext-2-handles-instruction"
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::GroupedZeroCiphertext3Handles => {
+            b"grouped-zero-ciphertext-3-handles-instruction"
+        }
+        #[cfg(feature = "unstable")]
+        ProofType::SplitConsistency => b"split-consistency-instruction",
+        #[cfg(feature = "unstable")]
+        ProofType::CiphertextCommitmentOpeningEquality => {
+            b"ciphertext-commitment-opening-equality-instruction"
+        }
+    };
+    Some(label)
+}
+
+impl ConformanceReport {
+    fn pass(&mut self, stage: ConformanceStage) {
+        self.passed.push(stage);
+    }
+
+    fn fail(&mut self, stage: ConformanceStage, reason: String) {
+        self.failure = Some(ConformanceFailure { stage, reason });
+    }
+}
+
+fn check_as<T, C>(
+    report: &mut ConformanceReport,
+    proof_bytes: &[u8],
+    statement_bytes: &[u8],
+    check_canonical: impl FnOnce(&T) -> Result<(), String>,
+) where
+    T: Pod + VerifyZkProof + ZkProofData<C>,
+    C: Pod,
+{
+    // the proof data is the context followed by the proof
+    let statement_len = size_of::<C>();
+    let proof_len = size_of::<T>() - statement_len;
+    if statement_bytes.len() != statement_len {
+        return report.fail(
+            ConformanceStage::Parsing,
+            format!(
+                "statement is {} bytes, expected {statement_len}",
+                statement_bytes.len()
+            ),
+        );
+    }
+    if proof_bytes.len() != proof_len {
+        return report.fail(
+            ConformanceStage::Parsing,
+            format!("proof is {} bytes, expected {proof_len}", proof_bytes.len()),
+        );
+    }
+    let proof_data_bytes = [statement_bytes, proof_bytes].concat();
+    let proof_data = bytemuck::pod_read_unaligned::<T>(&proof_data_bytes);
+    report.pass(ConformanceStage::Parsing);
+
+    if let Err(reason) = check_canonical(&proof_data) {
+        return report.fail(ConformanceStage::Canonicality, reason);
+    }
+    report.pass(ConformanceStage::Canonicality);
+
+    match proof_data.verify_proof() {
+        Ok(()) => {
+            report.pass(ConformanceStage::Transcript);
+            report.pass(ConformanceStage::Verification);
+        }
+        Err(err @ (ProofVerificationError::ElGamal(_) | ProofVerificationError::ProofContext)) => {
+            report.fail(ConformanceStage::Transcript, err.to_string())
+        }
+        Err(err) => {
+            report.pass(ConformanceStage::Transcript);
+            report.fail(ConformanceStage::Verification, err.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair, vectors::PROOF_VECTORS,
+            zk_elgamal_proof_program::build_zero_ciphertext_proof_data,
+        },
+        base64::{prelude::BASE64_STANDARD, Engine},
+    };
+
+    #[test]
+    fn test_conformance_check() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+        let statement = bytemuck::bytes_of(&proof_data.context).to_vec();
+        let proof = bytemuck::bytes_of(&proof_data.proof).to_vec();
+
+        let report = check(ProofType::ZeroCiphertext, &proof, &statement);
+        assert!(report.is_conforming());
+        assert_eq!(
+            report.transcript_label,
+            Some(b"zero-ciphertext-instruction".as_slice())
+        );
+        assert_eq!(report.passed.len(), 4);
+
+        // wrong proof type
+        let report = check(ProofType::PubkeyValidity, &proof, &statement);
+        assert_eq!(report.failure.unwrap().stage, ConformanceStage::Parsing);
+
+        // non-canonical scalar in the last 32 bytes of the proof
+        let mut non_canonical = proof.clone();
+        non_canonical[64..].copy_from_slice(&[0xff; 32]);
+        let report = check(ProofType::ZeroCiphertext, &non_canonical, &statement);
+        assert_eq!(
+            report.failure.unwrap().stage,
+            ConformanceStage::Canonicality
+        );
+
+        // statement with an invalid public key encoding
+        let mut invalid_statement = statement.clone();
+        invalid_statement[..32].copy_from_slice(&[0xff; 32]);
+        let report = check(ProofType::ZeroCiphertext, &proof, &invalid_statement);
+        assert_eq!(report.failure.unwrap().stage, ConformanceStage::Transcript);
+
+        // statement for a different ciphertext
+        let other_ciphertext = keypair.pubkey().encrypt(0_u64);
+        let mut other_statement = statement.clone();
+        other_statement[32..].copy_from_slice(&other_ciphertext.to_bytes());
+        let report = check(ProofType::ZeroCiphertext, &proof, &other_statement);
+        assert_eq!(report.passed.len(), 3);
+        assert_eq!(
+            report.failure.unwrap().stage,
+            ConformanceStage::Verification
+        );
+    }
+
+    #[test]
+    fn test_conformance_check_vectors() {
+        #[cfg(feature = "unstable")]
+        let vectors = PROOF_VECTORS
+            .iter()
+            .chain(crate::vectors::UNSTABLE_PROOF_VECTORS);
+        #[cfg(not(feature = "unstable"))]
+        let vectors = PROOF_VECTORS.iter();
+        for vector in vectors {
+            let statement = BASE64_STANDARD.decode(vector.context).unwrap();
+            let proof = BASE64_STANDARD.decode(vector.proof).unwrap();
+            let report = check(vector.proof_type, &proof, &statement);
+            assert!(report.is_conforming(), "{}", vector.name);
+            assert_eq!(report.transcript_label, Some(vector.transcript_label));
+        }
+    }
+}
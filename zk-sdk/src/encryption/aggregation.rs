@@ -0,0 +1,183 @@
+//! Overflow-aware aggregation of ElGamal ciphertexts.
+//!
+//! The sum of `n` ciphertexts of amounts in the range `[0, 2^b)` is a ciphertext of an amount in
+//! the range `[0, n * 2^b)`. A protocol that certifies the amounts of the individual ciphertexts
+//! with range proofs can therefore rely on a bound for the sum only as long as it bounds the
+//! number of terms. A [`CiphertextAggregate`] allows at most `2^max_terms_bits` terms, so the
+//! aggregated amount lies in the range `[0, 2^(b + max_terms_bits))`. Once the limit is reached,
+//! the amount of the aggregate must be certified with a fresh range proof before the aggregate is
+//! consolidated into a single term and more ciphertexts are added.
+
+use {
+    crate::{encryption::elgamal::ElGamalCiphertext, errors::AggregationError},
+    std::borrow::Borrow,
+};
+
+/// The maximum bit length of the number of terms in an aggregate.
+pub const MAX_TERMS_BIT_LENGTH: u32 = 63;
+
+/// The number of terms in an aggregate and the limit on the number of terms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregationMetadata {
+    /// The number of ciphertexts that were added to the aggregate
+    pub num_terms: u64,
+    /// The maximum number of ciphertexts that can be added to the aggregate
+    pub max_terms: u64,
+}
+
+impl AggregationMetadata {
+    /// The number of ciphertexts that can still be added to the aggregate.
+    pub fn remaining_terms(&self) -> u64 {
+        self.max_terms - self.num_terms
+    }
+
+    /// Returns `true` if the aggregate must be consolidated before more ciphertexts are added.
+    pub fn needs_consolidation(&self) -> bool {
+        self.num_terms == self.max_terms
+    }
+
+    /// The bit length of the range of the aggregated amount, if the amount of each term lies in
+    /// the range `[0, 2^term_bit_length)`.
+    ///
+    /// This is the bit length that a range proof on the aggregate must certify to consolidate it.
+    pub fn amount_bit_length(&self, term_bit_length: u32) -> u32 {
+        let terms_bit_length = match self.num_terms {
+            0 | 1 => 0,
+            n => u64::BITS - (n - 1).leading_zeros(),
+        };
+        term_bit_length + terms_bit_length
+    }
+}
+
+/// A running sum of ElGamal ciphertexts with a limit on the number of terms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CiphertextAggregate {
+    ciphertext: ElGamalCiphertext,
+    num_terms: u64,
+    max_terms: u64,
+}
+
+impl CiphertextAggregate {
+    /// Creates an empty aggregate of at most `2^max_terms_bits` terms.
+    pub fn new(max_terms_bits: u32) -> Result<Self, AggregationError> {
+        if max_terms_bits > MAX_TERMS_BIT_LENGTH {
+            return Err(AggregationError::InvalidTermsBitLength);
+        }
+        Ok(Self {
+            ciphertext: ElGamalCiphertext::default(),
+            num_terms: 0,
+            max_terms: 1 << max_terms_bits,
+        })
+    }
+
+    /// Adds a ciphertext to the aggregate.
+    ///
+    /// Returns [`AggregationError::TermLimitExceeded`] and leaves the aggregate unchanged if the
+    /// aggregate already holds the maximum number of terms.
+    pub fn add(&mut self, ciphertext: &ElGamalCiphertext) -> Result<(), AggregationError> {
+        if self.num_terms == self.max_terms {
+            return Err(AggregationError::TermLimitExceeded);
+        }
+        self.ciphertext += ciphertext;
+        self.num_terms += 1;
+        Ok(())
+    }
+
+    /// Marks the aggregate as consolidated into a single term.
+    ///
+    /// This should only be called once the amount of the aggregate has been certified with a
+    /// range proof of the bit length that the terms are assumed to have.
+    pub fn consolidate(&mut self) {
+        self.num_terms = self.num_terms.min(1);
+    }
+
+    /// The aggregated ciphertext.
+    pub fn ciphertext(&self) -> &ElGamalCiphertext {
+        &self.ciphertext
+    }
+
+    /// The number of terms in the aggregate and the limit on the number of terms.
+    pub fn metadata(&self) -> AggregationMetadata {
+        AggregationMetadata {
+            num_terms: self.num_terms,
+            max_terms: self.max_terms,
+        }
+    }
+}
+
+/// Sums ciphertexts into an aggregate of at most `2^max_terms_bits` terms.
+///
+/// Returns [`AggregationError::TermLimitExceeded`] if there are more ciphertexts than the limit.
+pub fn sum_ciphertexts_checked<I>(
+    ciphertexts: I,
+    max_terms_bits: u32,
+) -> Result<CiphertextAggregate, AggregationError>
+where
+    I: IntoIterator,
+    I::Item: Borrow<ElGamalCiphertext>,
+{
+    let mut aggregate = CiphertextAggregate::new(max_terms_bits)?;
+    for ciphertext in ciphertexts {
+        aggregate.add(ciphertext.borrow())?;
+    }
+    Ok(aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_sum_ciphertexts_checked() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertexts: Vec<_> = (1..=4_u64)
+            .map(|amount| keypair.pubkey().encrypt(amount))
+            .collect();
+
+        let aggregate = sum_ciphertexts_checked(&ciphertexts, 2).unwrap();
+        assert_eq!(
+            keypair.secret().decrypt_u32(aggregate.ciphertext()),
+            Some(10)
+        );
+        let metadata = aggregate.metadata();
+        assert_eq!(metadata.num_terms, 4);
+        assert_eq!(metadata.remaining_terms(), 0);
+        assert!(metadata.needs_consolidation());
+        assert_eq!(metadata.amount_bit_length(48), 50);
+
+        assert_eq!(
+            sum_ciphertexts_checked(&ciphertexts, 1),
+            Err(AggregationError::TermLimitExceeded)
+        );
+        assert_eq!(
+            sum_ciphertexts_checked(&ciphertexts, MAX_TERMS_BIT_LENGTH + 1),
+            Err(AggregationError::InvalidTermsBitLength)
+        );
+    }
+
+    #[test]
+    fn test_aggregate_consolidation() {
+        let keypair = ElGamalKeypair::new_rand();
+        let mut aggregate = CiphertextAggregate::new(1).unwrap();
+        assert_eq!(aggregate.metadata().amount_bit_length(32), 32);
+
+        aggregate.add(&keypair.pubkey().encrypt(1_u64)).unwrap();
+        aggregate.add(&keypair.pubkey().encrypt(2_u64)).unwrap();
+        assert_eq!(aggregate.metadata().amount_bit_length(32), 33);
+
+        let full = aggregate;
+        assert_eq!(
+            aggregate.add(&keypair.pubkey().encrypt(3_u64)),
+            Err(AggregationError::TermLimitExceeded)
+        );
+        assert_eq!(aggregate, full);
+
+        aggregate.consolidate();
+        assert_eq!(aggregate.metadata().remaining_terms(), 1);
+        aggregate.add(&keypair.pubkey().encrypt(3_u64)).unwrap();
+        assert_eq!(
+            keypair.secret().decrypt_u32(aggregate.ciphertext()),
+            Some(6)
+        );
+    }
+}
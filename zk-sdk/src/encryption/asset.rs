@@ -0,0 +1,183 @@
+//! Asset tags for multi-asset confidential amounts.
+//!
+//! A confidential pool that holds several assets derives a generator `J_a` for each asset `a` by
+//! hash-to-group. To hide which asset an amount belongs to, the generator is blinded with a
+//! random scalar `b` into an asset tag `T = J_a + b*G`. An amount `v` is committed with respect to
+//! the tag as the value generator:
+//!
+//! ```text
+//! C = v*T + r*H = v*J_a + (v*b)*G + r*H
+//! ```
+//!
+//! A commitment of this form is an ordinary Pedersen commitment with respect to the generators
+//! `(T, H)`, so range proofs with custom Pedersen generators apply to it directly. Two tags of the
+//! same asset differ by a multiple of `G`, which an asset equality proof certifies without
+//! revealing the asset.
+
+use {
+    crate::{
+        encryption::pedersen::{PedersenCommitment, PedersenGenerators, PedersenOpening, G, H},
+        errors::RngError,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+    },
+    curve25519_dalek::{
+        ristretto::{CompressedRistretto, RistrettoPoint},
+        scalar::Scalar,
+    },
+    sha3::{Digest, Sha3_512},
+    std::fmt,
+    zeroize::{Zeroize, ZeroizeOnDrop},
+};
+
+/// Domain separator for the hash-to-group derivation of asset generators.
+const ASSET_GENERATOR_DOMAIN: &[u8] = b"solana-zk-sdk-asset-generator";
+
+/// Byte length of an asset identifier.
+pub const ASSET_ID_LEN: usize = 32;
+
+/// Byte length of an asset tag.
+pub const ASSET_TAG_LEN: usize = 32;
+
+/// The identifier of an asset, such as the address of its mint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssetId(pub [u8; ASSET_ID_LEN]);
+
+impl AssetId {
+    /// Derives the generator of the asset via hash-to-group.
+    ///
+    /// The discrete log relation between the generators of distinct assets, or between an asset
+    /// generator and the Pedersen base points, is unknown.
+    pub fn generator(&self) -> RistrettoPoint {
+        let mut hasher = Sha3_512::new();
+        hasher.update(ASSET_GENERATOR_DOMAIN);
+        hasher.update(self.0);
+        RistrettoPoint::from_hash(hasher)
+    }
+}
+
+/// The blinding factor of an asset tag.
+#[derive(Clone, Default, Zeroize, ZeroizeOnDrop)]
+pub struct AssetTagOpening(Scalar);
+
+impl AssetTagOpening {
+    pub fn new(scalar: Scalar) -> Self {
+        Self(scalar)
+    }
+
+    pub fn get_scalar(&self) -> &Scalar {
+        &self.0
+    }
+
+    /// Generates a random blinding factor.
+    ///
+    /// This function is randomized. It internally samples a scalar element using `OsRng`.
+    pub fn new_rand() -> Self {
+        unwrap_default_rng(Self::new_rand_with_rng(&mut DefaultRng))
+    }
+
+    /// Generates a random blinding factor using `rng`.
+    pub fn new_rand_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, RngError> {
+        Ok(Self(try_random_scalar(rng)?))
+    }
+}
+
+impl fmt::Debug for AssetTagOpening {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AssetTagOpening")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+/// A blinded asset generator `T = J_a + b*G`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssetTag(RistrettoPoint);
+
+impl AssetTag {
+    /// Blinds the generator of an asset with an opening.
+    pub fn new(asset_id: &AssetId, opening: &AssetTagOpening) -> Self {
+        Self(asset_id.generator() + opening.get_scalar() * G)
+    }
+
+    /// Blinds the generator of an asset with a random opening.
+    ///
+    /// This function is randomized. It internally samples an opening using `OsRng`.
+    pub fn new_rand(asset_id: &AssetId) -> (Self, AssetTagOpening) {
+        let opening = AssetTagOpening::new_rand();
+        (Self::new(asset_id, &opening), opening)
+    }
+
+    pub fn get_point(&self) -> &RistrettoPoint {
+        &self.0
+    }
+
+    /// The Pedersen generators `(T, H)` of commitments with respect to the tag.
+    pub fn pedersen_generators(&self) -> PedersenGenerators {
+        PedersenGenerators::from_points(self.0, *H)
+    }
+
+    /// Commits to an amount with respect to the tag.
+    ///
+    /// This function is deterministic.
+    pub fn commit_with<T: Into<Scalar>>(
+        &self,
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> PedersenCommitment {
+        self.pedersen_generators().commit_with(amount, opening)
+    }
+
+    /// Commits to an amount with respect to the tag under a random opening.
+    ///
+    /// This function is randomized. It internally samples a Pedersen opening using `OsRng`.
+    pub fn commit<T: Into<Scalar>>(&self, amount: T) -> (PedersenCommitment, PedersenOpening) {
+        self.pedersen_generators().commit(amount)
+    }
+
+    pub fn to_bytes(&self) -> [u8; ASSET_TAG_LEN] {
+        self.0.compress().to_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != ASSET_TAG_LEN {
+            return None;
+        }
+        let Ok(compressed_ristretto) = CompressedRistretto::from_slice(bytes) else {
+            return None;
+        };
+
+        compressed_ristretto.decompress().map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_tag_commitments() {
+        let asset_id = AssetId([1; ASSET_ID_LEN]);
+        let (tag, tag_opening) = AssetTag::new_rand(&asset_id);
+        assert_eq!(tag, AssetTag::new(&asset_id, &tag_opening));
+        assert_ne!(tag, AssetTag::new_rand(&asset_id).0);
+        assert_eq!(AssetTag::from_bytes(&tag.to_bytes()), Some(tag));
+
+        // a tagged commitment expands to `v*J_a + (v*b)*G + r*H`
+        let amount = 55_u64;
+        let (commitment, opening) = tag.commit(amount);
+        let expected = Scalar::from(amount) * asset_id.generator()
+            + (Scalar::from(amount) * tag_opening.get_scalar()) * G
+            + opening.get_scalar() * *H;
+        assert_eq!(*commitment.get_point(), expected);
+
+        // tags of distinct assets are unrelated
+        assert_ne!(
+            AssetId([1; ASSET_ID_LEN]).generator(),
+            AssetId([2; ASSET_ID_LEN]).generator()
+        );
+        assert_eq!(
+            format!("{tag_opening:?}"),
+            "AssetTagOpening(\"[REDACTED]\")"
+        );
+    }
+}
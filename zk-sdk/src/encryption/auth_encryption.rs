@@ -7,6 +7,7 @@ use {
     crate::{
         encryption::derivation::{AE_HKDF_INFO, HKDF_SALT},
         errors::AuthenticatedEncryptionError,
+        rng::DefaultRng,
     },
     aes_gcm_siv::{
         aead::{Aead, KeyInit},
@@ -14,7 +15,7 @@ use {
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     hkdf::Hkdf,
-    rand::{rngs::OsRng, Rng},
+    rand::Rng,
     sha2::Sha512,
     sha3::{Digest, Sha3_512},
     solana_derivation_path::DerivationPath,
@@ -46,14 +47,14 @@ impl AuthenticatedEncryption {
     ///
     /// This function is randomized. It internally samples a 128-bit key using `OsRng`.
     fn keygen() -> AeKey {
-        AeKey(OsRng.gen::<[u8; AE_KEY_LEN]>())
+        AeKey(DefaultRng.gen::<[u8; AE_KEY_LEN]>())
     }
 
     /// On input of an authenticated encryption key and an amount, the function returns a
     /// corresponding authenticated encryption ciphertext.
     fn encrypt(key: &AeKey, balance: u64) -> AeCiphertext {
         let plaintext = Zeroizing::new(balance.to_le_bytes());
-        let nonce: Nonce = OsRng.gen::<[u8; NONCE_LEN]>();
+        let nonce: Nonce = DefaultRng.gen::<[u8; NONCE_LEN]>();
 
         // The balance and the nonce have fixed length and therefore, encryption should not fail.
         let ciphertext = Aes128GcmSiv::new(&key.0.into())
@@ -94,6 +95,9 @@ impl AuthenticatedEncryption {
 pub struct AeKey([u8; AE_KEY_LEN]);
 
 impl AeKey {
+    /// Byte length of an authenticated encryption key.
+    pub const LEN: usize = AE_KEY_LEN;
+
     /// Derive an authenticated encryption key from a Solana signer using the
     /// legacy SHA3-512-based KDF.
     ///
@@ -337,6 +341,9 @@ pub struct AeCiphertext {
     ciphertext: Ciphertext,
 }
 impl AeCiphertext {
+    /// Byte length of an authenticated encryption ciphertext, including the nonce.
+    pub const LEN: usize = AE_CIPHERTEXT_LEN;
+
     pub fn decrypt(&self, key: &AeKey) -> Option<u64> {
         AuthenticatedEncryption::decrypt(key, self)
     }
@@ -0,0 +1,174 @@
+//! Compact ElGamal ciphertexts for known recipients.
+//!
+//! An ElGamal ciphertext consists of a Pedersen commitment and a decryption handle. If the sender
+//! and the recipient share a Diffie-Hellman shared secret (see
+//! [`crate::encryption::shared_secret`]), the Pedersen opening of the ciphertext can be derived
+//! from the shared secret. The recipient can then recompute the opening, and hence the decryption
+//! handle, locally. Only the 32-byte commitment needs to be sent over the wire.
+//!
+//! ```text
+//! opening = Scalar::from_bytes_mod_order_wide(
+//!               HKDF-Expand(shared_secret, info = COMPACT_CIPHERTEXT_HKDF_INFO || nonce, L = 64)
+//!           )
+//! ```
+//!
+//! The nonce must be unique for each encryption under the same shared secret. If two amounts are
+//! encrypted under the same nonce, then the difference of the commitments reveals the difference
+//! of the amounts.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{DecryptHandle, ElGamalCiphertext, ElGamalPubkey},
+            pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
+            shared_secret::SharedSecret,
+        },
+        errors::ElGamalError,
+    },
+    curve25519_dalek::scalar::Scalar,
+    solana_zk_sdk_pod::encryption::{pedersen::PodPedersenCommitment, PEDERSEN_COMMITMENT_LEN},
+    zeroize::Zeroizing,
+};
+
+/// HKDF info prefix for the derivation of the opening of a compact ciphertext.
+pub const COMPACT_CIPHERTEXT_HKDF_INFO: &[u8] = b"compact-ciphertext";
+
+/// A compact ElGamal ciphertext that consists of only the commitment component.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CompactElGamalCiphertext(PedersenCommitment);
+
+impl CompactElGamalCiphertext {
+    /// Encrypts an amount with an opening that is derived from a shared secret and a nonce.
+    ///
+    /// Returns the compact ciphertext along with the opening so that the sender can generate
+    /// proofs on the ciphertext.
+    pub fn encrypt(
+        shared_secret: &SharedSecret,
+        amount: u64,
+        nonce: &[u8],
+    ) -> Result<(Self, PedersenOpening), ElGamalError> {
+        let opening = compact_ciphertext_opening(shared_secret, nonce)?;
+        let commitment = Pedersen::with(amount, &opening);
+        Ok((Self(commitment), opening))
+    }
+
+    /// Recomputes the full ElGamal ciphertext under the public key of the recipient.
+    ///
+    /// The shared secret and the nonce must be the same as the ones that were used to create the
+    /// compact ciphertext.
+    pub fn expand(
+        &self,
+        shared_secret: &SharedSecret,
+        recipient_pubkey: &ElGamalPubkey,
+        nonce: &[u8],
+    ) -> Result<ElGamalCiphertext, ElGamalError> {
+        let opening = compact_ciphertext_opening(shared_secret, nonce)?;
+        Ok(ElGamalCiphertext {
+            commitment: self.0,
+            handle: DecryptHandle::new(recipient_pubkey, &opening),
+        })
+    }
+
+    pub fn get_commitment(&self) -> &PedersenCommitment {
+        &self.0
+    }
+
+    pub fn to_bytes(&self) -> [u8; PEDERSEN_COMMITMENT_LEN] {
+        self.0.to_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        PedersenCommitment::from_bytes(bytes).map(Self)
+    }
+}
+
+impl From<ElGamalCiphertext> for CompactElGamalCiphertext {
+    fn from(ciphertext: ElGamalCiphertext) -> Self {
+        Self(ciphertext.commitment)
+    }
+}
+
+impl From<CompactElGamalCiphertext> for PodPedersenCommitment {
+    fn from(decoded_ciphertext: CompactElGamalCiphertext) -> Self {
+        decoded_ciphertext.0.into()
+    }
+}
+
+impl TryFrom<PodPedersenCommitment> for CompactElGamalCiphertext {
+    type Error = ElGamalError;
+
+    fn try_from(pod_commitment: PodPedersenCommitment) -> Result<Self, Self::Error> {
+        pod_commitment.try_into().map(Self)
+    }
+}
+
+/// Derives the Pedersen opening of a compact ciphertext from a shared secret and a nonce.
+fn compact_ciphertext_opening(
+    shared_secret: &SharedSecret,
+    nonce: &[u8],
+) -> Result<PedersenOpening, ElGamalError> {
+    let info = [COMPACT_CIPHERTEXT_HKDF_INFO, nonce].concat();
+
+    let mut wide = Zeroizing::new([0u8; 64]);
+    shared_secret.derive_key(&info, wide.as_mut_slice())?;
+    Ok(PedersenOpening::new(Scalar::from_bytes_mod_order_wide(
+        &wide,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_compact_ciphertext_correctness() {
+        let sender = ElGamalKeypair::new_rand();
+        let recipient = ElGamalKeypair::new_rand();
+
+        let sender_shared_secret = sender.secret().diffie_hellman(recipient.pubkey()).unwrap();
+        let (compact_ciphertext, opening) =
+            CompactElGamalCiphertext::encrypt(&sender_shared_secret, 55, b"nonce").unwrap();
+
+        // only the commitment is transmitted
+        let pod_commitment: PodPedersenCommitment = compact_ciphertext.into();
+        let received: CompactElGamalCiphertext = pod_commitment.try_into().unwrap();
+
+        let recipient_shared_secret = recipient.secret().diffie_hellman(sender.pubkey()).unwrap();
+        let ciphertext = received
+            .expand(&recipient_shared_secret, recipient.pubkey(), b"nonce")
+            .unwrap();
+
+        assert_eq!(
+            ciphertext,
+            recipient.pubkey().encrypt_with(55_u64, &opening)
+        );
+        assert_eq!(recipient.secret().decrypt_u32(&ciphertext), Some(55));
+        assert_eq!(
+            CompactElGamalCiphertext::from(ciphertext),
+            compact_ciphertext
+        );
+
+        // a different nonce does not recover the ciphertext
+        let other_ciphertext = received
+            .expand(&recipient_shared_secret, recipient.pubkey(), b"other-nonce")
+            .unwrap();
+        assert_ne!(other_ciphertext, ciphertext);
+    }
+
+    #[test]
+    fn test_compact_ciphertext_bytes() {
+        let sender = ElGamalKeypair::new_rand();
+        let recipient = ElGamalKeypair::new_rand();
+
+        let shared_secret = sender.secret().diffie_hellman(recipient.pubkey()).unwrap();
+        let (compact_ciphertext, _) =
+            CompactElGamalCiphertext::encrypt(&shared_secret, 77, b"nonce").unwrap();
+
+        let encoded = compact_ciphertext.to_bytes();
+        let decoded = CompactElGamalCiphertext::from_bytes(&encoded).unwrap();
+        assert_eq!(compact_ciphertext, decoded);
+
+        // incorrect length encoding
+        assert_eq!(CompactElGamalCiphertext::from_bytes(&[0; 33]), None);
+    }
+}
@@ -6,13 +6,16 @@
 //! signing message are protocol-identified (`solana-conf-bal/v1`) so that
 //! independent reimplementations on any platform derive byte-identical keys.
 //!
-//! Callers have three entry points:
+//! Callers have four entry points:
 //!
 //! - [`derive_confidential_keys`]: sign once with a Solana `Signer`, derive
 //!   both keys.
 //! - [`derive_confidential_keys_from_signature`]: when the caller already
 //!   holds a signature over the canonical message (e.g. produced via a
 //!   wallet-adapter signing flow or a KMS deterministic-sign call).
+//! - [`derive_confidential_keys_from_wallet_signature`]: when the signature
+//!   comes from a browser wallet's `signMessage` and must first be checked
+//!   against the wallet's pubkey.
 //! - [`derive_confidential_keys_from_ikm`]: when the caller has raw input
 //!   key material from any other source (WebAuthn PRF output, Secure Enclave
 //!   HMAC output, KMS `GenerateMac` output, a BIP39 seed, or HKDF over an
@@ -44,6 +47,7 @@ use {
     curve25519_dalek::scalar::Scalar,
     hkdf::Hkdf,
     sha2::Sha512,
+    solana_address::Address,
     solana_signature::Signature,
     solana_signer::Signer,
     solana_zk_sdk_pod::encryption::AE_KEY_LEN,
@@ -150,6 +154,43 @@ pub fn derive_confidential_keys_from_signature(
     derive_confidential_keys_from_ikm(signature.as_ref())
 }
 
+/// Derives the confidential-balances key pair from the signature a wallet
+/// returned from `signMessage` over [`confidential_derivation_message`].
+///
+/// Wallets that do not expose their seed can only hand back a signature, so
+/// the signature is validated before it is used as key material. Besides the
+/// default signature, the following are rejected:
+///
+/// - signatures whose `S` component is not reduced modulo the group order. An
+///   Ed25519 signature `(R, S)` also verifies as `(R, S + l)` under lenient
+///   verifiers, so accepting both encodings would let the same wallet derive
+///   two distinct key pairs;
+/// - signatures that do not strictly verify over the derivation message under
+///   `wallet_pubkey`, which also rules out small-order `R` components and weak
+///   wallet keys.
+pub fn derive_confidential_keys_from_wallet_signature(
+    wallet_pubkey: &Address,
+    public_seed: &[u8],
+    signature: &Signature,
+) -> Result<(ElGamalKeypair, AeKey), ElGamalError> {
+    if bool::from(signature.as_ref().ct_eq(Signature::default().as_ref())) {
+        return Err(ElGamalError::DefaultSignatureRejected);
+    }
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature.as_ref()[32..]);
+    if Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes)).is_none() {
+        return Err(ElGamalError::NonCanonicalSignature);
+    }
+
+    let message = confidential_derivation_message(public_seed);
+    if !signature.verify(wallet_pubkey.as_ref(), &message) {
+        return Err(ElGamalError::InvalidSignature);
+    }
+
+    derive_confidential_keys_from_ikm(signature.as_ref())
+}
+
 /// Derives the confidential-balances key pair from raw input key material.
 ///
 /// This is the universal entry point used by non-`Signer` adapters (WebAuthn
@@ -370,6 +411,81 @@ mod tests {
         assert!(matches!(err, ElGamalError::DefaultSignatureRejected));
     }
 
+    #[test]
+    fn test_derive_confidential_keys_from_wallet_signature() {
+        let keypair = Keypair::new();
+        let wallet_pubkey = keypair.pubkey();
+        let public_seed = [0x66u8; 32];
+
+        let message = confidential_derivation_message(&public_seed);
+        let signature = keypair.sign_message(&message);
+
+        let (kp_wallet, ae_wallet) = derive_confidential_keys_from_wallet_signature(
+            &wallet_pubkey,
+            &public_seed,
+            &signature,
+        )
+        .unwrap();
+        let (kp_signer, ae_signer) = derive_confidential_keys(&keypair, &public_seed).unwrap();
+        assert_eq!(kp_wallet.secret().as_bytes(), kp_signer.secret().as_bytes());
+        assert_eq!(
+            <[u8; AE_KEY_LEN]>::from(&ae_wallet),
+            <[u8; AE_KEY_LEN]>::from(&ae_signer)
+        );
+
+        // the malleated `(R, S + l)` encoding of the same signature is rejected
+        const GROUP_ORDER: [u8; 32] = [
+            0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+            0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x10,
+        ];
+        let mut malleated = <[u8; 64]>::from(signature);
+        let mut carry = 0u16;
+        for (byte, order_byte) in malleated[32..].iter_mut().zip(GROUP_ORDER) {
+            let sum = *byte as u16 + order_byte as u16 + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+        assert_eq!(
+            derive_confidential_keys_from_wallet_signature(
+                &wallet_pubkey,
+                &public_seed,
+                &Signature::from(malleated)
+            )
+            .unwrap_err(),
+            ElGamalError::NonCanonicalSignature
+        );
+
+        // a signature over a different seed or from a different wallet is rejected
+        assert_eq!(
+            derive_confidential_keys_from_wallet_signature(
+                &wallet_pubkey,
+                &[0x77u8; 32],
+                &signature
+            )
+            .unwrap_err(),
+            ElGamalError::InvalidSignature
+        );
+        assert_eq!(
+            derive_confidential_keys_from_wallet_signature(
+                &Keypair::new().pubkey(),
+                &public_seed,
+                &signature
+            )
+            .unwrap_err(),
+            ElGamalError::InvalidSignature
+        );
+        assert_eq!(
+            derive_confidential_keys_from_wallet_signature(
+                &wallet_pubkey,
+                &public_seed,
+                &Signature::default()
+            )
+            .unwrap_err(),
+            ElGamalError::DefaultSignatureRejected
+        );
+    }
+
     #[test]
     fn test_derive_confidential_keys_rejects_null_signer() {
         use solana_signer::null_signer::NullSigner;
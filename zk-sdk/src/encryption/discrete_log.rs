@@ -13,18 +13,28 @@
 //! implementation inherently not constant-time. This may theoretically allow an adversary to gain
 //! information on a discrete log solution depending on the execution time of the implementation.
 //!
+//! With the `parallel` feature, `DiscreteLog::decode_u32` partitions the search across threads and
+//! stops all threads as soon as one of them finds the solution. This trades the measure above for
+//! lower latency, so the execution time depends more directly on the solution.
 
 use {
     curve25519_dalek::{
         constants::RISTRETTO_BASEPOINT_POINT as G,
-        ristretto::RistrettoPoint,
+        ristretto::{CompressedRistretto, RistrettoPoint},
         scalar::Scalar,
         traits::{Identity, IsIdentity},
     },
     itertools::Itertools,
     serde::{Deserialize, Serialize},
+    sha2::{Digest, Sha256},
     solana_zk_sdk_pod::RISTRETTO_POINT_LEN,
-    std::{collections::HashMap, num::NonZeroUsize, thread},
+    std::{
+        collections::HashMap,
+        io::{Read, Write},
+        num::NonZeroUsize,
+        sync::atomic::{AtomicBool, Ordering},
+        thread,
+    },
     thiserror::Error,
 };
 
@@ -34,12 +44,29 @@ const TWO17: u64 = 131072; // 2^17
 /// Maximum number of threads permitted for discrete log computation
 const MAX_THREAD: usize = 65536;
 
+/// Maximum bit length of a solution that can be found with `DiscreteLog::decode_within`
+pub const MAX_DECODE_WITHIN_BIT_LENGTH: u8 = 32;
+
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum DiscreteLogError {
     #[error("discrete log number of threads not power-of-two")]
     DiscreteLogThreads,
     #[error("discrete log batch size too large")]
     DiscreteLogBatchSize,
+    #[error("discrete log table bit length out of range")]
+    DiscreteLogTableBits,
+    #[error("discrete log table malformed")]
+    DiscreteLogTableFormat,
+    #[error("discrete log table checksum mismatch")]
+    DiscreteLogTableChecksum,
+    #[error("discrete log table io error")]
+    DiscreteLogTableIo,
+    #[error("discrete log max steps not power-of-two or too large")]
+    DiscreteLogMaxSteps,
+    #[error("discrete log table does not match the configuration")]
+    DiscreteLogTableMismatch,
+    #[error("discrete log search range out of bounds")]
+    DiscreteLogSearchRange,
 }
 
 /// Type that captures a discrete log challenge.
@@ -82,6 +109,29 @@ fn decode_u32_precomputation() -> DecodePrecomputation {
     DecodePrecomputation(hashmap)
 }
 
+/// Builds a `HashMap` of the `2^16` elements `2*0G, 2*1G, 2*2G, ...`
+fn decode_within_precomputation() -> DecodePrecomputation {
+    let mut hashmap = HashMap::new();
+
+    // iterator for 1G, 2G, 3G, ...
+    //
+    // the identity point is skipped since batch compression errors on the identity point
+    let ristretto_iter = RistrettoIterator::new((G, 1), (G, 1));
+    let (points, indices): (Vec<_>, Vec<_>) = ristretto_iter.take(TWO16 as usize - 1).unzip();
+
+    let compressed = RistrettoPoint::double_and_compress_batch(&points);
+    for (point, x_lo) in compressed.iter().zip(indices.iter()) {
+        hashmap.insert(point.to_bytes(), *x_lo as u16);
+    }
+
+    DecodePrecomputation(hashmap)
+}
+
+/// Pre-computed `HashMap` of small multiples of `G` needed for bounded decryption. The `HashMap`
+/// is built the first time it is accessed.
+static DECODE_WITHIN_PRECOMPUTATION: std::sync::LazyLock<DecodePrecomputation> =
+    std::sync::LazyLock::new(decode_within_precomputation);
+
 /// Pre-computed `HashMap` needed for decryption. The `HashMap` is independent of (works for) any key.
 pub static DECODE_PRECOMPUTATION_FOR_G: std::sync::LazyLock<DecodePrecomputation> =
     std::sync::LazyLock::new(|| {
@@ -137,7 +187,23 @@ impl DiscreteLog {
 
     /// Solves the discrete log problem under the assumption that the solution
     /// is a positive 32-bit number.
+    ///
+    /// With the `parallel` feature, the search is partitioned across threads and returns as soon
+    /// as the solution is found (see `decode_u32_parallel`).
     pub fn decode_u32(self) -> Option<u64> {
+        #[cfg(feature = "parallel")]
+        {
+            self.decode_u32_parallel()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.decode_u32_sequential()
+        }
+    }
+
+    #[cfg_attr(feature = "parallel", allow(dead_code))]
+    fn decode_u32_sequential(self) -> Option<u64> {
         #[allow(unused_variables)]
         if let Some(num_threads) = self.num_threads {
             {
@@ -154,6 +220,8 @@ impl DiscreteLog {
                                 ristretto_iterator,
                                 self.range_bound,
                                 self.compression_batch_size,
+                                None,
+                                &lookup_precomputation_for_g,
                             )
                         });
 
@@ -176,22 +244,215 @@ impl DiscreteLog {
                 ristretto_iterator,
                 self.range_bound,
                 self.compression_batch_size,
+                None,
+                &lookup_precomputation_for_g,
             )
         }
     }
 
+    /// Solves the discrete log problem under the assumption that the solution is a positive
+    /// number of at most `max_bits` bits.
+    ///
+    /// The solution is split as `x = x_lo + 2^16 * x_hi`. The function steps through the
+    /// `2^(max_bits - 16)` candidates for `x_hi` (a single candidate if `max_bits` is at most 16)
+    /// and looks up `x_lo` in a precomputed table, so the running time is proportional to the
+    /// number of candidates. The table is computed the first time the function is called.
+    ///
+    /// If `max_bits` exceeds `MAX_DECODE_WITHIN_BIT_LENGTH` or the solution is not a positive
+    /// `max_bits`-bit number, then the function returns `None`. The search is single-threaded
+    /// and does not depend on the number of threads of the instance.
+    pub fn decode_within(self, max_bits: u8) -> Option<u64> {
+        if max_bits > MAX_DECODE_WITHIN_BIT_LENGTH {
+            return None;
+        }
+
+        let hashmap = &DECODE_WITHIN_PRECOMPUTATION;
+        let num_candidates = 1_usize << max_bits.saturating_sub(16);
+        let step_point = Scalar::from(TWO16) * G;
+        let mut decoded = None;
+
+        // iterator for target - 2^16*0G, target - 2^16*1G, target - 2^16*2G, ...
+        let ristretto_iterator = RistrettoIterator::new((self.target, 0), (-step_point, 1));
+        for batch in &ristretto_iterator
+            .take(num_candidates)
+            .chunks(self.compression_batch_size.get())
+        {
+            // batch compression currently errors if any point in the batch is the identity point
+            let (batch_points, batch_indices): (Vec<_>, Vec<_>) = batch
+                .filter(|(point, x_hi)| {
+                    if point.is_identity() {
+                        decoded = Some(TWO16 * x_hi);
+                        return false;
+                    }
+                    true
+                })
+                .unzip();
+
+            let batch_compressed = RistrettoPoint::double_and_compress_batch(&batch_points);
+
+            for (point, x_hi) in batch_compressed.iter().zip(batch_indices.iter()) {
+                if let Some(x_lo) = hashmap.0.get(&point.to_bytes()) {
+                    decoded = Some(*x_lo as u64 + TWO16 * x_hi);
+                }
+            }
+        }
+
+        decoded.filter(|x| x >> max_bits == 0)
+    }
+
+    /// Solves the discrete log problem under the assumption that the solution is a positive
+    /// 32-bit number, partitioning the search across threads.
+    ///
+    /// The `2^16` online steps are split into contiguous ranges, one for each thread. The number
+    /// of threads is the one that is set with `num_threads` or, if it is not set, the available
+    /// parallelism of the machine. The first thread that finds the solution signals the other
+    /// threads to stop, so the worst-case latency is roughly divided by the number of threads.
+    #[cfg(feature = "parallel")]
+    pub fn decode_u32_parallel(self) -> Option<u64> {
+        let num_threads = self
+            .num_threads
+            .or_else(|| thread::available_parallelism().ok())
+            .unwrap_or(NonZeroUsize::MIN);
+
+        self.decode_partitioned(
+            TWO16 as usize,
+            num_threads,
+            true,
+            &lookup_precomputation_for_g,
+        )
+    }
+
+    /// Solves the discrete log problem using a discrete log table and a configuration of the
+    /// time-memory tradeoff.
+    ///
+    /// The solution is assumed to be a positive number less than `max_steps * 2^table_bits`. The
+    /// `max_steps` online steps are split into contiguous ranges across `threads` threads. With
+    /// the `parallel` feature, the first thread that finds the solution signals the other threads
+    /// to stop. The solution is checked against the target, so a corrupted table cannot produce
+    /// an incorrect solution.
+    ///
+    /// The table must be built with `DiscreteLogTable::from_config` for a configuration with the
+    /// same `table_bits` and `max_steps`.
+    pub fn decode_with_config(
+        self,
+        config: &DiscreteLogConfig,
+        table: &DiscreteLogTable,
+    ) -> Result<Option<u64>, DiscreteLogError> {
+        let step_bits = config.step_bits()?;
+        if table.table_bits != config.table_bits || table.step_bits != step_bits {
+            return Err(DiscreteLogError::DiscreteLogTableMismatch);
+        }
+        let range_bound =
+            usize::try_from(config.max_steps).map_err(|_| DiscreteLogError::DiscreteLogMaxSteps)?;
+
+        Ok(self
+            .decode_partitioned(
+                range_bound,
+                config.threads,
+                cfg!(feature = "parallel"),
+                &|key| table.lookup(key),
+            )
+            .filter(|x| Scalar::from(*x) * G == self.target))
+    }
+
+    /// Searches through the online steps `0..range_bound`, split into contiguous ranges across
+    /// threads.
+    ///
+    /// If `early_exit` is set, the first thread that finds the solution signals the other threads
+    /// to stop.
+    fn decode_partitioned(
+        self,
+        range_bound: usize,
+        num_threads: NonZeroUsize,
+        early_exit: bool,
+        lookup: &(impl Fn(&[u8; RISTRETTO_POINT_LEN]) -> Option<u64> + Sync),
+    ) -> Option<u64> {
+        let num_threads = num_threads.get().min(range_bound);
+        let thread_range_bound = range_bound.div_ceil(num_threads);
+        let found = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let handles = (0..num_threads)
+                .filter_map(|i| {
+                    let start = i * thread_range_bound;
+                    let range_bound = NonZeroUsize::new(
+                        thread_range_bound.min(range_bound.saturating_sub(start)),
+                    )?;
+
+                    let starting_point = self.target - Scalar::from(start as u64) * G;
+                    let ristretto_iterator =
+                        RistrettoIterator::new((starting_point, start as u64), (-G, 1));
+
+                    let found = early_exit.then_some(&found);
+                    Some(scope.spawn(move || {
+                        Self::decode_range(
+                            ristretto_iterator,
+                            range_bound,
+                            self.compression_batch_size,
+                            found,
+                            lookup,
+                        )
+                    }))
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .filter_map(|h| h.join().ok())
+                .find_map(|x| x)
+        })
+    }
+
+    /// Solves the discrete log problem under the assumption that the solution is a positive
+    /// 32-bit number using a discrete log table.
+    ///
+    /// The number of online steps is `2^(32 - table_bits)` for the bit length of the table. The
+    /// search is single-threaded and does not depend on the number of threads of the instance.
+    /// The solution is checked against the target, so a corrupted table cannot produce an
+    /// incorrect solution, but it may fail to find a solution.
+    pub fn decode_u32_with_table(self, table: &DiscreteLogTable) -> Option<u64> {
+        let range_bound = NonZeroUsize::new(1_usize << table.step_bits)?;
+        let ristretto_iterator = RistrettoIterator::new((self.target, 0_u64), (-G, 1u64));
+
+        Self::decode_range(
+            ristretto_iterator,
+            range_bound,
+            self.compression_batch_size,
+            None,
+            &|key| table.lookup(key),
+        )
+        .filter(|x| *x >> 32 == 0 && Scalar::from(*x) * G == self.target)
+    }
+
+    /// Searches through a range of the online steps.
+    ///
+    /// For each point in the range, `lookup` returns the contribution of the precomputed giant
+    /// steps to the solution if the point is in the precomputed table. If `found` is provided,
+    /// the search stops as soon as the solution is found, or another search that shares the flag
+    /// has found it. Otherwise, the whole range is searched.
     fn decode_range(
         ristretto_iterator: RistrettoIterator,
         range_bound: NonZeroUsize,
         compression_batch_size: NonZeroUsize,
+        found: Option<&AtomicBool>,
+        lookup: &impl Fn(&[u8; RISTRETTO_POINT_LEN]) -> Option<u64>,
     ) -> Option<u64> {
-        let hashmap = &DECODE_PRECOMPUTATION_FOR_G;
         let mut decoded = None;
 
         for batch in &ristretto_iterator
             .take(range_bound.get())
             .chunks(compression_batch_size.get())
         {
+            if let Some(found) = found {
+                if decoded.is_some() {
+                    found.store(true, Ordering::Relaxed);
+                    break;
+                }
+                if found.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+
             // batch compression currently errors if any point in the batch is the identity point
             let (batch_points, batch_indices): (Vec<_>, Vec<_>) = batch
                 .filter(|(point, index)| {
@@ -206,10 +467,8 @@ impl DiscreteLog {
             let batch_compressed = RistrettoPoint::double_and_compress_batch(&batch_points);
 
             for (point, x_lo) in batch_compressed.iter().zip(batch_indices.iter()) {
-                let key = point.to_bytes();
-                if hashmap.0.contains_key(&key) {
-                    let x_hi = hashmap.0[&key];
-                    decoded = Some(x_lo + TWO16 * x_hi as u64);
+                if let Some(x_hi) = lookup(&point.to_bytes()) {
+                    decoded = Some(x_lo + x_hi);
                 }
             }
         }
@@ -218,6 +477,457 @@ impl DiscreteLog {
     }
 }
 
+/// Looks up the contribution of the giant steps to a solution in `DECODE_PRECOMPUTATION_FOR_G`.
+fn lookup_precomputation_for_g(key: &[u8; RISTRETTO_POINT_LEN]) -> Option<u64> {
+    DECODE_PRECOMPUTATION_FOR_G
+        .0
+        .get(key)
+        .map(|x_hi| TWO16 * *x_hi as u64)
+}
+
+/// Number of online steps in a search for a positive 32-bit solution
+pub const DISCRETE_LOG_SEARCH_STEPS: u64 = TWO16;
+
+/// Byte length of a serialized discrete log search: the target, the next and end steps, and the
+/// solution prefixed by a presence flag
+const DISCRETE_LOG_SEARCH_LEN: usize = RISTRETTO_POINT_LEN + 8 + 8 + 1 + 8;
+
+/// A resumable search for a positive 32-bit discrete log solution for the generator `G`.
+///
+/// The search covers the same `2^16` online steps as `DiscreteLog::decode_u32`, but it runs them
+/// in chunks of a caller-chosen size with `search`. The search state can be serialized between
+/// chunks with `to_bytes`, and the remaining steps can be split into contiguous ranges with
+/// `split`, so an environment without threads, such as a browser, can spread a search over event
+/// loop turns or across workers.
+///
+/// Unlike `DiscreteLog::decode_u32`, the search stops at the chunk in which the solution is found,
+/// so the time that it takes depends on the solution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DiscreteLogSearch {
+    target: RistrettoPoint,
+    next_step: u64,
+    end_step: u64,
+    solution: Option<u64>,
+}
+
+impl DiscreteLogSearch {
+    /// Byte length of a serialized discrete log search.
+    pub const LEN: usize = DISCRETE_LOG_SEARCH_LEN;
+
+    /// Creates a search for `x` such that `x * G = target` over all online steps.
+    pub fn new(target: RistrettoPoint) -> Self {
+        Self {
+            target,
+            next_step: 0,
+            end_step: DISCRETE_LOG_SEARCH_STEPS,
+            solution: None,
+        }
+    }
+
+    /// Creates a search over the online steps `start..end`.
+    pub fn with_range(
+        target: RistrettoPoint,
+        start: u64,
+        end: u64,
+    ) -> Result<Self, DiscreteLogError> {
+        if start > end || end > DISCRETE_LOG_SEARCH_STEPS {
+            return Err(DiscreteLogError::DiscreteLogSearchRange);
+        }
+        Ok(Self {
+            target,
+            next_step: start,
+            end_step: end,
+            solution: None,
+        })
+    }
+
+    /// Splits the remaining online steps into `parts` contiguous searches.
+    ///
+    /// Exactly one of the searches finds the solution. If the solution is already found, the
+    /// search is returned as is.
+    pub fn split(&self, parts: NonZeroUsize) -> Vec<Self> {
+        if self.solution.is_some() {
+            return vec![*self];
+        }
+        let parts = parts.get() as u64;
+        let part_len = self.steps_remaining().div_ceil(parts);
+        (0..parts)
+            .map(|i| {
+                let start = self
+                    .next_step
+                    .saturating_add(i.saturating_mul(part_len))
+                    .min(self.end_step);
+                let end = start.saturating_add(part_len).min(self.end_step);
+                Self {
+                    target: self.target,
+                    next_step: start,
+                    end_step: end,
+                    solution: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Runs at most `max_steps` online steps of the search and returns the solution if it is
+    /// found.
+    pub fn search(&mut self, max_steps: u64) -> Option<u64> {
+        if self.solution.is_some() {
+            return self.solution;
+        }
+        let range_bound = usize::try_from(max_steps.min(self.steps_remaining()))
+            .ok()
+            .and_then(NonZeroUsize::new)?;
+
+        let starting_point = self.target - Scalar::from(self.next_step) * G;
+        let ristretto_iterator = RistrettoIterator::new((starting_point, self.next_step), (-G, 1));
+        self.solution = DiscreteLog::decode_range(
+            ristretto_iterator,
+            range_bound,
+            32.try_into().unwrap(),
+            None,
+            &lookup_precomputation_for_g,
+        );
+        self.next_step += range_bound.get() as u64;
+
+        self.solution
+    }
+
+    /// The solution, if it has been found.
+    pub fn solution(&self) -> Option<u64> {
+        self.solution
+    }
+
+    /// Returns `true` if the solution is found or all online steps are searched.
+    pub fn is_finished(&self) -> bool {
+        self.solution.is_some() || self.next_step == self.end_step
+    }
+
+    /// The number of online steps that are left to search.
+    pub fn steps_remaining(&self) -> u64 {
+        if self.solution.is_some() {
+            0
+        } else {
+            self.end_step - self.next_step
+        }
+    }
+
+    /// Serializes the state of the search.
+    pub fn to_bytes(&self) -> [u8; DISCRETE_LOG_SEARCH_LEN] {
+        let mut bytes = [0u8; DISCRETE_LOG_SEARCH_LEN];
+        let (target, rest) = bytes.split_at_mut(RISTRETTO_POINT_LEN);
+        let (next_step, rest) = rest.split_at_mut(8);
+        let (end_step, rest) = rest.split_at_mut(8);
+        let (flag, solution) = rest.split_at_mut(1);
+
+        target.copy_from_slice(self.target.compress().as_bytes());
+        next_step.copy_from_slice(&self.next_step.to_le_bytes());
+        end_step.copy_from_slice(&self.end_step.to_le_bytes());
+        if let Some(x) = self.solution {
+            flag[0] = 1;
+            solution.copy_from_slice(&x.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes the state of a search.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != DISCRETE_LOG_SEARCH_LEN {
+            return None;
+        }
+        let (target, rest) = bytes.split_at(RISTRETTO_POINT_LEN);
+        let (next_step, rest) = rest.split_at(8);
+        let (end_step, rest) = rest.split_at(8);
+        let (flag, solution) = rest.split_at(1);
+
+        let target = CompressedRistretto::from_slice(target).ok()?.decompress()?;
+        let next_step = u64::from_le_bytes(next_step.try_into().ok()?);
+        let end_step = u64::from_le_bytes(end_step.try_into().ok()?);
+        let solution = match flag[0] {
+            0 => None,
+            1 => Some(u64::from_le_bytes(solution.try_into().ok()?)),
+            _ => return None,
+        };
+
+        let mut search = Self::with_range(target, next_step, end_step).ok()?;
+        search.solution = solution;
+        Some(search)
+    }
+}
+
+/// Magic bytes that identify a serialized discrete log table
+const DISCRETE_LOG_TABLE_MAGIC: &[u8; 8] = b"ZKDLOGTB";
+
+/// Version of the serialization format of discrete log tables
+const DISCRETE_LOG_TABLE_VERSION: u8 = 2;
+
+/// Version of the serialization format of discrete log tables without the step bit length, in
+/// which the step bit length is implicitly `32 - table_bits`
+const DISCRETE_LOG_TABLE_VERSION_1: u8 = 1;
+
+/// Byte length of the header of a serialized discrete log table: magic, version, table bits, and
+/// step bits
+const DISCRETE_LOG_TABLE_HEADER_LEN: usize = 11;
+
+/// Byte length of the header of a serialized discrete log table in version 1 of the format
+const DISCRETE_LOG_TABLE_HEADER_LEN_V1: usize = 10;
+
+/// Byte length of the SHA-256 checksum of a serialized discrete log table
+const DISCRETE_LOG_TABLE_CHECKSUM_LEN: usize = 32;
+
+/// Maximum bit length of a discrete log table
+pub const MAX_DISCRETE_LOG_TABLE_BITS: u8 = 32;
+
+/// Maximum bit length of the number of online steps of a discrete log search
+pub const MAX_DISCRETE_LOG_STEP_BITS: u8 = 32;
+
+/// Configuration of the time-memory tradeoff of the baby-step giant-step discrete log solver.
+///
+/// The solver finds solutions less than `max_steps * 2^table_bits`. The precomputed table holds
+/// `2^table_bits` points, so each additional table bit doubles the memory that is needed for the
+/// table and halves the `max_steps` needed to cover the same range of solutions. For positive
+/// 32-bit solutions, `table_bits + log2(max_steps) = 32`. A memory-constrained client may choose
+/// 12 table bits and `2^20` steps, while a server may choose 22 table bits and `2^10` steps
+/// spread across a number of threads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DiscreteLogConfig {
+    /// Bit length of the precomputed table
+    pub table_bits: u8,
+    /// Number of online steps, which must be a power of two
+    pub max_steps: u64,
+    /// Number of threads that the online steps are split across
+    pub threads: NonZeroUsize,
+}
+
+impl Default for DiscreteLogConfig {
+    /// The configuration that corresponds to `DiscreteLog::decode_u32`.
+    fn default() -> Self {
+        Self {
+            table_bits: 16,
+            max_steps: TWO16,
+            threads: NonZeroUsize::MIN,
+        }
+    }
+}
+
+impl DiscreteLogConfig {
+    /// Checks that the configuration is supported.
+    pub fn validate(&self) -> Result<(), DiscreteLogError> {
+        if self.table_bits == 0 || self.table_bits > MAX_DISCRETE_LOG_TABLE_BITS {
+            return Err(DiscreteLogError::DiscreteLogTableBits);
+        }
+        self.step_bits()?;
+        if self.threads.get() > MAX_THREAD {
+            return Err(DiscreteLogError::DiscreteLogThreads);
+        }
+        Ok(())
+    }
+
+    /// The bit length of the largest solution that can be found with the configuration.
+    pub fn max_bits(&self) -> Result<u8, DiscreteLogError> {
+        self.validate()?;
+        Ok(self.table_bits + self.step_bits()?)
+    }
+
+    /// The bit length of the number of online steps.
+    fn step_bits(&self) -> Result<u8, DiscreteLogError> {
+        if !self.max_steps.is_power_of_two()
+            || self.max_steps.trailing_zeros() > u32::from(MAX_DISCRETE_LOG_STEP_BITS)
+        {
+            return Err(DiscreteLogError::DiscreteLogMaxSteps);
+        }
+        // `max_steps` is at most `2^32`, so the conversion is lossless
+        Ok(self.max_steps.trailing_zeros() as u8)
+    }
+}
+
+/// A precomputed table of giant steps for the discrete log problem.
+///
+/// A table with bit length `table_bits` and step bit length `step_bits` splits a solution as
+/// `x = x_lo + 2^step_bits * x_hi`, and stores the points `2 * 2^step_bits * x_hi * G` for all
+/// `x_hi` up to `2^table_bits`. The online phase of the discrete log then takes `2^step_bits`
+/// steps. Larger tables take more time to build and more memory, but result in faster decoding.
+///
+/// Since building a large table can take minutes, a table can be serialized with `save` and
+/// restored with `load`. The serialization consists of a header with the magic bytes
+/// `ZKDLOGTB`, a format version, the table bit length, and the step bit length, followed by the
+/// compressed points ordered by `x_hi` starting from one, and a SHA-256 checksum of all preceding
+/// bytes. Version 1 of the format omits the step bit length, which is then `32 - table_bits`.
+/// The checksum only protects against accidental corruption. Tables should be loaded only from
+/// trusted sources.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscreteLogTable {
+    table_bits: u8,
+    step_bits: u8,
+    table: HashMap<[u8; RISTRETTO_POINT_LEN], u32>,
+}
+
+impl DiscreteLogTable {
+    /// Builds a discrete log table with the specified bit length for positive 32-bit solutions.
+    pub fn new(table_bits: u8) -> Result<Self, DiscreteLogError> {
+        if table_bits == 0 || table_bits > MAX_DISCRETE_LOG_TABLE_BITS {
+            return Err(DiscreteLogError::DiscreteLogTableBits);
+        }
+        Ok(Self::build(table_bits, 32 - table_bits))
+    }
+
+    /// Builds the discrete log table for a configuration of the time-memory tradeoff.
+    pub fn from_config(config: &DiscreteLogConfig) -> Result<Self, DiscreteLogError> {
+        config.validate()?;
+        Ok(Self::build(config.table_bits, config.step_bits()?))
+    }
+
+    fn build(table_bits: u8, step_bits: u8) -> Self {
+        let giant_step = Scalar::from(1_u64 << step_bits) * G;
+
+        // iterator for 1*2^s*G, 2*2^s*G, 3*2^s*G, ...
+        //
+        // the identity point is skipped since it is handled in the online phase
+        let ristretto_iter = RistrettoIterator::new((giant_step, 1), (giant_step, 1));
+
+        let mut table = HashMap::new();
+        for batch in &ristretto_iter
+            .take(Self::num_entries(table_bits))
+            .chunks(1024)
+        {
+            let (points, indices): (Vec<_>, Vec<_>) = batch.unzip();
+            let compressed = RistrettoPoint::double_and_compress_batch(&points);
+            for (point, x_hi) in compressed.iter().zip(indices.iter()) {
+                table.insert(point.to_bytes(), *x_hi as u32);
+            }
+        }
+
+        Self {
+            table_bits,
+            step_bits,
+            table,
+        }
+    }
+
+    /// The bit length of the table.
+    pub fn table_bits(&self) -> u8 {
+        self.table_bits
+    }
+
+    /// The bit length of the number of online steps that are needed to decode with the table.
+    pub fn step_bits(&self) -> u8 {
+        self.step_bits
+    }
+
+    /// The number of points that are stored in a table with the specified bit length.
+    fn num_entries(table_bits: u8) -> usize {
+        usize::MAX >> (usize::BITS - u32::from(table_bits))
+    }
+
+    /// Looks up the contribution of the giant steps to a solution in the table.
+    fn lookup(&self, key: &[u8; RISTRETTO_POINT_LEN]) -> Option<u64> {
+        self.table
+            .get(key)
+            .map(|x_hi| (*x_hi as u64) << self.step_bits)
+    }
+
+    /// Serializes the table in the versioned discrete log table format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut points = vec![[0u8; RISTRETTO_POINT_LEN]; self.table.len()];
+        for (point, x_hi) in self.table.iter() {
+            points[*x_hi as usize - 1] = *point;
+        }
+
+        let mut buf = Vec::with_capacity(
+            DISCRETE_LOG_TABLE_HEADER_LEN
+                + points.len() * RISTRETTO_POINT_LEN
+                + DISCRETE_LOG_TABLE_CHECKSUM_LEN,
+        );
+        buf.extend_from_slice(DISCRETE_LOG_TABLE_MAGIC);
+        buf.push(DISCRETE_LOG_TABLE_VERSION);
+        buf.push(self.table_bits);
+        buf.push(self.step_bits);
+        for point in points.iter() {
+            buf.extend_from_slice(point);
+        }
+
+        let checksum = Sha256::digest(&buf);
+        buf.extend_from_slice(&checksum);
+        buf
+    }
+
+    /// Restores a table from its serialization in the versioned discrete log table format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DiscreteLogError> {
+        let (magic, rest) = bytes
+            .split_at_checked(DISCRETE_LOG_TABLE_MAGIC.len())
+            .ok_or(DiscreteLogError::DiscreteLogTableFormat)?;
+        if magic != DISCRETE_LOG_TABLE_MAGIC {
+            return Err(DiscreteLogError::DiscreteLogTableFormat);
+        }
+
+        let (header_len, table_bits, step_bits) = match rest {
+            [DISCRETE_LOG_TABLE_VERSION_1, table_bits, ..] => (
+                DISCRETE_LOG_TABLE_HEADER_LEN_V1,
+                *table_bits,
+                32_u8.checked_sub(*table_bits),
+            ),
+            [DISCRETE_LOG_TABLE_VERSION, table_bits, step_bits, ..] => {
+                (DISCRETE_LOG_TABLE_HEADER_LEN, *table_bits, Some(*step_bits))
+            }
+            _ => return Err(DiscreteLogError::DiscreteLogTableFormat),
+        };
+
+        if table_bits == 0 || table_bits > MAX_DISCRETE_LOG_TABLE_BITS {
+            return Err(DiscreteLogError::DiscreteLogTableBits);
+        }
+        let step_bits = step_bits
+            .filter(|step_bits| *step_bits <= MAX_DISCRETE_LOG_STEP_BITS)
+            .ok_or(DiscreteLogError::DiscreteLogMaxSteps)?;
+
+        let num_entries = Self::num_entries(table_bits);
+        let expected_len = num_entries
+            .checked_mul(RISTRETTO_POINT_LEN)
+            .and_then(|len| len.checked_add(header_len + DISCRETE_LOG_TABLE_CHECKSUM_LEN))
+            .ok_or(DiscreteLogError::DiscreteLogTableFormat)?;
+        if bytes.len() != expected_len {
+            return Err(DiscreteLogError::DiscreteLogTableFormat);
+        }
+
+        let (data, checksum) = bytes.split_at(bytes.len() - DISCRETE_LOG_TABLE_CHECKSUM_LEN);
+        if Sha256::digest(data).as_slice() != checksum {
+            return Err(DiscreteLogError::DiscreteLogTableChecksum);
+        }
+
+        let mut table = HashMap::with_capacity(num_entries);
+        for (point, x_hi) in data[header_len..]
+            .chunks_exact(RISTRETTO_POINT_LEN)
+            .zip(1_u32..)
+        {
+            // `chunks_exact` produces chunks of exactly `RISTRETTO_POINT_LEN` bytes
+            let point: [u8; RISTRETTO_POINT_LEN] = point.try_into().unwrap();
+            if table.insert(point, x_hi).is_some() {
+                return Err(DiscreteLogError::DiscreteLogTableFormat);
+            }
+        }
+
+        Ok(Self {
+            table_bits,
+            step_bits,
+            table,
+        })
+    }
+
+    /// Writes the serialization of the table.
+    pub fn save<W: Write>(&self, writer: &mut W) -> Result<(), DiscreteLogError> {
+        writer
+            .write_all(&self.to_bytes())
+            .map_err(|_| DiscreteLogError::DiscreteLogTableIo)
+    }
+
+    /// Reads and restores a table from its serialization.
+    pub fn load<R: Read>(reader: &mut R) -> Result<Self, DiscreteLogError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|_| DiscreteLogError::DiscreteLogTableIo)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
 /// Hashable Ristretto iterator.
 ///
 /// Given an initial point X and a stepping point P, the iterator iterates through
@@ -281,6 +991,24 @@ mod tests {
         println!("single thread discrete log computation secs: {computation_secs:?} sec");
     }
 
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_decode_correctness_parallel() {
+        for amount in [0_u64, 1, 55, (1 << 16) + 3, (1_u64 << 32) - 1] {
+            let decoded = DiscreteLog::new_for_g(Scalar::from(amount) * G).decode_u32_parallel();
+            assert_eq!(Some(amount), decoded);
+
+            // an odd number of threads does not evenly divide the search space
+            let mut instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+            instance.num_threads = Some(3.try_into().unwrap());
+            assert_eq!(Some(amount), instance.decode_u32_parallel());
+        }
+
+        // solutions that are not 32-bit numbers are not found
+        let instance = DiscreteLog::new_for_g(Scalar::from(1_u64 << 32) * G);
+        assert_eq!(None, instance.decode_u32_parallel());
+    }
+
     #[test]
     fn test_decode_correctness_threaded() {
         // general case
@@ -338,4 +1066,193 @@ mod tests {
         let decoded = instance.decode_u32();
         assert_eq!(amount, decoded.unwrap());
     }
+
+    #[test]
+    fn test_decode_within() {
+        for (amount, max_bits) in [
+            (0_u64, 0_u8),
+            (1, 1),
+            (55, 8),
+            (u16::MAX as u64, 16),
+            (1 << 16, 17),
+            ((1 << 24) - 1, 24),
+            ((1 << 32) - 1, 32),
+        ] {
+            let instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+            assert_eq!(instance.decode_within(max_bits), Some(amount));
+        }
+
+        // amounts that do not fit in the bit length are not decoded
+        let instance = DiscreteLog::new_for_g(Scalar::from(1_u64 << 16) * G);
+        assert_eq!(instance.decode_within(16), None);
+
+        let instance = DiscreteLog::new_for_g(Scalar::from(300_u64) * G);
+        assert_eq!(instance.decode_within(8), None);
+
+        let instance = DiscreteLog::new_for_g(Scalar::from(1_u64 << 32) * G);
+        assert_eq!(instance.decode_within(32), None);
+        assert_eq!(instance.decode_within(33), None);
+    }
+
+    #[test]
+    fn test_discrete_log_search() {
+        let amount: u64 = 4_294_967_295;
+        let target = Scalar::from(amount) * G;
+
+        // search in chunks, passing the state through its byte encoding between chunks
+        let mut search = DiscreteLogSearch::new(target);
+        let mut solution = None;
+        while !search.is_finished() {
+            solution = search.search(4096);
+            search = DiscreteLogSearch::from_bytes(&search.to_bytes()).unwrap();
+        }
+        assert_eq!(solution, Some(amount));
+        assert_eq!(search.solution(), Some(amount));
+        assert_eq!(search.steps_remaining(), 0);
+
+        // exactly one part of a split search finds the solution
+        let parts = DiscreteLogSearch::new(target).split(NonZeroUsize::new(3).unwrap());
+        let solutions = parts
+            .into_iter()
+            .filter_map(|mut part| part.search(DISCRETE_LOG_SEARCH_STEPS))
+            .collect::<Vec<_>>();
+        assert_eq!(solutions, vec![amount]);
+
+        // a search over a range that does not contain the solution finishes without one
+        let mut search = DiscreteLogSearch::with_range(target, 0, 100).unwrap();
+        assert_eq!(search.search(DISCRETE_LOG_SEARCH_STEPS), None);
+        assert!(search.is_finished());
+
+        assert_eq!(
+            DiscreteLogSearch::with_range(target, 0, DISCRETE_LOG_SEARCH_STEPS + 1),
+            Err(DiscreteLogError::DiscreteLogSearchRange)
+        );
+    }
+
+    #[test]
+    fn test_discrete_log_table_matches_precomputation() {
+        let table = DiscreteLogTable::new(16).unwrap();
+        for (point, x_hi) in DECODE_PRECOMPUTATION_FOR_G
+            .0
+            .iter()
+            .filter(|(_, x_hi)| **x_hi > 0)
+        {
+            assert_eq!(table.lookup(point), Some(TWO16 * *x_hi as u64));
+        }
+
+        for amount in [0_u64, 1, (1 << 16) + 3, (1_u64 << 32) - 1] {
+            let instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+            assert_eq!(instance.decode_u32_with_table(&table), Some(amount));
+        }
+
+        let instance = DiscreteLog::new_for_g(Scalar::from(1_u64 << 32) * G);
+        assert_eq!(instance.decode_u32_with_table(&table), None);
+    }
+
+    #[test]
+    fn test_discrete_log_table_save_load() {
+        let table = DiscreteLogTable::new(20).unwrap();
+
+        let mut file = Vec::new();
+        table.save(&mut file).unwrap();
+        let loaded = DiscreteLogTable::load(&mut file.as_slice()).unwrap();
+        assert_eq!(loaded, table);
+
+        let amount = (1_u64 << 32) - 55;
+        let instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+        assert_eq!(instance.decode_u32_with_table(&loaded), Some(amount));
+
+        // corrupted table
+        let mut corrupted = file.clone();
+        corrupted[DISCRETE_LOG_TABLE_HEADER_LEN] ^= 1;
+        assert_eq!(
+            DiscreteLogTable::from_bytes(&corrupted).unwrap_err(),
+            DiscreteLogError::DiscreteLogTableChecksum
+        );
+
+        // unsupported version
+        let mut unsupported = file.clone();
+        unsupported[DISCRETE_LOG_TABLE_MAGIC.len()] = DISCRETE_LOG_TABLE_VERSION + 1;
+        assert_eq!(
+            DiscreteLogTable::from_bytes(&unsupported).unwrap_err(),
+            DiscreteLogError::DiscreteLogTableFormat
+        );
+
+        // truncated table
+        assert_eq!(
+            DiscreteLogTable::from_bytes(&file[..file.len() - 1]).unwrap_err(),
+            DiscreteLogError::DiscreteLogTableFormat
+        );
+
+        // version 1 tables without the step bit length
+        let mut file_v1 = [
+            DISCRETE_LOG_TABLE_MAGIC.as_slice(),
+            &[DISCRETE_LOG_TABLE_VERSION_1, 20],
+            &file[DISCRETE_LOG_TABLE_HEADER_LEN..file.len() - DISCRETE_LOG_TABLE_CHECKSUM_LEN],
+        ]
+        .concat();
+        let checksum = Sha256::digest(&file_v1);
+        file_v1.extend_from_slice(&checksum);
+        assert_eq!(DiscreteLogTable::from_bytes(&file_v1).unwrap(), table);
+
+        assert_eq!(
+            DiscreteLogTable::new(33).unwrap_err(),
+            DiscreteLogError::DiscreteLogTableBits
+        );
+    }
+
+    #[test]
+    fn test_decode_with_config() {
+        // memory-constrained configuration for 32-bit solutions
+        let config = DiscreteLogConfig {
+            table_bits: 14,
+            max_steps: 1 << 18,
+            threads: 4.try_into().unwrap(),
+        };
+        assert_eq!(config.max_bits(), Ok(32));
+
+        let table = DiscreteLogTable::from_config(&config).unwrap();
+        for amount in [0_u64, 77, (1 << 18) + 5, (1_u64 << 32) - 1] {
+            let instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+            assert_eq!(
+                instance.decode_with_config(&config, &table),
+                Ok(Some(amount))
+            );
+        }
+
+        // configuration for solutions beyond 32 bits
+        let config = DiscreteLogConfig {
+            table_bits: 20,
+            max_steps: 1 << 16,
+            threads: 3.try_into().unwrap(),
+        };
+        assert_eq!(config.max_bits(), Ok(36));
+
+        let wide_table = DiscreteLogTable::from_config(&config).unwrap();
+        let amount = (1_u64 << 35) + 55;
+        let instance = DiscreteLog::new_for_g(Scalar::from(amount) * G);
+        assert_eq!(
+            instance.decode_with_config(&config, &wide_table),
+            Ok(Some(amount))
+        );
+        assert_eq!(instance.decode_u32_with_table(&wide_table), None);
+
+        let instance = DiscreteLog::new_for_g(Scalar::from(1_u64 << 36) * G);
+        assert_eq!(instance.decode_with_config(&config, &wide_table), Ok(None));
+
+        // the table must match the configuration
+        assert_eq!(
+            instance.decode_with_config(&config, &table).unwrap_err(),
+            DiscreteLogError::DiscreteLogTableMismatch
+        );
+
+        let invalid_config = DiscreteLogConfig {
+            max_steps: 3,
+            ..DiscreteLogConfig::default()
+        };
+        assert_eq!(
+            invalid_config.validate().unwrap_err(),
+            DiscreteLogError::DiscreteLogMaxSteps
+        );
+    }
 }
@@ -22,17 +22,22 @@ use {
             derivation::{ELGAMAL_HKDF_INFO, HKDF_SALT},
             discrete_log::DiscreteLog,
             pedersen::{Pedersen, PedersenCommitment, PedersenOpening, G, H},
+            shared_secret::SharedSecret,
         },
-        errors::ElGamalError,
+        errors::{ElGamalError, RngError},
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
     },
     base64::{prelude::BASE64_STANDARD, Engine},
-    core::ops::{Add, Mul, Sub},
+    core::{
+        iter::Sum,
+        ops::{Add, AddAssign, Mul, Sub, SubAssign},
+    },
     curve25519_dalek::{
-        ristretto::{CompressedRistretto, RistrettoPoint},
+        ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint},
         scalar::Scalar,
+        traits::IsIdentity,
     },
     hkdf::Hkdf,
-    rand::rngs::OsRng,
     serde::{Deserialize, Serialize},
     sha2::Sha512,
     sha3::{Digest, Sha3_512},
@@ -47,25 +52,42 @@ use {
         ELGAMAL_SECRET_KEY_LEN, PEDERSEN_COMMITMENT_LEN,
     },
     std::{
+        borrow::Borrow,
         convert::TryInto,
         error, fmt,
         io::{Read, Write},
         path::Path,
     },
     subtle::{Choice, ConstantTimeEq},
-    zeroize::{Zeroize, Zeroizing},
+    zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing},
 };
 
+/// HKDF salt for the derivation of Pedersen openings in deterministic encryption.
+const DETERMINISTIC_ENCRYPTION_HKDF_SALT: &[u8] = b"solana-zk-sdk-deterministic-encryption/v1";
+
+/// The offset that is added to a signed amount before it is decoded as a 32-bit number in signed
+/// decryption.
+const SIGNED_DECRYPTION_OFFSET: u64 = 1 << 31;
+
+/// Converts a signed amount into a scalar, encoding negative amounts as their additive inverse.
+pub(crate) fn signed_amount_to_scalar(amount: i64) -> Scalar {
+    if amount < 0 {
+        -Scalar::from(amount.unsigned_abs())
+    } else {
+        Scalar::from(amount.unsigned_abs())
+    }
+}
+
 /// Algorithm handle for the twisted ElGamal encryption scheme
 pub struct ElGamal;
 impl ElGamal {
     /// Generates an ElGamal keypair.
     ///
     /// This function is randomized. It internally samples a scalar element using `OsRng`.
-    fn keygen() -> ElGamalKeypair {
+    fn keygen(rng: &mut impl CryptoRngCore) -> Result<ElGamalKeypair, RngError> {
         // secret scalar should be non-zero except with negligible probability
-        let s = Zeroizing::new(Scalar::random(&mut OsRng));
-        Self::keygen_with_scalar(&s)
+        let s = Zeroizing::new(try_random_scalar(rng)?);
+        Ok(Self::keygen_with_scalar(&s))
     }
 
     /// Generates an ElGamal keypair from a scalar input that determines the ElGamal private key.
@@ -145,13 +167,46 @@ impl ElGamal {
         let discrete_log_instance = Self::decrypt(secret, ciphertext);
         discrete_log_instance.decode_u32()
     }
+
+    /// On input a secret key and a ciphertext, the function returns the decrypted amount
+    /// interpreted as a positive number of at most `max_bits` bits.
+    ///
+    /// Decryption time is proportional to `2^(max_bits - 16)`, so amounts that are known to be
+    /// small can be decrypted faster than with `decrypt_u32`. If the originally encrypted amount
+    /// is not a positive `max_bits`-bit number or `max_bits` exceeds 32, then the function returns
+    /// `None`.
+    ///
+    /// NOTE: This function is not constant time.
+    fn decrypt_within(
+        secret: &ElGamalSecretKey,
+        ciphertext: &ElGamalCiphertext,
+        max_bits: u8,
+    ) -> Option<u64> {
+        let discrete_log_instance = Self::decrypt(secret, ciphertext);
+        discrete_log_instance.decode_within(max_bits)
+    }
+
+    /// On input a secret key and a ciphertext, the function returns the decrypted amount
+    /// interpreted as a signed 32-bit number (but still of type `i64`).
+    ///
+    /// If the originally encrypted amount is not in the range `[-2^31, 2^31)`, then the function
+    /// returns `None`.
+    ///
+    /// NOTE: This function is not constant time.
+    fn decrypt_signed(secret: &ElGamalSecretKey, ciphertext: &ElGamalCiphertext) -> Option<i64> {
+        let offset_ciphertext = ciphertext.add_amount(SIGNED_DECRYPTION_OFFSET);
+        let offset_amount = Self::decrypt_u32(secret, &offset_ciphertext)?;
+
+        // `offset_amount` is a 32-bit number and therefore, the conversion is lossless
+        Some(offset_amount as i64 - SIGNED_DECRYPTION_OFFSET as i64)
+    }
 }
 
 /// A (twisted) ElGamal encryption keypair.
 ///
-/// The instances of the secret key are zeroized on drop.
-#[derive(Clone, Deserialize, PartialEq, Eq, Serialize, Zeroize)]
-#[zeroize(drop)]
+/// The instances of the secret key are zeroized on drop, and the secret key is redacted from the
+/// `Debug` output.
+#[derive(Clone, Deserialize, PartialEq, Eq, Serialize, Zeroize, ZeroizeOnDrop)]
 pub struct ElGamalKeypair {
     /// The public half of this keypair.
     public: ElGamalPubkey,
@@ -164,7 +219,13 @@ impl ElGamalKeypair {
     ///
     /// This function is randomized. It internally samples a scalar element using `OsRng`.
     pub fn new_rand() -> Self {
-        ElGamal::keygen()
+        unwrap_default_rng(ElGamal::keygen(&mut DefaultRng))
+    }
+
+    /// Generates the public and secret keys for ElGamal encryption using `rng`, returning an
+    /// error if `rng` fails.
+    pub fn new_rand_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, RngError> {
+        ElGamal::keygen(rng)
     }
 
     pub fn pubkey(&self) -> &ElGamalPubkey {
@@ -181,6 +242,9 @@ impl ElGamalKeypair {
 }
 
 impl ElGamalKeypair {
+    /// Byte length of an ElGamal keypair.
+    pub const LEN: usize = ELGAMAL_KEYPAIR_LEN;
+
     /// Create an ElGamal keypair from an ElGamal public key and an ElGamal secret key.
     ///
     /// An ElGamal keypair should never be instantiated manually; `ElGamalKeypair::new`,
@@ -197,6 +261,35 @@ impl ElGamalKeypair {
         Self { public, secret }
     }
 
+    /// Derive an ElGamal keypair from a 32-byte seed.
+    ///
+    /// The secret key is derived with the versioned HKDF-SHA512 chain of
+    /// [`crate::encryption::derivation`]: the seed is the input key material, the salt is
+    /// [`HKDF_SALT`] (`solana-conf-bal/v1`), and 64 bytes that are expanded with the info string
+    /// [`ELGAMAL_HKDF_INFO`] are reduced to a scalar. A future change to the derivation must change
+    /// the version in the salt, so that keys derived under this version can always be regenerated.
+    ///
+    /// This produces the same keypair as `<ElGamalKeypair as SeedDerivable>::from_seed` on the same
+    /// bytes, but cannot fail since the seed length is fixed.
+    pub fn from_seed_bytes(seed: &[u8; ELGAMAL_SECRET_KEY_LEN]) -> Self {
+        // a 32-byte seed is within the accepted seed lengths
+        Self::new(ElGamalSecretKey::from_seed(seed).unwrap())
+    }
+
+    /// Derive an ElGamal keypair from a BIP39 mnemonic and passphrase.
+    ///
+    /// The 64-byte BIP39 seed of the mnemonic and passphrase is used as the input key material of
+    /// the derivation that is described in [`Self::from_seed_bytes`]. This produces the same
+    /// keypair as `<ElGamalKeypair as SeedDerivable>::from_seed_phrase_and_passphrase`.
+    pub fn from_seed_phrase(seed_phrase: &str, passphrase: &str) -> Self {
+        let seed = Zeroizing::new(generate_seed_from_seed_phrase_and_passphrase(
+            seed_phrase,
+            passphrase,
+        ));
+        // a 64-byte seed is within the accepted seed lengths
+        Self::new(ElGamalSecretKey::from_seed(&seed).unwrap())
+    }
+
     /// Derive an ElGamal keypair from a Solana signer using the legacy
     /// SHA3-512 KDF.
     ///
@@ -389,6 +482,9 @@ impl EncodableKeypair for ElGamalKeypair {
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Zeroize)]
 pub struct ElGamalPubkey(RistrettoPoint);
 impl ElGamalPubkey {
+    /// Byte length of an ElGamal public key.
+    pub const LEN: usize = ELGAMAL_PUBKEY_LEN;
+
     /// Derives the `ElGamalPubkey` that uniquely corresponds to an `ElGamalSecretKey`.
     pub fn new(secret: &ElGamalSecretKey) -> Self {
         let s = &secret.0;
@@ -401,6 +497,28 @@ impl ElGamalPubkey {
         &self.0
     }
 
+    pub(crate) fn from_point(point: RistrettoPoint) -> Self {
+        Self(point)
+    }
+
+    /// Returns `true` if the public key is the identity point.
+    pub fn is_identity(&self) -> bool {
+        self.0.is_identity()
+    }
+
+    /// Checks that the public key is a valid encryption key.
+    ///
+    /// The identity public key does not hide the encrypted amounts and any proof that is generated
+    /// with respect to the identity public key is rejected by the verifier. Since the Ristretto
+    /// group has prime order, the identity is the only point of low order and any other public key
+    /// that is decoded from its canonical encoding is valid.
+    pub fn validate(&self) -> Result<(), ElGamalError> {
+        if self.is_identity() {
+            return Err(ElGamalError::InvalidPubkey);
+        }
+        Ok(())
+    }
+
     /// Encrypts an amount under the public key.
     ///
     /// This function is randomized. It internally samples a scalar element using `OsRng`.
@@ -423,6 +541,22 @@ impl ElGamalPubkey {
         DecryptHandle::new(&self, opening)
     }
 
+    /// Generates decryption handles for an ElGamal public key under a list of Pedersen openings.
+    ///
+    /// For a sufficiently large number of openings, a precomputed table of the public key is
+    /// created internally to speed up the computation. To reuse the table across multiple calls,
+    /// use [`ElGamalPubkeyTable`] directly.
+    pub fn decrypt_handles(&self, openings: &[PedersenOpening]) -> Vec<DecryptHandle> {
+        if openings.len() < DECRYPT_HANDLES_TABLE_THRESHOLD {
+            openings
+                .iter()
+                .map(|opening| DecryptHandle::new(self, opening))
+                .collect()
+        } else {
+            ElGamalPubkeyTable::new(self).decrypt_handles(openings)
+        }
+    }
+
     pub fn to_bytes(&self) -> [u8; ELGAMAL_PUBKEY_LEN] {
         self.into()
     }
@@ -436,6 +570,80 @@ impl ElGamalPubkey {
     pub fn encrypt_with_u64(&self, amount: u64, opening: &PedersenOpening) -> ElGamalCiphertext {
         ElGamal::encrypt_with(amount, self, opening)
     }
+
+    /// Encrypts a signed amount under the public key.
+    ///
+    /// A negative amount is encrypted as the additive inverse of its magnitude, so signed
+    /// ciphertexts can be added and subtracted homomorphically. To certify that an encrypted
+    /// amount is in the range `[-2^31, 2^31)` that [`ElGamalCiphertext::decrypt_signed`] decodes,
+    /// use `zk_elgamal_proof_program::build_signed_amount_range_proof_data`.
+    ///
+    /// This function is randomized. It internally samples a scalar element using `OsRng`.
+    pub fn encrypt_signed(&self, amount: i64) -> ElGamalCiphertext {
+        ElGamal::encrypt(self, signed_amount_to_scalar(amount))
+    }
+
+    /// Encrypts a signed amount under the public key and an input Pedersen opening.
+    pub fn encrypt_signed_with(&self, amount: i64, opening: &PedersenOpening) -> ElGamalCiphertext {
+        ElGamal::encrypt_with(signed_amount_to_scalar(amount), self, opening)
+    }
+
+    /// Encrypts an amount under the public key with a Pedersen opening that is derived from a
+    /// secret PRF key.
+    ///
+    /// The opening is derived with HKDF-SHA512 from the PRF key, the domain, the public key, and
+    /// the amount (see [`Self::deterministic_opening`]). Encrypting the same amount under the
+    /// same domain and key always results in the same ciphertext, which allows a client to
+    /// reconstruct its ciphertexts and to retry a transaction idempotently.
+    ///
+    /// Since the amount is part of the PRF input, ciphertexts of distinct amounts have independent
+    /// openings. However, two encryptions of the same amount under the same domain and key are
+    /// equal and therefore linkable, so the domain should identify the ciphertext, for example
+    /// with a transaction nonce.
+    pub fn encrypt_deterministic(
+        &self,
+        amount: u64,
+        domain: &[u8],
+        key: &[u8],
+    ) -> Result<ElGamalCiphertext, ElGamalError> {
+        let opening = self.deterministic_opening(amount, domain, key)?;
+        Ok(ElGamal::encrypt_with(amount, self, &opening))
+    }
+
+    /// Derives the Pedersen opening of a ciphertext that is produced by
+    /// [`Self::encrypt_deterministic`].
+    ///
+    /// The opening can be used to generate the standard proofs on the ciphertext.
+    pub fn deterministic_opening(
+        &self,
+        amount: u64,
+        domain: &[u8],
+        key: &[u8],
+    ) -> Result<PedersenOpening, ElGamalError> {
+        const MINIMUM_KEY_LEN: usize = 32;
+
+        if key.len() < MINIMUM_KEY_LEN {
+            return Err(ElGamalError::SeedLengthTooShort);
+        }
+
+        // the domain is length-prefixed so that the PRF input is unambiguous
+        let info = [
+            &(domain.len() as u64).to_le_bytes(),
+            domain,
+            &self.to_bytes(),
+            &amount.to_le_bytes(),
+        ]
+        .concat();
+
+        let hkdf = Hkdf::<Sha512>::new(Some(DETERMINISTIC_ENCRYPTION_HKDF_SALT), key);
+        let mut wide = Zeroizing::new([0u8; 64]);
+        hkdf.expand(&info, wide.as_mut_slice())
+            .map_err(|_| ElGamalError::SeedLengthTooLong)?;
+
+        Ok(PedersenOpening::new(Scalar::from_bytes_mod_order_wide(
+            &wide,
+        )))
+    }
 }
 
 impl EncodableKey for ElGamalPubkey {
@@ -510,16 +718,18 @@ impl TryFrom<PodElGamalPubkey> for ElGamalPubkey {
 
 /// Secret key for the ElGamal encryption scheme.
 ///
-/// Instances of ElGamal secret key are zeroized on drop.
-#[derive(Clone, Deserialize, Serialize, Zeroize)]
-#[zeroize(drop)]
+/// Instances of ElGamal secret key are zeroized on drop and are redacted from the `Debug` output.
+#[derive(Clone, Deserialize, Serialize, Zeroize, ZeroizeOnDrop)]
 pub struct ElGamalSecretKey(Scalar);
 impl ElGamalSecretKey {
+    /// Byte length of an ElGamal secret key.
+    pub const LEN: usize = ELGAMAL_SECRET_KEY_LEN;
+
     /// Randomly samples an ElGamal secret key.
     ///
     /// This function is randomized. It internally samples a scalar element using `OsRng`.
     pub fn new_rand() -> Self {
-        ElGamalSecretKey(Scalar::random(&mut OsRng))
+        ElGamalSecretKey(Scalar::random(&mut DefaultRng))
     }
 
     /// Derive an ElGamal secret key from an entropy seed.
@@ -558,6 +768,13 @@ impl ElGamalSecretKey {
         self.0.as_bytes()
     }
 
+    /// Computes the Diffie-Hellman shared secret with the ElGamal public key of another party.
+    ///
+    /// See [`crate::encryption::shared_secret`] for details.
+    pub fn diffie_hellman(&self, pubkey: &ElGamalPubkey) -> Result<SharedSecret, ElGamalError> {
+        SharedSecret::new(self, pubkey)
+    }
+
     /// Decrypts a ciphertext using the ElGamal secret key.
     ///
     /// The output of this function is of type `DiscreteLog`. To recover, the originally encrypted
@@ -572,6 +789,22 @@ impl ElGamalSecretKey {
     pub fn decrypt_u32(&self, ciphertext: &ElGamalCiphertext) -> Option<u64> {
         ElGamal::decrypt_u32(self, ciphertext)
     }
+
+    /// Decrypts a ciphertext using the ElGamal secret key interpreting the message as a positive
+    /// number of at most `max_bits` bits.
+    ///
+    /// NOTE: This function is not constant time.
+    pub fn decrypt_within(&self, ciphertext: &ElGamalCiphertext, max_bits: u8) -> Option<u64> {
+        ElGamal::decrypt_within(self, ciphertext, max_bits)
+    }
+
+    /// Decrypts a ciphertext using the ElGamal secret key interpreting the message as a signed
+    /// 32-bit number.
+    ///
+    /// NOTE: This function is not constant time.
+    pub fn decrypt_signed(&self, ciphertext: &ElGamalCiphertext) -> Option<i64> {
+        ElGamal::decrypt_signed(self, ciphertext)
+    }
 }
 
 impl ElGamalSecretKey {
@@ -793,6 +1026,9 @@ pub struct ElGamalCiphertext {
     pub handle: DecryptHandle,
 }
 impl ElGamalCiphertext {
+    /// Byte length of an ElGamal ciphertext.
+    pub const LEN: usize = ELGAMAL_CIPHERTEXT_LEN;
+
     pub fn add_amount<T: Into<Scalar>>(&self, amount: T) -> Self {
         let point = amount.into() * G;
         let commitment_to_add = PedersenCommitment::new(point);
@@ -829,6 +1065,36 @@ impl ElGamalCiphertext {
         })
     }
 
+    /// Checks that the ciphertext is not degenerate.
+    ///
+    /// A ciphertext is degenerate if exactly one of its commitment and decryption handle is the
+    /// identity point:
+    /// - An identity commitment with a non-identity handle cannot be produced by an honest
+    ///   encryption.
+    /// - A non-identity commitment with an identity handle does not hide the encrypted amount.
+    ///   Note that adding an amount to the all-zero ciphertext results in a ciphertext of this
+    ///   form.
+    ///
+    /// The all-zero ciphertext, which is a valid encryption of zero, is not degenerate.
+    pub fn validate(&self) -> Result<(), ElGamalError> {
+        let commitment_is_identity = self.commitment.get_point().is_identity();
+        let handle_is_identity = self.handle.get_point().is_identity();
+
+        if commitment_is_identity != handle_is_identity {
+            return Err(ElGamalError::DegenerateCiphertext);
+        }
+        Ok(())
+    }
+
+    /// Checks that a `Pod` ciphertext is canonically encoded and not degenerate.
+    ///
+    /// This function can be used to reject malformed ciphertexts before they are decoded for
+    /// proof generation.
+    pub fn validate_pod(pod_ciphertext: &PodElGamalCiphertext) -> Result<(), ElGamalError> {
+        let ciphertext: ElGamalCiphertext = (*pod_ciphertext).try_into()?;
+        ciphertext.validate()
+    }
+
     /// Decrypts the ciphertext using an ElGamal secret key.
     ///
     /// The output of this function is of type `DiscreteLog`. To recover, the originally encrypted
@@ -847,6 +1113,29 @@ impl ElGamalCiphertext {
     pub fn decrypt_u32(&self, secret: &ElGamalSecretKey) -> Option<u64> {
         ElGamal::decrypt_u32(secret, self)
     }
+
+    /// Decrypts the ciphertext using an ElGamal secret key assuming that the message is a positive
+    /// number of at most `max_bits` bits.
+    ///
+    /// Smaller bit lengths result in proportionally faster decryption. If the originally
+    /// encrypted amount does not fit in `max_bits` bits or `max_bits` exceeds 32, then the
+    /// function returns `None`.
+    ///
+    /// NOTE: This function is not constant time.
+    pub fn decrypt_within(&self, secret: &ElGamalSecretKey, max_bits: u8) -> Option<u64> {
+        ElGamal::decrypt_within(secret, self, max_bits)
+    }
+
+    /// Decrypts the ciphertext using an ElGamal secret key assuming that the message is a signed
+    /// 32-bit number.
+    ///
+    /// If the originally encrypted amount is not in the range `[-2^31, 2^31)`, then the function
+    /// returns `None`.
+    ///
+    /// NOTE: This function is not constant time.
+    pub fn decrypt_signed(&self, secret: &ElGamalSecretKey) -> Option<i64> {
+        ElGamal::decrypt_signed(secret, self)
+    }
 }
 
 impl fmt::Display for ElGamalCiphertext {
@@ -903,6 +1192,49 @@ define_sub_variants!(
     Output = ElGamalCiphertext
 );
 
+impl<'b> AddAssign<&'b ElGamalCiphertext> for ElGamalCiphertext {
+    fn add_assign(&mut self, ciphertext: &'b ElGamalCiphertext) {
+        self.commitment += &ciphertext.commitment;
+        self.handle += &ciphertext.handle;
+    }
+}
+
+define_add_assign_variants!(LHS = ElGamalCiphertext, RHS = ElGamalCiphertext);
+
+impl<'b> SubAssign<&'b ElGamalCiphertext> for ElGamalCiphertext {
+    fn sub_assign(&mut self, ciphertext: &'b ElGamalCiphertext) {
+        self.commitment -= &ciphertext.commitment;
+        self.handle -= &ciphertext.handle;
+    }
+}
+
+define_sub_assign_variants!(LHS = ElGamalCiphertext, RHS = ElGamalCiphertext);
+
+define_sum_variants!(Type = ElGamalCiphertext);
+
+/// An iterator adapter that sums `Pod` ElGamal ciphertexts.
+pub trait SumPodCiphertexts: Iterator {
+    /// Decompresses and sums the ciphertexts of the iterator.
+    ///
+    /// The running total is kept in decompressed form and is compressed once at the end. The sum
+    /// of an empty iterator is the ciphertext of zero under the zero opening.
+    fn sum_ciphertexts(self) -> Result<PodElGamalCiphertext, ElGamalError>;
+}
+
+impl<I> SumPodCiphertexts for I
+where
+    I: Iterator,
+    I::Item: Borrow<PodElGamalCiphertext>,
+{
+    fn sum_ciphertexts(self) -> Result<PodElGamalCiphertext, ElGamalError> {
+        let mut total = ElGamalCiphertext::default();
+        for pod_ciphertext in self {
+            total += ElGamalCiphertext::try_from(*pod_ciphertext.borrow())?;
+        }
+        Ok(total.into())
+    }
+}
+
 impl<'b> Mul<&'b Scalar> for &ElGamalCiphertext {
     type Output = ElGamalCiphertext;
 
@@ -971,10 +1303,113 @@ define_mul_variants!(
     Output = ElGamalCiphertext
 );
 
+/// The minimum number of openings for which `ElGamalPubkey::decrypt_handles` precomputes a table
+/// of the public key.
+const DECRYPT_HANDLES_TABLE_THRESHOLD: usize = 8;
+
+/// A precomputed table of multiples of an ElGamal public key.
+///
+/// The table speeds up the generation of decryption handles for a fixed public key at the cost of
+/// a one-time precomputation and about 30 KiB of memory.
+pub struct ElGamalPubkeyTable(Box<RistrettoBasepointTable>);
+
+impl ElGamalPubkeyTable {
+    /// Precomputes the table for an ElGamal public key.
+    pub fn new(pubkey: &ElGamalPubkey) -> Self {
+        Self(Box::new(RistrettoBasepointTable::create(&pubkey.0)))
+    }
+
+    /// The ElGamal public key associated with the table.
+    pub fn pubkey(&self) -> ElGamalPubkey {
+        ElGamalPubkey(self.0.basepoint())
+    }
+
+    /// Generates a decryption handle under a Pedersen opening.
+    pub fn decrypt_handle(&self, opening: &PedersenOpening) -> DecryptHandle {
+        DecryptHandle(&*self.0 * opening.get_scalar())
+    }
+
+    /// Generates decryption handles under a list of Pedersen openings.
+    pub fn decrypt_handles(&self, openings: &[PedersenOpening]) -> Vec<DecryptHandle> {
+        openings
+            .iter()
+            .map(|opening| self.decrypt_handle(opening))
+            .collect()
+    }
+}
+
+impl fmt::Debug for ElGamalPubkeyTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ElGamalPubkeyTable")
+            .field(&self.pubkey())
+            .finish()
+    }
+}
+
+/// An ElGamal public key that is prepared for repeated encryption.
+///
+/// A prepared public key computes decryption handles with an [`ElGamalPubkeyTable`] and Pedersen
+/// commitments with precomputed tables of the Pedersen base points. Encryption is several times
+/// faster than under an [`ElGamalPubkey`], which pays off for public keys that many amounts are
+/// encrypted to, such as the deposit keys of an exchange. The ciphertexts are identical to those
+/// generated under the plain public key.
+#[derive(Debug)]
+pub struct PreparedElGamalPubkey(ElGamalPubkeyTable);
+
+impl PreparedElGamalPubkey {
+    /// Prepares an ElGamal public key for repeated encryption.
+    pub fn new(pubkey: &ElGamalPubkey) -> Self {
+        Self(ElGamalPubkeyTable::new(pubkey))
+    }
+
+    /// The ElGamal public key that is prepared.
+    pub fn pubkey(&self) -> ElGamalPubkey {
+        self.0.pubkey()
+    }
+
+    /// Encrypts an amount under the public key.
+    ///
+    /// This function is randomized. It internally samples a scalar element using `OsRng`.
+    pub fn encrypt<T: Into<Scalar>>(&self, amount: T) -> ElGamalCiphertext {
+        self.encrypt_with(amount, &PedersenOpening::new_rand())
+    }
+
+    /// Encrypts an amount under the public key and an input Pedersen opening.
+    pub fn encrypt_with<T: Into<Scalar>>(
+        &self,
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> ElGamalCiphertext {
+        ElGamalCiphertext {
+            commitment: Pedersen::with_tables(amount, opening),
+            handle: self.0.decrypt_handle(opening),
+        }
+    }
+
+    /// Generates a decryption handle for the public key under a Pedersen opening.
+    pub fn decrypt_handle(&self, opening: &PedersenOpening) -> DecryptHandle {
+        self.0.decrypt_handle(opening)
+    }
+
+    /// Generates decryption handles for the public key under a list of Pedersen openings.
+    pub fn decrypt_handles(&self, openings: &[PedersenOpening]) -> Vec<DecryptHandle> {
+        self.0.decrypt_handles(openings)
+    }
+}
+
+impl From<&ElGamalPubkey> for PreparedElGamalPubkey {
+    fn from(pubkey: &ElGamalPubkey) -> Self {
+        Self::new(pubkey)
+    }
+}
+
 /// Decryption handle for Pedersen commitment.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DecryptHandle(RistrettoPoint);
 impl DecryptHandle {
+    /// Byte length of a decrypt handle.
+    pub const LEN: usize = DECRYPT_HANDLE_LEN;
+
     pub fn new(public: &ElGamalPubkey, opening: &PedersenOpening) -> Self {
         Self(&public.0 * opening.get_scalar())
     }
@@ -1041,6 +1476,22 @@ define_sub_variants!(
     Output = DecryptHandle
 );
 
+impl<'b> AddAssign<&'b DecryptHandle> for DecryptHandle {
+    fn add_assign(&mut self, handle: &'b DecryptHandle) {
+        self.0 += &handle.0;
+    }
+}
+
+define_add_assign_variants!(LHS = DecryptHandle, RHS = DecryptHandle);
+
+impl<'b> SubAssign<&'b DecryptHandle> for DecryptHandle {
+    fn sub_assign(&mut self, handle: &'b DecryptHandle) {
+        self.0 -= &handle.0;
+    }
+}
+
+define_sub_assign_variants!(LHS = DecryptHandle, RHS = DecryptHandle);
+
 impl<'b> Mul<&'b Scalar> for &DecryptHandle {
     type Output = DecryptHandle;
 
@@ -1104,6 +1555,40 @@ mod tests {
         assert_eq!(57_u64, secret.decrypt_u32(&ciphertext).unwrap());
     }
 
+    #[test]
+    fn test_debug_redacts_secret_key() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<ElGamalKeypair>();
+        assert_zeroize_on_drop::<ElGamalSecretKey>();
+
+        let keypair = ElGamalKeypair::new_rand();
+        let secret_bytes = format!("{:?}", keypair.secret().as_bytes());
+
+        let keypair_debug = format!("{keypair:?}");
+        assert!(keypair_debug.contains("[REDACTED]"));
+        assert!(!keypair_debug.contains(&secret_bytes));
+        assert_eq!(
+            format!("{:?}", keypair.secret()),
+            "ElGamalSecretKey(\"[REDACTED]\")"
+        );
+    }
+
+    #[test]
+    fn test_decrypt_within() {
+        let keypair = ElGamalKeypair::new_rand();
+        let public = keypair.pubkey();
+        let secret = keypair.secret();
+
+        let ciphertext = public.encrypt(1_000_u64);
+        assert_eq!(ciphertext.decrypt_within(secret, 16), Some(1_000));
+        assert_eq!(secret.decrypt_within(&ciphertext, 24), Some(1_000));
+        assert_eq!(ciphertext.decrypt_within(secret, 8), None);
+
+        let ciphertext = public.encrypt(70_000_u64);
+        assert_eq!(ciphertext.decrypt_within(secret, 16), None);
+        assert_eq!(ciphertext.decrypt_within(secret, 24), Some(70_000));
+    }
+
     #[test]
     fn test_encrypt_decrypt_correctness_multithreaded() {
         let keypair = ElGamalKeypair::new_rand();
@@ -1148,6 +1633,97 @@ mod tests {
         assert_eq!(expected_instance, secret_1.decrypt(&ciphertext_1));
     }
 
+    #[test]
+    fn test_decrypt_handles() {
+        let keypair = ElGamalKeypair::new_rand();
+        let pubkey = keypair.pubkey();
+
+        for num_openings in [0, 1, DECRYPT_HANDLES_TABLE_THRESHOLD, 20] {
+            let openings: Vec<_> = (0..num_openings)
+                .map(|_| PedersenOpening::new_rand())
+                .collect();
+            let expected: Vec<_> = openings
+                .iter()
+                .map(|opening| pubkey.decrypt_handle(opening))
+                .collect();
+            assert_eq!(pubkey.decrypt_handles(&openings), expected);
+        }
+
+        let table = ElGamalPubkeyTable::new(pubkey);
+        assert_eq!(table.pubkey(), *pubkey);
+
+        let opening = PedersenOpening::new_rand();
+        let ciphertext = pubkey.encrypt_with(55_u64, &opening);
+        assert_eq!(table.decrypt_handle(&opening), ciphertext.handle);
+    }
+
+    #[test]
+    fn test_prepared_pubkey() {
+        let keypair = ElGamalKeypair::new_rand();
+        let pubkey = keypair.pubkey();
+        let prepared = PreparedElGamalPubkey::from(pubkey);
+        assert_eq!(prepared.pubkey(), *pubkey);
+
+        // encryption under a prepared public key matches encryption under the public key
+        let opening = PedersenOpening::new_rand();
+        assert_eq!(
+            prepared.encrypt_with(55_u64, &opening),
+            pubkey.encrypt_with(55_u64, &opening)
+        );
+        assert_eq!(
+            prepared.decrypt_handle(&opening),
+            pubkey.decrypt_handle(&opening)
+        );
+
+        let ciphertext = prepared.encrypt(77_u64);
+        assert_eq!(keypair.secret().decrypt_u32(&ciphertext), Some(77));
+    }
+
+    #[test]
+    fn test_ciphertext_assign_and_sum() {
+        let keypair = ElGamalKeypair::new_rand();
+        let pubkey = keypair.pubkey();
+        let ciphertexts: Vec<_> = (1..=10_u64).map(|amount| pubkey.encrypt(amount)).collect();
+
+        let mut total = ElGamalCiphertext::default();
+        for ciphertext in &ciphertexts {
+            total += ciphertext;
+        }
+        let expected = ciphertexts[1..]
+            .iter()
+            .fold(ciphertexts[0], |acc, ciphertext| acc + ciphertext);
+        assert_eq!(total, expected);
+        assert_eq!(ciphertexts.iter().sum::<ElGamalCiphertext>(), expected);
+        assert_eq!(
+            ciphertexts.iter().copied().sum::<ElGamalCiphertext>(),
+            expected
+        );
+        assert_eq!(keypair.secret().decrypt_u32(&total), Some(55));
+
+        total -= ciphertexts[9];
+        assert_eq!(keypair.secret().decrypt_u32(&total), Some(45));
+
+        let pod_ciphertexts: Vec<PodElGamalCiphertext> = ciphertexts
+            .iter()
+            .map(|&ciphertext| ciphertext.into())
+            .collect();
+        assert_eq!(
+            pod_ciphertexts.iter().sum_ciphertexts(),
+            Ok(PodElGamalCiphertext::from(expected))
+        );
+        assert_eq!(
+            std::iter::empty::<PodElGamalCiphertext>().sum_ciphertexts(),
+            Ok(PodElGamalCiphertext::from(ElGamalCiphertext::default()))
+        );
+
+        let invalid = PodElGamalCiphertext([0xff; ELGAMAL_CIPHERTEXT_LEN]);
+        assert!(pod_ciphertexts
+            .into_iter()
+            .chain(std::iter::once(invalid))
+            .sum_ciphertexts()
+            .is_err());
+    }
+
     #[test]
     fn test_homomorphic_addition() {
         let keypair = ElGamalKeypair::new_rand();
@@ -1234,6 +1810,148 @@ mod tests {
         assert_eq!(ciphertext, decoded);
     }
 
+    #[test]
+    fn test_pubkey_validate() {
+        let keypair = ElGamalKeypair::new_rand();
+        assert!(!keypair.pubkey().is_identity());
+        assert!(keypair.pubkey().validate().is_ok());
+
+        let identity_pubkey = ElGamalPubkey(RistrettoPoint::identity());
+        assert!(identity_pubkey.is_identity());
+        assert_eq!(
+            identity_pubkey.validate().unwrap_err(),
+            ElGamalError::InvalidPubkey
+        );
+
+        // the all-zero encoding decodes to the identity
+        let zeroed_pubkey = ElGamalPubkey::try_from([0; ELGAMAL_PUBKEY_LEN].as_slice()).unwrap();
+        assert!(zeroed_pubkey.validate().is_err());
+    }
+
+    #[test]
+    fn test_ciphertext_validate() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(77_u64);
+        assert!(ciphertext.validate().is_ok());
+        assert!(ElGamalCiphertext::validate_pod(&ciphertext.into()).is_ok());
+
+        // the all-zero ciphertext is a valid encryption of zero
+        let zero_ciphertext = ElGamalCiphertext::default();
+        assert!(zero_ciphertext.validate().is_ok());
+        assert!(ElGamalCiphertext::validate_pod(&PodElGamalCiphertext::default()).is_ok());
+
+        // identity commitment with non-identity handle
+        let ciphertext_with_identity_commitment = ElGamalCiphertext {
+            commitment: PedersenCommitment::default(),
+            handle: ciphertext.handle,
+        };
+        assert_eq!(
+            ciphertext_with_identity_commitment.validate().unwrap_err(),
+            ElGamalError::DegenerateCiphertext
+        );
+
+        // non-identity commitment with identity handle
+        let ciphertext_with_identity_handle = zero_ciphertext.add_amount(77_u64);
+        assert_eq!(
+            ciphertext_with_identity_handle.validate().unwrap_err(),
+            ElGamalError::DegenerateCiphertext
+        );
+        assert_eq!(
+            ElGamalCiphertext::validate_pod(&ciphertext_with_identity_handle.into()).unwrap_err(),
+            ElGamalError::DegenerateCiphertext
+        );
+
+        // non-canonical encoding
+        let mut pod_ciphertext: PodElGamalCiphertext = ciphertext.into();
+        pod_ciphertext.0[..PEDERSEN_COMMITMENT_LEN].fill(255);
+        assert_eq!(
+            ElGamalCiphertext::validate_pod(&pod_ciphertext).unwrap_err(),
+            ElGamalError::CiphertextDeserialization
+        );
+    }
+
+    #[test]
+    fn test_encrypt_deterministic() {
+        let keypair = ElGamalKeypair::new_rand();
+        let pubkey = keypair.pubkey();
+        let key = [7u8; 32];
+
+        let ciphertext = pubkey.encrypt_deterministic(55, b"nonce-0", &key).unwrap();
+        assert_eq!(
+            ciphertext,
+            pubkey.encrypt_deterministic(55, b"nonce-0", &key).unwrap()
+        );
+        assert_eq!(keypair.secret().decrypt_u32(&ciphertext), Some(55));
+
+        // the opening can be recovered for proof generation
+        let opening = pubkey.deterministic_opening(55, b"nonce-0", &key).unwrap();
+        assert_eq!(ciphertext, pubkey.encrypt_with(55_u64, &opening));
+
+        // distinct domains, keys, amounts, and public keys result in distinct openings
+        assert_ne!(
+            opening,
+            pubkey.deterministic_opening(55, b"nonce-1", &key).unwrap()
+        );
+        assert_ne!(
+            opening,
+            pubkey
+                .deterministic_opening(55, b"nonce-0", &[8u8; 32])
+                .unwrap()
+        );
+        assert_ne!(
+            opening,
+            pubkey.deterministic_opening(56, b"nonce-0", &key).unwrap()
+        );
+        assert_ne!(
+            opening,
+            ElGamalKeypair::new_rand()
+                .pubkey()
+                .deterministic_opening(55, b"nonce-0", &key)
+                .unwrap()
+        );
+
+        assert_eq!(
+            pubkey
+                .encrypt_deterministic(55, b"nonce-0", &[7u8; 31])
+                .unwrap_err(),
+            ElGamalError::SeedLengthTooShort
+        );
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_signed() {
+        let keypair = ElGamalKeypair::new_rand();
+        let public = keypair.pubkey();
+        let secret = keypair.secret();
+
+        let positive = public.encrypt_signed(77);
+        let negative = public.encrypt_signed(-55);
+        assert_eq!(positive.decrypt_signed(secret), Some(77));
+        assert_eq!(negative.decrypt_signed(secret), Some(-55));
+
+        // signed ciphertexts are homomorphic
+        assert_eq!((positive + negative).decrypt_signed(secret), Some(22));
+        assert_eq!((negative - positive).decrypt_signed(secret), Some(-132));
+        assert_eq!(negative.add_amount(55_u64).decrypt_signed(secret), Some(0));
+
+        // only amounts in the range `[-2^31, 2^31)` are decoded
+        for amount in [(1 << 31) - 1, -(1 << 31)] {
+            assert_eq!(
+                public.encrypt_signed(amount).decrypt_signed(secret),
+                Some(amount)
+            );
+        }
+        for amount in [1 << 31, -(1 << 31) - 1, (1 << 47) - 1, -(1 << 47) + 1] {
+            assert_eq!(public.encrypt_signed(amount).decrypt_signed(secret), None);
+        }
+
+        let opening = PedersenOpening::new_rand();
+        assert_eq!(
+            public.encrypt_signed_with(-1, &opening),
+            public.encrypt_with(-Scalar::ONE, &opening)
+        );
+    }
+
     #[test]
     fn test_serde_pubkey() {
         let keypair = ElGamalKeypair::new_rand();
@@ -1387,6 +2105,27 @@ mod tests {
         assert_eq!(keypair.public, expected_keypair.public);
     }
 
+    #[test]
+    fn test_keypair_from_seed_bytes() {
+        let seed = [7; ELGAMAL_SECRET_KEY_LEN];
+        let expected_keypair = <ElGamalKeypair as SeedDerivable>::from_seed(&seed).unwrap();
+        assert_eq!(ElGamalKeypair::from_seed_bytes(&seed), expected_keypair);
+        assert_ne!(
+            ElGamalKeypair::from_seed_bytes(&[8; ELGAMAL_SECRET_KEY_LEN]),
+            expected_keypair
+        );
+
+        let mnemonic = Mnemonic::generate_in(Language::English, 12).unwrap();
+        let passphrase = "42";
+        let expected_keypair =
+            ElGamalKeypair::from_seed_phrase_and_passphrase(&mnemonic.to_string(), passphrase)
+                .unwrap();
+        assert_eq!(
+            ElGamalKeypair::from_seed_phrase(&mnemonic.to_string(), passphrase),
+            expected_keypair
+        );
+    }
+
     #[test]
     fn test_decrypt_handle_bytes() {
         let handle = DecryptHandle(RistrettoPoint::default());
@@ -16,18 +16,25 @@ use {
     crate::{
         encryption::{
             discrete_log::DiscreteLog,
-            elgamal::{DecryptHandle, ElGamalCiphertext, ElGamalPubkey, ElGamalSecretKey},
+            elgamal::{
+                DecryptHandle, ElGamalCiphertext, ElGamalPubkey, ElGamalPubkeyTable,
+                ElGamalSecretKey,
+            },
             pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
         },
         errors::ElGamalError,
     },
     curve25519_dalek::scalar::Scalar,
     solana_zk_sdk_pod::{
-        encryption::grouped_elgamal::{
-            PodGroupedElGamalCiphertext2Handles, PodGroupedElGamalCiphertext3Handles,
+        encryption::{
+            grouped_elgamal::{
+                PodGroupedElGamalCiphertext2Handles, PodGroupedElGamalCiphertext3Handles,
+            },
+            GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN, GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN,
         },
         RISTRETTO_POINT_LEN,
     },
+    std::iter,
     thiserror::Error,
 };
 
@@ -35,6 +42,8 @@ use {
 pub enum GroupedElGamalError {
     #[error("index out of bounds")]
     IndexOutOfBounds,
+    #[error("ciphertext commitments do not match")]
+    CommitmentMismatch,
 }
 
 /// Algorithm handle for the grouped ElGamal encryption
@@ -82,6 +91,26 @@ impl<const N: usize> GroupedElGamal<N> {
         }
     }
 
+    /// Generates the decryption handles for an array of ElGamal public keys under a list of
+    /// Pedersen openings.
+    ///
+    /// The output contains one array of handles per opening. A precomputed table is created for
+    /// each public key, so this is most efficient when the number of openings is large.
+    pub fn decrypt_handles(
+        pubkeys: [&ElGamalPubkey; N],
+        openings: &[PedersenOpening],
+    ) -> Vec<[DecryptHandle; N]> {
+        let tables = pubkeys.map(ElGamalPubkeyTable::new);
+        openings
+            .iter()
+            .map(|opening| {
+                let handles: [DecryptHandle; N] =
+                    core::array::from_fn(|i| tables[i].decrypt_handle(opening));
+                handles
+            })
+            .collect()
+    }
+
     /// Converts a grouped ElGamal ciphertext into a regular ElGamal ciphertext using the decrypt
     /// handle at a specified index.
     fn to_elgamal_ciphertext(
@@ -143,6 +172,14 @@ pub struct GroupedElGamalCiphertext<const N: usize> {
 }
 
 impl<const N: usize> GroupedElGamalCiphertext<N> {
+    /// Byte length of a serialized grouped ElGamal ciphertext.
+    ///
+    /// A grouped ElGamal ciphertext consists of a Pedersen commitment and an array of decryption
+    /// handles. The commitment and decryption handles are each a single Curve25519 group element
+    /// that is serialized as 32 bytes. Therefore, the total byte length of a grouped ciphertext is
+    /// `(N+1) * 32`.
+    pub const LEN: usize = RISTRETTO_POINT_LEN * (N + 1);
+
     /// Converts a grouped ElGamal ciphertext into a regular ElGamal ciphertext using the decrypt
     /// handle at a specified index.
     pub fn to_elgamal_ciphertext(
@@ -152,29 +189,50 @@ impl<const N: usize> GroupedElGamalCiphertext<N> {
         GroupedElGamal::to_elgamal_ciphertext(self, index)
     }
 
-    /// The expected length of a serialized grouped ElGamal ciphertext.
+    /// Combines regular ElGamal ciphertexts that share the same Pedersen commitment into a
+    /// grouped ElGamal ciphertext.
     ///
-    /// A grouped ElGamal ciphertext consists of a Pedersen commitment and an array of decryption
-    /// handles. The commitment and decryption handles are each a single Curve25519 group element
-    /// that is serialized as 32 bytes. Therefore, the total byte length of a grouped ciphertext is
-    /// `(N+1) * 32`.
-    fn expected_byte_length() -> usize {
-        N.checked_add(1)
-            .and_then(|length| length.checked_mul(RISTRETTO_POINT_LEN))
-            .unwrap()
+    /// The decrypt handle of the ciphertext at position `i` becomes the handle at index `i` of the
+    /// grouped ciphertext.
+    pub fn try_from_elgamal_ciphertexts(
+        ciphertexts: [ElGamalCiphertext; N],
+    ) -> Result<Self, GroupedElGamalError> {
+        let commitment = ciphertexts
+            .first()
+            .map(|ciphertext| ciphertext.commitment)
+            .ok_or(GroupedElGamalError::IndexOutOfBounds)?;
+
+        if ciphertexts
+            .iter()
+            .any(|ciphertext| ciphertext.commitment != commitment)
+        {
+            return Err(GroupedElGamalError::CommitmentMismatch);
+        }
+
+        Ok(Self {
+            commitment,
+            handles: ciphertexts.map(|ciphertext| ciphertext.handle),
+        })
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(Self::expected_byte_length());
-        buf.extend_from_slice(&self.commitment.to_bytes());
-        self.handles
-            .iter()
-            .for_each(|handle| buf.extend_from_slice(&handle.to_bytes()));
+        let mut buf = vec![0_u8; Self::LEN];
+        self.write_bytes(&mut buf);
         buf
     }
 
+    /// Writes the commitment followed by the handles into `buf`, which must be `Self::LEN` bytes
+    /// long.
+    fn write_bytes(&self, buf: &mut [u8]) {
+        let units = iter::once(self.commitment.to_bytes())
+            .chain(self.handles.iter().map(|handle| handle.to_bytes()));
+        buf.chunks_exact_mut(RISTRETTO_POINT_LEN)
+            .zip(units)
+            .for_each(|(chunk, unit)| chunk.copy_from_slice(&unit));
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() != Self::expected_byte_length() {
+        if bytes.len() != Self::LEN {
             return None;
         }
 
@@ -225,7 +283,9 @@ impl<const N: usize> GroupedElGamalCiphertext<N> {
 
 impl From<GroupedElGamalCiphertext<2>> for PodGroupedElGamalCiphertext2Handles {
     fn from(decoded_ciphertext: GroupedElGamalCiphertext<2>) -> Self {
-        Self(decoded_ciphertext.to_bytes().try_into().unwrap())
+        let mut bytes = [0_u8; GROUPED_ELGAMAL_CIPHERTEXT_2_HANDLES_LEN];
+        decoded_ciphertext.write_bytes(&mut bytes);
+        Self(bytes)
     }
 }
 
@@ -239,7 +299,9 @@ impl TryFrom<PodGroupedElGamalCiphertext2Handles> for GroupedElGamalCiphertext<2
 
 impl From<GroupedElGamalCiphertext<3>> for PodGroupedElGamalCiphertext3Handles {
     fn from(decoded_ciphertext: GroupedElGamalCiphertext<3>) -> Self {
-        Self(decoded_ciphertext.to_bytes().try_into().unwrap())
+        let mut bytes = [0_u8; GROUPED_ELGAMAL_CIPHERTEXT_3_HANDLES_LEN];
+        decoded_ciphertext.write_bytes(&mut bytes);
+        Self(bytes)
     }
 }
 
@@ -318,6 +380,15 @@ mod tests {
 
         let produced_bytes = grouped_ciphertext.to_bytes();
         assert_eq!(produced_bytes.len(), 128);
+        assert_eq!(produced_bytes.len(), GroupedElGamalCiphertext::<3>::LEN);
+        assert_eq!(
+            GroupedElGamalCiphertext::<3>::LEN,
+            PodGroupedElGamalCiphertext3Handles::LEN
+        );
+        assert_eq!(
+            GroupedElGamalCiphertext::<2>::LEN,
+            PodGroupedElGamalCiphertext2Handles::LEN
+        );
 
         let decoded_grouped_ciphertext =
             GroupedElGamalCiphertext::<3>::from_bytes(&produced_bytes).unwrap();
@@ -359,6 +430,52 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_grouped_ciphertext_from_elgamal_ciphertexts() {
+        let elgamal_keypair_0 = ElGamalKeypair::new_rand();
+        let elgamal_keypair_1 = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 10;
+        let grouped_ciphertext = GroupedElGamal::encrypt(
+            [elgamal_keypair_0.pubkey(), elgamal_keypair_1.pubkey()],
+            amount,
+        );
+
+        let ciphertext_0 = grouped_ciphertext.to_elgamal_ciphertext(0).unwrap();
+        let ciphertext_1 = grouped_ciphertext.to_elgamal_ciphertext(1).unwrap();
+
+        let combined =
+            GroupedElGamalCiphertext::try_from_elgamal_ciphertexts([ciphertext_0, ciphertext_1])
+                .unwrap();
+        assert_eq!(combined, grouped_ciphertext);
+
+        let other_ciphertext = elgamal_keypair_1.pubkey().encrypt(amount);
+        assert_eq!(
+            GroupedElGamalCiphertext::try_from_elgamal_ciphertexts([
+                ciphertext_0,
+                other_ciphertext
+            ])
+            .unwrap_err(),
+            GroupedElGamalError::CommitmentMismatch
+        );
+    }
+
+    #[test]
+    fn test_grouped_decrypt_handles() {
+        let elgamal_keypair_0 = ElGamalKeypair::new_rand();
+        let elgamal_keypair_1 = ElGamalKeypair::new_rand();
+        let pubkeys = [elgamal_keypair_0.pubkey(), elgamal_keypair_1.pubkey()];
+
+        let openings: Vec<_> = (0..4).map(|_| PedersenOpening::new_rand()).collect();
+        let handles = GroupedElGamal::decrypt_handles(pubkeys, &openings);
+        assert_eq!(handles.len(), openings.len());
+
+        for (opening, handles) in openings.iter().zip(handles) {
+            let grouped_ciphertext = GroupedElGamal::encrypt_with(pubkeys, 10_u64, opening);
+            assert_eq!(grouped_ciphertext.handles, handles);
+        }
+    }
+
     #[test]
     fn test_zero_sized_group() {
         let amount: u64 = 42;
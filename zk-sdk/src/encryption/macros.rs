@@ -72,3 +72,45 @@ macro_rules! define_mul_variants {
         }
     };
 }
+
+macro_rules! define_add_assign_variants {
+    (LHS = $lhs:ty, RHS = $rhs:ty) => {
+        impl AddAssign<$rhs> for $lhs {
+            fn add_assign(&mut self, rhs: $rhs) {
+                *self += &rhs;
+            }
+        }
+    };
+}
+
+macro_rules! define_sub_assign_variants {
+    (LHS = $lhs:ty, RHS = $rhs:ty) => {
+        impl SubAssign<$rhs> for $lhs {
+            fn sub_assign(&mut self, rhs: $rhs) {
+                *self -= &rhs;
+            }
+        }
+    };
+}
+
+macro_rules! define_sum_variants {
+    (Type = $t:ty) => {
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::default(), |mut acc, item| {
+                    acc += &item;
+                    acc
+                })
+            }
+        }
+
+        impl<'a> Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::default(), |mut acc, item| {
+                    acc += item;
+                    acc
+                })
+            }
+        }
+    };
+}
@@ -12,9 +12,21 @@
 
 #[macro_use]
 pub(crate) mod macros;
+pub mod aggregation;
+pub mod asset;
 pub mod auth_encryption;
+pub mod compact_ciphertext;
 pub mod derivation;
 pub mod discrete_log;
 pub mod elgamal;
 pub mod grouped_elgamal;
+pub mod one_time_key;
+pub mod opening_tracker;
 pub mod pedersen;
+#[cfg(feature = "pkcs8")]
+pub mod pkcs8;
+pub mod program_derived_key;
+pub mod role;
+pub mod secret_key_handle;
+pub mod shared_secret;
+pub mod threshold;
@@ -0,0 +1,141 @@
+//! One-time (stealth) ElGamal public keys.
+//!
+//! Repeated transfers to the same long-term ElGamal public key are linkable by the public key. To
+//! avoid this, a sender can derive a fresh one-time public key for every transfer from the
+//! long-term public key of the recipient and an ephemeral keypair. The sender publishes the
+//! ephemeral public key alongside the transfer, and the recipient uses it to recover the secret
+//! key that corresponds to the one-time public key.
+//!
+//! For a recipient keypair `(s, P)` and a sender ephemeral keypair `(e, E)`, both parties can
+//! compute the Diffie-Hellman shared secret (see [`crate::encryption::shared_secret`]). A scalar
+//! tweak `t` is derived from the shared secret, and
+//!
+//! ```text
+//! one-time public key = t * P = (s * t^-1)^-1 * H
+//! one-time secret key = s * t^-1
+//! ```
+//!
+//! Without the knowledge of `e` or `s`, the one-time public key is indistinguishable from a
+//! random public key.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey},
+            shared_secret::SharedSecret,
+        },
+        errors::ElGamalError,
+    },
+    curve25519_dalek::scalar::Scalar,
+    zeroize::Zeroizing,
+};
+
+/// HKDF info string for the one-time public key tweak.
+pub const ONE_TIME_KEY_HKDF_INFO: &[u8] = b"one-time-pubkey";
+
+/// A one-time ElGamal public key along with the ephemeral public key that the recipient needs to
+/// recover the corresponding secret key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OneTimePubkey {
+    /// The one-time public key to encrypt the transfer under.
+    pub one_time_pubkey: ElGamalPubkey,
+    /// The ephemeral public key to be published with the transfer.
+    pub ephemeral_pubkey: ElGamalPubkey,
+}
+
+/// Derives a one-time public key for a recipient with a freshly sampled ephemeral keypair.
+///
+/// This function is randomized. It internally samples an ephemeral keypair using `OsRng`.
+pub fn derive_one_time_pubkey(
+    recipient_pubkey: &ElGamalPubkey,
+) -> Result<OneTimePubkey, ElGamalError> {
+    let ephemeral_keypair = ElGamalKeypair::new_rand();
+    derive_one_time_pubkey_with(recipient_pubkey, ephemeral_keypair.secret())
+}
+
+/// Derives a one-time public key for a recipient with an ephemeral secret key.
+///
+/// The ephemeral secret key must not be reused across transfers.
+pub fn derive_one_time_pubkey_with(
+    recipient_pubkey: &ElGamalPubkey,
+    ephemeral_secret: &ElGamalSecretKey,
+) -> Result<OneTimePubkey, ElGamalError> {
+    let shared_secret = ephemeral_secret.diffie_hellman(recipient_pubkey)?;
+    let tweak = one_time_key_tweak(&shared_secret)?;
+
+    Ok(OneTimePubkey {
+        one_time_pubkey: ElGamalPubkey::from_point(*tweak * recipient_pubkey.get_point()),
+        ephemeral_pubkey: ElGamalPubkey::new(ephemeral_secret),
+    })
+}
+
+/// Recovers the keypair that corresponds to a one-time public key from the secret key of the
+/// recipient and the ephemeral public key that was published with the transfer.
+///
+/// A recipient can check whether a transfer is addressed to it by comparing the public key of the
+/// recovered keypair against the one-time public key of the transfer.
+pub fn recover_one_time_keypair(
+    recipient_secret: &ElGamalSecretKey,
+    ephemeral_pubkey: &ElGamalPubkey,
+) -> Result<ElGamalKeypair, ElGamalError> {
+    let shared_secret = recipient_secret.diffie_hellman(ephemeral_pubkey)?;
+    let tweak = one_time_key_tweak(&shared_secret)?;
+
+    let one_time_secret = recipient_secret.get_scalar() * tweak.invert();
+    Ok(ElGamalKeypair::new(ElGamalSecretKey::from(one_time_secret)))
+}
+
+/// Derives the scalar tweak of a one-time public key from a shared secret.
+fn one_time_key_tweak(shared_secret: &SharedSecret) -> Result<Zeroizing<Scalar>, ElGamalError> {
+    let mut wide = Zeroizing::new([0u8; 64]);
+    shared_secret.derive_key(ONE_TIME_KEY_HKDF_INFO, wide.as_mut_slice())?;
+
+    let tweak = Zeroizing::new(Scalar::from_bytes_mod_order_wide(&wide));
+    if *tweak == Scalar::ZERO {
+        return Err(ElGamalError::InvalidPubkey);
+    }
+    Ok(tweak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_time_pubkey_recovery() {
+        let recipient = ElGamalKeypair::new_rand();
+
+        let derived = derive_one_time_pubkey(recipient.pubkey()).unwrap();
+        assert_ne!(derived.one_time_pubkey, *recipient.pubkey());
+
+        let one_time_keypair =
+            recover_one_time_keypair(recipient.secret(), &derived.ephemeral_pubkey).unwrap();
+        assert_eq!(*one_time_keypair.pubkey(), derived.one_time_pubkey);
+
+        // the recovered keypair decrypts ciphertexts under the one-time public key
+        let ciphertext = derived.one_time_pubkey.encrypt(55_u64);
+        assert_eq!(one_time_keypair.secret().decrypt_u32(&ciphertext), Some(55));
+
+        // another recipient does not recover the one-time keypair
+        let other = ElGamalKeypair::new_rand();
+        let other_keypair =
+            recover_one_time_keypair(other.secret(), &derived.ephemeral_pubkey).unwrap();
+        assert_ne!(*other_keypair.pubkey(), derived.one_time_pubkey);
+    }
+
+    #[test]
+    fn test_one_time_pubkeys_are_unlinkable() {
+        let recipient = ElGamalKeypair::new_rand();
+
+        let derived_0 = derive_one_time_pubkey(recipient.pubkey()).unwrap();
+        let derived_1 = derive_one_time_pubkey(recipient.pubkey()).unwrap();
+        assert_ne!(derived_0.one_time_pubkey, derived_1.one_time_pubkey);
+
+        // the derivation is deterministic in the ephemeral secret key
+        let ephemeral = ElGamalKeypair::new_rand();
+        assert_eq!(
+            derive_one_time_pubkey_with(recipient.pubkey(), ephemeral.secret()).unwrap(),
+            derive_one_time_pubkey_with(recipient.pubkey(), ephemeral.secret()).unwrap(),
+        );
+    }
+}
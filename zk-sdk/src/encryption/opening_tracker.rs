@@ -0,0 +1,279 @@
+//! A ledger of Pedersen openings for homomorphically maintained commitments and ciphertexts.
+//!
+//! Generating a proof on a commitment or a ciphertext that is the result of homomorphic
+//! operations requires the opening of the result, which is the corresponding combination of the
+//! openings of the inputs. The `OpeningTracker` records the opening associated with each
+//! commitment that a client holds and derives the openings of the results of homomorphic
+//! operations as they are applied.
+
+use {
+    crate::encryption::{
+        elgamal::ElGamalCiphertext,
+        pedersen::{PedersenCommitment, PedersenOpening},
+    },
+    solana_zk_sdk_pod::encryption::{PEDERSEN_COMMITMENT_LEN, PEDERSEN_OPENING_LEN},
+    std::collections::BTreeMap,
+};
+
+/// Byte length of a single serialized entry of an opening tracker
+const OPENING_TRACKER_ENTRY_LEN: usize = PEDERSEN_COMMITMENT_LEN + PEDERSEN_OPENING_LEN;
+
+/// A ledger of the Pedersen openings associated with commitments.
+///
+/// Openings are indexed by commitment. The opening of an ElGamal ciphertext is the opening of its
+/// commitment component, so ciphertexts are tracked through their commitments.
+///
+/// The homomorphic operations on the tracker compute the result of the operation and record the
+/// opening of the result. The inputs remain tracked until they are explicitly removed.
+#[derive(Clone, Debug, Default)]
+pub struct OpeningTracker {
+    openings: BTreeMap<[u8; PEDERSEN_COMMITMENT_LEN], PedersenOpening>,
+}
+
+impl OpeningTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of tracked commitments.
+    pub fn len(&self) -> usize {
+        self.openings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.openings.is_empty()
+    }
+
+    /// Records the opening of a commitment, returning the previously recorded opening if the
+    /// commitment was already tracked.
+    pub fn insert(
+        &mut self,
+        commitment: &PedersenCommitment,
+        opening: PedersenOpening,
+    ) -> Option<PedersenOpening> {
+        self.openings.insert(commitment.to_bytes(), opening)
+    }
+
+    /// Returns the opening of a commitment if it is tracked.
+    pub fn get(&self, commitment: &PedersenCommitment) -> Option<&PedersenOpening> {
+        self.openings.get(&commitment.to_bytes())
+    }
+
+    /// Stops tracking a commitment, returning its opening if it was tracked.
+    pub fn remove(&mut self, commitment: &PedersenCommitment) -> Option<PedersenOpening> {
+        self.openings.remove(&commitment.to_bytes())
+    }
+
+    /// Adds two tracked commitments and records the opening of the sum.
+    ///
+    /// Returns `None` if the opening of either commitment is not tracked.
+    pub fn add(
+        &mut self,
+        left_commitment: &PedersenCommitment,
+        right_commitment: &PedersenCommitment,
+    ) -> Option<PedersenCommitment> {
+        let opening = self.get(left_commitment)? + self.get(right_commitment)?;
+        let commitment = left_commitment + right_commitment;
+        self.insert(&commitment, opening);
+        Some(commitment)
+    }
+
+    /// Subtracts two tracked commitments and records the opening of the difference.
+    ///
+    /// Returns `None` if the opening of either commitment is not tracked.
+    pub fn subtract(
+        &mut self,
+        left_commitment: &PedersenCommitment,
+        right_commitment: &PedersenCommitment,
+    ) -> Option<PedersenCommitment> {
+        let opening = self.get(left_commitment)? - self.get(right_commitment)?;
+        let commitment = left_commitment - right_commitment;
+        self.insert(&commitment, opening);
+        Some(commitment)
+    }
+
+    /// Adds two ciphertexts with tracked commitments and records the opening of the sum.
+    ///
+    /// Returns `None` if the opening of either ciphertext is not tracked.
+    pub fn add_ciphertexts(
+        &mut self,
+        left_ciphertext: &ElGamalCiphertext,
+        right_ciphertext: &ElGamalCiphertext,
+    ) -> Option<ElGamalCiphertext> {
+        self.add(&left_ciphertext.commitment, &right_ciphertext.commitment)?;
+        Some(left_ciphertext + right_ciphertext)
+    }
+
+    /// Subtracts two ciphertexts with tracked commitments and records the opening of the
+    /// difference.
+    ///
+    /// Returns `None` if the opening of either ciphertext is not tracked.
+    pub fn subtract_ciphertexts(
+        &mut self,
+        left_ciphertext: &ElGamalCiphertext,
+        right_ciphertext: &ElGamalCiphertext,
+    ) -> Option<ElGamalCiphertext> {
+        self.subtract(&left_ciphertext.commitment, &right_ciphertext.commitment)?;
+        Some(left_ciphertext - right_ciphertext)
+    }
+
+    /// Adds a plaintext amount to a ciphertext with a tracked commitment and records the opening
+    /// of the result.
+    ///
+    /// Adding a plaintext amount does not change the opening of the ciphertext. Returns `None` if
+    /// the opening of the ciphertext is not tracked.
+    pub fn add_amount(
+        &mut self,
+        ciphertext: &ElGamalCiphertext,
+        amount: u64,
+    ) -> Option<ElGamalCiphertext> {
+        let opening = self.get(&ciphertext.commitment)?.clone();
+        let result = ciphertext.add_amount(amount);
+        self.insert(&result.commitment, opening);
+        Some(result)
+    }
+
+    /// Subtracts a plaintext amount from a ciphertext with a tracked commitment and records the
+    /// opening of the result.
+    ///
+    /// Subtracting a plaintext amount does not change the opening of the ciphertext. Returns
+    /// `None` if the opening of the ciphertext is not tracked.
+    pub fn subtract_amount(
+        &mut self,
+        ciphertext: &ElGamalCiphertext,
+        amount: u64,
+    ) -> Option<ElGamalCiphertext> {
+        let opening = self.get(&ciphertext.commitment)?.clone();
+        let result = ciphertext.subtract_amount(amount);
+        self.insert(&result.commitment, opening);
+        Some(result)
+    }
+
+    /// Serializes the tracked commitments and openings.
+    ///
+    /// The output contains the Pedersen openings in the clear and must be stored as secret data.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.openings.len() * OPENING_TRACKER_ENTRY_LEN);
+        for (commitment, opening) in self.openings.iter() {
+            buf.extend_from_slice(commitment);
+            buf.extend_from_slice(opening.as_bytes());
+        }
+        buf
+    }
+
+    /// Restores an opening tracker from its serialization.
+    ///
+    /// Returns `None` if the serialization contains an invalid commitment or opening, or if a
+    /// commitment is recorded more than once.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if !bytes.len().is_multiple_of(OPENING_TRACKER_ENTRY_LEN) {
+            return None;
+        }
+
+        let mut tracker = Self::new();
+        for entry in bytes.chunks_exact(OPENING_TRACKER_ENTRY_LEN) {
+            let (commitment, opening) = entry.split_at(PEDERSEN_COMMITMENT_LEN);
+            let commitment = PedersenCommitment::from_bytes(commitment)?;
+            let opening = PedersenOpening::from_bytes(opening)?;
+
+            if tracker.insert(&commitment, opening).is_some() {
+                return None;
+            }
+        }
+        Some(tracker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+    };
+
+    #[test]
+    fn test_opening_tracker_commitments() {
+        let mut tracker = OpeningTracker::new();
+
+        let (commitment_0, opening_0) = Pedersen::new(77_u64);
+        let (commitment_1, opening_1) = Pedersen::new(55_u64);
+        tracker.insert(&commitment_0, opening_0);
+        tracker.insert(&commitment_1, opening_1);
+
+        let sum = tracker.add(&commitment_0, &commitment_1).unwrap();
+        assert!(sum.verify_opening(132_u64, tracker.get(&sum).unwrap()));
+
+        let difference = tracker.subtract(&sum, &commitment_1).unwrap();
+        assert_eq!(difference, commitment_0);
+        assert!(difference.verify_opening(77_u64, tracker.get(&difference).unwrap()));
+
+        // operations on untracked commitments fail
+        let (untracked_commitment, _) = Pedersen::new(10_u64);
+        assert!(tracker.add(&sum, &untracked_commitment).is_none());
+        assert!(tracker.subtract(&untracked_commitment, &sum).is_none());
+
+        assert!(tracker.remove(&commitment_0).is_some());
+        assert!(tracker.get(&commitment_0).is_none());
+        assert_eq!(tracker.len(), 2);
+    }
+
+    #[test]
+    fn test_opening_tracker_ciphertexts() {
+        let keypair = ElGamalKeypair::new_rand();
+        let mut tracker = OpeningTracker::new();
+
+        let balance_opening = PedersenOpening::new_rand();
+        let balance = keypair.pubkey().encrypt_with(100_u64, &balance_opening);
+        tracker.insert(&balance.commitment, balance_opening);
+
+        let deposit_opening = PedersenOpening::new_rand();
+        let deposit = keypair.pubkey().encrypt_with(50_u64, &deposit_opening);
+        tracker.insert(&deposit.commitment, deposit_opening);
+
+        let balance = tracker.add_ciphertexts(&balance, &deposit).unwrap();
+        let balance = tracker.subtract_amount(&balance, 30).unwrap();
+        let balance = tracker.add_amount(&balance, 5).unwrap();
+        let balance = tracker.subtract_ciphertexts(&balance, &deposit).unwrap();
+
+        let balance_opening = tracker.get(&balance.commitment).unwrap();
+        assert_eq!(
+            balance,
+            keypair.pubkey().encrypt_with(75_u64, balance_opening)
+        );
+        assert_eq!(keypair.secret().decrypt_u32(&balance), Some(75));
+    }
+
+    #[test]
+    fn test_opening_tracker_bytes() {
+        let mut tracker = OpeningTracker::new();
+        for amount in [1_u64, 2, 3] {
+            let (commitment, opening) = Pedersen::new(amount);
+            tracker.insert(&commitment, opening);
+        }
+
+        let encoded = tracker.to_bytes();
+        assert_eq!(encoded.len(), 3 * OPENING_TRACKER_ENTRY_LEN);
+
+        let decoded = OpeningTracker::from_bytes(&encoded).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded.to_bytes(), encoded);
+
+        // incorrect length encoding
+        assert!(OpeningTracker::from_bytes(&encoded[1..]).is_none());
+
+        // duplicate commitments
+        let duplicated = [
+            &encoded[..OPENING_TRACKER_ENTRY_LEN],
+            &encoded[..OPENING_TRACKER_ENTRY_LEN],
+        ]
+        .concat();
+        assert!(OpeningTracker::from_bytes(&duplicated).is_none());
+
+        // non-canonical opening
+        let mut invalid = encoded.clone();
+        invalid[PEDERSEN_COMMITMENT_LEN..OPENING_TRACKER_ENTRY_LEN].fill(255);
+        assert!(OpeningTracker::from_bytes(&invalid).is_none());
+
+        assert!(OpeningTracker::from_bytes(&[]).unwrap().is_empty());
+    }
+}
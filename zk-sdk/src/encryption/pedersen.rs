@@ -1,23 +1,32 @@
 //! Pedersen commitment implementation using the Ristretto prime-order group.
 
 use {
-    crate::errors::ElGamalError,
-    core::ops::{Add, Mul, Sub},
+    crate::{
+        errors::{ElGamalError, RngError},
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+    },
+    core::{
+        iter::Sum,
+        ops::{Add, AddAssign, Mul, Sub, SubAssign},
+    },
     curve25519_dalek::{
-        constants::{RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_POINT},
-        ristretto::{CompressedRistretto, RistrettoPoint},
+        constants::{
+            RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_POINT, RISTRETTO_BASEPOINT_TABLE,
+        },
+        ristretto::{CompressedRistretto, RistrettoBasepointTable, RistrettoPoint},
         scalar::Scalar,
-        traits::MultiscalarMul,
+        traits::{IsIdentity, MultiscalarMul},
     },
-    rand::rngs::OsRng,
     serde::{Deserialize, Serialize},
-    sha3::Sha3_512,
+    sha3::{Digest, Sha3_512},
     solana_zk_sdk_pod::encryption::{
-        pedersen::PodPedersenCommitment, PEDERSEN_COMMITMENT_LEN, PEDERSEN_OPENING_LEN,
+        pedersen::{PodPedersenCommitment, PodPedersenOpening},
+        PEDERSEN_COMMITMENT_LEN, PEDERSEN_OPENING_LEN,
     },
     std::{convert::TryInto, fmt},
     subtle::{Choice, ConstantTimeEq},
-    zeroize::Zeroize,
+    zeroize::{Zeroize, ZeroizeOnDrop},
 };
 
 /// Pedersen base point for encoding messages to be committed.
@@ -26,6 +35,111 @@ pub const G: RistrettoPoint = RISTRETTO_BASEPOINT_POINT;
 pub static H: std::sync::LazyLock<RistrettoPoint> = std::sync::LazyLock::new(|| {
     RistrettoPoint::hash_from_bytes::<Sha3_512>(RISTRETTO_BASEPOINT_COMPRESSED.as_bytes())
 });
+/// A precomputed table of multiples of the Pedersen base point [`H`].
+static H_TABLE: std::sync::LazyLock<RistrettoBasepointTable> =
+    std::sync::LazyLock::new(|| RistrettoBasepointTable::create(&H));
+
+/// Domain separator for the hash-to-group derivation of custom Pedersen generators.
+const PEDERSEN_GENERATORS_DOMAIN: &[u8] = b"solana-zk-sdk-pedersen-generators";
+
+/// A pair of Pedersen base points `(G, H)` with respect to which commitments are computed.
+///
+/// The default generators are the global base points [`G`] and [`H`]. Protocols that share the
+/// same deployment can instead derive their own generators from an application label with
+/// [`PedersenGenerators::from_label`]. Commitments and proofs with respect to generators derived
+/// from distinct labels are not interchangeable, which prevents a proof generated for one
+/// protocol from being reused in another.
+#[allow(non_snake_case)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PedersenGenerators {
+    G: RistrettoPoint,
+    H: RistrettoPoint,
+}
+
+#[allow(non_snake_case)]
+impl PedersenGenerators {
+    /// Derives a pair of Pedersen generators from an application label via hash-to-group.
+    ///
+    /// The discrete log relation between the derived generators, or between the derived
+    /// generators and any other generators, is unknown.
+    pub fn from_label(label: &[u8]) -> Self {
+        let hash_to_point = |name: &[u8]| {
+            let mut hasher = Sha3_512::new();
+            hasher.update(PEDERSEN_GENERATORS_DOMAIN);
+            // the label is length-prefixed so that the hash input is unambiguous
+            hasher.update((label.len() as u64).to_le_bytes());
+            hasher.update(label);
+            hasher.update(name);
+            RistrettoPoint::from_hash(hasher)
+        };
+
+        Self {
+            G: hash_to_point(b"G"),
+            H: hash_to_point(b"H"),
+        }
+    }
+
+    /// Creates a pair of Pedersen generators from points whose discrete log relation is unknown.
+    pub(crate) fn from_points(value_base: RistrettoPoint, opening_base: RistrettoPoint) -> Self {
+        Self {
+            G: value_base,
+            H: opening_base,
+        }
+    }
+
+    /// The base point for encoding messages to be committed.
+    pub fn G(&self) -> &RistrettoPoint {
+        &self.G
+    }
+
+    /// The base point for encoding the commitment openings.
+    pub fn H(&self) -> &RistrettoPoint {
+        &self.H
+    }
+
+    /// On input a message (numeric amount), the function returns a Pedersen commitment of the
+    /// message with respect to the generators and the corresponding opening.
+    ///
+    /// This function is randomized. It internally samples a Pedersen opening using `OsRng`.
+    pub fn commit<T: Into<Scalar>>(&self, amount: T) -> (PedersenCommitment, PedersenOpening) {
+        let opening = PedersenOpening::new_rand();
+        let commitment = self.commit_with(amount, &opening);
+
+        (commitment, opening)
+    }
+
+    /// On input a message (numeric amount) and a Pedersen opening, the function returns the
+    /// corresponding Pedersen commitment with respect to the generators.
+    ///
+    /// This function is deterministic.
+    pub fn commit_with<T: Into<Scalar>>(
+        &self,
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> PedersenCommitment {
+        let x: Scalar = amount.into();
+        let r = opening.get_scalar();
+
+        PedersenCommitment(RistrettoPoint::multiscalar_mul(&[x, *r], &[self.G, self.H]))
+    }
+
+    /// Checks whether a commitment opens to a message (numeric amount) under a Pedersen opening
+    /// with respect to the generators.
+    pub fn verify_opening<T: Into<Scalar>>(
+        &self,
+        commitment: &PedersenCommitment,
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> bool {
+        self.commit_with(amount, opening) == *commitment
+    }
+}
+
+impl Default for PedersenGenerators {
+    fn default() -> Self {
+        Self { G, H: *H }
+    }
+}
 
 /// Algorithm handle for the Pedersen commitment scheme.
 pub struct Pedersen;
@@ -53,6 +167,76 @@ impl Pedersen {
         PedersenCommitment(RistrettoPoint::multiscalar_mul(&[x, *r], &[G, *H]))
     }
 
+    /// Computes the same commitment as [`Self::with`] with precomputed tables of the base points.
+    ///
+    /// The two fixed-base multiplications are faster than a multiscalar multiplication, but the
+    /// table of `H` is created on first use.
+    pub(crate) fn with_tables<T: Into<Scalar>>(
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> PedersenCommitment {
+        let x: Scalar = amount.into();
+        let r = opening.get_scalar();
+
+        PedersenCommitment(RISTRETTO_BASEPOINT_TABLE * &x + &*H_TABLE * r)
+    }
+
+    /// Checks whether a `Pod` Pedersen commitment opens to a message (numeric amount) under a
+    /// Pedersen opening.
+    ///
+    /// The commitment is compared in its compressed form, so the function does not need to
+    /// decompress the `Pod` commitment. An invalid commitment encoding is never a valid opening.
+    pub fn verify_pod_opening<T: Into<Scalar>>(
+        commitment: &PodPedersenCommitment,
+        amount: T,
+        opening: &PedersenOpening,
+    ) -> bool {
+        Pedersen::with(amount, opening).to_bytes() == commitment.0
+    }
+
+    /// Checks whether each commitment in a list opens to its amount under its opening.
+    ///
+    /// The openings are checked with a single multiscalar multiplication. The equation of each
+    /// opening is scaled by a random weight, so the batch is accepted only if every opening is
+    /// valid, except with negligible probability. An empty batch is valid.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the weights.
+    pub fn verify_openings_batch<T: Into<Scalar> + Copy>(
+        openings: &[(T, PedersenOpening, PedersenCommitment)],
+    ) -> bool {
+        unwrap_default_rng(Self::verify_openings_batch_with_rng(
+            openings,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Checks whether each commitment in a list opens to its amount under its opening, using
+    /// `rng` to generate the weights.
+    ///
+    /// See [`Self::verify_openings_batch`] for details.
+    pub fn verify_openings_batch_with_rng<T: Into<Scalar> + Copy>(
+        openings: &[(T, PedersenOpening, PedersenCommitment)],
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<bool, RngError> {
+        // check `sum_i w_i * (x_i * G + r_i * H - C_i) = 0`
+        let mut x_sum = Scalar::ZERO;
+        let mut r_sum = Scalar::ZERO;
+        let mut scalars = Vec::with_capacity(openings.len() + 2);
+        let mut points = Vec::with_capacity(openings.len() + 2);
+        for (amount, opening, commitment) in openings {
+            let w = try_random_scalar(rng)?;
+            let x: Scalar = (*amount).into();
+            x_sum += w * x;
+            r_sum += w * opening.get_scalar();
+            scalars.push(-w);
+            points.push(commitment.0);
+        }
+        scalars.extend([x_sum, r_sum]);
+        points.extend([G, *H]);
+
+        Ok(vartime_multiscalar_mul(scalars, points).is_identity())
+    }
+
     /// On input a message (numeric amount), the function returns a Pedersen commitment with zero
     /// as the opening.
     ///
@@ -74,14 +258,21 @@ impl Pedersen {
 
 /// Pedersen opening type.
 ///
-/// Instances of Pedersen openings are zeroized on drop.
-#[derive(Clone, Default, Zeroize)]
-#[zeroize(drop)]
+/// Instances of Pedersen openings are zeroized on drop and are redacted from the `Debug` output.
+#[derive(Clone, Default, Zeroize, ZeroizeOnDrop)]
 pub struct PedersenOpening(Scalar);
 
 impl PedersenOpening {
+    /// Byte length of a Pedersen opening.
+    pub const LEN: usize = PEDERSEN_OPENING_LEN;
+
     pub fn new_rand() -> Self {
-        PedersenOpening(Scalar::random(&mut OsRng))
+        unwrap_default_rng(Self::new_rand_with_rng(&mut DefaultRng))
+    }
+
+    /// Samples a random Pedersen opening using `rng`, returning an error if `rng` fails.
+    pub fn new_rand_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, RngError> {
+        try_random_scalar(rng).map(PedersenOpening)
     }
 }
 
@@ -131,6 +322,26 @@ impl fmt::Debug for PedersenOpening {
     }
 }
 
+impl From<&PedersenOpening> for PodPedersenOpening {
+    fn from(decoded_opening: &PedersenOpening) -> Self {
+        Self(decoded_opening.to_bytes())
+    }
+}
+
+impl From<PedersenOpening> for PodPedersenOpening {
+    fn from(decoded_opening: PedersenOpening) -> Self {
+        (&decoded_opening).into()
+    }
+}
+
+impl TryFrom<PodPedersenOpening> for PedersenOpening {
+    type Error = ElGamalError;
+
+    fn try_from(pod_opening: PodPedersenOpening) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_opening.0).ok_or(ElGamalError::OpeningDeserialization)
+    }
+}
+
 impl<'b> Add<&'b PedersenOpening> for &PedersenOpening {
     type Output = PedersenOpening;
 
@@ -211,6 +422,9 @@ define_mul_variants!(LHS = u64, RHS = PedersenOpening, Output = PedersenOpening)
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PedersenCommitment(RistrettoPoint);
 impl PedersenCommitment {
+    /// Byte length of a Pedersen commitment.
+    pub const LEN: usize = PEDERSEN_COMMITMENT_LEN;
+
     pub fn new(point: RistrettoPoint) -> Self {
         Self(point)
     }
@@ -219,6 +433,12 @@ impl PedersenCommitment {
         &self.0
     }
 
+    /// Checks whether the commitment opens to a message (numeric amount) under a Pedersen
+    /// opening, i.e. whether the commitment is equal to `amount*G + opening*H`.
+    pub fn verify_opening<T: Into<Scalar>>(&self, amount: T, opening: &PedersenOpening) -> bool {
+        Pedersen::with(amount, opening) == *self
+    }
+
     pub fn to_bytes(&self) -> [u8; PEDERSEN_COMMITMENT_LEN] {
         self.0.compress().to_bytes()
     }
@@ -278,6 +498,24 @@ define_sub_variants!(
     Output = PedersenCommitment
 );
 
+impl<'b> AddAssign<&'b PedersenCommitment> for PedersenCommitment {
+    fn add_assign(&mut self, commitment: &'b PedersenCommitment) {
+        self.0 += &commitment.0;
+    }
+}
+
+define_add_assign_variants!(LHS = PedersenCommitment, RHS = PedersenCommitment);
+
+impl<'b> SubAssign<&'b PedersenCommitment> for PedersenCommitment {
+    fn sub_assign(&mut self, commitment: &'b PedersenCommitment) {
+        self.0 -= &commitment.0;
+    }
+}
+
+define_sub_assign_variants!(LHS = PedersenCommitment, RHS = PedersenCommitment);
+
+define_sum_variants!(Type = PedersenCommitment);
+
 impl<'b> Mul<&'b Scalar> for &PedersenCommitment {
     type Output = PedersenCommitment;
 
@@ -336,7 +574,49 @@ define_mul_variants!(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, rand::rngs::OsRng};
+
+    #[test]
+    fn test_verify_openings_batch() {
+        let mut openings: Vec<_> = (0..20_u64)
+            .map(|amount| {
+                let (commitment, opening) = Pedersen::new(amount);
+                (amount, opening, commitment)
+            })
+            .collect();
+        assert!(Pedersen::verify_openings_batch(&openings));
+        assert!(Pedersen::verify_openings_batch::<u64>(&[]));
+
+        // a single wrong amount invalidates the batch
+        openings[7].0 += 1;
+        assert!(!Pedersen::verify_openings_batch(&openings));
+        openings[7].0 -= 1;
+
+        // so does a single wrong opening
+        openings[3].1 = PedersenOpening::new_rand();
+        assert!(!Pedersen::verify_openings_batch(&openings));
+    }
+
+    #[test]
+    fn test_pedersen_commitment_assign_and_sum() {
+        let openings: Vec<_> = (0..5).map(|_| PedersenOpening::new_rand()).collect();
+        let commitments: Vec<_> = openings
+            .iter()
+            .zip(1_u64..)
+            .map(|(opening, amount)| Pedersen::with(amount, opening))
+            .collect();
+        let opening_sum = openings[1..]
+            .iter()
+            .fold(openings[0].clone(), |acc, opening| &acc + opening);
+
+        let expected = Pedersen::with(15_u64, &opening_sum);
+        assert_eq!(commitments.iter().sum::<PedersenCommitment>(), expected);
+
+        let mut total = commitments.iter().copied().sum::<PedersenCommitment>();
+        total -= commitments[0];
+        total += commitments[0];
+        assert_eq!(total, expected);
+    }
 
     #[test]
     fn test_pedersen_homomorphic_addition() {
@@ -383,6 +663,92 @@ mod tests {
         assert_eq!(commitment_multiplication, scalar * commitment);
     }
 
+    #[test]
+    fn test_pedersen_opening_arithmetic() {
+        // track the opening of a balance that is updated homomorphically
+        let (mut balance_commitment, mut balance_opening) = Pedersen::new(100_u64);
+
+        let (deposit_commitment, deposit_opening) = Pedersen::new(50_u64);
+        balance_commitment += deposit_commitment;
+        balance_opening = &balance_opening + &deposit_opening;
+        assert!(balance_commitment.verify_opening(150_u64, &balance_opening));
+
+        let (withdraw_commitment, withdraw_opening) = Pedersen::new(30_u64);
+        balance_commitment -= withdraw_commitment;
+        balance_opening = &balance_opening - &withdraw_opening;
+        assert!(balance_commitment.verify_opening(120_u64, &balance_opening));
+
+        balance_commitment = balance_commitment * 2_u64;
+        balance_opening = &balance_opening * 2_u64;
+        assert!(balance_commitment.verify_opening(240_u64, &balance_opening));
+
+        let scalar = Scalar::from(3_u64);
+        assert!((balance_commitment * scalar).verify_opening(720_u64, &(scalar * &balance_opening)));
+    }
+
+    #[test]
+    fn test_pedersen_verify_opening() {
+        let amount: u64 = 77;
+        let (commitment, opening) = Pedersen::new(amount);
+
+        assert!(commitment.verify_opening(amount, &opening));
+        assert!(!commitment.verify_opening(amount + 1, &opening));
+        assert!(!commitment.verify_opening(amount, &PedersenOpening::new_rand()));
+
+        let pod_commitment: PodPedersenCommitment = commitment.into();
+        assert!(Pedersen::verify_pod_opening(
+            &pod_commitment,
+            amount,
+            &opening
+        ));
+        assert!(!Pedersen::verify_pod_opening(
+            &pod_commitment,
+            amount + 1,
+            &opening
+        ));
+
+        let invalid_pod_commitment = PodPedersenCommitment([255; PEDERSEN_COMMITMENT_LEN]);
+        assert!(!Pedersen::verify_pod_opening(
+            &invalid_pod_commitment,
+            amount,
+            &opening
+        ));
+    }
+
+    #[test]
+    fn test_pedersen_generators() {
+        let amount: u64 = 77;
+        let opening = PedersenOpening::new_rand();
+
+        // the default generators are the global generators
+        let default_generators = PedersenGenerators::default();
+        assert_eq!(
+            default_generators.commit_with(amount, &opening),
+            Pedersen::with(amount, &opening)
+        );
+
+        // generators derived from a label are deterministic and distinct across labels
+        let generators = PedersenGenerators::from_label(b"protocol-a");
+        assert_eq!(generators, PedersenGenerators::from_label(b"protocol-a"));
+        assert_ne!(generators, PedersenGenerators::from_label(b"protocol-b"));
+        assert_ne!(generators, default_generators);
+        assert_ne!(generators.G(), generators.H());
+
+        let (commitment, opening) = generators.commit(amount);
+        assert!(generators.verify_opening(&commitment, amount, &opening));
+        assert!(!generators.verify_opening(&commitment, amount + 1, &opening));
+        assert!(!default_generators.verify_opening(&commitment, amount, &opening));
+        assert!(!commitment.verify_opening(amount, &opening));
+
+        // commitments with respect to the same generators remain additively homomorphic
+        let (other_commitment, other_opening) = generators.commit(23_u64);
+        assert!(generators.verify_opening(
+            &(commitment + other_commitment),
+            100_u64,
+            &(opening + other_opening)
+        ));
+    }
+
     #[test]
     fn test_pedersen_commitment_bytes() {
         let amount: u64 = 77;
@@ -410,6 +776,22 @@ mod tests {
         assert_eq!(PedersenOpening::from_bytes(&[0; 33]), None);
     }
 
+    #[test]
+    fn test_pod_pedersen_opening_conversion() {
+        let opening = PedersenOpening::new_rand();
+
+        let pod_opening: PodPedersenOpening = (&opening).into();
+        let decoded: PedersenOpening = pod_opening.try_into().unwrap();
+        assert_eq!(opening, decoded);
+
+        // non-canonical scalar encoding
+        let invalid_pod_opening = PodPedersenOpening([255; PEDERSEN_OPENING_LEN]);
+        assert_eq!(
+            PedersenOpening::try_from(invalid_pod_opening).unwrap_err(),
+            ElGamalError::OpeningDeserialization
+        );
+    }
+
     #[test]
     fn test_serde_pedersen_commitment() {
         let amount: u64 = 77;
@@ -476,4 +858,13 @@ mod tests {
 
         assert_eq!(PedersenCommitment::from_bytes(&invalid_bytes), None);
     }
+
+    #[test]
+    fn test_opening_debug_is_redacted() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<PedersenOpening>();
+
+        let opening = PedersenOpening::new_rand();
+        assert_eq!(format!("{opening:?}"), "PedersenOpening(\"[REDACTED]\")");
+    }
 }
@@ -0,0 +1,123 @@
+//! PKCS#8 and SubjectPublicKeyInfo (SPKI) DER encodings of ElGamal keys.
+//!
+//! Secret keys are encoded as PKCS#8 `PrivateKeyInfo` documents and public keys as SPKI documents
+//! so that they can be stored in standard key management tooling. Both use the algorithm
+//! identifier [`ELGAMAL_ALGORITHM_ID`], which carries no parameters.
+//!
+//! Following the convention of RFC 8410 for Ed25519 keys, the `privateKey` field of a PKCS#8
+//! document holds the 32-byte secret scalar wrapped in a DER `OCTET STRING`, and the
+//! `subjectPublicKey` field of an SPKI document holds the 32-byte compressed public key.
+
+pub use ::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey};
+use {
+    crate::encryption::elgamal::{ElGamalPubkey, ElGamalSecretKey},
+    ::pkcs8::{
+        der::{
+            asn1::{BitStringRef, OctetStringRef},
+            Decode, Document, SecretDocument,
+        },
+        spki::{self, AlgorithmIdentifierRef, SubjectPublicKeyInfoRef},
+        ObjectIdentifier, PrivateKeyInfo,
+    },
+    solana_zk_sdk_pod::encryption::ELGAMAL_SECRET_KEY_LEN,
+    zeroize::Zeroizing,
+};
+
+/// The object identifier for twisted ElGamal keys over Ristretto.
+///
+/// The identifier lives in a private arc and is not registered with any authority.
+pub const ELGAMAL_ALGORITHM_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.99999.1.1");
+
+/// The algorithm identifier for twisted ElGamal keys over Ristretto.
+pub const ELGAMAL_ALGORITHM_ID: AlgorithmIdentifierRef<'static> = AlgorithmIdentifierRef {
+    oid: ELGAMAL_ALGORITHM_OID,
+    parameters: None,
+};
+
+/// DER tag of an `OCTET STRING`.
+const OCTET_STRING_TAG: u8 = 0x04;
+
+/// Byte length of the DER encoding of the secret scalar as an `OCTET STRING`.
+const ENCODED_SECRET_KEY_LEN: usize = ELGAMAL_SECRET_KEY_LEN + 2;
+
+fn check_algorithm(algorithm: &AlgorithmIdentifierRef<'_>) -> spki::Result<()> {
+    algorithm.assert_algorithm_oid(ELGAMAL_ALGORITHM_OID)?;
+    if algorithm.parameters.is_some() {
+        return Err(spki::Error::KeyMalformed);
+    }
+    Ok(())
+}
+
+impl EncodePrivateKey for ElGamalSecretKey {
+    fn to_pkcs8_der(&self) -> ::pkcs8::Result<SecretDocument> {
+        // encode the `OCTET STRING` by hand so that no unzeroized copy of the scalar is made
+        let mut private_key = Zeroizing::new([0u8; ENCODED_SECRET_KEY_LEN]);
+        private_key[0] = OCTET_STRING_TAG;
+        private_key[1] = ELGAMAL_SECRET_KEY_LEN as u8;
+        private_key[2..].copy_from_slice(self.as_bytes());
+
+        PrivateKeyInfo::new(ELGAMAL_ALGORITHM_ID, private_key.as_slice()).try_into()
+    }
+}
+
+impl TryFrom<PrivateKeyInfo<'_>> for ElGamalSecretKey {
+    type Error = ::pkcs8::Error;
+
+    fn try_from(private_key_info: PrivateKeyInfo<'_>) -> ::pkcs8::Result<Self> {
+        check_algorithm(&private_key_info.algorithm)?;
+        let private_key = OctetStringRef::from_der(private_key_info.private_key)?;
+        ElGamalSecretKey::try_from(private_key.as_bytes()).map_err(|_| ::pkcs8::Error::KeyMalformed)
+    }
+}
+
+impl EncodePublicKey for ElGamalPubkey {
+    fn to_public_key_der(&self) -> spki::Result<Document> {
+        let public_key = self.to_bytes();
+        SubjectPublicKeyInfoRef {
+            algorithm: ELGAMAL_ALGORITHM_ID,
+            subject_public_key: BitStringRef::from_bytes(&public_key)?,
+        }
+        .try_into()
+    }
+}
+
+impl TryFrom<SubjectPublicKeyInfoRef<'_>> for ElGamalPubkey {
+    type Error = spki::Error;
+
+    fn try_from(spki: SubjectPublicKeyInfoRef<'_>) -> spki::Result<Self> {
+        check_algorithm(&spki.algorithm)?;
+        let public_key = spki
+            .subject_public_key
+            .as_bytes()
+            .ok_or(spki::Error::KeyMalformed)?;
+        ElGamalPubkey::try_from(public_key).map_err(|_| spki::Error::KeyMalformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_pkcs8_round_trip() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let secret_der = keypair.secret().to_pkcs8_der().unwrap();
+        let secret = ElGamalSecretKey::from_pkcs8_der(secret_der.as_bytes()).unwrap();
+        assert_eq!(&secret, keypair.secret());
+
+        let pubkey_der = keypair.pubkey().to_public_key_der().unwrap();
+        let pubkey = ElGamalPubkey::from_public_key_der(pubkey_der.as_bytes()).unwrap();
+        assert_eq!(&pubkey, keypair.pubkey());
+
+        // a document for a different algorithm is rejected
+        let mut other_der = pubkey_der.as_bytes().to_vec();
+        let oid_position = other_der
+            .windows(ELGAMAL_ALGORITHM_OID.as_bytes().len())
+            .position(|window| window == ELGAMAL_ALGORITHM_OID.as_bytes())
+            .unwrap();
+        other_der[oid_position + ELGAMAL_ALGORITHM_OID.as_bytes().len() - 1] ^= 1;
+        assert!(ElGamalPubkey::from_public_key_der(&other_der).is_err());
+    }
+}
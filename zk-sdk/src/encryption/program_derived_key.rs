@@ -0,0 +1,161 @@
+//! Program-derived ElGamal public keys.
+//!
+//! A program-derived address (PDA) has no secret key, so a protocol-owned vault at a PDA cannot
+//! hold a confidential balance under a key of its own. Instead, an off-chain authority can
+//! delegate an ElGamal public key to every PDA of a program. The public key of a PDA is derived
+//! from the public key of the authority, the program id, and the seeds of the PDA, so that anyone
+//! can compute it without interacting with the authority. Only the authority can derive the
+//! corresponding secret key and decrypt the balance of the vault.
+//!
+//! For an authority keypair `(s, P)`, a scalar tweak `t` is derived by hashing `P`, the program
+//! id, and the PDA seeds, and
+//!
+//! ```text
+//! program-derived public key = t * P = (s * t^-1)^-1 * H
+//! program-derived secret key = s * t^-1
+//! ```
+//!
+//! Since the tweak is public, a program-derived secret key reveals the secret key of the
+//! authority. Program-derived secret keys must therefore be kept as secret as the authority key.
+
+use {
+    crate::{
+        encryption::elgamal::{ElGamalKeypair, ElGamalPubkey, ElGamalSecretKey},
+        errors::ElGamalError,
+    },
+    curve25519_dalek::scalar::Scalar,
+    sha2::{Digest, Sha512},
+    solana_address::Address,
+};
+
+/// Domain separator for the program-derived public key tweak.
+pub const PROGRAM_DERIVED_KEY_DOMAIN: &[u8] = b"solana-program-derived-elgamal/v1";
+
+/// The maximum number of seeds of a program-derived address.
+pub const MAX_SEEDS: usize = 16;
+
+/// The maximum byte length of a seed of a program-derived address.
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Derives the ElGamal public key of the program-derived address with `seeds` under `program_id`
+/// from the public key of the decryption authority.
+pub fn derive_program_pubkey(
+    authority_pubkey: &ElGamalPubkey,
+    program_id: &Address,
+    seeds: &[&[u8]],
+) -> Result<ElGamalPubkey, ElGamalError> {
+    authority_pubkey.validate()?;
+    let tweak = program_derived_key_tweak(authority_pubkey, program_id, seeds)?;
+    Ok(ElGamalPubkey::from_point(
+        tweak * authority_pubkey.get_point(),
+    ))
+}
+
+/// Derives the ElGamal keypair of the program-derived address with `seeds` under `program_id`
+/// from the secret key of the decryption authority.
+pub fn derive_program_keypair(
+    authority_secret: &ElGamalSecretKey,
+    program_id: &Address,
+    seeds: &[&[u8]],
+) -> Result<ElGamalKeypair, ElGamalError> {
+    let authority_pubkey = ElGamalPubkey::new(authority_secret);
+    let tweak = program_derived_key_tweak(&authority_pubkey, program_id, seeds)?;
+
+    let program_secret = authority_secret.get_scalar() * tweak.invert();
+    Ok(ElGamalKeypair::new(ElGamalSecretKey::from(program_secret)))
+}
+
+/// Derives the scalar tweak of a program-derived public key.
+///
+/// Each seed is prefixed with its length so that distinct seed sequences with the same
+/// concatenation derive distinct tweaks.
+fn program_derived_key_tweak(
+    authority_pubkey: &ElGamalPubkey,
+    program_id: &Address,
+    seeds: &[&[u8]],
+) -> Result<Scalar, ElGamalError> {
+    if seeds.len() > MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(ElGamalError::InvalidSeeds);
+    }
+
+    let mut hasher = Sha512::new();
+    hasher.update(PROGRAM_DERIVED_KEY_DOMAIN);
+    hasher.update(authority_pubkey.get_point().compress().as_bytes());
+    hasher.update(program_id.as_ref());
+    for seed in seeds {
+        hasher.update([seed.len() as u8]);
+        hasher.update(seed);
+    }
+    let mut wide = [0u8; 64];
+    wide.copy_from_slice(&hasher.finalize());
+
+    let tweak = Scalar::from_bytes_mod_order_wide(&wide);
+    if tweak == Scalar::ZERO {
+        return Err(ElGamalError::InvalidPubkey);
+    }
+    Ok(tweak)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program_derived_keypair() {
+        let authority = ElGamalKeypair::new_rand();
+        let program_id = Address::new_unique();
+        let seeds: &[&[u8]] = &[b"vault", &[1, 2, 3]];
+
+        let pubkey = derive_program_pubkey(authority.pubkey(), &program_id, seeds).unwrap();
+        assert_ne!(pubkey, *authority.pubkey());
+
+        // the authority derives the keypair that decrypts ciphertexts under the public key
+        let keypair = derive_program_keypair(authority.secret(), &program_id, seeds).unwrap();
+        assert_eq!(*keypair.pubkey(), pubkey);
+        let ciphertext = pubkey.encrypt(55_u64);
+        assert_eq!(keypair.secret().decrypt_u32(&ciphertext), Some(55));
+
+        // another authority does not derive the keypair
+        let other = ElGamalKeypair::new_rand();
+        let other_keypair = derive_program_keypair(other.secret(), &program_id, seeds).unwrap();
+        assert_ne!(*other_keypair.pubkey(), pubkey);
+    }
+
+    #[test]
+    fn test_program_derived_pubkeys_are_distinct() {
+        let authority = ElGamalKeypair::new_rand();
+        let program_id = Address::new_unique();
+
+        let derive = |program_id: &Address, seeds: &[&[u8]]| {
+            derive_program_pubkey(authority.pubkey(), program_id, seeds).unwrap()
+        };
+        let pubkey = derive(&program_id, &[b"vault"]);
+        assert_ne!(pubkey, derive(&Address::new_unique(), &[b"vault"]));
+        assert_ne!(pubkey, derive(&program_id, &[b"vault2"]));
+        assert_ne!(
+            derive(&program_id, &[b"ab", b"c"]),
+            derive(&program_id, &[b"a", b"bc"])
+        );
+    }
+
+    #[test]
+    fn test_program_derived_pubkey_rejects_invalid_input() {
+        let authority = ElGamalKeypair::new_rand();
+        let program_id = Address::new_unique();
+
+        let long_seed = [0u8; MAX_SEED_LEN + 1];
+        assert_eq!(
+            derive_program_pubkey(authority.pubkey(), &program_id, &[&long_seed]),
+            Err(ElGamalError::InvalidSeeds)
+        );
+        let seeds = [&[0u8][..]; MAX_SEEDS + 1];
+        assert_eq!(
+            derive_program_pubkey(authority.pubkey(), &program_id, &seeds),
+            Err(ElGamalError::InvalidSeeds)
+        );
+        assert_eq!(
+            derive_program_pubkey(&ElGamalPubkey::default(), &program_id, &[b"vault"]),
+            Err(ElGamalError::InvalidPubkey)
+        );
+    }
+}
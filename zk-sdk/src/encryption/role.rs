@@ -0,0 +1,104 @@
+//! ElGamal keys that are tagged with the role of their owner in a transfer.
+//!
+//! The proof constructors of the ZK ElGamal proof program identify public keys by position
+//! (`first_pubkey`, `second_pubkey`, `third_pubkey`), so passing the auditor public key where the
+//! receiver public key is expected goes unnoticed until the proof is rejected on chain. The
+//! transfer-specific constructors instead take keys that are tagged with a [`KeyRole`], so swapping
+//! the keys of two roles fails to compile.
+//!
+//! ```compile_fail
+//! use solana_zk_sdk::encryption::{
+//!     elgamal::ElGamalKeypair,
+//!     role::{AuditorElGamalPubkey, ReceiverElGamalPubkey},
+//! };
+//!
+//! let auditor_pubkey = AuditorElGamalPubkey::new(*ElGamalKeypair::new_rand().pubkey());
+//! let receiver_pubkey: ReceiverElGamalPubkey = auditor_pubkey;
+//! ```
+
+use {
+    crate::encryption::elgamal::{ElGamalKeypair, ElGamalPubkey},
+    std::marker::PhantomData,
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The role of the owner of an ElGamal key in a transfer.
+pub trait KeyRole: sealed::Sealed {}
+
+/// The role of the sender of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sender {}
+
+/// The role of the receiver of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Receiver {}
+
+/// The role of the auditor of a transfer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Auditor {}
+
+impl sealed::Sealed for Sender {}
+impl sealed::Sealed for Receiver {}
+impl sealed::Sealed for Auditor {}
+impl KeyRole for Sender {}
+impl KeyRole for Receiver {}
+impl KeyRole for Auditor {}
+
+/// An ElGamal public key that is tagged with the role `R` of its owner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoleElGamalPubkey<R: KeyRole> {
+    pubkey: ElGamalPubkey,
+    role: PhantomData<R>,
+}
+
+impl<R: KeyRole> RoleElGamalPubkey<R> {
+    /// Tags `pubkey` with the role `R`.
+    pub fn new(pubkey: ElGamalPubkey) -> Self {
+        Self {
+            pubkey,
+            role: PhantomData,
+        }
+    }
+
+    /// Returns the untagged public key.
+    pub fn pubkey(&self) -> &ElGamalPubkey {
+        &self.pubkey
+    }
+}
+
+/// An ElGamal keypair that is tagged with the role `R` of its owner.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RoleElGamalKeypair<R: KeyRole> {
+    keypair: ElGamalKeypair,
+    role: PhantomData<R>,
+}
+
+impl<R: KeyRole> RoleElGamalKeypair<R> {
+    /// Tags `keypair` with the role `R`.
+    pub fn new(keypair: ElGamalKeypair) -> Self {
+        Self {
+            keypair,
+            role: PhantomData,
+        }
+    }
+
+    /// Returns the untagged keypair.
+    pub fn keypair(&self) -> &ElGamalKeypair {
+        &self.keypair
+    }
+
+    /// Returns the public key of the keypair, tagged with the same role.
+    pub fn pubkey(&self) -> RoleElGamalPubkey<R> {
+        RoleElGamalPubkey::new(*self.keypair.pubkey())
+    }
+}
+
+pub type SenderElGamalPubkey = RoleElGamalPubkey<Sender>;
+pub type ReceiverElGamalPubkey = RoleElGamalPubkey<Receiver>;
+pub type AuditorElGamalPubkey = RoleElGamalPubkey<Auditor>;
+pub type SenderElGamalKeypair = RoleElGamalKeypair<Sender>;
+pub type ReceiverElGamalKeypair = RoleElGamalKeypair<Receiver>;
+pub type AuditorElGamalKeypair = RoleElGamalKeypair<Auditor>;
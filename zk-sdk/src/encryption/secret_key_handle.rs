@@ -0,0 +1,165 @@
+//! Opaque handles to ElGamal secret keys held by an external key store.
+//!
+//! A [`SecretKeyHandle`] refers to an ElGamal secret key `s` that never materializes in this
+//! crate. Every operation that needs `s` is delegated to a [`SecretKeyBackend`], which can be
+//! implemented on top of a PKCS#11 token, a cloud KMS, or any other store that can perform
+//! scalar multiplications with a resident key.
+//!
+//! A backend only needs to support two primitives:
+//! - multiplying a point by `s`, which is enough to strip the decrypt handle from a ciphertext;
+//! - answering a single sigma protocol round, where the backend samples the nonce `y`, commits to
+//!   it on a list of bases, and returns the masked response `c * s + y` for the challenge `c`.
+//!
+//! The zero-ciphertext and ciphertext-commitment equality proofs can be generated from a handle.
+//! The remaining constructions that take the secret key cannot be generated from a handle:
+//! - the pubkey validity proof and ElGamal signatures, which require `s^{-1}`;
+//! - the ciphertext-ciphertext equality proof, which uses `s` linearly but has no handle-based
+//!   constructor yet;
+//! - the compressed ciphertext-commitment equality proof, which folds the secret keys of all
+//!   statements into a single argument instead of answering one sigma protocol round per key.
+
+use {
+    crate::{
+        encryption::{
+            discrete_log::DiscreteLog,
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+        },
+        errors::SecretKeyBackendError,
+        rng::{try_random_scalar, DefaultRng},
+    },
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar},
+    zeroize::Zeroize,
+};
+
+/// An external store that performs operations with an ElGamal secret key `s` on behalf of the
+/// caller without revealing it.
+pub trait SecretKeyBackend {
+    /// The ElGamal public key `s^{-1} * H` that corresponds to the resident secret key.
+    fn pubkey(&self) -> ElGamalPubkey;
+
+    /// Returns `s * point`.
+    fn mul_point(&self, point: &RistrettoPoint) -> Result<RistrettoPoint, SecretKeyBackendError>;
+
+    /// Answers one sigma protocol round for the secret key.
+    ///
+    /// The backend must sample a fresh nonce `y`, call `challenge` exactly once with the points
+    /// `y * base` for each of the `bases` (in order), and return `c * s + y` where `c` is the
+    /// scalar returned by `challenge`. The nonce must be erased before returning.
+    fn sigma_response(
+        &self,
+        bases: &[RistrettoPoint],
+        challenge: &mut dyn FnMut(&[RistrettoPoint]) -> Scalar,
+    ) -> Result<Scalar, SecretKeyBackendError>;
+}
+
+/// An in-memory backend that keeps the secret key in process memory.
+///
+/// This is mainly useful for testing code that is written against [`SecretKeyHandle`].
+impl SecretKeyBackend for ElGamalKeypair {
+    fn pubkey(&self) -> ElGamalPubkey {
+        *ElGamalKeypair::pubkey(self)
+    }
+
+    fn mul_point(&self, point: &RistrettoPoint) -> Result<RistrettoPoint, SecretKeyBackendError> {
+        Ok(self.secret().get_scalar() * point)
+    }
+
+    fn sigma_response(
+        &self,
+        bases: &[RistrettoPoint],
+        challenge: &mut dyn FnMut(&[RistrettoPoint]) -> Scalar,
+    ) -> Result<Scalar, SecretKeyBackendError> {
+        let mut y = try_random_scalar(&mut DefaultRng)?;
+        let commitments = bases.iter().map(|base| y * base).collect::<Vec<_>>();
+        let c = challenge(&commitments);
+        let z = c * self.secret().get_scalar() + y;
+        y.zeroize();
+        Ok(z)
+    }
+}
+
+/// A handle to an ElGamal secret key that is held by a [`SecretKeyBackend`].
+pub struct SecretKeyHandle<B: SecretKeyBackend> {
+    backend: B,
+    pubkey: ElGamalPubkey,
+}
+
+impl<B: SecretKeyBackend> SecretKeyHandle<B> {
+    /// Creates a handle for the secret key resident in `backend`.
+    pub fn new(backend: B) -> Self {
+        let pubkey = backend.pubkey();
+        Self { backend, pubkey }
+    }
+
+    /// The ElGamal public key associated with the handle.
+    pub fn pubkey(&self) -> &ElGamalPubkey {
+        &self.pubkey
+    }
+
+    /// The backend that holds the secret key.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Decrypts a ciphertext, delegating the decrypt handle computation to the backend.
+    pub fn decrypt(
+        &self,
+        ciphertext: &ElGamalCiphertext,
+    ) -> Result<DiscreteLog, SecretKeyBackendError> {
+        let stripped_handle = self.backend.mul_point(ciphertext.handle.get_point())?;
+        Ok(DiscreteLog::new_for_g(
+            ciphertext.commitment.get_point() - stripped_handle,
+        ))
+    }
+
+    /// Decrypts a ciphertext interpreting the message as type `u32`.
+    ///
+    /// NOTE: This function is not constant time.
+    pub fn decrypt_u32(
+        &self,
+        ciphertext: &ElGamalCiphertext,
+    ) -> Result<Option<u64>, SecretKeyBackendError> {
+        Ok(self.decrypt(ciphertext)?.decode_u32())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UnavailableBackend(ElGamalPubkey);
+
+    impl SecretKeyBackend for UnavailableBackend {
+        fn pubkey(&self) -> ElGamalPubkey {
+            self.0
+        }
+
+        fn mul_point(&self, _: &RistrettoPoint) -> Result<RistrettoPoint, SecretKeyBackendError> {
+            Err(SecretKeyBackendError::Backend("unavailable".to_string()))
+        }
+
+        fn sigma_response(
+            &self,
+            _: &[RistrettoPoint],
+            _: &mut dyn FnMut(&[RistrettoPoint]) -> Scalar,
+        ) -> Result<Scalar, SecretKeyBackendError> {
+            Err(SecretKeyBackendError::Backend("unavailable".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_secret_key_handle_decrypt() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(57_u64);
+
+        let handle = SecretKeyHandle::new(keypair.clone());
+        assert_eq!(handle.pubkey(), keypair.pubkey());
+        assert_eq!(handle.decrypt_u32(&ciphertext).unwrap(), Some(57));
+
+        let handle = SecretKeyHandle::new(UnavailableBackend(*keypair.pubkey()));
+        assert_eq!(
+            handle.decrypt_u32(&ciphertext),
+            Err(SecretKeyBackendError::Backend("unavailable".to_string()))
+        );
+    }
+}
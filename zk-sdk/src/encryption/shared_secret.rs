@@ -0,0 +1,156 @@
+//! Diffie-Hellman key agreement over ElGamal keys.
+//!
+//! An ElGamal public key is of the form `P = s^-1 * H` for a secret key `s`. For two keypairs
+//! `(s_a, P_a)` and `(s_b, P_b)`, the point
+//!
+//! ```text
+//! s_a^-1 * P_b = s_a^-1 * s_b^-1 * H = s_b^-1 * P_a
+//! ```
+//!
+//! can be computed by either party and serves as the Diffie-Hellman shared point. The shared
+//! point is passed through HKDF-SHA512 (RFC 5869) before it is used, so applications can derive
+//! symmetric keys (e.g. to encrypt a transfer memo to the recipient) without exporting raw
+//! scalars.
+//!
+//! ```text
+//! prk           = HKDF-SHA512-Extract(salt = SHARED_SECRET_HKDF_SALT, ikm = compressed point)
+//! shared_secret = HKDF-Expand(prk, info = SHARED_SECRET_HKDF_INFO, L = 32)
+//! ```
+
+use {
+    crate::{
+        encryption::{
+            auth_encryption::AeKey,
+            elgamal::{ElGamalPubkey, ElGamalSecretKey},
+        },
+        errors::ElGamalError,
+    },
+    curve25519_dalek::traits::IsIdentity,
+    hkdf::Hkdf,
+    sha2::Sha512,
+    solana_zk_sdk_pod::encryption::AE_KEY_LEN,
+    std::fmt,
+    subtle::{Choice, ConstantTimeEq},
+    zeroize::{Zeroize, Zeroizing},
+};
+
+/// HKDF salt for the Diffie-Hellman shared secret derivation.
+pub const SHARED_SECRET_HKDF_SALT: &[u8] = b"solana-zk-sdk-ecdh/v1";
+
+/// HKDF info string for the Diffie-Hellman shared secret.
+pub const SHARED_SECRET_HKDF_INFO: &[u8] = b"shared-secret";
+
+/// Byte length of a Diffie-Hellman shared secret.
+pub const SHARED_SECRET_LEN: usize = 32;
+
+/// A Diffie-Hellman shared secret between two ElGamal keypairs.
+///
+/// Instances of shared secrets are zeroized on drop.
+#[derive(Clone, Zeroize)]
+#[zeroize(drop)]
+pub struct SharedSecret([u8; SHARED_SECRET_LEN]);
+
+impl SharedSecret {
+    /// Computes the shared secret between an ElGamal secret key and the ElGamal public key of the
+    /// other party.
+    ///
+    /// The identity public key, or a zero secret key, would result in a shared secret that is
+    /// known to everyone and is therefore rejected.
+    pub fn new(secret: &ElGamalSecretKey, pubkey: &ElGamalPubkey) -> Result<Self, ElGamalError> {
+        let shared_point = secret.get_scalar().invert() * pubkey.get_point();
+        if shared_point.is_identity() {
+            return Err(ElGamalError::InvalidPubkey);
+        }
+
+        let hkdf = Hkdf::<Sha512>::new(
+            Some(SHARED_SECRET_HKDF_SALT),
+            shared_point.compress().as_bytes(),
+        );
+        let mut shared_secret = [0u8; SHARED_SECRET_LEN];
+        hkdf.expand(SHARED_SECRET_HKDF_INFO, &mut shared_secret)
+            .map_err(|_| ElGamalError::SecretKeyDeserialization)?;
+
+        Ok(Self(shared_secret))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; SHARED_SECRET_LEN] {
+        &self.0
+    }
+
+    /// Derives application key material from the shared secret.
+    ///
+    /// The `info` string should identify the application and the purpose of the key so that keys
+    /// derived for different purposes are independent.
+    pub fn derive_key(&self, info: &[u8], okm: &mut [u8]) -> Result<(), ElGamalError> {
+        Hkdf::<Sha512>::new(None, &self.0)
+            .expand(info, okm)
+            .map_err(|_| ElGamalError::SecretKeyDeserialization)
+    }
+
+    /// Derives an authenticated encryption key from the shared secret.
+    pub fn derive_ae_key(&self, info: &[u8]) -> Result<AeKey, ElGamalError> {
+        let mut key = Zeroizing::new([0u8; AE_KEY_LEN]);
+        self.derive_key(info, key.as_mut_slice())?;
+        Ok(AeKey::from(*key))
+    }
+}
+
+impl Eq for SharedSecret {}
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).unwrap_u8() == 1u8
+    }
+}
+impl ConstantTimeEq for SharedSecret {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedSecret").field(&"[REDACTED]").finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_shared_secret_agreement() {
+        let sender = ElGamalKeypair::new_rand();
+        let recipient = ElGamalKeypair::new_rand();
+        let other = ElGamalKeypair::new_rand();
+
+        let sender_secret = sender.secret().diffie_hellman(recipient.pubkey()).unwrap();
+        let recipient_secret = recipient.secret().diffie_hellman(sender.pubkey()).unwrap();
+        assert_eq!(sender_secret, recipient_secret);
+
+        let other_secret = other.secret().diffie_hellman(sender.pubkey()).unwrap();
+        assert_ne!(sender_secret, other_secret);
+
+        // keys derived for different purposes are independent
+        let memo_key = sender_secret.derive_ae_key(b"memo").unwrap();
+        assert_eq!(memo_key, recipient_secret.derive_ae_key(b"memo").unwrap());
+        assert_ne!(memo_key, sender_secret.derive_ae_key(b"other").unwrap());
+
+        let ciphertext = memo_key.encrypt(55);
+        let recipient_memo_key = recipient_secret.derive_ae_key(b"memo").unwrap();
+        assert_eq!(recipient_memo_key.decrypt(&ciphertext), Some(55));
+    }
+
+    #[test]
+    fn test_shared_secret_rejects_identity() {
+        let keypair = ElGamalKeypair::new_rand();
+        let identity_pubkey = ElGamalPubkey::try_from([0u8; 32].as_slice()).unwrap();
+
+        assert_eq!(
+            keypair
+                .secret()
+                .diffie_hellman(&identity_pubkey)
+                .unwrap_err(),
+            ElGamalError::InvalidPubkey
+        );
+    }
+}
@@ -0,0 +1,707 @@
+//! Threshold sharing of ElGamal decryption keys.
+//!
+//! A long-lived ElGamal secret key `s` can be split among `n` custodians with Shamir secret
+//! sharing, so that any `t` of them can reconstruct it while fewer learn nothing about it.
+//! Custodian `i` holds the share `s_i = f(i)` of a random polynomial `f` of degree `t - 1` with
+//! `f(0) = s`, and publishes the share verification key `V_i = s_i * H`.
+//!
+//! Shares of a long-lived key are refreshed periodically, so that shares that leak in different
+//! epochs cannot be combined. In a refresh, every custodian deals a random polynomial `g` of
+//! degree `t - 1` with `g(0) = 0`. The dealer broadcasts a [`ShareRefreshCommitment`] to the
+//! coefficients of `g` and sends a [`ShareRefreshMessage`] with `g(j)` to each custodian `j`.
+//! Every custodian checks the messages that it receives against the broadcast commitments and
+//! adds them to its share. Since every dealt polynomial vanishes at zero, the refreshed shares are
+//! shares of the same secret key, and the public key is unchanged.
+//!
+//! To decrypt a ciphertext `(C, D)` without reconstructing the key, each custodian publishes the
+//! partial decryption `D_i = s_i * D` together with a proof that it is consistent with its
+//! verification key. [`combine_partial_decryptions`] checks the proofs, excludes the custodians
+//! whose partial decryptions are invalid, and interpolates `s * D` from a threshold of the valid
+//! ones.
+
+pub use crate::sigma_proofs::{
+    errors::PartialDecryptionProofVerificationError, partial_decryption::PartialDecryptionProof,
+};
+use {
+    crate::{
+        encryption::{
+            discrete_log::DiscreteLog,
+            elgamal::{ElGamalCiphertext, ElGamalSecretKey},
+            pedersen::H,
+        },
+        errors::{RngError, ThresholdError},
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::VartimeMultiscalarMul},
+    merlin::Transcript,
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeSet, fmt},
+    zeroize::{Zeroize, ZeroizeOnDrop},
+};
+
+/// The maximum number of custodians of a shared key.
+pub const MAX_CUSTODIANS: u32 = 256;
+
+/// The threshold and the number of custodians of a shared key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThresholdParameters {
+    threshold: u32,
+    num_custodians: u32,
+}
+
+impl ThresholdParameters {
+    /// Creates parameters for a key that is shared among `num_custodians` custodians, any
+    /// `threshold` of whom can reconstruct it.
+    pub fn new(threshold: u32, num_custodians: u32) -> Result<Self, ThresholdError> {
+        if threshold == 0 || threshold > num_custodians || num_custodians > MAX_CUSTODIANS {
+            return Err(ThresholdError::InvalidParameters);
+        }
+        Ok(Self {
+            threshold,
+            num_custodians,
+        })
+    }
+
+    /// The number of custodians that are needed to reconstruct the key.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// The number of custodians of the key.
+    pub fn num_custodians(&self) -> u32 {
+        self.num_custodians
+    }
+
+    fn check_index(&self, index: u32) -> Result<(), ThresholdError> {
+        if index == 0 || index > self.num_custodians {
+            return Err(ThresholdError::InvalidShareIndex);
+        }
+        Ok(())
+    }
+}
+
+/// The share of an ElGamal secret key that is held by a custodian.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct KeyShare {
+    index: u32,
+    share: Scalar,
+}
+
+impl KeyShare {
+    /// Splits an ElGamal secret key into one share for each custodian.
+    ///
+    /// The share of custodian `i` has index `i`, for `i` from 1 to the number of custodians.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the sharing
+    /// polynomial.
+    pub fn split(secret: &ElGamalSecretKey, parameters: &ThresholdParameters) -> Vec<Self> {
+        unwrap_default_rng(Self::split_with_rng(secret, parameters, &mut DefaultRng))
+    }
+
+    /// Splits an ElGamal secret key using `rng` to generate the sharing polynomial.
+    pub fn split_with_rng(
+        secret: &ElGamalSecretKey,
+        parameters: &ThresholdParameters,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Vec<Self>, RngError> {
+        let polynomial = Polynomial::random(*secret.get_scalar(), parameters.threshold, rng)?;
+        Ok((1..=parameters.num_custodians)
+            .map(|index| Self {
+                index,
+                share: polynomial.evaluate(index),
+            })
+            .collect())
+    }
+
+    /// Reconstructs the secret key from at least a threshold of shares.
+    ///
+    /// The shares must have distinct indices. If fewer than a threshold of shares are given, the
+    /// reconstructed key is unrelated to the shared key.
+    pub fn recover(shares: &[Self]) -> Result<ElGamalSecretKey, ThresholdError> {
+        let indices = shares.iter().map(|share| share.index).collect::<Vec<_>>();
+        let mut secret = Scalar::ZERO;
+        for share in shares {
+            secret += lagrange_coefficient(share.index, &indices)? * share.share;
+        }
+        Ok(ElGamalSecretKey::from(secret))
+    }
+
+    /// The index of the custodian that holds the share.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn get_scalar(&self) -> &Scalar {
+        &self.share
+    }
+
+    /// The public verification key of the share.
+    pub fn verification_key(&self) -> ShareVerificationKey {
+        ShareVerificationKey {
+            index: self.index,
+            point: self.share * *H,
+        }
+    }
+
+    /// Refreshes the share with the dealings of a refresh round.
+    ///
+    /// `commitments` holds the broadcast commitment of every dealer of the round and `messages`
+    /// the messages that the dealers sent to this custodian. Every message is checked against the
+    /// commitment of its dealer, and every dealer must have sent exactly one message.
+    pub fn refresh(
+        &self,
+        parameters: &ThresholdParameters,
+        commitments: &[ShareRefreshCommitment],
+        messages: &[ShareRefreshMessage],
+    ) -> Result<Self, ThresholdError> {
+        parameters.check_index(self.index)?;
+        check_refresh_commitments(parameters, commitments)?;
+        if messages.len() != commitments.len() {
+            return Err(ThresholdError::InvalidRefreshMessage);
+        }
+
+        let mut share = self.share;
+        for commitment in commitments {
+            let mut matching = messages
+                .iter()
+                .filter(|message| message.dealer == commitment.dealer);
+            let (Some(message), None) = (matching.next(), matching.next()) else {
+                return Err(ThresholdError::InvalidRefreshMessage);
+            };
+            if message.recipient != self.index
+                || message.delta * *H != commitment.evaluate(self.index)
+            {
+                return Err(ThresholdError::InvalidRefreshMessage);
+            }
+            share += message.delta;
+        }
+
+        Ok(Self {
+            index: self.index,
+            share,
+        })
+    }
+
+    /// Computes the partial decryption `s_i * D` of a ciphertext.
+    pub fn partial_decrypt(&self, ciphertext: &ElGamalCiphertext) -> PartialDecryption {
+        PartialDecryption {
+            index: self.index,
+            point: self.share * ciphertext.handle.get_point(),
+        }
+    }
+
+    /// Computes the partial decryption of a ciphertext together with a proof that it is consistent
+    /// with the verification key of the share.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the proof.
+    pub fn partial_decrypt_with_proof(
+        &self,
+        ciphertext: &ElGamalCiphertext,
+    ) -> VerifiablePartialDecryption {
+        let partial_decryption = self.partial_decrypt(ciphertext);
+        let mut transcript = partial_decryption_transcript();
+        let proof = PartialDecryptionProof::new(
+            self,
+            &ciphertext.handle,
+            &partial_decryption,
+            &mut transcript,
+        );
+        VerifiablePartialDecryption {
+            partial_decryption,
+            proof,
+        }
+    }
+}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("index", &self.index)
+            .field("share", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// The public verification key `V_i = s_i * H` of the key share of a custodian.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ShareVerificationKey {
+    index: u32,
+    point: RistrettoPoint,
+}
+
+impl ShareVerificationKey {
+    /// The index of the custodian that holds the share.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn get_point(&self) -> &RistrettoPoint {
+        &self.point
+    }
+
+    /// Computes the verification key of the refreshed share from the broadcast commitments of a
+    /// refresh round.
+    ///
+    /// Anyone can track the verification keys of the custodians across refreshes without seeing
+    /// the messages of the round.
+    pub fn refresh(
+        &self,
+        parameters: &ThresholdParameters,
+        commitments: &[ShareRefreshCommitment],
+    ) -> Result<Self, ThresholdError> {
+        parameters.check_index(self.index)?;
+        check_refresh_commitments(parameters, commitments)?;
+        let point = commitments.iter().fold(self.point, |acc, commitment| {
+            acc + commitment.evaluate(self.index)
+        });
+        Ok(Self {
+            index: self.index,
+            point,
+        })
+    }
+}
+
+/// The partial decryption `D_i = s_i * D` of a ciphertext by a custodian.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct PartialDecryption {
+    index: u32,
+    point: RistrettoPoint,
+}
+
+impl PartialDecryption {
+    /// The index of the custodian that computed the partial decryption.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn get_point(&self) -> &RistrettoPoint {
+        &self.point
+    }
+}
+
+/// A partial decryption together with a proof that it is consistent with the verification key
+/// of the custodian.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VerifiablePartialDecryption {
+    pub partial_decryption: PartialDecryption,
+    pub proof: PartialDecryptionProof,
+}
+
+impl VerifiablePartialDecryption {
+    /// Verifies the partial decryption of a ciphertext against the verification key of the
+    /// custodian.
+    pub fn verify(
+        &self,
+        verification_key: &ShareVerificationKey,
+        ciphertext: &ElGamalCiphertext,
+    ) -> Result<(), ThresholdError> {
+        let mut transcript = partial_decryption_transcript();
+        self.proof
+            .clone()
+            .verify(
+                verification_key,
+                &ciphertext.handle,
+                &self.partial_decryption,
+                &mut transcript,
+            )
+            .map_err(|_| ThresholdError::InvalidPartialDecryption)
+    }
+}
+
+/// The result of the combination of partial decryptions.
+pub struct ThresholdDecryption {
+    /// The discrete log instance of the encrypted amount
+    pub discrete_log: DiscreteLog,
+    /// The indices of the custodians whose partial decryptions were excluded
+    pub excluded: Vec<u32>,
+}
+
+/// Combines verifiable partial decryptions of a ciphertext.
+///
+/// Every partial decryption is verified against the verification key of its custodian.
+/// Partial decryptions of custodians without a verification key, with an invalid proof, or from a
+/// custodian that already contributed are excluded and reported in
+/// [`ThresholdDecryption::excluded`]. The encrypted amount is interpolated from the first
+/// threshold of valid partial decryptions.
+///
+/// Returns [`ThresholdError::InsufficientShares`] if fewer than a threshold of the partial
+/// decryptions are valid.
+pub fn combine_partial_decryptions(
+    parameters: &ThresholdParameters,
+    ciphertext: &ElGamalCiphertext,
+    verification_keys: &[ShareVerificationKey],
+    partial_decryptions: &[VerifiablePartialDecryption],
+) -> Result<ThresholdDecryption, ThresholdError> {
+    let mut valid = Vec::with_capacity(parameters.threshold as usize);
+    let mut excluded = Vec::new();
+    let mut seen = BTreeSet::new();
+    for partial_decryption in partial_decryptions {
+        let index = partial_decryption.partial_decryption.index;
+        let is_valid = verification_keys
+            .iter()
+            .find(|key| key.index == index)
+            .is_some_and(|key| partial_decryption.verify(key, ciphertext).is_ok());
+        if !is_valid || !seen.insert(index) {
+            excluded.push(index);
+        } else if valid.len() < parameters.threshold as usize {
+            valid.push(partial_decryption.partial_decryption);
+        }
+    }
+    if valid.len() < parameters.threshold as usize {
+        return Err(ThresholdError::InsufficientShares);
+    }
+
+    // interpolate `s * D` from the partial decryptions
+    let indices = valid
+        .iter()
+        .map(|partial| partial.index)
+        .collect::<Vec<_>>();
+    let coefficients = indices
+        .iter()
+        .map(|index| lagrange_coefficient(*index, &indices))
+        .collect::<Result<Vec<_>, _>>()?;
+    let decryption = RistrettoPoint::vartime_multiscalar_mul(
+        coefficients,
+        valid.iter().map(|partial| partial.point),
+    );
+
+    Ok(ThresholdDecryption {
+        discrete_log: DiscreteLog::new_for_g(ciphertext.commitment.get_point() - decryption),
+        excluded,
+    })
+}
+
+fn partial_decryption_transcript() -> Transcript {
+    Transcript::new_zk_elgamal_transcript(b"partial-decryption")
+}
+
+/// The broadcast commitment of a dealer to its refresh polynomial.
+///
+/// The commitment holds `a_k * H` for the coefficients `a_1, ..., a_{t-1}` of the polynomial. The
+/// constant coefficient is zero by construction and is not committed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ShareRefreshCommitment {
+    dealer: u32,
+    coefficients: Vec<RistrettoPoint>,
+}
+
+impl ShareRefreshCommitment {
+    /// The index of the custodian that dealt the polynomial.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// Computes `g(index) * H` for the committed polynomial `g`.
+    fn evaluate(&self, index: u32) -> RistrettoPoint {
+        let x = Scalar::from(index);
+        let powers = std::iter::successors(Some(x), |power| Some(power * x));
+        RistrettoPoint::vartime_multiscalar_mul(
+            powers.take(self.coefficients.len()),
+            &self.coefficients,
+        )
+    }
+}
+
+/// The message of a dealer to a single custodian in a refresh round.
+#[derive(Clone, Deserialize, Serialize, Zeroize, ZeroizeOnDrop)]
+pub struct ShareRefreshMessage {
+    dealer: u32,
+    recipient: u32,
+    delta: Scalar,
+}
+
+impl ShareRefreshMessage {
+    /// The index of the custodian that dealt the message.
+    pub fn dealer(&self) -> u32 {
+        self.dealer
+    }
+
+    /// The index of the custodian that the message is addressed to.
+    pub fn recipient(&self) -> u32 {
+        self.recipient
+    }
+}
+
+impl fmt::Debug for ShareRefreshMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShareRefreshMessage")
+            .field("dealer", &self.dealer)
+            .field("recipient", &self.recipient)
+            .field("delta", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// The dealing of a custodian in a refresh round.
+#[derive(Clone, Debug)]
+pub struct ShareRefreshDealing {
+    /// The commitment to be broadcast to all custodians
+    pub commitment: ShareRefreshCommitment,
+    /// The messages to be sent privately, one to each custodian
+    pub messages: Vec<ShareRefreshMessage>,
+}
+
+impl ShareRefreshDealing {
+    /// Deals a random refresh polynomial for all custodians.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the polynomial.
+    pub fn new(dealer: u32, parameters: &ThresholdParameters) -> Result<Self, ThresholdError> {
+        Self::new_with_rng(dealer, parameters, &mut DefaultRng)
+    }
+
+    /// Deals a random refresh polynomial using `rng` to generate the polynomial.
+    pub fn new_with_rng(
+        dealer: u32,
+        parameters: &ThresholdParameters,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, ThresholdError> {
+        parameters.check_index(dealer)?;
+        let polynomial = Polynomial::random(Scalar::ZERO, parameters.threshold, rng)?;
+
+        let commitment = ShareRefreshCommitment {
+            dealer,
+            coefficients: polynomial.coefficients[1..]
+                .iter()
+                .map(|coefficient| coefficient * *H)
+                .collect(),
+        };
+        let messages = (1..=parameters.num_custodians)
+            .map(|recipient| ShareRefreshMessage {
+                dealer,
+                recipient,
+                delta: polynomial.evaluate(recipient),
+            })
+            .collect();
+        Ok(Self {
+            commitment,
+            messages,
+        })
+    }
+}
+
+/// A polynomial over the scalar field with coefficients in increasing order of degree.
+#[derive(Zeroize, ZeroizeOnDrop)]
+struct Polynomial {
+    coefficients: Vec<Scalar>,
+}
+
+impl Polynomial {
+    /// Samples a random polynomial of degree `threshold - 1` with a fixed constant coefficient.
+    fn random(
+        constant: Scalar,
+        threshold: u32,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        let mut coefficients = Vec::with_capacity(threshold as usize);
+        coefficients.push(constant);
+        for _ in 1..threshold {
+            coefficients.push(try_random_scalar(rng)?);
+        }
+        Ok(Self { coefficients })
+    }
+
+    fn evaluate(&self, index: u32) -> Scalar {
+        let x = Scalar::from(index);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient)
+    }
+}
+
+/// Checks that the commitments of a refresh round are from distinct custodians and are of the
+/// degree of the threshold.
+fn check_refresh_commitments(
+    parameters: &ThresholdParameters,
+    commitments: &[ShareRefreshCommitment],
+) -> Result<(), ThresholdError> {
+    let mut dealers = BTreeSet::new();
+    for commitment in commitments {
+        parameters.check_index(commitment.dealer)?;
+        if !dealers.insert(commitment.dealer)
+            || commitment.coefficients.len() + 1 != parameters.threshold as usize
+        {
+            return Err(ThresholdError::InvalidRefreshCommitment);
+        }
+    }
+    Ok(())
+}
+
+/// Computes the Lagrange coefficient at zero of the share with index `index` among the shares
+/// with indices `indices`.
+pub(crate) fn lagrange_coefficient(index: u32, indices: &[u32]) -> Result<Scalar, ThresholdError> {
+    let mut seen = BTreeSet::new();
+    for other in indices {
+        if *other == 0 {
+            return Err(ThresholdError::InvalidShareIndex);
+        }
+        if !seen.insert(*other) {
+            return Err(ThresholdError::DuplicateShareIndex);
+        }
+    }
+    if !seen.contains(&index) {
+        return Err(ThresholdError::InvalidShareIndex);
+    }
+
+    let x_i = Scalar::from(index);
+    let (numerator, denominator) = indices
+        .iter()
+        .filter(|other| **other != index)
+        .map(|other| Scalar::from(*other))
+        .fold(
+            (Scalar::ONE, Scalar::ONE),
+            |(numerator, denominator), x_j| (numerator * x_j, denominator * (x_j - x_i)),
+        );
+    Ok(numerator * denominator.invert())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_split_and_recover() {
+        let keypair = ElGamalKeypair::new_rand();
+        let parameters = ThresholdParameters::new(3, 5).unwrap();
+        let shares = KeyShare::split(keypair.secret(), &parameters);
+        assert_eq!(shares.len(), 5);
+
+        let recovered = KeyShare::recover(&shares[1..4]).unwrap();
+        assert_eq!(&recovered, keypair.secret());
+        let recovered =
+            KeyShare::recover(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap();
+        assert_eq!(&recovered, keypair.secret());
+
+        // fewer than a threshold of shares reveal nothing about the key
+        assert_ne!(&KeyShare::recover(&shares[..2]).unwrap(), keypair.secret());
+        assert_eq!(
+            KeyShare::recover(&[shares[0].clone(), shares[0].clone()]).unwrap_err(),
+            ThresholdError::DuplicateShareIndex
+        );
+
+        assert_eq!(
+            ThresholdParameters::new(4, 3).unwrap_err(),
+            ThresholdError::InvalidParameters
+        );
+        assert_eq!(
+            format!("{:?}", shares[0]),
+            "KeyShare { index: 1, share: \"[REDACTED]\" }"
+        );
+    }
+
+    #[test]
+    fn test_combine_partial_decryptions() {
+        let keypair = ElGamalKeypair::new_rand();
+        let parameters = ThresholdParameters::new(2, 4).unwrap();
+        let shares = KeyShare::split(keypair.secret(), &parameters);
+        let verification_keys = shares
+            .iter()
+            .map(KeyShare::verification_key)
+            .collect::<Vec<_>>();
+        let ciphertext = keypair.pubkey().encrypt(55_u64);
+
+        let mut partial_decryptions = shares
+            .iter()
+            .map(|share| share.partial_decrypt_with_proof(&ciphertext))
+            .collect::<Vec<_>>();
+
+        // a malicious custodian publishes the partial decryption of another custodian
+        partial_decryptions[0].partial_decryption.point =
+            partial_decryptions[1].partial_decryption.point;
+
+        let decryption = combine_partial_decryptions(
+            &parameters,
+            &ciphertext,
+            &verification_keys,
+            &partial_decryptions,
+        )
+        .unwrap();
+        assert_eq!(decryption.discrete_log.decode_u32(), Some(55));
+        assert_eq!(decryption.excluded, vec![1]);
+
+        // too few valid partial decryptions
+        assert!(matches!(
+            combine_partial_decryptions(
+                &parameters,
+                &ciphertext,
+                &verification_keys,
+                &partial_decryptions[..2],
+            ),
+            Err(ThresholdError::InsufficientShares)
+        ));
+    }
+
+    #[test]
+    fn test_share_refresh() {
+        let keypair = ElGamalKeypair::new_rand();
+        let parameters = ThresholdParameters::new(2, 3).unwrap();
+        let shares = KeyShare::split(keypair.secret(), &parameters);
+
+        // every custodian deals a refresh polynomial
+        let dealings = (1..=3)
+            .map(|dealer| ShareRefreshDealing::new(dealer, &parameters).unwrap())
+            .collect::<Vec<_>>();
+        let commitments = dealings
+            .iter()
+            .map(|dealing| dealing.commitment.clone())
+            .collect::<Vec<_>>();
+        let messages_for = |recipient: u32| {
+            dealings
+                .iter()
+                .map(|dealing| dealing.messages[recipient as usize - 1].clone())
+                .collect::<Vec<_>>()
+        };
+
+        let refreshed = shares
+            .iter()
+            .map(|share| {
+                share
+                    .refresh(&parameters, &commitments, &messages_for(share.index()))
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        // the shares change but still share the same key
+        assert_ne!(refreshed[0].get_scalar(), shares[0].get_scalar());
+        assert_eq!(
+            &KeyShare::recover(&refreshed[1..]).unwrap(),
+            keypair.secret()
+        );
+
+        // old and new shares cannot be combined
+        assert_ne!(
+            &KeyShare::recover(&[shares[0].clone(), refreshed[1].clone()]).unwrap(),
+            keypair.secret()
+        );
+
+        // the verification keys are tracked from the broadcast commitments
+        for (share, refreshed_share) in shares.iter().zip(&refreshed) {
+            assert_eq!(
+                share
+                    .verification_key()
+                    .refresh(&parameters, &commitments)
+                    .unwrap(),
+                refreshed_share.verification_key()
+            );
+        }
+
+        // a message that is inconsistent with the commitment of its dealer is rejected
+        let mut messages = messages_for(1);
+        messages.swap(0, 1);
+        messages[0].dealer = 1;
+        messages[1].dealer = 2;
+        assert_eq!(
+            shares[0]
+                .refresh(&parameters, &commitments, &messages)
+                .unwrap_err(),
+            ThresholdError::InvalidRefreshMessage
+        );
+        assert_eq!(
+            shares[0]
+                .refresh(&parameters, &commitments, &messages_for(2))
+                .unwrap_err(),
+            ThresholdError::InvalidRefreshMessage
+        );
+    }
+}
@@ -1,5 +1,12 @@
 //! Errors related to proving and verifying proofs.
-use thiserror::Error;
+use {
+    crate::{
+        encryption::grouped_elgamal::GroupedElGamalError,
+        zk_elgamal_proof_program::errors::ProofGenerationError,
+    },
+    std::num::NonZeroU32,
+    thiserror::Error,
+};
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum AuthenticatedEncryptionError {
@@ -31,10 +38,179 @@ pub enum ElGamalError {
     SecretKeyDeserialization,
     #[error("rejecting default signature as key material")]
     DefaultSignatureRejected,
+    #[error("rejecting non-canonical signature as key material")]
+    NonCanonicalSignature,
+    #[error("signature does not verify over the derivation message")]
+    InvalidSignature,
+    #[error("failed to deserialize Pedersen opening")]
+    OpeningDeserialization,
+    #[error("invalid public key")]
+    InvalidPubkey,
+    #[error("degenerate ciphertext")]
+    DegenerateCiphertext,
+    #[error("invalid program-derived address seeds")]
+    InvalidSeeds,
 }
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum TranscriptError {
     #[error("point is the identity")]
     ValidationError,
+    #[error("all protocol statements have been proven")]
+    StatementsExhausted,
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum AmountError {
+    #[error("amount exceeds the bit length")]
+    ExceedsBitLength,
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum SignatureError {
+    #[error("failed to deserialize signature")]
+    Deserialization,
+    #[error("invalid public key")]
+    InvalidPubkey,
+    #[error("signature verification failed")]
+    Verification,
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum AggregationError {
+    #[error("invalid bit length for the number of terms")]
+    InvalidTermsBitLength,
+    #[error("number of terms exceeds the limit")]
+    TermLimitExceeded,
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum ThresholdError {
+    #[error("invalid threshold parameters")]
+    InvalidParameters,
+    #[error("invalid share index")]
+    InvalidShareIndex,
+    #[error("duplicate share index")]
+    DuplicateShareIndex,
+    #[error("invalid share refresh commitment")]
+    InvalidRefreshCommitment,
+    #[error("invalid share refresh message")]
+    InvalidRefreshMessage,
+    #[error("invalid partial decryption")]
+    InvalidPartialDecryption,
+    #[error("insufficient valid shares")]
+    InsufficientShares,
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum SessionError {
+    #[error("invalid session parameters")]
+    InvalidParameters,
+    #[error("message header is inconsistent with the payload")]
+    InconsistentHeader,
+    #[error("message belongs to another session")]
+    SessionMismatch,
+    #[error("message is not from another participant of the session")]
+    UnexpectedSender,
+    #[error("message is addressed to another participant")]
+    MisroutedMessage,
+    #[error("message belongs to another round")]
+    OutOfOrderMessage,
+    #[error("message was already received")]
+    ReplayedMessage,
+    #[error("unexpected payload for the round")]
+    UnexpectedPayload,
+    #[error("session is not in progress")]
+    NotInProgress,
+    #[error("session round timed out")]
+    TimedOut,
+    #[error("failed to serialize session message")]
+    Serialization,
+    #[error("failed to deserialize session message")]
+    Deserialization,
+}
+
+/// The error returned when a random number generator fails to produce random bytes.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("random number generator failed: {message}")]
+pub struct RngError {
+    code: Option<NonZeroU32>,
+    message: String,
+}
+
+impl RngError {
+    /// The error code reported by the random number generator, if any.
+    pub fn code(&self) -> Option<NonZeroU32> {
+        self.code
+    }
+}
+
+impl From<rand::Error> for RngError {
+    fn from(err: rand::Error) -> Self {
+        Self {
+            code: err.code(),
+            message: err.to_string(),
+        }
+    }
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum AuditError {
+    #[error("grouped ciphertext is not encrypted under the auditor public key")]
+    MissingAuditorHandle,
+    #[error("decrypt handles are inconsistent with the commitment")]
+    InconsistentHandles,
+    #[error("auditor handle does not open the commitment to a 32-bit amount")]
+    UndecodableAmount,
+}
+
+/// The error returned when a secret key backend fails to perform an operation.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum SecretKeyBackendError {
+    #[error("secret key backend failed: {0}")]
+    Backend(String),
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum ArmorError {
+    #[error("missing or malformed armor header")]
+    InvalidHeader,
+    #[error("missing or malformed armor footer")]
+    InvalidFooter,
+    #[error("missing armor checksum")]
+    MissingChecksum,
+    #[error("armor checksum mismatch")]
+    ChecksumMismatch,
+    #[error("invalid base64 encoding")]
+    InvalidBase64,
+    #[error("unexpected armor type tag: {0}")]
+    UnexpectedTypeTag(String),
+    #[error("invalid armored data")]
+    InvalidData,
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum ClientError {
+    #[error("failed to deserialize account state")]
+    AccountDeserialization,
+    #[error("failed to decrypt balance")]
+    DecryptionFailed,
+    #[error("insufficient funds")]
+    InsufficientFunds,
+    #[error("amount exceeds the maximum deposit or transfer amount")]
+    AmountTooLarge,
+    #[error("balance overflow")]
+    BalanceOverflow,
+    #[error("pending balance is inconsistent with the credit counter")]
+    PendingBalanceCreditCounterMismatch,
+    #[error("ElGamal ciphertext error")]
+    ElGamal(#[from] ElGamalError),
+    #[error("grouped ElGamal ciphertext error")]
+    GroupedElGamal(#[from] GroupedElGamalError),
+    #[error("proof generation failed")]
+    ProofGeneration(#[from] ProofGenerationError),
 }
@@ -0,0 +1,351 @@
+//! Helpers for computing transfer fees together with their percentage-with-cap proofs.
+//!
+//! A confidential transfer with fee requires a fee commitment, a delta commitment, a claimed
+//! commitment, their openings, and a percentage-with-cap proof that ties them to the transfer
+//! amount commitment. The [`calculate_fee_with_proof`] function computes all of these components
+//! in one call so that callers do not have to assemble the fee arithmetic by hand.
+//!
+//! Fee rates can be expressed in basis points or, for finer granularity, in parts-per-million
+//! (see [`FeeRate`]). With the `unstable` feature, maximum fees that do not fit in 64 bits are
+//! supported through `calculate_wide_fee_with_proof`, which produces a proof whose context encodes
+//! the maximum fee as a 128-bit integer.
+
+#[cfg(feature = "unstable")]
+use {
+    crate::zk_elgamal_proof_program::build_percentage_with_cap_u128_proof_data,
+    solana_zk_elgamal_proof_interface::proof_data::PercentageWithCapU128ProofData,
+};
+use {
+    crate::{
+        encryption::pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
+        zk_elgamal_proof_program::{
+            build_percentage_with_cap_proof_data, errors::ProofGenerationError,
+        },
+    },
+    solana_zk_elgamal_proof_interface::proof_data::PercentageWithCapProofData,
+};
+
+/// The denominator of a fee rate expressed in basis points.
+pub const ONE_IN_BASIS_POINTS: u64 = 10_000;
+
+/// The denominator of a fee rate expressed in parts-per-million.
+pub const ONE_IN_PARTS_PER_MILLION: u64 = 1_000_000;
+
+/// A fee rate together with the denominator that it is expressed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeRate {
+    /// A fee rate in basis points (1/10_000).
+    BasisPoints(u16),
+    /// A fee rate in parts-per-million (1/1_000_000).
+    PartsPerMillion(u32),
+}
+
+impl FeeRate {
+    /// The numerator of the fee rate.
+    pub fn numerator(&self) -> u64 {
+        match self {
+            Self::BasisPoints(rate) => u64::from(*rate),
+            Self::PartsPerMillion(rate) => u64::from(*rate),
+        }
+    }
+
+    /// The denominator of the fee rate.
+    pub fn denominator(&self) -> u64 {
+        match self {
+            Self::BasisPoints(_) => ONE_IN_BASIS_POINTS,
+            Self::PartsPerMillion(_) => ONE_IN_PARTS_PER_MILLION,
+        }
+    }
+
+    /// Computes the fee amount and the delta amount for a transfer.
+    ///
+    /// The fee is computed as `ceil(transfer_amount * numerator / denominator)` and capped at
+    /// `max_fee`. The returned delta amount is `raw_fee * denominator - transfer_amount *
+    /// numerator` where `raw_fee` is the uncapped fee. Returns `None` if the fee rate exceeds 100%.
+    pub fn calculate_fee(&self, transfer_amount: u64, max_fee: u128) -> Option<(u64, u64)> {
+        let numerator = self.numerator() as u128;
+        let denominator = self.denominator() as u128;
+        if numerator > denominator {
+            return None;
+        }
+
+        let scaled_amount = (transfer_amount as u128).checked_mul(numerator)?;
+        let raw_fee = scaled_amount.div_ceil(denominator);
+        let delta_amount = raw_fee
+            .checked_mul(denominator)?
+            .checked_sub(scaled_amount)?;
+
+        // the fee rate is at most 100%, so the fee always fits in 64 bits
+        let fee_amount = u64::try_from(raw_fee.min(max_fee)).ok()?;
+        let delta_amount = u64::try_from(delta_amount).ok()?;
+
+        Some((fee_amount, delta_amount))
+    }
+}
+
+/// A transfer fee together with all the components needed to prove that it was computed
+/// correctly.
+pub struct FeeWithProof<T = PercentageWithCapProofData> {
+    /// The Pedersen commitment to the transfer amount.
+    pub transfer_commitment: PedersenCommitment,
+
+    /// The fee amount, capped at the maximum fee.
+    pub fee_amount: u64,
+    /// The Pedersen commitment to the fee amount.
+    pub fee_commitment: PedersenCommitment,
+    /// The Pedersen opening of the fee commitment.
+    pub fee_opening: PedersenOpening,
+
+    /// The uncapped delta amount `fee * denominator - amount * rate`.
+    pub delta_amount: u64,
+    /// The Pedersen commitment to the delta amount, derived homomorphically from the transfer and
+    /// fee commitments.
+    pub delta_commitment: PedersenCommitment,
+    /// The Pedersen opening of the delta commitment.
+    pub delta_opening: PedersenOpening,
+
+    /// The Pedersen commitment to the claimed delta amount.
+    pub claimed_commitment: PedersenCommitment,
+    /// The Pedersen opening of the claimed delta commitment.
+    pub claimed_opening: PedersenOpening,
+
+    /// The percentage-with-cap proof data.
+    pub proof_data: T,
+}
+
+/// Computes the fee amount and the delta amount for a transfer with a fee rate in basis points.
+///
+/// Returns `None` if the fee rate exceeds 100%. See [`FeeRate::calculate_fee`].
+pub fn calculate_fee(transfer_amount: u64, rate_bps: u16, max_fee: u64) -> Option<(u64, u64)> {
+    FeeRate::BasisPoints(rate_bps).calculate_fee(transfer_amount, max_fee.into())
+}
+
+/// Computes the fee for a transfer together with its commitments and percentage-with-cap proof.
+///
+/// * `transfer_amount` - The transfer amount
+/// * `transfer_opening` - The Pedersen opening of the transfer amount commitment
+/// * `rate_bps` - The fee rate in basis points
+/// * `max_fee` - The maximum fee
+pub fn calculate_fee_with_proof(
+    transfer_amount: u64,
+    transfer_opening: &PedersenOpening,
+    rate_bps: u16,
+    max_fee: u64,
+) -> Result<FeeWithProof, ProofGenerationError> {
+    let fee = FeeWithProof::compute(
+        transfer_amount,
+        transfer_opening,
+        FeeRate::BasisPoints(rate_bps),
+        max_fee.into(),
+    )?;
+
+    let proof_data = build_percentage_with_cap_proof_data(
+        &fee.fee_commitment,
+        &fee.fee_opening,
+        fee.fee_amount,
+        &fee.delta_commitment,
+        &fee.delta_opening,
+        fee.delta_amount,
+        &fee.claimed_commitment,
+        &fee.claimed_opening,
+        max_fee,
+    )?;
+
+    Ok(fee.with_proof_data(proof_data))
+}
+
+/// Computes the fee for a transfer with an arbitrary fee rate denomination and a 128-bit maximum
+/// fee together with its commitments and percentage-with-cap proof.
+///
+/// * `transfer_amount` - The transfer amount
+/// * `transfer_opening` - The Pedersen opening of the transfer amount commitment
+/// * `rate` - The fee rate
+/// * `max_fee` - The 128-bit maximum fee
+#[cfg(feature = "unstable")]
+pub fn calculate_wide_fee_with_proof(
+    transfer_amount: u64,
+    transfer_opening: &PedersenOpening,
+    rate: FeeRate,
+    max_fee: u128,
+) -> Result<FeeWithProof<PercentageWithCapU128ProofData>, ProofGenerationError> {
+    let fee = FeeWithProof::compute(transfer_amount, transfer_opening, rate, max_fee)?;
+
+    let proof_data = build_percentage_with_cap_u128_proof_data(
+        &fee.fee_commitment,
+        &fee.fee_opening,
+        fee.fee_amount,
+        &fee.delta_commitment,
+        &fee.delta_opening,
+        fee.delta_amount,
+        &fee.claimed_commitment,
+        &fee.claimed_opening,
+        max_fee,
+    )?;
+
+    Ok(fee.with_proof_data(proof_data))
+}
+
+impl FeeWithProof<()> {
+    /// Computes the fee amounts, commitments, and openings without the proof data.
+    fn compute(
+        transfer_amount: u64,
+        transfer_opening: &PedersenOpening,
+        rate: FeeRate,
+        max_fee: u128,
+    ) -> Result<Self, ProofGenerationError> {
+        let (fee_amount, delta_amount) = rate
+            .calculate_fee(transfer_amount, max_fee)
+            .ok_or(ProofGenerationError::FeeCalculation)?;
+
+        let transfer_commitment = Pedersen::with(transfer_amount, transfer_opening);
+        let (fee_commitment, fee_opening) = Pedersen::new(fee_amount);
+
+        let delta_commitment =
+            &fee_commitment * rate.denominator() - &transfer_commitment * rate.numerator();
+        let delta_opening = &fee_opening * rate.denominator() - transfer_opening * rate.numerator();
+
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+
+        Ok(Self {
+            transfer_commitment,
+            fee_amount,
+            fee_commitment,
+            fee_opening,
+            delta_amount,
+            delta_commitment,
+            delta_opening,
+            claimed_commitment,
+            claimed_opening,
+            proof_data: (),
+        })
+    }
+
+    fn with_proof_data<T>(self, proof_data: T) -> FeeWithProof<T> {
+        FeeWithProof {
+            transfer_commitment: self.transfer_commitment,
+            fee_amount: self.fee_amount,
+            fee_commitment: self.fee_commitment,
+            fee_opening: self.fee_opening,
+            delta_amount: self.delta_amount,
+            delta_commitment: self.delta_commitment,
+            delta_opening: self.delta_opening,
+            claimed_commitment: self.claimed_commitment,
+            claimed_opening: self.claimed_opening,
+            proof_data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::zk_elgamal_proof_program::VerifyZkProof,
+        solana_zk_sdk_pod::encryption::pedersen::PodPedersenCommitment,
+    };
+
+    #[test]
+    fn test_calculate_fee() {
+        // 1% of 100 is exactly 1
+        assert_eq!(calculate_fee(100, 100, 10), Some((1, 0)));
+        // 5.55% of 55 is 3.0525, rounded up to 4
+        assert_eq!(calculate_fee(55, 555, 10), Some((4, 9475)));
+        // capped at the maximum fee
+        assert_eq!(calculate_fee(55, 555, 3), Some((3, 9475)));
+        // zero fee rate
+        assert_eq!(calculate_fee(55, 0, 3), Some((0, 0)));
+        // fee rate above 100%
+        assert_eq!(calculate_fee(55, 10_001, 3), None);
+        // no overflow for the maximum transfer amount
+        assert_eq!(
+            calculate_fee(u64::MAX, 10_000, u64::MAX),
+            Some((u64::MAX, 0))
+        );
+    }
+
+    #[test]
+    fn test_calculate_fee_with_proof_below_max() {
+        let (transfer_commitment, transfer_opening) = Pedersen::new(55_u64);
+        let fee = calculate_fee_with_proof(55, &transfer_opening, 555, 10).unwrap();
+
+        assert_eq!(fee.transfer_commitment, transfer_commitment);
+        assert_eq!(fee.fee_amount, 4);
+        assert_eq!(fee.delta_amount, 9475);
+        assert_eq!(
+            fee.delta_commitment,
+            Pedersen::with(fee.delta_amount, &fee.delta_opening)
+        );
+        assert_eq!(
+            fee.proof_data.context.percentage_commitment,
+            PodPedersenCommitment::from(fee.fee_commitment)
+        );
+        assert!(fee.proof_data.verify_proof().is_ok());
+    }
+
+    #[test]
+    fn test_calculate_fee_with_proof_above_max() {
+        let (_, transfer_opening) = Pedersen::new(1_000_000_u64);
+        let fee = calculate_fee_with_proof(1_000_000, &transfer_opening, 200, 50).unwrap();
+
+        assert_eq!(fee.fee_amount, 50);
+        assert_eq!(
+            fee.fee_commitment,
+            Pedersen::with(fee.fee_amount, &fee.fee_opening)
+        );
+        assert!(fee.proof_data.verify_proof().is_ok());
+    }
+
+    #[test]
+    fn test_calculate_fee_with_proof_invalid_rate() {
+        let (_, transfer_opening) = Pedersen::new(55_u64);
+        assert_eq!(
+            calculate_fee_with_proof(55, &transfer_opening, 10_001, 3).err(),
+            Some(ProofGenerationError::FeeCalculation)
+        );
+    }
+
+    #[test]
+    fn test_fee_rate_parts_per_million() {
+        let rate = FeeRate::PartsPerMillion(1_500);
+        assert_eq!(rate.numerator(), 1_500);
+        assert_eq!(rate.denominator(), ONE_IN_PARTS_PER_MILLION);
+
+        // 0.15% of 1_000 is 1.5, rounded up to 2
+        assert_eq!(rate.calculate_fee(1_000, u128::MAX), Some((2, 500_000)));
+        assert_eq!(
+            FeeRate::PartsPerMillion(1_000_001).calculate_fee(1_000, u128::MAX),
+            None
+        );
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_calculate_wide_fee_with_proof() {
+        let (_, transfer_opening) = Pedersen::new(1_000_u64);
+        let fee = calculate_wide_fee_with_proof(
+            1_000,
+            &transfer_opening,
+            FeeRate::PartsPerMillion(1_500),
+            u64::MAX as u128 + 1,
+        )
+        .unwrap();
+
+        assert_eq!(fee.fee_amount, 2);
+        assert_eq!(fee.delta_amount, 500_000);
+        assert_eq!(
+            u128::from(fee.proof_data.context.max_value),
+            u64::MAX as u128 + 1
+        );
+        assert!(fee.proof_data.verify_proof().is_ok());
+
+        // capped fee
+        let fee = calculate_wide_fee_with_proof(
+            1_000,
+            &transfer_opening,
+            FeeRate::PartsPerMillion(1_500),
+            1,
+        )
+        .unwrap();
+        assert_eq!(fee.fee_amount, 1);
+        assert!(fee.proof_data.verify_proof().is_ok());
+    }
+}
@@ -19,12 +19,27 @@
 // `clippy::op_ref` is turned off to prevent clippy from warning that this is not idiomatic code.
 #![allow(clippy::arithmetic_side_effects, clippy::op_ref)]
 
+pub mod amount;
+pub mod armor;
+pub mod audit;
+pub mod cleanup;
+pub mod client;
+pub mod conformance;
 pub mod encryption;
 pub mod errors;
+pub mod fee;
+pub mod msm;
 #[doc(hidden)]
 mod range_proof;
+pub mod rng;
+pub mod session;
+pub mod sig;
 mod sigma_proofs;
+pub mod stream;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
 pub mod transcript;
+pub mod vectors;
 pub mod zk_elgamal_proof_program;
 
 /// Global transcript domain separator.
@@ -0,0 +1,147 @@
+//! Backends for the multiscalar multiplications in proof verification.
+//!
+//! The verification of a range proof is dominated by a single large multiscalar multiplication
+//! (MSM). Applications that verify proofs in bulk, such as re-verification of historical proofs,
+//! can supply a [`MultiscalarMulBackend`] to offload these multiplications to dedicated hardware.
+//! The default [`CpuMultiscalarMulBackend`] uses the curve25519-dalek implementation.
+//!
+//! A backend is passed explicitly to the verifiers that take one, such as
+//! [`CombinedProofVerifier::verify`](crate::zk_elgamal_proof_program::CombinedProofVerifier::verify)
+//! and [`verify_proof_batch_with_backend`](crate::zk_elgamal_proof_program::verify_proof_batch_with_backend).
+//! Verifiers that do not take a backend as an argument, such as the sigma proof verifiers and
+//! [`VerifyZkProof::verify_proof`](crate::zk_elgamal_proof_program::VerifyZkProof::verify_proof),
+//! use [`CpuMultiscalarMulBackend`].
+
+use {
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::VartimeMultiscalarMul},
+    std::borrow::Borrow,
+};
+
+/// A backend that computes multiscalar multiplications.
+///
+/// The inputs to a multiscalar multiplication in proof verification are public, so a backend
+/// need not run in constant time.
+pub trait MultiscalarMulBackend {
+    /// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`.
+    ///
+    /// The `scalars` and `points` slices are of the same length.
+    fn multiscalar_mul(&self, scalars: &[Scalar], points: &[RistrettoPoint]) -> RistrettoPoint;
+}
+
+/// The variable-time multiscalar multiplication backend that runs on the CPU.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuMultiscalarMulBackend;
+
+impl MultiscalarMulBackend for CpuMultiscalarMulBackend {
+    fn multiscalar_mul(&self, scalars: &[Scalar], points: &[RistrettoPoint]) -> RistrettoPoint {
+        RistrettoPoint::vartime_multiscalar_mul(scalars, points)
+    }
+}
+
+/// Computes a variable-time multiscalar multiplication on the CPU.
+///
+/// This is the multiscalar multiplication of the verifiers that do not take a backend.
+pub(crate) fn vartime_multiscalar_mul<I, J>(scalars: I, points: J) -> RistrettoPoint
+where
+    I: IntoIterator,
+    I::Item: Borrow<Scalar>,
+    J: IntoIterator,
+    J::Item: Borrow<RistrettoPoint>,
+{
+    RistrettoPoint::vartime_multiscalar_mul(scalars, points)
+}
+
+/// A buffer that collects the terms of a multiscalar multiplication.
+///
+/// Verifiers append the terms of their verification equations to the buffer, so that the
+/// equations of several proofs can be checked with a single multiscalar multiplication. The
+/// buffer writes into caller-provided storage and never allocates.
+pub struct MultiscalarMulBuffer<'a> {
+    scalars: &'a mut [Scalar],
+    points: &'a mut [RistrettoPoint],
+    len: usize,
+}
+
+impl<'a> MultiscalarMulBuffer<'a> {
+    /// Creates an empty buffer that writes into `scalars` and `points`.
+    ///
+    /// The capacity of the buffer is the length of the shorter slice.
+    pub fn new(scalars: &'a mut [Scalar], points: &'a mut [RistrettoPoint]) -> Self {
+        Self {
+            scalars,
+            points,
+            len: 0,
+        }
+    }
+
+    /// The number of terms in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no terms.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `n` terms to the buffer and returns their scalars and points for the caller to
+    /// fill in.
+    ///
+    /// Returns `None` if the buffer does not have room for `n` more terms.
+    pub(crate) fn reserve(&mut self, n: usize) -> Option<(&mut [Scalar], &mut [RistrettoPoint])> {
+        let end = self.len.checked_add(n)?;
+        if end > self.scalars.len() || end > self.points.len() {
+            return None;
+        }
+        let start = self.len;
+        self.len = end;
+        Some((&mut self.scalars[start..end], &mut self.points[start..end]))
+    }
+
+    /// Multiplies the scalars of all terms from index `start` onward by `weight`.
+    pub(crate) fn scale_from(&mut self, start: usize, weight: &Scalar) {
+        for scalar in &mut self.scalars[start..self.len] {
+            *scalar *= weight;
+        }
+    }
+
+    /// Computes the multiscalar multiplication of the terms in the buffer.
+    pub fn multiscalar_mul(&self, backend: &dyn MultiscalarMulBackend) -> RistrettoPoint {
+        backend.multiscalar_mul(&self.scalars[..self.len], &self.points[..self.len])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{encryption::elgamal::ElGamalKeypair, zk_elgamal_proof_program::*},
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    struct CountingBackend(AtomicUsize);
+
+    impl MultiscalarMulBackend for CountingBackend {
+        fn multiscalar_mul(&self, scalars: &[Scalar], points: &[RistrettoPoint]) -> RistrettoPoint {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            CpuMultiscalarMulBackend.multiscalar_mul(scalars, points)
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_batch_with_backend() {
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data = [
+            build_pubkey_validity_proof_data(&keypair).unwrap(),
+            build_pubkey_validity_proof_data(&ElGamalKeypair::new_rand()).unwrap(),
+        ];
+
+        let backend = CountingBackend(AtomicUsize::new(0));
+        verify_proof_batch_with_backend(&proof_data, &backend).unwrap();
+        assert_eq!(backend.0.load(Ordering::Relaxed), 1);
+
+        // the verifiers that do not take a backend do not use it
+        proof_data[0].verify_proof().unwrap();
+        assert_eq!(backend.0.load(Ordering::Relaxed), 1);
+    }
+}
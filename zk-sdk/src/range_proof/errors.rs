@@ -1,5 +1,8 @@
 //! Errors related to proving and verifying range proofs.
-use {crate::errors::TranscriptError, thiserror::Error};
+use {
+    crate::errors::{RngError, TranscriptError},
+    thiserror::Error,
+};
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum RangeProofGenerationError {
@@ -13,6 +16,8 @@ pub enum RangeProofGenerationError {
     GeneratorLengthMismatch,
     #[error("inner product length mismatch")]
     InnerProductLengthMismatch,
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
 }
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
@@ -43,4 +48,6 @@ pub enum RangeProofVerificationError {
 pub enum RangeProofGeneratorError {
     #[error("maximum generator length exceeded")]
     MaximumGeneratorLengthExceeded,
+    #[error("shared generators are already initialized")]
+    AlreadyInitialized,
 }
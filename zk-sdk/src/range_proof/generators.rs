@@ -7,6 +7,12 @@
 //! A key security feature of Bulletproofs is that these generators are created
 //! deterministically from a seed using a hash function (in this case, SHAKE256).
 //! This avoids the need for a trusted setup ceremony.
+//!
+//! Since the generators are deterministic, the first `n` generators of any set are the same
+//! regardless of its capacity. The range proof prover and verifier therefore share a single,
+//! lazily-initialized set of generators described by `BulletproofGens`. The capacity of the
+//! shared set can be configured with `configure_range_proof_generators` and it can be
+//! precomputed eagerly with `warm_up_range_proof_generators`.
 
 use {
     crate::range_proof::errors::RangeProofGeneratorError,
@@ -15,11 +21,24 @@ use {
         digest::{ExtendableOutput, Update, XofReader},
         Shake256, Shake256Reader,
     },
+    std::{borrow::Cow, sync::OnceLock},
 };
 
 /// The maximum number of generators that can be created.
 const MAX_GENERATOR_LENGTH: usize = u32::MAX as usize;
 
+/// The default number of bits per party of the shared generators.
+pub const DEFAULT_GENERATORS_BIT_CAPACITY: usize = 64;
+
+/// The default number of parties of the shared generators.
+///
+/// Together with `DEFAULT_GENERATORS_BIT_CAPACITY`, this covers the largest batched range proof
+/// instruction, which certifies a total of 256 bits.
+pub const DEFAULT_GENERATORS_PARTY_CAPACITY: usize = 4;
+
+/// The shared generators that are used by the range proof prover and verifier.
+static SHARED_GENERATORS: OnceLock<BulletproofGens> = OnceLock::new();
+
 /// A factory for creating an effectively infinite stream of generator points.
 ///
 /// `GeneratorsChain` is an iterator that produces `RistrettoPoint`s by hashing
@@ -134,6 +153,43 @@ impl RangeProofGens {
         Ok(())
     }
 
+    /// Creates the generators of the dalek-cryptography `bulletproofs` crate for an aggregated
+    /// range proof of `m` values of `n` bits each.
+    ///
+    /// The `bulletproofs` crate derives a separate chain of generators for each party from a label
+    /// that encodes the party index. The generators of the parties are concatenated in order.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    #[allow(non_snake_case)]
+    pub(crate) fn new_dalek_bulletproofs(
+        n: usize,
+        m: usize,
+    ) -> Result<Self, RangeProofGeneratorError> {
+        let gens_capacity = n
+            .checked_mul(m)
+            .filter(|capacity| *capacity <= MAX_GENERATOR_LENGTH)
+            .ok_or(RangeProofGeneratorError::MaximumGeneratorLengthExceeded)?;
+
+        let mut G_vec = Vec::with_capacity(gens_capacity);
+        let mut H_vec = Vec::with_capacity(gens_capacity);
+        for party_index in 0..m {
+            // the party index is less than `MAX_GENERATOR_LENGTH` and therefore, fits in a `u32`
+            let party_index = u32::try_from(party_index).unwrap();
+
+            let mut label = [b'G', 0, 0, 0, 0];
+            label[1..].copy_from_slice(&party_index.to_le_bytes());
+            G_vec.extend(GeneratorsChain::new(&label).take(n));
+
+            label[0] = b'H';
+            H_vec.extend(GeneratorsChain::new(&label).take(n));
+        }
+
+        Ok(Self {
+            gens_capacity,
+            G_vec,
+            H_vec,
+        })
+    }
+
     /// Returns an iterator over the first `n` **G** generators.
     #[allow(non_snake_case)]
     pub(crate) fn G(&self, n: usize) -> impl Iterator<Item = &RistrettoPoint> {
@@ -155,6 +211,113 @@ impl RangeProofGens {
     }
 }
 
+/// A lazily-initialized set of generators with a configurable capacity.
+///
+/// The capacity is specified as the number of bits per party and the number of parties that an
+/// aggregated range proof can support. The generators themselves are computed on first use or on
+/// an explicit call to [`BulletproofGens::warm_up`].
+pub struct BulletproofGens {
+    bit_capacity: usize,
+    party_capacity: usize,
+    gens: OnceLock<RangeProofGens>,
+}
+
+impl BulletproofGens {
+    /// Creates a new set of generators with the specified capacity without computing them.
+    pub const fn new(bit_capacity: usize, party_capacity: usize) -> Self {
+        Self {
+            bit_capacity,
+            party_capacity,
+            gens: OnceLock::new(),
+        }
+    }
+
+    /// The number of bits per party that the generators support.
+    pub fn bit_capacity(&self) -> usize {
+        self.bit_capacity
+    }
+
+    /// The number of parties that the generators support.
+    pub fn party_capacity(&self) -> usize {
+        self.party_capacity
+    }
+
+    /// The total number of **G** and **H** generators.
+    pub fn capacity(&self) -> Result<usize, RangeProofGeneratorError> {
+        self.bit_capacity
+            .checked_mul(self.party_capacity)
+            .filter(|capacity| *capacity <= MAX_GENERATOR_LENGTH)
+            .ok_or(RangeProofGeneratorError::MaximumGeneratorLengthExceeded)
+    }
+
+    /// Whether the generators are already computed.
+    pub fn is_initialized(&self) -> bool {
+        self.gens.get().is_some()
+    }
+
+    /// Computes the generators if they are not already computed.
+    pub fn warm_up(&self) -> Result<(), RangeProofGeneratorError> {
+        self.get_or_init().map(|_| ())
+    }
+
+    fn get_or_init(&self) -> Result<&RangeProofGens, RangeProofGeneratorError> {
+        if let Some(gens) = self.gens.get() {
+            return Ok(gens);
+        }
+        let gens = RangeProofGens::new(self.capacity()?)?;
+        Ok(self.gens.get_or_init(|| gens))
+    }
+
+    /// Returns a set of generators with capacity at least `n`.
+    ///
+    /// If `n` exceeds the configured capacity, a new set of generators is created for the
+    /// caller without modifying the shared set.
+    pub(crate) fn gens(
+        &self,
+        n: usize,
+    ) -> Result<Cow<'_, RangeProofGens>, RangeProofGeneratorError> {
+        if n <= self.capacity()? {
+            self.get_or_init().map(Cow::Borrowed)
+        } else {
+            RangeProofGens::new(n).map(Cow::Owned)
+        }
+    }
+}
+
+impl Default for BulletproofGens {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_GENERATORS_BIT_CAPACITY,
+            DEFAULT_GENERATORS_PARTY_CAPACITY,
+        )
+    }
+}
+
+/// Configures the shared generators that are used for range proof generation and verification.
+///
+/// This must be called before any range proof is generated or verified. Returns an error if the
+/// shared generators are already configured or in use.
+pub fn configure_range_proof_generators(
+    gens: BulletproofGens,
+) -> Result<(), RangeProofGeneratorError> {
+    gens.capacity()?;
+    SHARED_GENERATORS
+        .set(gens)
+        .map_err(|_| RangeProofGeneratorError::AlreadyInitialized)
+}
+
+/// Precomputes the shared generators so that the first range proof does not pay for their
+/// computation.
+pub fn warm_up_range_proof_generators() -> Result<(), RangeProofGeneratorError> {
+    shared_generators().warm_up()
+}
+
+/// Returns the shared generators, initializing them with the default capacity if they are not
+/// configured.
+pub(crate) fn shared_generators() -> &'static BulletproofGens {
+    SHARED_GENERATORS.get_or_init(BulletproofGens::default)
+}
+
 /// An iterator that provides a view into the first `n` elements of a generator vector.
 struct GensIter<'a> {
     array: &'a Vec<RistrettoPoint>,
@@ -180,3 +343,38 @@ impl<'a> Iterator for GensIter<'a> {
         (size, Some(size))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulletproof_gens_prefix_consistency() {
+        let gens = BulletproofGens::new(8, 2);
+        assert!(!gens.is_initialized());
+
+        gens.warm_up().unwrap();
+        assert!(gens.is_initialized());
+
+        let shared = gens.gens(16).unwrap();
+        assert!(matches!(shared, Cow::Borrowed(_)));
+
+        let fresh = RangeProofGens::new(8).unwrap();
+        assert!(shared.G(8).eq(fresh.G(8)));
+        assert!(shared.H(8).eq(fresh.H(8)));
+
+        // requests beyond the capacity fall back to a new set of generators
+        let extended = gens.gens(32).unwrap();
+        assert!(matches!(extended, Cow::Owned(_)));
+        assert!(extended.G(16).eq(shared.G(16)));
+    }
+
+    #[test]
+    fn test_bulletproof_gens_capacity_overflow() {
+        let gens = BulletproofGens::new(usize::MAX, 2);
+        assert_eq!(
+            gens.warm_up(),
+            Err(RangeProofGeneratorError::MaximumGeneratorLengthExceeded)
+        );
+    }
+}
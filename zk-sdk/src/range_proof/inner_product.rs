@@ -12,6 +12,7 @@ use {
     crate::{
         range_proof::{
             errors::{RangeProofGenerationError, RangeProofVerificationError},
+            range::RangeProofFormat,
             util,
         },
         transcript::TranscriptProtocol,
@@ -22,7 +23,6 @@ use {
         scalar::Scalar,
         traits::MultiscalarMul,
     },
-    merlin::Transcript,
     zeroize::Zeroize,
 };
 #[cfg(test)]
@@ -58,7 +58,8 @@ impl InnerProductProof {
         mut H_vec: Vec<RistrettoPoint>,
         mut a_vec: Vec<Scalar>,
         mut b_vec: Vec<Scalar>,
-        transcript: &mut Transcript,
+        format: RangeProofFormat,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<Self, RangeProofGenerationError> {
         // Create slices G, H, a, b backed by their respective
         // vectors.  This lets us reslice as we compress the lengths
@@ -86,7 +87,7 @@ impl InnerProductProof {
             return Err(RangeProofGenerationError::InvalidBitSize);
         }
 
-        transcript.inner_product_proof_domain_separator(n as u64);
+        format.inner_product_proof_domain_separator(transcript, n as u64);
 
         let lg_n = n.next_power_of_two().trailing_zeros() as usize;
         let mut L_vec = Vec::with_capacity(lg_n);
@@ -109,36 +110,42 @@ impl InnerProductProof {
                 .ok_or(RangeProofGenerationError::InnerProductLengthMismatch)?;
 
             // Compute L and R points for this round
-            // L = <a_L, G_R> + <b_R, H_L> + c_L * Q
-            let L = RistrettoPoint::multiscalar_mul(
-                a_L.iter()
-                    // `n` was previously divided in half and therefore, it cannot overflow.
-                    .zip(G_factors[n..n.checked_mul(2).unwrap()].iter())
-                    .map(|(a_L_i, g)| a_L_i * g)
-                    .chain(
-                        b_R.iter()
-                            .zip(H_factors[0..n].iter())
-                            .map(|(b_R_i, h)| b_R_i * h),
+            let (L, R) = util::join(
+                || {
+                    // L = <a_L, G_R> + <b_R, H_L> + c_L * Q
+                    RistrettoPoint::multiscalar_mul(
+                        a_L.iter()
+                            // `n` was previously divided in half and therefore, it cannot
+                            // overflow.
+                            .zip(G_factors[n..n.checked_mul(2).unwrap()].iter())
+                            .map(|(a_L_i, g)| a_L_i * g)
+                            .chain(
+                                b_R.iter()
+                                    .zip(H_factors[0..n].iter())
+                                    .map(|(b_R_i, h)| b_R_i * h),
+                            )
+                            .chain(iter::once(c_L)),
+                        G_R.iter().chain(H_L.iter()).chain(iter::once(Q)),
                     )
-                    .chain(iter::once(c_L)),
-                G_R.iter().chain(H_L.iter()).chain(iter::once(Q)),
-            )
-            .compress();
-
-            // R = <a_R, G_L> + <b_L, H_R> + c_R * Q
-            let R = RistrettoPoint::multiscalar_mul(
-                a_R.iter()
-                    .zip(G_factors[0..n].iter())
-                    .map(|(a_R_i, g)| a_R_i * g)
-                    .chain(
-                        b_L.iter()
-                            .zip(H_factors[n..n.checked_mul(2).unwrap()].iter())
-                            .map(|(b_L_i, h)| b_L_i * h),
+                    .compress()
+                },
+                || {
+                    // R = <a_R, G_L> + <b_L, H_R> + c_R * Q
+                    RistrettoPoint::multiscalar_mul(
+                        a_R.iter()
+                            .zip(G_factors[0..n].iter())
+                            .map(|(a_R_i, g)| a_R_i * g)
+                            .chain(
+                                b_L.iter()
+                                    .zip(H_factors[n..n.checked_mul(2).unwrap()].iter())
+                                    .map(|(b_L_i, h)| b_L_i * h),
+                            )
+                            .chain(iter::once(c_R)),
+                        G_L.iter().chain(H_R.iter()).chain(iter::once(Q)),
                     )
-                    .chain(iter::once(c_R)),
-                G_L.iter().chain(H_R.iter()).chain(iter::once(Q)),
-            )
-            .compress();
+                    .compress()
+                },
+            );
 
             L_vec.push(L);
             R_vec.push(R);
@@ -152,22 +159,34 @@ impl InnerProductProof {
             for i in 0..n {
                 a_L[i] = a_L[i] * u + u_inv * a_R[i];
                 b_L[i] = b_L[i] * u_inv + u * b_R[i];
-                G_L[i] = RistrettoPoint::multiscalar_mul(
-                    &[
-                        u_inv * G_factors[i],
-                        u * G_factors[n.checked_add(i).unwrap()],
-                    ],
-                    &[G_L[i], G_R[i]],
-                );
-                H_L[i] = RistrettoPoint::multiscalar_mul(
-                    &[
-                        u * H_factors[i],
-                        u_inv * H_factors[n.checked_add(i).unwrap()],
-                    ],
-                    &[H_L[i], H_R[i]],
-                )
             }
 
+            // The generators are folded independently of each other.
+            util::join(
+                || {
+                    for i in 0..n {
+                        G_L[i] = RistrettoPoint::multiscalar_mul(
+                            &[
+                                u_inv * G_factors[i],
+                                u * G_factors[n.checked_add(i).unwrap()],
+                            ],
+                            &[G_L[i], G_R[i]],
+                        );
+                    }
+                },
+                || {
+                    for i in 0..n {
+                        H_L[i] = RistrettoPoint::multiscalar_mul(
+                            &[
+                                u * H_factors[i],
+                                u_inv * H_factors[n.checked_add(i).unwrap()],
+                            ],
+                            &[H_L[i], H_R[i]],
+                        );
+                    }
+                },
+            );
+
             a = a_L;
             b = b_L;
             G = G_L;
@@ -189,19 +208,24 @@ impl InnerProductProof {
                 .ok_or(RangeProofGenerationError::InnerProductLengthMismatch)?;
 
             // Compute L and R points for this round
-            // L = <a_L, G_R> + <b_R, H_L> + c_L * Q
-            let L = RistrettoPoint::multiscalar_mul(
-                a_L.iter().chain(b_R.iter()).chain(iter::once(&c_L)),
-                G_R.iter().chain(H_L.iter()).chain(iter::once(Q)),
-            )
-            .compress();
-
-            // R = <a_R, G_L> + <b_L, H_R> + c_R * Q
-            let R = RistrettoPoint::multiscalar_mul(
-                a_R.iter().chain(b_L.iter()).chain(iter::once(&c_R)),
-                G_L.iter().chain(H_R.iter()).chain(iter::once(Q)),
-            )
-            .compress();
+            let (L, R) = util::join(
+                || {
+                    // L = <a_L, G_R> + <b_R, H_L> + c_L * Q
+                    RistrettoPoint::multiscalar_mul(
+                        a_L.iter().chain(b_R.iter()).chain(iter::once(&c_L)),
+                        G_R.iter().chain(H_L.iter()).chain(iter::once(Q)),
+                    )
+                    .compress()
+                },
+                || {
+                    // R = <a_R, G_L> + <b_L, H_R> + c_R * Q
+                    RistrettoPoint::multiscalar_mul(
+                        a_R.iter().chain(b_L.iter()).chain(iter::once(&c_R)),
+                        G_L.iter().chain(H_R.iter()).chain(iter::once(Q)),
+                    )
+                    .compress()
+                },
+            );
 
             L_vec.push(L);
             R_vec.push(R);
@@ -215,10 +239,21 @@ impl InnerProductProof {
             for i in 0..n {
                 a_L[i] = a_L[i] * u + u_inv * a_R[i];
                 b_L[i] = b_L[i] * u_inv + u * b_R[i];
-                G_L[i] = RistrettoPoint::multiscalar_mul(&[u_inv, u], &[G_L[i], G_R[i]]);
-                H_L[i] = RistrettoPoint::multiscalar_mul(&[u, u_inv], &[H_L[i], H_R[i]]);
             }
 
+            util::join(
+                || {
+                    for i in 0..n {
+                        G_L[i] = RistrettoPoint::multiscalar_mul(&[u_inv, u], &[G_L[i], G_R[i]]);
+                    }
+                },
+                || {
+                    for i in 0..n {
+                        H_L[i] = RistrettoPoint::multiscalar_mul(&[u, u_inv], &[H_L[i], H_R[i]]);
+                    }
+                },
+            );
+
             a = a_L;
             b = b_L;
             G = G_L;
@@ -243,12 +278,19 @@ impl InnerProductProof {
     /// This is a helper function for the verifier, implementing the logic from
     /// Section 3.1 of the paper. It computes the scalars `u_i^2`, `u_i^-2`, and `s_i`
     /// which are needed for the final, single multiscalar multiplication check.
-    #[allow(clippy::type_complexity)]
+    ///
+    /// The scalars are written into `challenges_sq`, `challenges_inv_sq`, and `s`, which must be
+    /// of length `log(n)`, `log(n)`, and `n`, so that the verifier can place them directly in
+    /// its multiscalar multiplication buffer.
     pub(crate) fn verification_scalars(
         &self,
         n: usize,
-        transcript: &mut Transcript,
-    ) -> Result<(Vec<Scalar>, Vec<Scalar>, Vec<Scalar>), RangeProofVerificationError> {
+        format: RangeProofFormat,
+        transcript: &mut impl TranscriptProtocol,
+        challenges_sq: &mut [Scalar],
+        challenges_inv_sq: &mut [Scalar],
+        s: &mut [Scalar],
+    ) -> Result<(), RangeProofVerificationError> {
         let lg_n = self.L_vec.len();
         if lg_n != self.R_vec.len() {
             return Err(RangeProofVerificationError::LRVectorLengthMismatch);
@@ -261,21 +303,39 @@ impl InnerProductProof {
         if n != (1_usize.checked_shl(lg_n as u32).unwrap()) {
             return Err(RangeProofVerificationError::InvalidBitSize);
         }
+        if challenges_sq.len() != lg_n || challenges_inv_sq.len() != lg_n || s.len() != n {
+            return Err(RangeProofVerificationError::VectorLengthMismatch);
+        }
 
-        transcript.inner_product_proof_domain_separator(n as u64);
+        format.inner_product_proof_domain_separator(transcript, n as u64);
 
         // 1. Recompute challenges `u_i` from the proof transcript (`x_i` in the paper).
-        let mut challenges = Vec::with_capacity(lg_n);
-        for (L, R) in self.L_vec.iter().zip(self.R_vec.iter()) {
+        let challenges = challenges_sq;
+        for ((L, R), challenge) in self
+            .L_vec
+            .iter()
+            .zip(self.R_vec.iter())
+            .zip(challenges.iter_mut())
+        {
             transcript.validate_and_append_point(b"L", L)?;
             transcript.validate_and_append_point(b"R", R)?;
-            challenges.push(transcript.challenge_scalar(b"u"));
+            *challenge = transcript.challenge_scalar(b"u");
         }
 
-        // 2. Compute `u_i^-1` for all `i`.
-        let mut challenges_inv = challenges.clone();
-        // This computes `(u_k * ... * u_1)^-1` and stores `u_i^-1` in `challenges_inv`.
-        let allinv = Scalar::batch_invert(&mut challenges_inv);
+        // 2. Compute `u_i^-1` for all `i` and `allinv = (u_k * ... * u_1)^-1` with a single
+        // inversion, first storing the prefix products `u_1 * ... * u_{i-1}` in `challenges_inv`.
+        let challenges_inv = challenges_inv_sq;
+        let mut acc = Scalar::ONE;
+        for (challenge, challenge_inv) in challenges.iter().zip(challenges_inv.iter_mut()) {
+            *challenge_inv = acc;
+            acc *= challenge;
+        }
+        let allinv = acc.invert();
+        let mut acc_inv = allinv;
+        for (challenge, challenge_inv) in challenges.iter().zip(challenges_inv.iter_mut()).rev() {
+            *challenge_inv *= acc_inv;
+            acc_inv *= challenge;
+        }
 
         // 3. Compute `u_i^2` and `u_i^-2` for all `i`.
         for i in 0..lg_n {
@@ -283,11 +343,9 @@ impl InnerProductProof {
             challenges_inv[i] = challenges_inv[i] * challenges_inv[i];
         }
         let challenges_sq = challenges;
-        let challenges_inv_sq = challenges_inv;
 
         // 4. Compute `s_i` values inductively, as described in Section 6.2 of the paper.
-        let mut s = Vec::with_capacity(n);
-        s.push(allinv);
+        s[0] = allinv;
         for i in 1..n {
             let lg_i = 31_u32.checked_sub((i as u32).leading_zeros()).unwrap() as usize;
             let k = 1_usize.checked_shl(lg_i as u32).unwrap();
@@ -297,10 +355,10 @@ impl InnerProductProof {
                 .checked_sub(1)
                 .and_then(|x| x.checked_sub(lg_i))
                 .unwrap()];
-            s.push(s[i - k] * u_lg_i_sq);
+            s[i] = s[i - k] * u_lg_i_sq;
         }
 
-        Ok((challenges_sq, challenges_inv_sq, s))
+        Ok(())
     }
 
     /// Verifies an inner product proof.
@@ -319,7 +377,7 @@ impl InnerProductProof {
         Q: &RistrettoPoint,
         G: &[RistrettoPoint],
         H: &[RistrettoPoint],
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), RangeProofVerificationError>
     where
         IG: IntoIterator,
@@ -327,7 +385,18 @@ impl InnerProductProof {
         IH: IntoIterator,
         IH::Item: Borrow<Scalar>,
     {
-        let (u_sq, u_inv_sq, s) = self.verification_scalars(n, transcript)?;
+        let lg_n = self.L_vec.len();
+        let mut u_sq = vec![Scalar::ZERO; lg_n];
+        let mut u_inv_sq = vec![Scalar::ZERO; lg_n];
+        let mut s = vec![Scalar::ZERO; n];
+        self.verification_scalars(
+            n,
+            RangeProofFormat::Native,
+            transcript,
+            &mut u_sq,
+            &mut u_inv_sq,
+            &mut s,
+        )?;
 
         let g_times_a_times_s = G_factors
             .into_iter()
@@ -401,14 +470,22 @@ impl InnerProductProof {
     /// - a scalar `a`
     /// - a scalar `b`
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(self.serialized_size());
+        let mut buf = vec![0; self.serialized_size()];
+        self.write_bytes(&mut buf);
+        buf
+    }
+
+    /// Serializes the proof into a buffer of length `serialized_size()` without allocating.
+    ///
+    /// The layout is the same as the layout of [`Self::to_bytes`].
+    pub fn write_bytes(&self, buf: &mut [u8]) {
+        let mut chunks = buf.chunks_mut(32);
         for (l, r) in self.L_vec.iter().zip(self.R_vec.iter()) {
-            buf.extend_from_slice(l.as_bytes());
-            buf.extend_from_slice(r.as_bytes());
+            chunks.next().unwrap().copy_from_slice(l.as_bytes());
+            chunks.next().unwrap().copy_from_slice(r.as_bytes());
         }
-        buf.extend_from_slice(self.a.as_bytes());
-        buf.extend_from_slice(self.b.as_bytes());
-        buf
+        chunks.next().unwrap().copy_from_slice(self.a.as_bytes());
+        chunks.next().unwrap().copy_from_slice(self.b.as_bytes());
     }
 
     /// Deserializes the proof from a byte slice.
@@ -468,7 +545,8 @@ impl InnerProductProof {
 #[cfg(test)]
 mod tests {
     use {
-        super::*, crate::range_proof::generators::RangeProofGens, rand::rngs::OsRng, sha3::Sha3_512,
+        super::*, crate::range_proof::generators::RangeProofGens, merlin::Transcript,
+        rand::rngs::OsRng, sha3::Sha3_512,
     };
 
     #[test]
@@ -516,6 +594,7 @@ mod tests {
             H.clone(),
             a.clone(),
             b.clone(),
+            RangeProofFormat::Native,
             &mut prover_transcript,
         )
         .unwrap();
@@ -1,22 +1,26 @@
 use {
     crate::{
-        encryption::pedersen::{Pedersen, PedersenCommitment, PedersenOpening, G, H},
+        encryption::pedersen::{
+            Pedersen, PedersenCommitment, PedersenGenerators, PedersenOpening, H,
+        },
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBackend, MultiscalarMulBuffer},
         range_proof::{
-            errors::{RangeProofGenerationError, RangeProofVerificationError},
-            generators::RangeProofGens,
+            errors::{
+                RangeProofGenerationError, RangeProofGeneratorError, RangeProofVerificationError,
+            },
+            generators::{shared_generators, RangeProofGens},
             inner_product::InnerProductProof,
             util,
         },
+        rng::{try_random_scalar, CryptoRngCore, DefaultRng},
         transcript::TranscriptProtocol,
     },
     core::iter,
     curve25519_dalek::{
-        ristretto::{CompressedRistretto, RistrettoPoint},
+        ristretto::{CompressedRistretto, RistrettoPoint, VartimeRistrettoPrecomputation},
         scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+        traits::{Identity, IsIdentity, MultiscalarMul, VartimePrecomputedMultiscalarMul},
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
     solana_zk_sdk_pod::{
         range_proof::{
             PodRangeProofU128, PodRangeProofU256, PodRangeProofU64, INNER_PRODUCT_PROOF_U128_LEN,
@@ -26,10 +30,19 @@ use {
         },
         UNIT_LEN,
     },
+    std::borrow::Cow,
     subtle::{Choice, ConditionallySelectable},
     zeroize::Zeroize,
 };
 
+/// The maximum bit length of a single component of a range proof.
+pub const MAX_SINGLE_BIT_LENGTH: usize = u128::BITS as usize;
+
+/// The bit lengths of the range proofs that are supported by the dalek-cryptography
+/// `bulletproofs` crate.
+#[cfg(feature = "dalek-bulletproofs-compat")]
+const DALEK_BULLETPROOFS_BIT_LENGTHS: [usize; 4] = [8, 16, 32, 64];
+
 /// A Bulletproofs range proof.
 #[allow(non_snake_case)]
 #[derive(Clone)]
@@ -66,15 +79,189 @@ impl RangeProof {
     /// the caller (the main protocol) must hash these public statement prior to invoking this
     /// constructor.
     ///
-    /// # Panics
-    /// This function will panic if the `openings` vector does not contain the same number
-    /// of elements as the `amounts` and `bit_lengths` vectors.
-    #[allow(clippy::many_single_char_names)]
+    /// Each bit length must be in the range `[1, 64]` and the bit lengths must sum to a power of
+    /// two. For bit lengths up to 128, use [`Self::new_u128`]. For bit lengths that do not sum to
+    /// a power of two, use [`Self::new_padded`].
     pub fn new(
         amounts: Vec<u64>,
         bit_lengths: Vec<usize>,
         openings: Vec<&PedersenOpening>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        Self::new_with_rng(amounts, bit_lengths, openings, transcript, &mut DefaultRng)
+    }
+
+    /// Creates an aggregated range proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RangeProofGenerationError> {
+        // each bit length must be greater than 0 for the proof to make sense
+        if bit_lengths
+            .iter()
+            .any(|bit_length| *bit_length == 0 || *bit_length > u64::BITS as usize)
+        {
+            return Err(RangeProofGenerationError::InvalidBitSize);
+        }
+
+        let amounts = amounts.into_iter().map(u128::from).collect();
+        Self::new_u128_with_rng(amounts, bit_lengths, openings, transcript, rng)
+    }
+
+    /// Creates an aggregated range proof for a set of 128-bit values.
+    ///
+    /// Each bit length must be in the range `[1, 128]` and the bit lengths must sum to a power of
+    /// two.
+    ///
+    /// WARNING: This function does *not* hash the public statement for the proof. For security,
+    /// the caller (the main protocol) must hash these public statement prior to invoking this
+    /// constructor.
+    pub fn new_u128(
+        amounts: Vec<u128>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        Self::new_u128_with_rng(amounts, bit_lengths, openings, transcript, &mut DefaultRng)
+    }
+
+    /// Creates an aggregated range proof for a set of 128-bit values using `rng` to generate the
+    /// random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new_u128`] for a description of the arguments.
+    pub fn new_u128_with_rng(
+        amounts: Vec<u128>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RangeProofGenerationError> {
+        Self::prove(
+            amounts,
+            bit_lengths,
+            openings,
+            &PedersenGenerators::default(),
+            RangeProofFormat::Native,
+            transcript,
+            rng,
+        )
+    }
+
+    /// Creates an aggregated range proof for a set of values that are committed with respect to
+    /// custom Pedersen generators.
+    ///
+    /// The generators are appended to the transcript, so the proof can only be verified with
+    /// [`Self::verify_with_generators`] under the same generators.
+    ///
+    /// WARNING: This function does *not* hash the public statement for the proof. For security,
+    /// the caller (the main protocol) must hash these public statement prior to invoking this
+    /// constructor.
+    pub fn new_with_generators(
+        amounts: Vec<u128>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        pedersen_generators: &PedersenGenerators,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        transcript.pedersen_generators_domain_separator(pedersen_generators);
+        Self::prove(
+            amounts,
+            bit_lengths,
+            openings,
+            pedersen_generators,
+            RangeProofFormat::Native,
+            transcript,
+            &mut DefaultRng,
+        )
+    }
+
+    /// Creates an aggregated range proof that hashes its public statement into the transcript.
+    ///
+    /// The commitments are derived from the amounts and openings, and are appended to the
+    /// transcript together with the bit lengths before the proof is generated. The proof must be
+    /// verified with [`Self::verify_hashed`].
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_hashed(
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        if bit_lengths.len() != amounts.len() || openings.len() != amounts.len() {
+            return Err(RangeProofGenerationError::VectorLengthMismatch);
+        }
+
+        let comms: Vec<_> = amounts
+            .iter()
+            .zip(&openings)
+            .map(|(amount, opening)| Pedersen::with(*amount, opening))
+            .collect();
+        Self::hash_statement_into_transcript(&comms, &bit_lengths, transcript);
+
+        Self::new(amounts, bit_lengths, openings, transcript)
+    }
+
+    /// Creates an aggregated range proof in the format of the dalek-cryptography `bulletproofs`
+    /// crate.
+    ///
+    /// The transcript and the generators of the proof match those of `RangeProof::prove_multiple`
+    /// in the `bulletproofs` crate with the default `PedersenGens` and `BulletproofGens`, so the
+    /// proof can be verified by that crate under an identically initialized transcript. The
+    /// commitments to the amounts are appended to the transcript before the proof is generated.
+    ///
+    /// As in the `bulletproofs` crate, all amounts have the same bit length, which must be 8, 16,
+    /// 32, or 64, and the number of amounts must be a power of two.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    pub fn new_dalek_bulletproofs(
+        amounts: Vec<u64>,
+        bit_length: usize,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        let m = amounts.len();
+        if openings.len() != m || !m.is_power_of_two() {
+            return Err(RangeProofGenerationError::VectorLengthMismatch);
+        }
+        if !DALEK_BULLETPROOFS_BIT_LENGTHS.contains(&bit_length) {
+            return Err(RangeProofGenerationError::InvalidBitSize);
+        }
+
+        transcript.dalek_range_proof_domain_separator(bit_length as u64, m as u64);
+        for (amount, opening) in amounts.iter().zip(&openings) {
+            let comm = Pedersen::with(*amount, opening);
+            transcript.append_point(b"V", &comm.get_point().compress());
+        }
+
+        Self::prove(
+            amounts.into_iter().map(u128::from).collect(),
+            vec![bit_length; m],
+            openings,
+            &PedersenGenerators::default(),
+            RangeProofFormat::DalekBulletproofs,
+            transcript,
+            &mut DefaultRng,
+        )
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    fn prove(
+        amounts: Vec<u128>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        pedersen_generators: &PedersenGenerators,
+        format: RangeProofFormat,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
     ) -> Result<Self, RangeProofGenerationError> {
         // 1. Validate inputs
         let m = amounts.len();
@@ -85,7 +272,7 @@ impl RangeProof {
         // each bit length must be greater than 0 for the proof to make sense
         if bit_lengths
             .iter()
-            .any(|bit_length| *bit_length == 0 || *bit_length > u64::BITS as usize)
+            .any(|bit_length| *bit_length == 0 || *bit_length > MAX_SINGLE_BIT_LENGTH)
         {
             return Err(RangeProofGenerationError::InvalidBitSize);
         }
@@ -96,45 +283,62 @@ impl RangeProof {
             return Err(RangeProofGenerationError::VectorLengthMismatch);
         }
 
-        let bp_gens = RangeProofGens::new(nm)
+        let bp_gens = format
+            .generators(&bit_lengths)
             .map_err(|_| RangeProofGenerationError::MaximumGeneratorLengthExceeded)?;
 
-        transcript.range_proof_domain_separator(nm as u64);
+        if format == RangeProofFormat::Native {
+            transcript.range_proof_domain_separator(nm as u64);
+        }
 
         // 2. Create commitments A and S.
-        // A is a commitment to the bit-vectors a_L and a_R
-        let mut a_blinding = Scalar::random(&mut OsRng);
-        let mut A = a_blinding * &(*H);
-
-        let mut gens_iter = bp_gens.G(nm).zip(bp_gens.H(nm));
-        for (amount_i, n_i) in amounts.iter().zip(bit_lengths.iter()) {
-            for j in 0..(*n_i) {
-                let (G_ij, H_ij) = gens_iter.next().unwrap();
-
-                // `j` is guaranteed to be at most `u64::BITS` (a 6-bit number) and therefore,
-                // casting is lossless and right shift can be safely unwrapped
-                let v_ij = Choice::from((amount_i.checked_shr(j as u32).unwrap() & 1) as u8);
-                let mut point = -H_ij;
-                // Add G_ij if bit is 1, else do nothing (since a_R = a_L - 1)
-                point.conditional_assign(G_ij, v_ij);
-                A += point;
-            }
-        }
-        let A = A.compress();
+        let mut a_blinding = try_random_scalar(rng)?;
 
         // generate blinding factors and generate their Pedersen vector commitment
-        let mut s_L: Vec<Scalar> = (0..nm).map(|_| Scalar::random(&mut OsRng)).collect();
-        let mut s_R: Vec<Scalar> = (0..nm).map(|_| Scalar::random(&mut OsRng)).collect();
+        let mut s_L = (0..nm)
+            .map(|_| try_random_scalar(rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut s_R = (0..nm)
+            .map(|_| try_random_scalar(rng))
+            .collect::<Result<Vec<_>, _>>()?;
 
         // generate blinding factor for Pedersen commitment; `s_blinding` should not to be confused
         // with blinding factors for the actual inner product vector
-        let mut s_blinding = Scalar::random(&mut OsRng);
-
-        let S = RistrettoPoint::multiscalar_mul(
-            iter::once(&s_blinding).chain(s_L.iter()).chain(s_R.iter()),
-            iter::once(&(*H)).chain(bp_gens.G(nm)).chain(bp_gens.H(nm)),
-        )
-        .compress();
+        let mut s_blinding = try_random_scalar(rng)?;
+
+        let bp_gens = &*bp_gens;
+        let (A, S) = util::join(
+            || {
+                // A is a commitment to the bit-vectors a_L and a_R
+                let mut A = a_blinding * pedersen_generators.H();
+
+                let mut gens_iter = bp_gens.G(nm).zip(bp_gens.H(nm));
+                for (amount_i, n_i) in amounts.iter().zip(bit_lengths.iter()) {
+                    for j in 0..(*n_i) {
+                        let (G_ij, H_ij) = gens_iter.next().unwrap();
+
+                        // `j` is guaranteed to be less than `u128::BITS` (a 7-bit number) and
+                        // therefore, casting is lossless and right shift can be safely unwrapped
+                        let v_ij =
+                            Choice::from((amount_i.checked_shr(j as u32).unwrap() & 1) as u8);
+                        let mut point = -H_ij;
+                        // Add G_ij if bit is 1, else do nothing (since a_R = a_L - 1)
+                        point.conditional_assign(G_ij, v_ij);
+                        A += point;
+                    }
+                }
+                A.compress()
+            },
+            || {
+                RistrettoPoint::multiscalar_mul(
+                    iter::once(&s_blinding).chain(s_L.iter()).chain(s_R.iter()),
+                    iter::once(pedersen_generators.H())
+                        .chain(bp_gens.G(nm))
+                        .chain(bp_gens.H(nm)),
+                )
+                .compress()
+            },
+        );
 
         // 3. Derive challenges y and z.
         transcript.append_point(b"A", &A);
@@ -158,7 +362,7 @@ impl RangeProof {
             let mut exp_2 = Scalar::ONE;
 
             for j in 0..(*n_i) {
-                // `j` is guaranteed to be at most `u64::BITS` (a 6-bit number) and therefore,
+                // `j` is guaranteed to be less than `u128::BITS` (a 7-bit number) and therefore,
                 // casting is lossless and right shift can be safely unwrapped
                 let a_L_j = Scalar::from(amount_i.checked_shr(j as u32).unwrap() & 1);
                 let a_R_j = a_L_j - Scalar::ONE;
@@ -183,8 +387,8 @@ impl RangeProof {
             .ok_or(RangeProofGenerationError::InnerProductLengthMismatch)?;
 
         // 6. Commit to the t_1 and t_2 coefficients of t(x).
-        let (T_1, t_1_blinding) = Pedersen::new(t_poly.1);
-        let (T_2, t_2_blinding) = Pedersen::new(t_poly.2);
+        let (T_1, t_1_blinding) = pedersen_generators.commit(t_poly.1);
+        let (T_2, t_2_blinding) = pedersen_generators.commit(t_poly.2);
 
         let T_1 = T_1.get_point().compress();
         let T_2 = T_2.get_point().compress();
@@ -227,13 +431,15 @@ impl RangeProof {
         // compute the inner product argument on the commitment:
         // P = <l(x), G> + <r(x), H'> + <l(x), r(x)>*Q
         let w = transcript.challenge_scalar(b"w");
-        let Q = w * &G;
+        let Q = w * pedersen_generators.G();
 
         let G_factors: Vec<Scalar> = iter::repeat_n(Scalar::ONE, nm).collect();
         let H_factors: Vec<Scalar> = util::exp_iter(y.invert()).take(nm).collect();
 
         // compute challenge `c` for consistency with the verifier
-        let _c = transcript.challenge_scalar(b"c");
+        if format == RangeProofFormat::Native {
+            let _c = transcript.challenge_scalar(b"c");
+        }
 
         let ipp_proof = InnerProductProof::new(
             &Q,
@@ -243,13 +449,16 @@ impl RangeProof {
             bp_gens.H(nm).cloned().collect(),
             l_vec,
             r_vec,
+            format,
             transcript,
         )?;
 
         // compute challenge `d` for consistency with the verifier
-        transcript.append_scalar(b"ipp_a", &ipp_proof.a);
-        transcript.append_scalar(b"ipp_b", &ipp_proof.b);
-        let _d = transcript.challenge_scalar(b"d");
+        if format == RangeProofFormat::Native {
+            transcript.append_scalar(b"ipp_a", &ipp_proof.a);
+            transcript.append_scalar(b"ipp_b", &ipp_proof.b);
+            let _d = transcript.challenge_scalar(b"d");
+        }
 
         a_blinding.zeroize();
         s_blinding.zeroize();
@@ -273,14 +482,254 @@ impl RangeProof {
     /// This function implements the verifier's logic, which is optimized into a
     /// single large multiscalar multiplication (`mega_check`) for efficiency. This
     /// check simultaneously verifies all aspects of the proof.
-    #[allow(clippy::many_single_char_names)]
     pub fn verify(
         &self,
         comms: Vec<&PedersenCommitment>,
         bit_lengths: Vec<usize>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        self.verify_with_backend(comms, bit_lengths, &CpuMultiscalarMulBackend, transcript)
+    }
+
+    /// Verifies an aggregated range proof that was generated with [`Self::new_hashed`].
+    ///
+    /// The commitments and bit lengths are appended to the transcript before the proof is
+    /// verified.
+    pub fn verify_hashed(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        if bit_lengths.len() != comms.len() {
+            return Err(RangeProofVerificationError::VectorLengthMismatch);
+        }
+
+        let owned_comms: Vec<_> = comms.iter().map(|comm| **comm).collect();
+        Self::hash_statement_into_transcript(&owned_comms, &bit_lengths, transcript);
+
+        self.verify(comms, bit_lengths, transcript)
+    }
+
+    fn hash_statement_into_transcript(
+        comms: &[PedersenCommitment],
+        bit_lengths: &[usize],
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        transcript.range_proof_statement_domain_separator(comms.len() as u64);
+        for (comm, bit_length) in comms.iter().zip(bit_lengths) {
+            transcript.append_message(b"commitment", &comm.to_bytes());
+            transcript.append_u64(b"bit-length", *bit_length as u64);
+        }
+    }
+
+    /// Verifies an aggregated range proof, computing the final multiscalar multiplication with
+    /// the specified backend.
+    pub fn verify_with_backend(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        backend: &dyn MultiscalarMulBackend,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), RangeProofVerificationError> {
-        // 1. Validate inputs and reconstruct challenges from the transcript.
+        self.verify_against(
+            comms,
+            bit_lengths,
+            &PedersenGenerators::default(),
+            RangeProofFormat::Native,
+            backend,
+            transcript,
+        )
+    }
+
+    /// Verifies an aggregated range proof that was generated with [`Self::new_with_generators`].
+    pub fn verify_with_generators(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        pedersen_generators: &PedersenGenerators,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        self.verify_with_generators_and_backend(
+            comms,
+            bit_lengths,
+            pedersen_generators,
+            &CpuMultiscalarMulBackend,
+            transcript,
+        )
+    }
+
+    /// Verifies an aggregated range proof that was generated with [`Self::new_with_generators`],
+    /// computing the final multiscalar multiplication with the specified backend.
+    pub fn verify_with_generators_and_backend(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        pedersen_generators: &PedersenGenerators,
+        backend: &dyn MultiscalarMulBackend,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        transcript.pedersen_generators_domain_separator(pedersen_generators);
+        self.verify_against(
+            comms,
+            bit_lengths,
+            pedersen_generators,
+            RangeProofFormat::Native,
+            backend,
+            transcript,
+        )
+    }
+
+    /// Verifies an aggregated range proof with a [`RangeProofVerifierContext`].
+    ///
+    /// The bit lengths of the proof are those of the context. The multiscalar multiplication is
+    /// computed with the precomputed tables of the context, so the configured
+    /// [`MultiscalarMulBackend`] is not used.
+    pub fn verify_with_context(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        context: &RangeProofVerifierContext,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        if let Some(pedersen_generators) = &context.pedersen_generators {
+            transcript.pedersen_generators_domain_separator(pedersen_generators);
+        }
+
+        let nm = Self::validate_statement(&comms, &context.bit_lengths)?;
+        let m = comms.len();
+        let lg_n = nm.trailing_zeros() as usize;
+
+        let mut scalars = vec![Scalar::ZERO; Self::verification_terms(nm, m)];
+        self.verification_scalars(
+            &context.bit_lengths,
+            RangeProofFormat::Native,
+            transcript,
+            &mut scalars,
+        )?;
+
+        // The scalars of the Pedersen and Bulletproofs generators are the static terms of the
+        // verification equation, and all other scalars are the dynamic terms.
+        let (proof_scalars, scalars) = scalars.split_at(4);
+        let (pedersen_scalars, scalars) = scalars.split_at(2);
+        let (ipp_scalars, scalars) = scalars.split_at(2 * lg_n);
+        let (gens_scalars, value_commitment_scalars) = scalars.split_at(2 * nm);
+
+        let dynamic_points = [
+            self.A.decompress(),
+            self.S.decompress(),
+            self.T_1.decompress(),
+            self.T_2.decompress(),
+        ]
+        .into_iter()
+        .chain(self.ipp_proof.L_vec.iter().map(|L| L.decompress()))
+        .chain(self.ipp_proof.R_vec.iter().map(|R| R.decompress()))
+        .chain(comms.iter().map(|V| Some(*V.get_point())))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(RangeProofVerificationError::MultiscalarMul)?;
+
+        let mega_check = context.precomputation.vartime_mixed_multiscalar_mul(
+            pedersen_scalars.iter().chain(gens_scalars),
+            proof_scalars
+                .iter()
+                .chain(ipp_scalars)
+                .chain(value_commitment_scalars),
+            dynamic_points,
+        );
+
+        if mega_check.is_identity() {
+            Ok(())
+        } else {
+            Err(RangeProofVerificationError::AlgebraicRelation)
+        }
+    }
+
+    /// Verifies an aggregated range proof in the format of the dalek-cryptography `bulletproofs`
+    /// crate.
+    ///
+    /// This is the counterpart of `RangeProof::verify_multiple` in the `bulletproofs` crate, and
+    /// it accepts proofs that were generated by that crate or by [`Self::new_dalek_bulletproofs`].
+    /// The commitments are appended to the transcript before the proof is verified.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    pub fn verify_dalek_bulletproofs(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_length: usize,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        let m = comms.len();
+        if !m.is_power_of_two() {
+            return Err(RangeProofVerificationError::VectorLengthMismatch);
+        }
+        if !DALEK_BULLETPROOFS_BIT_LENGTHS.contains(&bit_length) {
+            return Err(RangeProofVerificationError::InvalidBitSize);
+        }
+
+        transcript.dalek_range_proof_domain_separator(bit_length as u64, m as u64);
+        for comm in &comms {
+            transcript.append_point(b"V", &comm.get_point().compress());
+        }
+
+        self.verify_against(
+            comms,
+            vec![bit_length; m],
+            &PedersenGenerators::default(),
+            RangeProofFormat::DalekBulletproofs,
+            &CpuMultiscalarMulBackend,
+            transcript,
+        )
+    }
+
+    fn verify_against(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+        pedersen_generators: &PedersenGenerators,
+        format: RangeProofFormat,
+        backend: &dyn MultiscalarMulBackend,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        let nm = Self::validate_statement(&comms, &bit_lengths)?;
+        let num_terms = Self::verification_terms(nm, comms.len());
+
+        let mut scalars = vec![Scalar::ZERO; num_terms];
+        let mut points = vec![RistrettoPoint::identity(); num_terms];
+        let mut buffer = MultiscalarMulBuffer::new(&mut scalars, &mut points);
+        self.append_verification_terms_with_format(
+            &comms,
+            &bit_lengths,
+            pedersen_generators,
+            format,
+            transcript,
+            &mut buffer,
+        )?;
+
+        // Perform the final "mega-check"
+        // This single multiscalar multiplication verifies all relations simultaneously.
+        let mega_check = buffer.multiscalar_mul(backend);
+
+        if mega_check.is_identity() {
+            Ok(())
+        } else {
+            Err(RangeProofVerificationError::AlgebraicRelation)
+        }
+    }
+
+    /// The number of multiscalar multiplication terms in the verification equation of a range
+    /// proof with `num_commitments` commitments whose bit lengths sum to `batched_bit_length`.
+    pub(crate) const fn verification_terms(
+        batched_bit_length: usize,
+        num_commitments: usize,
+    ) -> usize {
+        let lg_n = batched_bit_length.trailing_zeros() as usize;
+        6 + 2 * lg_n + 2 * batched_bit_length + num_commitments
+    }
+
+    /// Checks that the commitments and bit lengths form a valid statement and returns the sum of
+    /// the bit lengths.
+    fn validate_statement(
+        comms: &[&PedersenCommitment],
+        bit_lengths: &[usize],
+    ) -> Result<usize, RangeProofVerificationError> {
         if comms.len() != bit_lengths.len() {
             return Err(RangeProofVerificationError::VectorLengthMismatch);
         }
@@ -290,16 +739,107 @@ impl RangeProof {
             return Err(RangeProofVerificationError::AlgebraicRelation);
         }
 
-        let m = bit_lengths.len();
         let nm: usize = bit_lengths.iter().sum();
         if !nm.is_power_of_two() {
             return Err(RangeProofVerificationError::InvalidBitSize);
         }
 
-        let bp_gens = RangeProofGens::new(nm)
+        if bit_lengths
+            .iter()
+            .any(|bit_length| *bit_length > MAX_SINGLE_BIT_LENGTH)
+        {
+            return Err(RangeProofVerificationError::InvalidBitSize);
+        }
+
+        Ok(nm)
+    }
+
+    /// Appends the terms of the verification equation to a multiscalar multiplication buffer.
+    ///
+    /// The verification equation combines all relations of the proof into a single large
+    /// multiscalar multiplication (`mega_check`). The proof is valid if and only if the appended
+    /// terms sum to the identity. The scalars are computed in place in the buffer, so this
+    /// function does not allocate.
+    pub(crate) fn append_verification_terms(
+        &self,
+        comms: &[&PedersenCommitment],
+        bit_lengths: &[usize],
+        pedersen_generators: &PedersenGenerators,
+        transcript: &mut impl TranscriptProtocol,
+        buffer: &mut MultiscalarMulBuffer,
+    ) -> Result<(), RangeProofVerificationError> {
+        self.append_verification_terms_with_format(
+            comms,
+            bit_lengths,
+            pedersen_generators,
+            RangeProofFormat::Native,
+            transcript,
+            buffer,
+        )
+    }
+
+    fn append_verification_terms_with_format(
+        &self,
+        comms: &[&PedersenCommitment],
+        bit_lengths: &[usize],
+        pedersen_generators: &PedersenGenerators,
+        format: RangeProofFormat,
+        transcript: &mut impl TranscriptProtocol,
+        buffer: &mut MultiscalarMulBuffer,
+    ) -> Result<(), RangeProofVerificationError> {
+        let nm = Self::validate_statement(comms, bit_lengths)?;
+
+        let bp_gens = format
+            .generators(bit_lengths)
             .map_err(|_| RangeProofVerificationError::MaximumGeneratorLengthExceeded)?;
 
-        transcript.range_proof_domain_separator(nm as u64);
+        let (scalars, points) = buffer
+            .reserve(Self::verification_terms(nm, comms.len()))
+            .ok_or(RangeProofVerificationError::MultiscalarMul)?;
+        self.verification_scalars(bit_lengths, format, transcript, scalars)?;
+
+        let mega_check_points = iter::once(self.A.decompress())
+            .chain(iter::once(self.S.decompress()))
+            .chain(iter::once(self.T_1.decompress()))
+            .chain(iter::once(self.T_2.decompress()))
+            .chain(iter::once(Some(*pedersen_generators.H())))
+            .chain(iter::once(Some(*pedersen_generators.G())))
+            .chain(self.ipp_proof.L_vec.iter().map(|L| L.decompress()))
+            .chain(self.ipp_proof.R_vec.iter().map(|R| R.decompress()))
+            .chain(bp_gens.G(nm).map(|&x| Some(x)))
+            .chain(bp_gens.H(nm).map(|&x| Some(x)))
+            .chain(comms.iter().map(|V| Some(*V.get_point())));
+        for (point, mega_check_point) in points.iter_mut().zip(mega_check_points) {
+            *point = mega_check_point.ok_or(RangeProofVerificationError::MultiscalarMul)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reconstructs the challenges of the proof from the transcript and computes the scalars of
+    /// the verification equation.
+    ///
+    /// The scalars are written to `scalars` in the layout
+    /// `A, S, T_1, T_2, H, G, L_vec, R_vec, G_vec, H_vec, comms`. The bit lengths must already be
+    /// validated with [`Self::validate_statement`].
+    #[allow(clippy::many_single_char_names)]
+    fn verification_scalars(
+        &self,
+        bit_lengths: &[usize],
+        format: RangeProofFormat,
+        transcript: &mut impl TranscriptProtocol,
+        scalars: &mut [Scalar],
+    ) -> Result<(), RangeProofVerificationError> {
+        // 1. Reconstruct challenges from the transcript.
+        let nm: usize = bit_lengths.iter().sum();
+        let m = bit_lengths.len();
+        if scalars.len() != Self::verification_terms(nm, m) {
+            return Err(RangeProofVerificationError::MultiscalarMul);
+        }
+
+        if format == RangeProofFormat::Native {
+            transcript.range_proof_domain_separator(nm as u64);
+        }
 
         // append proof data to transcript and derive appropriate challenge scalars
         transcript.validate_and_append_point(b"A", &self.A)?;
@@ -324,78 +864,127 @@ impl RangeProof {
 
         // The challenge `c` is a legacy component from an older implementation.
         // It is now unused, but is kept here for backward compatibility.
-        let _c = transcript.challenge_scalar(b"c");
+        if format == RangeProofFormat::Native {
+            let _c = transcript.challenge_scalar(b"c");
+        }
 
-        // 2. Compute the scalars for the verification equation.
-        let (x_sq, x_inv_sq, s) = self.ipp_proof.verification_scalars(nm, transcript)?;
-        let s_inv = s.iter().rev();
+        let lg_n = nm.trailing_zeros() as usize;
+        let (fixed_scalars, scalars) = scalars.split_at_mut(6);
+        let (x_sq, scalars) = scalars.split_at_mut(lg_n);
+        let (x_inv_sq, scalars) = scalars.split_at_mut(lg_n);
+        let (gs, scalars) = scalars.split_at_mut(nm);
+        let (hs, value_commitment_scalars) = scalars.split_at_mut(nm);
+
+        // 2. Compute the scalars for the verification equation. The inner product proof
+        // scalars `s` are written to the `G_vec` scalars and are then updated in place.
+        self.ipp_proof
+            .verification_scalars(nm, format, transcript, x_sq, x_inv_sq, gs)?;
 
         let a = self.ipp_proof.a;
         let b = self.ipp_proof.b;
 
-        transcript.append_scalar(b"ipp_a", &a);
-        transcript.append_scalar(b"ipp_b", &b);
-
         // Challenge for batching the main algebraic relation checks
-        let d = transcript.challenge_scalar(b"d");
+        let d = match format {
+            RangeProofFormat::Native => {
+                transcript.append_scalar(b"ipp_a", &a);
+                transcript.append_scalar(b"ipp_b", &b);
+                transcript.batching_weight(b"d")
+            }
+            // The `bulletproofs` crate samples the batching weight at random instead of
+            // deriving it from the transcript.
+            #[cfg(feature = "dalek-bulletproofs-compat")]
+            RangeProofFormat::DalekBulletproofs => crate::rng::verifier_weight(),
+        };
 
         // 3. Construct the scalars for the single large multiscalar multiplication.
 
-        // This vector is used in the `h` terms of the final check.
+        // This sequence is used in the `h` terms of the final check.
         // It's a concatenation of powers-of-2 vectors, each scaled by a power of z.
         // Formula: z^0*2^n_0 || z^1*2^n_1 || ... || z^{m-1}*2^n_{m-1}
-        let concat_z_and_2: Vec<Scalar> = util::exp_iter(z)
+        let concat_z_and_2 = util::exp_iter(z)
             .zip(bit_lengths.iter())
             .flat_map(|(exp_z, n_i)| {
                 util::exp_iter(Scalar::from(2u64))
                     .take(*n_i)
                     .map(move |exp_2| exp_2 * exp_z)
-            })
-            .collect();
+            });
 
-        let gs = s.iter().map(|s_i| minus_z - a * s_i);
-        let hs = s_inv
+        // 1/s[i] is s[!i], and !i runs from nm-1 to 0 as i runs from 0 to nm-1
+        for (((h_i, s_i_inv), exp_y_inv), z_and_2) in hs
+            .iter_mut()
+            .zip(gs.iter().rev())
             .zip(util::exp_iter(y.invert()))
-            .zip(concat_z_and_2.iter())
-            .map(|((s_i_inv, exp_y_inv), z_and_2)| z + exp_y_inv * (zz * z_and_2 - b * s_i_inv));
+            .zip(concat_z_and_2)
+        {
+            *h_i = z + exp_y_inv * (zz * z_and_2 - b * s_i_inv);
+        }
+        for g_i in gs.iter_mut() {
+            *g_i = minus_z - a * *g_i;
+        }
 
-        let basepoint_scalar =
-            w * (self.t_x - a * b) + d * (delta(&bit_lengths, &y, &z) - self.t_x);
-        let value_commitment_scalars = util::exp_iter(z).take(m).map(|z_exp| d * zz * z_exp);
+        let basepoint_scalar = w * (self.t_x - a * b) + d * (delta(bit_lengths, &y, &z) - self.t_x);
+        fixed_scalars.copy_from_slice(&[
+            Scalar::ONE,
+            x,
+            d * x,
+            d * x * x,
+            -self.e_blinding - d * self.t_x_blinding,
+            basepoint_scalar,
+        ]);
+        for (value_commitment_scalar, z_exp) in
+            value_commitment_scalars.iter_mut().zip(util::exp_iter(z))
+        {
+            *value_commitment_scalar = d * zz * z_exp;
+        }
 
-        // 4. Perform the final "mega-check"
-        // This single multiscalar multiplication verifies all relations simultaneously.
-        let mega_check = RistrettoPoint::optional_multiscalar_mul(
-            iter::once(Scalar::ONE)
-                .chain(iter::once(x))
-                .chain(iter::once(d * x))
-                .chain(iter::once(d * x * x))
-                .chain(iter::once(-self.e_blinding - d * self.t_x_blinding))
-                .chain(iter::once(basepoint_scalar))
-                .chain(x_sq.iter().cloned())
-                .chain(x_inv_sq.iter().cloned())
-                .chain(gs)
-                .chain(hs)
-                .chain(value_commitment_scalars),
-            iter::once(self.A.decompress())
-                .chain(iter::once(self.S.decompress()))
-                .chain(iter::once(self.T_1.decompress()))
-                .chain(iter::once(self.T_2.decompress()))
-                .chain(iter::once(Some(*H)))
-                .chain(iter::once(Some(G)))
-                .chain(self.ipp_proof.L_vec.iter().map(|L| L.decompress()))
-                .chain(self.ipp_proof.R_vec.iter().map(|R| R.decompress()))
-                .chain(bp_gens.G(nm).map(|&x| Some(x)))
-                .chain(bp_gens.H(nm).map(|&x| Some(x)))
-                .chain(comms.iter().map(|V| Some(*V.get_point()))),
-        )
-        .ok_or(RangeProofVerificationError::MultiscalarMul)?;
+        Ok(())
+    }
 
-        if mega_check.is_identity() {
-            Ok(())
-        } else {
-            Err(RangeProofVerificationError::AlgebraicRelation)
-        }
+    /// Creates an aggregated range proof for bit lengths that do not necessarily sum to a power
+    /// of two.
+    ///
+    /// If the bit lengths do not sum to a power of two, the proof is padded with additional
+    /// components that encode the value zero with respect to the fixed padding commitment
+    /// [`padding_commitment`]. The verifier reconstructs the same padding with
+    /// [`Self::verify_padded`], so the padding components do not need to be communicated.
+    pub fn new_padded(
+        mut amounts: Vec<u128>,
+        mut bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, RangeProofGenerationError> {
+        let padding = padding_bit_lengths(&bit_lengths)
+            .ok_or(RangeProofGenerationError::MaximumGeneratorLengthExceeded)?;
+        let padding_opening = padding_opening();
+
+        amounts.extend(iter::repeat_n(0, padding.len()));
+        let openings = openings
+            .into_iter()
+            .chain(iter::repeat_n(&padding_opening, padding.len()))
+            .collect();
+        bit_lengths.extend(padding);
+
+        Self::new_u128(amounts, bit_lengths, openings, transcript)
+    }
+
+    /// Verifies an aggregated range proof that was generated with [`Self::new_padded`].
+    pub fn verify_padded(
+        &self,
+        comms: Vec<&PedersenCommitment>,
+        mut bit_lengths: Vec<usize>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), RangeProofVerificationError> {
+        let padding = padding_bit_lengths(&bit_lengths)
+            .ok_or(RangeProofVerificationError::MaximumGeneratorLengthExceeded)?;
+        let padding_commitment = padding_commitment();
+
+        let comms = comms
+            .into_iter()
+            .chain(iter::repeat_n(&padding_commitment, padding.len()))
+            .collect();
+        bit_lengths.extend(padding);
+
+        self.verify(comms, bit_lengths, transcript)
     }
 
     // Following the dalek rangeproof library signature for now. The exact method signature can be
@@ -453,6 +1042,155 @@ impl RangeProof {
     }
 }
 
+/// The transcript format of a range proof.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum RangeProofFormat {
+    /// The transcript format of this crate.
+    Native,
+    /// The transcript format of the dalek-cryptography `bulletproofs` crate.
+    ///
+    /// In this format, the range proof domain separator and the value commitments are appended
+    /// to the transcript by the caller, and the proof uses the per-party generators of the
+    /// `bulletproofs` crate.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    DalekBulletproofs,
+}
+
+impl RangeProofFormat {
+    /// Returns the Bulletproofs generators for a range proof with the specified bit lengths.
+    fn generators(
+        self,
+        bit_lengths: &[usize],
+    ) -> Result<Cow<'static, RangeProofGens>, RangeProofGeneratorError> {
+        match self {
+            Self::Native => shared_generators().gens(bit_lengths.iter().sum()),
+            #[cfg(feature = "dalek-bulletproofs-compat")]
+            Self::DalekBulletproofs => RangeProofGens::new_dalek_bulletproofs(
+                bit_lengths.first().copied().unwrap_or_default(),
+                bit_lengths.len(),
+            )
+            .map(Cow::Owned),
+        }
+    }
+
+    /// Appends the domain separator of a length-`n` inner product proof to the transcript.
+    pub(crate) fn inner_product_proof_domain_separator(
+        self,
+        transcript: &mut impl TranscriptProtocol,
+        n: u64,
+    ) {
+        match self {
+            Self::Native => transcript.inner_product_proof_domain_separator(n),
+            #[cfg(feature = "dalek-bulletproofs-compat")]
+            Self::DalekBulletproofs => transcript.dalek_inner_product_proof_domain_separator(n),
+        }
+    }
+}
+
+/// Precomputed data for verifying range proofs with a fixed bit-length configuration.
+///
+/// All range proofs with the same bit lengths share the Pedersen and Bulletproofs generator terms
+/// of their verification equations. The context precomputes multiplication tables for these
+/// generators once, so that each verification with the context only needs to compute the terms
+/// that depend on the proof itself. This is useful for verifiers that
+/// check many proofs of the same shape.
+pub struct RangeProofVerifierContext {
+    bit_lengths: Vec<usize>,
+    pedersen_generators: Option<PedersenGenerators>,
+    precomputation: VartimeRistrettoPrecomputation,
+}
+
+impl RangeProofVerifierContext {
+    /// Creates a context for range proofs with the specified bit lengths.
+    pub fn new(bit_lengths: Vec<usize>) -> Result<Self, RangeProofVerificationError> {
+        Self::new_internal(bit_lengths, None)
+    }
+
+    /// Creates a context for range proofs with the specified bit lengths that were generated
+    /// with respect to custom Pedersen generators.
+    pub fn new_with_generators(
+        bit_lengths: Vec<usize>,
+        pedersen_generators: &PedersenGenerators,
+    ) -> Result<Self, RangeProofVerificationError> {
+        Self::new_internal(bit_lengths, Some(*pedersen_generators))
+    }
+
+    fn new_internal(
+        bit_lengths: Vec<usize>,
+        pedersen_generators: Option<PedersenGenerators>,
+    ) -> Result<Self, RangeProofVerificationError> {
+        if bit_lengths.is_empty() || bit_lengths.contains(&0) {
+            return Err(RangeProofVerificationError::InvalidBitSize);
+        }
+        let nm = bit_lengths
+            .iter()
+            .try_fold(0_usize, |acc, &x| acc.checked_add(x))
+            .ok_or(RangeProofVerificationError::InvalidBitSize)?;
+        if !nm.is_power_of_two()
+            || bit_lengths
+                .iter()
+                .any(|bit_length| *bit_length > MAX_SINGLE_BIT_LENGTH)
+        {
+            return Err(RangeProofVerificationError::InvalidBitSize);
+        }
+
+        let bp_gens = shared_generators()
+            .gens(nm)
+            .map_err(|_| RangeProofVerificationError::MaximumGeneratorLengthExceeded)?;
+        let pedersen_bases = pedersen_generators.unwrap_or_default();
+
+        let static_points = [*pedersen_bases.H(), *pedersen_bases.G()]
+            .into_iter()
+            .chain(bp_gens.G(nm).copied())
+            .chain(bp_gens.H(nm).copied());
+        let precomputation = VartimeRistrettoPrecomputation::new(static_points);
+
+        Ok(Self {
+            bit_lengths,
+            pedersen_generators,
+            precomputation,
+        })
+    }
+
+    /// The bit lengths of the range proofs that can be verified with the context.
+    pub fn bit_lengths(&self) -> &[usize] {
+        &self.bit_lengths
+    }
+}
+
+/// Computes the bit lengths of the padding components that are needed for the bit lengths to sum
+/// to a power of two.
+///
+/// Each padding component has a bit length of at most `MAX_SINGLE_BIT_LENGTH`. Returns `None` if
+/// the padded length overflows.
+pub fn padding_bit_lengths(bit_lengths: &[usize]) -> Option<Vec<usize>> {
+    let nm = bit_lengths
+        .iter()
+        .try_fold(0_usize, |acc, bit_length| acc.checked_add(*bit_length))?;
+    let mut remaining = nm.checked_next_power_of_two()? - nm;
+
+    let mut padding = Vec::new();
+    while remaining > 0 {
+        let bit_length = remaining.min(MAX_SINGLE_BIT_LENGTH);
+        padding.push(bit_length);
+        remaining -= bit_length;
+    }
+    Some(padding)
+}
+
+/// The Pedersen opening of a padding component in a padded range proof.
+pub fn padding_opening() -> PedersenOpening {
+    PedersenOpening::new(Scalar::ONE)
+}
+
+/// The Pedersen commitment of a padding component in a padded range proof.
+///
+/// The commitment encodes the value zero with the opening one, which is the point `H`. Unlike the
+/// identity point, this commitment is accepted by the range proof verifier.
+pub fn padding_commitment() -> PedersenCommitment {
+    PedersenCommitment::new(*H)
+}
+
 /// Computes the `delta(y,z)` term for the verification equation.
 ///
 /// This term is a function of the challenges `y` and `z` and the proof dimensions.
@@ -476,14 +1214,23 @@ impl TryFrom<RangeProof> for PodRangeProofU64 {
     type Error = RangeProofVerificationError;
 
     fn try_from(decoded_proof: RangeProof) -> Result<Self, Self::Error> {
+        Self::try_from(&decoded_proof)
+    }
+}
+
+impl TryFrom<&RangeProof> for PodRangeProofU64 {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(decoded_proof: &RangeProof) -> Result<Self, Self::Error> {
         if decoded_proof.ipp_proof.serialized_size() != INNER_PRODUCT_PROOF_U64_LEN {
             return Err(RangeProofVerificationError::Deserialization);
         }
 
         let mut buf = [0_u8; RANGE_PROOF_U64_LEN];
-        copy_range_proof_modulo_inner_product_proof(&decoded_proof, &mut buf);
-        buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U64_LEN]
-            .copy_from_slice(&decoded_proof.ipp_proof.to_bytes());
+        copy_range_proof_modulo_inner_product_proof(decoded_proof, &mut buf);
+        decoded_proof
+            .ipp_proof
+            .write_bytes(&mut buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U64_LEN]);
         Ok(PodRangeProofU64(buf))
     }
 }
@@ -496,18 +1243,35 @@ impl TryFrom<PodRangeProofU64> for RangeProof {
     }
 }
 
+impl TryFrom<&PodRangeProofU64> for RangeProof {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(pod_proof: &PodRangeProofU64) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 impl TryFrom<RangeProof> for PodRangeProofU128 {
     type Error = RangeProofVerificationError;
 
     fn try_from(decoded_proof: RangeProof) -> Result<Self, Self::Error> {
+        Self::try_from(&decoded_proof)
+    }
+}
+
+impl TryFrom<&RangeProof> for PodRangeProofU128 {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(decoded_proof: &RangeProof) -> Result<Self, Self::Error> {
         if decoded_proof.ipp_proof.serialized_size() != INNER_PRODUCT_PROOF_U128_LEN {
             return Err(RangeProofVerificationError::Deserialization);
         }
 
         let mut buf = [0_u8; RANGE_PROOF_U128_LEN];
-        copy_range_proof_modulo_inner_product_proof(&decoded_proof, &mut buf);
-        buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U128_LEN]
-            .copy_from_slice(&decoded_proof.ipp_proof.to_bytes());
+        copy_range_proof_modulo_inner_product_proof(decoded_proof, &mut buf);
+        decoded_proof.ipp_proof.write_bytes(
+            &mut buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U128_LEN],
+        );
         Ok(PodRangeProofU128(buf))
     }
 }
@@ -520,18 +1284,35 @@ impl TryFrom<PodRangeProofU128> for RangeProof {
     }
 }
 
+impl TryFrom<&PodRangeProofU128> for RangeProof {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(pod_proof: &PodRangeProofU128) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 impl TryFrom<RangeProof> for PodRangeProofU256 {
     type Error = RangeProofVerificationError;
 
     fn try_from(decoded_proof: RangeProof) -> Result<Self, Self::Error> {
+        Self::try_from(&decoded_proof)
+    }
+}
+
+impl TryFrom<&RangeProof> for PodRangeProofU256 {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(decoded_proof: &RangeProof) -> Result<Self, Self::Error> {
         if decoded_proof.ipp_proof.serialized_size() != INNER_PRODUCT_PROOF_U256_LEN {
             return Err(RangeProofVerificationError::Deserialization);
         }
 
         let mut buf = [0_u8; RANGE_PROOF_U256_LEN];
-        copy_range_proof_modulo_inner_product_proof(&decoded_proof, &mut buf);
-        buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U256_LEN]
-            .copy_from_slice(&decoded_proof.ipp_proof.to_bytes());
+        copy_range_proof_modulo_inner_product_proof(decoded_proof, &mut buf);
+        decoded_proof.ipp_proof.write_bytes(
+            &mut buf[RANGE_PROOF_MODULO_INNER_PRODUCT_PROOF_LEN..RANGE_PROOF_U256_LEN],
+        );
         Ok(PodRangeProofU256(buf))
     }
 }
@@ -544,6 +1325,14 @@ impl TryFrom<PodRangeProofU256> for RangeProof {
     }
 }
 
+impl TryFrom<&PodRangeProofU256> for RangeProof {
+    type Error = RangeProofVerificationError;
+
+    fn try_from(pod_proof: &PodRangeProofU256) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 fn copy_range_proof_modulo_inner_product_proof(proof: &RangeProof, buf: &mut [u8]) {
     let mut chunks = buf.chunks_mut(UNIT_LEN);
     chunks.next().unwrap().copy_from_slice(proof.A.as_bytes());
@@ -565,12 +1354,45 @@ fn copy_range_proof_modulo_inner_product_proof(proof: &RangeProof, buf: &mut [u8
 mod tests {
     use {
         super::*,
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::pedersen::PodPedersenCommitment, range_proof::PodRangeProofU128,
         },
         std::str::FromStr,
     };
 
+    #[test]
+    fn test_hashed_rangeproof() {
+        let (comm_1, open_1) = Pedersen::new(55_u64);
+        let (comm_2, open_2) = Pedersen::new(77_u64);
+
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let proof = RangeProof::new_hashed(
+            vec![55, 77],
+            vec![32, 32],
+            vec![&open_1, &open_2],
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify_hashed(vec![&comm_1, &comm_2], vec![32, 32], &mut transcript_verify)
+            .is_ok());
+
+        // the statement is bound to the proof, so reordering the commitments fails
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify_hashed(vec![&comm_2, &comm_1], vec![32, 32], &mut transcript_verify)
+            .is_err());
+
+        // the proof does not verify without hashing the statement
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify(vec![&comm_1, &comm_2], vec![32, 32], &mut transcript_verify)
+            .is_err());
+    }
+
     #[test]
     fn test_single_rangeproof() {
         let (comm, open) = Pedersen::new(55_u64);
@@ -591,6 +1413,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_range_proof_pod_conversion() {
+        let (_, opening) = Pedersen::new(55_u64);
+        let mut transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+        let proof = RangeProof::new(vec![55], vec![64], vec![&opening], &mut transcript).unwrap();
+
+        let pod_proof = PodRangeProofU64::try_from(&proof).unwrap();
+        assert_eq!(pod_proof.0.as_slice(), proof.to_bytes().as_slice());
+
+        let decoded_proof = RangeProof::try_from(pod_proof).unwrap();
+        assert_eq!(decoded_proof.to_bytes(), proof.to_bytes());
+
+        // the length of the proof must match the pod type
+        assert_eq!(
+            PodRangeProofU128::try_from(&proof).unwrap_err(),
+            RangeProofVerificationError::Deserialization
+        );
+    }
+
     #[test]
     fn test_aggregated_rangeproof() {
         let (comm_1, open_1) = Pedersen::new(55_u64);
@@ -712,4 +1553,245 @@ mod tests {
             transcript_verify.challenge_scalar(b"test"),
         );
     }
+
+    #[test]
+    fn test_rangeproof_u128_bit_lengths() {
+        let amount_1: u128 = (1 << 96) - 1;
+        let amount_2: u128 = 255;
+        let amount_3: u128 = 1 << 20;
+
+        let (comm_1, open_1) = Pedersen::new(amount_1);
+        let (comm_2, open_2) = Pedersen::new(amount_2);
+        let (comm_3, open_3) = Pedersen::new(amount_3);
+
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = RangeProof::new_u128(
+            vec![amount_1, amount_2, amount_3],
+            vec![96, 8, 24],
+            vec![&open_1, &open_2, &open_3],
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        proof
+            .verify(
+                vec![&comm_1, &comm_2, &comm_3],
+                vec![96, 8, 24],
+                &mut transcript_verify,
+            )
+            .unwrap();
+
+        // amount does not fit in the claimed bit length
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = RangeProof::new_u128(
+            vec![amount_1, amount_2],
+            vec![64, 64],
+            vec![&open_1, &open_2],
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        assert!(proof
+            .verify(vec![&comm_1, &comm_2], vec![64, 64], &mut transcript_verify)
+            .is_err());
+
+        // bit lengths above 128 are rejected
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert_eq!(
+            RangeProof::new_u128(
+                vec![amount_1, amount_2],
+                vec![192, 64],
+                vec![&open_1, &open_2],
+                &mut transcript_create,
+            )
+            .err(),
+            Some(RangeProofGenerationError::InvalidBitSize)
+        );
+
+        // bit lengths above 64 are rejected for 64-bit amounts
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert_eq!(
+            RangeProof::new(
+                vec![255, 255],
+                vec![96, 32],
+                vec![&open_1, &open_2],
+                &mut transcript_create,
+            )
+            .err(),
+            Some(RangeProofGenerationError::InvalidBitSize)
+        );
+    }
+
+    #[test]
+    fn test_padding_bit_lengths() {
+        assert_eq!(padding_bit_lengths(&[64]), Some(vec![]));
+        assert_eq!(padding_bit_lengths(&[8]), Some(vec![]));
+        assert_eq!(padding_bit_lengths(&[8, 96]), Some(vec![24]));
+        assert_eq!(padding_bit_lengths(&[3, 5, 7]), Some(vec![1]));
+        assert_eq!(padding_bit_lengths(&[96, 96, 96]), Some(vec![128, 96]));
+        assert_eq!(padding_bit_lengths(&[usize::MAX, 1]), None);
+    }
+
+    #[test]
+    fn test_padded_rangeproof() {
+        let amount_1: u128 = 200;
+        let amount_2: u128 = 1 << 90;
+
+        let (comm_1, open_1) = Pedersen::new(amount_1);
+        let (comm_2, open_2) = Pedersen::new(amount_2);
+
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        // 8 + 96 = 104 is padded to 128
+        let proof = RangeProof::new_padded(
+            vec![amount_1, amount_2],
+            vec![8, 96],
+            vec![&open_1, &open_2],
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        proof
+            .verify_padded(vec![&comm_1, &comm_2], vec![8, 96], &mut transcript_verify)
+            .unwrap();
+
+        // the padding is equivalent to explicitly providing the padding commitment
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        let padding = padding_commitment();
+        proof
+            .verify(
+                vec![&comm_1, &comm_2, &padding],
+                vec![8, 96, 24],
+                &mut transcript_verify,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rangeproof_with_generators() {
+        let generators = PedersenGenerators::from_label(b"Test protocol");
+        let (comm_1, open_1) = generators.commit(55_u64);
+        let (comm_2, open_2) = generators.commit(77_u64);
+
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = RangeProof::new_with_generators(
+            vec![55, 77],
+            vec![64, 64],
+            vec![&open_1, &open_2],
+            &generators,
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        proof
+            .verify_with_generators(
+                vec![&comm_1, &comm_2],
+                vec![64, 64],
+                &generators,
+                &mut transcript_verify,
+            )
+            .unwrap();
+
+        // the proof does not verify with respect to different generators
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify_with_generators(
+                vec![&comm_1, &comm_2],
+                vec![64, 64],
+                &PedersenGenerators::from_label(b"Other protocol"),
+                &mut transcript_verify,
+            )
+            .is_err());
+
+        // the proof does not verify as a proof with respect to the global generators
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify(vec![&comm_1, &comm_2], vec![64, 64], &mut transcript_verify)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rangeproof_with_context() {
+        let context = RangeProofVerifierContext::new(vec![64, 32, 32]).unwrap();
+
+        // the context is reused across the verification of several proofs
+        for amounts in [[55, 77, 99], [u64::MAX, u32::MAX.into(), 0]] {
+            let (comms, opens): (Vec<_>, Vec<_>) =
+                amounts.iter().map(|amount| Pedersen::new(*amount)).unzip();
+
+            let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+            let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+
+            let proof = RangeProof::new(
+                amounts.to_vec(),
+                vec![64, 32, 32],
+                opens.iter().collect(),
+                &mut transcript_create,
+            )
+            .unwrap();
+
+            proof
+                .verify_with_context(comms.iter().collect(), &context, &mut transcript_verify)
+                .unwrap();
+
+            assert_eq!(
+                transcript_create.challenge_scalar(b"test"),
+                transcript_verify.challenge_scalar(b"test"),
+            );
+
+            // the proof does not verify with a context for different bit lengths
+            let other_context = RangeProofVerifierContext::new(vec![32, 64, 32]).unwrap();
+            let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+            assert_eq!(
+                proof
+                    .verify_with_context(
+                        comms.iter().collect(),
+                        &other_context,
+                        &mut transcript_verify
+                    )
+                    .unwrap_err(),
+                RangeProofVerificationError::AlgebraicRelation
+            );
+        }
+
+        // the proof with custom generators only verifies with a context for the same generators
+        let generators = PedersenGenerators::from_label(b"Test protocol");
+        let (comm_1, open_1) = generators.commit(55_u64);
+        let (comm_2, open_2) = generators.commit(77_u64);
+
+        let mut transcript_create = Transcript::new_zk_elgamal_transcript(b"Test");
+        let proof = RangeProof::new_with_generators(
+            vec![55, 77],
+            vec![64, 64],
+            vec![&open_1, &open_2],
+            &generators,
+            &mut transcript_create,
+        )
+        .unwrap();
+
+        let context =
+            RangeProofVerifierContext::new_with_generators(vec![64, 64], &generators).unwrap();
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        proof
+            .verify_with_context(vec![&comm_1, &comm_2], &context, &mut transcript_verify)
+            .unwrap();
+
+        let context = RangeProofVerifierContext::new(vec![64, 64]).unwrap();
+        let mut transcript_verify = Transcript::new_zk_elgamal_transcript(b"Test");
+        assert!(proof
+            .verify_with_context(vec![&comm_1, &comm_2], &context, &mut transcript_verify)
+            .is_err());
+
+        // invalid bit length configurations are rejected
+        assert!(RangeProofVerifierContext::new(vec![]).is_err());
+        assert!(RangeProofVerifierContext::new(vec![0, 64]).is_err());
+        assert!(RangeProofVerifierContext::new(vec![32, 64]).is_err());
+    }
 }
@@ -123,6 +123,36 @@ pub fn read32(data: &[u8]) -> [u8; 32] {
     buf32
 }
 
+/// Runs two closures, potentially in parallel, and returns both results.
+///
+/// With the `parallel` feature, `op_b` is executed on a scoped thread while `op_a` is executed on
+/// the current thread. Otherwise, the closures are executed sequentially.
+pub fn join<A, B, RA, RB>(op_a: A, op_b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        std::thread::scope(|scope| {
+            let handle_b = scope.spawn(op_b);
+            let result_a = op_a();
+            let result_b = match handle_b.join() {
+                Ok(result_b) => result_b,
+                Err(panic) => std::panic::resume_unwind(panic),
+            };
+            (result_a, result_b)
+        })
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        (op_a(), op_b())
+    }
+}
+
 /// Computes the inner product of two vectors of scalars.
 ///
 /// The inner product is defined as:
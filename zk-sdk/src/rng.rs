@@ -0,0 +1,328 @@
+//! Random number generation for the randomized constructors.
+//!
+//! Every randomized constructor in this crate has a `new_with_rng` counterpart that takes a
+//! cryptographically secure random number generator. The `new_with_rng` constructors return an
+//! [`RngError`] if the generator fails to produce random bytes. The constructors without an
+//! explicit generator use [`DefaultRng`], which draws from `OsRng` and panics on failure. With the
+//! `test-rng` feature, the hidden `insecure_with_seeded_rng` replaces `OsRng` with a seeded ChaCha
+//! generator so that the generated proofs are byte-reproducible in tests.
+//!
+//! On targets where the `getrandom` backend of `OsRng` is unavailable, such as enclaves or
+//! bare-metal signers, a custom entropy source can be registered with
+//! [`register_entropy_source`]. With the `custom-entropy` feature, [`DefaultRng`] never falls back
+//! to `OsRng` and panics if no entropy source is registered.
+//!
+//! The random weights with which verifiers combine verification equations are drawn from the
+//! registered entropy source, or from `OsRng` if none is registered, just like [`DefaultRng`].
+//! Unlike the randomized constructors, they are never drawn from the seeded generator of
+//! `insecure_with_seeded_rng`, so they stay unpredictable to the prover.
+
+#[cfg(not(feature = "custom-entropy"))]
+use rand::rngs::OsRng;
+use {
+    crate::errors::RngError,
+    curve25519_dalek::scalar::Scalar,
+    rand::{CryptoRng, Error, RngCore},
+    std::sync::OnceLock,
+    zeroize::Zeroizing,
+};
+#[cfg(feature = "test-rng")]
+use {rand::SeedableRng, rand_chacha::ChaCha20Rng, std::cell::RefCell};
+
+/// A cryptographically secure random number generator.
+pub trait CryptoRngCore: RngCore + CryptoRng {}
+
+impl<T: RngCore + CryptoRng> CryptoRngCore for T {}
+
+/// A function that fills the given buffer with cryptographically secure random bytes.
+pub type EntropySource = fn(&mut [u8]) -> Result<(), Error>;
+
+static ENTROPY_SOURCE: OnceLock<EntropySource> = OnceLock::new();
+
+/// Registers `source` as the entropy source of [`DefaultRng`] and of the verifier weights in
+/// place of `OsRng`.
+///
+/// The entropy source can be registered only once per process. If an entropy source is already
+/// registered, the registered source is left in place and `source` is returned as an error.
+pub fn register_entropy_source(source: EntropySource) -> Result<(), EntropySource> {
+    ENTROPY_SOURCE.set(source)
+}
+
+/// An adapter that exposes an [`EntropySource`] as a random number generator.
+struct EntropySourceRng(EntropySource);
+
+impl RngCore for EntropySourceRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = self.try_fill_bytes(dest) {
+            panic!("Error: {err}");
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        (self.0)(dest)
+    }
+}
+
+#[cfg(feature = "test-rng")]
+thread_local! {
+    static SEEDED_RNG: RefCell<Option<ChaCha20Rng>> = const { RefCell::new(None) };
+}
+
+/// The generator used by the constructors that do not take an explicit generator.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultRng;
+
+impl DefaultRng {
+    fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        #[cfg(feature = "test-rng")]
+        if let Some(mut rng) = SEEDED_RNG.with(|cell| cell.borrow_mut().take()) {
+            let result = f(&mut rng);
+            SEEDED_RNG.with(|cell| *cell.borrow_mut() = Some(rng));
+            return result;
+        }
+
+        with_entropy_source(f)
+    }
+}
+
+/// Runs `f` with the registered entropy source, falling back to `OsRng` if none is registered.
+///
+/// With the `custom-entropy` feature, this panics if no entropy source is registered.
+fn with_entropy_source<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    match ENTROPY_SOURCE.get() {
+        Some(source) => f(&mut EntropySourceRng(*source)),
+        #[cfg(not(feature = "custom-entropy"))]
+        None => f(&mut OsRng),
+        #[cfg(feature = "custom-entropy")]
+        None => panic!("no entropy source is registered"),
+    }
+}
+
+impl RngCore for DefaultRng {
+    fn next_u32(&mut self) -> u32 {
+        Self::with_rng(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Self::with_rng(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        Self::with_rng(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        Self::with_rng(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+impl CryptoRng for DefaultRng {}
+
+/// Unwraps the result of a constructor that was run with [`DefaultRng`].
+///
+/// Like `OsRng`, the default generator panics if it fails to produce random bytes.
+pub(crate) fn unwrap_default_rng<T>(result: Result<T, RngError>) -> T {
+    result.unwrap_or_else(|err| panic!("Error: {err}"))
+}
+
+/// Samples a uniformly random scalar, returning an error if `rng` fails.
+///
+/// The scalar is sampled exactly as `Scalar::random`, so a seeded generator produces the same
+/// scalars with either function.
+pub(crate) fn try_random_scalar(rng: &mut impl CryptoRngCore) -> Result<Scalar, RngError> {
+    let mut scalar_bytes = Zeroizing::new([0u8; 64]);
+    rng.try_fill_bytes(scalar_bytes.as_mut())?;
+    Ok(Scalar::from_bytes_mod_order_wide(&scalar_bytes))
+}
+
+/// Samples a weight that combines the verification equations of a verifier.
+///
+/// The weight is drawn from the same entropy source as [`DefaultRng`], but the weights must be
+/// unpredictable to the prover, so a seeded generator never replaces the entropy source for them.
+pub(crate) fn verifier_weight() -> Scalar {
+    with_entropy_source(|rng| {
+        let mut scalar_bytes = [0u8; 64];
+        rng.fill_bytes(&mut scalar_bytes);
+        Scalar::from_bytes_mod_order_wide(&scalar_bytes)
+    })
+}
+
+/// Runs `f` with every constructor on the current thread that does not take an explicit
+/// generator driven by a ChaCha generator seeded with `seed`.
+///
+/// Two calls with the same seed that perform the same sequence of operations produce identical
+/// keys, openings, and proofs. The seed determines every secret generated inside `f`, so this
+/// must only be used in tests.
+#[cfg(feature = "test-rng")]
+#[doc(hidden)]
+pub fn insecure_with_seeded_rng<T>(seed: [u8; 32], f: impl FnOnce() -> T) -> T {
+    struct RestoreRng(Option<ChaCha20Rng>);
+
+    impl Drop for RestoreRng {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SEEDED_RNG.with(|cell| *cell.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SEEDED_RNG.with(|cell| cell.replace(Some(ChaCha20Rng::from_seed(seed))));
+    let _restore = RestoreRng(previous);
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "test-rng")]
+    use crate::zk_elgamal_proof_program::{
+        build_batched_range_proof_u64_data, build_ciphertext_commitment_equality_proof_data,
+        VerifyZkProof,
+    };
+    use {
+        super::*,
+        crate::{
+            encryption::{
+                elgamal::ElGamalKeypair,
+                pedersen::{Pedersen, PedersenOpening},
+            },
+            range_proof::{errors::RangeProofGenerationError, range::RangeProof},
+            sigma_proofs::{
+                percentage_with_cap_split::PercentageWithCapSplitProof,
+                pubkey_validity::PubkeyValidityProof,
+            },
+        },
+        merlin::Transcript,
+        std::num::NonZeroU32,
+    };
+
+    const FAILING_RNG_CODE: u32 = Error::CUSTOM_START + 7;
+
+    /// A generator whose entropy source is always unavailable.
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            panic!("infallible generator methods must not be used")
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            panic!("infallible generator methods must not be used")
+        }
+
+        fn fill_bytes(&mut self, _dest: &mut [u8]) {
+            panic!("infallible generator methods must not be used")
+        }
+
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+            Err(NonZeroU32::new(FAILING_RNG_CODE).unwrap().into())
+        }
+    }
+
+    impl CryptoRng for FailingRng {}
+
+    #[test]
+    fn test_rng_failure_is_returned() {
+        let expected_code = NonZeroU32::new(FAILING_RNG_CODE);
+
+        let err = ElGamalKeypair::new_rand_with_rng(&mut FailingRng).unwrap_err();
+        assert_eq!(err.code(), expected_code);
+
+        let err = PedersenOpening::new_rand_with_rng(&mut FailingRng).unwrap_err();
+        assert_eq!(err.code(), expected_code);
+
+        let keypair = ElGamalKeypair::new_rand();
+        let result = PubkeyValidityProof::new_with_rng(
+            &keypair,
+            &mut Transcript::new(b"Test"),
+            &mut FailingRng,
+        );
+        assert!(matches!(result, Err(err) if err.code() == expected_code));
+
+        let opening = PedersenOpening::new_rand();
+        let result = RangeProof::new_with_rng(
+            vec![55],
+            vec![64],
+            vec![&opening],
+            &mut Transcript::new(b"Test"),
+            &mut FailingRng,
+        );
+        assert!(
+            matches!(result, Err(RangeProofGenerationError::Rng(err)) if err.code() == expected_code)
+        );
+
+        let (fee_commitment, fee_opening) = Pedersen::new(200_u64);
+        let (delta_commitment, delta_opening) = Pedersen::new(0_u64);
+        let (claimed_commitment, claimed_opening) = Pedersen::new(0_u64);
+        let result = PercentageWithCapSplitProof::new_with_rng(
+            &fee_commitment,
+            &fee_opening,
+            200,
+            &delta_commitment,
+            &delta_opening,
+            0,
+            &claimed_commitment,
+            &claimed_opening,
+            1_000,
+            &[&fee_commitment],
+            &[&fee_opening],
+            &mut Transcript::new(b"Test"),
+            &mut FailingRng,
+        );
+        assert!(matches!(result, Err(err) if err.code() == expected_code));
+    }
+
+    #[cfg(feature = "test-rng")]
+    fn build_proof_bundle() -> Vec<u8> {
+        let keypair = ElGamalKeypair::new_rand();
+        let amount = 55_u64;
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+
+        let equality_proof_data = build_ciphertext_commitment_equality_proof_data(
+            &keypair,
+            &ciphertext,
+            &commitment,
+            &opening,
+            amount,
+        )
+        .unwrap();
+        let range_proof_data = build_batched_range_proof_u64_data(
+            vec![&commitment],
+            vec![amount],
+            vec![64],
+            vec![&opening],
+        )
+        .unwrap();
+        assert!(equality_proof_data.verify_proof().is_ok());
+        assert!(range_proof_data.verify_proof().is_ok());
+
+        let mut bundle = bytemuck::bytes_of(&equality_proof_data).to_vec();
+        bundle.extend_from_slice(bytemuck::bytes_of(&range_proof_data));
+        bundle
+    }
+
+    #[cfg(feature = "test-rng")]
+    #[test]
+    fn test_seeded_rng_reproducible_proofs() {
+        let first = insecure_with_seeded_rng([1; 32], build_proof_bundle);
+        let second = insecure_with_seeded_rng([1; 32], build_proof_bundle);
+        assert_eq!(first, second);
+
+        let other_seed = insecure_with_seeded_rng([2; 32], build_proof_bundle);
+        assert_ne!(first, other_seed);
+
+        // outside of `insecure_with_seeded_rng`, the constructors use the default entropy source
+        assert_ne!(build_proof_bundle(), build_proof_bundle());
+    }
+}
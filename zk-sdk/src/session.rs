@@ -0,0 +1,701 @@
+//! Message framing for distributed proving sessions.
+//!
+//! The threshold protocols of [`crate::encryption::threshold`] run between custodians that
+//! exchange messages in rounds. In a share refresh round, every custodian broadcasts the
+//! commitment to its refresh polynomial and sends a private message to each other custodian. In a
+//! threshold decryption round, every custodian broadcasts its partial decryption.
+//!
+//! A [`SessionMessage`] wraps the payload of a round with the identifier of the session, the round
+//! number, the index of the sender, and the index of the recipient of a private message. A
+//! coordinator can transport the messages over any channel and route them by their header without
+//! inspecting the payload. The messages implement `serde` serialization, and
+//! [`SessionMessage::to_bytes`] and [`SessionMessage::from_bytes`] provide a bounded bincode
+//! encoding.
+//!
+//! A [`ProvingSession`] tracks the view of a single custodian of a session that runs a fixed
+//! sequence of rounds. It rejects messages of other sessions, of other rounds, and messages that
+//! were already received, advances to the next round once every other participant has sent its
+//! messages for the round, and aborts the session if a round does not complete before its
+//! deadline. The state of a session can be serialized and resumed after a process restart.
+//!
+//! The framing does not encrypt or authenticate the messages. Private messages carry secret share
+//! material and must be sent over a confidential and authenticated channel.
+
+use {
+    crate::{
+        encryption::threshold::{
+            ShareRefreshCommitment, ShareRefreshDealing, ShareRefreshMessage,
+            VerifiablePartialDecryption,
+        },
+        errors::{RngError, SessionError},
+        rng::{unwrap_default_rng, CryptoRngCore, DefaultRng},
+    },
+    bincode::Options,
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeSet,
+};
+
+/// Byte length of a session identifier.
+pub const SESSION_ID_LEN: usize = 32;
+
+/// The maximum byte length of an encoded session message.
+///
+/// The bound admits a refresh commitment for the maximum number of custodians.
+pub const MAX_SESSION_MESSAGE_LEN: usize = 16 * 1024;
+
+/// The identifier of a session.
+///
+/// Every message of a session carries the identifier so that messages of concurrent sessions
+/// cannot be mixed up.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub struct SessionId(pub [u8; SESSION_ID_LEN]);
+
+impl SessionId {
+    /// Generates a random session identifier.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the identifier.
+    pub fn new_rand() -> Self {
+        unwrap_default_rng(Self::new_rand_with_rng(&mut DefaultRng))
+    }
+
+    /// Generates a random session identifier using `rng`.
+    pub fn new_rand_with_rng(rng: &mut impl CryptoRngCore) -> Result<Self, RngError> {
+        let mut bytes = [0_u8; SESSION_ID_LEN];
+        rng.try_fill_bytes(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// The payload of a session message.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum SessionPayload {
+    /// The broadcast commitment of a dealer in a share refresh round
+    RefreshCommitment(ShareRefreshCommitment),
+    /// The private message of a dealer to a custodian in a share refresh round
+    RefreshMessage(ShareRefreshMessage),
+    /// The broadcast partial decryption of a custodian in a threshold decryption round
+    PartialDecryption(Box<VerifiablePartialDecryption>),
+    /// The broadcast abort of a session by a custodian
+    Abort(SessionAbort),
+}
+
+impl SessionPayload {
+    /// Returns `true` if the payload must be sent privately to a single custodian.
+    pub fn is_private(&self) -> bool {
+        matches!(self, Self::RefreshMessage(_))
+    }
+
+    /// The index of the custodian that produced the payload.
+    fn sender(&self) -> u32 {
+        match self {
+            Self::RefreshCommitment(commitment) => commitment.dealer(),
+            Self::RefreshMessage(message) => message.dealer(),
+            Self::PartialDecryption(partial) => partial.partial_decryption.index(),
+            Self::Abort(abort) => abort.sender,
+        }
+    }
+
+    /// The index of the custodian that the payload is addressed to, if it is private.
+    fn recipient(&self) -> Option<u32> {
+        match self {
+            Self::RefreshMessage(message) => Some(message.recipient()),
+            _ => None,
+        }
+    }
+}
+
+/// A message of a round of a session.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SessionMessage {
+    /// The identifier of the session
+    pub session_id: SessionId,
+    /// The number of the round within the session
+    pub round: u32,
+    /// The index of the custodian that sent the message
+    pub sender: u32,
+    /// The index of the custodian that the message is addressed to, or `None` for a broadcast
+    /// message
+    pub recipient: Option<u32>,
+    /// The payload of the message
+    pub payload: SessionPayload,
+}
+
+impl SessionMessage {
+    /// Wraps a payload in a message with a header that is derived from the payload.
+    ///
+    /// The sender is the custodian that produced the payload, and the recipient is set for private
+    /// payloads only.
+    pub fn new(session_id: SessionId, round: u32, payload: SessionPayload) -> Self {
+        Self {
+            session_id,
+            round,
+            sender: payload.sender(),
+            recipient: payload.recipient(),
+            payload,
+        }
+    }
+
+    /// Wraps the dealing of a custodian in a share refresh round.
+    ///
+    /// Returns the broadcast commitment followed by the private messages to the other
+    /// custodians. The message of the dealer to itself is not included and must be kept by the
+    /// dealer.
+    pub fn from_refresh_dealing(
+        session_id: SessionId,
+        round: u32,
+        dealing: &ShareRefreshDealing,
+    ) -> Vec<Self> {
+        let dealer = dealing.commitment.dealer();
+        let commitment = SessionPayload::RefreshCommitment(dealing.commitment.clone());
+        std::iter::once(Self::new(session_id, round, commitment))
+            .chain(
+                dealing
+                    .messages
+                    .iter()
+                    .filter(|message| message.recipient() != dealer)
+                    .map(|message| {
+                        let payload = SessionPayload::RefreshMessage(message.clone());
+                        Self::new(session_id, round, payload)
+                    }),
+            )
+            .collect()
+    }
+
+    /// Returns `true` if the message is broadcast to all custodians.
+    pub fn is_broadcast(&self) -> bool {
+        self.recipient.is_none()
+    }
+
+    /// Checks that the header of the message is consistent with its payload.
+    ///
+    /// A coordinator routes messages by their header, so a receiver must check the header of a
+    /// message before it processes the payload.
+    pub fn check_header(&self) -> Result<(), SessionError> {
+        if self.sender != self.payload.sender() || self.recipient != self.payload.recipient() {
+            return Err(SessionError::InconsistentHeader);
+        }
+        Ok(())
+    }
+
+    /// Encodes the message with bincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SessionError> {
+        bincode_options()
+            .serialize(self)
+            .map_err(|_| SessionError::Serialization)
+    }
+
+    /// Decodes a message that was encoded with [`SessionMessage::to_bytes`].
+    ///
+    /// The header of the decoded message is checked against its payload.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SessionError> {
+        let message: Self = bincode_options()
+            .deserialize(bytes)
+            .map_err(|_| SessionError::Deserialization)?;
+        message.check_header()?;
+        Ok(message)
+    }
+}
+
+/// The kind of a round of a session.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RoundKind {
+    /// A share refresh round, in which every custodian broadcasts its refresh commitment and
+    /// sends a private refresh message to every other custodian
+    ShareRefresh,
+    /// A threshold decryption round, in which every custodian broadcasts its partial decryption
+    ThresholdDecryption,
+}
+
+impl RoundKind {
+    fn accepts(&self, payload: &SessionPayload) -> bool {
+        matches!(
+            (self, payload),
+            (
+                Self::ShareRefresh,
+                SessionPayload::RefreshCommitment(_) | SessionPayload::RefreshMessage(_)
+            ) | (
+                Self::ThresholdDecryption,
+                SessionPayload::PartialDecryption(_)
+            )
+        )
+    }
+
+    fn has_private_messages(&self) -> bool {
+        matches!(self, Self::ShareRefresh)
+    }
+}
+
+/// The reason for the abort of a session.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AbortReason {
+    /// A round did not complete before its deadline
+    Timeout,
+    /// A participant sent an invalid message
+    InvalidMessage,
+    /// A participant requested the abort
+    Requested,
+}
+
+/// The abort of a session, with the participants that are blamed for it.
+///
+/// The blame of an abort that was received from another participant is a claim of that
+/// participant and is not verified.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SessionAbort {
+    /// The index of the custodian that aborted the session
+    pub sender: u32,
+    /// The reason for the abort
+    pub reason: AbortReason,
+    /// The indices of the custodians that are blamed for the abort
+    pub blamed: Vec<u32>,
+}
+
+/// The status of a session.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum SessionStatus {
+    /// The session waits for the messages of the given round
+    InProgress { round: u32 },
+    /// All rounds of the session completed
+    Completed,
+    /// The session was aborted
+    Aborted(SessionAbort),
+}
+
+/// The state of a session from the view of a single custodian.
+///
+/// Time is passed in explicitly as seconds since an arbitrary epoch, usually the Unix epoch, so
+/// that the state does not depend on a clock and can be resumed in another process.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProvingSession {
+    session_id: SessionId,
+    local_index: u32,
+    participants: Vec<u32>,
+    rounds: Vec<RoundKind>,
+    round_timeout: u64,
+    round: u32,
+    deadline: u64,
+    status: SessionStatus,
+    broadcasts: BTreeSet<u32>,
+    private_messages: BTreeSet<u32>,
+}
+
+impl ProvingSession {
+    /// Starts a session at time `now`.
+    ///
+    /// * `session_id` - The identifier of the session
+    /// * `local_index` - The index of the local custodian, which must be a participant
+    /// * `participants` - The indices of all custodians that participate in the session
+    /// * `rounds` - The kinds of the rounds of the session, in order
+    /// * `round_timeout` - The number of seconds after which a round that has not completed times
+    ///   out
+    /// * `now` - The current time in seconds
+    pub fn new(
+        session_id: SessionId,
+        local_index: u32,
+        participants: &[u32],
+        rounds: Vec<RoundKind>,
+        round_timeout: u64,
+        now: u64,
+    ) -> Result<Self, SessionError> {
+        let participants = participants
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let session = Self {
+            session_id,
+            local_index,
+            participants,
+            rounds,
+            round_timeout,
+            round: 0,
+            deadline: now.saturating_add(round_timeout),
+            status: SessionStatus::InProgress { round: 0 },
+            broadcasts: BTreeSet::new(),
+            private_messages: BTreeSet::new(),
+        };
+        session.check()?;
+        Ok(session)
+    }
+
+    pub fn session_id(&self) -> &SessionId {
+        &self.session_id
+    }
+
+    pub fn status(&self) -> &SessionStatus {
+        &self.status
+    }
+
+    /// The time in seconds at which the current round times out.
+    pub fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    /// The other participants that have not sent all their messages for the current round.
+    pub fn missing_participants(&self) -> Vec<u32> {
+        let Some(kind) = self.rounds.get(self.round as usize) else {
+            return Vec::new();
+        };
+        self.participants
+            .iter()
+            .copied()
+            .filter(|index| *index != self.local_index)
+            .filter(|index| {
+                !self.broadcasts.contains(index)
+                    || (kind.has_private_messages() && !self.private_messages.contains(index))
+            })
+            .collect()
+    }
+
+    /// Processes a message that was received at time `now`.
+    ///
+    /// The session advances to the next round, or completes, once every other participant has
+    /// sent its messages for the current round. An abort message of another participant aborts the
+    /// session. If the current round has timed out, the session is aborted with the missing
+    /// participants blamed, and the message is rejected.
+    pub fn receive(&mut self, message: &SessionMessage, now: u64) -> Result<(), SessionError> {
+        if self.check_timeout(now) {
+            return Err(SessionError::TimedOut);
+        }
+        let SessionStatus::InProgress { round } = self.status else {
+            return Err(SessionError::NotInProgress);
+        };
+
+        message.check_header()?;
+        if message.session_id != self.session_id {
+            return Err(SessionError::SessionMismatch);
+        }
+        if message.sender == self.local_index
+            || self.participants.binary_search(&message.sender).is_err()
+        {
+            return Err(SessionError::UnexpectedSender);
+        }
+        if message
+            .recipient
+            .is_some_and(|recipient| recipient != self.local_index)
+        {
+            return Err(SessionError::MisroutedMessage);
+        }
+        if message.round != round {
+            return Err(SessionError::OutOfOrderMessage);
+        }
+
+        if let SessionPayload::Abort(abort) = &message.payload {
+            self.status = SessionStatus::Aborted(abort.clone());
+            return Ok(());
+        }
+        if !self.rounds[round as usize].accepts(&message.payload) {
+            return Err(SessionError::UnexpectedPayload);
+        }
+        let received = if message.is_broadcast() {
+            &mut self.broadcasts
+        } else {
+            &mut self.private_messages
+        };
+        if !received.insert(message.sender) {
+            return Err(SessionError::ReplayedMessage);
+        }
+
+        if self.missing_participants().is_empty() {
+            self.advance(now);
+        }
+        Ok(())
+    }
+
+    /// Aborts the session if the current round has timed out at time `now`.
+    ///
+    /// The participants that have not sent all their messages for the round are blamed. Returns
+    /// `true` if the session is aborted because of a timeout.
+    pub fn check_timeout(&mut self, now: u64) -> bool {
+        if let SessionStatus::Aborted(abort) = &self.status {
+            return abort.reason == AbortReason::Timeout;
+        }
+        if self.status != (SessionStatus::InProgress { round: self.round }) || now < self.deadline {
+            return false;
+        }
+        self.status = SessionStatus::Aborted(SessionAbort {
+            sender: self.local_index,
+            reason: AbortReason::Timeout,
+            blamed: self.missing_participants(),
+        });
+        true
+    }
+
+    /// Aborts the session and returns the abort message to be broadcast to the other
+    /// participants.
+    ///
+    /// A custodian that receives a message that fails verification, for instance a refresh
+    /// message that is inconsistent with the commitment of its dealer, aborts the session with
+    /// [`AbortReason::InvalidMessage`] and blames the dealer.
+    pub fn abort(
+        &mut self,
+        reason: AbortReason,
+        blamed: Vec<u32>,
+    ) -> Result<SessionMessage, SessionError> {
+        if !matches!(self.status, SessionStatus::InProgress { .. }) {
+            return Err(SessionError::NotInProgress);
+        }
+        let abort = SessionAbort {
+            sender: self.local_index,
+            reason,
+            blamed,
+        };
+        self.status = SessionStatus::Aborted(abort.clone());
+        Ok(SessionMessage::new(
+            self.session_id,
+            self.round,
+            SessionPayload::Abort(abort),
+        ))
+    }
+
+    /// Encodes the state of the session with bincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SessionError> {
+        bincode_options()
+            .serialize(self)
+            .map_err(|_| SessionError::Serialization)
+    }
+
+    /// Resumes a session from a state that was encoded with [`ProvingSession::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SessionError> {
+        let session: Self = bincode_options()
+            .deserialize(bytes)
+            .map_err(|_| SessionError::Deserialization)?;
+        session.check().map_err(|_| SessionError::Deserialization)?;
+        Ok(session)
+    }
+
+    fn advance(&mut self, now: u64) {
+        self.round += 1;
+        self.broadcasts.clear();
+        self.private_messages.clear();
+        if self.round as usize == self.rounds.len() {
+            self.status = SessionStatus::Completed;
+        } else {
+            self.deadline = now.saturating_add(self.round_timeout);
+            self.status = SessionStatus::InProgress { round: self.round };
+        }
+    }
+
+    fn check(&self) -> Result<(), SessionError> {
+        if self.rounds.is_empty()
+            || self.rounds.len() > u32::MAX as usize
+            || self.participants.len() < 2
+            || self.participants.windows(2).any(|pair| pair[0] >= pair[1])
+            || self.participants.binary_search(&self.local_index).is_err()
+            || self.round as usize > self.rounds.len()
+        {
+            return Err(SessionError::InvalidParameters);
+        }
+        Ok(())
+    }
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_limit(MAX_SESSION_MESSAGE_LEN as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::{
+            elgamal::ElGamalKeypair,
+            threshold::{combine_partial_decryptions, KeyShare, ThresholdParameters},
+        },
+    };
+
+    #[test]
+    fn test_refresh_session_messages() {
+        let parameters = ThresholdParameters::new(2, 3).unwrap();
+        let session_id = SessionId::new_rand();
+        let dealing = ShareRefreshDealing::new(1, &parameters).unwrap();
+
+        let messages = SessionMessage::from_refresh_dealing(session_id, 0, &dealing);
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].is_broadcast());
+        assert!(messages[1..]
+            .iter()
+            .all(|message| message.recipient.is_some() && message.recipient != Some(1)));
+
+        for message in &messages {
+            let decoded = SessionMessage::from_bytes(&message.to_bytes().unwrap()).unwrap();
+            assert_eq!(decoded.session_id, session_id);
+            assert_eq!(decoded.sender, 1);
+            assert_eq!(decoded.recipient, message.recipient);
+        }
+        let SessionPayload::RefreshCommitment(commitment) =
+            SessionMessage::from_bytes(&messages[0].to_bytes().unwrap())
+                .unwrap()
+                .payload
+        else {
+            panic!("unexpected payload");
+        };
+        assert_eq!(commitment, dealing.commitment);
+
+        // a message whose header is inconsistent with its payload
+        let mut message = messages[1].clone();
+        message.recipient = None;
+        assert_eq!(
+            SessionMessage::from_bytes(&message.to_bytes().unwrap()).unwrap_err(),
+            SessionError::InconsistentHeader
+        );
+    }
+
+    #[test]
+    fn test_proving_session() {
+        let session_id = SessionId::new_rand();
+        let mut session = ProvingSession::new(
+            session_id,
+            1,
+            &[1, 2, 3],
+            vec![RoundKind::ShareRefresh, RoundKind::ThresholdDecryption],
+            60,
+            1_000,
+        )
+        .unwrap();
+
+        let parameters = ThresholdParameters::new(2, 3).unwrap();
+        let dealing_2 = ShareRefreshDealing::new(2, &parameters).unwrap();
+        let dealing_3 = ShareRefreshDealing::new(3, &parameters).unwrap();
+        let messages_2 = SessionMessage::from_refresh_dealing(session_id, 0, &dealing_2);
+        let messages_3 = SessionMessage::from_refresh_dealing(session_id, 0, &dealing_3);
+
+        session.receive(&messages_2[0], 1_010).unwrap();
+        assert_eq!(
+            session.receive(&messages_2[0], 1_010).unwrap_err(),
+            SessionError::ReplayedMessage
+        );
+        // the private message of custodian 2 to custodian 3
+        assert_eq!(
+            session.receive(&messages_2[2], 1_010).unwrap_err(),
+            SessionError::MisroutedMessage
+        );
+        let mut other_session_message = messages_3[0].clone();
+        other_session_message.session_id = SessionId::new_rand();
+        assert_eq!(
+            session.receive(&other_session_message, 1_010).unwrap_err(),
+            SessionError::SessionMismatch
+        );
+        session.receive(&messages_2[1], 1_010).unwrap();
+        session.receive(&messages_3[0], 1_020).unwrap();
+        assert_eq!(session.missing_participants(), vec![3]);
+
+        // resume the session from its serialized state
+        let mut session = ProvingSession::from_bytes(&session.to_bytes().unwrap()).unwrap();
+        session.receive(&messages_3[1], 1_030).unwrap();
+        assert_eq!(session.status(), &SessionStatus::InProgress { round: 1 });
+        assert_eq!(session.deadline(), 1_090);
+
+        // messages of a previous round are rejected
+        assert_eq!(
+            session.receive(&messages_3[0], 1_040).unwrap_err(),
+            SessionError::OutOfOrderMessage
+        );
+
+        // custodian 3 does not respond in the second round
+        assert!(!session.check_timeout(1_089));
+        assert!(session.check_timeout(1_090));
+        assert_eq!(
+            session.status(),
+            &SessionStatus::Aborted(SessionAbort {
+                sender: 1,
+                reason: AbortReason::Timeout,
+                blamed: vec![2, 3],
+            })
+        );
+        assert_eq!(
+            session.receive(&messages_2[0], 1_100).unwrap_err(),
+            SessionError::TimedOut
+        );
+    }
+
+    #[test]
+    fn test_proving_session_abort() {
+        let session_id = SessionId::new_rand();
+        let rounds = vec![RoundKind::ShareRefresh];
+        let mut session_1 =
+            ProvingSession::new(session_id, 1, &[1, 2, 3], rounds.clone(), 60, 0).unwrap();
+        let mut session_2 = ProvingSession::new(session_id, 2, &[1, 2, 3], rounds, 60, 0).unwrap();
+
+        // custodian 1 blames custodian 3 for an invalid refresh message
+        let abort = session_1
+            .abort(AbortReason::InvalidMessage, vec![3])
+            .unwrap();
+        let abort = SessionMessage::from_bytes(&abort.to_bytes().unwrap()).unwrap();
+        session_2.receive(&abort, 10).unwrap();
+        assert_eq!(
+            session_2.status(),
+            &SessionStatus::Aborted(SessionAbort {
+                sender: 1,
+                reason: AbortReason::InvalidMessage,
+                blamed: vec![3],
+            })
+        );
+        assert_eq!(
+            session_2.abort(AbortReason::Requested, vec![]).unwrap_err(),
+            SessionError::NotInProgress
+        );
+
+        assert_eq!(
+            ProvingSession::new(
+                session_id,
+                4,
+                &[1, 2, 3],
+                vec![RoundKind::ShareRefresh],
+                60,
+                0
+            )
+            .unwrap_err(),
+            SessionError::InvalidParameters
+        );
+    }
+
+    #[test]
+    fn test_decryption_session_messages() {
+        let keypair = ElGamalKeypair::new_rand();
+        let parameters = ThresholdParameters::new(2, 3).unwrap();
+        let shares = KeyShare::split(keypair.secret(), &parameters);
+        let verification_keys = shares
+            .iter()
+            .map(KeyShare::verification_key)
+            .collect::<Vec<_>>();
+        let ciphertext = keypair.pubkey().encrypt(55_u64);
+        let session_id = SessionId::new_rand();
+
+        let partial_decryptions = shares
+            .iter()
+            .map(|share| {
+                let payload = SessionPayload::PartialDecryption(Box::new(
+                    share.partial_decrypt_with_proof(&ciphertext),
+                ));
+                let bytes = SessionMessage::new(session_id, 0, payload)
+                    .to_bytes()
+                    .unwrap();
+                match SessionMessage::from_bytes(&bytes).unwrap().payload {
+                    SessionPayload::PartialDecryption(partial) => *partial,
+                    _ => panic!("unexpected payload"),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let decryption = combine_partial_decryptions(
+            &parameters,
+            &ciphertext,
+            &verification_keys,
+            &partial_decryptions,
+        )
+        .unwrap();
+        assert_eq!(decryption.discrete_log.decode_u32(), Some(55));
+        assert!(decryption.excluded.is_empty());
+
+        assert_eq!(
+            SessionMessage::from_bytes(&[0; 8]).unwrap_err(),
+            SessionError::Deserialization
+        );
+    }
+}
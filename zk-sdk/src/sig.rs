@@ -0,0 +1,166 @@
+//! Schnorr signatures over ElGamal keys.
+//!
+//! An ElGamal public key `P = s^-1 * H` is a discrete log commitment to the inverse of the secret
+//! key with respect to the Pedersen base point `H`. A Schnorr signature proves knowledge of
+//! `s^-1`, so an account holder can authenticate messages, such as API requests, with the
+//! ElGamal keypair that they already protect.
+//!
+//! The signature challenge is derived from a transcript with its own domain separator, which is
+//! distinct from the domain separator of the proof transcripts. A signature therefore cannot be
+//! reinterpreted as a proof, nor a proof as a signature.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalKeypair, ElGamalPubkey},
+            pedersen::H,
+        },
+        errors::{RngError, SignatureError},
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    merlin::Transcript,
+    zeroize::Zeroize,
+};
+
+/// Domain separator for the transcripts of signatures.
+const SIGNATURE_DOMAIN: &[u8] = b"solana-zk-sdk-elgamal-schnorr-signature-v1";
+
+/// Byte length of a signature.
+pub const ELGAMAL_SIGNATURE_LEN: usize = 64;
+
+/// A Schnorr signature over an ElGamal key.
+#[allow(non_snake_case)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElGamalSignature {
+    R: CompressedRistretto,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl ElGamalSignature {
+    /// Byte length of a signature.
+    pub const LEN: usize = ELGAMAL_SIGNATURE_LEN;
+
+    /// Signs a message with an ElGamal keypair.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the nonce.
+    pub fn sign(keypair: &ElGamalKeypair, message: &[u8]) -> Self {
+        unwrap_default_rng(Self::sign_with_rng(keypair, message, &mut DefaultRng))
+    }
+
+    /// Signs a message with an ElGamal keypair, using `rng` to generate the nonce.
+    pub fn sign_with_rng(
+        keypair: &ElGamalKeypair,
+        message: &[u8],
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        let s = keypair.secret().get_scalar();
+        assert!(s != &Scalar::ZERO);
+        let mut s_inv = s.invert();
+
+        let mut k = try_random_scalar(rng)?;
+        let R = (&k * &(*H)).compress();
+
+        let c = Self::challenge(keypair.pubkey(), &R, message);
+        let z = &(&c * &s_inv) + &k;
+
+        s_inv.zeroize();
+        k.zeroize();
+
+        Ok(Self { R, z })
+    }
+
+    /// Verifies the signature of a message under an ElGamal public key.
+    pub fn verify(&self, pubkey: &ElGamalPubkey, message: &[u8]) -> Result<(), SignatureError> {
+        let P = pubkey.get_point();
+        if P.is_identity() {
+            return Err(SignatureError::InvalidPubkey);
+        }
+        let R = self.R.decompress().ok_or(SignatureError::Deserialization)?;
+
+        let c = Self::challenge(pubkey, &self.R, message);
+
+        // check `z * H = R + c * P`
+        let check = vartime_multiscalar_mul([self.z, -Scalar::ONE, -c], [*H, R, *P]);
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SignatureError::Verification)
+        }
+    }
+
+    fn challenge(pubkey: &ElGamalPubkey, R: &CompressedRistretto, message: &[u8]) -> Scalar {
+        let mut transcript = Transcript::new(SIGNATURE_DOMAIN);
+        transcript.append_message(b"pubkey", &pubkey.to_bytes());
+        transcript.append_point(b"R", R);
+        transcript.append_message(b"message", message);
+        transcript.challenge_scalar(b"c")
+    }
+
+    pub fn to_bytes(&self) -> [u8; ELGAMAL_SIGNATURE_LEN] {
+        let mut bytes = [0u8; ELGAMAL_SIGNATURE_LEN];
+        bytes[..32].copy_from_slice(self.R.as_bytes());
+        bytes[32..].copy_from_slice(self.z.as_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SignatureError> {
+        let bytes: &[u8; ELGAMAL_SIGNATURE_LEN] = bytes
+            .try_into()
+            .map_err(|_| SignatureError::Deserialization)?;
+        let R = CompressedRistretto::from_slice(&bytes[..32])
+            .map_err(|_| SignatureError::Deserialization)?;
+        let z = Option::from(Scalar::from_canonical_bytes(
+            bytes[32..].try_into().unwrap(),
+        ))
+        .ok_or(SignatureError::Deserialization)?;
+        Ok(Self { R, z })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signature_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+        let message = b"GET /v1/balances";
+
+        let signature = ElGamalSignature::sign(&keypair, message);
+        assert!(signature.verify(keypair.pubkey(), message).is_ok());
+
+        let signature = ElGamalSignature::from_bytes(&signature.to_bytes()).unwrap();
+        assert!(signature.verify(keypair.pubkey(), message).is_ok());
+
+        assert_eq!(
+            signature.verify(keypair.pubkey(), b"GET /v1/transfers"),
+            Err(SignatureError::Verification)
+        );
+        assert_eq!(
+            signature.verify(ElGamalKeypair::new_rand().pubkey(), message),
+            Err(SignatureError::Verification)
+        );
+        assert_eq!(
+            signature.verify(&ElGamalPubkey::default(), message),
+            Err(SignatureError::InvalidPubkey)
+        );
+    }
+
+    #[test]
+    fn test_signature_from_bytes() {
+        assert_eq!(
+            ElGamalSignature::from_bytes(&[0; ELGAMAL_SIGNATURE_LEN - 1]),
+            Err(SignatureError::Deserialization)
+        );
+        let mut bytes = ElGamalSignature::sign(&ElGamalKeypair::new_rand(), b"").to_bytes();
+        bytes[32..].fill(0xff);
+        assert_eq!(
+            ElGamalSignature::from_bytes(&bytes),
+            Err(SignatureError::Deserialization)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! The asset equality sigma proof system.
+//!
+//! An asset equality proof certifies that two asset tags `T_0 = J_a + b_0*G` and
+//! `T_1 = J_b + b_1*G` blind the same asset generator, without revealing the asset. The prover
+//! shows knowledge of the discrete log of `T_0 - T_1` with respect to `G`, which is only known if
+//! `J_a = J_b`.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+use {
+    crate::{
+        encryption::{
+            asset::{AssetTag, AssetTagOpening},
+            pedersen::G,
+        },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            bytes_to_units, canonical_scalar_from_bytes,
+            errors::{AssetEqualityProofVerificationError, SigmaProofVerificationError},
+            units_to_bytes,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::UNIT_LEN,
+    zeroize::Zeroize,
+};
+
+/// Asset equality proof.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct AssetEqualityProof {
+    Y: CompressedRistretto,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl AssetEqualityProof {
+    /// Byte length of an asset equality proof.
+    pub const LEN: usize = UNIT_LEN * 2;
+
+    /// Creates an asset equality proof.
+    ///
+    /// The function does not check that the tags blind the same asset. If they do not, the
+    /// resulting proof does not verify.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `first_tag` - The first asset tag
+    /// * `first_opening` - The blinding factor of the first asset tag
+    /// * `second_tag` - The second asset tag
+    /// * `second_opening` - The blinding factor of the second asset tag
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        first_tag: &AssetTag,
+        first_opening: &AssetTagOpening,
+        second_tag: &AssetTag,
+        second_opening: &AssetTagOpening,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_tag,
+            first_opening,
+            second_tag,
+            second_opening,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates an asset equality proof using `rng` to generate the random scalars.
+    pub fn new_with_rng(
+        first_tag: &AssetTag,
+        first_opening: &AssetTagOpening,
+        second_tag: &AssetTag,
+        second_opening: &AssetTagOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(first_tag, second_tag, transcript);
+        transcript.asset_equality_proof_domain_separator();
+
+        // the discrete log of `T_0 - T_1` with respect to `G`
+        let mut b = first_opening.get_scalar() - second_opening.get_scalar();
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = try_random_scalar(rng)?;
+        let Y = (&y * &G).compress();
+
+        // record masking factors in transcript and get challenges
+        transcript.append_point(b"Y", &Y);
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute masked blinding factor
+        let z = &(&c * &b) + &y;
+
+        // zeroize all sensitive non-reference variables
+        b.zeroize();
+        y.zeroize();
+
+        Ok(Self { Y, z })
+    }
+
+    /// Verifies an asset equality proof.
+    ///
+    /// * `first_tag` - The first asset tag
+    /// * `second_tag` - The second asset tag
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        first_tag: &AssetTag,
+        second_tag: &AssetTag,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), AssetEqualityProofVerificationError> {
+        Self::hash_context_into_transcript(first_tag, second_tag, transcript);
+        transcript.asset_equality_proof_domain_separator();
+
+        let T_0 = first_tag.get_point();
+        let T_1 = second_tag.get_point();
+        if T_0.is_identity() || T_1.is_identity() {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        // include Y to transcript and extract challenge
+        transcript.validate_and_append_point(b"Y", &self.Y)?;
+        let c = transcript.challenge_scalar(b"c");
+
+        let Y = self
+            .Y
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+        // check `z*G = c*(T_0 - T_1) + Y`
+        let check = vartime_multiscalar_mul([self.z, -c, c, -Scalar::ONE], [&G, T_0, T_1, &Y]);
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        first_tag: &AssetTag,
+        second_tag: &AssetTag,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        transcript.append_message(b"first-tag", &first_tag.to_bytes());
+        transcript.append_message(b"second-tag", &second_tag.to_bytes());
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([self.Y.to_bytes(), self.z.to_bytes()])
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AssetEqualityProofVerificationError> {
+        let [Y, z] = bytes_to_units(bytes)?;
+
+        let Y = CompressedRistretto(Y);
+        let z = canonical_scalar_from_bytes(z)?;
+        Ok(AssetEqualityProof { Y, z })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::asset::{AssetId, ASSET_ID_LEN},
+        merlin::Transcript,
+    };
+
+    #[test]
+    fn test_asset_equality_proof_correctness() {
+        let asset_id = AssetId([1; ASSET_ID_LEN]);
+        let (first_tag, first_opening) = AssetTag::new_rand(&asset_id);
+        let (second_tag, second_opening) = AssetTag::new_rand(&asset_id);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = AssetEqualityProof::new(
+            &first_tag,
+            &first_opening,
+            &second_tag,
+            &second_opening,
+            &mut prover_transcript,
+        );
+        let proof = AssetEqualityProof::from_bytes(&proof.to_bytes()).unwrap();
+        proof
+            .verify(&first_tag, &second_tag, &mut verifier_transcript)
+            .unwrap();
+
+        // tags of distinct assets
+        let (other_tag, other_opening) = AssetTag::new_rand(&AssetId([2; ASSET_ID_LEN]));
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = AssetEqualityProof::new(
+            &first_tag,
+            &first_opening,
+            &other_tag,
+            &other_opening,
+            &mut prover_transcript,
+        );
+        assert!(proof
+            .verify(&first_tag, &other_tag, &mut verifier_transcript)
+            .is_err());
+    }
+}
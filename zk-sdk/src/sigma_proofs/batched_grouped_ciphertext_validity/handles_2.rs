@@ -23,6 +23,8 @@ use {
             elgamal::ElGamalPubkey, grouped_elgamal::GroupedElGamalCiphertext,
             pedersen::PedersenOpening,
         },
+        errors::RngError,
+        rng::{unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
             errors::{SigmaProofVerificationError, ValidityProofVerificationError},
             grouped_ciphertext_validity::GroupedCiphertext2HandlesValidityProof,
@@ -30,8 +32,10 @@ use {
         transcript::TranscriptProtocol,
     },
     curve25519_dalek::{scalar::Scalar, traits::IsIdentity},
-    merlin::Transcript,
-    solana_zk_sdk_pod::sigma_proofs::PodBatchedGroupedCiphertext2HandlesValidityProof,
+    solana_zk_sdk_pod::{
+        encryption::grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
+        sigma_proofs::PodBatchedGroupedCiphertext2HandlesValidityProof,
+    },
     zeroize::Zeroize,
 };
 
@@ -50,6 +54,9 @@ pub struct BatchedGroupedCiphertext2HandlesValidityProof(GroupedCiphertext2Handl
 
 #[allow(non_snake_case)]
 impl BatchedGroupedCiphertext2HandlesValidityProof {
+    /// Byte length of a batched grouped ciphertext validity proof for 2 handles
+    pub const LEN: usize = GroupedCiphertext2HandlesValidityProof::LEN;
+
     /// Creates a batched grouped ciphertext validity proof.
     ///
     /// The function simply batches the input openings and invokes the standard grouped ciphertext
@@ -66,8 +73,40 @@ impl BatchedGroupedCiphertext2HandlesValidityProof {
         amount_hi: T,
         opening_lo: &PedersenOpening,
         opening_hi: &PedersenOpening,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            amount_lo,
+            amount_hi,
+            opening_lo,
+            opening_hi,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a batched grouped ciphertext validity proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng<T: Into<Scalar>>(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        grouped_ciphertext_lo: &GroupedElGamalCiphertext<2>,
+        grouped_ciphertext_hi: &GroupedElGamalCiphertext<2>,
+        amount_lo: T,
+        amount_hi: T,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             first_pubkey,
             second_pubkey,
@@ -82,15 +121,15 @@ impl BatchedGroupedCiphertext2HandlesValidityProof {
         let mut batched_message = amount_lo.into() + amount_hi.into() * t;
         let batched_opening = opening_lo + &(opening_hi * &t);
 
-        let proof = BatchedGroupedCiphertext2HandlesValidityProof(
-            GroupedCiphertext2HandlesValidityProof::new_direct(
-                first_pubkey,
-                second_pubkey,
-                batched_message,
-                &batched_opening,
-                transcript,
-            ),
-        );
+        let proof = GroupedCiphertext2HandlesValidityProof::new_direct(
+            first_pubkey,
+            second_pubkey,
+            batched_message,
+            &batched_opening,
+            transcript,
+            rng,
+        )
+        .map(BatchedGroupedCiphertext2HandlesValidityProof);
 
         // zeroize all sensitive owned variables
         batched_message.zeroize();
@@ -106,7 +145,7 @@ impl BatchedGroupedCiphertext2HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         grouped_ciphertext_lo: &GroupedElGamalCiphertext<2>,
         grouped_ciphertext_hi: &GroupedElGamalCiphertext<2>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), ValidityProofVerificationError> {
         // We reject if the first public key or the commitments are the identity point.
         // We allow the second public key to be an identity point as it is often the auditor's
@@ -162,15 +201,21 @@ impl BatchedGroupedCiphertext2HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         grouped_ciphertext_lo: &GroupedElGamalCiphertext<2>,
         grouped_ciphertext_hi: &GroupedElGamalCiphertext<2>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"first-pubkey", &first_pubkey.to_bytes());
         transcript.append_message(b"second-pubkey", &second_pubkey.to_bytes());
-        transcript.append_message(b"grouped-ciphertext-lo", &grouped_ciphertext_lo.to_bytes());
-        transcript.append_message(b"grouped-ciphertext-hi", &grouped_ciphertext_hi.to_bytes());
+        transcript.append_message(
+            b"grouped-ciphertext-lo",
+            &PodGroupedElGamalCiphertext2Handles::from(*grouped_ciphertext_lo).0,
+        );
+        transcript.append_message(
+            b"grouped-ciphertext-hi",
+            &PodGroupedElGamalCiphertext2Handles::from(*grouped_ciphertext_hi).0,
+        );
     }
 
-    pub fn to_bytes(&self) -> [u8; 160] {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
         self.0.to_bytes()
     }
 
@@ -187,6 +232,14 @@ impl From<BatchedGroupedCiphertext2HandlesValidityProof>
     }
 }
 
+impl From<&BatchedGroupedCiphertext2HandlesValidityProof>
+    for PodBatchedGroupedCiphertext2HandlesValidityProof
+{
+    fn from(decoded_proof: &BatchedGroupedCiphertext2HandlesValidityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodBatchedGroupedCiphertext2HandlesValidityProof>
     for BatchedGroupedCiphertext2HandlesValidityProof
 {
@@ -199,11 +252,24 @@ impl TryFrom<PodBatchedGroupedCiphertext2HandlesValidityProof>
     }
 }
 
+impl TryFrom<&PodBatchedGroupedCiphertext2HandlesValidityProof>
+    for BatchedGroupedCiphertext2HandlesValidityProof
+{
+    type Error = ValidityProofVerificationError;
+
+    fn try_from(
+        pod_proof: &PodBatchedGroupedCiphertext2HandlesValidityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
         crate::encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::{
                 elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
@@ -27,22 +27,27 @@ use {
             elgamal::ElGamalPubkey, grouped_elgamal::GroupedElGamalCiphertext,
             pedersen::PedersenOpening,
         },
+        errors::RngError,
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBuffer},
+        rng::{unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
             errors::{SigmaProofVerificationError, ValidityProofVerificationError},
             grouped_ciphertext_validity::GroupedCiphertext3HandlesValidityProof,
         },
         transcript::TranscriptProtocol,
     },
-    curve25519_dalek::{scalar::Scalar, traits::IsIdentity},
-    merlin::Transcript,
-    solana_zk_sdk_pod::{sigma_proofs::PodBatchedGroupedCiphertext3HandlesValidityProof, UNIT_LEN},
+    curve25519_dalek::{
+        ristretto::RistrettoPoint,
+        scalar::Scalar,
+        traits::{Identity, IsIdentity},
+    },
+    solana_zk_sdk_pod::{
+        encryption::grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
+        sigma_proofs::PodBatchedGroupedCiphertext3HandlesValidityProof,
+    },
     zeroize::Zeroize,
 };
 
-/// Byte length of a batched grouped ciphertext validity proof for 3 handles
-#[allow(dead_code)]
-const BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN: usize = UNIT_LEN * 6;
-
 /// Batched grouped ciphertext validity proof with two handles.
 #[allow(non_snake_case)]
 #[derive(Clone)]
@@ -51,6 +56,13 @@ pub struct BatchedGroupedCiphertext3HandlesValidityProof(GroupedCiphertext3Handl
 #[allow(non_snake_case)]
 #[allow(dead_code)]
 impl BatchedGroupedCiphertext3HandlesValidityProof {
+    /// Byte length of a batched grouped ciphertext validity proof for 3 handles
+    pub const LEN: usize = GroupedCiphertext3HandlesValidityProof::LEN;
+
+    /// Number of multiscalar multiplication terms in the verification equation.
+    pub(crate) const VERIFICATION_TERMS: usize =
+        GroupedCiphertext3HandlesValidityProof::VERIFICATION_TERMS;
+
     /// Creates a batched grouped ciphertext validity proof.
     ///
     /// The function simply batches the input openings and invokes the standard grouped ciphertext
@@ -66,8 +78,42 @@ impl BatchedGroupedCiphertext3HandlesValidityProof {
         amount_hi: T,
         opening_lo: &PedersenOpening,
         opening_hi: &PedersenOpening,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            amount_lo,
+            amount_hi,
+            opening_lo,
+            opening_hi,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a batched grouped ciphertext validity proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng<T: Into<Scalar>>(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
+        grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
+        amount_lo: T,
+        amount_hi: T,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             first_pubkey,
             second_pubkey,
@@ -83,16 +129,16 @@ impl BatchedGroupedCiphertext3HandlesValidityProof {
         let mut batched_message = amount_lo.into() + amount_hi.into() * t;
         let batched_opening = opening_lo + &(opening_hi * &t);
 
-        let proof = BatchedGroupedCiphertext3HandlesValidityProof(
-            GroupedCiphertext3HandlesValidityProof::new_direct(
-                first_pubkey,
-                second_pubkey,
-                third_pubkey,
-                batched_message,
-                &batched_opening,
-                transcript,
-            ),
-        );
+        let proof = GroupedCiphertext3HandlesValidityProof::new_direct(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            batched_message,
+            &batched_opening,
+            transcript,
+            rng,
+        )
+        .map(BatchedGroupedCiphertext3HandlesValidityProof);
 
         // zeroize all sensitive owned variables
         batched_message.zeroize();
@@ -111,7 +157,44 @@ impl BatchedGroupedCiphertext3HandlesValidityProof {
         third_pubkey: &ElGamalPubkey,
         grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
         grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), ValidityProofVerificationError> {
+        let mut scalars = [Scalar::ZERO; Self::VERIFICATION_TERMS];
+        let mut points = [RistrettoPoint::identity(); Self::VERIFICATION_TERMS];
+        let mut buffer = MultiscalarMulBuffer::new(&mut scalars, &mut points);
+        self.append_verification_terms(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            transcript,
+            &mut buffer,
+        )?;
+
+        if buffer
+            .multiscalar_mul(&CpuMultiscalarMulBackend)
+            .is_identity()
+        {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Appends the terms of the verification equation to a multiscalar multiplication buffer.
+    ///
+    /// The proof is valid if and only if the appended terms sum to the identity.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn append_verification_terms(
+        &self,
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
+        grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
+        transcript: &mut impl TranscriptProtocol,
+        buffer: &mut MultiscalarMulBuffer,
     ) -> Result<(), ValidityProofVerificationError> {
         // We reject if the public keys or the commitments are the identity point.
         // The exception is the third public key, which is often the auditor's
@@ -164,12 +247,13 @@ impl BatchedGroupedCiphertext3HandlesValidityProof {
 
         let BatchedGroupedCiphertext3HandlesValidityProof(validity_proof) = self;
 
-        validity_proof.verify_direct(
+        validity_proof.append_verification_terms_direct(
             first_pubkey,
             second_pubkey,
             third_pubkey,
             &batched_grouped_ciphertext,
             transcript,
+            buffer,
         )
     }
 
@@ -179,16 +263,22 @@ impl BatchedGroupedCiphertext3HandlesValidityProof {
         third_pubkey: &ElGamalPubkey,
         grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
         grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"first-pubkey", &first_pubkey.to_bytes());
         transcript.append_message(b"second-pubkey", &second_pubkey.to_bytes());
         transcript.append_message(b"third-pubkey", &third_pubkey.to_bytes());
-        transcript.append_message(b"grouped-ciphertext-lo", &grouped_ciphertext_lo.to_bytes());
-        transcript.append_message(b"grouped-ciphertext-hi", &grouped_ciphertext_hi.to_bytes());
+        transcript.append_message(
+            b"grouped-ciphertext-lo",
+            &PodGroupedElGamalCiphertext3Handles::from(*grouped_ciphertext_lo).0,
+        );
+        transcript.append_message(
+            b"grouped-ciphertext-hi",
+            &PodGroupedElGamalCiphertext3Handles::from(*grouped_ciphertext_hi).0,
+        );
     }
 
-    pub fn to_bytes(&self) -> [u8; BATCHED_GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN] {
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
         self.0.to_bytes()
     }
 
@@ -205,6 +295,14 @@ impl From<BatchedGroupedCiphertext3HandlesValidityProof>
     }
 }
 
+impl From<&BatchedGroupedCiphertext3HandlesValidityProof>
+    for PodBatchedGroupedCiphertext3HandlesValidityProof
+{
+    fn from(decoded_proof: &BatchedGroupedCiphertext3HandlesValidityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodBatchedGroupedCiphertext3HandlesValidityProof>
     for BatchedGroupedCiphertext3HandlesValidityProof
 {
@@ -217,11 +315,24 @@ impl TryFrom<PodBatchedGroupedCiphertext3HandlesValidityProof>
     }
 }
 
+impl TryFrom<&PodBatchedGroupedCiphertext3HandlesValidityProof>
+    for BatchedGroupedCiphertext3HandlesValidityProof
+{
+    type Error = ValidityProofVerificationError;
+
+    fn try_from(
+        pod_proof: &PodBatchedGroupedCiphertext3HandlesValidityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
         crate::encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::{
                 elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
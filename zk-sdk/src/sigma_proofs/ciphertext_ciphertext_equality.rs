@@ -9,27 +9,21 @@ use {
             elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
             pedersen::{PedersenOpening, G, H},
         },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{EqualityProofVerificationError, SigmaProofVerificationError},
-            ristretto_point_from_optional_slice,
+            prover_commitment, units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
-    curve25519_dalek::{
-        ristretto::{CompressedRistretto, RistrettoPoint},
-        scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
-    },
-    merlin::Transcript,
-    rand::rngs::OsRng,
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
     solana_zk_sdk_pod::{sigma_proofs::PodCiphertextCiphertextEqualityProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a ciphertext-ciphertext equality proof.
-const CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN: usize = UNIT_LEN * 7;
-
 /// The ciphertext-ciphertext equality proof.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -47,6 +41,9 @@ pub struct CiphertextCiphertextEqualityProof {
 
 #[allow(non_snake_case)]
 impl CiphertextCiphertextEqualityProof {
+    /// Byte length of a ciphertext-ciphertext equality proof.
+    pub const LEN: usize = UNIT_LEN * 7;
+
     /// Creates a ciphertext-ciphertext equality proof.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -67,8 +64,36 @@ impl CiphertextCiphertextEqualityProof {
         second_ciphertext: &ElGamalCiphertext,
         second_opening: &PedersenOpening,
         amount: u64,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_keypair,
+            second_pubkey,
+            first_ciphertext,
+            second_ciphertext,
+            second_opening,
+            amount,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a ciphertext-ciphertext equality proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng(
+        first_keypair: &ElGamalKeypair,
+        second_pubkey: &ElGamalPubkey,
+        first_ciphertext: &ElGamalCiphertext,
+        second_ciphertext: &ElGamalCiphertext,
+        second_opening: &PedersenOpening,
+        amount: u64,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             first_keypair.pubkey(),
             second_pubkey,
@@ -88,13 +113,13 @@ impl CiphertextCiphertextEqualityProof {
         let r = second_opening.get_scalar();
 
         // generate random masking factors that also serves as nonces
-        let mut y_s = Scalar::random(&mut OsRng);
-        let mut y_x = Scalar::random(&mut OsRng);
-        let mut y_r = Scalar::random(&mut OsRng);
+        let mut y_s = try_random_scalar(rng)?;
+        let mut y_x = try_random_scalar(rng)?;
+        let mut y_r = try_random_scalar(rng)?;
 
         let Y_0 = (&y_s * P_first).compress();
-        let Y_1 = RistrettoPoint::multiscalar_mul(vec![&y_x, &y_s], vec![&G, D_first]).compress();
-        let Y_2 = RistrettoPoint::multiscalar_mul(vec![&y_x, &y_r], vec![&G, &(*H)]).compress();
+        let Y_1 = prover_commitment([&y_x, &y_s], [&G, D_first]).compress();
+        let Y_2 = prover_commitment([&y_x, &y_r], [&G, &(*H)]).compress();
         let Y_3 = (&y_r * P_second).compress();
 
         // record masking factors in the transcript
@@ -122,7 +147,7 @@ impl CiphertextCiphertextEqualityProof {
         y_x.zeroize();
         y_r.zeroize();
 
-        CiphertextCiphertextEqualityProof {
+        Ok(CiphertextCiphertextEqualityProof {
             Y_0,
             Y_1,
             Y_2,
@@ -130,7 +155,7 @@ impl CiphertextCiphertextEqualityProof {
             z_s,
             z_x,
             z_r,
-        }
+        })
     }
 
     /// Verifies a ciphertext-ciphertext equality proof.
@@ -146,7 +171,7 @@ impl CiphertextCiphertextEqualityProof {
         second_pubkey: &ElGamalPubkey,
         first_ciphertext: &ElGamalCiphertext,
         second_ciphertext: &ElGamalCiphertext,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), EqualityProofVerificationError> {
         // Reject if any public key or the first ciphertext is the identity point.
         // The second ciphertext is allowed to be the identity point, as this is
@@ -188,7 +213,7 @@ impl CiphertextCiphertextEqualityProof {
         transcript.append_scalar(b"z_s", &self.z_s);
         transcript.append_scalar(b"z_x", &self.z_x);
         transcript.append_scalar(b"z_r", &self.z_r);
-        let w = transcript.challenge_scalar(b"w"); // w used for batch verification
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
         let ww = &w * &w;
         let www = &w * &ww;
 
@@ -214,7 +239,7 @@ impl CiphertextCiphertextEqualityProof {
             .decompress()
             .ok_or(SigmaProofVerificationError::Deserialization)?;
 
-        let check = RistrettoPoint::vartime_multiscalar_mul(
+        let check = vartime_multiscalar_mul(
             vec![
                 &self.z_s,            // z_s
                 &(-&c),               // -c
@@ -261,7 +286,7 @@ impl CiphertextCiphertextEqualityProof {
         second_pubkey: &ElGamalPubkey,
         first_ciphertext: &ElGamalCiphertext,
         second_ciphertext: &ElGamalCiphertext,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"first-pubkey", &first_pubkey.to_bytes());
         transcript.append_message(b"second-pubkey", &second_pubkey.to_bytes());
@@ -269,35 +294,28 @@ impl CiphertextCiphertextEqualityProof {
         transcript.append_message(b"second-ciphertext", &second_ciphertext.to_bytes());
     }
 
-    pub fn to_bytes(&self) -> [u8; CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN] {
-        let mut buf = [0_u8; CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-
-        chunks.next().unwrap().copy_from_slice(self.Y_0.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_1.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_2.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_3.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_s.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_x.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_r.as_bytes());
-
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([
+            self.Y_0.to_bytes(),
+            self.Y_1.to_bytes(),
+            self.Y_2.to_bytes(),
+            self.Y_3.to_bytes(),
+            self.z_s.to_bytes(),
+            self.z_x.to_bytes(),
+            self.z_r.to_bytes(),
+        ])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, EqualityProofVerificationError> {
-        if bytes.len() != CIPHERTEXT_CIPHERTEXT_EQUALITY_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
-
-        let mut chunks = bytes.chunks(UNIT_LEN);
+        let [Y_0, Y_1, Y_2, Y_3, z_s, z_x, z_r] = bytes_to_units(bytes)?;
 
-        let Y_0 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_1 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_2 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_3 = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_s = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_x = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_r = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y_0 = CompressedRistretto(Y_0);
+        let Y_1 = CompressedRistretto(Y_1);
+        let Y_2 = CompressedRistretto(Y_2);
+        let Y_3 = CompressedRistretto(Y_3);
+        let z_s = canonical_scalar_from_bytes(z_s)?;
+        let z_x = canonical_scalar_from_bytes(z_x)?;
+        let z_r = canonical_scalar_from_bytes(z_r)?;
 
         Ok(CiphertextCiphertextEqualityProof {
             Y_0,
@@ -317,6 +335,12 @@ impl From<CiphertextCiphertextEqualityProof> for PodCiphertextCiphertextEquality
     }
 }
 
+impl From<&CiphertextCiphertextEqualityProof> for PodCiphertextCiphertextEqualityProof {
+    fn from(decoded_proof: &CiphertextCiphertextEqualityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodCiphertextCiphertextEqualityProof> for CiphertextCiphertextEqualityProof {
     type Error = EqualityProofVerificationError;
 
@@ -325,10 +349,19 @@ impl TryFrom<PodCiphertextCiphertextEqualityProof> for CiphertextCiphertextEqual
     }
 }
 
+impl TryFrom<&PodCiphertextCiphertextEqualityProof> for CiphertextCiphertextEqualityProof {
+    type Error = EqualityProofVerificationError;
+
+    fn try_from(pod_proof: &PodCiphertextCiphertextEqualityProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
             sigma_proofs::PodCiphertextCiphertextEqualityProof,
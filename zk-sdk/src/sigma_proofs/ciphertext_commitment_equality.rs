@@ -13,28 +13,27 @@ use {
         encryption::{
             elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
             pedersen::{PedersenCommitment, PedersenOpening, G, H},
+            secret_key_handle::{SecretKeyBackend, SecretKeyHandle},
         },
+        errors::{RngError, SecretKeyBackendError},
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBuffer},
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{EqualityProofVerificationError, SigmaProofVerificationError},
-            ristretto_point_from_optional_slice,
+            prover_commitment, units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
     curve25519_dalek::{
         ristretto::{CompressedRistretto, RistrettoPoint},
         scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+        traits::{Identity, IsIdentity},
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
     solana_zk_sdk_pod::{sigma_proofs::PodCiphertextCommitmentEqualityProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a ciphertext-commitment equality proof.
-const CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN: usize = UNIT_LEN * 6;
-
 /// Equality proof.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -51,6 +50,12 @@ pub struct CiphertextCommitmentEqualityProof {
 
 #[allow(non_snake_case)]
 impl CiphertextCommitmentEqualityProof {
+    /// Byte length of a ciphertext-commitment equality proof.
+    pub const LEN: usize = UNIT_LEN * 6;
+
+    /// Number of multiscalar multiplication terms in the verification equation.
+    pub(crate) const VERIFICATION_TERMS: usize = 11;
+
     /// Creates a ciphertext-commitment equality proof.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -70,8 +75,33 @@ impl CiphertextCommitmentEqualityProof {
         commitment: &PedersenCommitment,
         opening: &PedersenOpening,
         amount: u64,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            keypair,
+            ciphertext,
+            commitment,
+            opening,
+            amount,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a ciphertext-commitment equality proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        opening: &PedersenOpening,
+        amount: u64,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(keypair.pubkey(), ciphertext, commitment, transcript);
         transcript.ciphertext_commitment_equality_proof_domain_separator();
 
@@ -84,13 +114,13 @@ impl CiphertextCommitmentEqualityProof {
         let r = opening.get_scalar();
 
         // generate random masking factors that also serves as nonces
-        let mut y_s = Scalar::random(&mut OsRng);
-        let mut y_x = Scalar::random(&mut OsRng);
-        let mut y_r = Scalar::random(&mut OsRng);
+        let mut y_s = try_random_scalar(rng)?;
+        let mut y_x = try_random_scalar(rng)?;
+        let mut y_r = try_random_scalar(rng)?;
 
         let Y_0 = (&y_s * P).compress();
-        let Y_1 = RistrettoPoint::multiscalar_mul(vec![&y_x, &y_s], vec![&G, D]).compress();
-        let Y_2 = RistrettoPoint::multiscalar_mul(vec![&y_x, &y_r], vec![&G, &(*H)]).compress();
+        let Y_1 = prover_commitment([&y_x, &y_s], [&G, D]).compress();
+        let Y_2 = prover_commitment([&y_x, &y_r], [&G, &(*H)]).compress();
 
         // record masking factors in the transcript
         transcript.append_point(b"Y_0", &Y_0);
@@ -116,14 +146,90 @@ impl CiphertextCommitmentEqualityProof {
         y_x.zeroize();
         y_r.zeroize();
 
-        CiphertextCommitmentEqualityProof {
+        Ok(CiphertextCommitmentEqualityProof {
             Y_0,
             Y_1,
             Y_2,
             z_s,
             z_x,
             z_r,
-        }
+        })
+    }
+
+    /// Creates a ciphertext-commitment equality proof with a secret key held by an external
+    /// backend.
+    ///
+    /// The backend answers the sigma protocol round for the secret key, so the secret scalar and
+    /// its nonce never enter this crate. The remaining nonces are generated with [`DefaultRng`].
+    ///
+    /// * `secret_key_handle` - The handle to the secret key associated with the ciphertext
+    ///
+    /// See [`Self::new`] for a description of the remaining arguments.
+    pub fn new_with_secret_key_handle<B: SecretKeyBackend>(
+        secret_key_handle: &SecretKeyHandle<B>,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        opening: &PedersenOpening,
+        amount: u64,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, SecretKeyBackendError> {
+        Self::hash_context_into_transcript(
+            secret_key_handle.pubkey(),
+            ciphertext,
+            commitment,
+            transcript,
+        );
+        transcript.ciphertext_commitment_equality_proof_domain_separator();
+
+        let P = secret_key_handle.pubkey().get_point();
+        let D = ciphertext.handle.get_point();
+
+        let mut x = Scalar::from(amount);
+        let r = opening.get_scalar();
+
+        let mut y_x = try_random_scalar(&mut DefaultRng)?;
+        let mut y_r = try_random_scalar(&mut DefaultRng)?;
+
+        let Y_2 = prover_commitment([&y_x, &y_r], [&G, &(*H)]).compress();
+
+        // the backend commits to its nonce `y_s` on `P` and `D` and masks the secret key
+        let mut Y_0 = CompressedRistretto::default();
+        let mut Y_1 = CompressedRistretto::default();
+        let mut c = Scalar::ZERO;
+        let z_s = secret_key_handle
+            .backend()
+            .sigma_response(&[*P, *D], &mut |Y| {
+                Y_0 = Y[0].compress();
+                Y_1 = (&y_x * &G + Y[1]).compress();
+
+                transcript.append_point(b"Y_0", &Y_0);
+                transcript.append_point(b"Y_1", &Y_1);
+                transcript.append_point(b"Y_2", &Y_2);
+
+                c = transcript.challenge_scalar(b"c");
+                c
+            })?;
+
+        let z_x = &(&c * &x) + &y_x;
+        let z_r = &(&c * r) + &y_r;
+
+        transcript.append_scalar(b"z_s", &z_s);
+        transcript.append_scalar(b"z_x", &z_x);
+        transcript.append_scalar(b"z_r", &z_r);
+        let _w = transcript.challenge_scalar(b"w");
+
+        x.zeroize();
+        y_x.zeroize();
+        y_r.zeroize();
+
+        Ok(CiphertextCommitmentEqualityProof {
+            Y_0,
+            Y_1,
+            Y_2,
+            z_s,
+            z_x,
+            z_r,
+        })
     }
 
     /// Verifies a ciphertext-commitment equality proof.
@@ -137,7 +243,33 @@ impl CiphertextCommitmentEqualityProof {
         pubkey: &ElGamalPubkey,
         ciphertext: &ElGamalCiphertext,
         commitment: &PedersenCommitment,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), EqualityProofVerificationError> {
+        let mut scalars = [Scalar::ZERO; Self::VERIFICATION_TERMS];
+        let mut points = [RistrettoPoint::identity(); Self::VERIFICATION_TERMS];
+        let mut buffer = MultiscalarMulBuffer::new(&mut scalars, &mut points);
+        self.append_verification_terms(pubkey, ciphertext, commitment, transcript, &mut buffer)?;
+
+        if buffer
+            .multiscalar_mul(&CpuMultiscalarMulBackend)
+            .is_identity()
+        {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Appends the terms of the verification equation to a multiscalar multiplication buffer.
+    ///
+    /// The proof is valid if and only if the appended terms sum to the identity.
+    pub(crate) fn append_verification_terms(
+        &self,
+        pubkey: &ElGamalPubkey,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        transcript: &mut impl TranscriptProtocol,
+        buffer: &mut MultiscalarMulBuffer,
     ) -> Result<(), EqualityProofVerificationError> {
         if pubkey.get_point().is_identity()
             || ciphertext.commitment.get_point().is_identity()
@@ -166,7 +298,7 @@ impl CiphertextCommitmentEqualityProof {
         transcript.append_scalar(b"z_s", &self.z_s);
         transcript.append_scalar(b"z_x", &self.z_x);
         transcript.append_scalar(b"z_r", &self.z_r);
-        let w = transcript.challenge_scalar(b"w"); // w used for batch verification
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
         let ww = &w * &w;
 
         let w_negated = -&w;
@@ -186,77 +318,70 @@ impl CiphertextCommitmentEqualityProof {
             .decompress()
             .ok_or(SigmaProofVerificationError::Deserialization)?;
 
-        let check = RistrettoPoint::vartime_multiscalar_mul(
-            vec![
-                &self.z_s,           // z_s
-                &(-&c),              // -c
-                &(-&Scalar::ONE),    // -identity
-                &(&w * &self.z_x),   // w * z_x
-                &(&w * &self.z_s),   // w * z_s
-                &(&w_negated * &c),  // -w * c
-                &w_negated,          // -w
-                &(&ww * &self.z_x),  // ww * z_x
-                &(&ww * &self.z_r),  // ww * z_r
-                &(&ww_negated * &c), // -ww * c
-                &ww_negated,         // -ww
-            ],
-            vec![
-                P,            // P
-                &(*H),        // H
-                &Y_0,         // Y_0
-                &G,           // G
-                D,            // D
-                C_ciphertext, // C_ciphertext
-                &Y_1,         // Y_1
-                &G,           // G
-                &(*H),        // H
-                C_commitment, // C_commitment
-                &Y_2,         // Y_2
-            ],
-        );
-
-        if check.is_identity() {
-            Ok(())
-        } else {
-            Err(SigmaProofVerificationError::AlgebraicRelation.into())
-        }
+        let (scalars, points) = buffer
+            .reserve(Self::VERIFICATION_TERMS)
+            .ok_or(SigmaProofVerificationError::MultiscalarMul)?;
+        scalars.copy_from_slice(&[
+            self.z_s,       // z_s
+            -c,             // -c
+            -Scalar::ONE,   // -identity
+            w * self.z_x,   // w * z_x
+            w * self.z_s,   // w * z_s
+            w_negated * c,  // -w * c
+            w_negated,      // -w
+            ww * self.z_x,  // ww * z_x
+            ww * self.z_r,  // ww * z_r
+            ww_negated * c, // -ww * c
+            ww_negated,     // -ww
+        ]);
+        points.copy_from_slice(&[
+            *P,            // P
+            *H,            // H
+            Y_0,           // Y_0
+            G,             // G
+            *D,            // D
+            *C_ciphertext, // C_ciphertext
+            Y_1,           // Y_1
+            G,             // G
+            *H,            // H
+            *C_commitment, // C_commitment
+            Y_2,           // Y_2
+        ]);
+
+        Ok(())
     }
 
     fn hash_context_into_transcript(
         pubkey: &ElGamalPubkey,
         ciphertext: &ElGamalCiphertext,
         commitment: &PedersenCommitment,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"pubkey", &pubkey.to_bytes());
         transcript.append_message(b"ciphertext", &ciphertext.to_bytes());
         transcript.append_message(b"commitment", &commitment.to_bytes());
     }
 
-    pub fn to_bytes(&self) -> [u8; CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN] {
-        let mut buf = [0_u8; CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks.next().unwrap().copy_from_slice(self.Y_0.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_1.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_2.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_s.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_x.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_r.as_bytes());
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([
+            self.Y_0.to_bytes(),
+            self.Y_1.to_bytes(),
+            self.Y_2.to_bytes(),
+            self.z_s.to_bytes(),
+            self.z_x.to_bytes(),
+            self.z_r.to_bytes(),
+        ])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, EqualityProofVerificationError> {
-        if bytes.len() != CIPHERTEXT_COMMITMENT_EQUALITY_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        let [Y_0, Y_1, Y_2, z_s, z_x, z_r] = bytes_to_units(bytes)?;
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y_0 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_1 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_2 = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_s = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_x = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_r = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y_0 = CompressedRistretto(Y_0);
+        let Y_1 = CompressedRistretto(Y_1);
+        let Y_2 = CompressedRistretto(Y_2);
+        let z_s = canonical_scalar_from_bytes(z_s)?;
+        let z_x = canonical_scalar_from_bytes(z_x)?;
+        let z_r = canonical_scalar_from_bytes(z_r)?;
 
         Ok(CiphertextCommitmentEqualityProof {
             Y_0,
@@ -275,6 +400,12 @@ impl From<CiphertextCommitmentEqualityProof> for PodCiphertextCommitmentEquality
     }
 }
 
+impl From<&CiphertextCommitmentEqualityProof> for PodCiphertextCommitmentEqualityProof {
+    fn from(decoded_proof: &CiphertextCommitmentEqualityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodCiphertextCommitmentEqualityProof> for CiphertextCommitmentEqualityProof {
     type Error = EqualityProofVerificationError;
 
@@ -283,11 +414,20 @@ impl TryFrom<PodCiphertextCommitmentEqualityProof> for CiphertextCommitmentEqual
     }
 }
 
+impl TryFrom<&PodCiphertextCommitmentEqualityProof> for CiphertextCommitmentEqualityProof {
+    type Error = EqualityProofVerificationError;
+
+    fn try_from(pod_proof: &PodCiphertextCommitmentEqualityProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
         crate::encryption::{elgamal::ElGamalSecretKey, pedersen::Pedersen},
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::{
                 elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
@@ -0,0 +1,428 @@
+//! The opening-based ciphertext-commitment equality sigma proof system.
+//!
+//! An opening-based ciphertext-commitment equality proof is defined with respect to a twisted
+//! ElGamal ciphertext and a Pedersen commitment. Like the ciphertext-commitment equality proof, it
+//! certifies that the ciphertext and the commitment encrypt and encode the same message. Unlike
+//! that proof, a prover does not need the decryption key for the ciphertext. Instead, a prover
+//! must provide the Pedersen openings of the ciphertext and of the commitment. This covers the
+//! case where the party that encrypted the message, rather than the owner of the key, needs to
+//! prove consistency.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::{PedersenCommitment, PedersenOpening, G, H},
+        },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            bytes_to_units, canonical_scalar_from_bytes,
+            errors::{EqualityProofVerificationError, SigmaProofVerificationError},
+            prover_commitment, units_to_bytes,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::{sigma_proofs::PodCiphertextCommitmentOpeningEqualityProof, UNIT_LEN},
+    zeroize::Zeroize,
+};
+
+/// Opening-based equality proof.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct CiphertextCommitmentOpeningEqualityProof {
+    Y_0: CompressedRistretto,
+    Y_1: CompressedRistretto,
+    Y_2: CompressedRistretto,
+    z_x: Scalar,
+    z_r: Scalar,
+    z_s: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl CiphertextCommitmentOpeningEqualityProof {
+    /// Byte length of an opening-based ciphertext-commitment equality proof.
+    pub const LEN: usize = UNIT_LEN * 6;
+
+    /// Creates an opening-based ciphertext-commitment equality proof.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `pubkey` - The ElGamal pubkey under which the ciphertext is encrypted
+    /// * `ciphertext` - The ElGamal ciphertext to be proved
+    /// * `commitment` - The Pedersen commitment to be proved
+    /// * `ciphertext_opening` - The opening associated with the ElGamal ciphertext
+    /// * `commitment_opening` - The opening associated with the Pedersen commitment
+    /// * `amount` - The message associated with the ElGamal ciphertext and Pedersen commitment
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        pubkey: &ElGamalPubkey,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        ciphertext_opening: &PedersenOpening,
+        commitment_opening: &PedersenOpening,
+        amount: u64,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            pubkey,
+            ciphertext,
+            commitment,
+            ciphertext_opening,
+            commitment_opening,
+            amount,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates an opening-based ciphertext-commitment equality proof using `rng` to generate the
+    /// random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng(
+        pubkey: &ElGamalPubkey,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        ciphertext_opening: &PedersenOpening,
+        commitment_opening: &PedersenOpening,
+        amount: u64,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(pubkey, ciphertext, commitment, transcript);
+        transcript.ciphertext_commitment_opening_equality_proof_domain_separator();
+
+        // extract the relevant scalar and Ristretto points from the inputs
+        let P = pubkey.get_point();
+
+        let mut x = Scalar::from(amount);
+        let r = ciphertext_opening.get_scalar();
+        let s = commitment_opening.get_scalar();
+
+        // generate random masking factors that also serves as nonces
+        let mut y_x = try_random_scalar(rng)?;
+        let mut y_r = try_random_scalar(rng)?;
+        let mut y_s = try_random_scalar(rng)?;
+
+        let Y_0 = prover_commitment([&y_x, &y_r], [&G, &(*H)]).compress();
+        let Y_1 = (&y_r * P).compress();
+        let Y_2 = prover_commitment([&y_x, &y_s], [&G, &(*H)]).compress();
+
+        // record masking factors in the transcript
+        transcript.append_point(b"Y_0", &Y_0);
+        transcript.append_point(b"Y_1", &Y_1);
+        transcript.append_point(b"Y_2", &Y_2);
+
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute the masked values
+        let z_x = &(&c * &x) + &y_x;
+        let z_r = &(&c * r) + &y_r;
+        let z_s = &(&c * s) + &y_s;
+
+        // compute challenge `w` for consistency with verification
+        transcript.append_scalar(b"z_x", &z_x);
+        transcript.append_scalar(b"z_r", &z_r);
+        transcript.append_scalar(b"z_s", &z_s);
+        let _w = transcript.challenge_scalar(b"w");
+
+        // zeroize random scalars
+        x.zeroize();
+        y_x.zeroize();
+        y_r.zeroize();
+        y_s.zeroize();
+
+        Ok(CiphertextCommitmentOpeningEqualityProof {
+            Y_0,
+            Y_1,
+            Y_2,
+            z_x,
+            z_r,
+            z_s,
+        })
+    }
+
+    /// Verifies an opening-based ciphertext-commitment equality proof.
+    ///
+    /// * `pubkey` - The ElGamal pubkey under which the ciphertext is encrypted
+    /// * `ciphertext` - The ElGamal ciphertext to be proved
+    /// * `commitment` - The Pedersen commitment to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        pubkey: &ElGamalPubkey,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), EqualityProofVerificationError> {
+        if pubkey.get_point().is_identity()
+            || ciphertext.commitment.get_point().is_identity()
+            || ciphertext.handle.get_point().is_identity()
+            || commitment.get_point().is_identity()
+        {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(pubkey, ciphertext, commitment, transcript);
+        transcript.ciphertext_commitment_opening_equality_proof_domain_separator();
+
+        // extract the relevant scalar and Ristretto points from the inputs
+        let P = pubkey.get_point();
+        let C_ciphertext = ciphertext.commitment.get_point();
+        let D = ciphertext.handle.get_point();
+        let C_commitment = commitment.get_point();
+
+        // include Y_0, Y_1, Y_2 to transcript and extract challenges
+        transcript.validate_and_append_point(b"Y_0", &self.Y_0)?;
+        transcript.validate_and_append_point(b"Y_1", &self.Y_1)?;
+        transcript.validate_and_append_point(b"Y_2", &self.Y_2)?;
+
+        let c = transcript.challenge_scalar(b"c");
+
+        transcript.append_scalar(b"z_x", &self.z_x);
+        transcript.append_scalar(b"z_r", &self.z_r);
+        transcript.append_scalar(b"z_s", &self.z_s);
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
+        let ww = &w * &w;
+
+        let w_negated = -&w;
+        let ww_negated = -&ww;
+
+        // check that the required algebraic condition holds
+        let Y_0 = self
+            .Y_0
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let Y_1 = self
+            .Y_1
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let Y_2 = self
+            .Y_2
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+        let check = vartime_multiscalar_mul(
+            vec![
+                &self.z_x,           // z_x
+                &self.z_r,           // z_r
+                &(-&c),              // -c
+                &(-&Scalar::ONE),    // -identity
+                &(&w * &self.z_r),   // w * z_r
+                &(&w_negated * &c),  // -w * c
+                &w_negated,          // -w
+                &(&ww * &self.z_x),  // ww * z_x
+                &(&ww * &self.z_s),  // ww * z_s
+                &(&ww_negated * &c), // -ww * c
+                &ww_negated,         // -ww
+            ],
+            vec![
+                &G,           // G
+                &(*H),        // H
+                C_ciphertext, // C_ciphertext
+                &Y_0,         // Y_0
+                P,            // P
+                D,            // D
+                &Y_1,         // Y_1
+                &G,           // G
+                &(*H),        // H
+                C_commitment, // C_commitment
+                &Y_2,         // Y_2
+            ],
+        );
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        pubkey: &ElGamalPubkey,
+        ciphertext: &ElGamalCiphertext,
+        commitment: &PedersenCommitment,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        transcript.append_message(b"pubkey", &pubkey.to_bytes());
+        transcript.append_message(b"ciphertext", &ciphertext.to_bytes());
+        transcript.append_message(b"commitment", &commitment.to_bytes());
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([
+            self.Y_0.to_bytes(),
+            self.Y_1.to_bytes(),
+            self.Y_2.to_bytes(),
+            self.z_x.to_bytes(),
+            self.z_r.to_bytes(),
+            self.z_s.to_bytes(),
+        ])
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EqualityProofVerificationError> {
+        let [Y_0, Y_1, Y_2, z_x, z_r, z_s] = bytes_to_units(bytes)?;
+
+        let Y_0 = CompressedRistretto(Y_0);
+        let Y_1 = CompressedRistretto(Y_1);
+        let Y_2 = CompressedRistretto(Y_2);
+        let z_x = canonical_scalar_from_bytes(z_x)?;
+        let z_r = canonical_scalar_from_bytes(z_r)?;
+        let z_s = canonical_scalar_from_bytes(z_s)?;
+
+        Ok(CiphertextCommitmentOpeningEqualityProof {
+            Y_0,
+            Y_1,
+            Y_2,
+            z_x,
+            z_r,
+            z_s,
+        })
+    }
+}
+
+impl From<CiphertextCommitmentOpeningEqualityProof>
+    for PodCiphertextCommitmentOpeningEqualityProof
+{
+    fn from(decoded_proof: CiphertextCommitmentOpeningEqualityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl From<&CiphertextCommitmentOpeningEqualityProof>
+    for PodCiphertextCommitmentOpeningEqualityProof
+{
+    fn from(decoded_proof: &CiphertextCommitmentOpeningEqualityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl TryFrom<PodCiphertextCommitmentOpeningEqualityProof>
+    for CiphertextCommitmentOpeningEqualityProof
+{
+    type Error = EqualityProofVerificationError;
+
+    fn try_from(
+        pod_proof: PodCiphertextCommitmentOpeningEqualityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl TryFrom<&PodCiphertextCommitmentOpeningEqualityProof>
+    for CiphertextCommitmentOpeningEqualityProof
+{
+    type Error = EqualityProofVerificationError;
+
+    fn try_from(
+        pod_proof: &PodCiphertextCommitmentOpeningEqualityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::{
+            elgamal::ElGamalKeypair,
+            pedersen::{Pedersen, PedersenOpening},
+        },
+        merlin::Transcript,
+    };
+
+    #[test]
+    fn test_ciphertext_commitment_opening_equality_proof_correctness() {
+        // success case: the prover only knows the recipient pubkey and the openings
+        let pubkey = *ElGamalKeypair::new_rand().pubkey();
+        let message: u64 = 55;
+
+        let ciphertext_opening = PedersenOpening::new_rand();
+        let ciphertext = pubkey.encrypt_with(message, &ciphertext_opening);
+        let (commitment, commitment_opening) = Pedersen::new(message);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = CiphertextCommitmentOpeningEqualityProof::new(
+            &pubkey,
+            &ciphertext,
+            &commitment,
+            &ciphertext_opening,
+            &commitment_opening,
+            message,
+            &mut prover_transcript,
+        );
+
+        // round trip through the pod representation
+        let pod_proof = PodCiphertextCommitmentOpeningEqualityProof::from(&proof);
+        let decoded_proof = CiphertextCommitmentOpeningEqualityProof::try_from(pod_proof).unwrap();
+        assert_eq!(decoded_proof.to_bytes(), proof.to_bytes());
+
+        proof
+            .verify(&pubkey, &ciphertext, &commitment, &mut verifier_transcript)
+            .unwrap();
+
+        assert_eq!(
+            prover_transcript.challenge_scalar(b"test"),
+            verifier_transcript.challenge_scalar(b"test"),
+        );
+
+        // fail case: encrypted and committed messages are different
+        let encrypted_message: u64 = 55;
+        let committed_message: u64 = 77;
+
+        let ciphertext = pubkey.encrypt_with(encrypted_message, &ciphertext_opening);
+        let (commitment, commitment_opening) = Pedersen::new(committed_message);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = CiphertextCommitmentOpeningEqualityProof::new(
+            &pubkey,
+            &ciphertext,
+            &commitment,
+            &ciphertext_opening,
+            &commitment_opening,
+            encrypted_message,
+            &mut prover_transcript,
+        );
+
+        assert!(proof
+            .verify(&pubkey, &ciphertext, &commitment, &mut verifier_transcript)
+            .is_err());
+
+        // fail case: the ciphertext opening does not match the ciphertext
+        let ciphertext = pubkey.encrypt(message);
+        let (commitment, commitment_opening) = Pedersen::new(message);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+
+        let proof = CiphertextCommitmentOpeningEqualityProof::new(
+            &pubkey,
+            &ciphertext,
+            &commitment,
+            &ciphertext_opening,
+            &commitment_opening,
+            message,
+            &mut prover_transcript,
+        );
+
+        assert!(proof
+            .verify(&pubkey, &ciphertext, &commitment, &mut verifier_transcript)
+            .is_err());
+    }
+}
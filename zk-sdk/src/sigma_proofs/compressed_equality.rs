@@ -0,0 +1,697 @@
+//! The compressed ciphertext-commitment equality proof system.
+//!
+//! A compressed equality proof certifies a batch of ciphertext-commitment equality statements
+//! (see [`CiphertextCommitmentEqualityProof`]) with a single proof whose size is logarithmic in
+//! the number of statements.
+//!
+//! For statements `(P_i, (C_i, D_i), C'_i)`, the witnesses are the secret keys `s_i`, the amounts
+//! `x_i`, and the Pedersen openings `r_i`, which satisfy the linear relations
+//!
+//! ```text
+//! s_i * P_i = H,    x_i * G + s_i * D_i = C_i,    x_i * G + r_i * H = C'_i.
+//! ```
+//!
+//! The prover first commits to the vector of all witnesses with a vector Pedersen commitment.
+//! The verifier then combines all relations into a single linear form with the powers of a
+//! random challenge, and a sigma protocol proves that the committed vector satisfies the combined
+//! relation. Rather than sending the response vector of the sigma protocol, the prover proves
+//! knowledge of it with Bulletproof-style folding. A proof for `m` statements consists of
+//! `2 * log2(n) + 5` units, where `n` is `3 * m` rounded up to a power of two.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+//!
+//! [`CiphertextCommitmentEqualityProof`]: crate::sigma_proofs::ciphertext_commitment_equality::CiphertextCommitmentEqualityProof
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+            pedersen::{PedersenCommitment, PedersenOpening, G, H},
+        },
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            canonical_scalar_from_bytes,
+            errors::{
+                CompressedEqualityProofGenerationError, EqualityProofVerificationError,
+                SigmaProofVerificationError,
+            },
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{
+        ristretto::{CompressedRistretto, RistrettoPoint},
+        scalar::Scalar,
+        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+    },
+    sha3::{Digest, Sha3_512},
+    solana_zk_sdk_pod::UNIT_LEN,
+    std::iter,
+    zeroize::Zeroize,
+};
+
+/// Domain separator for the generators of the witness commitment.
+const WITNESS_GENERATORS_DOMAIN: &[u8] = b"compressed-equality-proof-generators";
+
+/// The number of witnesses of a single equality statement.
+const WITNESSES_PER_STATEMENT: usize = 3;
+
+/// The maximum number of statements of a compressed equality proof.
+pub const MAX_COMPRESSED_EQUALITY_STATEMENTS: usize = 1024;
+
+/// The maximum number of folding rounds of a compressed equality proof.
+const MAX_ROUNDS: usize = (WITNESSES_PER_STATEMENT * MAX_COMPRESSED_EQUALITY_STATEMENTS)
+    .next_power_of_two()
+    .ilog2() as usize;
+
+/// The public inputs of a ciphertext-commitment equality statement.
+#[derive(Clone, Copy)]
+pub struct CompressedEqualityStatement<'a> {
+    /// The ElGamal pubkey associated with the ciphertext
+    pub pubkey: &'a ElGamalPubkey,
+    /// The ElGamal ciphertext
+    pub ciphertext: &'a ElGamalCiphertext,
+    /// The Pedersen commitment that encodes the same amount as the ciphertext
+    pub commitment: &'a PedersenCommitment,
+}
+
+/// The public and secret inputs of a ciphertext-commitment equality statement.
+#[derive(Clone, Copy)]
+pub struct CompressedEqualityWitness<'a> {
+    /// The ElGamal keypair associated with the ciphertext
+    pub keypair: &'a ElGamalKeypair,
+    /// The ElGamal ciphertext
+    pub ciphertext: &'a ElGamalCiphertext,
+    /// The Pedersen commitment that encodes the same amount as the ciphertext
+    pub commitment: &'a PedersenCommitment,
+    /// The opening of the Pedersen commitment
+    pub opening: &'a PedersenOpening,
+    /// The amount that is encrypted by the ciphertext and encoded by the commitment
+    pub amount: u64,
+}
+
+impl<'a> CompressedEqualityWitness<'a> {
+    /// The public inputs of the statement.
+    pub fn statement(&self) -> CompressedEqualityStatement<'a> {
+        CompressedEqualityStatement {
+            pubkey: self.keypair.pubkey(),
+            ciphertext: self.ciphertext,
+            commitment: self.commitment,
+        }
+    }
+}
+
+/// Compressed ciphertext-commitment equality proof.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct CompressedEqualityProof {
+    W: CompressedRistretto,
+    A: CompressedRistretto,
+    T: CompressedRistretto,
+    phi: Scalar,
+    L_vec: Vec<CompressedRistretto>,
+    R_vec: Vec<CompressedRistretto>,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl CompressedEqualityProof {
+    /// Byte length of a compressed equality proof of `num_statements` statements.
+    pub fn proof_len(num_statements: usize) -> usize {
+        (2 * rounds(num_statements) + 5) * UNIT_LEN
+    }
+
+    /// Creates a compressed equality proof.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// Returns an error if the number of statements is zero or exceeds
+    /// [`MAX_COMPRESSED_EQUALITY_STATEMENTS`].
+    ///
+    /// * `witnesses` - The statements to be proved along with their secret inputs
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        witnesses: &[CompressedEqualityWitness],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, CompressedEqualityProofGenerationError> {
+        Self::new_with_rng(witnesses, transcript, &mut DefaultRng)
+    }
+
+    /// Creates a compressed equality proof using `rng` to generate the random scalars.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        witnesses: &[CompressedEqualityWitness],
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, CompressedEqualityProofGenerationError> {
+        if witnesses.is_empty() || witnesses.len() > MAX_COMPRESSED_EQUALITY_STATEMENTS {
+            return Err(CompressedEqualityProofGenerationError::InvalidStatementCount);
+        }
+
+        let statements = witnesses
+            .iter()
+            .map(CompressedEqualityWitness::statement)
+            .collect::<Vec<_>>();
+        Self::hash_context_into_transcript(&statements, transcript);
+        transcript.compressed_equality_proof_domain_separator(statements.len() as u64);
+
+        let n = padded_len(statements.len());
+        let generators = witness_generators(n);
+
+        // the witness vector holds `(s_i, x_i, r_i)` for each statement, padded with zeros
+        let mut w = vec![Scalar::ZERO; n];
+        for (chunk, witness) in w.chunks_exact_mut(WITNESSES_PER_STATEMENT).zip(witnesses) {
+            chunk[0] = *witness.keypair.secret().get_scalar();
+            chunk[1] = Scalar::from(witness.amount);
+            chunk[2] = *witness.opening.get_scalar();
+        }
+
+        // commit to the witness vector
+        let mut gamma = try_random_scalar(rng)?;
+        let W = RistrettoPoint::multiscalar_mul(
+            w.iter().chain(iter::once(&gamma)),
+            generators.iter().chain(iter::once(&*H)),
+        )
+        .compress();
+        transcript.append_point(b"W", &W);
+
+        // combine the relations of all statements into a single linear form
+        let rho = transcript.challenge_scalar(b"rho");
+        let bases = linear_form_bases(&statements, &rho, n);
+
+        // generate random masking factors that also serve as nonces
+        let mut y = (0..n)
+            .map(|_| try_random_scalar(rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut delta = try_random_scalar(rng)?;
+
+        let A = RistrettoPoint::multiscalar_mul(
+            y.iter().chain(iter::once(&delta)),
+            generators.iter().chain(iter::once(&*H)),
+        )
+        .compress();
+        let T = RistrettoPoint::multiscalar_mul(&y, &bases).compress();
+        transcript.append_point(b"A", &A);
+        transcript.append_point(b"T", &T);
+
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute the masked values
+        let mut z = y
+            .iter()
+            .zip(&w)
+            .map(|(y_i, w_i)| y_i + c * w_i)
+            .collect::<Vec<_>>();
+        let phi = delta + c * gamma;
+        transcript.append_scalar(b"phi", &phi);
+
+        // the response vector `z` must open both `A + c * W` and the linear form; the two
+        // relations are combined with the challenge `beta` into a single inner product
+        let beta = transcript.challenge_scalar(b"beta");
+        let mut V = generators
+            .iter()
+            .zip(&bases)
+            .map(|(g, b)| g + beta * b)
+            .collect::<Vec<_>>();
+
+        // fold the response vector; `z` is a sigma protocol response and is not secret
+        let mut L_vec = Vec::with_capacity(rounds(statements.len()));
+        let mut R_vec = Vec::with_capacity(rounds(statements.len()));
+        let mut len = n;
+        while len > 1 {
+            len /= 2;
+            let (z_L, z_R) = z.split_at(len);
+            let (V_L, V_R) = V.split_at(len);
+
+            let L = RistrettoPoint::vartime_multiscalar_mul(z_L, V_R).compress();
+            let R = RistrettoPoint::vartime_multiscalar_mul(z_R, V_L).compress();
+            transcript.append_point(b"L", &L);
+            transcript.append_point(b"R", &R);
+            L_vec.push(L);
+            R_vec.push(R);
+
+            let u = transcript.challenge_scalar(b"u");
+            for i in 0..len {
+                z[i] = z[i] + u * z[len + i];
+                V[i] = u * V[i] + V[len + i];
+            }
+            z.truncate(len);
+            V.truncate(len);
+        }
+        let z = z[0];
+        transcript.append_scalar(b"z", &z);
+
+        // zeroize all sensitive owned variables
+        w.zeroize();
+        y.zeroize();
+        gamma.zeroize();
+        delta.zeroize();
+
+        Ok(CompressedEqualityProof {
+            W,
+            A,
+            T,
+            phi,
+            L_vec,
+            R_vec,
+            z,
+        })
+    }
+
+    /// Verifies a compressed equality proof.
+    ///
+    /// * `statements` - The statements to be verified, in the order in which they were proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        &self,
+        statements: &[CompressedEqualityStatement],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), EqualityProofVerificationError> {
+        if statements.is_empty()
+            || statements.len() > MAX_COMPRESSED_EQUALITY_STATEMENTS
+            || self.L_vec.len() != rounds(statements.len())
+        {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        }
+        if statements.iter().any(|statement| {
+            statement.pubkey.get_point().is_identity()
+                || statement.ciphertext.commitment.get_point().is_identity()
+                || statement.ciphertext.handle.get_point().is_identity()
+                || statement.commitment.get_point().is_identity()
+        }) {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(statements, transcript);
+        transcript.compressed_equality_proof_domain_separator(statements.len() as u64);
+
+        let n = padded_len(statements.len());
+
+        transcript.validate_and_append_point(b"W", &self.W)?;
+        let rho = transcript.challenge_scalar(b"rho");
+        transcript.validate_and_append_point(b"A", &self.A)?;
+        transcript.validate_and_append_point(b"T", &self.T)?;
+        let c = transcript.challenge_scalar(b"c");
+        transcript.append_scalar(b"phi", &self.phi);
+        let beta = transcript.challenge_scalar(b"beta");
+
+        let mut challenges = Vec::with_capacity(self.L_vec.len());
+        for (L, R) in self.L_vec.iter().zip(&self.R_vec) {
+            transcript.validate_and_append_point(b"L", L)?;
+            transcript.validate_and_append_point(b"R", R)?;
+            challenges.push(transcript.challenge_scalar(b"u"));
+        }
+        transcript.append_scalar(b"z", &self.z);
+
+        // The folded relation is `z * <s, V> = u_prod * Q + sum_j u_suffix_j * (L_j + u_j^2 R_j)`,
+        // where `Q = A + c * W + beta * (T + c * Y) - phi * H`, `Y` is the value of the linear
+        // form, `s_k` is the product of the challenges of the rounds in which `V_k` is in the left
+        // half, and `u_suffix_j` is the product of the challenges after round `j`.
+        let s = folding_scalars(&challenges, n);
+        let mut suffix_products = vec![Scalar::ONE; challenges.len()];
+        for j in (0..challenges.len().saturating_sub(1)).rev() {
+            suffix_products[j] = suffix_products[j + 1] * challenges[j + 1];
+        }
+        let u_prod = challenges.iter().product::<Scalar>();
+
+        let z_beta = self.z * beta;
+        let mut g_scalar = Scalar::ZERO;
+        let mut h_scalar = u_prod * self.phi;
+
+        let mut scalars = Vec::with_capacity(n + 4 * statements.len() + 2 * challenges.len() + 5);
+        let mut points = Vec::with_capacity(n + 4 * statements.len() + 2 * challenges.len() + 5);
+
+        let generators = witness_generators(n);
+        for (s_k, g_k) in s.iter().zip(&generators) {
+            scalars.push(self.z * s_k);
+            points.push(*g_k);
+        }
+
+        let y_scalar = -u_prod * beta * c;
+        for ((statement, weights), s_i) in statements
+            .iter()
+            .zip(RelationWeights::new(&rho))
+            .zip(s.chunks_exact(WITNESSES_PER_STATEMENT))
+        {
+            // `z * beta * <s, B>` for the linear form bases of the statement
+            scalars.push(z_beta * s_i[0] * weights.pubkey);
+            points.push(*statement.pubkey.get_point());
+            scalars.push(z_beta * s_i[0] * weights.ciphertext);
+            points.push(*statement.ciphertext.handle.get_point());
+            g_scalar += z_beta * s_i[1] * (weights.ciphertext + weights.commitment);
+            h_scalar += z_beta * s_i[2] * weights.commitment;
+
+            // `-u_prod * beta * c * Y` for the value of the linear form of the statement
+            h_scalar += y_scalar * weights.pubkey;
+            scalars.push(y_scalar * weights.ciphertext);
+            points.push(*statement.ciphertext.commitment.get_point());
+            scalars.push(y_scalar * weights.commitment);
+            points.push(*statement.commitment.get_point());
+        }
+
+        let W = self
+            .W
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let A = self
+            .A
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let T = self
+            .T
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        scalars.extend_from_slice(&[-u_prod, -u_prod * c, -u_prod * beta, g_scalar, h_scalar]);
+        points.extend_from_slice(&[A, W, T, G, *H]);
+
+        for (((L, R), u), suffix_product) in self
+            .L_vec
+            .iter()
+            .zip(&self.R_vec)
+            .zip(&challenges)
+            .zip(&suffix_products)
+        {
+            scalars.push(-suffix_product);
+            points.push(
+                L.decompress()
+                    .ok_or(SigmaProofVerificationError::Deserialization)?,
+            );
+            scalars.push(-suffix_product * u * u);
+            points.push(
+                R.decompress()
+                    .ok_or(SigmaProofVerificationError::Deserialization)?,
+            );
+        }
+
+        let check = vartime_multiscalar_mul(scalars, points);
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        statements: &[CompressedEqualityStatement],
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        for statement in statements {
+            transcript.append_message(b"pubkey", &statement.pubkey.to_bytes());
+            transcript.append_message(b"ciphertext", &statement.ciphertext.to_bytes());
+            transcript.append_message(b"commitment", &statement.commitment.to_bytes());
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity((2 * self.L_vec.len() + 5) * UNIT_LEN);
+        buf.extend_from_slice(self.W.as_bytes());
+        buf.extend_from_slice(self.A.as_bytes());
+        buf.extend_from_slice(self.T.as_bytes());
+        buf.extend_from_slice(self.phi.as_bytes());
+        for (L, R) in self.L_vec.iter().zip(&self.R_vec) {
+            buf.extend_from_slice(L.as_bytes());
+            buf.extend_from_slice(R.as_bytes());
+        }
+        buf.extend_from_slice(self.z.as_bytes());
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EqualityProofVerificationError> {
+        let (units, []) = bytes.as_chunks::<UNIT_LEN>() else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        let [W, A, T, phi, folding @ .., z] = units else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        let (folding, []) = folding.as_chunks::<2>() else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        if folding.len() > MAX_ROUNDS {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        }
+
+        Ok(CompressedEqualityProof {
+            W: CompressedRistretto(*W),
+            A: CompressedRistretto(*A),
+            T: CompressedRistretto(*T),
+            phi: canonical_scalar_from_bytes(*phi)?,
+            L_vec: folding
+                .iter()
+                .map(|[L, _]| CompressedRistretto(*L))
+                .collect(),
+            R_vec: folding
+                .iter()
+                .map(|[_, R]| CompressedRistretto(*R))
+                .collect(),
+            z: canonical_scalar_from_bytes(*z)?,
+        })
+    }
+}
+
+/// The length of the witness vector of `num_statements` statements.
+fn padded_len(num_statements: usize) -> usize {
+    (WITNESSES_PER_STATEMENT * num_statements).next_power_of_two()
+}
+
+/// The number of folding rounds of a proof of `num_statements` statements.
+fn rounds(num_statements: usize) -> usize {
+    padded_len(num_statements).ilog2() as usize
+}
+
+/// Derives the `n` generators of the witness commitment.
+///
+/// The discrete log relation between the generators, or between the generators and `G` and `H`,
+/// is unknown.
+fn witness_generators(n: usize) -> Vec<RistrettoPoint> {
+    (0..n as u64)
+        .map(|index| {
+            let mut hasher = Sha3_512::new();
+            hasher.update(WITNESS_GENERATORS_DOMAIN);
+            hasher.update(index.to_le_bytes());
+            RistrettoPoint::from_hash(hasher)
+        })
+        .collect()
+}
+
+/// The weights of the three relations of a statement in the combined linear form.
+struct RelationWeights {
+    /// The weight of `s_i * P_i = H`
+    pubkey: Scalar,
+    /// The weight of `x_i * G + s_i * D_i = C_i`
+    ciphertext: Scalar,
+    /// The weight of `x_i * G + r_i * H = C'_i`
+    commitment: Scalar,
+}
+
+impl RelationWeights {
+    /// Returns the weights of consecutive statements, which are consecutive powers of `rho`.
+    fn new(rho: &Scalar) -> impl Iterator<Item = Self> + '_ {
+        iter::successors(Some(Scalar::ONE), move |power| {
+            Some(power * rho * rho * rho)
+        })
+        .map(move |power| {
+            let ciphertext = power * rho;
+            RelationWeights {
+                pubkey: power,
+                ciphertext,
+                commitment: ciphertext * rho,
+            }
+        })
+    }
+}
+
+/// Computes the bases of the combined linear form over the witness vector.
+fn linear_form_bases(
+    statements: &[CompressedEqualityStatement],
+    rho: &Scalar,
+    n: usize,
+) -> Vec<RistrettoPoint> {
+    let mut bases = vec![RistrettoPoint::default(); n];
+    for ((chunk, statement), weights) in bases
+        .chunks_exact_mut(WITNESSES_PER_STATEMENT)
+        .zip(statements)
+        .zip(RelationWeights::new(rho))
+    {
+        chunk[0] = RistrettoPoint::vartime_multiscalar_mul(
+            [weights.pubkey, weights.ciphertext],
+            [
+                statement.pubkey.get_point(),
+                statement.ciphertext.handle.get_point(),
+            ],
+        );
+        chunk[1] = (weights.ciphertext + weights.commitment) * G;
+        chunk[2] = weights.commitment * *H;
+    }
+    bases
+}
+
+/// Computes the coefficients of the original bases in the folded base.
+///
+/// In round `j`, the bases in the left half are multiplied by the challenge `u_j`, so the
+/// coefficient of the base at `index` is the product of the challenges of the rounds in which the
+/// corresponding bit of `index`, from most to least significant, is zero.
+fn folding_scalars(challenges: &[Scalar], n: usize) -> Vec<Scalar> {
+    let rounds = challenges.len();
+    (0..n)
+        .map(|index| {
+            challenges
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| (index >> (rounds - 1 - j)) & 1 == 0)
+                .map(|(_, u)| u)
+                .product()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::pedersen::Pedersen, merlin::Transcript};
+
+    struct TestStatement {
+        keypair: ElGamalKeypair,
+        ciphertext: ElGamalCiphertext,
+        commitment: PedersenCommitment,
+        opening: PedersenOpening,
+        amount: u64,
+    }
+
+    impl TestStatement {
+        fn new(amount: u64) -> Self {
+            let keypair = ElGamalKeypair::new_rand();
+            let ciphertext = keypair.pubkey().encrypt(amount);
+            let (commitment, opening) = Pedersen::new(amount);
+            Self {
+                keypair,
+                ciphertext,
+                commitment,
+                opening,
+                amount,
+            }
+        }
+
+        fn witness(&self) -> CompressedEqualityWitness<'_> {
+            CompressedEqualityWitness {
+                keypair: &self.keypair,
+                ciphertext: &self.ciphertext,
+                commitment: &self.commitment,
+                opening: &self.opening,
+                amount: self.amount,
+            }
+        }
+    }
+
+    fn prove_and_verify(
+        test_statements: &[TestStatement],
+    ) -> Result<(), EqualityProofVerificationError> {
+        let witnesses = test_statements
+            .iter()
+            .map(TestStatement::witness)
+            .collect::<Vec<_>>();
+        let statements = witnesses
+            .iter()
+            .map(CompressedEqualityWitness::statement)
+            .collect::<Vec<_>>();
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = CompressedEqualityProof::new(&witnesses, &mut prover_transcript).unwrap();
+        assert_eq!(
+            proof.to_bytes().len(),
+            CompressedEqualityProof::proof_len(statements.len())
+        );
+        proof.verify(&statements, &mut verifier_transcript)
+    }
+
+    #[test]
+    fn test_compressed_equality_proof_correctness() {
+        for num_statements in [1, 2, 5, 33] {
+            let test_statements = (0..num_statements)
+                .map(|i| TestStatement::new(55 * i as u64))
+                .collect::<Vec<_>>();
+            assert!(prove_and_verify(&test_statements).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_compressed_equality_proof_rejects_inconsistent_statement() {
+        let mut test_statements = (0..4).map(TestStatement::new).collect::<Vec<_>>();
+
+        // the commitment of one statement encodes a different amount than its ciphertext
+        let (commitment, opening) = Pedersen::new(77_u64);
+        test_statements[2].commitment = commitment;
+        test_statements[2].opening = opening;
+        assert!(prove_and_verify(&test_statements).is_err());
+    }
+
+    #[test]
+    fn test_compressed_equality_proof_statement_binding() {
+        let test_statements = (0..3).map(TestStatement::new).collect::<Vec<_>>();
+        let witnesses = test_statements
+            .iter()
+            .map(TestStatement::witness)
+            .collect::<Vec<_>>();
+        let statements = witnesses
+            .iter()
+            .map(CompressedEqualityWitness::statement)
+            .collect::<Vec<_>>();
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let proof = CompressedEqualityProof::new(&witnesses, &mut prover_transcript).unwrap();
+
+        // the statements cannot be reordered
+        let mut reordered = statements.clone();
+        reordered.swap(0, 1);
+        let mut verifier_transcript = Transcript::new(b"test");
+        assert!(proof.verify(&reordered, &mut verifier_transcript).is_err());
+
+        // the proof does not verify for a subset of the statements
+        let mut verifier_transcript = Transcript::new(b"test");
+        assert!(proof
+            .verify(&statements[..2], &mut verifier_transcript)
+            .is_err());
+
+        let mut verifier_transcript = Transcript::new(b"test");
+        assert!(proof.verify(&statements, &mut verifier_transcript).is_ok());
+    }
+
+    #[test]
+    fn test_compressed_equality_proof_bytes() {
+        let test_statements = (0..5).map(TestStatement::new).collect::<Vec<_>>();
+        let witnesses = test_statements
+            .iter()
+            .map(TestStatement::witness)
+            .collect::<Vec<_>>();
+        let statements = witnesses
+            .iter()
+            .map(CompressedEqualityWitness::statement)
+            .collect::<Vec<_>>();
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let proof = CompressedEqualityProof::new(&witnesses, &mut prover_transcript).unwrap();
+        let bytes = proof.to_bytes();
+
+        let decoded = CompressedEqualityProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        let mut verifier_transcript = Transcript::new(b"test");
+        assert!(decoded
+            .verify(&statements, &mut verifier_transcript)
+            .is_ok());
+
+        assert!(CompressedEqualityProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CompressedEqualityProof::from_bytes(&bytes[..bytes.len() - UNIT_LEN]).is_err());
+        assert!(CompressedEqualityProof::from_bytes(&[0; UNIT_LEN * 4]).is_err());
+    }
+
+    #[test]
+    fn test_compressed_equality_proof_statement_count() {
+        let mut transcript = Transcript::new(b"test");
+        assert!(matches!(
+            CompressedEqualityProof::new(&[], &mut transcript),
+            Err(CompressedEqualityProofGenerationError::InvalidStatementCount)
+        ));
+    }
+}
@@ -1,5 +1,8 @@
 //! Errors related to proving and verifying sigma proofs.
-use {crate::errors::TranscriptError, thiserror::Error};
+use {
+    crate::errors::{RngError, TranscriptError},
+    thiserror::Error,
+};
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum SigmaProofVerificationError {
@@ -13,6 +16,8 @@ pub enum SigmaProofVerificationError {
     Transcript(#[from] TranscriptError),
     #[error("input point is the identity")]
     IdentityPoint,
+    #[error("the numbers of proofs, statements, and transcripts differ")]
+    BatchLength,
 }
 
 macro_rules! impl_from_transcript_error {
@@ -25,11 +30,24 @@ macro_rules! impl_from_transcript_error {
     };
 }
 
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("split consistency proof verification failed: {0}")]
+pub struct SplitConsistencyProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
+impl_from_transcript_error!(SplitConsistencyProofVerificationError);
+
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 #[error("equality proof verification failed: {0}")]
 pub struct EqualityProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
 impl_from_transcript_error!(EqualityProofVerificationError);
 
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum CompressedEqualityProofGenerationError {
+    #[error("the number of statements is zero or exceeds the maximum")]
+    InvalidStatementCount,
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
+}
+
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 #[error("validity proof verification failed: {0}")]
 pub struct ValidityProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
@@ -49,3 +67,28 @@ impl_from_transcript_error!(PercentageWithCapProofVerificationError);
 #[error("public key validity proof verification failed: {0}")]
 pub struct PubkeyValidityProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
 impl_from_transcript_error!(PubkeyValidityProofVerificationError);
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("asset equality proof verification failed: {0}")]
+pub struct AssetEqualityProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
+impl_from_transcript_error!(AssetEqualityProofVerificationError);
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum KeyPrivateValidityProofGenerationError {
+    #[error("the anonymity set is empty or exceeds the maximum size")]
+    InvalidAnonymitySetSize,
+    #[error("the recipient index is not a position in the anonymity set")]
+    IndexOutOfBounds,
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
+}
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("key-private validity proof verification failed: {0}")]
+pub struct KeyPrivateValidityProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
+impl_from_transcript_error!(KeyPrivateValidityProofVerificationError);
+
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("partial decryption proof verification failed: {0}")]
+pub struct PartialDecryptionProofVerificationError(#[from] pub(crate) SigmaProofVerificationError);
+impl_from_transcript_error!(PartialDecryptionProofVerificationError);
@@ -15,27 +15,24 @@ use {
             grouped_elgamal::GroupedElGamalCiphertext,
             pedersen::{PedersenOpening, G, H},
         },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{SigmaProofVerificationError, ValidityProofVerificationError},
-            ristretto_point_from_optional_slice,
+            prover_commitment, units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
-    curve25519_dalek::{
-        ristretto::{CompressedRistretto, RistrettoPoint},
-        scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::{
+        encryption::grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
+        sigma_proofs::PodGroupedCiphertext2HandlesValidityProof, UNIT_LEN,
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
-    solana_zk_sdk_pod::{sigma_proofs::PodGroupedCiphertext2HandlesValidityProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a grouped ciphertext validity proof for 2 handles
-const GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN: usize = UNIT_LEN * 5;
-
 /// The grouped ciphertext validity proof for 2 handles.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -51,6 +48,9 @@ pub struct GroupedCiphertext2HandlesValidityProof {
 
 #[allow(non_snake_case)]
 impl GroupedCiphertext2HandlesValidityProof {
+    /// Byte length of a grouped ciphertext validity proof for 2 handles
+    pub const LEN: usize = UNIT_LEN * 5;
+
     /// Creates a grouped ciphertext validity proof for 2 handles.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -67,15 +67,47 @@ impl GroupedCiphertext2HandlesValidityProof {
         grouped_ciphertext: &GroupedElGamalCiphertext<2>,
         amount: T,
         opening: &PedersenOpening,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a grouped ciphertext validity proof for 2 handles using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng<T: Into<Scalar>>(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+        amount: T,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             first_pubkey,
             second_pubkey,
             grouped_ciphertext,
             transcript,
         );
-        Self::new_direct(first_pubkey, second_pubkey, amount, opening, transcript)
+        Self::new_direct(
+            first_pubkey,
+            second_pubkey,
+            amount,
+            opening,
+            transcript,
+            rng,
+        )
     }
 
     pub(crate) fn new_direct<T: Into<Scalar>>(
@@ -83,8 +115,9 @@ impl GroupedCiphertext2HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         amount: T,
         opening: &PedersenOpening,
-        transcript: &mut Transcript,
-    ) -> Self {
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         transcript.grouped_ciphertext_validity_proof_domain_separator(2);
 
         // extract the relevant scalar and Ristretto points from the inputs
@@ -95,10 +128,10 @@ impl GroupedCiphertext2HandlesValidityProof {
         let r = opening.get_scalar();
 
         // generate random masking factors that also serves as nonces
-        let mut y_r = Scalar::random(&mut OsRng);
-        let mut y_x = Scalar::random(&mut OsRng);
+        let mut y_r = try_random_scalar(rng)?;
+        let mut y_x = try_random_scalar(rng)?;
 
-        let Y_0 = RistrettoPoint::multiscalar_mul(vec![&y_r, &y_x], vec![&(*H), &G]).compress();
+        let Y_0 = prover_commitment([&y_r, &y_x], [&(*H), &G]).compress();
         let Y_1 = (&y_r * P_first).compress();
         let Y_2 = (&y_r * P_second).compress();
 
@@ -123,13 +156,13 @@ impl GroupedCiphertext2HandlesValidityProof {
         y_r.zeroize();
         y_x.zeroize();
 
-        Self {
+        Ok(Self {
             Y_0,
             Y_1,
             Y_2,
             z_r,
             z_x,
-        }
+        })
     }
 
     /// Verifies a grouped ciphertext validity proof for 2 handles.
@@ -143,7 +176,7 @@ impl GroupedCiphertext2HandlesValidityProof {
         first_pubkey: &ElGamalPubkey,
         second_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<2>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), ValidityProofVerificationError> {
         // We reject if the first public key or the commitment is the identity point.
         // We allow the second public key to be an identity point as it is often the auditor's
@@ -168,7 +201,7 @@ impl GroupedCiphertext2HandlesValidityProof {
         first_pubkey: &ElGamalPubkey,
         second_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<2>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), ValidityProofVerificationError> {
         transcript.grouped_ciphertext_validity_proof_domain_separator(2);
 
@@ -182,7 +215,7 @@ impl GroupedCiphertext2HandlesValidityProof {
 
         transcript.append_scalar(b"z_r", &self.z_r);
         transcript.append_scalar(b"z_x", &self.z_x);
-        let w = transcript.challenge_scalar(b"w");
+        let w = transcript.batching_weight(b"w");
         let ww = &w * &w;
 
         let w_negated = -&w;
@@ -212,7 +245,7 @@ impl GroupedCiphertext2HandlesValidityProof {
         let D_first = first_handle.get_point();
         let D_second = second_handle.get_point();
 
-        let check = RistrettoPoint::vartime_multiscalar_mul(
+        let check = vartime_multiscalar_mul(
             vec![
                 &self.z_r,           // z_r
                 &self.z_x,           // z_x
@@ -250,35 +283,34 @@ impl GroupedCiphertext2HandlesValidityProof {
         first_pubkey: &ElGamalPubkey,
         second_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<2>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"first-pubkey", &first_pubkey.to_bytes());
         transcript.append_message(b"second-pubkey", &second_pubkey.to_bytes());
-        transcript.append_message(b"grouped-ciphertext", &grouped_ciphertext.to_bytes());
+        transcript.append_message(
+            b"grouped-ciphertext",
+            &PodGroupedElGamalCiphertext2Handles::from(*grouped_ciphertext).0,
+        );
     }
 
-    pub fn to_bytes(&self) -> [u8; GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN] {
-        let mut buf = [0_u8; GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks.next().unwrap().copy_from_slice(self.Y_0.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_1.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_2.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_r.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_x.as_bytes());
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([
+            self.Y_0.to_bytes(),
+            self.Y_1.to_bytes(),
+            self.Y_2.to_bytes(),
+            self.z_r.to_bytes(),
+            self.z_x.to_bytes(),
+        ])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ValidityProofVerificationError> {
-        if bytes.len() != GROUPED_CIPHERTEXT_2_HANDLES_VALIDITY_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        let [Y_0, Y_1, Y_2, z_r, z_x] = bytes_to_units(bytes)?;
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y_0 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_1 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_2 = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_r = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_x = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y_0 = CompressedRistretto(Y_0);
+        let Y_1 = CompressedRistretto(Y_1);
+        let Y_2 = CompressedRistretto(Y_2);
+        let z_r = canonical_scalar_from_bytes(z_r)?;
+        let z_x = canonical_scalar_from_bytes(z_x)?;
 
         Ok(GroupedCiphertext2HandlesValidityProof {
             Y_0,
@@ -296,6 +328,12 @@ impl From<GroupedCiphertext2HandlesValidityProof> for PodGroupedCiphertext2Handl
     }
 }
 
+impl From<&GroupedCiphertext2HandlesValidityProof> for PodGroupedCiphertext2HandlesValidityProof {
+    fn from(decoded_proof: &GroupedCiphertext2HandlesValidityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodGroupedCiphertext2HandlesValidityProof> for GroupedCiphertext2HandlesValidityProof {
     type Error = ValidityProofVerificationError;
 
@@ -304,6 +342,18 @@ impl TryFrom<PodGroupedCiphertext2HandlesValidityProof> for GroupedCiphertext2Ha
     }
 }
 
+impl TryFrom<&PodGroupedCiphertext2HandlesValidityProof>
+    for GroupedCiphertext2HandlesValidityProof
+{
+    type Error = ValidityProofVerificationError;
+
+    fn try_from(
+        pod_proof: &PodGroupedCiphertext2HandlesValidityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
@@ -312,6 +362,7 @@ mod test {
             elgamal::ElGamalKeypair,
             pedersen::{Pedersen, PedersenCommitment},
         },
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::{
                 elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext2Handles,
@@ -15,27 +15,28 @@ use {
             grouped_elgamal::GroupedElGamalCiphertext,
             pedersen::{PedersenOpening, G, H},
         },
+        errors::RngError,
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBuffer},
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{SigmaProofVerificationError, ValidityProofVerificationError},
-            ristretto_point_from_optional_slice,
+            prover_commitment, units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
     curve25519_dalek::{
         ristretto::{CompressedRistretto, RistrettoPoint},
         scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+        traits::{Identity, IsIdentity},
+    },
+    solana_zk_sdk_pod::{
+        encryption::grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
+        sigma_proofs::PodGroupedCiphertext3HandlesValidityProof, UNIT_LEN,
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
-    solana_zk_sdk_pod::{sigma_proofs::PodGroupedCiphertext3HandlesValidityProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a grouped ciphertext validity proof for 3 handles
-const GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN: usize = UNIT_LEN * 6;
-
 /// The grouped ciphertext validity proof for 3 handles.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -52,6 +53,12 @@ pub struct GroupedCiphertext3HandlesValidityProof {
 
 #[allow(non_snake_case)]
 impl GroupedCiphertext3HandlesValidityProof {
+    /// Byte length of a grouped ciphertext validity proof for 3 handles
+    pub const LEN: usize = UNIT_LEN * 6;
+
+    /// Number of multiscalar multiplication terms in the verification equation.
+    pub(crate) const VERIFICATION_TERMS: usize = 13;
+
     /// Creates a grouped ciphertext with 3 handles validity proof.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -70,8 +77,36 @@ impl GroupedCiphertext3HandlesValidityProof {
         grouped_ciphertext: &GroupedElGamalCiphertext<3>,
         amount: T,
         opening: &PedersenOpening,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a grouped ciphertext with 3 handles validity proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng<T: Into<Scalar>>(
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext<3>,
+        amount: T,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             first_pubkey,
             second_pubkey,
@@ -86,6 +121,7 @@ impl GroupedCiphertext3HandlesValidityProof {
             amount,
             opening,
             transcript,
+            rng,
         )
     }
 
@@ -95,8 +131,9 @@ impl GroupedCiphertext3HandlesValidityProof {
         third_pubkey: &ElGamalPubkey,
         amount: T,
         opening: &PedersenOpening,
-        transcript: &mut Transcript,
-    ) -> Self {
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         transcript.grouped_ciphertext_validity_proof_domain_separator(3);
 
         // extract the relevant scalar and Ristretto points from the inputs
@@ -108,10 +145,10 @@ impl GroupedCiphertext3HandlesValidityProof {
         let r = opening.get_scalar();
 
         // generate random masking factors that also serves as nonces
-        let mut y_r = Scalar::random(&mut OsRng);
-        let mut y_x = Scalar::random(&mut OsRng);
+        let mut y_r = try_random_scalar(rng)?;
+        let mut y_x = try_random_scalar(rng)?;
 
-        let Y_0 = RistrettoPoint::multiscalar_mul(vec![&y_r, &y_x], vec![&(*H), &G]).compress();
+        let Y_0 = prover_commitment([&y_r, &y_x], [&(*H), &G]).compress();
         let Y_1 = (&y_r * P_first).compress();
         let Y_2 = (&y_r * P_second).compress();
         let Y_3 = (&y_r * P_third).compress();
@@ -138,14 +175,14 @@ impl GroupedCiphertext3HandlesValidityProof {
         y_r.zeroize();
         y_x.zeroize();
 
-        Self {
+        Ok(Self {
             Y_0,
             Y_1,
             Y_2,
             Y_3,
             z_r,
             z_x,
-        }
+        })
     }
 
     /// Verifies a grouped ciphertext with 3 handles validity proof.
@@ -162,7 +199,7 @@ impl GroupedCiphertext3HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         third_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<3>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), ValidityProofVerificationError> {
         // We reject if the public keys or the commitment is the identity point.
         // The exception is the third public key, which is often the auditor's
@@ -197,7 +234,42 @@ impl GroupedCiphertext3HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         third_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<3>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), ValidityProofVerificationError> {
+        let mut scalars = [Scalar::ZERO; Self::VERIFICATION_TERMS];
+        let mut points = [RistrettoPoint::identity(); Self::VERIFICATION_TERMS];
+        let mut buffer = MultiscalarMulBuffer::new(&mut scalars, &mut points);
+        self.append_verification_terms_direct(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
+            transcript,
+            &mut buffer,
+        )?;
+
+        if buffer
+            .multiscalar_mul(&CpuMultiscalarMulBackend)
+            .is_identity()
+        {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Appends the terms of the verification equation to a multiscalar multiplication buffer
+    /// without hashing the context into the transcript.
+    ///
+    /// The proof is valid if and only if the appended terms sum to the identity.
+    pub(crate) fn append_verification_terms_direct(
+        &self,
+        first_pubkey: &ElGamalPubkey,
+        second_pubkey: &ElGamalPubkey,
+        third_pubkey: &ElGamalPubkey,
+        grouped_ciphertext: &GroupedElGamalCiphertext<3>,
+        transcript: &mut impl TranscriptProtocol,
+        buffer: &mut MultiscalarMulBuffer,
     ) -> Result<(), ValidityProofVerificationError> {
         transcript.grouped_ciphertext_validity_proof_domain_separator(3);
 
@@ -213,7 +285,7 @@ impl GroupedCiphertext3HandlesValidityProof {
 
         transcript.append_scalar(b"z_r", &self.z_r);
         transcript.append_scalar(b"z_x", &self.z_x);
-        let w = transcript.challenge_scalar(b"w");
+        let w = transcript.batching_weight(b"w");
         let ww = &w * &w;
         let www = &w * &ww;
 
@@ -252,44 +324,41 @@ impl GroupedCiphertext3HandlesValidityProof {
         let D_second = second_handle.get_point();
         let D_third = third_handle.get_point();
 
-        let check = RistrettoPoint::vartime_multiscalar_mul(
-            vec![
-                &self.z_r,            // z_r
-                &self.z_x,            // z_x
-                &(-&c),               // -c
-                &-(&Scalar::ONE),     // -identity
-                &(&w * &self.z_r),    // w * z_r
-                &(&w_negated * &c),   // -w * c
-                &w_negated,           // -w
-                &(&ww * &self.z_r),   // ww * z_r
-                &(&ww_negated * &c),  // -ww * c
-                &ww_negated,          // -ww
-                &(&www * &self.z_r),  // www * z_r
-                &(&www_negated * &c), // -www * c
-                &www_negated,         // -www
-            ],
-            vec![
-                &(*H),    // H
-                &G,       // G
-                C,        // C
-                &Y_0,     // Y_0
-                P_first,  // P_first
-                D_first,  // D_first
-                &Y_1,     // Y_1
-                P_second, // P_second
-                D_second, // D_second
-                &Y_2,     // Y_2
-                P_third,  // P_third
-                D_third,  // D_third
-                &Y_3,     // Y_3
-            ],
-        );
-
-        if check.is_identity() {
-            Ok(())
-        } else {
-            Err(SigmaProofVerificationError::AlgebraicRelation.into())
-        }
+        let (scalars, points) = buffer
+            .reserve(Self::VERIFICATION_TERMS)
+            .ok_or(SigmaProofVerificationError::MultiscalarMul)?;
+        scalars.copy_from_slice(&[
+            self.z_r,        // z_r
+            self.z_x,        // z_x
+            -c,              // -c
+            -Scalar::ONE,    // -identity
+            w * self.z_r,    // w * z_r
+            w_negated * c,   // -w * c
+            w_negated,       // -w
+            ww * self.z_r,   // ww * z_r
+            ww_negated * c,  // -ww * c
+            ww_negated,      // -ww
+            www * self.z_r,  // www * z_r
+            www_negated * c, // -www * c
+            www_negated,     // -www
+        ]);
+        points.copy_from_slice(&[
+            *H,        // H
+            G,         // G
+            *C,        // C
+            Y_0,       // Y_0
+            *P_first,  // P_first
+            *D_first,  // D_first
+            Y_1,       // Y_1
+            *P_second, // P_second
+            *D_second, // D_second
+            Y_2,       // Y_2
+            *P_third,  // P_third
+            *D_third,  // D_third
+            Y_3,       // Y_3
+        ]);
+
+        Ok(())
     }
 
     fn hash_context_into_transcript(
@@ -297,38 +366,37 @@ impl GroupedCiphertext3HandlesValidityProof {
         second_pubkey: &ElGamalPubkey,
         third_pubkey: &ElGamalPubkey,
         grouped_ciphertext: &GroupedElGamalCiphertext<3>,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"first-pubkey", &first_pubkey.to_bytes());
         transcript.append_message(b"second-pubkey", &second_pubkey.to_bytes());
         transcript.append_message(b"third-pubkey", &third_pubkey.to_bytes());
-        transcript.append_message(b"grouped-ciphertext", &grouped_ciphertext.to_bytes());
+        transcript.append_message(
+            b"grouped-ciphertext",
+            &PodGroupedElGamalCiphertext3Handles::from(*grouped_ciphertext).0,
+        );
     }
 
-    pub fn to_bytes(&self) -> [u8; GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN] {
-        let mut buf = [0_u8; GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks.next().unwrap().copy_from_slice(self.Y_0.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_1.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_2.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_3.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_r.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z_x.as_bytes());
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([
+            self.Y_0.to_bytes(),
+            self.Y_1.to_bytes(),
+            self.Y_2.to_bytes(),
+            self.Y_3.to_bytes(),
+            self.z_r.to_bytes(),
+            self.z_x.to_bytes(),
+        ])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ValidityProofVerificationError> {
-        if bytes.len() != GROUPED_CIPHERTEXT_3_HANDLES_VALIDITY_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        let [Y_0, Y_1, Y_2, Y_3, z_r, z_x] = bytes_to_units(bytes)?;
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y_0 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_1 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_2 = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_3 = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_r = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_x = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y_0 = CompressedRistretto(Y_0);
+        let Y_1 = CompressedRistretto(Y_1);
+        let Y_2 = CompressedRistretto(Y_2);
+        let Y_3 = CompressedRistretto(Y_3);
+        let z_r = canonical_scalar_from_bytes(z_r)?;
+        let z_x = canonical_scalar_from_bytes(z_x)?;
 
         Ok(GroupedCiphertext3HandlesValidityProof {
             Y_0,
@@ -347,6 +415,12 @@ impl From<GroupedCiphertext3HandlesValidityProof> for PodGroupedCiphertext3Handl
     }
 }
 
+impl From<&GroupedCiphertext3HandlesValidityProof> for PodGroupedCiphertext3HandlesValidityProof {
+    fn from(decoded_proof: &GroupedCiphertext3HandlesValidityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodGroupedCiphertext3HandlesValidityProof> for GroupedCiphertext3HandlesValidityProof {
     type Error = ValidityProofVerificationError;
 
@@ -355,6 +429,18 @@ impl TryFrom<PodGroupedCiphertext3HandlesValidityProof> for GroupedCiphertext3Ha
     }
 }
 
+impl TryFrom<&PodGroupedCiphertext3HandlesValidityProof>
+    for GroupedCiphertext3HandlesValidityProof
+{
+    type Error = ValidityProofVerificationError;
+
+    fn try_from(
+        pod_proof: &PodGroupedCiphertext3HandlesValidityProof,
+    ) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
@@ -363,6 +449,7 @@ mod test {
             elgamal::ElGamalKeypair,
             pedersen::{Pedersen, PedersenCommitment},
         },
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::{
                 elgamal::PodElGamalPubkey, grouped_elgamal::PodGroupedElGamalCiphertext3Handles,
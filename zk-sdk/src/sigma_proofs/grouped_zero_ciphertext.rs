@@ -0,0 +1,455 @@
+//! The grouped zero-ciphertext sigma proof system.
+//!
+//! The grouped zero-ciphertext proof is defined with respect to a grouped ElGamal ciphertext. The
+//! proof certifies that the grouped ciphertext encrypts the message zero with respect to every one
+//! of its decryption handles. To generate the proof, a prover must provide the Pedersen opening
+//! associated with the grouped ciphertext's commitment.
+//!
+//! A grouped ciphertext `(C, D_1, ..., D_N)` encrypts zero under every handle if and only if
+//! `C = r*H` and `D_i = r*P_i` for a single opening `r`. The proof is a Schnorr proof of
+//! knowledge of `r` for all of these relations at once, and the verifier checks all relations in
+//! a single multiscalar multiplication.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::ElGamalPubkey,
+            grouped_elgamal::GroupedElGamalCiphertext,
+            pedersen::{PedersenOpening, H},
+        },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            canonical_scalar_from_bytes,
+            errors::{SigmaProofVerificationError, ZeroCiphertextProofVerificationError},
+            write_units,
+        },
+        transcript::TranscriptProtocol,
+    },
+    bytemuck::Zeroable,
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::{
+        sigma_proofs::{
+            PodGroupedZeroCiphertext2HandlesProof, PodGroupedZeroCiphertext3HandlesProof,
+        },
+        UNIT_LEN,
+    },
+    std::iter,
+    zeroize::Zeroize,
+};
+
+/// The grouped zero-ciphertext proof for `N` handles.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct GroupedZeroCiphertextProof<const N: usize> {
+    Y_C: CompressedRistretto,
+    Y_D: [CompressedRistretto; N],
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl<const N: usize> GroupedZeroCiphertextProof<N> {
+    /// Byte length of a grouped zero-ciphertext proof for `N` handles.
+    pub const LEN: usize = UNIT_LEN * (N + 2);
+
+    /// Creates a grouped zero-ciphertext proof.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `pubkeys` - The ElGamal public keys associated with the decryption handles
+    /// * `grouped_ciphertext` - The grouped ciphertext to be proved
+    /// * `opening` - The opening associated with the grouped ciphertext's commitment
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        pubkeys: [&ElGamalPubkey; N],
+        grouped_ciphertext: &GroupedElGamalCiphertext<N>,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            pubkeys,
+            grouped_ciphertext,
+            opening,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a grouped zero-ciphertext proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        pubkeys: [&ElGamalPubkey; N],
+        grouped_ciphertext: &GroupedElGamalCiphertext<N>,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(&pubkeys, grouped_ciphertext, transcript);
+        transcript.grouped_zero_ciphertext_proof_domain_separator(N as u64);
+
+        let r = opening.get_scalar();
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = try_random_scalar(rng)?;
+        let Y_C = (&y * &(*H)).compress();
+        let Y_D = pubkeys.map(|pubkey| (&y * pubkey.get_point()).compress());
+
+        // record masking factors in the transcript and receive a challenge scalar
+        transcript.append_point(b"Y_C", &Y_C);
+        for Y_D_i in &Y_D {
+            transcript.append_point(b"Y_D", Y_D_i);
+        }
+
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute the masked opening
+        let z = &(&c * r) + &y;
+
+        // compute challenge `w` for consistency with verification
+        transcript.append_scalar(b"z", &z);
+        let _w = transcript.challenge_scalar(b"w");
+
+        // zeroize random scalar
+        y.zeroize();
+
+        Ok(Self { Y_C, Y_D, z })
+    }
+
+    /// Verifies a grouped zero-ciphertext proof.
+    ///
+    /// * `pubkeys` - The ElGamal public keys associated with the decryption handles
+    /// * `grouped_ciphertext` - The grouped ciphertext to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        pubkeys: [&ElGamalPubkey; N],
+        grouped_ciphertext: &GroupedElGamalCiphertext<N>,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), ZeroCiphertextProofVerificationError> {
+        // We reject if there are no handles, or if the first public key or the commitment is the
+        // identity point. Other public keys are allowed to be the identity as they are often
+        // auditor public keys that can be unset.
+        let Some(first_pubkey) = pubkeys.first() else {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        };
+        if first_pubkey.get_point().is_identity()
+            || grouped_ciphertext.commitment.get_point().is_identity()
+        {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(&pubkeys, grouped_ciphertext, transcript);
+        transcript.grouped_zero_ciphertext_proof_domain_separator(N as u64);
+
+        // record masking factors in transcript and receive challenge scalars
+        transcript.validate_and_append_point(b"Y_C", &self.Y_C)?;
+        for (i, Y_D_i) in self.Y_D.iter().enumerate() {
+            if i == 0 {
+                transcript.validate_and_append_point(b"Y_D", Y_D_i)?;
+            } else {
+                // Y_D can be an all zero point if the corresponding public key is all zero
+                transcript.append_point(b"Y_D", Y_D_i);
+            }
+        }
+
+        let c = transcript.challenge_scalar(b"c");
+
+        transcript.append_scalar(b"z", &self.z);
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
+
+        // decompress Y or return verification error
+        let Y_C = self
+            .Y_C
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let Y_D = self
+            .Y_D
+            .iter()
+            .map(|Y_D_i| {
+                Y_D_i
+                    .decompress()
+                    .ok_or(SigmaProofVerificationError::Deserialization)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // check the required algebraic relations
+        //   z*H = c*C + Y_C
+        //   z*P_i = c*D_i + Y_D_i   for all i
+        // where the relation for handle `i` is weighted by `w^(i+1)`
+        let mut scalars = vec![self.z, -c, -Scalar::ONE];
+        let mut points = vec![*H, *grouped_ciphertext.commitment.get_point(), Y_C];
+
+        let mut exp_w = Scalar::ONE;
+        for ((pubkey, handle), Y_D_i) in pubkeys
+            .iter()
+            .zip(grouped_ciphertext.handles.iter())
+            .zip(Y_D)
+        {
+            exp_w *= w;
+            scalars.extend([&exp_w * &self.z, -(&exp_w * &c), -exp_w]);
+            points.extend([*pubkey.get_point(), *handle.get_point(), Y_D_i]);
+        }
+
+        let check = vartime_multiscalar_mul(scalars, points);
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        pubkeys: &[&ElGamalPubkey; N],
+        grouped_ciphertext: &GroupedElGamalCiphertext<N>,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        for pubkey in pubkeys {
+            transcript.append_message(b"pubkey", &pubkey.to_bytes());
+        }
+        transcript.append_message(b"grouped-ciphertext", &grouped_ciphertext.to_bytes());
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![0; Self::LEN];
+        self.write_bytes(&mut buf);
+        buf
+    }
+
+    /// Writes the proof into a buffer of length `LEN` without allocating.
+    fn write_bytes(&self, buf: &mut [u8]) {
+        let units = iter::once(self.Y_C.to_bytes())
+            .chain(self.Y_D.iter().map(CompressedRistretto::to_bytes))
+            .chain(iter::once(self.z.to_bytes()));
+        write_units(buf, units);
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZeroCiphertextProofVerificationError> {
+        let (units, []) = bytes.as_chunks::<UNIT_LEN>() else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        let [Y_C, Y_D @ .., z] = units else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        let Y_D: [[u8; UNIT_LEN]; N] = Y_D
+            .try_into()
+            .map_err(|_| SigmaProofVerificationError::Deserialization)?;
+
+        let Y_C = CompressedRistretto(*Y_C);
+        let Y_D = Y_D.map(CompressedRistretto);
+        let z = canonical_scalar_from_bytes(*z)?;
+
+        Ok(Self { Y_C, Y_D, z })
+    }
+}
+
+impl From<GroupedZeroCiphertextProof<2>> for PodGroupedZeroCiphertext2HandlesProof {
+    fn from(decoded_proof: GroupedZeroCiphertextProof<2>) -> Self {
+        Self::from(&decoded_proof)
+    }
+}
+
+impl From<&GroupedZeroCiphertextProof<2>> for PodGroupedZeroCiphertext2HandlesProof {
+    fn from(decoded_proof: &GroupedZeroCiphertextProof<2>) -> Self {
+        // the proof length is fixed by the number of handles
+        let mut pod_proof = Self::zeroed();
+        decoded_proof.write_bytes(&mut pod_proof.0);
+        pod_proof
+    }
+}
+
+impl TryFrom<PodGroupedZeroCiphertext2HandlesProof> for GroupedZeroCiphertextProof<2> {
+    type Error = ZeroCiphertextProofVerificationError;
+
+    fn try_from(pod_proof: PodGroupedZeroCiphertext2HandlesProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl TryFrom<&PodGroupedZeroCiphertext2HandlesProof> for GroupedZeroCiphertextProof<2> {
+    type Error = ZeroCiphertextProofVerificationError;
+
+    fn try_from(pod_proof: &PodGroupedZeroCiphertext2HandlesProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl From<GroupedZeroCiphertextProof<3>> for PodGroupedZeroCiphertext3HandlesProof {
+    fn from(decoded_proof: GroupedZeroCiphertextProof<3>) -> Self {
+        Self::from(&decoded_proof)
+    }
+}
+
+impl From<&GroupedZeroCiphertextProof<3>> for PodGroupedZeroCiphertext3HandlesProof {
+    fn from(decoded_proof: &GroupedZeroCiphertextProof<3>) -> Self {
+        // the proof length is fixed by the number of handles
+        let mut pod_proof = Self::zeroed();
+        decoded_proof.write_bytes(&mut pod_proof.0);
+        pod_proof
+    }
+}
+
+impl TryFrom<PodGroupedZeroCiphertext3HandlesProof> for GroupedZeroCiphertextProof<3> {
+    type Error = ZeroCiphertextProofVerificationError;
+
+    fn try_from(pod_proof: PodGroupedZeroCiphertext3HandlesProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl TryFrom<&PodGroupedZeroCiphertext3HandlesProof> for GroupedZeroCiphertextProof<3> {
+    type Error = ZeroCiphertextProofVerificationError;
+
+    fn try_from(pod_proof: &PodGroupedZeroCiphertext3HandlesProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::{
+            elgamal::ElGamalKeypair, grouped_elgamal::GroupedElGamal, pedersen::PedersenCommitment,
+        },
+        merlin::Transcript,
+    };
+
+    #[test]
+    fn test_grouped_zero_ciphertext_proof_correctness() {
+        let first_keypair = ElGamalKeypair::new_rand();
+        let second_keypair = ElGamalKeypair::new_rand();
+        let third_keypair = ElGamalKeypair::new_rand();
+        let pubkeys = [
+            first_keypair.pubkey(),
+            second_keypair.pubkey(),
+            third_keypair.pubkey(),
+        ];
+
+        let opening = PedersenOpening::new_rand();
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(pubkeys, 0_u64, &opening);
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = GroupedZeroCiphertextProof::new(
+            pubkeys,
+            &grouped_ciphertext,
+            &opening,
+            &mut prover_transcript,
+        );
+        proof
+            .clone()
+            .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+            .unwrap();
+
+        // round trip through bytes
+        let decoded_proof = GroupedZeroCiphertextProof::<3>::from_bytes(&proof.to_bytes()).unwrap();
+        let mut verifier_transcript = Transcript::new(b"test");
+        decoded_proof
+            .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+            .unwrap();
+
+        // round trip through the pod representation
+        let pod_proof = PodGroupedZeroCiphertext3HandlesProof::from(&proof);
+        assert_eq!(pod_proof.0.as_slice(), proof.to_bytes().as_slice());
+        let decoded_proof = GroupedZeroCiphertextProof::<3>::try_from(pod_proof).unwrap();
+        assert_eq!(decoded_proof.to_bytes(), proof.to_bytes());
+
+        // nonzero amount
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(pubkeys, 1_u64, &opening);
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = GroupedZeroCiphertextProof::new(
+            pubkeys,
+            &grouped_ciphertext,
+            &opening,
+            &mut prover_transcript,
+        );
+        assert!(proof
+            .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+            .is_err());
+    }
+
+    #[test]
+    fn test_grouped_zero_ciphertext_proof_inconsistent_handle() {
+        let first_keypair = ElGamalKeypair::new_rand();
+        let second_keypair = ElGamalKeypair::new_rand();
+        let pubkeys = [first_keypair.pubkey(), second_keypair.pubkey()];
+
+        // the second handle is generated with a different opening
+        let opening = PedersenOpening::new_rand();
+        let mut grouped_ciphertext = GroupedElGamal::encrypt_with(pubkeys, 0_u64, &opening);
+        grouped_ciphertext.handles[1] = second_keypair
+            .pubkey()
+            .decrypt_handle(&PedersenOpening::new_rand());
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = GroupedZeroCiphertextProof::new(
+            pubkeys,
+            &grouped_ciphertext,
+            &opening,
+            &mut prover_transcript,
+        );
+        assert!(proof
+            .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+            .is_err());
+    }
+
+    #[test]
+    fn test_grouped_zero_ciphertext_proof_edge_cases() {
+        let first_keypair = ElGamalKeypair::new_rand();
+        let second_pubkey = ElGamalPubkey::try_from([0u8; 32].as_slice()).unwrap();
+        let pubkeys = [first_keypair.pubkey(), &second_pubkey];
+
+        // the second public key can be the identity
+        let opening = PedersenOpening::new_rand();
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(pubkeys, 0_u64, &opening);
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = GroupedZeroCiphertextProof::new(
+            pubkeys,
+            &grouped_ciphertext,
+            &opening,
+            &mut prover_transcript,
+        );
+        proof
+            .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+            .unwrap();
+
+        // the commitment cannot be the identity
+        let mut grouped_ciphertext = grouped_ciphertext;
+        grouped_ciphertext.commitment = PedersenCommitment::from_bytes(&[0u8; 32]).unwrap();
+
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = GroupedZeroCiphertextProof::new(
+            pubkeys,
+            &grouped_ciphertext,
+            &opening,
+            &mut prover_transcript,
+        );
+        assert_eq!(
+            proof
+                .verify(pubkeys, &grouped_ciphertext, &mut verifier_transcript)
+                .unwrap_err(),
+            SigmaProofVerificationError::IdentityPoint.into()
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! The key-private ciphertext validity proof system.
+//!
+//! A key-private validity proof certifies that an ElGamal ciphertext `(C, D)` is well-formed with
+//! respect to one of the public keys in a published anonymity set `P_0, ..., P_{n-1}`, without
+//! revealing which one. The decryption handle `D = r * P_i` is the public key of the recipient
+//! blinded by the opening `r` of the Pedersen commitment `C = x * G + r * H`, and the proof shows
+//! that the blinding factor is the same opening for some `i`.
+//!
+//! The proof is a disjunction of `n` validity relations. For every key other than the recipient,
+//! the prover simulates a transcript with a chosen challenge, and the challenge of the
+//! recipient's relation is fixed by the requirement that all challenges sum to the Fiat-Shamir
+//! challenge. A proof for an anonymity set of `n` keys consists of `5 * n` units.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model. The ciphertext itself hides the recipient under the
+//! decisional Diffie-Hellman assumption.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::{PedersenOpening, G, H},
+        },
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            canonical_scalar_from_bytes,
+            errors::{
+                KeyPrivateValidityProofGenerationError, KeyPrivateValidityProofVerificationError,
+                SigmaProofVerificationError,
+            },
+            prover_commitment,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{
+        ristretto::{CompressedRistretto, RistrettoPoint},
+        scalar::Scalar,
+        traits::IsIdentity,
+    },
+    solana_zk_sdk_pod::UNIT_LEN,
+    zeroize::Zeroize,
+};
+
+/// The maximum number of public keys in the anonymity set of a key-private validity proof.
+pub const MAX_ANONYMITY_SET_SIZE: usize = 256;
+
+/// The number of units of a single branch of the disjunction.
+const UNITS_PER_BRANCH: usize = 5;
+
+/// The prover messages and responses of the validity relation for a single key.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+struct KeyPrivateValidityBranch {
+    Y_0: CompressedRistretto,
+    Y_1: CompressedRistretto,
+    c: Scalar,
+    z_x: Scalar,
+    z_r: Scalar,
+}
+
+/// Key-private ciphertext validity proof.
+///
+/// Contains one branch of the disjunction for every key in the anonymity set.
+#[derive(Clone)]
+pub struct KeyPrivateValidityProof {
+    branches: Vec<KeyPrivateValidityBranch>,
+}
+
+#[allow(non_snake_case)]
+impl KeyPrivateValidityProof {
+    /// Byte length of a key-private validity proof for an anonymity set of `num_pubkeys` keys.
+    pub fn proof_len(num_pubkeys: usize) -> usize {
+        UNITS_PER_BRANCH * num_pubkeys * UNIT_LEN
+    }
+
+    /// Creates a key-private validity proof.
+    ///
+    /// The function does not check that the ciphertext encrypts `amount` under
+    /// `anonymity_set[index]` with `opening`. If it does not, the resulting proof does not verify.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// Returns an error if the anonymity set is empty or exceeds [`MAX_ANONYMITY_SET_SIZE`], or if
+    /// `index` is not a position in the set.
+    ///
+    /// * `anonymity_set` - The public keys among which the recipient is hidden
+    /// * `index` - The position of the recipient's public key in the anonymity set
+    /// * `ciphertext` - The ciphertext encrypted under the recipient's public key
+    /// * `amount` - The amount encrypted in the ciphertext
+    /// * `opening` - The opening of the commitment of the ciphertext
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        anonymity_set: &[ElGamalPubkey],
+        index: usize,
+        ciphertext: &ElGamalCiphertext,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, KeyPrivateValidityProofGenerationError> {
+        Self::new_with_rng(
+            anonymity_set,
+            index,
+            ciphertext,
+            amount,
+            opening,
+            transcript,
+            &mut DefaultRng,
+        )
+    }
+
+    /// Creates a key-private validity proof using `rng` to generate the random scalars.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        anonymity_set: &[ElGamalPubkey],
+        index: usize,
+        ciphertext: &ElGamalCiphertext,
+        amount: u64,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, KeyPrivateValidityProofGenerationError> {
+        if anonymity_set.is_empty() || anonymity_set.len() > MAX_ANONYMITY_SET_SIZE {
+            return Err(KeyPrivateValidityProofGenerationError::InvalidAnonymitySetSize);
+        }
+        if index >= anonymity_set.len() {
+            return Err(KeyPrivateValidityProofGenerationError::IndexOutOfBounds);
+        }
+
+        Self::hash_context_into_transcript(anonymity_set, ciphertext, transcript);
+        transcript.key_private_validity_proof_domain_separator(anonymity_set.len() as u64);
+
+        let C = ciphertext.commitment.get_point();
+        let D = ciphertext.handle.get_point();
+
+        // simulate the relations of all keys other than the recipient's
+        let mut branches = Vec::with_capacity(anonymity_set.len());
+        for (j, pubkey) in anonymity_set.iter().enumerate() {
+            if j == index {
+                // placeholder that is overwritten once the challenge is known
+                branches.push(KeyPrivateValidityBranch {
+                    Y_0: CompressedRistretto::default(),
+                    Y_1: CompressedRistretto::default(),
+                    c: Scalar::ZERO,
+                    z_x: Scalar::ZERO,
+                    z_r: Scalar::ZERO,
+                });
+                continue;
+            }
+
+            let c = try_random_scalar(rng)?;
+            let z_x = try_random_scalar(rng)?;
+            let z_r = try_random_scalar(rng)?;
+            let neg_c = -c;
+            let Y_0 = prover_commitment([&z_x, &z_r, &neg_c], [&G, &(*H), C]).compress();
+            let Y_1 = prover_commitment([&z_r, &neg_c], [pubkey.get_point(), D]).compress();
+            branches.push(KeyPrivateValidityBranch {
+                Y_0,
+                Y_1,
+                c,
+                z_x,
+                z_r,
+            });
+        }
+
+        // generate the masking factors of the recipient's relation
+        let mut x = Scalar::from(amount);
+        let r = opening.get_scalar();
+        let mut y_x = try_random_scalar(rng)?;
+        let mut y_r = try_random_scalar(rng)?;
+        branches[index].Y_0 = prover_commitment([&y_x, &y_r], [&G, &(*H)]).compress();
+        branches[index].Y_1 = (&y_r * anonymity_set[index].get_point()).compress();
+
+        // record masking factors in the transcript and get the challenge
+        for branch in &branches {
+            transcript.append_point(b"Y_0", &branch.Y_0);
+            transcript.append_point(b"Y_1", &branch.Y_1);
+        }
+        let c = transcript.challenge_scalar(b"c");
+
+        // the challenge of the recipient's relation completes the sum of challenges
+        let c_i = branches
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != index)
+            .fold(c, |acc, (_, branch)| acc - branch.c);
+        branches[index].c = c_i;
+        branches[index].z_x = &(&c_i * &x) + &y_x;
+        branches[index].z_r = &(&c_i * r) + &y_r;
+
+        // zeroize random scalars
+        x.zeroize();
+        y_x.zeroize();
+        y_r.zeroize();
+
+        Ok(Self { branches })
+    }
+
+    /// Verifies a key-private validity proof.
+    ///
+    /// * `anonymity_set` - The public keys among which the recipient is hidden
+    /// * `ciphertext` - The ciphertext to be verified
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        anonymity_set: &[ElGamalPubkey],
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), KeyPrivateValidityProofVerificationError> {
+        if anonymity_set.is_empty()
+            || anonymity_set.len() > MAX_ANONYMITY_SET_SIZE
+            || anonymity_set.len() != self.branches.len()
+        {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        }
+        if anonymity_set
+            .iter()
+            .any(|pubkey| pubkey.get_point().is_identity())
+        {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(anonymity_set, ciphertext, transcript);
+        transcript.key_private_validity_proof_domain_separator(anonymity_set.len() as u64);
+
+        // include the masking factors in the transcript and extract the challenge
+        for branch in &self.branches {
+            transcript.validate_and_append_point(b"Y_0", &branch.Y_0)?;
+            transcript.validate_and_append_point(b"Y_1", &branch.Y_1)?;
+        }
+        let c = transcript.challenge_scalar(b"c");
+        let w = transcript.batching_weight(b"w");
+
+        // the challenges of all relations must sum to the Fiat-Shamir challenge
+        let c_sum = self
+            .branches
+            .iter()
+            .fold(Scalar::ZERO, |acc, branch| acc + branch.c);
+        if c_sum != c {
+            return Err(SigmaProofVerificationError::AlgebraicRelation.into());
+        }
+
+        // check all relations
+        //
+        //   z_x_j * G + z_r_j * H = c_j * C + Y_0_j
+        //   z_r_j * P_j = c_j * D + Y_1_j
+        //
+        // combined with consecutive powers of `w`
+        let num_branches = self.branches.len();
+        let mut scalars = Vec::with_capacity(4 + 3 * num_branches);
+        let mut points = Vec::with_capacity(4 + 3 * num_branches);
+
+        let mut g_scalar = Scalar::ZERO;
+        let mut h_scalar = Scalar::ZERO;
+        let mut c_scalar = Scalar::ZERO;
+        let mut d_scalar = Scalar::ZERO;
+        let mut weight = Scalar::ONE;
+        for (branch, pubkey) in self.branches.iter().zip(anonymity_set) {
+            let w_0 = weight;
+            let w_1 = w_0 * w;
+            weight = w_1 * w;
+
+            g_scalar += w_0 * branch.z_x;
+            h_scalar += w_0 * branch.z_r;
+            c_scalar -= w_0 * branch.c;
+            d_scalar -= w_1 * branch.c;
+
+            let Y_0 = branch
+                .Y_0
+                .decompress()
+                .ok_or(SigmaProofVerificationError::Deserialization)?;
+            let Y_1 = branch
+                .Y_1
+                .decompress()
+                .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+            scalars.extend([w_1 * branch.z_r, -w_0, -w_1]);
+            points.extend([*pubkey.get_point(), Y_0, Y_1]);
+        }
+        scalars.extend([g_scalar, h_scalar, c_scalar, d_scalar]);
+        points.extend([
+            G,
+            *H,
+            *ciphertext.commitment.get_point(),
+            *ciphertext.handle.get_point(),
+        ]);
+
+        let check: RistrettoPoint = vartime_multiscalar_mul(&scalars, &points);
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        anonymity_set: &[ElGamalPubkey],
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        for pubkey in anonymity_set {
+            transcript.append_message(b"pubkey", &pubkey.to_bytes());
+        }
+        transcript.append_message(b"ciphertext", &ciphertext.to_bytes());
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::proof_len(self.branches.len()));
+        for branch in &self.branches {
+            buf.extend_from_slice(branch.Y_0.as_bytes());
+            buf.extend_from_slice(branch.Y_1.as_bytes());
+            buf.extend_from_slice(branch.c.as_bytes());
+            buf.extend_from_slice(branch.z_x.as_bytes());
+            buf.extend_from_slice(branch.z_r.as_bytes());
+        }
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KeyPrivateValidityProofVerificationError> {
+        let (units, []) = bytes.as_chunks::<UNIT_LEN>() else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        let (branches, []) = units.as_chunks::<UNITS_PER_BRANCH>() else {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        };
+        if branches.is_empty() || branches.len() > MAX_ANONYMITY_SET_SIZE {
+            return Err(SigmaProofVerificationError::Deserialization.into());
+        }
+
+        let branches = branches
+            .iter()
+            .map(|[Y_0, Y_1, c, z_x, z_r]| {
+                Ok(KeyPrivateValidityBranch {
+                    Y_0: CompressedRistretto(*Y_0),
+                    Y_1: CompressedRistretto(*Y_1),
+                    c: canonical_scalar_from_bytes(*c)?,
+                    z_x: canonical_scalar_from_bytes(*z_x)?,
+                    z_r: canonical_scalar_from_bytes(*z_r)?,
+                })
+            })
+            .collect::<Result<Vec<_>, SigmaProofVerificationError>>()?;
+        Ok(Self { branches })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair, merlin::Transcript};
+
+    fn anonymity_set(size: usize) -> Vec<ElGamalPubkey> {
+        (0..size)
+            .map(|_| *ElGamalKeypair::new_rand().pubkey())
+            .collect()
+    }
+
+    #[test]
+    fn test_key_private_validity_proof_correctness() {
+        let mut anonymity_set = anonymity_set(4);
+        let recipient = ElGamalKeypair::new_rand();
+        anonymity_set[2] = *recipient.pubkey();
+
+        let amount = 55_u64;
+        let opening = PedersenOpening::new_rand();
+        let ciphertext = recipient.pubkey().encrypt_with(amount, &opening);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = KeyPrivateValidityProof::new(
+            &anonymity_set,
+            2,
+            &ciphertext,
+            amount,
+            &opening,
+            &mut prover_transcript,
+        )
+        .unwrap();
+        assert_eq!(
+            proof.to_bytes().len(),
+            KeyPrivateValidityProof::proof_len(4)
+        );
+        let proof = KeyPrivateValidityProof::from_bytes(&proof.to_bytes()).unwrap();
+
+        // the recipient can decrypt the ciphertext
+        assert_eq!(recipient.secret().decrypt_u32(&ciphertext), Some(amount));
+
+        proof
+            .clone()
+            .verify(&anonymity_set, &ciphertext, &mut verifier_transcript)
+            .unwrap();
+
+        // the proof is bound to the anonymity set
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut other_set = anonymity_set.clone();
+        other_set.swap(0, 1);
+        assert!(proof
+            .verify(&other_set, &ciphertext, &mut verifier_transcript)
+            .is_err());
+    }
+
+    #[test]
+    fn test_key_private_validity_proof_recipient_outside_set() {
+        let anonymity_set = anonymity_set(3);
+        let recipient = ElGamalKeypair::new_rand();
+
+        let amount = 55_u64;
+        let opening = PedersenOpening::new_rand();
+        let ciphertext = recipient.pubkey().encrypt_with(amount, &opening);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = KeyPrivateValidityProof::new(
+            &anonymity_set,
+            0,
+            &ciphertext,
+            amount,
+            &opening,
+            &mut prover_transcript,
+        )
+        .unwrap();
+        assert!(proof
+            .verify(&anonymity_set, &ciphertext, &mut verifier_transcript)
+            .is_err());
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        assert_eq!(
+            KeyPrivateValidityProof::new(
+                &anonymity_set,
+                3,
+                &ciphertext,
+                amount,
+                &opening,
+                &mut prover_transcript,
+            )
+            .err(),
+            Some(KeyPrivateValidityProofGenerationError::IndexOutOfBounds)
+        );
+    }
+}
@@ -5,49 +5,311 @@
 //!
 //! [`ZK Token proof`]: https://docs.solanalabs.com/runtime/zk-token-proof
 
+pub mod asset_equality;
 pub mod batched_grouped_ciphertext_validity;
 pub mod ciphertext_ciphertext_equality;
 pub mod ciphertext_commitment_equality;
+pub mod ciphertext_commitment_opening_equality;
+pub mod compressed_equality;
 pub mod errors;
 pub mod grouped_ciphertext_validity;
+pub mod grouped_zero_ciphertext;
+pub mod key_private_validity;
+pub mod partial_decryption;
 pub mod percentage_with_cap;
+pub mod percentage_with_cap_split;
 pub mod pubkey_validity;
+pub mod split_consistency;
 pub mod zero_ciphertext;
 
 use {
     crate::sigma_proofs::errors::SigmaProofVerificationError,
-    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar},
-    solana_zk_sdk_pod::{RISTRETTO_POINT_LEN, SCALAR_LEN},
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar},
+    solana_zk_sdk_pod::{SCALAR_LEN, UNIT_LEN},
 };
+#[cfg(not(feature = "heapless"))]
+use curve25519_dalek::traits::MultiscalarMul;
 
-/// Deserializes an optional slice of bytes to a compressed Ristretto point.
+/// Concatenates 32-byte units into a byte array of length `LEN`.
 ///
-/// This is a helper function for deserializing byte encodings of sigma proofs. It is designed to
-/// be used with `std::slice::Chunks`.
-fn ristretto_point_from_optional_slice(
-    optional_slice: Option<&[u8]>,
-) -> Result<CompressedRistretto, SigmaProofVerificationError> {
-    let Some(slice) = optional_slice else {
-        return Err(SigmaProofVerificationError::Deserialization);
-    };
+/// This is a helper function for serializing sigma proofs. The number of units is checked against
+/// `LEN` at compile time, so serialization has no runtime panic path.
+fn units_to_bytes<const N: usize, const LEN: usize>(units: [[u8; UNIT_LEN]; N]) -> [u8; LEN] {
+    const { assert!(N * UNIT_LEN == LEN) };
+    let mut buf = [0_u8; LEN];
+    write_units(&mut buf, units);
+    buf
+}
 
-    if slice.len() != RISTRETTO_POINT_LEN {
-        return Err(SigmaProofVerificationError::Deserialization);
+/// Writes 32-byte units into consecutive 32-byte chunks of a buffer.
+///
+/// Units that do not fit in the buffer are dropped rather than causing a panic; callers size the
+/// buffer from the proof length.
+fn write_units(buf: &mut [u8], units: impl IntoIterator<Item = [u8; UNIT_LEN]>) {
+    let (chunks, _) = buf.as_chunks_mut::<UNIT_LEN>();
+    for (chunk, unit) in chunks.iter_mut().zip(units) {
+        *chunk = unit;
     }
+}
 
-    CompressedRistretto::from_slice(slice).map_err(|_| SigmaProofVerificationError::Deserialization)
+/// Splits a byte slice into exactly `N` 32-byte units.
+///
+/// This is a helper function for deserializing byte encodings of sigma proofs. It returns an error
+/// if the slice is not exactly `N * 32` bytes long.
+fn bytes_to_units<const N: usize>(
+    bytes: &[u8],
+) -> Result<[[u8; UNIT_LEN]; N], SigmaProofVerificationError> {
+    let (units, []) = bytes.as_chunks::<UNIT_LEN>() else {
+        return Err(SigmaProofVerificationError::Deserialization);
+    };
+    units
+        .try_into()
+        .map_err(|_| SigmaProofVerificationError::Deserialization)
 }
 
-/// Deserializes an optional slice of bytes to a scalar.
+/// Computes a prover commitment `scalars[0] * points[0] + ... + scalars[N - 1] * points[N - 1]`.
 ///
-/// This is a helper function for deserializing byte encodings of sigma proofs. It is designed to
-/// be used with `std::slice::Chunks`.
-fn canonical_scalar_from_optional_slice(
-    optional_slice: Option<&[u8]>,
+/// A multiscalar multiplication allocates its lookup tables on the heap. With the `heapless`
+/// feature, the commitment is instead computed as a sum of constant-time scalar multiplications,
+/// so that the equality and validity proofs are generated without heap allocations. The crate
+/// still depends on `std`, so the feature does not make the proofs available to `no_std` targets.
+fn prover_commitment<const N: usize>(
+    scalars: [&Scalar; N],
+    points: [&RistrettoPoint; N],
+) -> RistrettoPoint {
+    #[cfg(not(feature = "heapless"))]
+    {
+        RistrettoPoint::multiscalar_mul(scalars, points)
+    }
+    #[cfg(feature = "heapless")]
+    {
+        scalars
+            .into_iter()
+            .zip(points)
+            .map(|(scalar, point)| scalar * point)
+            .sum()
+    }
+}
+
+/// Deserializes a 32-byte unit to a canonical scalar.
+fn canonical_scalar_from_bytes(
+    bytes: [u8; SCALAR_LEN],
 ) -> Result<Scalar, SigmaProofVerificationError> {
-    optional_slice
-        .and_then(|slice| (slice.len() == SCALAR_LEN).then_some(slice)) // if chunk is the wrong length, convert to None
-        .and_then(|slice| slice.try_into().ok()) // convert to array
-        .and_then(|slice| Scalar::from_canonical_bytes(slice).into_option())
+    Scalar::from_canonical_bytes(bytes)
+        .into_option()
         .ok_or(SigmaProofVerificationError::Deserialization)
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            batched_grouped_ciphertext_validity::{
+                BatchedGroupedCiphertext2HandlesValidityProof,
+                BatchedGroupedCiphertext3HandlesValidityProof,
+            },
+            ciphertext_ciphertext_equality::CiphertextCiphertextEqualityProof,
+            ciphertext_commitment_equality::CiphertextCommitmentEqualityProof,
+            ciphertext_commitment_opening_equality::CiphertextCommitmentOpeningEqualityProof,
+            grouped_ciphertext_validity::{
+                GroupedCiphertext2HandlesValidityProof, GroupedCiphertext3HandlesValidityProof,
+            },
+            grouped_zero_ciphertext::GroupedZeroCiphertextProof,
+            percentage_with_cap::PercentageWithCapProof,
+            percentage_with_cap_split::PercentageWithCapSplitProof,
+            pubkey_validity::PubkeyValidityProof,
+            split_consistency::SplitConsistencyProof,
+            zero_ciphertext::ZeroCiphertextProof,
+        },
+        super::{bytes_to_units, prover_commitment, units_to_bytes},
+        crate::encryption::pedersen::{G, H},
+        curve25519_dalek::scalar::Scalar,
+        solana_zk_sdk_pod::{sigma_proofs::*, UNIT_LEN},
+    };
+
+    #[test]
+    fn test_prover_commitment() {
+        let x = Scalar::from(55_u64);
+        let r = Scalar::from(77_u64);
+        assert_eq!(prover_commitment([&x, &r], [&G, &(*H)]), x * G + r * *H);
+    }
+
+    #[cfg(feature = "heapless")]
+    mod heapless {
+        use {
+            super::*,
+            crate::{
+                encryption::{
+                    elgamal::ElGamalKeypair, grouped_elgamal::GroupedElGamalCiphertext,
+                    pedersen::Pedersen,
+                },
+                transcript::TranscriptProtocol,
+            },
+            merlin::Transcript,
+            std::{
+                alloc::{GlobalAlloc, Layout, System},
+                cell::Cell,
+            },
+        };
+
+        /// An allocator that counts the allocations of the current thread.
+        struct CountingAllocator;
+
+        thread_local! {
+            static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        unsafe impl GlobalAlloc for CountingAllocator {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                let _ = ALLOCATIONS.try_with(|count| count.set(count.get().wrapping_add(1)));
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                System.dealloc(ptr, layout)
+            }
+        }
+
+        #[global_allocator]
+        static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+        /// Returns the number of allocations that `f` makes on the current thread.
+        fn count_allocations(f: impl FnOnce()) -> usize {
+            let before = ALLOCATIONS.with(Cell::get);
+            f();
+            ALLOCATIONS.with(Cell::get).wrapping_sub(before)
+        }
+
+        #[test]
+        fn test_proof_generation_does_not_allocate() {
+            let first_keypair = ElGamalKeypair::new_rand();
+            let second_keypair = ElGamalKeypair::new_rand();
+            let amount = 55_u64;
+            let (commitment, opening) = Pedersen::new(amount);
+            let first_ciphertext = first_keypair.pubkey().encrypt_with(amount, &opening);
+            let second_ciphertext = second_keypair.pubkey().encrypt_with(amount, &opening);
+            let grouped_ciphertext = GroupedElGamalCiphertext {
+                commitment,
+                handles: [
+                    first_keypair.pubkey().decrypt_handle(&opening),
+                    second_keypair.pubkey().decrypt_handle(&opening),
+                ],
+            };
+
+            let generate_proofs = || {
+                let mut transcript = Transcript::new_zk_elgamal_transcript(b"Test");
+                CiphertextCommitmentEqualityProof::new(
+                    &first_keypair,
+                    &first_ciphertext,
+                    &commitment,
+                    &opening,
+                    amount,
+                    &mut transcript,
+                );
+                CiphertextCiphertextEqualityProof::new(
+                    &first_keypair,
+                    second_keypair.pubkey(),
+                    &first_ciphertext,
+                    &second_ciphertext,
+                    &opening,
+                    amount,
+                    &mut transcript,
+                );
+                CiphertextCommitmentOpeningEqualityProof::new(
+                    first_keypair.pubkey(),
+                    &first_ciphertext,
+                    &commitment,
+                    &opening,
+                    &opening,
+                    amount,
+                    &mut transcript,
+                );
+                GroupedCiphertext2HandlesValidityProof::new(
+                    first_keypair.pubkey(),
+                    second_keypair.pubkey(),
+                    &grouped_ciphertext,
+                    amount,
+                    &opening,
+                    &mut transcript,
+                );
+                BatchedGroupedCiphertext2HandlesValidityProof::new(
+                    first_keypair.pubkey(),
+                    second_keypair.pubkey(),
+                    &grouped_ciphertext,
+                    &grouped_ciphertext,
+                    amount,
+                    amount,
+                    &opening,
+                    &opening,
+                    &mut transcript,
+                );
+                PubkeyValidityProof::new(&first_keypair, &mut transcript);
+            };
+
+            // the first run initializes the lazily computed generators and the entropy source
+            generate_proofs();
+            assert_eq!(count_allocations(generate_proofs), 0);
+        }
+    }
+
+    #[test]
+    fn test_proof_lengths_match_pod_lengths() {
+        assert_eq!(
+            CiphertextCommitmentEqualityProof::LEN,
+            PodCiphertextCommitmentEqualityProof::LEN
+        );
+        assert_eq!(
+            CiphertextCommitmentOpeningEqualityProof::LEN,
+            PodCiphertextCommitmentOpeningEqualityProof::LEN
+        );
+        assert_eq!(
+            CiphertextCiphertextEqualityProof::LEN,
+            PodCiphertextCiphertextEqualityProof::LEN
+        );
+        assert_eq!(
+            GroupedCiphertext2HandlesValidityProof::LEN,
+            PodGroupedCiphertext2HandlesValidityProof::LEN
+        );
+        assert_eq!(
+            GroupedCiphertext3HandlesValidityProof::LEN,
+            PodGroupedCiphertext3HandlesValidityProof::LEN
+        );
+        assert_eq!(
+            BatchedGroupedCiphertext2HandlesValidityProof::LEN,
+            PodBatchedGroupedCiphertext2HandlesValidityProof::LEN
+        );
+        assert_eq!(
+            BatchedGroupedCiphertext3HandlesValidityProof::LEN,
+            PodBatchedGroupedCiphertext3HandlesValidityProof::LEN
+        );
+        assert_eq!(ZeroCiphertextProof::LEN, PodZeroCiphertextProof::LEN);
+        assert_eq!(
+            GroupedZeroCiphertextProof::<2>::LEN,
+            PodGroupedZeroCiphertext2HandlesProof::LEN
+        );
+        assert_eq!(
+            GroupedZeroCiphertextProof::<3>::LEN,
+            PodGroupedZeroCiphertext3HandlesProof::LEN
+        );
+        assert_eq!(PercentageWithCapProof::LEN, PodPercentageWithCapProof::LEN);
+        assert_eq!(
+            PercentageWithCapSplitProof::LEN,
+            PodPercentageWithCapSplitProof::LEN
+        );
+        assert_eq!(PubkeyValidityProof::LEN, PodPubkeyValidityProof::LEN);
+        assert_eq!(SplitConsistencyProof::LEN, PodSplitConsistencyProof::LEN);
+    }
+
+    #[test]
+    fn test_units_round_trip() {
+        let units = [[1_u8; UNIT_LEN], [2_u8; UNIT_LEN], [3_u8; UNIT_LEN]];
+        let bytes: [u8; UNIT_LEN * 3] = units_to_bytes(units);
+        assert_eq!(bytes_to_units::<3>(&bytes).unwrap(), units);
+
+        // wrong number of units or a trailing partial unit is rejected
+        assert!(bytes_to_units::<2>(&bytes).is_err());
+        assert!(bytes_to_units::<4>(&bytes).is_err());
+        assert!(bytes_to_units::<3>(&bytes[1..]).is_err());
+        assert!(bytes_to_units::<2>(&bytes[1..]).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! The partial decryption sigma proof system.
+//!
+//! A custodian of a threshold key with the share `s_i` and the verification key `V_i = s_i * H`
+//! decrypts its part of a ciphertext with decryption handle `D` by computing the partial
+//! decryption `D_i = s_i * D`. A partial decryption proof certifies that `D_i` is consistent with
+//! the registered verification key, that is, that `V_i` and `D_i` have the same discrete log with
+//! respect to `H` and `D`. A combiner can then exclude malformed partial decryptions before it
+//! combines them.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::DecryptHandle,
+            pedersen::H,
+            threshold::{KeyShare, PartialDecryption, ShareVerificationKey},
+        },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            bytes_to_units, canonical_scalar_from_bytes,
+            errors::{PartialDecryptionProofVerificationError, SigmaProofVerificationError},
+            units_to_bytes,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    serde::{Deserialize, Serialize},
+    solana_zk_sdk_pod::UNIT_LEN,
+    zeroize::Zeroize,
+};
+
+/// Partial decryption proof.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PartialDecryptionProof {
+    Y_H: CompressedRistretto,
+    Y_D: CompressedRistretto,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl PartialDecryptionProof {
+    /// Byte length of a partial decryption proof.
+    pub const LEN: usize = UNIT_LEN * 3;
+
+    /// Creates a partial decryption proof.
+    ///
+    /// The function does not check that `partial_decryption` is the partial decryption of
+    /// `handle` under `share`. If it is not, the resulting proof does not verify.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `share` - The key share of the custodian
+    /// * `handle` - The decryption handle of the ciphertext
+    /// * `partial_decryption` - The partial decryption of the handle under the share
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new(
+        share: &KeyShare,
+        handle: &DecryptHandle,
+        partial_decryption: &PartialDecryption,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            share,
+            handle,
+            partial_decryption,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a partial decryption proof using `rng` to generate the random scalars.
+    pub fn new_with_rng(
+        share: &KeyShare,
+        handle: &DecryptHandle,
+        partial_decryption: &PartialDecryption,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(
+            &share.verification_key(),
+            handle,
+            partial_decryption,
+            transcript,
+        );
+        transcript.partial_decryption_proof_domain_separator();
+
+        let s = share.get_scalar();
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = try_random_scalar(rng)?;
+        let Y_H = (&y * &(*H)).compress();
+        let Y_D = (&y * handle.get_point()).compress();
+
+        // record masking factors in transcript and get challenges
+        transcript.append_point(b"Y_H", &Y_H);
+        transcript.append_point(b"Y_D", &Y_D);
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute masked share
+        let z = &(&c * s) + &y;
+
+        // zeroize random scalar
+        y.zeroize();
+
+        Ok(Self { Y_H, Y_D, z })
+    }
+
+    /// Verifies a partial decryption proof.
+    ///
+    /// * `verification_key` - The registered verification key of the custodian
+    /// * `handle` - The decryption handle of the ciphertext
+    /// * `partial_decryption` - The partial decryption to be verified
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        verification_key: &ShareVerificationKey,
+        handle: &DecryptHandle,
+        partial_decryption: &PartialDecryption,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), PartialDecryptionProofVerificationError> {
+        Self::hash_context_into_transcript(
+            verification_key,
+            handle,
+            partial_decryption,
+            transcript,
+        );
+        transcript.partial_decryption_proof_domain_separator();
+
+        if verification_key.index() != partial_decryption.index() {
+            return Err(SigmaProofVerificationError::AlgebraicRelation.into());
+        }
+        let V = verification_key.get_point();
+        let D = handle.get_point();
+        if V.is_identity() || D.is_identity() {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        // include Y_H and Y_D in the transcript and extract challenges
+        transcript.validate_and_append_point(b"Y_H", &self.Y_H)?;
+        transcript.validate_and_append_point(b"Y_D", &self.Y_D)?;
+        let c = transcript.challenge_scalar(b"c");
+        let w = transcript.batching_weight(b"w");
+
+        let Y_H = self
+            .Y_H
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let Y_D = self
+            .Y_D
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+        // check `z * H = c * V + Y_H` and `z * D = c * D_i + Y_D`
+        let check = vartime_multiscalar_mul(
+            [self.z, -c, -Scalar::ONE, w * self.z, -w * c, -w],
+            [&(*H), V, &Y_H, D, partial_decryption.get_point(), &Y_D],
+        );
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        verification_key: &ShareVerificationKey,
+        handle: &DecryptHandle,
+        partial_decryption: &PartialDecryption,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        transcript.append_u64(b"index", verification_key.index() as u64);
+        transcript.append_point(
+            b"verification-key",
+            &verification_key.get_point().compress(),
+        );
+        transcript.append_message(b"handle", &handle.to_bytes());
+        transcript.append_point(
+            b"partial-decryption",
+            &partial_decryption.get_point().compress(),
+        );
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([self.Y_H.to_bytes(), self.Y_D.to_bytes(), self.z.to_bytes()])
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PartialDecryptionProofVerificationError> {
+        let [Y_H, Y_D, z] = bytes_to_units(bytes)?;
+
+        let Y_H = CompressedRistretto(Y_H);
+        let Y_D = CompressedRistretto(Y_D);
+        let z = canonical_scalar_from_bytes(z)?;
+        Ok(PartialDecryptionProof { Y_H, Y_D, z })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::{elgamal::ElGamalKeypair, threshold::ThresholdParameters},
+        merlin::Transcript,
+    };
+
+    #[test]
+    fn test_partial_decryption_proof_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+        let parameters = ThresholdParameters::new(2, 3).unwrap();
+        let shares = KeyShare::split(keypair.secret(), &parameters);
+        let ciphertext = keypair.pubkey().encrypt(55_u64);
+
+        let partial_decryption = shares[0].partial_decrypt(&ciphertext);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PartialDecryptionProof::new(
+            &shares[0],
+            &ciphertext.handle,
+            &partial_decryption,
+            &mut prover_transcript,
+        );
+        let proof = PartialDecryptionProof::from_bytes(&proof.to_bytes()).unwrap();
+        proof
+            .clone()
+            .verify(
+                &shares[0].verification_key(),
+                &ciphertext.handle,
+                &partial_decryption,
+                &mut verifier_transcript,
+            )
+            .unwrap();
+
+        // the proof does not verify against the verification key of another custodian
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        assert!(proof
+            .verify(
+                &shares[1].verification_key(),
+                &ciphertext.handle,
+                &partial_decryption,
+                &mut verifier_transcript,
+            )
+            .is_err());
+
+        // a partial decryption under the wrong share does not verify
+        let wrong_partial_decryption = shares[1].partial_decrypt(&ciphertext);
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let proof = PartialDecryptionProof::new(
+            &shares[0],
+            &ciphertext.handle,
+            &wrong_partial_decryption,
+            &mut prover_transcript,
+        );
+        assert!(proof
+            .verify(
+                &shares[0].verification_key(),
+                &ciphertext.handle,
+                &wrong_partial_decryption,
+                &mut verifier_transcript,
+            )
+            .is_err());
+    }
+}
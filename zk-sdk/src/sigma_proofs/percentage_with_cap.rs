@@ -39,28 +39,26 @@
 use {
     crate::{
         encryption::pedersen::{PedersenCommitment, PedersenOpening, G, H},
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, verifier_weight, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{PercentageWithCapProofVerificationError, SigmaProofVerificationError},
-            ristretto_point_from_optional_slice,
+            units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
     curve25519_dalek::{
         ristretto::{CompressedRistretto, RistrettoPoint},
         scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul, VartimeMultiscalarMul},
+        traits::{IsIdentity, MultiscalarMul},
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
     solana_zk_sdk_pod::{sigma_proofs::PodPercentageWithCapProof, UNIT_LEN},
     subtle::{Choice, ConditionallySelectable, ConstantTimeGreater},
     zeroize::Zeroize,
 };
 
-/// Byte length of a percentage-with-cap proof.
-const PERCENTAGE_WITH_CAP_PROOF_LEN: usize = UNIT_LEN * 8;
-
 /// Percentage-with-cap proof.
 ///
 /// The proof consists of two main components: `percentage_max_proof` and
@@ -79,6 +77,9 @@ pub struct PercentageWithCapProof {
 
 #[allow(non_snake_case, dead_code)]
 impl PercentageWithCapProof {
+    /// Byte length of a percentage-with-cap proof.
+    pub const LEN: usize = UNIT_LEN * 8;
+
     /// Creates a percentage-with-cap sigma proof.
     ///
     /// This proof certifies that a committed `percentage_amount` satisfies one of two conditions,
@@ -117,8 +118,139 @@ impl PercentageWithCapProof {
         claimed_commitment: &PedersenCommitment,
         claimed_opening: &PedersenOpening,
         max_value: u64,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a percentage-with-cap sigma proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u64,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::new_with_max_value(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            PercentageMaxValue::U64(max_value),
+            transcript,
+            rng,
+        )
+    }
+
+    /// Creates a percentage-with-cap sigma proof with respect to a 128-bit maximum cap bound.
+    ///
+    /// The proof is identical to the one produced by [`Self::new`] except that the maximum cap
+    /// bound is hashed into the transcript as a 128-bit value. Proofs produced by this function
+    /// must be verified with [`Self::verify_u128`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_u128(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u128,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_u128_with_rng(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a percentage-with-cap sigma proof with respect to a 128-bit maximum cap bound
+    /// using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new_u128`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_u128_with_rng(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u128,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::new_with_max_value(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            PercentageMaxValue::U128(max_value),
+            transcript,
+            rng,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_with_max_value(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: PercentageMaxValue,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(
             percentage_commitment,
             delta_commitment,
@@ -147,7 +279,8 @@ impl PercentageWithCapProof {
             delta_commitment,
             claimed_commitment,
             &mut transcript_percentage_above_max,
-        );
+            rng,
+        )?;
 
         // `proof_above_max` will be invalid in case 1, but it will be discarded below
         let proof_below_max = Self::create_proof_percentage_below_max(
@@ -155,11 +288,20 @@ impl PercentageWithCapProof {
             delta_opening,
             delta_amount,
             claimed_opening,
-            max_value,
+            max_value.to_scalar(),
             &mut transcript_percentage_below_max,
-        );
-
-        let below_max = u64::ct_gt(&max_value, &percentage_amount);
+            rng,
+        )?;
+
+        // the maximum cap bound is public, so only the comparison against the percentage amount
+        // needs to be done in constant time
+        let below_max = match max_value {
+            PercentageMaxValue::U64(max_value) => u64::ct_gt(&max_value, &percentage_amount),
+            PercentageMaxValue::U128(max_value) => match u64::try_from(max_value) {
+                Ok(max_value) => u64::ct_gt(&max_value, &percentage_amount),
+                Err(_) => Choice::from(1),
+            },
+        };
 
         // choose one of `proof_above_max` or `proof_below_max` depending on whether the computed
         // fee is less than the max value
@@ -187,10 +329,10 @@ impl PercentageWithCapProof {
         transcript.append_scalar(b"z_claimed", &percentage_equality_proof.z_claimed);
         let _w = transcript.challenge_scalar(b"w");
 
-        Self {
+        Ok(Self {
             percentage_max_proof,
             percentage_equality_proof,
-        }
+        })
     }
 
     /// Creates a percentage-with-cap proof assuming that the committed percentage is greater than
@@ -211,8 +353,9 @@ impl PercentageWithCapProof {
         percentage_opening: &PedersenOpening,
         delta_commitment: &PedersenCommitment,
         claimed_commitment: &PedersenCommitment,
-        transcript: &mut Transcript,
-    ) -> Self {
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         // simulate equality proof
         // 1. sample random values for the scalar components
         // 2. solve for `Y_delta` and `Y_claimed` values that will satisfy the algebraic
@@ -220,10 +363,10 @@ impl PercentageWithCapProof {
         let C_delta = delta_commitment.get_point();
         let C_claimed = claimed_commitment.get_point();
 
-        let z_x = Scalar::random(&mut OsRng);
-        let z_delta = Scalar::random(&mut OsRng);
-        let z_claimed = Scalar::random(&mut OsRng);
-        let mut c_equality = Scalar::random(&mut OsRng);
+        let z_x = try_random_scalar(rng)?;
+        let z_delta = try_random_scalar(rng)?;
+        let z_claimed = try_random_scalar(rng)?;
+        let mut c_equality = try_random_scalar(rng)?;
 
         let Y_delta = RistrettoPoint::multiscalar_mul(
             vec![z_x, z_delta, -c_equality],
@@ -248,7 +391,7 @@ impl PercentageWithCapProof {
         // generate max proof properly
         let r_percentage = percentage_opening.get_scalar();
 
-        let mut y_max_proof = Scalar::random(&mut OsRng); // blinding factor for the percentage opening
+        let mut y_max_proof = try_random_scalar(rng)?; // blinding factor for the percentage opening
         let Y_max_proof = (y_max_proof * &(*H)).compress(); // commitment to blinding factor
 
         // provide the simulated `Y_max_proof`, `Y_delta`, and the properly generated `Y_claimed`
@@ -285,10 +428,10 @@ impl PercentageWithCapProof {
         c_equality.zeroize();
         y_max_proof.zeroize();
 
-        Self {
+        Ok(Self {
             percentage_max_proof,
             percentage_equality_proof,
-        }
+        })
     }
 
     /// Creates a percentage-with-cap proof assuming that the committed amount is less than the
@@ -304,24 +447,24 @@ impl PercentageWithCapProof {
     /// * `delta_opening` - The Pedersen opening of a delta amount
     /// * `delta_amount` - The delta amount
     /// * `claimed_opening` - The Pedersen opening of a claimed amount
-    /// * `max_value` - The maximum cap bound
+    /// * `m` - The maximum cap bound as a scalar
     /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
     fn create_proof_percentage_below_max(
         percentage_commitment: &PedersenCommitment,
         delta_opening: &PedersenOpening,
         delta_amount: u64,
         claimed_opening: &PedersenOpening,
-        max_value: u64,
-        transcript: &mut Transcript,
-    ) -> Self {
+        m: Scalar,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         // simulate max proof
         // 1. sample random values for the scalar components
         // 2. solve for `Y_max_proof` value that will satisfy the algebraic verification relation
-        let m = Scalar::from(max_value);
         let C_percentage = percentage_commitment.get_point();
 
-        let z_max_proof = Scalar::random(&mut OsRng);
-        let c_max_proof = Scalar::random(&mut OsRng); // random challenge
+        let z_max_proof = try_random_scalar(rng)?;
+        let c_max_proof = try_random_scalar(rng)?; // random challenge
 
         // solve for Y_max in the verification algebraic relation
         let Y_max_proof = RistrettoPoint::multiscalar_mul(
@@ -342,9 +485,9 @@ impl PercentageWithCapProof {
         let r_delta = delta_opening.get_scalar();
         let r_claimed = claimed_opening.get_scalar();
 
-        let mut y_x = Scalar::random(&mut OsRng); // generate blinding factors
-        let mut y_delta = Scalar::random(&mut OsRng);
-        let mut y_claimed = Scalar::random(&mut OsRng);
+        let mut y_x = try_random_scalar(rng)?; // generate blinding factors
+        let mut y_delta = try_random_scalar(rng)?;
+        let mut y_claimed = try_random_scalar(rng)?;
 
         // commitment to blinding factors
         let Y_delta =
@@ -392,10 +535,10 @@ impl PercentageWithCapProof {
         y_delta.zeroize();
         y_claimed.zeroize();
 
-        Self {
+        Ok(Self {
             percentage_max_proof,
             percentage_equality_proof,
-        }
+        })
     }
 
     /// Verifies a percentage-with-cap proof.
@@ -411,8 +554,142 @@ impl PercentageWithCapProof {
         delta_commitment: &PedersenCommitment,
         claimed_commitment: &PedersenCommitment,
         max_value: u64,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), PercentageWithCapProofVerificationError> {
+        self.verify_with_max_value(
+            percentage_commitment,
+            delta_commitment,
+            claimed_commitment,
+            PercentageMaxValue::U64(max_value),
+            transcript,
+        )
+    }
+
+    /// Verifies a percentage-with-cap proof with respect to a 128-bit maximum cap bound.
+    ///
+    /// * `percentage_commitment` - The Pedersen commitment of the value being proved
+    /// * `delta_commitment` - The Pedersen commitment of the "real" delta value
+    /// * `claimed_commitment` - The Pedersen commitment of the "claimed" delta value
+    /// * `max_value` - The 128-bit maximum cap bound
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify_u128(
+        self,
+        percentage_commitment: &PedersenCommitment,
+        delta_commitment: &PedersenCommitment,
+        claimed_commitment: &PedersenCommitment,
+        max_value: u128,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), PercentageWithCapProofVerificationError> {
+        self.verify_with_max_value(
+            percentage_commitment,
+            delta_commitment,
+            claimed_commitment,
+            PercentageMaxValue::U128(max_value),
+            transcript,
+        )
+    }
+
+    /// Verifies a batch of percentage-with-cap proofs with respect to the same maximum cap bound.
+    ///
+    /// The verification equations of all proofs are combined with random weights and checked
+    /// with a single multiscalar multiplication, in which the terms of the generators `G` and `H`
+    /// are shared by all proofs. The `i`-th proof is verified with respect to the `i`-th
+    /// statement and the `i`-th transcript. If the batch fails to verify, it does not identify
+    /// the invalid proofs.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the weights that
+    /// combine the proofs.
+    ///
+    /// * `proofs` - The proofs to be verified
+    /// * `statements` - The commitments that each proof is verified against
+    /// * `max_value` - The maximum cap bound
+    /// * `transcripts` - The transcripts of the proofs
+    pub fn verify_batch<T: TranscriptProtocol>(
+        proofs: &[Self],
+        statements: &[PercentageWithCapStatement],
+        max_value: u64,
+        transcripts: &mut [T],
+    ) -> Result<(), PercentageWithCapProofVerificationError> {
+        if proofs.len() != statements.len() || proofs.len() != transcripts.len() {
+            return Err(SigmaProofVerificationError::BatchLength.into());
+        }
+
+        let mut g = Scalar::ZERO;
+        let mut h = Scalar::ZERO;
+        let mut scalars = Vec::with_capacity(proofs.len() * VERIFICATION_POINTS + 2);
+        let mut points = Vec::with_capacity(proofs.len() * VERIFICATION_POINTS + 2);
+        for (i, ((proof, statement), transcript)) in proofs
+            .iter()
+            .zip(statements)
+            .zip(transcripts.iter_mut())
+            .enumerate()
+        {
+            let terms = proof.verification_terms(
+                &statement.percentage_commitment,
+                &statement.delta_commitment,
+                &statement.claimed_commitment,
+                PercentageMaxValue::U64(max_value),
+                transcript,
+            )?;
+
+            // the first equation is left unscaled, as only the relative weights matter
+            let weight = if i == 0 {
+                Scalar::ONE
+            } else {
+                verifier_weight()
+            };
+            g += weight * terms.g;
+            h += weight * terms.h;
+            scalars.extend(terms.scalars.iter().map(|scalar| weight * scalar));
+            points.extend(terms.points);
+        }
+        scalars.extend([g, h]);
+        points.extend([G, *H]);
+
+        if vartime_multiscalar_mul(scalars, points).is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn verify_with_max_value(
+        self,
+        percentage_commitment: &PedersenCommitment,
+        delta_commitment: &PedersenCommitment,
+        claimed_commitment: &PedersenCommitment,
+        max_value: PercentageMaxValue,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), PercentageWithCapProofVerificationError> {
+        let terms = self.verification_terms(
+            percentage_commitment,
+            delta_commitment,
+            claimed_commitment,
+            max_value,
+            transcript,
+        )?;
+
+        let check = vartime_multiscalar_mul(
+            terms.scalars.iter().chain([&terms.g, &terms.h]),
+            terms.points.iter().chain([&G, &(*H)]),
+        );
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Computes the terms of the verification equation of the proof.
+    fn verification_terms(
+        &self,
+        percentage_commitment: &PedersenCommitment,
+        delta_commitment: &PedersenCommitment,
+        claimed_commitment: &PedersenCommitment,
+        max_value: PercentageMaxValue,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<VerificationTerms, PercentageWithCapProofVerificationError> {
         if percentage_commitment.get_point().is_identity()
             || delta_commitment.get_point().is_identity()
             || claimed_commitment.get_point().is_identity()
@@ -430,7 +707,7 @@ impl PercentageWithCapProof {
         transcript.percentage_with_cap_proof_domain_separator();
 
         // extract the relevant scalar and Ristretto points from the input
-        let m = Scalar::from(max_value);
+        let m = max_value.to_scalar();
 
         let C_max = percentage_commitment.get_point();
         let C_delta = delta_commitment.get_point();
@@ -473,113 +750,84 @@ impl PercentageWithCapProof {
         transcript.append_scalar(b"z_x", &z_x);
         transcript.append_scalar(b"z_delta_real", &z_delta_real);
         transcript.append_scalar(b"z_claimed", &z_claimed);
-        let w = transcript.challenge_scalar(b"w");
+        let w = transcript.batching_weight(b"w");
         let ww = w * w;
 
-        let check = RistrettoPoint::vartime_multiscalar_mul(
-            vec![
+        // the terms of `G` and `H` are collected, so that they can be shared across a batch
+        Ok(VerificationTerms {
+            g: -c_max_proof * m + w * z_x + ww * z_x,
+            h: -z_max + w * z_delta_real + ww * z_claimed,
+            scalars: [
                 c_max_proof,
-                -c_max_proof * m,
-                -z_max,
                 Scalar::ONE,
-                w * z_x,
-                w * z_delta_real,
                 -w * c_equality,
                 -w,
-                ww * z_x,
-                ww * z_claimed,
                 -ww * c_equality,
                 -ww,
             ],
-            vec![
-                C_max,
-                &G,
-                &(*H),
-                &Y_max,
-                &G,
-                &(*H),
-                C_delta,
-                &Y_delta_real,
-                &G,
-                &(*H),
-                C_claimed,
-                &Y_claimed,
-            ],
-        );
-
-        if check.is_identity() {
-            Ok(())
-        } else {
-            Err(SigmaProofVerificationError::AlgebraicRelation.into())
-        }
+            points: [*C_max, Y_max, *C_delta, Y_delta_real, *C_claimed, Y_claimed],
+        })
     }
 
     fn hash_context_into_transcript(
         percentage_commitment: &PedersenCommitment,
         delta_commitment: &PedersenCommitment,
         claimed_commitment: &PedersenCommitment,
-        max_value: u64,
-        transcript: &mut Transcript,
+        max_value: PercentageMaxValue,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"percentage-commitment", &percentage_commitment.to_bytes());
         transcript.append_message(b"delta-commitment", &delta_commitment.to_bytes());
         transcript.append_message(b"claimed-commitment", &claimed_commitment.to_bytes());
-        transcript.append_u64(b"max-value", max_value);
-    }
-
-    pub fn to_bytes(&self) -> [u8; PERCENTAGE_WITH_CAP_PROOF_LEN] {
-        let mut buf = [0_u8; PERCENTAGE_WITH_CAP_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_max_proof.Y_max_proof.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_max_proof.z_max_proof.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_max_proof.c_max_proof.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_equality_proof.Y_delta.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_equality_proof.Y_claimed.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_equality_proof.z_x.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_equality_proof.z_delta.as_bytes());
-        chunks
-            .next()
-            .unwrap()
-            .copy_from_slice(self.percentage_equality_proof.z_claimed.as_bytes());
-        buf
+        match max_value {
+            PercentageMaxValue::U64(max_value) => transcript.append_u64(b"max-value", max_value),
+            PercentageMaxValue::U128(max_value) => {
+                transcript.append_message(b"max-value-u128", &max_value.to_le_bytes())
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes(self.to_units())
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, PercentageWithCapProofVerificationError> {
-        if bytes.len() != PERCENTAGE_WITH_CAP_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        Self::from_units(bytes_to_units(bytes)?)
+    }
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y_max_proof = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_max_proof = canonical_scalar_from_optional_slice(chunks.next())?;
-        let c_max_proof = canonical_scalar_from_optional_slice(chunks.next())?;
+    /// Splits the proof into its 32-byte components in serialization order.
+    ///
+    /// The percentage-with-cap fee-split proof embeds these components at the start of its own
+    /// encoding.
+    pub(crate) fn to_units(&self) -> [[u8; UNIT_LEN]; 8] {
+        [
+            self.percentage_max_proof.Y_max_proof.to_bytes(),
+            self.percentage_max_proof.z_max_proof.to_bytes(),
+            self.percentage_max_proof.c_max_proof.to_bytes(),
+            self.percentage_equality_proof.Y_delta.to_bytes(),
+            self.percentage_equality_proof.Y_claimed.to_bytes(),
+            self.percentage_equality_proof.z_x.to_bytes(),
+            self.percentage_equality_proof.z_delta.to_bytes(),
+            self.percentage_equality_proof.z_claimed.to_bytes(),
+        ]
+    }
 
-        let Y_delta = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_claimed = ristretto_point_from_optional_slice(chunks.next())?;
-        let z_x = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_delta = canonical_scalar_from_optional_slice(chunks.next())?;
-        let z_claimed = canonical_scalar_from_optional_slice(chunks.next())?;
+    /// Reassembles a proof from its 32-byte components.
+    pub(crate) fn from_units(
+        units: [[u8; UNIT_LEN]; 8],
+    ) -> Result<Self, PercentageWithCapProofVerificationError> {
+        let [Y_max_proof, z_max_proof, c_max_proof, Y_delta, Y_claimed, z_x, z_delta, z_claimed] =
+            units;
+
+        let Y_max_proof = CompressedRistretto(Y_max_proof);
+        let z_max_proof = canonical_scalar_from_bytes(z_max_proof)?;
+        let c_max_proof = canonical_scalar_from_bytes(c_max_proof)?;
+
+        let Y_delta = CompressedRistretto(Y_delta);
+        let Y_claimed = CompressedRistretto(Y_claimed);
+        let z_x = canonical_scalar_from_bytes(z_x)?;
+        let z_delta = canonical_scalar_from_bytes(z_delta)?;
+        let z_claimed = canonical_scalar_from_bytes(z_claimed)?;
 
         Ok(Self {
             percentage_max_proof: PercentageMaxProof {
@@ -598,6 +846,50 @@ impl PercentageWithCapProof {
     }
 }
 
+/// The commitments that a percentage-with-cap proof is verified against.
+#[derive(Clone, Copy, Debug)]
+pub struct PercentageWithCapStatement {
+    /// The Pedersen commitment of the value being proved
+    pub percentage_commitment: PedersenCommitment,
+    /// The Pedersen commitment of the "real" delta value
+    pub delta_commitment: PedersenCommitment,
+    /// The Pedersen commitment of the "claimed" delta value
+    pub claimed_commitment: PedersenCommitment,
+}
+
+/// The number of terms of the verification equation other than the terms of `G` and `H`.
+const VERIFICATION_POINTS: usize = 6;
+
+/// The terms of the verification equation of a percentage-with-cap proof.
+///
+/// The scalars of the generators `G` and `H` are kept apart from the other terms.
+struct VerificationTerms {
+    g: Scalar,
+    h: Scalar,
+    scalars: [Scalar; VERIFICATION_POINTS],
+    points: [RistrettoPoint; VERIFICATION_POINTS],
+}
+
+/// The maximum cap bound of a percentage-with-cap proof.
+///
+/// The encoding width of the bound is hashed into the transcript so that a proof generated with
+/// respect to a 64-bit bound cannot be verified as a proof with respect to a 128-bit bound and
+/// vice versa.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PercentageMaxValue {
+    U64(u64),
+    U128(u128),
+}
+
+impl PercentageMaxValue {
+    fn to_scalar(self) -> Scalar {
+        match self {
+            Self::U64(max_value) => Scalar::from(max_value),
+            Self::U128(max_value) => Scalar::from(max_value),
+        }
+    }
+}
+
 /// The percentage max proof.
 ///
 /// The proof certifies that a Pedersen commitment encodes the maximum cap bound.
@@ -665,6 +957,12 @@ impl From<PercentageWithCapProof> for PodPercentageWithCapProof {
     }
 }
 
+impl From<&PercentageWithCapProof> for PodPercentageWithCapProof {
+    fn from(decoded_proof: &PercentageWithCapProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodPercentageWithCapProof> for PercentageWithCapProof {
     type Error = PercentageWithCapProofVerificationError;
 
@@ -673,11 +971,20 @@ impl TryFrom<PodPercentageWithCapProof> for PercentageWithCapProof {
     }
 }
 
+impl TryFrom<&PodPercentageWithCapProof> for PercentageWithCapProof {
+    type Error = PercentageWithCapProofVerificationError;
+
+    fn try_from(pod_proof: &PodPercentageWithCapProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
         crate::encryption::pedersen::Pedersen,
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::pedersen::PodPedersenCommitment, sigma_proofs::PodPercentageWithCapProof,
         },
@@ -714,7 +1021,9 @@ mod test {
             &delta_commitment,
             &claimed_commitment,
             &mut prover_transcript,
-        );
+            &mut DefaultRng,
+        )
+        .unwrap();
 
         assert!(proof
             .verify(
@@ -761,9 +1070,11 @@ mod test {
             &delta_opening,
             delta_amount,
             &claimed_opening,
-            max_value,
+            Scalar::from(max_value),
             &mut prover_transcript,
-        );
+            &mut DefaultRng,
+        )
+        .unwrap();
 
         assert!(proof
             .verify(
@@ -885,6 +1196,77 @@ mod test {
             .unwrap();
     }
 
+    #[test]
+    fn test_verify_batch() {
+        let max_value: u64 = 3;
+        let percentage_rate: u64 = 400; // 4.00%
+
+        let (proofs, statements): (Vec<_>, Vec<_>) = [1_u64, 50, 10_000]
+            .into_iter()
+            .map(|transfer_amount| {
+                let fee = (transfer_amount * percentage_rate).div_ceil(10_000);
+                let percentage_amount = fee.min(max_value);
+                // the claimed delta is irrelevant if the fee is capped
+                let delta = (percentage_amount * 10_000)
+                    .checked_sub(transfer_amount * percentage_rate)
+                    .unwrap_or_default();
+
+                let (transfer_commitment, transfer_opening) = Pedersen::new(transfer_amount);
+                let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+                let delta_commitment =
+                    &percentage_commitment * 10_000 - &transfer_commitment * percentage_rate;
+                let delta_opening =
+                    &percentage_opening * 10_000 - &transfer_opening * percentage_rate;
+                let (claimed_commitment, claimed_opening) = Pedersen::new(delta);
+
+                let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+                let proof = PercentageWithCapProof::new(
+                    &percentage_commitment,
+                    &percentage_opening,
+                    percentage_amount,
+                    &delta_commitment,
+                    &delta_opening,
+                    delta,
+                    &claimed_commitment,
+                    &claimed_opening,
+                    max_value,
+                    &mut prover_transcript,
+                );
+                let statement = PercentageWithCapStatement {
+                    percentage_commitment,
+                    delta_commitment,
+                    claimed_commitment,
+                };
+                (proof, statement)
+            })
+            .unzip();
+
+        let mut transcripts = vec![Transcript::new_zk_elgamal_transcript(b"test"); 3];
+        PercentageWithCapProof::verify_batch(&proofs, &statements, max_value, &mut transcripts)
+            .unwrap();
+
+        // the statements of two proofs are swapped
+        let mut transcripts = vec![Transcript::new_zk_elgamal_transcript(b"test"); 3];
+        let swapped_statements = [statements[1], statements[0], statements[2]];
+        assert_eq!(
+            PercentageWithCapProof::verify_batch(
+                &proofs,
+                &swapped_statements,
+                max_value,
+                &mut transcripts,
+            )
+            .unwrap_err(),
+            SigmaProofVerificationError::AlgebraicRelation.into()
+        );
+
+        let mut transcripts = vec![Transcript::new_zk_elgamal_transcript(b"test"); 2];
+        assert_eq!(
+            PercentageWithCapProof::verify_batch(&proofs, &statements, max_value, &mut transcripts)
+                .unwrap_err(),
+            SigmaProofVerificationError::BatchLength.into()
+        );
+    }
+
     #[test]
     fn test_proof_delta_is_zero() {
         let transfer_amount: u64 = 100;
@@ -967,4 +1349,78 @@ mod test {
             )
             .unwrap();
     }
+
+    #[test]
+    fn test_proof_u128_max_value() {
+        let transfer_amount: u64 = 1_000_000;
+        let max_value: u128 = u64::MAX as u128 + 1;
+
+        let percentage_rate: u64 = 1_500; // 0.15% in parts-per-million
+        let percentage_amount: u64 = 1_500;
+        let delta: u64 = 0; // 1_500 * 1_000_000 - 1_000_000 * 1_500
+
+        let (transfer_commitment, transfer_opening) = Pedersen::new(transfer_amount);
+        let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+
+        let delta_commitment =
+            &percentage_commitment * 1_000_000_u64 - &transfer_commitment * percentage_rate;
+        let delta_opening =
+            &percentage_opening * 1_000_000_u64 - &transfer_opening * percentage_rate;
+
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapProof::new_u128(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+            &mut prover_transcript,
+        );
+
+        proof
+            .clone()
+            .verify_u128(
+                &percentage_commitment,
+                &delta_commitment,
+                &claimed_commitment,
+                max_value,
+                &mut verifier_transcript,
+            )
+            .unwrap();
+
+        // the encoding width of the max value is bound to the proof
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapProof::new(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta,
+            &claimed_commitment,
+            &claimed_opening,
+            u64::MAX,
+            &mut prover_transcript,
+        );
+
+        assert!(proof
+            .verify_u128(
+                &percentage_commitment,
+                &delta_commitment,
+                &claimed_commitment,
+                u64::MAX as u128,
+                &mut verifier_transcript,
+            )
+            .is_err());
+    }
 }
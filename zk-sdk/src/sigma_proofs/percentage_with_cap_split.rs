@@ -0,0 +1,552 @@
+//! The percentage-with-cap fee-split sigma proof.
+//!
+//! The proof extends the [`PercentageWithCapProof`] with a sequence of Pedersen commitments that
+//! encode how the capped fee is split among multiple recipients (e.g. a protocol fee and a
+//! referrer fee). In addition to the percentage-with-cap relation, the proof certifies that the
+//! share commitments `C_1, ..., C_n` add up to the `percentage` (fee) commitment `C_fee`. This is
+//! done by proving knowledge of an opening `r` such that `C_fee - (C_1 + ... + C_n) = r * H`.
+//!
+//! Both relations are proven under the same transcript, so the fee shares are bound to the same
+//! fee and transfer amount that the percentage-with-cap relation is defined over. Chaining
+//! independent percentage-with-cap proofs for each recipient would not provide this guarantee.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+//!
+//! # Security Warning: Standalone Usage
+//!
+//! Similar to the percentage-with-cap proof, the share relation is verified using scalar field
+//! arithmetic. Any protocol using this module MUST also enforce a `RangeProof` on each of the
+//! share commitments so that the shares cannot "wrap around" the field modulus.
+
+use {
+    crate::{
+        encryption::pedersen::{PedersenCommitment, PedersenOpening, H},
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            bytes_to_units, canonical_scalar_from_bytes,
+            errors::{PercentageWithCapProofVerificationError, SigmaProofVerificationError},
+            percentage_with_cap::PercentageWithCapProof,
+            write_units,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::{sigma_proofs::PodPercentageWithCapSplitProof, UNIT_LEN},
+    zeroize::Zeroize,
+};
+
+/// Percentage-with-cap fee-split proof.
+///
+/// The proof consists of a percentage-with-cap proof for the total fee and a sigma proof that
+/// certifies that the fee share commitments add up to the total fee commitment.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct PercentageWithCapSplitProof {
+    /// Proof that the total fee is a capped percentage of the transfer amount
+    percentage_with_cap_proof: PercentageWithCapProof,
+
+    /// Proof that the fee shares add up to the total fee
+    Y_split: CompressedRistretto,
+    z_split: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl PercentageWithCapSplitProof {
+    /// Byte length of a percentage-with-cap fee-split proof.
+    pub const LEN: usize = PercentageWithCapProof::LEN + UNIT_LEN * 2;
+
+    /// Creates a percentage-with-cap fee-split proof.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// The caller must ensure that `share_commitments` is non-empty and free of the identity point,
+    /// that `share_commitments` and `share_openings` have the same length, and that the share
+    /// commitments add up to `percentage_commitment`. Otherwise, the resulting proof will not
+    /// verify.
+    ///
+    /// * `percentage_commitment` - The Pedersen commitment to the total fee amount
+    /// * `percentage_opening` - The Pedersen opening of the total fee amount
+    /// * `percentage_amount` - The total fee amount
+    /// * `delta_commitment` - The Pedersen commitment to a delta amount
+    /// * `delta_opening` - The Pedersen opening of a delta amount
+    /// * `delta_amount` - The delta amount
+    /// * `claimed_commitment` - The Pedersen commitment to a claimed amount
+    /// * `claimed_opening` - The Pedersen opening of a claimed amount
+    /// * `max_value` - The maximum cap bound
+    /// * `share_commitments` - The Pedersen commitments to the fee shares
+    /// * `share_openings` - The Pedersen openings of the fee shares
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u64,
+        share_commitments: &[&PedersenCommitment],
+        share_openings: &[&PedersenOpening],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            share_commitments,
+            share_openings,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a percentage-with-cap fee-split proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng(
+        percentage_commitment: &PedersenCommitment,
+        percentage_opening: &PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: &PedersenCommitment,
+        delta_opening: &PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: &PedersenCommitment,
+        claimed_opening: &PedersenOpening,
+        max_value: u64,
+        share_commitments: &[&PedersenCommitment],
+        share_openings: &[&PedersenOpening],
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(share_commitments, transcript);
+        transcript.percentage_with_cap_split_proof_domain_separator(share_commitments.len() as u64);
+
+        let percentage_with_cap_proof = PercentageWithCapProof::new_with_rng(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            transcript,
+            rng,
+        )?;
+
+        // the opening of `C_fee - (C_1 + ... + C_n)` with respect to `H`
+        let mut r = share_openings
+            .iter()
+            .fold(*percentage_opening.get_scalar(), |acc, opening| {
+                acc - opening.get_scalar()
+            });
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = try_random_scalar(rng)?;
+        let Y_split = (&y * &(*H)).compress();
+
+        transcript.append_point(b"Y_split", &Y_split);
+        let c = transcript.challenge_scalar(b"c_split");
+
+        let z_split = &(&c * &r) + &y;
+
+        transcript.append_scalar(b"z_split", &z_split);
+
+        r.zeroize();
+        y.zeroize();
+
+        Ok(Self {
+            percentage_with_cap_proof,
+            Y_split,
+            z_split,
+        })
+    }
+
+    /// Verifies a percentage-with-cap fee-split proof.
+    ///
+    /// * `percentage_commitment` - The Pedersen commitment to the total fee amount
+    /// * `delta_commitment` - The Pedersen commitment of the "real" delta value
+    /// * `claimed_commitment` - The Pedersen commitment of the "claimed" delta value
+    /// * `max_value` - The maximum cap bound
+    /// * `share_commitments` - The Pedersen commitments to the fee shares
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        percentage_commitment: &PedersenCommitment,
+        delta_commitment: &PedersenCommitment,
+        claimed_commitment: &PedersenCommitment,
+        max_value: u64,
+        share_commitments: &[&PedersenCommitment],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), PercentageWithCapProofVerificationError> {
+        if share_commitments.is_empty()
+            || share_commitments
+                .iter()
+                .any(|commitment| commitment.get_point().is_identity())
+        {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(share_commitments, transcript);
+        transcript.percentage_with_cap_split_proof_domain_separator(share_commitments.len() as u64);
+
+        self.percentage_with_cap_proof.verify(
+            percentage_commitment,
+            delta_commitment,
+            claimed_commitment,
+            max_value,
+            transcript,
+        )?;
+
+        transcript.validate_and_append_point(b"Y_split", &self.Y_split)?;
+        let c = transcript.challenge_scalar(b"c_split");
+
+        transcript.append_scalar(b"z_split", &self.z_split);
+
+        let Y_split = self
+            .Y_split
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+        // check `z * H = c * (C_fee - (C_1 + ... + C_n)) + Y`
+        let scalars = [self.z_split, -c, -Scalar::ONE]
+            .into_iter()
+            .chain(std::iter::repeat_n(c, share_commitments.len()));
+        let points = [&(*H), percentage_commitment.get_point(), &Y_split]
+            .into_iter()
+            .chain(
+                share_commitments
+                    .iter()
+                    .map(|commitment| commitment.get_point()),
+            );
+
+        let check = vartime_multiscalar_mul(scalars, points);
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        share_commitments: &[&PedersenCommitment],
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        for commitment in share_commitments {
+            transcript.append_message(b"share-commitment", &commitment.to_bytes());
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut buf = [0_u8; Self::LEN];
+        let units = self
+            .percentage_with_cap_proof
+            .to_units()
+            .into_iter()
+            .chain([self.Y_split.to_bytes(), self.z_split.to_bytes()]);
+        write_units(&mut buf, units);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PercentageWithCapProofVerificationError> {
+        let [percentage_with_cap_units @ .., Y_split, z_split]: [_; Self::LEN / UNIT_LEN] =
+            bytes_to_units(bytes)?;
+
+        let percentage_with_cap_proof =
+            PercentageWithCapProof::from_units(percentage_with_cap_units)?;
+        let Y_split = CompressedRistretto(Y_split);
+        let z_split = canonical_scalar_from_bytes(z_split)?;
+
+        Ok(Self {
+            percentage_with_cap_proof,
+            Y_split,
+            z_split,
+        })
+    }
+}
+
+impl From<PercentageWithCapSplitProof> for PodPercentageWithCapSplitProof {
+    fn from(decoded_proof: PercentageWithCapSplitProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl From<&PercentageWithCapSplitProof> for PodPercentageWithCapSplitProof {
+    fn from(decoded_proof: &PercentageWithCapSplitProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl TryFrom<PodPercentageWithCapSplitProof> for PercentageWithCapSplitProof {
+    type Error = PercentageWithCapProofVerificationError;
+
+    fn try_from(pod_proof: PodPercentageWithCapSplitProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl TryFrom<&PodPercentageWithCapSplitProof> for PercentageWithCapSplitProof {
+    type Error = PercentageWithCapProofVerificationError;
+
+    fn try_from(pod_proof: &PodPercentageWithCapSplitProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::pedersen::Pedersen, merlin::Transcript};
+
+    struct FeeTestCase {
+        percentage_commitment: PedersenCommitment,
+        percentage_opening: PedersenOpening,
+        percentage_amount: u64,
+        delta_commitment: PedersenCommitment,
+        delta_opening: PedersenOpening,
+        delta_amount: u64,
+        claimed_commitment: PedersenCommitment,
+        claimed_opening: PedersenOpening,
+    }
+
+    fn fee_test_case(transfer_amount: u64, percentage_rate: u64, fee_amount: u64) -> FeeTestCase {
+        let (transfer_commitment, transfer_opening) = Pedersen::new(transfer_amount);
+        let (percentage_commitment, percentage_opening) = Pedersen::new(fee_amount);
+
+        let delta_commitment =
+            &percentage_commitment * 10_000 - &transfer_commitment * percentage_rate;
+        let delta_opening = &percentage_opening * 10_000 - &transfer_opening * percentage_rate;
+        let delta_amount = fee_amount * 10_000 - transfer_amount * percentage_rate;
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+
+        FeeTestCase {
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount: fee_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+        }
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_proof_correctness() {
+        // 2% of 10_000 is 200, split into a protocol fee and a referrer fee
+        let case = fee_test_case(10_000, 200, 200);
+        let max_value: u64 = 1_000;
+
+        let (protocol_commitment, protocol_opening) = Pedersen::new(150_u64);
+        // the last opening is chosen so that the openings add up to the fee opening
+        let referrer_opening = &case.percentage_opening - &protocol_opening;
+        let referrer_commitment = Pedersen::with(50_u64, &referrer_opening);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapSplitProof::new(
+            &case.percentage_commitment,
+            &case.percentage_opening,
+            case.percentage_amount,
+            &case.delta_commitment,
+            &case.delta_opening,
+            case.delta_amount,
+            &case.claimed_commitment,
+            &case.claimed_opening,
+            max_value,
+            &[&protocol_commitment, &referrer_commitment],
+            &[&protocol_opening, &referrer_opening],
+            &mut prover_transcript,
+        );
+
+        proof
+            .verify(
+                &case.percentage_commitment,
+                &case.delta_commitment,
+                &case.claimed_commitment,
+                max_value,
+                &[&protocol_commitment, &referrer_commitment],
+                &mut verifier_transcript,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_proof_independent_openings() {
+        // the share openings do not need to add up to the fee opening
+        let case = fee_test_case(10_000, 200, 200);
+        let max_value: u64 = 1_000;
+
+        let (protocol_commitment, protocol_opening) = Pedersen::new(120_u64);
+        let (referrer_commitment, referrer_opening) = Pedersen::new(50_u64);
+        let (partner_commitment, partner_opening) = Pedersen::new(30_u64);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapSplitProof::new(
+            &case.percentage_commitment,
+            &case.percentage_opening,
+            case.percentage_amount,
+            &case.delta_commitment,
+            &case.delta_opening,
+            case.delta_amount,
+            &case.claimed_commitment,
+            &case.claimed_opening,
+            max_value,
+            &[
+                &protocol_commitment,
+                &referrer_commitment,
+                &partner_commitment,
+            ],
+            &[&protocol_opening, &referrer_opening, &partner_opening],
+            &mut prover_transcript,
+        );
+
+        let proof = PercentageWithCapSplitProof::from_bytes(&proof.to_bytes()).unwrap();
+
+        proof
+            .verify(
+                &case.percentage_commitment,
+                &case.delta_commitment,
+                &case.claimed_commitment,
+                max_value,
+                &[
+                    &protocol_commitment,
+                    &referrer_commitment,
+                    &partner_commitment,
+                ],
+                &mut verifier_transcript,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_proof_empty_shares() {
+        let case = fee_test_case(10_000, 200, 200);
+
+        let max_value: u64 = 1_000;
+
+        let proof = PercentageWithCapSplitProof::new(
+            &case.percentage_commitment,
+            &case.percentage_opening,
+            case.percentage_amount,
+            &case.delta_commitment,
+            &case.delta_opening,
+            case.delta_amount,
+            &case.claimed_commitment,
+            &case.claimed_opening,
+            max_value,
+            &[],
+            &[],
+            &mut Transcript::new_zk_elgamal_transcript(b"test"),
+        );
+
+        let err = proof
+            .verify(
+                &case.percentage_commitment,
+                &case.delta_commitment,
+                &case.claimed_commitment,
+                max_value,
+                &[],
+                &mut Transcript::new_zk_elgamal_transcript(b"test"),
+            )
+            .unwrap_err();
+        assert_eq!(err, SigmaProofVerificationError::IdentityPoint.into());
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_proof_inconsistent_shares() {
+        let case = fee_test_case(10_000, 200, 200);
+        let max_value: u64 = 1_000;
+
+        // the shares add up to 201 instead of 200
+        let (protocol_commitment, protocol_opening) = Pedersen::new(151_u64);
+        let (referrer_commitment, referrer_opening) = Pedersen::new(50_u64);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapSplitProof::new(
+            &case.percentage_commitment,
+            &case.percentage_opening,
+            case.percentage_amount,
+            &case.delta_commitment,
+            &case.delta_opening,
+            case.delta_amount,
+            &case.claimed_commitment,
+            &case.claimed_opening,
+            max_value,
+            &[&protocol_commitment, &referrer_commitment],
+            &[&protocol_opening, &referrer_opening],
+            &mut prover_transcript,
+        );
+
+        let err = proof
+            .verify(
+                &case.percentage_commitment,
+                &case.delta_commitment,
+                &case.claimed_commitment,
+                max_value,
+                &[&protocol_commitment, &referrer_commitment],
+                &mut verifier_transcript,
+            )
+            .unwrap_err();
+        assert_eq!(err, SigmaProofVerificationError::AlgebraicRelation.into());
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_proof_swapped_shares() {
+        let case = fee_test_case(10_000, 200, 200);
+        let max_value: u64 = 1_000;
+
+        let (protocol_commitment, protocol_opening) = Pedersen::new(150_u64);
+        let (referrer_commitment, referrer_opening) = Pedersen::new(50_u64);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        let proof = PercentageWithCapSplitProof::new(
+            &case.percentage_commitment,
+            &case.percentage_opening,
+            case.percentage_amount,
+            &case.delta_commitment,
+            &case.delta_opening,
+            case.delta_amount,
+            &case.claimed_commitment,
+            &case.claimed_opening,
+            max_value,
+            &[&protocol_commitment, &referrer_commitment],
+            &[&protocol_opening, &referrer_opening],
+            &mut prover_transcript,
+        );
+
+        // the order of the shares is bound to the transcript
+        assert!(proof
+            .verify(
+                &case.percentage_commitment,
+                &case.delta_commitment,
+                &case.claimed_commitment,
+                max_value,
+                &[&referrer_commitment, &protocol_commitment],
+                &mut verifier_transcript,
+            )
+            .is_err());
+    }
+}
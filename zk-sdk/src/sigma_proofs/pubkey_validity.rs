@@ -9,27 +9,25 @@ use {
             elgamal::{ElGamalKeypair, ElGamalPubkey},
             pedersen::H,
         },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, verifier_weight, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{PubkeyValidityProofVerificationError, SigmaProofVerificationError},
-            ristretto_point_from_optional_slice,
+            units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
     curve25519_dalek::{
         ristretto::{CompressedRistretto, RistrettoPoint},
         scalar::Scalar,
-        traits::{IsIdentity, VartimeMultiscalarMul},
+        traits::IsIdentity,
     },
-    merlin::Transcript,
-    rand::rngs::OsRng,
     solana_zk_sdk_pod::{sigma_proofs::PodPubkeyValidityProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a public key validity proof.
-const PUBKEY_VALIDITY_PROOF_LEN: usize = UNIT_LEN * 2;
-
 /// Public-key proof.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -42,6 +40,9 @@ pub struct PubkeyValidityProof {
 
 #[allow(non_snake_case)]
 impl PubkeyValidityProof {
+    /// Byte length of a public key validity proof.
+    pub const LEN: usize = UNIT_LEN * 2;
+
     /// Creates a public key validity proof.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -52,7 +53,24 @@ impl PubkeyValidityProof {
     /// * `elgamal_keypair` - The ElGamal keypair that pertains to the ElGamal public key to be
     ///   proved
     /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
-    pub fn new(elgamal_keypair: &ElGamalKeypair, transcript: &mut Transcript) -> Self {
+    pub fn new(elgamal_keypair: &ElGamalKeypair, transcript: &mut impl TranscriptProtocol) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            elgamal_keypair,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a public key validity proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        elgamal_keypair: &ElGamalKeypair,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(elgamal_keypair.pubkey(), transcript);
         transcript.pubkey_proof_domain_separator();
 
@@ -63,7 +81,7 @@ impl PubkeyValidityProof {
         let mut s_inv = s.invert();
 
         // generate a random masking factor that also serves as a nonce
-        let mut y = Scalar::random(&mut OsRng);
+        let mut y = try_random_scalar(rng)?;
         let Y = (&y * &(*H)).compress();
 
         // record masking factors in transcript and get challenges
@@ -77,7 +95,47 @@ impl PubkeyValidityProof {
         s_inv.zeroize();
         y.zeroize();
 
-        Self { Y, z }
+        Ok(Self { Y, z })
+    }
+
+    /// Creates public key validity proofs for a batch of keypairs in a single transcript.
+    ///
+    /// The size of the batch is hashed into the transcript and the proofs are generated in order,
+    /// so each proof is bound to the public keys that precede it. The proofs must be verified
+    /// together and in the same order with [`Self::verify_batch`].
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// This function panics if any of the provided keypairs is not valid (i.e. secret key is not
+    /// invertible).
+    ///
+    /// * `elgamal_keypairs` - The ElGamal keypairs that pertain to the public keys to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new_batch(
+        elgamal_keypairs: &[ElGamalKeypair],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Vec<Self> {
+        unwrap_default_rng(Self::new_batch_with_rng(
+            elgamal_keypairs,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates public key validity proofs for a batch of keypairs using `rng` to generate the
+    /// random scalars.
+    ///
+    /// See [`Self::new_batch`] for a description of the arguments.
+    pub fn new_batch_with_rng(
+        elgamal_keypairs: &[ElGamalKeypair],
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Vec<Self>, RngError> {
+        transcript.batched_pubkey_proof_domain_separator(elgamal_keypairs.len() as u64);
+        elgamal_keypairs
+            .iter()
+            .map(|keypair| Self::new_with_rng(keypair, transcript, rng))
+            .collect()
     }
 
     /// Verifies a public key validity proof. The function rejects identity public keys
@@ -88,15 +146,83 @@ impl PubkeyValidityProof {
     pub fn verify(
         self,
         elgamal_pubkey: &ElGamalPubkey,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), PubkeyValidityProofVerificationError> {
-        Self::hash_context_into_transcript(elgamal_pubkey, transcript);
-        transcript.pubkey_proof_domain_separator();
+        let (c, Y) = self.verification_challenge(elgamal_pubkey, transcript)?;
 
-        // extract the relevant scalar and Ristretto points from the input
+        // check that the required algebraic condition holds
         let P = elgamal_pubkey.get_point();
+        let check =
+            vartime_multiscalar_mul(vec![&self.z, &(-&c), &(-&Scalar::ONE)], vec![&(*H), P, &Y]);
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Verifies a batch of public key validity proofs that were generated with
+    /// [`Self::new_batch`].
+    ///
+    /// The verification equations of the proofs are combined with random weights and checked
+    /// with a single multiscalar multiplication. If the batch fails to verify, it does not
+    /// identify the invalid proofs.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the weights that
+    /// combine the proofs.
+    ///
+    /// * `proofs` - The proofs to be verified, in the order in which they were generated
+    /// * `elgamal_pubkeys` - The ElGamal public keys to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify_batch(
+        proofs: &[Self],
+        elgamal_pubkeys: &[ElGamalPubkey],
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), PubkeyValidityProofVerificationError> {
+        if proofs.len() != elgamal_pubkeys.len() {
+            return Err(SigmaProofVerificationError::BatchLength.into());
+        }
+        transcript.batched_pubkey_proof_domain_separator(proofs.len() as u64);
+
+        // the scalar of `H` is shared by all proofs
+        let mut h = Scalar::ZERO;
+        let mut scalars = Vec::with_capacity(2 * proofs.len() + 1);
+        let mut points = Vec::with_capacity(2 * proofs.len() + 1);
+        for (i, (proof, pubkey)) in proofs.iter().zip(elgamal_pubkeys).enumerate() {
+            let (c, Y) = proof.verification_challenge(pubkey, transcript)?;
+
+            // the first equation is left unscaled, as only the relative weights matter
+            let weight = if i == 0 {
+                Scalar::ONE
+            } else {
+                verifier_weight()
+            };
+            h += weight * proof.z;
+            scalars.extend([-weight * c, -weight]);
+            points.extend([*pubkey.get_point(), Y]);
+        }
+        scalars.push(h);
+        points.push(*H);
+
+        if vartime_multiscalar_mul(scalars, points).is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    /// Hashes the statement and the commitment of the proof into the transcript and returns the
+    /// challenge and the decompressed commitment.
+    fn verification_challenge(
+        &self,
+        elgamal_pubkey: &ElGamalPubkey,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(Scalar, RistrettoPoint), PubkeyValidityProofVerificationError> {
+        Self::hash_context_into_transcript(elgamal_pubkey, transcript);
+        transcript.pubkey_proof_domain_separator();
 
-        if P.is_identity() {
+        if elgamal_pubkey.get_point().is_identity() {
             return Err(SigmaProofVerificationError::IdentityPoint.into());
         }
 
@@ -104,44 +230,29 @@ impl PubkeyValidityProof {
         transcript.validate_and_append_point(b"Y", &self.Y)?;
         let c = transcript.challenge_scalar(b"c");
 
-        // check that the required algebraic condition holds
         let Y = self
             .Y
             .decompress()
             .ok_or(SigmaProofVerificationError::Deserialization)?;
-
-        let check = RistrettoPoint::vartime_multiscalar_mul(
-            vec![&self.z, &(-&c), &(-&Scalar::ONE)],
-            vec![&(*H), P, &Y],
-        );
-
-        if check.is_identity() {
-            Ok(())
-        } else {
-            Err(SigmaProofVerificationError::AlgebraicRelation.into())
-        }
+        Ok((c, Y))
     }
 
-    fn hash_context_into_transcript(pubkey: &ElGamalPubkey, transcript: &mut Transcript) {
+    fn hash_context_into_transcript(
+        pubkey: &ElGamalPubkey,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
         transcript.append_message(b"pubkey", &pubkey.to_bytes());
     }
 
-    pub fn to_bytes(&self) -> [u8; PUBKEY_VALIDITY_PROOF_LEN] {
-        let mut buf = [0_u8; PUBKEY_VALIDITY_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks.next().unwrap().copy_from_slice(self.Y.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z.as_bytes());
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([self.Y.to_bytes(), self.z.to_bytes()])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, PubkeyValidityProofVerificationError> {
-        if bytes.len() != PUBKEY_VALIDITY_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        let [Y, z] = bytes_to_units(bytes)?;
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y = ristretto_point_from_optional_slice(chunks.next())?;
-        let z = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y = CompressedRistretto(Y);
+        let z = canonical_scalar_from_bytes(z)?;
         Ok(PubkeyValidityProof { Y, z })
     }
 }
@@ -152,6 +263,12 @@ impl From<PubkeyValidityProof> for PodPubkeyValidityProof {
     }
 }
 
+impl From<&PubkeyValidityProof> for PodPubkeyValidityProof {
+    fn from(decoded_proof: &PubkeyValidityProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodPubkeyValidityProof> for PubkeyValidityProof {
     type Error = PubkeyValidityProofVerificationError;
 
@@ -160,12 +277,21 @@ impl TryFrom<PodPubkeyValidityProof> for PubkeyValidityProof {
     }
 }
 
+impl TryFrom<&PodPubkeyValidityProof> for PubkeyValidityProof {
+    type Error = PubkeyValidityProofVerificationError;
+
+    fn try_from(pod_proof: &PodPubkeyValidityProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
         super::*,
         bytemuck::Zeroable,
-        curve25519_dalek::traits::Identity,
+        curve25519_dalek::{ristretto::RistrettoPoint, traits::Identity},
+        merlin::Transcript,
         solana_address::Address,
         solana_keypair::Keypair,
         solana_zk_sdk_pod::{
@@ -216,6 +342,12 @@ mod test {
 
         let proof_str = "utgoLBANuVRtvN7YyZrUwz0dZL+ObsDlRpJdb6erXiQZWCtkvRbSJ8mSBKPvkahHunah80JooQWqhFQXkOCWBw==";
         let pod_proof = PodPubkeyValidityProof::from_str(proof_str).unwrap();
+        let proof: PubkeyValidityProof = (&pod_proof).try_into().unwrap();
+
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+
+        proof.verify(&pubkey, &mut verifier_transcript).unwrap();
+
         let proof: PubkeyValidityProof = pod_proof.try_into().unwrap();
 
         let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
@@ -0,0 +1,459 @@
+//! The lo/hi split consistency sigma proof system.
+//!
+//! The split consistency proof is defined with respect to an ElGamal public key, two ElGamal
+//! ciphertexts that encrypt the low and high bits of an amount, and an ElGamal ciphertext of the
+//! full amount, all under the same public key. The proof certifies that the ciphertext of the full
+//! amount is consistent with the split, i.e. that it encrypts `amount_lo + 2^48 * amount_hi`. To
+//! generate the proof, a prover must provide the Pedersen openings associated with the three
+//! ciphertexts.
+//!
+//! If `(C_lo, D_lo)`, `(C_hi, D_hi)`, and `(C, D)` are the three ciphertexts under the public key
+//! `P`, then the split is consistent if and only if `(C - C_lo - 2^48*C_hi, D - D_lo - 2^48*D_hi)`
+//! is an encryption of zero, i.e. if there is an `r` such that `C - C_lo - 2^48*C_hi = r*H` and
+//! `D - D_lo - 2^48*D_hi = r*P`. The proof is a proof of knowledge of this common discrete log with
+//! respect to `H` and `P`.
+//!
+//! The proof does not certify the bit lengths of the two halves, and it is sound only together
+//! with a range proof on the commitments of both halves: `C_lo` must be proven to fit in 48 bits
+//! and `C_hi` must be proven to fit in 16 bits. Without a range proof on `C_hi`, a prover can
+//! split an amount into halves whose combination wraps around the group order.
+//!
+//! The protocol guarantees computational soundness (by the hardness of discrete log) and perfect
+//! zero-knowledge in the random oracle model.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::{PedersenOpening, H},
+        },
+        errors::RngError,
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
+        sigma_proofs::{
+            bytes_to_units, canonical_scalar_from_bytes,
+            errors::{SigmaProofVerificationError, SplitConsistencyProofVerificationError},
+            units_to_bytes,
+        },
+        transcript::TranscriptProtocol,
+    },
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
+    solana_zk_sdk_pod::{sigma_proofs::PodSplitConsistencyProof, UNIT_LEN},
+    zeroize::Zeroize,
+};
+
+/// Bit length of the low bits of a split amount.
+pub const SPLIT_LO_BIT_LENGTH: usize = 48;
+
+/// Bit length of the high bits of a split amount.
+pub const SPLIT_HI_BIT_LENGTH: usize = 16;
+
+/// Split consistency proof.
+///
+/// Contains all the elliptic curve and scalar components that make up the sigma protocol.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct SplitConsistencyProof {
+    Y_H: CompressedRistretto,
+    Y_P: CompressedRistretto,
+    z: Scalar,
+}
+
+#[allow(non_snake_case)]
+impl SplitConsistencyProof {
+    /// Byte length of a split consistency proof.
+    pub const LEN: usize = UNIT_LEN * 3;
+
+    /// Creates a split consistency proof.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate random scalars.
+    ///
+    /// * `elgamal_pubkey` - The ElGamal pubkey under which the three ciphertexts are encrypted
+    /// * `ciphertext_lo` - The ciphertext of the low bits of the amount
+    /// * `ciphertext_hi` - The ciphertext of the high bits of the amount
+    /// * `ciphertext` - The ciphertext of the full amount
+    /// * `opening_lo` - The opening associated with `ciphertext_lo`
+    /// * `opening_hi` - The opening associated with `ciphertext_hi`
+    /// * `opening` - The opening associated with `ciphertext`
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        elgamal_pubkey: &ElGamalPubkey,
+        ciphertext_lo: &ElGamalCiphertext,
+        ciphertext_hi: &ElGamalCiphertext,
+        ciphertext: &ElGamalCiphertext,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            elgamal_pubkey,
+            ciphertext_lo,
+            ciphertext_hi,
+            ciphertext,
+            opening_lo,
+            opening_hi,
+            opening,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a split consistency proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_rng(
+        elgamal_pubkey: &ElGamalPubkey,
+        ciphertext_lo: &ElGamalCiphertext,
+        ciphertext_hi: &ElGamalCiphertext,
+        ciphertext: &ElGamalCiphertext,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        opening: &PedersenOpening,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
+        Self::hash_context_into_transcript(
+            elgamal_pubkey,
+            ciphertext_lo,
+            ciphertext_hi,
+            ciphertext,
+            transcript,
+        );
+        transcript.split_consistency_proof_domain_separator();
+
+        let P = elgamal_pubkey.get_point();
+
+        // the common opening of `C - C_lo - 2^48*C_hi` and `D - D_lo - 2^48*D_hi`
+        let mut r =
+            opening.get_scalar() - opening_lo.get_scalar() - hi_shift() * opening_hi.get_scalar();
+
+        // generate a random masking factor that also serves as a nonce
+        let mut y = try_random_scalar(rng)?;
+        let Y_H = (&y * &(*H)).compress();
+        let Y_P = (&y * P).compress();
+
+        // record masking factors in the transcript and receive a challenge scalar
+        transcript.append_point(b"Y_H", &Y_H);
+        transcript.append_point(b"Y_P", &Y_P);
+        let c = transcript.challenge_scalar(b"c");
+
+        // compute the masked opening
+        let z = &(&c * &r) + &y;
+
+        transcript.append_scalar(b"z", &z);
+        let _w = transcript.challenge_scalar(b"w");
+
+        // zeroize all sensitive owned variables
+        r.zeroize();
+        y.zeroize();
+
+        Ok(Self { Y_H, Y_P, z })
+    }
+
+    /// Verifies a split consistency proof.
+    ///
+    /// * `elgamal_pubkey` - The ElGamal pubkey under which the three ciphertexts are encrypted
+    /// * `ciphertext_lo` - The ciphertext of the low bits of the amount
+    /// * `ciphertext_hi` - The ciphertext of the high bits of the amount
+    /// * `ciphertext` - The ciphertext of the full amount
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn verify(
+        self,
+        elgamal_pubkey: &ElGamalPubkey,
+        ciphertext_lo: &ElGamalCiphertext,
+        ciphertext_hi: &ElGamalCiphertext,
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<(), SplitConsistencyProofVerificationError> {
+        // We reject if the pubkey or any component of the three ciphertexts is the identity point.
+        if elgamal_pubkey.get_point().is_identity()
+            || [ciphertext_lo, ciphertext_hi, ciphertext]
+                .iter()
+                .any(|ciphertext| {
+                    ciphertext.commitment.get_point().is_identity()
+                        || ciphertext.handle.get_point().is_identity()
+                })
+        {
+            return Err(SigmaProofVerificationError::IdentityPoint.into());
+        }
+
+        Self::hash_context_into_transcript(
+            elgamal_pubkey,
+            ciphertext_lo,
+            ciphertext_hi,
+            ciphertext,
+            transcript,
+        );
+        transcript.split_consistency_proof_domain_separator();
+
+        // record masking factors in transcript and receive challenge scalars
+        transcript.validate_and_append_point(b"Y_H", &self.Y_H)?;
+        transcript.validate_and_append_point(b"Y_P", &self.Y_P)?;
+        let c = transcript.challenge_scalar(b"c");
+
+        transcript.append_scalar(b"z", &self.z);
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
+
+        // decompress Y or return verification error
+        let Y_H = self
+            .Y_H
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+        let Y_P = self
+            .Y_P
+            .decompress()
+            .ok_or(SigmaProofVerificationError::Deserialization)?;
+
+        // check the required algebraic relations
+        //   z*H = c*(C - C_lo - 2^48*C_hi) + Y_H
+        //   z*P = c*(D - D_lo - 2^48*D_hi) + Y_P
+        let c_shifted = c * hi_shift();
+        let w_z = w * self.z;
+        let w_c = w * c;
+        let w_c_shifted = w * c_shifted;
+        let check = vartime_multiscalar_mul(
+            vec![
+                self.z,
+                -c,
+                c,
+                c_shifted,
+                -Scalar::ONE,
+                w_z,
+                -w_c,
+                w_c,
+                w_c_shifted,
+                -w,
+            ],
+            vec![
+                *H,
+                *ciphertext.commitment.get_point(),
+                *ciphertext_lo.commitment.get_point(),
+                *ciphertext_hi.commitment.get_point(),
+                Y_H,
+                *elgamal_pubkey.get_point(),
+                *ciphertext.handle.get_point(),
+                *ciphertext_lo.handle.get_point(),
+                *ciphertext_hi.handle.get_point(),
+                Y_P,
+            ],
+        );
+
+        if check.is_identity() {
+            Ok(())
+        } else {
+            Err(SigmaProofVerificationError::AlgebraicRelation.into())
+        }
+    }
+
+    fn hash_context_into_transcript(
+        elgamal_pubkey: &ElGamalPubkey,
+        ciphertext_lo: &ElGamalCiphertext,
+        ciphertext_hi: &ElGamalCiphertext,
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+    ) {
+        transcript.append_message(b"pubkey", &elgamal_pubkey.to_bytes());
+        transcript.append_message(b"ciphertext-lo", &ciphertext_lo.to_bytes());
+        transcript.append_message(b"ciphertext-hi", &ciphertext_hi.to_bytes());
+        transcript.append_message(b"ciphertext", &ciphertext.to_bytes());
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([self.Y_H.to_bytes(), self.Y_P.to_bytes(), self.z.to_bytes()])
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SplitConsistencyProofVerificationError> {
+        let [Y_H, Y_P, z] = bytes_to_units(bytes)?;
+
+        let Y_H = CompressedRistretto(Y_H);
+        let Y_P = CompressedRistretto(Y_P);
+        let z = canonical_scalar_from_bytes(z)?;
+        Ok(SplitConsistencyProof { Y_H, Y_P, z })
+    }
+}
+
+/// The scalar `2^48` by which the high bits of a split amount are shifted.
+fn hi_shift() -> Scalar {
+    Scalar::from(1_u64 << SPLIT_LO_BIT_LENGTH)
+}
+
+impl From<SplitConsistencyProof> for PodSplitConsistencyProof {
+    fn from(decoded_proof: SplitConsistencyProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl From<&SplitConsistencyProof> for PodSplitConsistencyProof {
+    fn from(decoded_proof: &SplitConsistencyProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
+impl TryFrom<PodSplitConsistencyProof> for SplitConsistencyProof {
+    type Error = SplitConsistencyProofVerificationError;
+
+    fn try_from(pod_proof: PodSplitConsistencyProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+impl TryFrom<&PodSplitConsistencyProof> for SplitConsistencyProof {
+    type Error = SplitConsistencyProofVerificationError;
+
+    fn try_from(pod_proof: &PodSplitConsistencyProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::encryption::{elgamal::ElGamalKeypair, pedersen::PedersenCommitment},
+        merlin::Transcript,
+    };
+
+    fn prove_and_verify(
+        keypair: &ElGamalKeypair,
+        ciphertext_lo: &ElGamalCiphertext,
+        ciphertext_hi: &ElGamalCiphertext,
+        ciphertext: &ElGamalCiphertext,
+        opening_lo: &PedersenOpening,
+        opening_hi: &PedersenOpening,
+        opening: &PedersenOpening,
+    ) -> Result<(), SplitConsistencyProofVerificationError> {
+        let mut prover_transcript = Transcript::new(b"test");
+        let mut verifier_transcript = Transcript::new(b"test");
+
+        let proof = SplitConsistencyProof::new(
+            keypair.pubkey(),
+            ciphertext_lo,
+            ciphertext_hi,
+            ciphertext,
+            opening_lo,
+            opening_hi,
+            opening,
+            &mut prover_transcript,
+        );
+
+        // round trip through the pod representation
+        let pod_proof = PodSplitConsistencyProof::from(&proof);
+        let decoded_proof = SplitConsistencyProof::try_from(pod_proof).unwrap();
+        assert_eq!(decoded_proof.to_bytes(), proof.to_bytes());
+
+        proof.verify(
+            keypair.pubkey(),
+            ciphertext_lo,
+            ciphertext_hi,
+            ciphertext,
+            &mut verifier_transcript,
+        )
+    }
+
+    #[test]
+    fn test_split_consistency_proof_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 0xabcd_1234_5678_9abc;
+        let amount_lo = amount & ((1 << SPLIT_LO_BIT_LENGTH) - 1);
+        let amount_hi = amount >> SPLIT_LO_BIT_LENGTH;
+
+        let opening_lo = PedersenOpening::new_rand();
+        let opening_hi = PedersenOpening::new_rand();
+        let opening = PedersenOpening::new_rand();
+        let ciphertext_lo = keypair.pubkey().encrypt_with(amount_lo, &opening_lo);
+        let ciphertext_hi = keypair.pubkey().encrypt_with(amount_hi, &opening_hi);
+        let ciphertext = keypair.pubkey().encrypt_with(amount, &opening);
+
+        prove_and_verify(
+            &keypair,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            &ciphertext,
+            &opening_lo,
+            &opening_hi,
+            &opening,
+        )
+        .unwrap();
+
+        // a ciphertext of a different amount is rejected
+        let ciphertext = keypair.pubkey().encrypt_with(amount + 1, &opening);
+        assert!(prove_and_verify(
+            &keypair,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            &ciphertext,
+            &opening_lo,
+            &opening_hi,
+            &opening,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_split_consistency_proof_inconsistent_handle() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 0xabcd_1234_5678_9abc;
+        let amount_lo = amount & ((1 << SPLIT_LO_BIT_LENGTH) - 1);
+        let amount_hi = amount >> SPLIT_LO_BIT_LENGTH;
+
+        let opening_lo = PedersenOpening::new_rand();
+        let opening_hi = PedersenOpening::new_rand();
+        let opening = PedersenOpening::new_rand();
+        let ciphertext_lo = keypair.pubkey().encrypt_with(amount_lo, &opening_lo);
+        let ciphertext_hi = keypair.pubkey().encrypt_with(amount_hi, &opening_hi);
+
+        // the commitment of the full ciphertext is consistent with the split, but its handle is
+        // encrypted under a different opening
+        let mut ciphertext = keypair.pubkey().encrypt_with(amount, &opening);
+        ciphertext.handle = keypair
+            .pubkey()
+            .decrypt_handle(&PedersenOpening::new_rand());
+
+        assert!(prove_and_verify(
+            &keypair,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            &ciphertext,
+            &opening_lo,
+            &opening_hi,
+            &opening,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_split_consistency_proof_identity() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let opening_lo = PedersenOpening::new_rand();
+        let opening_hi = PedersenOpening::new_rand();
+        let opening = PedersenOpening::new_rand();
+        let ciphertext_lo = keypair.pubkey().encrypt_with(0_u64, &opening_lo);
+        let ciphertext_hi = keypair.pubkey().encrypt_with(0_u64, &opening_hi);
+        let ciphertext = keypair.pubkey().encrypt_with(0_u64, &opening);
+
+        // a ciphertext with an identity commitment is rejected
+        let mut identity_ciphertext = ciphertext_hi;
+        identity_ciphertext.commitment = PedersenCommitment::from_bytes(&[0; 32]).unwrap();
+        assert_eq!(
+            prove_and_verify(
+                &keypair,
+                &ciphertext_lo,
+                &identity_ciphertext,
+                &ciphertext,
+                &opening_lo,
+                &opening_hi,
+                &opening,
+            )
+            .unwrap_err(),
+            SigmaProofVerificationError::IdentityPoint.into(),
+        );
+    }
+}
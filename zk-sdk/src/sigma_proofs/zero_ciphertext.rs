@@ -8,28 +8,23 @@ use {
         encryption::{
             elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
             pedersen::H,
+            secret_key_handle::{SecretKeyBackend, SecretKeyHandle},
         },
+        errors::{RngError, SecretKeyBackendError},
+        msm::vartime_multiscalar_mul,
+        rng::{try_random_scalar, unwrap_default_rng, CryptoRngCore, DefaultRng},
         sigma_proofs::{
-            canonical_scalar_from_optional_slice,
+            bytes_to_units, canonical_scalar_from_bytes,
             errors::{SigmaProofVerificationError, ZeroCiphertextProofVerificationError},
-            ristretto_point_from_optional_slice,
+            units_to_bytes,
         },
         transcript::TranscriptProtocol,
     },
-    curve25519_dalek::{
-        ristretto::{CompressedRistretto, RistrettoPoint},
-        scalar::Scalar,
-        traits::{IsIdentity, MultiscalarMul},
-    },
-    merlin::Transcript,
-    rand::rngs::OsRng,
+    curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
     solana_zk_sdk_pod::{sigma_proofs::PodZeroCiphertextProof, UNIT_LEN},
     zeroize::Zeroize,
 };
 
-/// Byte length of a zero-ciphertext proof.
-const ZERO_CIPHERTEXT_PROOF_LEN: usize = UNIT_LEN * 3;
-
 /// Zero-ciphertext proof.
 ///
 /// Contains all the elliptic curve and scalar components that make up the sigma protocol.
@@ -43,6 +38,9 @@ pub struct ZeroCiphertextProof {
 
 #[allow(non_snake_case)]
 impl ZeroCiphertextProof {
+    /// Byte length of a zero-ciphertext proof.
+    pub const LEN: usize = UNIT_LEN * 3;
+
     /// Creates a zero-ciphertext proof.
     ///
     /// This function is randomized. It uses `OsRng` internally to generate random scalars.
@@ -53,8 +51,27 @@ impl ZeroCiphertextProof {
     pub fn new(
         elgamal_keypair: &ElGamalKeypair,
         ciphertext: &ElGamalCiphertext,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Self {
+        unwrap_default_rng(Self::new_with_rng(
+            elgamal_keypair,
+            ciphertext,
+            transcript,
+            &mut DefaultRng,
+        ))
+    }
+
+    /// Creates a zero-ciphertext proof using `rng` to generate the random scalars.
+    ///
+    /// Returns an error if `rng` fails to produce random bytes.
+    ///
+    /// See [`Self::new`] for a description of the arguments.
+    pub fn new_with_rng(
+        elgamal_keypair: &ElGamalKeypair,
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<Self, RngError> {
         Self::hash_context_into_transcript(elgamal_keypair.pubkey(), ciphertext, transcript);
         transcript.zero_ciphertext_proof_domain_separator();
 
@@ -64,7 +81,7 @@ impl ZeroCiphertextProof {
         let D = ciphertext.handle.get_point();
 
         // generate a random masking factor that also serves as a nonce
-        let mut y = Scalar::random(&mut OsRng);
+        let mut y = try_random_scalar(rng)?;
         let Y_P = (&y * P).compress();
         let Y_D = (&y * D).compress();
 
@@ -83,7 +100,47 @@ impl ZeroCiphertextProof {
         // zeroize random scalar
         y.zeroize();
 
-        Self { Y_P, Y_D, z }
+        Ok(Self { Y_P, Y_D, z })
+    }
+
+    /// Creates a zero-ciphertext proof with a secret key held by an external backend.
+    ///
+    /// The backend answers the sigma protocol round for the secret key, so the secret scalar and
+    /// its nonce never enter this crate.
+    ///
+    /// * `secret_key_handle` - The handle to the secret key associated with the ciphertext
+    /// * `ciphertext` - The main ElGamal ciphertext to be proved
+    /// * `transcript` - The transcript that does the bookkeeping for the Fiat-Shamir heuristic
+    pub fn new_with_secret_key_handle<B: SecretKeyBackend>(
+        secret_key_handle: &SecretKeyHandle<B>,
+        ciphertext: &ElGamalCiphertext,
+        transcript: &mut impl TranscriptProtocol,
+    ) -> Result<Self, SecretKeyBackendError> {
+        Self::hash_context_into_transcript(secret_key_handle.pubkey(), ciphertext, transcript);
+        transcript.zero_ciphertext_proof_domain_separator();
+
+        let P = secret_key_handle.pubkey().get_point();
+        let D = ciphertext.handle.get_point();
+
+        // the backend commits to its nonce on `P` and `D` and masks the secret key
+        let mut Y_P = CompressedRistretto::default();
+        let mut Y_D = CompressedRistretto::default();
+        let z = secret_key_handle
+            .backend()
+            .sigma_response(&[*P, *D], &mut |Y| {
+                Y_P = Y[0].compress();
+                Y_D = Y[1].compress();
+
+                transcript.append_point(b"Y_P", &Y_P);
+                transcript.append_point(b"Y_D", &Y_D);
+
+                transcript.challenge_scalar(b"c")
+            })?;
+
+        transcript.append_scalar(b"z", &z);
+        let _w = transcript.challenge_scalar(b"w");
+
+        Ok(Self { Y_P, Y_D, z })
     }
 
     /// Verifies a zero-ciphertext proof.
@@ -95,7 +152,7 @@ impl ZeroCiphertextProof {
         self,
         elgamal_pubkey: &ElGamalPubkey,
         ciphertext: &ElGamalCiphertext,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) -> Result<(), ZeroCiphertextProofVerificationError> {
         if elgamal_pubkey.get_point().is_identity()
             || ciphertext.commitment.get_point().is_identity()
@@ -119,7 +176,7 @@ impl ZeroCiphertextProof {
         let c = transcript.challenge_scalar(b"c");
 
         transcript.append_scalar(b"z", &self.z);
-        let w = transcript.challenge_scalar(b"w"); // w used for batch verification
+        let w = transcript.batching_weight(b"w"); // w used for batch verification
 
         let w_negated = -&w;
 
@@ -134,7 +191,7 @@ impl ZeroCiphertextProof {
             .ok_or(SigmaProofVerificationError::Deserialization)?;
 
         // check the required algebraic relation
-        let check = RistrettoPoint::multiscalar_mul(
+        let check = vartime_multiscalar_mul(
             vec![
                 &self.z,            // z
                 &(-&c),             // -c
@@ -163,30 +220,22 @@ impl ZeroCiphertextProof {
     fn hash_context_into_transcript(
         pubkey: &ElGamalPubkey,
         ciphertext: &ElGamalCiphertext,
-        transcript: &mut Transcript,
+        transcript: &mut impl TranscriptProtocol,
     ) {
         transcript.append_message(b"pubkey", &pubkey.to_bytes());
         transcript.append_message(b"ciphertext", &ciphertext.to_bytes());
     }
 
-    pub fn to_bytes(&self) -> [u8; ZERO_CIPHERTEXT_PROOF_LEN] {
-        let mut buf = [0_u8; ZERO_CIPHERTEXT_PROOF_LEN];
-        let mut chunks = buf.chunks_mut(UNIT_LEN);
-        chunks.next().unwrap().copy_from_slice(self.Y_P.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.Y_D.as_bytes());
-        chunks.next().unwrap().copy_from_slice(self.z.as_bytes());
-        buf
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        units_to_bytes([self.Y_P.to_bytes(), self.Y_D.to_bytes(), self.z.to_bytes()])
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZeroCiphertextProofVerificationError> {
-        if bytes.len() != ZERO_CIPHERTEXT_PROOF_LEN {
-            return Err(SigmaProofVerificationError::Deserialization.into());
-        }
+        let [Y_P, Y_D, z] = bytes_to_units(bytes)?;
 
-        let mut chunks = bytes.chunks(UNIT_LEN);
-        let Y_P = ristretto_point_from_optional_slice(chunks.next())?;
-        let Y_D = ristretto_point_from_optional_slice(chunks.next())?;
-        let z = canonical_scalar_from_optional_slice(chunks.next())?;
+        let Y_P = CompressedRistretto(Y_P);
+        let Y_D = CompressedRistretto(Y_D);
+        let z = canonical_scalar_from_bytes(z)?;
         Ok(ZeroCiphertextProof { Y_P, Y_D, z })
     }
 }
@@ -197,6 +246,12 @@ impl From<ZeroCiphertextProof> for PodZeroCiphertextProof {
     }
 }
 
+impl From<&ZeroCiphertextProof> for PodZeroCiphertextProof {
+    fn from(decoded_proof: &ZeroCiphertextProof) -> Self {
+        Self(decoded_proof.to_bytes())
+    }
+}
+
 impl TryFrom<PodZeroCiphertextProof> for ZeroCiphertextProof {
     type Error = ZeroCiphertextProofVerificationError;
 
@@ -205,6 +260,14 @@ impl TryFrom<PodZeroCiphertextProof> for ZeroCiphertextProof {
     }
 }
 
+impl TryFrom<&PodZeroCiphertextProof> for ZeroCiphertextProof {
+    type Error = ZeroCiphertextProofVerificationError;
+
+    fn try_from(pod_proof: &PodZeroCiphertextProof) -> Result<Self, Self::Error> {
+        Self::from_bytes(&pod_proof.0)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
@@ -213,6 +276,7 @@ mod test {
             elgamal::ElGamalKeypair,
             pedersen::{PedersenCommitment, PedersenOpening},
         },
+        merlin::Transcript,
         solana_zk_sdk_pod::{
             encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
             sigma_proofs::PodZeroCiphertextProof,
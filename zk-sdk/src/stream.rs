@@ -0,0 +1,207 @@
+//! Streaming serialization of proofs, ciphertexts, and proof data.
+//!
+//! [`StreamSerialize`] writes and reads the same fixed-length byte encodings that the `to_bytes`
+//! and `from_bytes` functions use, directly to and from `std::io` streams. Every encoding has a
+//! fixed length, so values can be written back to back without length prefixes.
+//!
+//! The trait is implemented for every `Pod` type, which covers the pod proofs, the proof data
+//! instruction types, and their contexts. `Pod` values are read as is, in the same way as
+//! `bytemuck::from_bytes`, and are validated when they are converted or verified. The trait is
+//! also implemented for the native ElGamal, grouped ElGamal, Pedersen, and authenticated
+//! encryption types, which are validated as they are read.
+
+use {
+    crate::encryption::{
+        auth_encryption::AeCiphertext,
+        elgamal::{DecryptHandle, ElGamalCiphertext, ElGamalPubkey},
+        grouped_elgamal::GroupedElGamalCiphertext,
+        pedersen::PedersenCommitment,
+    },
+    bytemuck::Pod,
+    std::io::{self, Read, Write},
+};
+
+/// Serialization of fixed-length byte encodings to and from `std::io` streams.
+pub trait StreamSerialize: Sized {
+    /// Writes the byte encoding of the value to `writer`.
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Reads a value from the next bytes of `reader`.
+    ///
+    /// Returns an error of kind `UnexpectedEof` if the stream ends before a complete encoding is
+    /// read and an error of kind `InvalidData` if the bytes are not a valid encoding.
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+impl<T: Pod> StreamSerialize for T {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(bytemuck::bytes_of(self))
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut value = T::zeroed();
+        reader.read_exact(bytemuck::bytes_of_mut(&mut value))?;
+        Ok(value)
+    }
+}
+
+macro_rules! impl_stream_serialize {
+    (TYPE = $type:ident, NAME = $name:expr) => {
+        impl StreamSerialize for $type {
+            fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                writer.write_all(&self.to_bytes())
+            }
+
+            fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+                let mut buf = [0_u8; $type::LEN];
+                reader.read_exact(&mut buf)?;
+                $type::from_bytes(&buf).ok_or_else(|| invalid_data($name))
+            }
+        }
+    };
+}
+
+impl_stream_serialize!(TYPE = ElGamalCiphertext, NAME = "ElGamal ciphertext");
+impl_stream_serialize!(TYPE = DecryptHandle, NAME = "decrypt handle");
+impl_stream_serialize!(TYPE = PedersenCommitment, NAME = "Pedersen commitment");
+impl_stream_serialize!(
+    TYPE = AeCiphertext,
+    NAME = "authenticated encryption ciphertext"
+);
+
+impl StreamSerialize for ElGamalPubkey {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut buf = [0_u8; ElGamalPubkey::LEN];
+        reader.read_exact(&mut buf)?;
+        ElGamalPubkey::try_from(buf.as_slice()).map_err(|_| invalid_data("ElGamal public key"))
+    }
+}
+
+impl<const N: usize> StreamSerialize for GroupedElGamalCiphertext<N> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.commitment.write_to(writer)?;
+        for handle in &self.handles {
+            handle.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        // the components are read one at a time so that no buffer of length `N` is needed
+        let commitment = PedersenCommitment::read_from(reader)?;
+        let mut handles = [DecryptHandle::default(); N];
+        for handle in handles.iter_mut() {
+            *handle = DecryptHandle::read_from(reader)?;
+        }
+        Ok(Self {
+            commitment,
+            handles,
+        })
+    }
+}
+
+fn invalid_data(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid {name} encoding"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            encryption::{auth_encryption::AeKey, elgamal::ElGamalKeypair},
+            zk_elgamal_proof_program::{build_pubkey_validity_proof_data, VerifyZkProof},
+        },
+        solana_zk_elgamal_proof_interface::proof_data::PubkeyValidityProofData,
+        std::io::Cursor,
+    };
+
+    #[test]
+    fn test_stream_proof_data() {
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+
+        let mut stream = vec![];
+        proof_data.write_to(&mut stream).unwrap();
+        proof_data.context.write_to(&mut stream).unwrap();
+        assert_eq!(
+            stream.len(),
+            size_of::<PubkeyValidityProofData>() + size_of_val(&proof_data.context)
+        );
+
+        let mut reader = Cursor::new(stream);
+        let decoded = PubkeyValidityProofData::read_from(&mut reader).unwrap();
+        decoded.verify_proof().unwrap();
+        assert_eq!(decoded.context, proof_data.context);
+        assert_eq!(
+            PubkeyValidityProofData::read_from(&mut reader)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_stream_ciphertexts() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(55_u64);
+        let grouped_ciphertext = crate::encryption::grouped_elgamal::GroupedElGamal::encrypt(
+            [keypair.pubkey(), keypair.pubkey()],
+            77_u64,
+        );
+        let ae_ciphertext = AeKey::new_rand().encrypt(99);
+
+        let mut stream = vec![];
+        keypair.pubkey().write_to(&mut stream).unwrap();
+        ciphertext.write_to(&mut stream).unwrap();
+        grouped_ciphertext.write_to(&mut stream).unwrap();
+        ae_ciphertext.write_to(&mut stream).unwrap();
+
+        let mut reader = Cursor::new(stream);
+        assert_eq!(
+            ElGamalPubkey::read_from(&mut reader).unwrap(),
+            *keypair.pubkey()
+        );
+        assert_eq!(
+            ElGamalCiphertext::read_from(&mut reader).unwrap(),
+            ciphertext
+        );
+        let decoded_grouped_ciphertext =
+            GroupedElGamalCiphertext::<2>::read_from(&mut reader).unwrap();
+        assert_eq!(
+            decoded_grouped_ciphertext.to_bytes(),
+            grouped_ciphertext.to_bytes()
+        );
+        assert_eq!(
+            AeCiphertext::read_from(&mut reader).unwrap().to_bytes(),
+            ae_ciphertext.to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_stream_invalid_data() {
+        // a non-canonical point encoding
+        let stream = [0xFF_u8; ElGamalCiphertext::LEN];
+        assert_eq!(
+            ElGamalCiphertext::read_from(&mut stream.as_slice())
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let stream = [0_u8; ElGamalCiphertext::LEN - 1];
+        assert_eq!(
+            ElGamalCiphertext::read_from(&mut stream.as_slice())
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! An in-memory processor for the ZK ElGamal proof program.
+//!
+//! Programs that verify proofs through the proof program need a validator to run their
+//! integration tests against the program. [`ProofProgramHarness`] processes the instructions of
+//! the proof program in memory instead. It keeps the accounts that the instructions read and
+//! write in a map and returns the same errors as the program, so that tests can exercise the
+//! success and failure paths of proof verification without a validator.
+//!
+//! The harness also creates the expiring and hashed context states that the proof program does
+//! not support yet, by decoding instructions with `ProofInstruction::proof_data_with_expiry` and
+//! `ProofInstruction::proof_data_with_hashed_context`.
+//!
+//! The harness checks the accounts that the proof program checks. It does not model the runtime,
+//! such as rent, compute units, or the ownership rules for account writes.
+
+#[cfg(feature = "unstable")]
+use crate::zk_elgamal_proof_program::verify_proof_batch_instruction_data;
+use {
+    crate::zk_elgamal_proof_program::{
+        stateless::{visit_proof_instruction, ProofDataVisitor},
+        VerifyZkProof,
+    },
+    bytemuck::Pod,
+    solana_address::Address,
+    solana_instruction::{error::InstructionError, Instruction},
+    solana_zk_elgamal_proof_interface::{
+        instruction::ProofInstruction,
+        proof_data::{PodProofType, ProofType, ZkProofData},
+        state::{HashedProofContextState, ProofContextState, ProofContextStateMeta},
+    },
+    std::{collections::HashMap, mem::size_of},
+};
+
+/// The byte length of the data of a proof verification instruction that reads the proof from an
+/// account: the instruction discriminator followed by a `u32` byte offset.
+const INSTRUCTION_DATA_LENGTH_WITH_PROOF_ACCOUNT: usize = 5;
+
+/// An account in the harness.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HarnessAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Address,
+}
+
+/// An in-memory processor for the instructions of the ZK ElGamal proof program.
+#[derive(Clone, Debug, Default)]
+pub struct ProofProgramHarness {
+    accounts: HashMap<Address, HarnessAccount>,
+}
+
+impl ProofProgramHarness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the account at `address`, if it exists.
+    pub fn account(&self, address: &Address) -> Option<&HarnessAccount> {
+        self.accounts.get(address)
+    }
+
+    /// Sets the account at `address`.
+    pub fn set_account(&mut self, address: Address, account: HarnessAccount) {
+        self.accounts.insert(address, account);
+    }
+
+    /// Creates an uninitialized context state account of `len` bytes that is owned by the proof
+    /// program.
+    pub fn create_context_state_account(&mut self, address: Address, len: usize, lamports: u64) {
+        self.set_account(
+            address,
+            HarnessAccount {
+                lamports,
+                data: vec![0; len],
+                owner: solana_zk_elgamal_proof_interface::id(),
+            },
+        );
+    }
+
+    /// Reads the proof context state of type `U` from the account at `address`.
+    pub fn proof_context_state<U: Pod>(
+        &self,
+        address: &Address,
+    ) -> Result<&ProofContextState<U>, InstructionError> {
+        let account = self
+            .accounts
+            .get(address)
+            .ok_or(InstructionError::InvalidAccountData)?;
+        let (state, _) = ProofContextState::<U>::try_from_bytes_with_expiry(&account.data)?;
+        Ok(state)
+    }
+
+    /// Processes an instruction of the proof program.
+    ///
+    /// The accounts of the instruction that are marked as signers are treated as if they signed
+    /// the transaction. If the instruction fails, the accounts are left unchanged.
+    pub fn process_instruction(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(), InstructionError> {
+        if !solana_zk_elgamal_proof_interface::check_id(&instruction.program_id) {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+        let proof_instruction = ProofInstruction::instruction_type(&instruction.data)
+            .ok_or(InstructionError::InvalidInstructionData)?;
+
+        match proof_instruction {
+            ProofInstruction::CloseContextState => self.process_close_context_state(instruction),
+            #[cfg(feature = "unstable")]
+            ProofInstruction::VerifyProofBatch => {
+                verify_proof_batch_instruction_data(&instruction.data)
+                    .map_err(|_| InstructionError::InvalidInstructionData)
+            }
+            _ => visit_proof_instruction(
+                proof_instruction,
+                VerifyProof {
+                    harness: self,
+                    instruction,
+                },
+            )
+            .ok_or(InstructionError::InvalidInstructionData)?,
+        }
+    }
+
+    fn process_verify_proof<T, U>(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(), InstructionError>
+    where
+        T: Pod + ZkProofData<U> + VerifyZkProof,
+        U: Pod,
+    {
+        let data = &instruction.data;
+        let mut accounts = instruction.accounts.iter();
+
+        let (proof_data, expiry_slot, hashed) =
+            if data.len() == INSTRUCTION_DATA_LENGTH_WITH_PROOF_ACCOUNT {
+                let proof_account = accounts.next().ok_or(InstructionError::MissingAccount)?;
+                let proof_account = self
+                    .accounts
+                    .get(&proof_account.pubkey)
+                    .ok_or(InstructionError::InvalidAccountData)?;
+
+                let offset = u32::from_le_bytes(data[1..].try_into().unwrap()) as usize;
+                let proof_data = offset
+                    .checked_add(size_of::<T>())
+                    .and_then(|end| proof_account.data.get(offset..end))
+                    .ok_or(InstructionError::InvalidAccountData)?;
+                let proof_data: &T = bytemuck::try_from_bytes(proof_data)
+                    .map_err(|_| InstructionError::InvalidInstructionData)?;
+                (*proof_data, None, false)
+            } else if let Some(proof_data) =
+                ProofInstruction::proof_data_with_hashed_context::<T, U>(data)
+            {
+                (*proof_data, None, true)
+            } else {
+                let (proof_data, expiry) = ProofInstruction::proof_data_with_expiry::<T, U>(data)
+                    .ok_or(InstructionError::InvalidInstructionData)?;
+                let expiry_slot = expiry.map(|expiry| u64::from(expiry.expiry_slot));
+                (*proof_data, expiry_slot, false)
+            };
+
+        proof_data
+            .verify_proof()
+            .map_err(|_| InstructionError::InvalidInstructionData)?;
+
+        let Some(context_state_account) = accounts.next() else {
+            return Ok(());
+        };
+        let context_state_authority = accounts.next().ok_or(InstructionError::MissingAccount)?;
+
+        let account = self
+            .accounts
+            .get_mut(&context_state_account.pubkey)
+            .ok_or(InstructionError::InvalidAccountData)?;
+        if !solana_zk_elgamal_proof_interface::check_id(&account.owner) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let meta = ProofContextStateMeta::try_from_bytes(&account.data)?;
+        if meta.proof_type != PodProofType::from(ProofType::Uninitialized) {
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+
+        let state = if hashed {
+            HashedProofContextState::encode(
+                &context_state_authority.pubkey,
+                T::PROOF_TYPE,
+                proof_data.context_data(),
+            )
+        } else {
+            ProofContextState::<U>::encode_with_expiry(
+                &context_state_authority.pubkey,
+                T::PROOF_TYPE,
+                proof_data.context_data(),
+                expiry_slot,
+            )
+        };
+        if account.data.len() != state.len() {
+            return Err(InstructionError::InvalidAccountData);
+        }
+        account.data.copy_from_slice(&state);
+        Ok(())
+    }
+
+    fn process_close_context_state(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(), InstructionError> {
+        let [context_state_account, destination_account, context_state_authority, ..] =
+            instruction.accounts.as_slice()
+        else {
+            return Err(InstructionError::MissingAccount);
+        };
+
+        if !context_state_authority.is_signer {
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        if context_state_account.pubkey == destination_account.pubkey {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+
+        let account = self
+            .accounts
+            .get(&context_state_account.pubkey)
+            .ok_or(InstructionError::InvalidAccountData)?;
+        if !solana_zk_elgamal_proof_interface::check_id(&account.owner) {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+        let meta = ProofContextStateMeta::try_from_bytes(&account.data)?;
+        if meta.context_state_authority != context_state_authority.pubkey {
+            return Err(InstructionError::InvalidAccountOwner);
+        }
+
+        let lamports = account.lamports;
+        let destination = self.accounts.entry(destination_account.pubkey).or_default();
+        destination.lamports = destination
+            .lamports
+            .checked_add(lamports)
+            .ok_or(InstructionError::ArithmeticOverflow)?;
+        self.accounts.remove(&context_state_account.pubkey);
+        Ok(())
+    }
+}
+
+struct VerifyProof<'a> {
+    harness: &'a mut ProofProgramHarness,
+    instruction: &'a Instruction,
+}
+
+impl ProofDataVisitor for VerifyProof<'_> {
+    type Output = Result<(), InstructionError>;
+
+    fn visit<T, U>(self) -> Self::Output
+    where
+        T: Pod + ZkProofData<U> + VerifyZkProof,
+        U: Pod,
+    {
+        self.harness.process_verify_proof::<T, U>(self.instruction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair,
+            zk_elgamal_proof_program::{
+                build_pubkey_validity_proof_data, build_zero_ciphertext_proof_data,
+            },
+        },
+        solana_zk_elgamal_proof_interface::{
+            instruction::{close_context_state, ContextStateInfo},
+            proof_data::{PubkeyValidityProofContext, ZeroCiphertextProofContext},
+        },
+    };
+
+    #[test]
+    fn test_verify_proof_into_context_state() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+
+        let mut harness = ProofProgramHarness::new();
+        let context_state_account = Address::new_unique();
+        let context_state_authority = Address::new_unique();
+        let context_state_info = ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        };
+        harness.create_context_state_account(
+            context_state_account,
+            size_of::<ProofContextState<ZeroCiphertextProofContext>>(),
+            1_000,
+        );
+
+        // proof without a context state
+        let instruction =
+            ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, &proof_data);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+
+        let instruction = ProofInstruction::VerifyZeroCiphertext
+            .encode_verify_proof(Some(context_state_info), &proof_data);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+        let state = harness
+            .proof_context_state::<ZeroCiphertextProofContext>(&context_state_account)
+            .unwrap();
+        assert_eq!(state.context_state_authority, context_state_authority);
+        assert_eq!(state.proof_context, proof_data.context);
+
+        // a context state cannot be overwritten
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::AccountAlreadyInitialized)
+        );
+
+        // an invalid proof is rejected
+        let mut invalid_data = proof_data;
+        invalid_data.context.ciphertext = keypair.pubkey().encrypt(1_u64).into();
+        let instruction =
+            ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, &invalid_data);
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::InvalidInstructionData)
+        );
+
+        // the context state account is closed by its authority
+        let destination = Address::new_unique();
+        let mut instruction = close_context_state(context_state_info, &destination);
+        instruction.accounts[2].is_signer = false;
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::MissingRequiredSignature)
+        );
+        let instruction = close_context_state(context_state_info, &destination);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+        assert!(harness.account(&context_state_account).is_none());
+        assert_eq!(harness.account(&destination).unwrap().lamports, 1_000);
+    }
+
+    #[test]
+    fn test_verify_proof_from_account() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+
+        let mut harness = ProofProgramHarness::new();
+        let proof_account = Address::new_unique();
+        let mut data = vec![0; 8];
+        data.extend_from_slice(bytemuck::bytes_of(&proof_data));
+        harness.set_account(
+            proof_account,
+            HarnessAccount {
+                lamports: 1_000,
+                data,
+                owner: Address::new_unique(),
+            },
+        );
+
+        let instruction = ProofInstruction::VerifyZeroCiphertext.encode_verify_proof_from_account(
+            None,
+            &proof_account,
+            8,
+        );
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+
+        let instruction = ProofInstruction::VerifyZeroCiphertext.encode_verify_proof_from_account(
+            None,
+            &proof_account,
+            9,
+        );
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_context_state_account_checks() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+
+        let mut harness = ProofProgramHarness::new();
+        let context_state_account = Address::new_unique();
+        let context_state_authority = Address::new_unique();
+        let context_state_info = ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        };
+        let instruction = ProofInstruction::VerifyZeroCiphertext
+            .encode_verify_proof(Some(context_state_info), &proof_data);
+
+        // the context state account must be allocated to the size of the context state
+        harness.create_context_state_account(context_state_account, 10, 1_000);
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::InvalidAccountData)
+        );
+
+        // the context state account must be owned by the proof program
+        harness.set_account(
+            context_state_account,
+            HarnessAccount {
+                lamports: 1_000,
+                data: vec![0; size_of::<ProofContextState<ZeroCiphertextProofContext>>()],
+                owner: Address::new_unique(),
+            },
+        );
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::InvalidAccountOwner)
+        );
+
+        // a hashed context state is not written by an instruction without the hashed flag
+        harness.create_context_state_account(
+            context_state_account,
+            size_of::<HashedProofContextState>(),
+            1_000,
+        );
+        assert_eq!(
+            harness.process_instruction(&instruction),
+            Err(InstructionError::InvalidAccountData)
+        );
+
+        let instruction = ProofInstruction::VerifyZeroCiphertext
+            .encode_verify_proof_hashed(context_state_info, &proof_data);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+        let account = harness.account(&context_state_account).unwrap();
+        let state = HashedProofContextState::try_from_bytes(&account.data).unwrap();
+        assert!(state.verify_proof_context(&proof_data.context).is_ok());
+        assert!(ProofContextStateMeta::try_from_bytes(&account.data)
+            .unwrap()
+            .is_hashed());
+    }
+
+    #[test]
+    fn test_hashed_context_state_of_same_size() {
+        // a hashed context state has the same size as the context state of a pubkey validity
+        // proof, so the two states are told apart by the proof type
+        assert_eq!(
+            size_of::<HashedProofContextState>(),
+            size_of::<ProofContextState<PubkeyValidityProofContext>>()
+        );
+
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+
+        let mut harness = ProofProgramHarness::new();
+        let context_state_account = Address::new_unique();
+        let context_state_authority = Address::new_unique();
+        let context_state_info = ContextStateInfo {
+            context_state_account: &context_state_account,
+            context_state_authority: &context_state_authority,
+        };
+
+        harness.create_context_state_account(
+            context_state_account,
+            size_of::<HashedProofContextState>(),
+            1_000,
+        );
+        let instruction = ProofInstruction::VerifyPubkeyValidity
+            .encode_verify_proof_hashed(context_state_info, &proof_data);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+        let account = harness.account(&context_state_account).unwrap();
+        assert!(ProofContextStateMeta::try_from_bytes(&account.data)
+            .unwrap()
+            .is_hashed());
+        let state = HashedProofContextState::try_from_bytes(&account.data).unwrap();
+        assert_eq!(state.proof_type(), Ok(ProofType::PubkeyValidity));
+        assert!(state.verify_proof_context(&proof_data.context).is_ok());
+        assert_eq!(
+            ProofContextState::<PubkeyValidityProofContext>::try_from_bytes(&account.data)
+                .unwrap_err(),
+            InstructionError::InvalidAccountData
+        );
+
+        harness.create_context_state_account(
+            context_state_account,
+            size_of::<ProofContextState<PubkeyValidityProofContext>>(),
+            1_000,
+        );
+        let instruction = ProofInstruction::VerifyPubkeyValidity
+            .encode_verify_proof(Some(context_state_info), &proof_data);
+        assert_eq!(harness.process_instruction(&instruction), Ok(()));
+        let account = harness.account(&context_state_account).unwrap();
+        assert!(!ProofContextStateMeta::try_from_bytes(&account.data)
+            .unwrap()
+            .is_hashed());
+        assert!(HashedProofContextState::try_from_bytes(&account.data).is_err());
+        assert!(
+            ProofContextState::<PubkeyValidityProofContext>::try_from_bytes(&account.data).is_ok()
+        );
+    }
+}
@@ -1,73 +1,363 @@
 use {
-    crate::{errors::TranscriptError, TRANSCRIPT_DOMAIN},
+    crate::{
+        encryption::{elgamal::ElGamalPubkey, pedersen::PedersenGenerators},
+        errors::TranscriptError,
+        rng::verifier_weight,
+        TRANSCRIPT_DOMAIN,
+    },
     curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar, traits::IsIdentity},
     merlin::Transcript,
+    solana_address::Address,
 };
 
-pub trait TranscriptProtocol {
-    /// Create a new transcript with the global domain separator and a specific label.
-    fn new_zk_elgamal_transcript(label: &'static [u8]) -> Transcript;
+/// External freshness data that is bound into proof transcripts.
+///
+/// A proof for a statement that is generated under one context verifies only under the same
+/// context, so proofs of identical statements that are generated at different slots, epochs, or
+/// with different caller nonces are not interchangeable. The context must be supplied in the same
+/// way to both the prover and the verifier: proofs that take an explicit transcript bind it with
+/// [`TranscriptProtocol::append_transcript_context`], and proof data, which creates its own
+/// transcripts, binds it as the statement context of [`TranscriptContext::to_statement_context`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TranscriptContext<'a> {
+    /// The slot at which the proof is generated
+    pub slot: Option<u64>,
+    /// The epoch at which the proof is generated
+    pub epoch: Option<u64>,
+    /// Caller-chosen nonce bytes
+    pub nonce: Option<&'a [u8]>,
+}
 
-    /// Append a `scalar` with the given `label`.
-    fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar);
+impl<'a> TranscriptContext<'a> {
+    const SLOT_FLAG: u8 = 1;
+    const EPOCH_FLAG: u8 = 1 << 1;
+    const NONCE_FLAG: u8 = 1 << 2;
 
-    /// Append a `point` with the given `label`.
-    fn append_point(&mut self, label: &'static [u8], point: &CompressedRistretto);
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    /// Check that a point is not the identity, then append it to the
-    /// transcript.  Otherwise, return an error.
-    fn validate_and_append_point(
-        &mut self,
-        label: &'static [u8],
-        point: &CompressedRistretto,
-    ) -> Result<(), TranscriptError>;
+    /// Sets the slot of the context.
+    pub fn with_slot(self, slot: u64) -> Self {
+        Self {
+            slot: Some(slot),
+            ..self
+        }
+    }
 
-    /// Append a domain separator for an `n`-bit range proof
-    fn range_proof_domain_separator(&mut self, n: u64);
+    /// Sets the epoch of the context.
+    pub fn with_epoch(self, epoch: u64) -> Self {
+        Self {
+            epoch: Some(epoch),
+            ..self
+        }
+    }
 
-    /// Append a domain separator for a length-`n` inner product proof.
-    fn inner_product_proof_domain_separator(&mut self, n: u64);
+    /// Sets the nonce of the context.
+    pub fn with_nonce(self, nonce: &'a [u8]) -> Self {
+        Self {
+            nonce: Some(nonce),
+            ..self
+        }
+    }
 
-    /// Append a domain separator for ciphertext-ciphertext equality proof.
-    fn ciphertext_ciphertext_equality_proof_domain_separator(&mut self);
+    /// The canonical encoding of the context.
+    ///
+    /// The encoding starts with a byte that flags the fields that are set, followed by the slot
+    /// and the epoch as little-endian `u64`s and the length-prefixed nonce, each only if set.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut flags = 0;
+        let mut fields = Vec::new();
+        if let Some(slot) = self.slot {
+            flags |= Self::SLOT_FLAG;
+            fields.extend_from_slice(&slot.to_le_bytes());
+        }
+        if let Some(epoch) = self.epoch {
+            flags |= Self::EPOCH_FLAG;
+            fields.extend_from_slice(&epoch.to_le_bytes());
+        }
+        if let Some(nonce) = self.nonce {
+            flags |= Self::NONCE_FLAG;
+            fields.extend_from_slice(&(nonce.len() as u64).to_le_bytes());
+            fields.extend_from_slice(nonce);
+        }
 
-    /// Append a domain separator for ciphertext-commitment equality proof.
-    fn ciphertext_commitment_equality_proof_domain_separator(&mut self);
+        let mut bytes = Vec::with_capacity(1 + fields.len());
+        bytes.push(flags);
+        bytes.extend_from_slice(&fields);
+        bytes
+    }
 
-    /// Append a domain separator for zero-ciphertext proof.
-    fn zero_ciphertext_proof_domain_separator(&mut self);
+    /// The statement context that binds the context into the transcripts of proof data.
+    ///
+    /// Proof data that is generated with this statement context verifies only with the same
+    /// statement context.
+    pub fn to_statement_context(&self) -> Vec<u8> {
+        let mut statement_context = b"transcript-context".to_vec();
+        statement_context.extend_from_slice(&self.to_bytes());
+        statement_context
+    }
+}
 
-    /// Append a domain separator for grouped ciphertext validity proof.
-    fn grouped_ciphertext_validity_proof_domain_separator(&mut self, handles: u64);
+/// Associated data that is bound to a ciphertext through the transcripts of the proofs about it.
+///
+/// A proof about a ciphertext that is generated under one context verifies only under the same
+/// context, so a proof cannot be detached from the memo or the destination that it was generated
+/// for and replayed with another. The context is supplied in the same way as a
+/// [`TranscriptContext`]: proofs that take an explicit transcript bind it with
+/// [`TranscriptProtocol::append_ciphertext_context`], and proof data binds it as the statement
+/// context of [`CiphertextContext::to_statement_context`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CiphertextContext<'a> {
+    /// The hash of the memo that accompanies the ciphertext
+    pub memo_hash: Option<[u8; 32]>,
+    /// The address of the destination of the ciphertext
+    pub destination: Option<[u8; 32]>,
+    /// Caller-defined associated data
+    pub associated_data: Option<&'a [u8]>,
+}
 
-    /// Append a domain separator for batched grouped ciphertext validity proof.
-    fn batched_grouped_ciphertext_validity_proof_domain_separator(&mut self, handles: u64);
+impl<'a> CiphertextContext<'a> {
+    const MEMO_HASH_FLAG: u8 = 1;
+    const DESTINATION_FLAG: u8 = 1 << 1;
+    const ASSOCIATED_DATA_FLAG: u8 = 1 << 2;
 
-    /// Append a domain separator for percentage with cap proof.
-    fn percentage_with_cap_proof_domain_separator(&mut self);
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-    /// Append a domain separator for public-key proof.
-    fn pubkey_proof_domain_separator(&mut self);
+    /// Sets the memo hash of the context.
+    pub fn with_memo_hash(self, memo_hash: [u8; 32]) -> Self {
+        Self {
+            memo_hash: Some(memo_hash),
+            ..self
+        }
+    }
 
-    /// Compute a `label`ed challenge variable.
-    fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar;
+    /// Sets the memo hash of the context to the SHA3-256 hash of `memo`.
+    pub fn with_memo(self, memo: &[u8]) -> Self {
+        use sha3::Digest;
+
+        self.with_memo_hash(sha3::Sha3_256::digest(memo).into())
+    }
+
+    /// Sets the destination address of the context.
+    pub fn with_destination(self, destination: [u8; 32]) -> Self {
+        Self {
+            destination: Some(destination),
+            ..self
+        }
+    }
+
+    /// Sets the caller-defined associated data of the context.
+    pub fn with_associated_data(self, associated_data: &'a [u8]) -> Self {
+        Self {
+            associated_data: Some(associated_data),
+            ..self
+        }
+    }
+
+    /// The canonical encoding of the context.
+    ///
+    /// The encoding starts with a byte that flags the fields that are set, followed by the memo
+    /// hash, the destination address, and the length-prefixed associated data, each only if set.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut flags = 0;
+        let mut fields = Vec::new();
+        if let Some(memo_hash) = self.memo_hash {
+            flags |= Self::MEMO_HASH_FLAG;
+            fields.extend_from_slice(&memo_hash);
+        }
+        if let Some(destination) = self.destination {
+            flags |= Self::DESTINATION_FLAG;
+            fields.extend_from_slice(&destination);
+        }
+        if let Some(associated_data) = self.associated_data {
+            flags |= Self::ASSOCIATED_DATA_FLAG;
+            fields.extend_from_slice(&(associated_data.len() as u64).to_le_bytes());
+            fields.extend_from_slice(associated_data);
+        }
+
+        let mut bytes = Vec::with_capacity(1 + fields.len());
+        bytes.push(flags);
+        bytes.extend_from_slice(&fields);
+        bytes
+    }
+
+    /// The statement context that binds the context into the transcripts of proof data.
+    ///
+    /// See [`TranscriptContext::to_statement_context`] for details. The two contexts are encoded
+    /// with distinct prefixes, so a ciphertext context never collides with a transcript context.
+    pub fn to_statement_context(&self) -> Vec<u8> {
+        let mut statement_context = b"ciphertext-context".to_vec();
+        statement_context.extend_from_slice(&self.to_bytes());
+        statement_context
+    }
 }
 
-impl TranscriptProtocol for Transcript {
-    fn new_zk_elgamal_transcript(label: &'static [u8]) -> Transcript {
-        let mut transcript = Transcript::new(TRANSCRIPT_DOMAIN);
+/// The token accounts of an operation that are bound into proof transcripts.
+///
+/// A proof that is generated under the statement context of one operation verifies only under the
+/// same context, so a proof that is generated for one token account never verifies for another,
+/// even if the encrypted balances of the two accounts coincide. The context is supplied in the
+/// same way as a [`TranscriptContext`]: proofs that take an explicit transcript bind it with
+/// [`TranscriptProtocol::append_statement_context`], and proof data and the proof bundles of
+/// [`client`](crate::client) bind it as the statement context of
+/// [`StatementContext::to_statement_context`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatementContext {
+    /// The mint of the token accounts
+    pub mint: Address,
+    /// The token account that is debited
+    pub source: Address,
+    /// The token account that is credited
+    pub destination: Address,
+    /// The ElGamal public key of the auditor of the mint, if any
+    pub auditor: Option<ElGamalPubkey>,
+    /// Caller-chosen nonce that distinguishes operations between the same accounts
+    pub nonce: u64,
+}
+
+impl StatementContext {
+    /// Creates a context for an operation between two token accounts of a mint.
+    pub fn new(mint: Address, source: Address, destination: Address) -> Self {
+        Self {
+            mint,
+            source,
+            destination,
+            auditor: None,
+            nonce: 0,
+        }
+    }
+
+    /// Creates a context for an operation on a single token account of `owner`, such as the
+    /// registration of the encryption key of the account.
+    ///
+    /// The token account is encoded as the source and the owner as the destination of the
+    /// context. The mint is left as the default address.
+    pub fn for_account(owner: Address, token_account: Address) -> Self {
+        Self::new(Address::default(), token_account, owner)
+    }
+
+    /// Sets the auditor public key of the context.
+    pub fn with_auditor(self, auditor: ElGamalPubkey) -> Self {
+        Self {
+            auditor: Some(auditor),
+            ..self
+        }
+    }
+
+    /// Sets the nonce of the context.
+    pub fn with_nonce(self, nonce: u64) -> Self {
+        Self { nonce, ..self }
+    }
+
+    /// The canonical encoding of the context.
+    ///
+    /// The encoding is the mint, source, and destination addresses, followed by a byte that flags
+    /// whether an auditor is set, the auditor public key if set, and the nonce as a little-endian
+    /// `u64`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(3 * 32 + 1 + 32 + 8);
+        bytes.extend_from_slice(self.mint.as_ref());
+        bytes.extend_from_slice(self.source.as_ref());
+        bytes.extend_from_slice(self.destination.as_ref());
+        match self.auditor {
+            Some(auditor) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&auditor.to_bytes());
+            }
+            None => bytes.push(0),
+        }
+        bytes.extend_from_slice(&self.nonce.to_le_bytes());
+        bytes
+    }
+
+    /// The statement context that binds the context into the transcripts of proof data.
+    ///
+    /// See [`TranscriptContext::to_statement_context`] for details.
+    pub fn to_statement_context(&self) -> Vec<u8> {
+        let mut statement_context = b"statement-context".to_vec();
+        statement_context.extend_from_slice(&self.to_bytes());
+        statement_context
+    }
+}
+
+/// The Fiat-Shamir transcript interface used by the sigma and range proofs.
+///
+/// Implementations provide the primitive operations `new_with_domain`, `append_message`,
+/// `append_u64`, and `challenge_bytes`. All labeling and domain separation is defined by the
+/// provided methods in terms of these primitives, so every transcript implementation follows the
+/// same labeling semantics.
+pub trait TranscriptProtocol: Clone {
+    /// Create a new transcript with the given domain separator.
+    fn new_with_domain(domain: &'static [u8]) -> Self;
+
+    /// Append a `message` with the given `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Append a `u64` with the given `label`.
+    fn append_u64(&mut self, label: &'static [u8], x: u64);
+
+    /// Fill `dest` with challenge bytes for the given `label`.
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+
+    /// Create a new transcript with the global domain separator and a specific label.
+    fn new_zk_elgamal_transcript(label: &'static [u8]) -> Self {
+        Self::new_zk_elgamal_transcript_with_statement_context(label, None)
+    }
+
+    /// Create a new transcript with the global domain separator, a specific label, and an
+    /// optional statement context.
+    ///
+    /// A proof that is generated with a statement context verifies only with the same statement
+    /// context. Callers use this to bind application data, such as a request identifier, to a
+    /// proof without changing the proof format. Without a statement context, the transcript is
+    /// the one of [`TranscriptProtocol::new_zk_elgamal_transcript`].
+    fn new_zk_elgamal_transcript_with_statement_context(
+        label: &'static [u8],
+        statement_context: Option<&[u8]>,
+    ) -> Self {
+        let mut transcript = Self::new_with_domain(TRANSCRIPT_DOMAIN);
         transcript.append_message(b"dom-sep", label);
+        if let Some(statement_context) = statement_context {
+            transcript.append_message(b"statement-context", statement_context);
+        }
         transcript
     }
 
+    /// Append external freshness data to the transcript.
+    fn append_transcript_context(&mut self, context: &TranscriptContext) {
+        self.append_message(b"dom-sep", b"transcript-context");
+        self.append_message(b"transcript-context", &context.to_bytes());
+    }
+
+    /// Append the associated data of a ciphertext to the transcript.
+    fn append_ciphertext_context(&mut self, context: &CiphertextContext) {
+        self.append_message(b"dom-sep", b"ciphertext-context");
+        self.append_message(b"ciphertext-context", &context.to_bytes());
+    }
+
+    /// Append the token accounts of an operation to the transcript.
+    fn append_statement_context(&mut self, context: &StatementContext) {
+        self.append_message(b"dom-sep", b"statement-context");
+        self.append_message(b"statement-context", &context.to_bytes());
+    }
+
+    /// Append a `scalar` with the given `label`.
     fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar) {
         self.append_message(label, scalar.as_bytes());
     }
 
+    /// Append a `point` with the given `label`.
     fn append_point(&mut self, label: &'static [u8], point: &CompressedRistretto) {
         self.append_message(label, point.as_bytes());
     }
 
+    /// Check that a point is not the identity, then append it to the
+    /// transcript.  Otherwise, return an error.
     fn validate_and_append_point(
         &mut self,
         label: &'static [u8],
@@ -81,50 +371,690 @@ impl TranscriptProtocol for Transcript {
         }
     }
 
+    /// Append a domain separator for an `n`-bit range proof
     fn range_proof_domain_separator(&mut self, n: u64) {
         self.append_message(b"dom-sep", b"range-proof");
         self.append_u64(b"n", n);
     }
 
+    /// Append a domain separator for the statement of a range proof with `m` commitments.
+    fn range_proof_statement_domain_separator(&mut self, m: u64) {
+        self.append_message(b"dom-sep", b"range-proof-statement");
+        self.append_u64(b"m", m);
+    }
+
+    /// Append a domain separator for a length-`n` inner product proof.
     fn inner_product_proof_domain_separator(&mut self, n: u64) {
         self.append_message(b"dom-sep", b"inner-product");
         self.append_u64(b"n", n);
     }
 
+    /// Append a domain separator for an aggregated range proof of `m` values of `n` bits in the
+    /// format of the dalek-cryptography `bulletproofs` crate.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    fn dalek_range_proof_domain_separator(&mut self, n: u64, m: u64) {
+        self.append_message(b"dom-sep", b"rangeproof v1");
+        self.append_u64(b"n", n);
+        self.append_u64(b"m", m);
+    }
+
+    /// Append a domain separator for a length-`n` inner product proof in the format of the
+    /// dalek-cryptography `bulletproofs` crate.
+    #[cfg(feature = "dalek-bulletproofs-compat")]
+    fn dalek_inner_product_proof_domain_separator(&mut self, n: u64) {
+        self.append_message(b"dom-sep", b"ipp v1");
+        self.append_u64(b"n", n);
+    }
+
+    /// Append a domain separator for custom Pedersen generators along with the generators.
+    fn pedersen_generators_domain_separator(&mut self, generators: &PedersenGenerators) {
+        self.append_message(b"dom-sep", b"pedersen-generators");
+        self.append_point(b"G", &generators.G().compress());
+        self.append_point(b"H", &generators.H().compress());
+    }
+
+    /// Append a domain separator for ciphertext-ciphertext equality proof.
     fn ciphertext_ciphertext_equality_proof_domain_separator(&mut self) {
         self.append_message(b"dom-sep", b"ciphertext-ciphertext-equality-proof")
     }
 
+    /// Append a domain separator for ciphertext-commitment equality proof.
     fn ciphertext_commitment_equality_proof_domain_separator(&mut self) {
         self.append_message(b"dom-sep", b"ciphertext-commitment-equality-proof")
     }
 
+    /// Append a domain separator for a compressed equality proof of `statements` statements.
+    fn compressed_equality_proof_domain_separator(&mut self, statements: u64) {
+        self.append_message(b"dom-sep", b"compressed-equality-proof");
+        self.append_u64(b"statements", statements);
+    }
+
+    /// Append a domain separator for opening-based ciphertext-commitment equality proof.
+    fn ciphertext_commitment_opening_equality_proof_domain_separator(&mut self) {
+        self.append_message(b"dom-sep", b"ciphertext-commitment-opening-equality-proof")
+    }
+
+    /// Append a domain separator for asset equality proof.
+    fn asset_equality_proof_domain_separator(&mut self) {
+        self.append_message(b"dom-sep", b"asset-equality-proof")
+    }
+
+    /// Append a domain separator for key-private validity proof over `pubkeys` public keys.
+    fn key_private_validity_proof_domain_separator(&mut self, pubkeys: u64) {
+        self.append_message(b"dom-sep", b"key-private-validity-proof");
+        self.append_u64(b"pubkeys", pubkeys);
+    }
+
+    /// Append a domain separator for partial decryption proof.
+    fn partial_decryption_proof_domain_separator(&mut self) {
+        self.append_message(b"dom-sep", b"partial-decryption-proof")
+    }
+
+    /// Append a domain separator for zero-ciphertext proof.
     fn zero_ciphertext_proof_domain_separator(&mut self) {
         self.append_message(b"dom-sep", b"zero-ciphertext-proof")
     }
 
+    /// Append a domain separator for grouped ciphertext validity proof.
     fn grouped_ciphertext_validity_proof_domain_separator(&mut self, handles: u64) {
         self.append_message(b"dom-sep", b"validity-proof");
         self.append_u64(b"handles", handles);
     }
 
+    /// Append a domain separator for batched grouped ciphertext validity proof.
     fn batched_grouped_ciphertext_validity_proof_domain_separator(&mut self, handles: u64) {
         self.append_message(b"dom-sep", b"batched-validity-proof");
         self.append_u64(b"handles", handles);
     }
 
+    /// Append a domain separator for grouped zero-ciphertext proof.
+    fn grouped_zero_ciphertext_proof_domain_separator(&mut self, handles: u64) {
+        self.append_message(b"dom-sep", b"grouped-zero-ciphertext-proof");
+        self.append_u64(b"handles", handles);
+    }
+
+    /// Append a domain separator for split consistency proof.
+    fn split_consistency_proof_domain_separator(&mut self) {
+        self.append_message(b"dom-sep", b"split-consistency-proof")
+    }
+
+    /// Append a domain separator for percentage with cap proof.
     fn percentage_with_cap_proof_domain_separator(&mut self) {
         self.append_message(b"dom-sep", b"percentage-with-cap-proof")
     }
 
+    /// Append a domain separator for percentage with cap fee-split proof.
+    fn percentage_with_cap_split_proof_domain_separator(&mut self, shares: u64) {
+        self.append_message(b"dom-sep", b"percentage-with-cap-split-proof");
+        self.append_u64(b"shares", shares);
+    }
+
+    /// Append a domain separator for public-key proof.
     fn pubkey_proof_domain_separator(&mut self) {
         self.append_message(b"dom-sep", b"pubkey-proof")
     }
 
+    /// Append a domain separator for a batch of `proofs` public-key proofs.
+    fn batched_pubkey_proof_domain_separator(&mut self, proofs: u64) {
+        self.append_message(b"dom-sep", b"batched-pubkey-proof");
+        self.append_u64(b"proofs", proofs);
+    }
+
+    /// Compute a `label`ed challenge variable.
     fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
         let mut buf = [0u8; 64];
         self.challenge_bytes(label, &mut buf);
 
         Scalar::from_bytes_mod_order_wide(&buf)
     }
+
+    /// Compute a `label`ed batching weight for the relations of a verification equation.
+    ///
+    /// The weight is the `label`ed challenge. [`VerifierSampledWeights`] overrides this to sample
+    /// the weight freshly.
+    fn batching_weight(&mut self, label: &'static [u8]) -> Scalar {
+        self.challenge_scalar(label)
+    }
+}
+
+impl TranscriptProtocol for Transcript {
+    fn new_with_domain(domain: &'static [u8]) -> Self {
+        Transcript::new(domain)
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        Transcript::append_message(self, label, message);
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        Transcript::append_u64(self, label, x);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        Transcript::challenge_bytes(self, label, dest);
+    }
+}
+
+/// A transcript based on SHA3-256 that follows the same labeling semantics as the Merlin
+/// transcript.
+///
+/// This transcript does not produce the same challenges as a Merlin transcript, so a proof must
+/// be generated and verified with the same transcript type. It is intended for interoperability
+/// with implementations that cannot embed Merlin or STROBE.
+///
+/// Every operation absorbs a tag, the label, and the message into a running SHA3-256 state with
+/// explicit length prefixes. A challenge finalizes the state into a seed, expands the seed into
+/// the requested number of bytes in counter mode, and ratchets the seed back into the state.
+#[cfg(feature = "sha3-transcript")]
+#[derive(Clone)]
+pub struct Sha3Transcript {
+    state: sha3::Sha3_256,
+}
+
+#[cfg(feature = "sha3-transcript")]
+impl Sha3Transcript {
+    const PROTOCOL_LABEL: &'static [u8] = b"solana-zk-sha3-transcript-v1";
+
+    const DOMAIN_TAG: u8 = 0;
+    const MESSAGE_TAG: u8 = 1;
+    const CHALLENGE_TAG: u8 = 2;
+    const RATCHET_TAG: u8 = 3;
+
+    fn absorb(&mut self, tag: u8, label: &[u8], message: &[u8]) {
+        use sha3::Digest;
+
+        self.state.update([tag]);
+        self.state.update((label.len() as u64).to_le_bytes());
+        self.state.update(label);
+        self.state.update((message.len() as u64).to_le_bytes());
+        self.state.update(message);
+    }
+}
+
+#[cfg(feature = "sha3-transcript")]
+impl TranscriptProtocol for Sha3Transcript {
+    fn new_with_domain(domain: &'static [u8]) -> Self {
+        use sha3::Digest;
+
+        let mut transcript = Self {
+            state: sha3::Sha3_256::new(),
+        };
+        transcript.absorb(Self::DOMAIN_TAG, Self::PROTOCOL_LABEL, domain);
+        transcript
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.absorb(Self::MESSAGE_TAG, label, message);
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.append_message(label, &x.to_le_bytes());
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        use sha3::Digest;
+
+        self.absorb(
+            Self::CHALLENGE_TAG,
+            label,
+            &(dest.len() as u64).to_le_bytes(),
+        );
+        let seed = self.state.finalize_reset();
+
+        for (counter, chunk) in dest.chunks_mut(32).enumerate() {
+            let block = sha3::Sha3_256::new()
+                .chain_update(seed)
+                .chain_update((counter as u64).to_le_bytes())
+                .finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        self.absorb(Self::RATCHET_TAG, Self::PROTOCOL_LABEL, &seed);
+    }
+}
+
+/// A verifier transcript whose batching weights are sampled by the verifier.
+///
+/// A verifier that checks several algebraic relations of a proof with a single multiscalar
+/// multiplication combines them with a batching weight. By default, the weight is a challenge
+/// that is squeezed from the transcript after all prover messages. Verifying with this transcript
+/// instead samples the weights from `OsRng`, so the prover has no influence over them. The
+/// challenges are still squeezed and discarded, so the wrapped transcript evolves in the same way
+/// in both modes.
+///
+/// This mode is meant for off-chain verification. The proof program derives all weights from the
+/// transcript, so that verification is deterministic.
+#[derive(Clone)]
+pub struct VerifierSampledWeights<T = Transcript>(T);
+
+impl<T: TranscriptProtocol> VerifierSampledWeights<T> {
+    /// Wraps `transcript` so that the batching weights of a verification are sampled freshly.
+    pub fn new(transcript: T) -> Self {
+        Self(transcript)
+    }
+
+    /// Returns the wrapped transcript.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: TranscriptProtocol> TranscriptProtocol for VerifierSampledWeights<T> {
+    fn new_with_domain(domain: &'static [u8]) -> Self {
+        Self(T::new_with_domain(domain))
+    }
+
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.0.append_message(label, message);
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.0.append_u64(label, x);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.0.challenge_bytes(label, dest);
+    }
+
+    fn batching_weight(&mut self, label: &'static [u8]) -> Scalar {
+        self.0.challenge_scalar(label);
+        verifier_weight()
+    }
+}
+
+/// Derives the transcripts of the individual proofs in a protocol that consists of several
+/// proofs.
+///
+/// The protocol label and the ordered list of statements are committed to up front. Each proof
+/// transcript is then derived from this commitment together with the index of its statement and
+/// the label of the proof. Consequently, a proof generated for one statement of a protocol does
+/// not verify for any other statement, any other position, or any other protocol, and proofs
+/// never share a transcript.
+pub struct ProtocolTranscript {
+    transcript: Transcript,
+    statement_count: usize,
+    next_index: usize,
+}
+
+impl ProtocolTranscript {
+    /// Create a protocol transcript for the ordered list of `statements`.
+    ///
+    /// Each statement is the canonical byte encoding of the public data that its proof certifies.
+    pub fn new(label: &'static [u8], statements: &[&[u8]]) -> Self {
+        let mut transcript = Transcript::new_zk_elgamal_transcript(b"protocol-transcript");
+        transcript.append_message(b"protocol", label);
+        transcript.append_u64(b"statement-count", statements.len() as u64);
+        for (index, statement) in statements.iter().enumerate() {
+            transcript.append_u64(b"statement-index", index as u64);
+            transcript.append_message(b"statement", statement);
+        }
+
+        Self {
+            transcript,
+            statement_count: statements.len(),
+            next_index: 0,
+        }
+    }
+
+    /// The number of statements that have not yet been assigned a proof transcript.
+    pub fn remaining(&self) -> usize {
+        self.statement_count - self.next_index
+    }
+
+    /// Derive the transcript for the proof of the next statement in order.
+    ///
+    /// Returns an error once a transcript has been derived for every statement.
+    pub fn next_proof_transcript(
+        &mut self,
+        proof_label: &'static [u8],
+    ) -> Result<Transcript, TranscriptError> {
+        if self.next_index == self.statement_count {
+            return Err(TranscriptError::StatementsExhausted);
+        }
+
+        let mut transcript = self.transcript.clone();
+        transcript.append_message(b"dom-sep", b"protocol-proof");
+        transcript.append_u64(b"statement-index", self.next_index as u64);
+        transcript.append_message(b"proof", proof_label);
+        self.next_index += 1;
+
+        Ok(transcript)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair, sigma_proofs::pubkey_validity::PubkeyValidityProof,
+        },
+    };
+
+    #[test]
+    fn test_protocol_transcript_domain_separation() {
+        let statements: [&[u8]; 2] = [b"first", b"second"];
+
+        let mut protocol = ProtocolTranscript::new(b"test-protocol", &statements);
+        assert_eq!(protocol.remaining(), 2);
+        let mut first = protocol.next_proof_transcript(b"proof").unwrap();
+        let mut second = protocol.next_proof_transcript(b"proof").unwrap();
+        assert_eq!(protocol.remaining(), 0);
+        assert!(matches!(
+            protocol.next_proof_transcript(b"proof"),
+            Err(TranscriptError::StatementsExhausted)
+        ));
+
+        // proofs in the same protocol never share a challenge
+        assert_ne!(first.challenge_scalar(b"c"), second.challenge_scalar(b"c"));
+
+        // the derivation is deterministic
+        let mut replayed = ProtocolTranscript::new(b"test-protocol", &statements)
+            .next_proof_transcript(b"proof")
+            .unwrap();
+        let mut first = ProtocolTranscript::new(b"test-protocol", &statements)
+            .next_proof_transcript(b"proof")
+            .unwrap();
+        assert_eq!(
+            first.challenge_scalar(b"c"),
+            replayed.challenge_scalar(b"c")
+        );
+
+        // a different protocol label or statement list yields a different transcript
+        let mut other_protocol = ProtocolTranscript::new(b"other-protocol", &statements)
+            .next_proof_transcript(b"proof")
+            .unwrap();
+        let mut reordered = ProtocolTranscript::new(b"test-protocol", &[b"second", b"first"])
+            .next_proof_transcript(b"proof")
+            .unwrap();
+        let mut first = ProtocolTranscript::new(b"test-protocol", &statements)
+            .next_proof_transcript(b"proof")
+            .unwrap();
+        let challenge = first.challenge_scalar(b"c");
+        assert_ne!(challenge, other_protocol.challenge_scalar(b"c"));
+        assert_ne!(challenge, reordered.challenge_scalar(b"c"));
+    }
+
+    #[test]
+    fn test_protocol_transcript_cross_proof() {
+        let first_keypair = ElGamalKeypair::new_rand();
+        let second_keypair = ElGamalKeypair::new_rand();
+        let first_statement = first_keypair.pubkey().to_bytes();
+        let second_statement = second_keypair.pubkey().to_bytes();
+        let statements: [&[u8]; 2] = [&first_statement, &second_statement];
+
+        let mut prover = ProtocolTranscript::new(b"test-protocol", &statements);
+        let proof = PubkeyValidityProof::new(
+            &first_keypair,
+            &mut prover.next_proof_transcript(b"pubkey-validity").unwrap(),
+        );
+
+        // the proof verifies only at its own position in the protocol
+        let mut verifier = ProtocolTranscript::new(b"test-protocol", &statements);
+        let mut first_transcript = verifier.next_proof_transcript(b"pubkey-validity").unwrap();
+        let mut second_transcript = verifier.next_proof_transcript(b"pubkey-validity").unwrap();
+        assert!(proof
+            .clone()
+            .verify(first_keypair.pubkey(), &mut second_transcript)
+            .is_err());
+        assert!(proof
+            .clone()
+            .verify(first_keypair.pubkey(), &mut first_transcript)
+            .is_ok());
+
+        // the proof does not verify in a protocol with a different statement list
+        let mut other = ProtocolTranscript::new(b"test-protocol", &statements[..1]);
+        assert!(proof
+            .verify(
+                first_keypair.pubkey(),
+                &mut other.next_proof_transcript(b"pubkey-validity").unwrap()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_transcript_context() {
+        let keypair = ElGamalKeypair::new_rand();
+        let context = TranscriptContext::new().with_slot(100).with_nonce(b"nonce");
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        prover_transcript.append_transcript_context(&context);
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+
+        let verify = |context: &TranscriptContext| {
+            let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+            verifier_transcript.append_transcript_context(context);
+            proof
+                .clone()
+                .verify(keypair.pubkey(), &mut verifier_transcript)
+        };
+        assert!(verify(&context).is_ok());
+        assert!(verify(&context.with_slot(101)).is_err());
+        assert!(verify(&context.with_epoch(100)).is_err());
+        assert!(verify(
+            &TranscriptContext::new()
+                .with_epoch(100)
+                .with_nonce(b"nonce")
+        )
+        .is_err());
+
+        // fields with the same value are encoded differently
+        assert_ne!(
+            TranscriptContext::new().with_slot(1).to_bytes(),
+            TranscriptContext::new().with_epoch(1).to_bytes()
+        );
+        assert_ne!(
+            TranscriptContext::new().to_bytes(),
+            TranscriptContext::new().with_nonce(&[]).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_transcript_context_statement_context() {
+        use crate::zk_elgamal_proof_program::{
+            build_pubkey_validity_proof_data_with_statement_context, VerifyZkProof,
+        };
+
+        let keypair = ElGamalKeypair::new_rand();
+        let context = TranscriptContext::new().with_epoch(7);
+        let proof_data = build_pubkey_validity_proof_data_with_statement_context(
+            &keypair,
+            Some(&context.to_statement_context()),
+        )
+        .unwrap();
+
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(&context.to_statement_context()))
+            .is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(
+                &TranscriptContext::new()
+                    .with_epoch(8)
+                    .to_statement_context()
+            ))
+            .is_err());
+        assert!(proof_data.verify_proof().is_err());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(&context.to_bytes()))
+            .is_err());
+
+        // the context is carried by the call, so it is not lost on another thread
+        let statement_context = context.to_statement_context();
+        std::thread::spawn(move || {
+            assert!(proof_data
+                .verify_proof_with_statement_context(Some(&statement_context))
+                .is_ok());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_ciphertext_context() {
+        use crate::{
+            sigma_proofs::zero_ciphertext::ZeroCiphertextProof,
+            zk_elgamal_proof_program::{
+                build_zero_ciphertext_proof_data_with_statement_context, VerifyZkProof,
+            },
+        };
+
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let context = CiphertextContext::new()
+            .with_memo(b"invoice 42")
+            .with_destination([1; 32]);
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        prover_transcript.append_ciphertext_context(&context);
+        let proof = ZeroCiphertextProof::new(&keypair, &ciphertext, &mut prover_transcript);
+
+        let verify = |context: &CiphertextContext| {
+            let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+            verifier_transcript.append_ciphertext_context(context);
+            proof
+                .clone()
+                .verify(keypair.pubkey(), &ciphertext, &mut verifier_transcript)
+        };
+        assert!(verify(&context).is_ok());
+        assert!(verify(&context.with_memo(b"invoice 43")).is_err());
+        assert!(verify(&context.with_destination([2; 32])).is_err());
+        assert!(verify(&context.with_associated_data(b"")).is_err());
+
+        // proof data is bound with the same context
+        let proof_data = build_zero_ciphertext_proof_data_with_statement_context(
+            &keypair,
+            &ciphertext,
+            Some(&context.to_statement_context()),
+        )
+        .unwrap();
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(&context.to_statement_context()))
+            .is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(
+                &CiphertextContext::new()
+                    .with_memo(b"invoice 42")
+                    .to_statement_context()
+            ))
+            .is_err());
+        assert!(proof_data.verify_proof().is_err());
+    }
+
+    #[test]
+    fn test_statement_context_encoding() {
+        let (mint, source, destination) = (
+            Address::new_from_array([1; 32]),
+            Address::new_from_array([2; 32]),
+            Address::new_from_array([3; 32]),
+        );
+        let context = StatementContext::new(mint, source, destination);
+        assert_eq!(context.to_bytes().len(), 3 * 32 + 1 + 8);
+        assert_ne!(
+            context.to_bytes(),
+            StatementContext::new(mint, destination, source).to_bytes()
+        );
+
+        let auditor = *ElGamalKeypair::new_rand().pubkey();
+        let audited_context = context.with_auditor(auditor);
+        assert_eq!(audited_context.to_bytes().len(), 3 * 32 + 1 + 32 + 8);
+        assert_ne!(context.to_bytes(), context.with_nonce(1).to_bytes());
+
+        let mut prover_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        prover_transcript.append_statement_context(&audited_context);
+        let keypair = ElGamalKeypair::new_rand();
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+
+        let mut verifier_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        verifier_transcript.append_statement_context(&context);
+        assert!(proof
+            .verify(keypair.pubkey(), &mut verifier_transcript)
+            .is_err());
+    }
+
+    #[cfg(feature = "sha3-transcript")]
+    #[test]
+    fn test_sha3_transcript_proofs() {
+        use crate::{encryption::pedersen::Pedersen, range_proof::range::RangeProof};
+
+        let keypair = ElGamalKeypair::new_rand();
+        let mut prover_transcript = Sha3Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Sha3Transcript::new_zk_elgamal_transcript(b"test");
+        let proof = PubkeyValidityProof::new(&keypair, &mut prover_transcript);
+        assert!(proof
+            .clone()
+            .verify(keypair.pubkey(), &mut verifier_transcript)
+            .is_ok());
+
+        // a proof generated over one transcript type does not verify over another
+        let mut merlin_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        assert!(proof
+            .verify(keypair.pubkey(), &mut merlin_transcript)
+            .is_err());
+
+        let (commitment, opening) = Pedersen::new(55_u64);
+        let mut prover_transcript = Sha3Transcript::new_zk_elgamal_transcript(b"test");
+        let mut verifier_transcript = Sha3Transcript::new_zk_elgamal_transcript(b"test");
+        let proof =
+            RangeProof::new(vec![55], vec![32], vec![&opening], &mut prover_transcript).unwrap();
+        assert!(proof
+            .verify(vec![&commitment], vec![32], &mut verifier_transcript)
+            .is_ok());
+    }
+
+    #[cfg(feature = "sha3-transcript")]
+    #[test]
+    fn test_sha3_transcript_challenges() {
+        let mut first = Sha3Transcript::new_zk_elgamal_transcript(b"test");
+        let mut second = first.clone();
+        assert_eq!(first.challenge_scalar(b"c"), second.challenge_scalar(b"c"));
+
+        // successive challenges and differently labeled messages diverge
+        assert_ne!(
+            first.challenge_scalar(b"c"),
+            second.clone().challenge_scalar(b"d")
+        );
+        first.append_message(b"label", b"message");
+        second.append_message(b"labe", b"lmessage");
+        assert_ne!(first.challenge_scalar(b"c"), second.challenge_scalar(b"c"));
+    }
+
+    #[test]
+    fn test_verifier_sampled_weights() {
+        use crate::sigma_proofs::zero_ciphertext::ZeroCiphertextProof;
+
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof = ZeroCiphertextProof::new(
+            &keypair,
+            &ciphertext,
+            &mut Transcript::new_zk_elgamal_transcript(b"test"),
+        );
+
+        let verify = |ciphertext| {
+            proof.clone().verify(
+                keypair.pubkey(),
+                ciphertext,
+                &mut VerifierSampledWeights::new(Transcript::new_zk_elgamal_transcript(b"test")),
+            )
+        };
+        assert!(verify(&ciphertext).is_ok());
+        let other_ciphertext = keypair.pubkey().encrypt(1_u64);
+        assert!(verify(&other_ciphertext).is_err());
+
+        // the mode only changes the weights, not the challenges of the transcript
+        let mut default_transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        let mut sampled_transcript =
+            VerifierSampledWeights::new(Transcript::new_zk_elgamal_transcript(b"test"));
+        let default_weight = default_transcript.batching_weight(b"w");
+        let sampled_weight = sampled_transcript.batching_weight(b"w");
+        assert_ne!(default_weight, sampled_weight);
+        assert_eq!(
+            default_transcript.challenge_scalar(b"c"),
+            sampled_transcript.into_inner().challenge_scalar(b"c")
+        );
+
+        // the mode applies only to the wrapped transcript
+        let mut transcript = Transcript::new_zk_elgamal_transcript(b"test");
+        assert_eq!(transcript.batching_weight(b"w"), default_weight);
+    }
 }
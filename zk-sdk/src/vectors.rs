@@ -0,0 +1,542 @@
+//! Test vectors for cross-implementation compatibility.
+//!
+//! The vectors are fixed byte encodings of keys and proof data that were generated by this crate.
+//! Alternative implementations and on-chain verifiers can assert byte-for-byte compatibility by
+//! deriving the same keys from [`KEY_VECTORS`] and by verifying (or re-encoding) every entry of
+//! [`PROOF_VECTORS`]. The vectors of the proof types that are only available with the `unstable`
+//! feature are kept apart in `UNSTABLE_PROOF_VECTORS`. All byte strings are encoded in standard
+//! base64.
+//!
+//! Each [`ProofVector`] records the statement (the proof context) and the proof separately, along
+//! with the label of the Merlin transcript that the proof is generated under. The instruction data
+//! for the proof is the concatenation of the two. The keys that are used in the statements are the
+//! keys of [`KEY_VECTORS`], in order.
+
+use {
+    crate::{
+        encryption::elgamal::ElGamalKeypair,
+        zk_elgamal_proof_program::{errors::ProofVerificationError, VerifyZkProof},
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    bytemuck::Pod,
+    solana_zk_elgamal_proof_interface::proof_data::*,
+    solana_zk_sdk_pod::encryption::ELGAMAL_SECRET_KEY_LEN,
+};
+
+/// An ElGamal keypair that is derived from a fixed seed.
+pub struct KeyVector {
+    /// The seed that is passed to [`ElGamalKeypair::from_seed_bytes`]
+    pub seed: &'static str,
+    /// The derived secret key
+    pub secret_key: &'static str,
+    /// The derived public key
+    pub pubkey: &'static str,
+}
+
+impl KeyVector {
+    /// Returns `true` if the keypair derived from the seed matches the recorded keys.
+    pub fn is_consistent(&self) -> bool {
+        let Some(seed) =
+            decode(self.seed).and_then(|seed| <[u8; ELGAMAL_SECRET_KEY_LEN]>::try_from(seed).ok())
+        else {
+            return false;
+        };
+        let keypair = ElGamalKeypair::from_seed_bytes(&seed);
+        decode(self.secret_key).as_deref() == Some(keypair.secret().as_bytes().as_slice())
+            && decode(self.pubkey).as_deref() == Some(keypair.pubkey().to_bytes().as_slice())
+    }
+}
+
+/// The statement and proof of a proof instruction.
+pub struct ProofVector {
+    /// The name of the vector
+    pub name: &'static str,
+    /// The type of the proof
+    pub proof_type: ProofType,
+    /// The label of the transcript that the proof is generated under
+    pub transcript_label: &'static [u8],
+    /// The proof context, which encodes the statement
+    pub context: &'static str,
+    /// The proof
+    pub proof: &'static str,
+}
+
+impl ProofVector {
+    /// The instruction data of the proof, which is the context followed by the proof.
+    ///
+    /// Returns `None` if the context or the proof is not valid base64.
+    pub fn proof_data_bytes(&self) -> Option<Vec<u8>> {
+        let mut bytes = decode(self.context)?;
+        bytes.extend(decode(self.proof)?);
+        Some(bytes)
+    }
+
+    /// Decodes the instruction data as the proof data type of [`Self::proof_type`] and verifies
+    /// the proof.
+    pub fn verify(&self) -> Result<(), ProofVerificationError> {
+        let bytes = self
+            .proof_data_bytes()
+            .ok_or(ProofVerificationError::ProofContext)?;
+        match self.proof_type {
+            ProofType::Uninitialized => Err(ProofVerificationError::ProofContext),
+            ProofType::ZeroCiphertext => verify_as::<ZeroCiphertextProofData>(&bytes),
+            ProofType::CiphertextCiphertextEquality => {
+                verify_as::<CiphertextCiphertextEqualityProofData>(&bytes)
+            }
+            ProofType::CiphertextCommitmentEquality => {
+                verify_as::<CiphertextCommitmentEqualityProofData>(&bytes)
+            }
+            ProofType::PubkeyValidity => verify_as::<PubkeyValidityProofData>(&bytes),
+            ProofType::PercentageWithCap => verify_as::<PercentageWithCapProofData>(&bytes),
+            #[cfg(feature = "unstable")]
+            ProofType::PercentageWithCapU128 => verify_as::<PercentageWithCapU128ProofData>(&bytes),
+            #[cfg(feature = "unstable")]
+            ProofType::PercentageWithCapSplit => {
+                verify_as::<PercentageWithCapSplitProofData>(&bytes)
+            }
+            ProofType::BatchedRangeProofU64 => verify_as::<BatchedRangeProofU64Data>(&bytes),
+            ProofType::BatchedRangeProofU128 => verify_as::<BatchedRangeProofU128Data>(&bytes),
+            ProofType::BatchedRangeProofU256 => verify_as::<BatchedRangeProofU256Data>(&bytes),
+            ProofType::GroupedCiphertext2HandlesValidity => {
+                verify_as::<GroupedCiphertext2HandlesValidityProofData>(&bytes)
+            }
+            ProofType::GroupedCiphertext3HandlesValidity => {
+                verify_as::<GroupedCiphertext3HandlesValidityProofData>(&bytes)
+            }
+            ProofType::BatchedGroupedCiphertext2HandlesValidity => {
+                verify_as::<BatchedGroupedCiphertext2HandlesValidityProofData>(&bytes)
+            }
+            ProofType::BatchedGroupedCiphertext3HandlesValidity => {
+                verify_as::<BatchedGroupedCiphertext3HandlesValidityProofData>(&bytes)
+            }
+            #[cfg(feature = "unstable")]
+            ProofType::GroupedCiphertext2HandlesCiphertextEquality => {
+                verify_as::<GroupedCiphertext2HandlesCiphertextEqualityProofData>(&bytes)
+            }
+            #[cfg(feature = "unstable")]
+            ProofType::GroupedCiphertext3HandlesCiphertextEquality => {
+                verify_as::<GroupedCiphertext3HandlesCiphertextEqualityProofData>(&bytes)
+            }
+            #[cfg(feature = "unstable")]
+            ProofType::GroupedZeroCiphertext2Handles => {
+                verify_as::<GroupedZeroCiphertext2HandlesProofData>(&bytes)
+            }
+            #[cfg(feature = "unstable")]
+            ProofType::GroupedZeroCiphertext3Handles => {
+                verify_as::<GroupedZeroCiphertext3HandlesProofData>(&bytes)
+            }
+            #[cfg(feature = "unstable")]
+            ProofType::SplitConsistency => verify_as::<SplitConsistencyProofData>(&bytes),
+            #[cfg(feature = "unstable")]
+            ProofType::CiphertextCommitmentOpeningEquality => {
+                verify_as::<CiphertextCommitmentOpeningEqualityProofData>(&bytes)
+            }
+        }
+    }
+}
+
+fn verify_as<T: Pod + VerifyZkProof>(bytes: &[u8]) -> Result<(), ProofVerificationError> {
+    bytemuck::try_pod_read_unaligned::<T>(bytes)
+        .map_err(|_| ProofVerificationError::ProofContext)?
+        .verify_proof()
+}
+
+fn decode(base64: &str) -> Option<Vec<u8>> {
+    BASE64_STANDARD.decode(base64).ok()
+}
+
+/// ElGamal keypairs that are derived from the seeds `[1; 32]`, `[2; 32]`, and `[3; 32]`.
+pub const KEY_VECTORS: &[KeyVector] = &[
+    KeyVector {
+        seed: "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=",
+        secret_key: "/p2RDw6SEuB7gS8RRIxyCvYEcmatl9eJYlIewdooNA4=",
+        pubkey: "IsyEttTgP/KsDJQCPygBj9X+uOQfCVCK6xFKQSkv1zo=",
+    },
+    KeyVector {
+        seed: "AwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwM=",
+        secret_key: "9YVEjmqTMyk3f4WMyQNR4EI5tk9FHhluVxpZMhJgeAA=",
+        pubkey: "utQSQb4ROuNGtFNXAdJI6SJAOGPICQxQWronafk3fT8=",
+    },
+];
+
+/// Proof data for every proof type that the proof program supports.
+pub const PROOF_VECTORS: &[ProofVector] = &[
+    ProofVector {
+        name: "zero_ciphertext",
+        proof_type: ProofType::ZeroCiphertext,
+        transcript_label: b"zero-ciphertext-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIChQVwJWtTOmIoCIilkNDJx+PsAxSBsjHnfwE/04U5EbQ2",
+            "Z7rG6icvCFblOOKY1Kpze6F2Pingt8U1dSmp8NVL",
+        ),
+        proof: concat!(
+            "6muaz6GjcTu4axFXx+3zVX4b7sVemcURebxGvjznIiIGWb4m4B6ZmNUkNSO5Lz6baXEN9iCR4U9HPDRiodeZcCyU",
+            "zHEm47ivPcI3QoO8BNC3dRF2HKndRXNr7Tw2cB4H",
+        ),
+    },
+    ProofVector {
+        name: "ciphertext_ciphertext_equality",
+        proof_type: ProofType::CiphertextCiphertextEquality,
+        transcript_label: b"ciphertext-ciphertext-equality-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOvIN",
+            "snlqTuBsiEYb51E/PQpyY7uU2OJYCVr+OXTHLjN0PsJCsBCL1Ay6oOxfAB8w4bSqqMOGQkNDneXyIfLVhR4ARt4W",
+            "oRMgUxZqP0TcYVqHmnZCIDIoYFPX+bOpCswWEE5KI95+WwwFdMwq8aF0uycLdOwTwAu2MzlwGoa00kgg",
+        ),
+        proof: concat!(
+            "tginJw0POyk9r/qOTgpDhQt12/uZOG/rYG6YBjD7JDHGndiCvliaHTN9bJuiQJTks/oDR4Q63Kt90LQmebHFRkgZ",
+            "Xy7TxPnkZrX6sshHTvRyNcVhf52Q9ovHY3k1iG8qSh38f2ik5yCgaf+qposG124452VX4lwV1C4Qv97ZCQCfayjz",
+            "6t59VLQwzWykO0wkGiarCNfFMQcfipZG6HYxAp/T1Ki+5oW7Or+JjX6/pS6bXSiHm/P383SftkmgUw4CypWLd5In",
+            "A434JUSxz4cvAOJBIklGetRjcZbzgYpxiAo=",
+        ),
+    },
+    ProofVector {
+        name: "ciphertext_commitment_equality",
+        proof_type: ProofType::CiphertextCommitmentEquality,
+        transcript_label: b"ciphertext-commitment-equality-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHLyDbJ5ak7gbIhGG+dRPz0KcmO7lNjiWAla/jl0xy4zdD7C",
+            "QrAQi9QMuqDsXwAfMOG0qqjDhkJDQ53l8iHy1YUeMm8HZquPCOGEWKgOiSJB/lfZD/fsBtGmHyLZh2EW1Rk=",
+        ),
+        proof: concat!(
+            "Vma8KZWV1Un5zry1q0tbpjzq88MtRJHJ01ZI7RSiKy5Kpgcpm1K+DQr680ETpXHWPNrX/AEqFkGvKaor7dYCSGoD",
+            "TW3IbTdP1ZCJIglxiZPNlCCf5dDCfEFrXnvreaVnKZ7es5HhkrWBXCtUNudb8WtlT0LBkiU04+n+mXCfvQTDqliH",
+            "bKJ94mwWXO9QDpreFhqkXhOYEJx631/W2Kp/DcVzvTJ3h7hbOod7tHZ16F8qOxL/1h5+KXS1Kg5AqqsF",
+        ),
+    },
+    ProofVector {
+        name: "pubkey_validity",
+        proof_type: ProofType::PubkeyValidity,
+        transcript_label: b"pubkey-validity-instruction",
+        context: "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHI=",
+        proof: "KFXWaxKn5bLVm/5vtuVimgIxBX+E+jH9BJoP1bbpKSJgDBwTXkVvoTGDnVrpS4PcuK4LIowumVByJ18F7gldDQ==",
+    },
+    ProofVector {
+        name: "percentage_with_cap",
+        proof_type: ProofType::PercentageWithCap,
+        transcript_label: b"percentage-with-cap-instruction",
+        context: concat!(
+            "VGnMUi0gLot5YvtFQMCVjA4zSrT/6cMTrRr9XZMORkn61EHdDia0Wxe0ZAYDUqvZe3LgbCZsknogbnYDF3xuYhKQ",
+            "dGhNRcaISk2dbTHNeganpHmDavl8vecGLw9rpLA76AMAAAAAAAA=",
+        ),
+        proof: concat!(
+            "vL7CuBkyyoA6FVIIjXDRy8IFzYXrF7Yxpo2ZXFrvCwkH7eooW9n2I/IDae7TbgMDA6xdv9ZDKV1tPulYcWToDA1r",
+            "qLUGFt3LGrs/nesQuaaFS+BqarfiVEAK1SVyo14PXt1FYjFIMVyIGc0J4sgtcFhIrohYVkAWTVLJdJFurlyENJzm",
+            "BbxkM4sdFXUu8xupAYdQmR0udMOSr6Mua9ZyEbT4LGtVYfxkDoxH3gx9E1dQuSiUKDiQYqbiksfRhyoGiGNiM4W8",
+            "eQgZajjD7o6XFmBLoorQl7k9zRbyAeooHgvQ07M+qzXMXhXsooDXeYlKO/rGayGt2uXedMFBdvMeCg==",
+        ),
+    },
+    ProofVector {
+        name: "batched_range_proof_u64",
+        proof_type: ProofType::BatchedRangeProofU64,
+        transcript_label: b"batched-range-proof-instruction",
+        context: concat!(
+            "YgPKsdqklrDaIEPRSphx6TDUyLrRhIJFDdP3XV+KI3f65pP53+mg3WOI8Vgow6Ovrxnf6lDObaCkt1l4bwp8KFAM",
+            "BV2N2JphPKIEW5xGPIADX3QyOzvBW/CF3npO+RBtAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAQEAAAAAAA",
+        ),
+        proof: concat!(
+            "tKTsbQUmG5c1EYxJDotGMo89iRYrF1h0aSIYDNF1DCB0rhsoM+h4NPrJ9dzQqn359psz4K+is4IXR+VKWw/IaUrz",
+            "Ug2KOFWaYEs6uV71/Po5m0L5yRSstZ+ixmatPg1uxJZ1Es6RPgf56+ij2RBsK9vBy7qR9V8zKP3Xwyp1piQ5/TFg",
+            "+qphX9KL+xZrfE8jdhgdLdwDlEA730vpRHkvCObbS0tn/r+0ECLrDddXQrANcyl5cySJkXtEQ0MIfCsAMKYi7Ywt",
+            "ZtXizbvrz/fFydCZWGr7t9cEVb3dj9oW6Qr2f8WDs9ye417Ckpl1S87BskZMBXfkHbpS29H4EOcvfUTnBjWqec5v",
+            "NVk5oXe+C5KL1CTIZCeJYhuUUa3qJPwiAoCqke0S5c0FpXl/1eb5k1cpJL5xh78ilzMYS18B7RnEjmtujX+Hd5+s",
+            "CV5CmWNpTZgW+/4JtJaaob4Bxm3AbU6rPdiflKZIeBbslngL9eXqv2QebpJ1t/fltXJwiZdPXth7Vm12su4zPCSy",
+            "lK+RpSxNGKVmt1Gc/N/4B4IvBkxG+8o/qvzYKme8IBdqEB6mp85NhhOcCCoMBvM/qyvAFjIcwkj0iStb/ILeF1c/",
+            "/u28tnecA53vvFlTiIn0ehgqgJheyX47ftqwnSUcPFGQ+5va6DAc93bzPbDl5RtgTHqe6r8jwTW15jBkCNjerBbG",
+            "I/TEl3uI2J8u5p2D95AyBtx1lVf/IyJSNywie1Xd7aDhkC6FVJ7M9FVblYGSVyxz0N2V62opHRWdks6bhMfhkTWH",
+            "A45KTGMlAXjnUmXj00fK/+F5Imsdu0Stm3p3bN/1Trr8ZuwHuM9wTvkk3YoHCoLfV3qOB/+tzUjRRxuGow3CpboV",
+            "i6tI0xhb+dfV9/wB",
+        ),
+    },
+    ProofVector {
+        name: "batched_range_proof_u128",
+        proof_type: ProofType::BatchedRangeProofU128,
+        transcript_label: b"batched-range-proof-instruction",
+        context: concat!(
+            "YgPKsdqklrDaIEPRSphx6TDUyLrRhIJFDdP3XV+KI3f65pP53+mg3WOI8Vgow6Ovrxnf6lDObaCkt1l4bwp8KFAM",
+            "BV2N2JphPKIEW5xGPIADX3QyOzvBW/CF3npO+RBtAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAEAgIAAAAAAA",
+        ),
+        proof: concat!(
+            "VivPZymV0HGhqvqsqFE4wRzROcpIsiE7R1IPRC+3Rh7QZdq+z14E0JgzaFopcjQnrDQF15fjbczj1WKaXPgKUXgK",
+            "JOs5ZR/KXzlM4pCqJujXrBnnOWMZNII1Exect4AeLtErGrmBgIgqNN+KZbOkuL5Y32Cih4ZRceWAXLrxZSrDemD2",
+            "xWb/9uFAkXvVQM741mKrjIVMTp1rZVwVAJN5AM0U6rmH4abjQ2XHbsmz/t/xeLlKTMMlFt7HiArDPfwN4M1LKKLD",
+            "yLkRQGciy9PocOEUiFJcYGoHjsh/kUVycw/c5UATq7Fh6QohVaXCOXfCiCXb91uB8tNIIYYboqIlN4YCCkgOt8ZL",
+            "zhKt4ei+qYx9HCBo3jfdL8axzOln80xyhlOaqy2Ws4efaXdjQgjvbWKwhCDKUZqc91HTWu/86lBklstO2s+vmYWK",
+            "KuJy3YVYffIdqrO36SNzOUAjjQe0fZRnstykCjx7uduqRNgC+o8eFpBPO3tfpKQ/X5JrqR1KlgRWSFSFRnY4WtRk",
+            "3OrkBHMF+De0yYbWpPtj+mpSsni0XrSVHetdVJgcesppIjodvHiOOIv51Rq08XHz3M1NagTvch1eB0BlCm/jnI6M",
+            "IqgV6fgXutXOYfFwvtpXjdRCnELM9+jGPareWwxc8+FDedDqcygyiUbHtVzH3so+vzJ+CY7BtEXVX5fBHoMOuceI",
+            "LSLLcQ6l7ezc385l0zckZrw/MBRaqdOx4LsDlIVxDiw1js4sGINYiKsUzNgiiB47FIE9EjV6uCYS2v3kr9QBwsVs",
+            "eA7jUn9b6PC8IpT91EjarKx3uoDwWFL0coz3420wZFdf3zeAQ3zOHl4bkeOONeDH3Pa3/oOr2mTQxyMoJhQAn6fu",
+            "nPB2oGTgXA5BPk1wV9mtIhctRqoYlmLgdX5IK/kRjuecAbytsoAeWIsoYgp/pkfbONoo50UfbrIumwYPHjPa4QMI",
+            "rBAIRD4ryoATBg==",
+        ),
+    },
+    ProofVector {
+        name: "batched_range_proof_u256",
+        proof_type: ProofType::BatchedRangeProofU256,
+        transcript_label: b"batched-range-proof-instruction",
+        context: concat!(
+            "YgPKsdqklrDaIEPRSphx6TDUyLrRhIJFDdP3XV+KI3f65pP53+mg3WOI8Vgow6Ovrxnf6lDObaCkt1l4bwp8KFAM",
+            "BV2N2JphPKIEW5xGPIADX3QyOzvBW/CF3npO+RBtiEUtfoyNiDxqMbfrPzbmtV87epu7fL8zti+zASd/YA4AAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAEBAQEAAAAAA",
+        ),
+        proof: concat!(
+            "oLoa1aeWyXbDjU6uoGVwd04v9NIs/K7rybcLE6oDXTyoFbY4J4xI5Qgtzem3bVjxBhuPek+WIsEdl9OYbZZTQGTs",
+            "9KMZeYOef5fOzx7CEejhD/Iyewz3ySrO4fJJyVA/5sKtk58iIJzk9QOp8ZAgELpZ/QhTBJVV5mNYf7mBwwnMimyb",
+            "TF34aXW3iu+N6ALdvGBTvgF82WJ31AuHdoGhAoeOasFFistCvHU77pYcsX5qOYEnjARJEcv0HGWR138OrSTXRSdR",
+            "0KcCuolupPTTBjAX4eyZsMOHDdTrDBuRnAJYf+jbJNBBjrQat33jR7ksxDDuP0jPnQqUFalOyOWNTIxb4XQiclau",
+            "HsI63ng3mXRVjY9B/LrflUVVTrTfE04oFEwDniyURjlbbFnjMvJyVz5gV17fICALX223HQUJmnSc3eJuWkaWNr0v",
+            "5MaqZdAu3jXOglmVm5DXnnUyEsMTTsJTqKsnqWXgApC798f/pmfqkbyWmH4sM8gFdHxG0RRgEiVica0xrNpKkDNQ",
+            "DcIGu+e/b1VHtxS4fU94KOpa4nFmEkJtJrUZyBvEyxAVCZ+AHcr0SBxooIx/WCXRiIeqNKy1O3F1GNcdt4Jtqp25",
+            "xA83b0QgadKXkakAfnj0j6NkmJbWeCXc29rr32Sbuoi6IVQVleML8UGEVljkA0XR4hg4nQP2aZAPsmnMRVrb6x03",
+            "lDRkZuI7Y76SBo+qzc9fQgSeI2IUWQ9rlsNxw9BeMhblrIXrm0KuuNnMWPJeCItRJNq6eqkgKHZ0O6+gFKMPZ02A",
+            "S8pRPv5yxGNsL+r2wgUylYaHfskjPptN/fWx+eDO/JTtek4v//8v0a3IVBgJZ0qvZx+eizaPVIfCQcMLvRmKiwto",
+            "ZGzEN80Wyu/mvxwrKojpRA4Dgmv6sfwwA/2tkmzWgCkHHJj6GQlwi4wYf3fEazAMQ0k0NE04iOJAf/gkh/bonnpe",
+            "MtYgpzKkfUoKN4Aar7GDlwVYrwqJAI6keL3r/prxoj3FwVGRGxK8JfcNYgH0qk9mKjNRglp9nIRvynaQZfoJCvV9",
+            "A9zjA/aqAAw=",
+        ),
+    },
+    ProofVector {
+        name: "grouped_ciphertext_2_handles_validity",
+        proof_type: ProofType::GroupedCiphertext2HandlesValidity,
+        transcript_label: b"grouped-ciphertext-validity-2-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOkqj",
+            "Ddhj7UJg4vC+W03NsX50fovabUiy+ALNmYlxgVZr3Ca6c1oNPM6aOsI4p35LA1hPPwC/V89Y4nTIClK4VF0YNfJw",
+            "rphnxqu6wq5+NAQA3nAzdYd6XA7W+KPsoK6aSw==",
+        ),
+        proof: concat!(
+            "1FBceltc7pMEIz7ybM7fvAnBs7drgjYv8Eb7BxEX0xK4XRSKVY8Wk0dtaM37eZJ4C9/k0Tn7sETfNG2xaAX9GfrH",
+            "l793MLHG1EcN4gRG5oKxKZuMWRTurcLwIgrLMaALqa0a7Ho/otw+NziDoxIV7O6vF8WKI6vN42DW0/88Ng6OYHpZ",
+            "DzvIGsTZ8pMLFieMqNF1vQdTColNXuy/ioJ2CA==",
+        ),
+    },
+    ProofVector {
+        name: "grouped_ciphertext_3_handles_validity",
+        proof_type: ProofType::GroupedCiphertext3HandlesValidity,
+        transcript_label: b"grouped-ciphertext-validity-3-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOrrU",
+            "EkG+ETrjRrRTVwHSSOkiQDhjyAkMUFq6J2n5N30/SqMN2GPtQmDi8L5bTc2xfnR+i9ptSLL4As2ZiXGBVmvcJrpz",
+            "Wg08zpo6wjinfksDWE8/AL9Xz1jidMgKUrhUXRg18nCumGfGq7rCrn40BADecDN1h3pcDtb4o+ygrppLShw8oJPn",
+            "GNtOWwMdK1kftTO9V8bxJgJNwx60FSceJxY=",
+        ),
+        proof: concat!(
+            "vJXpJmLM9v5chP/19V9DAkDQnybLUgKLS6KzdWZeiCDIBpJbX+p+XAKv2hLTRAYfPoA5CBkEIhMD+bqag2ivVWYj",
+            "eFWCOJextHyQDZcATCDIGie4vRWWskDUUXOmDypXolgomYOZccKLfY/KBJvBDv9Ey3t5neceUZWy4sGmZWTIanP/",
+            "TbheMrKnTw+7HCKqLOfimqEAaPPern8lztFKCIfelCxTkNuk8hsUY1Iz+RbtUnne2nTTyBNTYiMfLSUD",
+        ),
+    },
+    ProofVector {
+        name: "batched_grouped_ciphertext_2_handles_validity",
+        proof_type: ProofType::BatchedGroupedCiphertext2HandlesValidity,
+        transcript_label: b"batched-grouped-ciphertext-validity-2-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOjQO",
+            "zDpkgqwxFibtbUEittEwsKcGK05P0dWsRlRCmeNyQsuOmMHG/Pnkm+NpTbUCBr4iBW0p+KF3eITtO/idayUUOr1G",
+            "9y7rZZFWUSb/yDaMmulBaD8K0Bzp4p3ob2c6cgIa6U+7n4pytHEWXeQzsevaYxQxgIWLSpQCl/MWz54RII9t5qN+",
+            "YKKIwZ2G08IIss/I8adF6gAJ6mgWErG1DQIGCcmuQqDo8VdvyGvmq91qmyissxBt//KKi8W+ADJgYg==",
+        ),
+        proof: concat!(
+            "1Hy27UNq+S7Uv/kQyLObGXDHpY+3Cc6Ilv0MWVLxTRmUb+FgAcOjVHikrxmcDtGkZuc3mNb8v6zwMhMsh2CmY/pX",
+            "lqW0UTKGO3kOdjyfVIo/tZFWVNsL6VKEtSoJ/bcG5Mwux5UOm3DDgZSt4Op9Nn/gb+PnVeaDcJV+29B4ywUXkIUm",
+            "anmWAo6G0ZYt+Xl+a/nbXNQeaVd/KO+wPpVaDA==",
+        ),
+    },
+    ProofVector {
+        name: "batched_grouped_ciphertext_3_handles_validity",
+        proof_type: ProofType::BatchedGroupedCiphertext3HandlesValidity,
+        transcript_label: b"batched-grouped-ciphertext-validity-3-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOrrU",
+            "EkG+ETrjRrRTVwHSSOkiQDhjyAkMUFq6J2n5N30/NA7MOmSCrDEWJu1tQSK20TCwpwYrTk/R1axGVEKZ43JCy46Y",
+            "wcb8+eSb42lNtQIGviIFbSn4oXd4hO07+J1rJRQ6vUb3LutlkVZRJv/INoya6UFoPwrQHOninehvZzpytEl7Hc+y",
+            "qjWYMo/L+QThzgiQDsGCK98uOYAXBFcLOSwCGulPu5+KcrRxFl3kM7Hr2mMUMYCFi0qUApfzFs+eESCPbeajfmCi",
+            "iMGdhtPCCLLPyPGnReoACepoFhKxtQ0CBgnJrkKg6PFXb8hr5qvdapsorLMQbf/yiovFvgAyYGLq+HDAlsdlI6cX",
+            "tsoQlLN7dTM6hGe27dPbgAKEVEWiKw==",
+        ),
+        proof: concat!(
+            "/jq4MMucwYf6tmgFYYGLsamZR2dac6mnR5vLAu5PPBPi42bIRDbR1ttaZ2oi21knw7qhfy1NJEagRJd+ls0GYqRF",
+            "6FMjiLacSW95+krbpQNb1qvGo+4AuPz2AEeWIulz1NrSlVuKiTyqPdY5ioHLg36Q27Oty1G1t8RQ+FP8s2/G/z11",
+            "3fr6G/0gIuCAXWAULGwlmi7s50rHDQpLczeuC3QYB/ZoTBJsToztYC+8yeAiu+qMKtkK879mlJiTzU4I",
+        ),
+    },
+];
+
+/// Proof data for every proof type that is only available with the `unstable` feature.
+#[cfg(feature = "unstable")]
+pub const UNSTABLE_PROOF_VECTORS: &[ProofVector] = &[
+    ProofVector {
+        name: "percentage_with_cap_u128",
+        proof_type: ProofType::PercentageWithCapU128,
+        transcript_label: b"percentage-with-cap-u128-instruction",
+        context: concat!(
+            "VGnMUi0gLot5YvtFQMCVjA4zSrT/6cMTrRr9XZMORkn61EHdDia0Wxe0ZAYDUqvZe3LgbCZsknogbnYDF3xuYhKQ",
+            "dGhNRcaISk2dbTHNeganpHmDavl8vecGLw9rpLA76AMAAAAAAAAAAAAAAAAAAA==",
+        ),
+        proof: concat!(
+            "Ahxi6SrMUCPkmElshVX3d8DXW8I37aTMJwHC5DbmETs+GyaFRWlO3OYiPcWmHyeRv0ps0MebuvqxJ7VTXrmJApeg",
+            "Wqs0/0GNoyACw+ZS4ETHCgQ3PDb+oIFJMsS661YEGqJvOxUF5me+107rix0jAGX3vYVqyrTJt0tBauYWu2Rsprp9",
+            "wq54s+K8/dtL9O4UAfjUsfJIj+4CXrRfDsh8QcvfkTMWvHUpr/YQAIVPpTIzMTgZ9Xbhe0sgW8zGoH4CXBe5XDI4",
+            "clRwX4jBYR6GXQdyUO+rHk9OYO3H8chqBQZ1tmUctRe4RDqMeZv4NeT0bnTkvoNeii4SbAtWFg8IAw==",
+        ),
+    },
+    ProofVector {
+        name: "percentage_with_cap_split",
+        proof_type: ProofType::PercentageWithCapSplit,
+        transcript_label: b"percentage-with-cap-split-instruction",
+        context: concat!(
+            "VGnMUi0gLot5YvtFQMCVjA4zSrT/6cMTrRr9XZMORkn61EHdDia0Wxe0ZAYDUqvZe3LgbCZsknogbnYDF3xuYhKQ",
+            "dGhNRcaISk2dbTHNeganpHmDavl8vecGLw9rpLA76AMAAAAAAADumJDyJ8J4ZWCQ+UnfkglwrvGVRUfAEUSdBLCs",
+            "5jJPFWJ7sf7GKcup9lZe+zsfzgnlmvKOkGCMh86U3vKdlVNsAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==",
+        ),
+        proof: concat!(
+            "UvYzaLf0B9TfGMhBkpAuAftCPjwrfxlpwNXskBt4LRJaPksyH/AG51bG7pl2kXN45ghmMnt2MCUzESc/pPDhAWt8",
+            "DQvemh9P7HK43k7naEtlIfa2kGqESUiuVqQlGbYNTu4wOuQLc8JitH+CX86jG9IRm7ux1Ay9I5qmmAYmqmcg+Okr",
+            "vFMYqqkkHlZEWrIPSknYeexiFRMYewDtHAyQOCJCYTLxmgHs43Hv/iBKOAX6e8UuenkCSF2UO2qa9ugNjcvOXVcA",
+            "1g1iQq+dfgDZ0DUEXfB2Os50crEp4A1C9w2wWKcYSYJs0XFxraLbONcRqZbhDmmo/Rs3yv1wuJQFA6TQFpW7P5Bo",
+            "y+iXPRew6FUlHlG+KwH9GU6Uohp3V5pYduMB42CdhP3WUmgTTvbAHivdJLSxGmCWE7CLq0e/SA4=",
+        ),
+    },
+    ProofVector {
+        name: "grouped_ciphertext_2_handles_ciphertext_equality",
+        proof_type: ProofType::GroupedCiphertext2HandlesCiphertextEquality,
+        transcript_label: b"grouped-ciphertext-2-handles-ciphertext-equality-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHK61BJBvhE640a0U1cB0kjpIkA4Y8gJDFBauidp+Td9Pz7s",
+            "kHhDKX3Gcq9Gr2VWJ11qqP9/HTS9wzSF4IOzauoy0FNjShtGAzsWrlNVDAjAZOt3GS+KcG94DfiRK14q7jGc+tUx",
+            "VRFfLkcDvyTB7w4UA+R3GHG/rDWJefQmTYr5RQBG3hahEyBTFmo/RNxhWoeadkIgMihgU9f5s6kKzBYQIB6uppFd",
+            "DlAntR4YnOM4iqUYUuZpgWBag1mONYeknngA",
+        ),
+        proof: concat!(
+            "4BXPI8wY3xZO1fJSupgom03XRcJ+k3sXtHgpUGjHoFai9DJbaOdU2htcNxQwoAKPgdys+s9BMtGWyC+QYb10QH6B",
+            "CrKz21yLouhDmGBLXhU245WQdazz1EStqgXVFYZDVMr/lRP48BuEw/mMDU7Hx2IwumbFEQOg6Cy5l3ZCcnt4G/Tx",
+            "5Hu0k6poqCLX2fQqkGAQzDatNgFThqEW6WLGAN8zAxVDbRklTk7zjxoBEOFnOwUQGd03rJA2QCTFVmsOaiGK5kRK",
+            "hVbbL60JlQoqETKOU0mhmESSEn8vYfsUvAI=",
+        ),
+    },
+    ProofVector {
+        name: "grouped_ciphertext_3_handles_ciphertext_equality",
+        proof_type: ProofType::GroupedCiphertext3HandlesCiphertextEquality,
+        transcript_label: b"grouped-ciphertext-3-handles-ciphertext-equality-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHK61BJBvhE640a0U1cB0kjpIkA4Y8gJDFBauidp+Td9Pz7s",
+            "kHhDKX3Gcq9Gr2VWJ11qqP9/HTS9wzSF4IOzauoy0FNjShtGAzsWrlNVDAjAZOt3GS+KcG94DfiRK14q7jGc+tUx",
+            "VRFfLkcDvyTB7w4UA+R3GHG/rDWJefQmTYr5RQzy2Zz05UFUG2OZh2P+QEne4nawOsdq0DqyEHxPTWcoAEbeFqET",
+            "IFMWaj9E3GFah5p2QiAyKGBT1/mzqQrMFhAgHq6mkV0OUCe1Hhic4ziKpRhS5mmBYFqDWY41h6SeeAA=",
+        ),
+        proof: concat!(
+            "5uFb05XLCc1//i/mZHyeajmwcbqWGvRCsaZyvGahAy1uKkJqKlAru2P1751hNAbMe2qvPanCmvMTLC15sBTxP/AU",
+            "a1A3xodYF7TX5WZ9t3sWKrouXZNrkQ+O2Rb1gIlsIhx8MBtjMFQnpKIBeAi/abt2NoV7SPdRX1BP4YE3lAr09paT",
+            "Ob+26yddEZfKb7PfgtPGdFmFgqRf0VEklVgnB0gV/7x4FBI5haoQOKVcrBhWPOioE5BIfUsDTumjrGUKtAYgTuyS",
+            "Ja8NCKYRQsM5lTLtlC6rnhnwRCE9CUHotgE=",
+        ),
+    },
+    ProofVector {
+        name: "grouped_zero_ciphertext_2_handles",
+        proof_type: ProofType::GroupedZeroCiphertext2Handles,
+        transcript_label: b"grouped-zero-ciphertext-2-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOiZO",
+            "Xo82uvrrkk4EV1Qunm6KVkJeen2a+4s2KT7gmCJJUOCRyQCG/US6USzyLxjPNLY+wnIUpmh8TUYdOdQfeHkQ/HPE",
+            "fdlVlcaaOcyJRIFbtJwWVrvI+Up2yzazVlt4Sw==",
+        ),
+        proof: concat!(
+            "QhsgkS0wEXeiN+6dfXnlLf373HTIGA7vG3G968n5ew3YTttnOjUDJLBMng41iJIiaSQGIAuw09eKZxm/xcq9PYYg",
+            "uu57G7dUVSLD2huOA78ZQx24gUCrieL2uMqlE3diP2SN3pW2zi0g5PZ73yH0QbOhY+mIJpd7WS0sVWU+mg4=",
+        ),
+    },
+    ProofVector {
+        name: "grouped_zero_ciphertext_3_handles",
+        proof_type: ProofType::GroupedZeroCiphertext3Handles,
+        transcript_label: b"grouped-zero-ciphertext-3-handles-instruction",
+        context: concat!(
+            "IL31WtZeAjgAW8x0ZqCCdUm/ibTtRZ0aOi6mA1wjXHIizIS21OA/8qwMlAI/KAGP1f645B8JUIrrEUpBKS/XOrrU",
+            "EkG+ETrjRrRTVwHSSOkiQDhjyAkMUFq6J2n5N30/Jk5ejza6+uuSTgRXVC6ebopWQl56fZr7izYpPuCYIklQ4JHJ",
+            "AIb9RLpRLPIvGM80tj7CchSmaHxNRh051B94eRD8c8R92VWVxpo5zIlEgVu0nBZWu8j5SnbLNrNWW3hLGAxEgl+s",
+            "q1etCHmek06xS+DyCxGvXTDw2m5echJxkzQ=",
+        ),
+        proof: concat!(
+            "6C9IuOF4KoDw2tOSx1ws6gFWe86YOXYGuORslMhYthfuumwr8tUcnWgxHsrx4INI0vqbFmbBb36YRumxrC93Ig7y",
+            "fRcbT/+KUB/bGYfuTzrYBT2g9TqtroENhxH87fZm1G0vUwf78ENhNyLId9OiWTIyNIuXm1Bzg5sxdGINsAiz34Kt",
+            "FkfnzE9tSbSbxayEamo9XQAKjah6XQDE3G4hBw==",
+        ),
+    },
+    ProofVector {
+        name: "split_consistency",
+        proof_type: ProofType::SplitConsistency,
+        transcript_label: b"split-consistency-instruction",
+        context: concat!(
+            "MjlTaq8lL2WL9hbRh1brEYJfHJri+MhhPlrui19YRQKgUEkcF26CqaGBIE5NC2nxvgOaxYoDSr1FD2ogxCtMPZSK",
+            "NeAxAhexoP1fTHhlWrYlWnWU4snfSQWEqMFhbKEBci4i4Delq8JJZbVNtPaQZHidk4kIjbRaDBmViejbbBju18k6",
+            "kf8ax7vlz2G7NBjewFd1KBcF7fqL6xZjfghNPJL5Oe9HbT+6ITET03+6J4aoCycnRwrqAawonOOIJwcYpJln78nL",
+            "TEflq1qo/elgbGm6vcB69h7d9CSEBKwUrkE=",
+        ),
+        proof: concat!(
+            "hERTr97lI491mwviWl72mCIhFrN3OKRpOqayKmTceXusyG7frXeJZ3dO1aUBlpIJluKsvFdIbC3lfRq0Gl6WQUvf",
+            "MUA6Z0vseUjHCmG/cIigG6RO2/cKvHmkmrL3mHwD",
+        ),
+    },
+    ProofVector {
+        name: "ciphertext_commitment_opening_equality",
+        proof_type: ProofType::CiphertextCommitmentOpeningEquality,
+        transcript_label: b"ciphertext-commitment-opening-equality-instruction",
+        context: concat!(
+            "1LvsJR6GgKsg0CrZ6cR5XfePXBH9fK1sUQrkxloiLV5sIgK8NSER4CSL+vlJfS2UjJl/ifaUB0W44ty53B94ForG",
+            "iRkBPGEG1bqHEWo32YlwUCBo+FhAIhxB4j7aIlYMvuWISczo7yW040HHh7IQuyLxkvmpAAjKhvvv8UNg7AU=",
+        ),
+        proof: concat!(
+            "iqOKKdssiS5xB562jKf71mReZV/gB1KjIG7PlafmjzHSp5qxZjfyS2MAKNHQvIX4YAUqQxcAl/dufdpaDltqGlxm",
+            "ndkfzj0msUw6Fu3fBzLeOQlSd3xQmIVDx0uqIbk7QU3JlrFyfSQMZBEgksaSSZPNP3+jRA1/1FMG73cnDwSkDpVP",
+            "UwTueC08BfaiQULe71RH3kJgDm/ndwtstehwADxnyLkGwiOSQzxV9UkxdFz0SKPMPaCEo3ANVUTnfDID",
+        ),
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_vectors() {
+        assert!(KEY_VECTORS.iter().all(KeyVector::is_consistent));
+    }
+
+    #[test]
+    fn test_proof_vectors() {
+        for vector in PROOF_VECTORS {
+            assert!(vector.verify().is_ok(), "{}", vector.name);
+        }
+
+        // every proof type has a vector
+        assert_eq!(
+            PROOF_VECTORS.len(),
+            ProofType::BatchedGroupedCiphertext3HandlesValidity as usize
+        );
+
+        // a vector with a modified statement fails to verify
+        let mut bytes = PROOF_VECTORS[0].proof_data_bytes().unwrap();
+        bytes[0] ^= 1;
+        assert!(verify_as::<ZeroCiphertextProofData>(&bytes).is_err());
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_unstable_proof_vectors() {
+        for vector in UNSTABLE_PROOF_VECTORS {
+            assert!(vector.verify().is_ok(), "{}", vector.name);
+        }
+
+        // every unstable proof type has a vector
+        assert_eq!(
+            PROOF_VECTORS.len() + UNSTABLE_PROOF_VECTORS.len(),
+            ProofType::CiphertextCommitmentOpeningEquality as usize
+        );
+    }
+}
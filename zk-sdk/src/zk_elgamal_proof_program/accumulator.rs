@@ -0,0 +1,260 @@
+//! An accumulator for the verification equations of many proofs.
+//!
+//! A [`VerificationAccumulator`] collects the multiscalar multiplication terms of the
+//! verification equations of any number of proofs, of the same or of different types, and checks
+//! all of them with a single multiscalar multiplication when it is finalized. Unlike
+//! [`CombinedProofVerifier`](crate::zk_elgamal_proof_program::CombinedProofVerifier), which writes
+//! to caller-provided storage of a fixed capacity, the accumulator grows as proofs are appended.
+//!
+//! The terms of each proof are scaled by a random weight before they are accumulated, so the
+//! combined equation holds only if every individual equation holds, except with negligible
+//! probability. The weights are sampled by the verifier from a random number generator after the
+//! proof is appended and are never derived from the transcript of the proof. A weight that is a
+//! function of the proof could be ground by a prover who searches for invalid proofs whose
+//! equations cancel out in the combined check.
+
+use {
+    crate::{
+        msm::{MultiscalarMulBackend, MultiscalarMulBuffer},
+        rng::{try_random_scalar, verifier_weight, CryptoRngCore},
+        zk_elgamal_proof_program::{errors::ProofVerificationError, AppendVerificationTerms},
+    },
+    curve25519_dalek::{
+        ristretto::RistrettoPoint,
+        scalar::Scalar,
+        traits::{Identity, IsIdentity},
+    },
+};
+
+/// An accumulator for the verification equations of many proofs.
+#[derive(Clone, Debug, Default)]
+pub struct VerificationAccumulator {
+    scalars: Vec<Scalar>,
+    points: Vec<RistrettoPoint>,
+    num_proofs: usize,
+}
+
+impl VerificationAccumulator {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty accumulator with room for `num_terms` terms.
+    pub fn with_capacity(num_terms: usize) -> Self {
+        Self {
+            scalars: Vec::with_capacity(num_terms),
+            points: Vec::with_capacity(num_terms),
+            num_proofs: 0,
+        }
+    }
+
+    /// The number of proofs in the accumulator.
+    pub fn num_proofs(&self) -> usize {
+        self.num_proofs
+    }
+
+    /// The number of multiscalar multiplication terms in the accumulator.
+    pub fn num_terms(&self) -> usize {
+        self.scalars.len()
+    }
+
+    /// Returns `true` if the accumulator contains no proofs.
+    pub fn is_empty(&self) -> bool {
+        self.num_proofs == 0
+    }
+
+    /// Appends the verification equation of `proof_data`.
+    ///
+    /// The checks of the proof that do not depend on the multiscalar multiplication, such as the
+    /// validation of the proof context, are performed immediately. If a check fails, the
+    /// accumulator is left unchanged.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the weight of the
+    /// equation.
+    pub fn append<T: AppendVerificationTerms>(
+        &mut self,
+        proof_data: &T,
+    ) -> Result<(), ProofVerificationError> {
+        self.append_with_weight(proof_data, || Ok(verifier_weight()))
+    }
+
+    /// Appends the verification equation of `proof_data`, using `rng` to generate the weight of
+    /// the equation.
+    ///
+    /// See [`Self::append`] for details.
+    pub fn append_with_rng<T: AppendVerificationTerms>(
+        &mut self,
+        proof_data: &T,
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<(), ProofVerificationError> {
+        self.append_with_weight(proof_data, || Ok(try_random_scalar(rng)?))
+    }
+
+    fn append_with_weight<T: AppendVerificationTerms>(
+        &mut self,
+        proof_data: &T,
+        weight: impl FnOnce() -> Result<Scalar, ProofVerificationError>,
+    ) -> Result<(), ProofVerificationError> {
+        let start = self.scalars.len();
+        let end = start + T::MAX_VERIFICATION_TERMS;
+        self.scalars.resize(end, Scalar::ZERO);
+        self.points.resize(end, RistrettoPoint::identity());
+
+        let mut buffer =
+            MultiscalarMulBuffer::new(&mut self.scalars[start..], &mut self.points[start..]);
+        let appended = proof_data
+            .append_verification_terms(&mut buffer, None)
+            .and_then(|()| {
+                let weight = weight()?;
+                buffer.scale_from(0, &weight);
+                Ok(buffer.len())
+            });
+
+        match appended {
+            Ok(num_terms) => {
+                self.scalars.truncate(start + num_terms);
+                self.points.truncate(start + num_terms);
+                self.num_proofs += 1;
+                Ok(())
+            }
+            Err(err) => {
+                self.scalars.truncate(start);
+                self.points.truncate(start);
+                Err(err)
+            }
+        }
+    }
+
+    /// Checks the combined verification equation of all appended proofs with a single
+    /// multiscalar multiplication computed by `backend`.
+    ///
+    /// An empty accumulator is trivially valid.
+    pub fn finalize(
+        self,
+        backend: &dyn MultiscalarMulBackend,
+    ) -> Result<(), ProofVerificationError> {
+        if backend
+            .multiscalar_mul(&self.scalars, &self.points)
+            .is_identity()
+        {
+            Ok(())
+        } else {
+            Err(ProofVerificationError::CombinedAlgebraicRelation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::{
+                elgamal::ElGamalKeypair, grouped_elgamal::GroupedElGamal, pedersen::Pedersen,
+            },
+            msm::CpuMultiscalarMulBackend,
+            zk_elgamal_proof_program::{
+                build_batched_grouped_ciphertext_3_handles_validity_proof_data,
+                build_batched_range_proof_u64_data,
+                build_ciphertext_commitment_equality_proof_data,
+            },
+        },
+        solana_zk_elgamal_proof_interface::proof_data::CiphertextCommitmentEqualityProofData,
+        solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    };
+
+    fn build_equality_proof_data(amount: u64) -> CiphertextCommitmentEqualityProofData {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+        build_ciphertext_commitment_equality_proof_data(
+            &keypair,
+            &ciphertext,
+            &commitment,
+            &opening,
+            amount,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_accumulator_mixed_proof_types() {
+        let mut accumulator = VerificationAccumulator::new();
+        assert!(accumulator.is_empty());
+
+        accumulator.append(&build_equality_proof_data(55)).unwrap();
+
+        let keypairs = [
+            ElGamalKeypair::new_rand(),
+            ElGamalKeypair::new_rand(),
+            ElGamalKeypair::new_rand(),
+        ];
+        let pubkeys = keypairs.each_ref().map(ElGamalKeypair::pubkey);
+        let (amount_lo, amount_hi) = (11_u64, 22_u64);
+        let (commitment_lo, opening_lo) = Pedersen::new(amount_lo);
+        let (commitment_hi, opening_hi) = Pedersen::new(amount_hi);
+        let grouped_lo = GroupedElGamal::encrypt_with(pubkeys, amount_lo, &opening_lo);
+        let grouped_hi = GroupedElGamal::encrypt_with(pubkeys, amount_hi, &opening_hi);
+        let validity_proof_data = build_batched_grouped_ciphertext_3_handles_validity_proof_data(
+            pubkeys[0],
+            pubkeys[1],
+            pubkeys[2],
+            &grouped_lo,
+            &grouped_hi,
+            amount_lo,
+            amount_hi,
+            &opening_lo,
+            &opening_hi,
+        )
+        .unwrap();
+        accumulator.append(&validity_proof_data).unwrap();
+
+        let range_proof_data = build_batched_range_proof_u64_data(
+            vec![&commitment_lo, &commitment_hi],
+            vec![amount_lo, amount_hi],
+            vec![32, 32],
+            vec![&opening_lo, &opening_hi],
+        )
+        .unwrap();
+        accumulator.append(&range_proof_data).unwrap();
+
+        assert_eq!(accumulator.num_proofs(), 3);
+        assert!(accumulator.finalize(&CpuMultiscalarMulBackend).is_ok());
+    }
+
+    #[test]
+    fn test_accumulator_rejects_invalid_proof() {
+        let mut accumulator = VerificationAccumulator::new();
+        for amount in 0..3 {
+            accumulator
+                .append(&build_equality_proof_data(amount))
+                .unwrap();
+        }
+
+        let mut invalid_data = build_equality_proof_data(3);
+        invalid_data.context.ciphertext = build_equality_proof_data(4).context.ciphertext;
+        accumulator.append(&invalid_data).unwrap();
+
+        assert_eq!(
+            accumulator.finalize(&CpuMultiscalarMulBackend),
+            Err(ProofVerificationError::CombinedAlgebraicRelation)
+        );
+    }
+
+    #[test]
+    fn test_accumulator_failed_append_is_discarded() {
+        let mut accumulator = VerificationAccumulator::new();
+        accumulator.append(&build_equality_proof_data(1)).unwrap();
+        let num_terms = accumulator.num_terms();
+
+        // a proof with a malformed context is rejected before it is accumulated
+        let mut malformed_data = build_equality_proof_data(2);
+        malformed_data.context.pubkey = PodElGamalPubkey::default();
+        assert!(accumulator.append(&malformed_data).is_err());
+
+        assert_eq!(accumulator.num_proofs(), 1);
+        assert_eq!(accumulator.num_terms(), num_terms);
+        assert!(accumulator.finalize(&CpuMultiscalarMulBackend).is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+//! Proofs about multi-asset confidential amounts.
+//!
+//! Amounts of a multi-asset pool are committed with respect to an [`AssetTag`] as the value
+//! generator. A range proof about such commitments is a batched range proof with respect to the
+//! Pedersen generators of the tag, and an [`AssetEqualityProof`] certifies that two tags blind the
+//! same asset. Neither proof reveals the asset.
+//!
+//! These proofs are not supported by the ZK ElGamal proof program and must be verified by the
+//! application that holds the pool.
+
+pub use crate::sigma_proofs::{
+    asset_equality::AssetEqualityProof, errors::AssetEqualityProofVerificationError,
+};
+use crate::{
+    encryption::{
+        asset::AssetTag,
+        pedersen::{PedersenCommitment, PedersenOpening},
+    },
+    zk_elgamal_proof_program::{
+        errors::{ProofGenerationError, ProofVerificationError},
+        BatchedRangeProofData, BatchedRangeProofVerifyOptions,
+    },
+};
+
+/// Generates a batched range proof about amounts that are committed with respect to an asset
+/// tag.
+///
+/// The arguments are as in [`BatchedRangeProofData::build`]. The proof can only be verified with
+/// [`verify_asset_range_proof_data`] under the same tag.
+pub fn build_asset_range_proof_data<T: BatchedRangeProofData>(
+    tag: &AssetTag,
+    commitments: Vec<&PedersenCommitment>,
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+) -> Result<T, ProofGenerationError> {
+    T::build_with_generators(
+        commitments,
+        amounts,
+        bit_lengths,
+        openings,
+        &tag.pedersen_generators(),
+    )
+}
+
+/// Verifies a batched range proof about amounts that are committed with respect to an asset tag.
+pub fn verify_asset_range_proof_data<T: BatchedRangeProofData>(
+    tag: &AssetTag,
+    proof_data: &T,
+) -> Result<(), ProofVerificationError> {
+    proof_data.verify_proof_with_options(&BatchedRangeProofVerifyOptions {
+        pedersen_generators: Some(&tag.pedersen_generators()),
+        ..BatchedRangeProofVerifyOptions::default()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::asset::{AssetId, ASSET_ID_LEN},
+            zk_elgamal_proof_program::VerifyZkProof,
+        },
+        solana_zk_elgamal_proof_interface::proof_data::BatchedRangeProofU64Data,
+    };
+
+    #[test]
+    fn test_asset_range_proof_data() {
+        let asset_id = AssetId([1; ASSET_ID_LEN]);
+        let (tag, _) = AssetTag::new_rand(&asset_id);
+        let (commitment_1, opening_1) = tag.commit(55_u64);
+        let (commitment_2, opening_2) = tag.commit(77_u64);
+
+        let proof_data: BatchedRangeProofU64Data = build_asset_range_proof_data(
+            &tag,
+            vec![&commitment_1, &commitment_2],
+            vec![55, 77],
+            vec![32, 32],
+            vec![&opening_1, &opening_2],
+        )
+        .unwrap();
+        assert!(verify_asset_range_proof_data(&tag, &proof_data).is_ok());
+        assert!(proof_data.verify_proof().is_err());
+
+        // the proof is bound to the tag, even a tag of the same asset
+        let (other_tag, _) = AssetTag::new_rand(&asset_id);
+        assert!(verify_asset_range_proof_data(&other_tag, &proof_data).is_err());
+    }
+}
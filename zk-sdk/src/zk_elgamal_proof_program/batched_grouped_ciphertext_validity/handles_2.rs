@@ -30,6 +30,33 @@ pub fn build_batched_grouped_ciphertext_2_handles_validity_proof_data(
     amount_hi: u64,
     opening_lo: &PedersenOpening,
     opening_hi: &PedersenOpening,
+) -> Result<BatchedGroupedCiphertext2HandlesValidityProofData, ProofGenerationError> {
+    build_batched_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+        first_pubkey,
+        second_pubkey,
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+        amount_lo,
+        amount_hi,
+        opening_lo,
+        opening_hi,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_batched_grouped_ciphertext_2_handles_validity_proof_data`]
+/// with `statement_context`, if any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_batched_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext_lo: &GroupedElGamalCiphertext<2>,
+    grouped_ciphertext_hi: &GroupedElGamalCiphertext<2>,
+    amount_lo: u64,
+    amount_hi: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+    statement_context: Option<&[u8]>,
 ) -> Result<BatchedGroupedCiphertext2HandlesValidityProofData, ProofGenerationError> {
     let expected_lo =
         GroupedElGamal::encrypt_with([first_pubkey, second_pubkey], amount_lo, opening_lo);
@@ -43,22 +70,113 @@ pub fn build_batched_grouped_ciphertext_2_handles_validity_proof_data(
         return Err(ProofGenerationError::InconsistentInput);
     }
 
-    let pod_first_pubkey = PodElGamalPubkey(first_pubkey.into());
-    let pod_second_pubkey = PodElGamalPubkey(second_pubkey.into());
-    let pod_grouped_ciphertext_lo = (*grouped_ciphertext_lo).into();
-    let pod_grouped_ciphertext_hi = (*grouped_ciphertext_hi).into();
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"batched-grouped-ciphertext-validity-2-handles-instruction",
+        statement_context,
+    );
 
-    let context = BatchedGroupedCiphertext2HandlesValidityProofContext {
-        first_pubkey: pod_first_pubkey,
-        second_pubkey: pod_second_pubkey,
-        grouped_ciphertext_lo: pod_grouped_ciphertext_lo,
-        grouped_ciphertext_hi: pod_grouped_ciphertext_hi,
-    };
+    Ok(prove_batched_grouped_ciphertext_2_handles_validity(
+        first_pubkey,
+        second_pubkey,
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+        amount_lo,
+        amount_hi,
+        opening_lo,
+        opening_hi,
+        &mut transcript,
+    ))
+}
+
+/// The secret inputs of a single transfer in
+/// [`build_batched_grouped_ciphertext_2_handles_validity_proof_data_for_transfers`].
+pub struct BatchedValidityProofEntry<'a> {
+    /// The low bits of the transfer amount.
+    pub amount_lo: u64,
+    /// The high bits of the transfer amount.
+    pub amount_hi: u64,
+    /// The public key of the transfer destination, which is used for the first handle.
+    pub destination_pubkey: &'a ElGamalPubkey,
+    /// The Pedersen opening of the low bits of the amount.
+    pub opening_lo: &'a PedersenOpening,
+    /// The Pedersen opening of the high bits of the amount.
+    pub opening_hi: &'a PedersenOpening,
+}
 
-    let mut transcript = Transcript::new_zk_elgamal_transcript(
+/// The grouped ciphertexts and validity proof data generated for a single transfer.
+pub struct BatchedValidityProofOutput {
+    pub grouped_ciphertext_lo: GroupedElGamalCiphertext<2>,
+    pub grouped_ciphertext_hi: GroupedElGamalCiphertext<2>,
+    pub proof_data: BatchedGroupedCiphertext2HandlesValidityProofData,
+}
+
+/// Encrypts the amounts of a list of transfers and generates their batched grouped ciphertext
+/// validity proof data in one call.
+///
+/// The first handle of each grouped ciphertext pertains to the destination of the transfer and
+/// the second handle to `second_pubkey` (typically an auditor), which is shared by all transfers.
+/// The outputs are returned in the order of `entries`.
+///
+/// Since the ciphertexts are generated from the openings, they are consistent by construction and
+/// are not re-checked as in [`build_batched_grouped_ciphertext_2_handles_validity_proof_data`].
+/// The initial transcript is computed once and cloned for each proof.
+pub fn build_batched_grouped_ciphertext_2_handles_validity_proof_data_for_transfers(
+    second_pubkey: &ElGamalPubkey,
+    entries: &[BatchedValidityProofEntry],
+) -> Vec<BatchedValidityProofOutput> {
+    let initial_transcript = Transcript::new_zk_elgamal_transcript(
         b"batched-grouped-ciphertext-validity-2-handles-instruction",
     );
 
+    let mut outputs = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let pubkeys = [entry.destination_pubkey, second_pubkey];
+        let grouped_ciphertext_lo =
+            GroupedElGamal::encrypt_with(pubkeys, entry.amount_lo, entry.opening_lo);
+        let grouped_ciphertext_hi =
+            GroupedElGamal::encrypt_with(pubkeys, entry.amount_hi, entry.opening_hi);
+
+        let mut transcript = initial_transcript.clone();
+        let proof_data = prove_batched_grouped_ciphertext_2_handles_validity(
+            entry.destination_pubkey,
+            second_pubkey,
+            &grouped_ciphertext_lo,
+            &grouped_ciphertext_hi,
+            entry.amount_lo,
+            entry.amount_hi,
+            entry.opening_lo,
+            entry.opening_hi,
+            &mut transcript,
+        );
+
+        outputs.push(BatchedValidityProofOutput {
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            proof_data,
+        });
+    }
+    outputs
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prove_batched_grouped_ciphertext_2_handles_validity(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext_lo: &GroupedElGamalCiphertext<2>,
+    grouped_ciphertext_hi: &GroupedElGamalCiphertext<2>,
+    amount_lo: u64,
+    amount_hi: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+    transcript: &mut Transcript,
+) -> BatchedGroupedCiphertext2HandlesValidityProofData {
+    let context = BatchedGroupedCiphertext2HandlesValidityProofContext {
+        first_pubkey: PodElGamalPubkey(first_pubkey.into()),
+        second_pubkey: PodElGamalPubkey(second_pubkey.into()),
+        grouped_ciphertext_lo: (*grouped_ciphertext_lo).into(),
+        grouped_ciphertext_hi: (*grouped_ciphertext_hi).into(),
+    };
+
     let proof = BatchedGroupedCiphertext2HandlesValidityProof::new(
         first_pubkey,
         second_pubkey,
@@ -68,17 +186,25 @@ pub fn build_batched_grouped_ciphertext_2_handles_validity_proof_data(
         amount_hi,
         opening_lo,
         opening_hi,
-        &mut transcript,
+        transcript,
     )
     .into();
 
-    Ok(BatchedGroupedCiphertext2HandlesValidityProofData { context, proof })
+    BatchedGroupedCiphertext2HandlesValidityProofData { context, proof }
 }
 
 impl VerifyZkProof for BatchedGroupedCiphertext2HandlesValidityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
             b"batched-grouped-ciphertext-validity-2-handles-instruction",
+            statement_context,
         );
 
         let first_pubkey = self.context.first_pubkey.try_into()?;
@@ -155,4 +281,68 @@ mod test {
         );
         assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
     }
+
+    #[test]
+    fn test_batched_validity_proof_data_for_transfers() {
+        let auditor_keypair = ElGamalKeypair::new_rand();
+        let destination_keypairs = (0..3)
+            .map(|_| ElGamalKeypair::new_rand())
+            .collect::<Vec<_>>();
+        let openings = (0..6)
+            .map(|_| PedersenOpening::new_rand())
+            .collect::<Vec<_>>();
+
+        let entries = destination_keypairs
+            .iter()
+            .zip(openings.chunks(2))
+            .enumerate()
+            .map(|(i, (keypair, openings))| BatchedValidityProofEntry {
+                amount_lo: i as u64,
+                amount_hi: 100 + i as u64,
+                destination_pubkey: keypair.pubkey(),
+                opening_lo: &openings[0],
+                opening_hi: &openings[1],
+            })
+            .collect::<Vec<_>>();
+
+        let outputs = build_batched_grouped_ciphertext_2_handles_validity_proof_data_for_transfers(
+            auditor_keypair.pubkey(),
+            &entries,
+        );
+        assert_eq!(outputs.len(), entries.len());
+
+        for ((output, entry), keypair) in outputs.iter().zip(&entries).zip(&destination_keypairs) {
+            assert!(output.proof_data.verify_proof().is_ok());
+            assert_eq!(
+                output
+                    .grouped_ciphertext_lo
+                    .to_elgamal_ciphertext(0)
+                    .unwrap()
+                    .decrypt_u32(keypair.secret()),
+                Some(entry.amount_lo)
+            );
+            assert_eq!(
+                output
+                    .grouped_ciphertext_hi
+                    .to_elgamal_ciphertext(1)
+                    .unwrap()
+                    .decrypt_u32(auditor_keypair.secret()),
+                Some(entry.amount_hi)
+            );
+
+            // the batch output matches the single-shot builder
+            let single = build_batched_grouped_ciphertext_2_handles_validity_proof_data(
+                entry.destination_pubkey,
+                auditor_keypair.pubkey(),
+                &output.grouped_ciphertext_lo,
+                &output.grouped_ciphertext_hi,
+                entry.amount_lo,
+                entry.amount_hi,
+                entry.opening_lo,
+                entry.opening_hi,
+            )
+            .unwrap();
+            assert_eq!(single.context, output.proof_data.context);
+        }
+    }
 }
@@ -4,12 +4,14 @@ use {
             elgamal::ElGamalPubkey,
             grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
             pedersen::PedersenOpening,
+            role::{AuditorElGamalPubkey, ReceiverElGamalPubkey, SenderElGamalPubkey},
         },
+        msm::MultiscalarMulBuffer,
         sigma_proofs::batched_grouped_ciphertext_validity::BatchedGroupedCiphertext3HandlesValidityProof,
         transcript::TranscriptProtocol,
         zk_elgamal_proof_program::{
             errors::{ProofGenerationError, ProofVerificationError},
-            VerifyZkProof,
+            AppendVerificationTerms, VerifyZkProof,
         },
     },
     merlin::Transcript,
@@ -31,6 +33,35 @@ pub fn build_batched_grouped_ciphertext_3_handles_validity_proof_data(
     amount_hi: u64,
     opening_lo: &PedersenOpening,
     opening_hi: &PedersenOpening,
+) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
+    build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+        first_pubkey,
+        second_pubkey,
+        third_pubkey,
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+        amount_lo,
+        amount_hi,
+        opening_lo,
+        opening_hi,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_batched_grouped_ciphertext_3_handles_validity_proof_data`]
+/// with `statement_context`, if any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    third_pubkey: &ElGamalPubkey,
+    grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
+    grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
+    amount_lo: u64,
+    amount_hi: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+    statement_context: Option<&[u8]>,
 ) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
     let expected_lo = GroupedElGamal::encrypt_with(
         [first_pubkey, second_pubkey, third_pubkey],
@@ -64,8 +95,9 @@ pub fn build_batched_grouped_ciphertext_3_handles_validity_proof_data(
         grouped_ciphertext_hi: pod_grouped_ciphertext_hi,
     };
 
-    let mut transcript = Transcript::new_zk_elgamal_transcript(
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
         b"batched-grouped-ciphertext-validity-3-handles-instruction",
+        statement_context,
     );
 
     let proof = BatchedGroupedCiphertext3HandlesValidityProof::new(
@@ -85,10 +117,48 @@ pub fn build_batched_grouped_ciphertext_3_handles_validity_proof_data(
     Ok(BatchedGroupedCiphertext3HandlesValidityProofData { context, proof })
 }
 
+/// Generates the batched validity proof data for the grouped ciphertexts of the low and high bits
+/// of a transfer amount.
+///
+/// The handles of the grouped ciphertexts pertain to the sender, the receiver, and the auditor, in
+/// that order.
+#[allow(clippy::too_many_arguments)]
+pub fn build_batched_transfer_ciphertext_validity_proof_data(
+    sender_pubkey: &SenderElGamalPubkey,
+    receiver_pubkey: &ReceiverElGamalPubkey,
+    auditor_pubkey: &AuditorElGamalPubkey,
+    grouped_ciphertext_lo: &GroupedElGamalCiphertext<3>,
+    grouped_ciphertext_hi: &GroupedElGamalCiphertext<3>,
+    amount_lo: u64,
+    amount_hi: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+) -> Result<BatchedGroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
+    build_batched_grouped_ciphertext_3_handles_validity_proof_data(
+        sender_pubkey.pubkey(),
+        receiver_pubkey.pubkey(),
+        auditor_pubkey.pubkey(),
+        grouped_ciphertext_lo,
+        grouped_ciphertext_hi,
+        amount_lo,
+        amount_hi,
+        opening_lo,
+        opening_hi,
+    )
+}
+
 impl VerifyZkProof for BatchedGroupedCiphertext3HandlesValidityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
             b"batched-grouped-ciphertext-validity-3-handles-instruction",
+            statement_context,
         );
 
         let first_pubkey = self.context.first_pubkey.try_into()?;
@@ -114,6 +184,44 @@ impl VerifyZkProof for BatchedGroupedCiphertext3HandlesValidityProofData {
     }
 }
 
+impl AppendVerificationTerms for BatchedGroupedCiphertext3HandlesValidityProofData {
+    const MAX_VERIFICATION_TERMS: usize =
+        BatchedGroupedCiphertext3HandlesValidityProof::VERIFICATION_TERMS;
+
+    fn append_verification_terms(
+        &self,
+        buffer: &mut MultiscalarMulBuffer,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"batched-grouped-ciphertext-validity-3-handles-instruction",
+            statement_context,
+        );
+
+        let first_pubkey = self.context.first_pubkey.try_into()?;
+        let second_pubkey = self.context.second_pubkey.try_into()?;
+        let third_pubkey = self.context.third_pubkey.try_into()?;
+        let grouped_ciphertext_lo: GroupedElGamalCiphertext<3> =
+            self.context.grouped_ciphertext_lo.try_into()?;
+        let grouped_ciphertext_hi: GroupedElGamalCiphertext<3> =
+            self.context.grouped_ciphertext_hi.try_into()?;
+
+        let proof: BatchedGroupedCiphertext3HandlesValidityProof = self.proof.try_into()?;
+
+        proof
+            .append_verification_terms(
+                &first_pubkey,
+                &second_pubkey,
+                &third_pubkey,
+                &grouped_ciphertext_lo,
+                &grouped_ciphertext_hi,
+                &mut transcript,
+                buffer,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
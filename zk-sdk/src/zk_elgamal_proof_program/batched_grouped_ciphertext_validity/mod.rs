@@ -1,7 +1,18 @@
 mod handles_2;
 mod handles_3;
 
+pub(crate) use {
+    handles_2::build_batched_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context,
+    handles_3::build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context,
+};
 pub use {
-    handles_2::build_batched_grouped_ciphertext_2_handles_validity_proof_data,
-    handles_3::build_batched_grouped_ciphertext_3_handles_validity_proof_data,
+    handles_2::{
+        build_batched_grouped_ciphertext_2_handles_validity_proof_data,
+        build_batched_grouped_ciphertext_2_handles_validity_proof_data_for_transfers,
+        BatchedValidityProofEntry, BatchedValidityProofOutput,
+    },
+    handles_3::{
+        build_batched_grouped_ciphertext_3_handles_validity_proof_data,
+        build_batched_transfer_ciphertext_validity_proof_data,
+    },
 };
@@ -1,14 +1,17 @@
 use {
     crate::{
-        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        encryption::pedersen::{PedersenCommitment, PedersenGenerators, PedersenOpening},
+        msm::MultiscalarMulBuffer,
         range_proof::range::RangeProof,
         zk_elgamal_proof_program::{
             batched_range_proof::{
-                batched_range_proof_transcript, build_batched_range_proof_context,
-                verify_batched_range_proof_context, MAX_COMMITMENTS,
+                append_batched_range_proof_verification_terms, batched_range_proof_transcript,
+                build_batched_range_proof_context, prove_batched_range_proof,
+                verify_batched_range_proof_data, BatchedRangeProofData,
+                BatchedRangeProofVerifyOptions, MAX_COMMITMENTS,
             },
             errors::{ProofGenerationError, ProofVerificationError},
-            VerifyZkProof,
+            AppendVerificationTerms, VerifyZkProof,
         },
     },
     solana_zk_elgamal_proof_interface::proof_data::BatchedRangeProofU128Data,
@@ -20,6 +23,42 @@ pub fn build_batched_range_proof_u128_data(
     amounts: Vec<u64>,
     bit_lengths: Vec<usize>,
     openings: Vec<&PedersenOpening>,
+) -> Result<BatchedRangeProofU128Data, ProofGenerationError> {
+    build_batched_range_proof_u128_data_with_statement_context(
+        commitments,
+        amounts,
+        bit_lengths,
+        openings,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_batched_range_proof_u128_data`] with `statement_context`, if
+/// any, bound into the transcript of the proof.
+pub(crate) fn build_batched_range_proof_u128_data_with_statement_context(
+    commitments: Vec<&PedersenCommitment>,
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+    statement_context: Option<&[u8]>,
+) -> Result<BatchedRangeProofU128Data, ProofGenerationError> {
+    build_data(
+        commitments,
+        amounts,
+        bit_lengths,
+        openings,
+        None,
+        statement_context,
+    )
+}
+
+fn build_data(
+    commitments: Vec<&PedersenCommitment>,
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+    pedersen_generators: Option<&PedersenGenerators>,
+    statement_context: Option<&[u8]>,
 ) -> Result<BatchedRangeProofU128Data, ProofGenerationError> {
     // the sum of the bit lengths must be 128
     let batched_bit_length = bit_lengths
@@ -38,39 +77,98 @@ pub fn build_batched_range_proof_u128_data(
     let context =
         build_batched_range_proof_context(&commitments, &amounts, &bit_lengths, &openings)?;
 
-    let mut transcript = batched_range_proof_transcript(&context);
-    let proof = RangeProof::new(amounts, bit_lengths, openings, &mut transcript)?
-        .try_into()
-        .map_err(|_| ProofGenerationError::ProofLength)?;
+    let mut transcript = batched_range_proof_transcript(&context, statement_context);
+    let proof = prove_batched_range_proof(
+        amounts,
+        bit_lengths,
+        openings,
+        pedersen_generators,
+        &mut transcript,
+    )?
+    .try_into()
+    .map_err(|_| ProofGenerationError::ProofLength)?;
 
     Ok(BatchedRangeProofU128Data { context, proof })
 }
 
+impl BatchedRangeProofData for BatchedRangeProofU128Data {
+    const BATCHED_BIT_LENGTH: usize = 128;
+
+    fn build(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+    ) -> Result<Self, ProofGenerationError> {
+        build_batched_range_proof_u128_data(commitments, amounts, bit_lengths, openings)
+    }
+
+    fn build_with_generators(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        pedersen_generators: &PedersenGenerators,
+    ) -> Result<Self, ProofGenerationError> {
+        build_data(
+            commitments,
+            amounts,
+            bit_lengths,
+            openings,
+            Some(pedersen_generators),
+            None,
+        )
+    }
+
+    fn verify_proof_with_options(
+        &self,
+        options: &BatchedRangeProofVerifyOptions,
+    ) -> Result<(), ProofVerificationError> {
+        let proof: RangeProof = self.proof.try_into()?;
+        verify_batched_range_proof_data(
+            &self.context,
+            &proof,
+            <Self as BatchedRangeProofData>::BATCHED_BIT_LENGTH,
+            options,
+        )
+    }
+}
+
 impl VerifyZkProof for BatchedRangeProofU128Data {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let (commitments, bit_lengths) = verify_batched_range_proof_context(&self.context)?;
-        let num_commitments = commitments.len();
-
-        if num_commitments > MAX_COMMITMENTS {
-            return Err(ProofVerificationError::IllegalCommitmentLength);
-        }
+        self.verify_proof_with_statement_context(None)
+    }
 
-        let batched_bit_length = bit_lengths
-            .iter()
-            .try_fold(0_usize, |acc, &x| acc.checked_add(x))
-            .ok_or(ProofVerificationError::ProofContext)?;
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_options(&BatchedRangeProofVerifyOptions {
+            statement_context,
+            ..BatchedRangeProofVerifyOptions::default()
+        })
+    }
+}
 
-        let expected_bit_length = usize::try_from(u128::BITS).unwrap();
-        if batched_bit_length != expected_bit_length {
-            return Err(ProofVerificationError::IllegalCommitmentLength);
-        }
+impl AppendVerificationTerms for BatchedRangeProofU128Data {
+    const MAX_VERIFICATION_TERMS: usize = RangeProof::verification_terms(
+        <Self as BatchedRangeProofData>::BATCHED_BIT_LENGTH,
+        MAX_COMMITMENTS,
+    );
 
-        let mut transcript = batched_range_proof_transcript(&self.context);
+    fn append_verification_terms(
+        &self,
+        buffer: &mut MultiscalarMulBuffer,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
         let proof: RangeProof = self.proof.try_into()?;
-
-        proof
-            .verify(commitments.iter().collect(), bit_lengths, &mut transcript)
-            .map_err(|e| e.into())
+        append_batched_range_proof_verification_terms(
+            &self.context,
+            &proof,
+            <Self as BatchedRangeProofData>::BATCHED_BIT_LENGTH,
+            buffer,
+            statement_context,
+        )
     }
 }
 
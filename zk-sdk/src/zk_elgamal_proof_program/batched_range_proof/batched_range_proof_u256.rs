@@ -1,11 +1,12 @@
 use {
     crate::{
-        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        encryption::pedersen::{PedersenCommitment, PedersenGenerators, PedersenOpening},
         range_proof::range::RangeProof,
         zk_elgamal_proof_program::{
             batched_range_proof::{
                 batched_range_proof_transcript, build_batched_range_proof_context,
-                verify_batched_range_proof_context, MAX_COMMITMENTS, MAX_SINGLE_BIT_LENGTH,
+                prove_batched_range_proof, verify_batched_range_proof_data, BatchedRangeProofData,
+                BatchedRangeProofVerifyOptions, MAX_SINGLE_BIT_LENGTH,
             },
             errors::{ProofGenerationError, ProofVerificationError},
             VerifyZkProof,
@@ -22,10 +23,46 @@ pub fn build_batched_range_proof_u256_data(
     amounts: Vec<u64>,
     bit_lengths: Vec<usize>,
     openings: Vec<&PedersenOpening>,
+) -> Result<BatchedRangeProofU256Data, ProofGenerationError> {
+    build_batched_range_proof_u256_data_with_statement_context(
+        commitments,
+        amounts,
+        bit_lengths,
+        openings,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_batched_range_proof_u256_data`] with `statement_context`, if
+/// any, bound into the transcript of the proof.
+pub(crate) fn build_batched_range_proof_u256_data_with_statement_context(
+    commitments: Vec<&PedersenCommitment>,
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+    statement_context: Option<&[u8]>,
+) -> Result<BatchedRangeProofU256Data, ProofGenerationError> {
+    build_data(
+        commitments,
+        amounts,
+        bit_lengths,
+        openings,
+        None,
+        statement_context,
+    )
+}
+
+fn build_data(
+    commitments: Vec<&PedersenCommitment>,
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+    pedersen_generators: Option<&PedersenGenerators>,
+    statement_context: Option<&[u8]>,
 ) -> Result<BatchedRangeProofU256Data, ProofGenerationError> {
     // Range proof on 256 bit length could potentially result in an unexpected behavior and
-    // therefore, restrict the bit length to be at most 128. This check is not needed for the
-    // `BatchedRangeProofU64` or `BatchedRangeProofU128`.
+    // therefore, restrict the bit length to be at most 64. This check is not needed for the
+    // `BatchedRangeProofU64` or `BatchedRangeProofU128`, whose total bit length is at most 128.
     if bit_lengths
         .iter()
         .any(|length| *length > MAX_SINGLE_BIT_LENGTH)
@@ -45,38 +82,76 @@ pub fn build_batched_range_proof_u256_data(
     let context =
         build_batched_range_proof_context(&commitments, &amounts, &bit_lengths, &openings)?;
 
-    let mut transcript = batched_range_proof_transcript(&context);
-    let proof = RangeProof::new(amounts, bit_lengths, openings, &mut transcript)?
-        .try_into()
-        .map_err(|_| ProofGenerationError::ProofLength)?;
+    let mut transcript = batched_range_proof_transcript(&context, statement_context);
+    let proof = prove_batched_range_proof(
+        amounts,
+        bit_lengths,
+        openings,
+        pedersen_generators,
+        &mut transcript,
+    )?
+    .try_into()
+    .map_err(|_| ProofGenerationError::ProofLength)?;
 
     Ok(BatchedRangeProofU256Data { context, proof })
 }
 
-impl VerifyZkProof for BatchedRangeProofU256Data {
-    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let (commitments, bit_lengths) = verify_batched_range_proof_context(&self.context)?;
-        let num_commitments = commitments.len();
+impl BatchedRangeProofData for BatchedRangeProofU256Data {
+    const BATCHED_BIT_LENGTH: usize = BATCHED_RANGE_PROOF_U256_BIT_LENGTH;
 
-        if num_commitments > MAX_COMMITMENTS {
-            return Err(ProofVerificationError::IllegalCommitmentLength);
-        }
-
-        let batched_bit_length = bit_lengths
-            .iter()
-            .try_fold(0_usize, |acc, &x| acc.checked_add(x))
-            .ok_or(ProofVerificationError::ProofContext)?;
+    fn build(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+    ) -> Result<Self, ProofGenerationError> {
+        build_batched_range_proof_u256_data(commitments, amounts, bit_lengths, openings)
+    }
 
-        if batched_bit_length != BATCHED_RANGE_PROOF_U256_BIT_LENGTH {
-            return Err(ProofVerificationError::IllegalCommitmentLength);
-        }
+    fn build_with_generators(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        pedersen_generators: &PedersenGenerators,
+    ) -> Result<Self, ProofGenerationError> {
+        build_data(
+            commitments,
+            amounts,
+            bit_lengths,
+            openings,
+            Some(pedersen_generators),
+            None,
+        )
+    }
 
-        let mut transcript = batched_range_proof_transcript(&self.context);
+    fn verify_proof_with_options(
+        &self,
+        options: &BatchedRangeProofVerifyOptions,
+    ) -> Result<(), ProofVerificationError> {
         let proof: RangeProof = self.proof.try_into()?;
+        verify_batched_range_proof_data(
+            &self.context,
+            &proof,
+            <Self as BatchedRangeProofData>::BATCHED_BIT_LENGTH,
+            options,
+        )
+    }
+}
+
+impl VerifyZkProof for BatchedRangeProofU256Data {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
 
-        proof
-            .verify(commitments.iter().collect(), bit_lengths, &mut transcript)
-            .map_err(|e| e.into())
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_options(&BatchedRangeProofVerifyOptions {
+            statement_context,
+            ..BatchedRangeProofVerifyOptions::default()
+        })
     }
 }
 
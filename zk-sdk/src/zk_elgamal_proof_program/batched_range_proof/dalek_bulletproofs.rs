@@ -0,0 +1,207 @@
+//! Range proofs in the format of the dalek-cryptography `bulletproofs` crate.
+//!
+//! The proofs that are generated and verified by the functions in this module use the same
+//! transcript, generators, and serialization as `RangeProof::prove_multiple` and
+//! `RangeProof::verify_multiple` in the `bulletproofs` crate with the default `PedersenGens` and
+//! `BulletproofGens`. Deployments of the `bulletproofs` crate can therefore migrate to this crate
+//! while proofs generated by either implementation continue to verify on the other. The
+//! transcript must be initialized identically on both sides.
+//!
+//! These proofs are not batched range proof instructions, and they are rejected by the ZK ElGamal
+//! proof program.
+
+use {
+    crate::{
+        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        range_proof::range::RangeProof,
+        zk_elgamal_proof_program::errors::{ProofGenerationError, ProofVerificationError},
+    },
+    merlin::Transcript,
+};
+
+/// Generates a range proof for a set of amounts in the format of the `bulletproofs` crate.
+///
+/// All amounts have the same bit length, which must be 8, 16, 32, or 64, and the number of
+/// amounts must be a power of two. The proof is returned in the serialization of the
+/// `bulletproofs` crate.
+pub fn build_dalek_bulletproofs_range_proof(
+    amounts: &[u64],
+    bit_length: usize,
+    openings: &[&PedersenOpening],
+    transcript: &mut Transcript,
+) -> Result<Vec<u8>, ProofGenerationError> {
+    let proof = RangeProof::new_dalek_bulletproofs(
+        amounts.to_vec(),
+        bit_length,
+        openings.to_vec(),
+        transcript,
+    )?;
+    Ok(proof.to_bytes())
+}
+
+/// Verifies a range proof in the format of the `bulletproofs` crate.
+///
+/// The commitments must be in the same order as the amounts of the proof, and all amounts are
+/// certified to be in the range of `bit_length` bits.
+pub fn verify_dalek_bulletproofs_range_proof(
+    proof: &[u8],
+    commitments: &[PedersenCommitment],
+    bit_length: usize,
+    transcript: &mut Transcript,
+) -> Result<(), ProofVerificationError> {
+    let proof = RangeProof::from_bytes(proof)?;
+    proof
+        .verify_dalek_bulletproofs(commitments.iter().collect(), bit_length, transcript)
+        .map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{encryption::pedersen::Pedersen, range_proof::errors::RangeProofVerificationError},
+        bulletproofs::{BulletproofGens, PedersenGens},
+        curve25519_dalek::ristretto::CompressedRistretto,
+    };
+
+    // the bit lengths and numbers of amounts of the cross-verification with `bulletproofs`
+    const DALEK_BULLETPROOFS_PARAMETERS: [(usize, usize); 3] = [(32, 2), (64, 1), (8, 4)];
+
+    #[test]
+    fn test_dalek_bulletproofs_range_proof_correctness() {
+        let amounts = [55_u64, u32::MAX.into()];
+        let (commitments, openings): (Vec<_>, Vec<_>) =
+            amounts.iter().map(|amount| Pedersen::new(*amount)).unzip();
+
+        let mut transcript = Transcript::new(b"Test");
+        let proof = build_dalek_bulletproofs_range_proof(
+            &amounts,
+            32,
+            &openings.iter().collect::<Vec<_>>(),
+            &mut transcript,
+        )
+        .unwrap();
+
+        // the proof has the size of a `bulletproofs` proof for 2 values of 32 bits
+        assert_eq!(proof.len(), (2 * 6 + 9) * 32);
+
+        let mut transcript = Transcript::new(b"Test");
+        assert!(
+            verify_dalek_bulletproofs_range_proof(&proof, &commitments, 32, &mut transcript)
+                .is_ok()
+        );
+
+        // the proof is bound to the bit length and the commitments
+        let mut transcript = Transcript::new(b"Test");
+        assert!(
+            verify_dalek_bulletproofs_range_proof(&proof, &commitments, 64, &mut transcript)
+                .is_err()
+        );
+
+        let mut transcript = Transcript::new(b"Test");
+        assert!(verify_dalek_bulletproofs_range_proof(
+            &proof,
+            &[commitments[1], commitments[0]],
+            32,
+            &mut transcript
+        )
+        .is_err());
+
+        // the proof does not verify in the native transcript format
+        let range_proof = RangeProof::from_bytes(&proof).unwrap();
+        let mut transcript = Transcript::new(b"Test");
+        assert!(range_proof
+            .verify(commitments.iter().collect(), vec![32, 32], &mut transcript)
+            .is_err());
+
+        // unsupported bit lengths and numbers of amounts are rejected
+        let mut transcript = Transcript::new(b"Test");
+        assert_eq!(
+            verify_dalek_bulletproofs_range_proof(&proof, &commitments, 48, &mut transcript)
+                .unwrap_err(),
+            ProofVerificationError::RangeProof(RangeProofVerificationError::InvalidBitSize)
+        );
+        assert!(build_dalek_bulletproofs_range_proof(
+            &amounts[..1],
+            12,
+            &[&openings[0]],
+            &mut Transcript::new(b"Test"),
+        )
+        .is_err());
+        assert!(build_dalek_bulletproofs_range_proof(
+            &[1, 2, 3],
+            8,
+            &[&openings[0], &openings[0], &openings[0]],
+            &mut Transcript::new(b"Test"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_dalek_bulletproofs_range_proof_verifies_with_bulletproofs() {
+        for (bit_length, num_amounts) in DALEK_BULLETPROOFS_PARAMETERS {
+            let amounts: Vec<u64> = (0..num_amounts as u64).map(|i| 1 << (i + 3)).collect();
+            let (commitments, openings): (Vec<_>, Vec<_>) =
+                amounts.iter().map(|amount| Pedersen::new(*amount)).unzip();
+
+            let proof = build_dalek_bulletproofs_range_proof(
+                &amounts,
+                bit_length,
+                &openings.iter().collect::<Vec<_>>(),
+                &mut Transcript::new(b"Test"),
+            )
+            .unwrap();
+
+            let proof = bulletproofs::RangeProof::from_bytes(&proof).unwrap();
+            let commitments: Vec<_> = commitments
+                .iter()
+                .map(|commitment| CompressedRistretto(commitment.to_bytes()))
+                .collect();
+            assert!(proof
+                .verify_multiple(
+                    &BulletproofGens::new(bit_length, num_amounts),
+                    &PedersenGens::default(),
+                    &mut Transcript::new(b"Test"),
+                    &commitments,
+                    bit_length,
+                )
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_bulletproofs_range_proof_verifies_with_dalek_bulletproofs() {
+        for (bit_length, num_amounts) in DALEK_BULLETPROOFS_PARAMETERS {
+            let amounts: Vec<u64> = (0..num_amounts as u64).map(|i| 1 << (i + 3)).collect();
+            let openings: Vec<_> = amounts
+                .iter()
+                .map(|_| PedersenOpening::new_rand())
+                .collect();
+
+            let (proof, commitments) = bulletproofs::RangeProof::prove_multiple(
+                &BulletproofGens::new(bit_length, num_amounts),
+                &PedersenGens::default(),
+                &mut Transcript::new(b"Test"),
+                &amounts,
+                &openings
+                    .iter()
+                    .map(|opening| *opening.get_scalar())
+                    .collect::<Vec<_>>(),
+                bit_length,
+            )
+            .unwrap();
+
+            let commitments: Vec<_> = commitments
+                .iter()
+                .map(|commitment| PedersenCommitment::from_bytes(commitment.as_bytes()).unwrap())
+                .collect();
+            assert!(verify_dalek_bulletproofs_range_proof(
+                &proof.to_bytes(),
+                &commitments,
+                bit_length,
+                &mut Transcript::new(b"Test"),
+            )
+            .is_ok());
+        }
+    }
+}
@@ -1,30 +1,311 @@
 pub mod batched_range_proof_u128;
 pub mod batched_range_proof_u256;
 pub mod batched_range_proof_u64;
+#[cfg(feature = "dalek-bulletproofs-compat")]
+pub mod dalek_bulletproofs;
 
+#[cfg(feature = "dalek-bulletproofs-compat")]
+pub use dalek_bulletproofs::*;
+pub use {
+    crate::range_proof::{
+        errors::RangeProofGeneratorError,
+        generators::{
+            configure_range_proof_generators, warm_up_range_proof_generators, BulletproofGens,
+            DEFAULT_GENERATORS_BIT_CAPACITY, DEFAULT_GENERATORS_PARTY_CAPACITY,
+        },
+        range::RangeProofVerifierContext,
+    },
+    batched_range_proof_u128::*,
+    batched_range_proof_u256::*,
+    batched_range_proof_u64::*,
+};
 use {
     crate::{
-        encryption::pedersen::{PedersenCommitment, PedersenOpening},
+        amount::BoundedAmount,
+        encryption::pedersen::{PedersenCommitment, PedersenGenerators, PedersenOpening, G},
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBackend, MultiscalarMulBuffer},
+        range_proof::{
+            errors::RangeProofGenerationError,
+            range::{padding_commitment, padding_opening, RangeProof},
+        },
         transcript::TranscriptProtocol,
-        zk_elgamal_proof_program::errors::{ProofGenerationError, ProofVerificationError},
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            VerifyZkProof,
+        },
     },
     bytemuck::{bytes_of, Zeroable},
-    curve25519_dalek::traits::IsIdentity,
+    curve25519_dalek::{scalar::Scalar, traits::IsIdentity},
     merlin::Transcript,
-    solana_zk_elgamal_proof_interface::proof_data::{BatchedRangeProofContext, MAX_COMMITMENTS},
+    solana_zk_elgamal_proof_interface::proof_data::{
+        BatchedRangeProofContext, BatchedRangeProofU64Data, MAX_COMMITMENTS,
+    },
     solana_zk_sdk_pod::encryption::pedersen::PodPedersenCommitment,
     std::convert::TryInto,
 };
-pub use {batched_range_proof_u128::*, batched_range_proof_u256::*, batched_range_proof_u64::*};
+
+/// A batched range proof instruction data with a fixed total bit length.
+///
+/// This trait allows protocols to generate a batched range proof without committing to a specific
+/// `BatchedRangeProof{N}` instruction at the call site.
+pub trait BatchedRangeProofData: Sized {
+    /// The total bit length that the batched range proof certifies.
+    const BATCHED_BIT_LENGTH: usize;
+
+    /// Generates the batched range proof instruction data.
+    fn build(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+    ) -> Result<Self, ProofGenerationError>;
+
+    /// Generates the batched range proof instruction data for amounts with statically known bit
+    /// lengths.
+    ///
+    /// Each amount is proven to lie in the range of its own bit length, so the bit lengths of the
+    /// amounts must sum to [`Self::BATCHED_BIT_LENGTH`].
+    fn build_from_amounts(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: &[&dyn BoundedAmount],
+        openings: Vec<&PedersenOpening>,
+    ) -> Result<Self, ProofGenerationError> {
+        let bit_lengths = amounts.iter().map(|amount| amount.bit_length()).collect();
+        let amounts = amounts.iter().map(|amount| amount.value()).collect();
+        Self::build(commitments, amounts, bit_lengths, openings)
+    }
+
+    /// Generates the batched range proof instruction data with respect to custom Pedersen
+    /// generators.
+    ///
+    /// The commitments must be computed with respect to the same generators. The resulting
+    /// proof data verifies only with [`Self::verify_proof_with_options`] with the same
+    /// [`BatchedRangeProofVerifyOptions::pedersen_generators`], and in particular, it is rejected
+    /// by the ZK ElGamal proof program.
+    fn build_with_generators(
+        commitments: Vec<&PedersenCommitment>,
+        amounts: Vec<u64>,
+        bit_lengths: Vec<usize>,
+        openings: Vec<&PedersenOpening>,
+        pedersen_generators: &PedersenGenerators,
+    ) -> Result<Self, ProofGenerationError>;
+
+    /// Verifies batched range proof instruction data with the specified options.
+    ///
+    /// With the default options, this is the same as [`VerifyZkProof::verify_proof`].
+    fn verify_proof_with_options(
+        &self,
+        options: &BatchedRangeProofVerifyOptions,
+    ) -> Result<(), ProofVerificationError>;
+}
+
+/// Options for the verification of batched range proof instruction data with
+/// [`BatchedRangeProofData::verify_proof_with_options`].
+///
+/// A verifier context computes the multiscalar multiplication of the verification with its own
+/// precomputed tables and with respect to its own Pedersen generators, so it cannot be combined
+/// with `pedersen_generators` or `backend`. Such a combination is rejected with
+/// [`ProofVerificationError::ProofContext`].
+#[derive(Clone, Copy, Default)]
+pub struct BatchedRangeProofVerifyOptions<'a> {
+    /// The statement context that was bound into the transcript of the proof, if any
+    pub statement_context: Option<&'a [u8]>,
+    /// The custom Pedersen generators of proof data that was generated with
+    /// [`BatchedRangeProofData::build_with_generators`]
+    pub pedersen_generators: Option<&'a PedersenGenerators>,
+    /// The backend of the multiscalar multiplication of the verification in place of
+    /// [`CpuMultiscalarMulBackend`]
+    pub backend: Option<&'a dyn MultiscalarMulBackend>,
+    /// A verifier context whose bit lengths must match the bit lengths of the proof context
+    ///
+    /// Verifiers that check many proofs with the same bit lengths can reuse a single verifier
+    /// context to avoid recomputing the generator terms of the verification equation.
+    pub verifier_context: Option<&'a RangeProofVerifierContext>,
+}
+
+/// Splits a 128-bit amount into limbs with the specified bit lengths.
+///
+/// The limbs are ordered from the least significant to the most significant. Each limb must be
+/// at most 64 bits and the amount must be representable with the sum of the limb bit lengths.
+pub fn split_u128_amount(
+    amount: u128,
+    limb_bit_lengths: &[usize],
+) -> Result<Vec<u64>, ProofGenerationError> {
+    let mut remaining = amount;
+    let mut limbs = Vec::with_capacity(limb_bit_lengths.len());
+    for bit_length in limb_bit_lengths {
+        if *bit_length == 0 || *bit_length > MAX_SINGLE_BIT_LENGTH {
+            return Err(ProofGenerationError::IllegalAmountBitLength);
+        }
+        let mask = u128::from(u64::MAX) >> (MAX_SINGLE_BIT_LENGTH - bit_length);
+        // the limb is masked to at most 64 bits and therefore, the conversion is lossless
+        limbs.push((remaining & mask) as u64);
+        remaining = remaining.checked_shr(*bit_length as u32).unwrap_or(0);
+    }
+
+    if remaining != 0 {
+        return Err(ProofGenerationError::IllegalAmountBitLength);
+    }
+    Ok(limbs)
+}
+
+/// Generates a single batched range proof for the limbs of a set of 128-bit split amounts.
+///
+/// Each amount is split into limbs according to `limb_bit_lengths`. The `commitments` and
+/// `openings` vectors must contain the limb commitments and openings of all amounts, ordered by
+/// amount and then from the least significant to the most significant limb. The total bit length
+/// of all limbs must match the bit length of the batched range proof `T`.
+pub fn build_batched_range_proof_split_u128_data<T: BatchedRangeProofData>(
+    commitments: Vec<&PedersenCommitment>,
+    amounts: &[u128],
+    limb_bit_lengths: &[usize],
+    openings: Vec<&PedersenOpening>,
+) -> Result<T, ProofGenerationError> {
+    let mut limbs = Vec::with_capacity(amounts.len() * limb_bit_lengths.len());
+    let mut bit_lengths = Vec::with_capacity(amounts.len() * limb_bit_lengths.len());
+    for amount in amounts {
+        limbs.extend(split_u128_amount(*amount, limb_bit_lengths)?);
+        bit_lengths.extend_from_slice(limb_bit_lengths);
+    }
+
+    T::build(commitments, limbs, bit_lengths, openings)
+}
+
+/// The bit length of the range `[-2^31, 2^31)` of signed amounts that can be certified by a
+/// signed amount range proof.
+///
+/// This is the range that `ElGamalCiphertext::decrypt_signed` decodes, so every certified amount
+/// can be decrypted.
+pub const SIGNED_AMOUNT_BIT_LENGTH: usize = 32;
+
+/// The offset that maps the signed range `[-2^31, 2^31)` to the unsigned range `[0, 2^32)`.
+const SIGNED_AMOUNT_OFFSET: u64 = 1 << (SIGNED_AMOUNT_BIT_LENGTH - 1);
+
+/// Computes the commitment that a signed amount range proof certifies for a signed amount
+/// commitment.
+///
+/// A signed amount `x` is certified to be in the range `[-2^31, 2^31)` by proving that
+/// `x + 2^31` is a 32-bit number. The offset commitment is computed homomorphically and has the
+/// same opening as the original commitment.
+fn signed_amount_offset_commitment(commitment: &PedersenCommitment) -> PedersenCommitment {
+    PedersenCommitment::new(commitment.get_point() + Scalar::from(SIGNED_AMOUNT_OFFSET) * &G)
+}
+
+/// Generates a batched range proof certifying that a commitment to a signed amount encodes a
+/// value in the range `[-2^31, 2^31)`.
+///
+/// The commitment must be a commitment to the signed amount as produced by
+/// `ElGamalPubkey::encrypt_signed`. The proof is generated on the offset commitment along with a
+/// 32-bit padding commitment so that it can be verified as a `BatchedRangeProofU64` instruction.
+/// Verifiers must check the proof context with [`verify_signed_amount_range_proof`].
+pub fn build_signed_amount_range_proof_data(
+    commitment: &PedersenCommitment,
+    amount: i64,
+    opening: &PedersenOpening,
+) -> Result<BatchedRangeProofU64Data, ProofGenerationError> {
+    build_signed_amount_range_proof_data_with_statement_context(commitment, amount, opening, None)
+}
+
+/// Generates the proof data of [`build_signed_amount_range_proof_data`] with `statement_context`,
+/// if any, bound into the transcript of the proof.
+///
+/// Verifiers must check the proof with [`verify_signed_amount_range_proof`] for the same statement
+/// context.
+pub(crate) fn build_signed_amount_range_proof_data_with_statement_context(
+    commitment: &PedersenCommitment,
+    amount: i64,
+    opening: &PedersenOpening,
+    statement_context: Option<&[u8]>,
+) -> Result<BatchedRangeProofU64Data, ProofGenerationError> {
+    let offset_amount = amount
+        .checked_add_unsigned(SIGNED_AMOUNT_OFFSET)
+        .and_then(|offset_amount| u64::try_from(offset_amount).ok())
+        .filter(|offset_amount| *offset_amount >> SIGNED_AMOUNT_BIT_LENGTH == 0)
+        .ok_or(ProofGenerationError::IllegalAmountBitLength)?;
+
+    let offset_commitment = signed_amount_offset_commitment(commitment);
+    let padding_commitment = padding_commitment();
+    let padding_opening = padding_opening();
+
+    build_batched_range_proof_u64_data_with_statement_context(
+        vec![&offset_commitment, &padding_commitment],
+        vec![offset_amount, 0],
+        vec![
+            SIGNED_AMOUNT_BIT_LENGTH,
+            BatchedRangeProofU64Data::BATCHED_BIT_LENGTH - SIGNED_AMOUNT_BIT_LENGTH,
+        ],
+        vec![opening, &padding_opening],
+        statement_context,
+    )
+}
+
+/// Verifies that a batched range proof certifies that a commitment to a signed amount encodes a
+/// value in the range `[-2^31, 2^31)`.
+///
+/// In addition to verifying the proof itself, the function checks that the proof context
+/// consists of exactly the offset commitment and the padding commitment that are produced by
+/// [`build_signed_amount_range_proof_data`]. A proof that is generated with a statement context
+/// verifies only with the same `statement_context`.
+pub fn verify_signed_amount_range_proof(
+    proof_data: &BatchedRangeProofU64Data,
+    commitment: &PedersenCommitment,
+    statement_context: Option<&[u8]>,
+) -> Result<(), ProofVerificationError> {
+    let (commitments, bit_lengths) = verify_batched_range_proof_context(&proof_data.context)?;
+
+    let expected_commitments = [
+        signed_amount_offset_commitment(commitment),
+        padding_commitment(),
+    ];
+    let expected_bit_lengths = [
+        SIGNED_AMOUNT_BIT_LENGTH,
+        BatchedRangeProofU64Data::BATCHED_BIT_LENGTH - SIGNED_AMOUNT_BIT_LENGTH,
+    ];
+    if commitments != expected_commitments || bit_lengths != expected_bit_lengths {
+        return Err(ProofVerificationError::ProofContext);
+    }
+
+    proof_data.verify_proof_with_statement_context(statement_context)
+}
 
 /// A bit length in a batched range proof must be at most 64.
 ///
 /// Although the batched proof supports a total of 256 bits, individual components are restricted
-/// to `u64` amounts (64 bits).
+/// to `u64` amounts (64 bits). The limit is checked both when the proof data is generated and
+/// when its context is decoded. It is lower than the limit of 128 bits of
+/// [`RangeProof::new_u128`] and [`RangeProof::new_padded`], which the batched range proof
+/// instructions do not expose.
 const MAX_SINGLE_BIT_LENGTH: usize = 64;
 
-pub(crate) fn batched_range_proof_transcript(context: &BatchedRangeProofContext) -> Transcript {
-    let mut transcript = Transcript::new_zk_elgamal_transcript(b"batched-range-proof-instruction");
+/// Generates a range proof for a batched range proof instruction, optionally with respect to
+/// custom Pedersen generators.
+pub(crate) fn prove_batched_range_proof(
+    amounts: Vec<u64>,
+    bit_lengths: Vec<usize>,
+    openings: Vec<&PedersenOpening>,
+    pedersen_generators: Option<&PedersenGenerators>,
+    transcript: &mut Transcript,
+) -> Result<RangeProof, RangeProofGenerationError> {
+    match pedersen_generators {
+        Some(pedersen_generators) => RangeProof::new_with_generators(
+            amounts.into_iter().map(u128::from).collect(),
+            bit_lengths,
+            openings,
+            pedersen_generators,
+            transcript,
+        ),
+        None => RangeProof::new(amounts, bit_lengths, openings, transcript),
+    }
+}
+
+pub(crate) fn batched_range_proof_transcript(
+    context: &BatchedRangeProofContext,
+    statement_context: Option<&[u8]>,
+) -> Transcript {
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"batched-range-proof-instruction",
+        statement_context,
+    );
     transcript.append_message(b"commitments", bytes_of(&context.commitments));
     transcript.append_message(b"bit-lengths", bytes_of(&context.bit_lengths));
     transcript
@@ -75,28 +356,59 @@ pub(crate) fn build_batched_range_proof_context(
 pub(crate) fn verify_batched_range_proof_context(
     context: &BatchedRangeProofContext,
 ) -> Result<(Vec<PedersenCommitment>, Vec<usize>), ProofVerificationError> {
-    let commitments = context
+    let statement = decode_batched_range_proof_context(context)?;
+    Ok((
+        statement.commitments().to_vec(),
+        statement.bit_lengths().to_vec(),
+    ))
+}
+
+/// The commitments and bit lengths of a batched range proof context.
+pub(crate) struct BatchedRangeProofStatement {
+    commitments: [PedersenCommitment; MAX_COMMITMENTS],
+    bit_lengths: [usize; MAX_COMMITMENTS],
+    len: usize,
+}
+
+impl BatchedRangeProofStatement {
+    pub(crate) fn commitments(&self) -> &[PedersenCommitment] {
+        &self.commitments[..self.len]
+    }
+
+    pub(crate) fn bit_lengths(&self) -> &[usize] {
+        &self.bit_lengths[..self.len]
+    }
+}
+
+/// Decodes and validates a batched range proof context without allocating.
+pub(crate) fn decode_batched_range_proof_context(
+    context: &BatchedRangeProofContext,
+) -> Result<BatchedRangeProofStatement, ProofVerificationError> {
+    let len = context
         .commitments
-        .into_iter()
-        .take_while(|commitment| *commitment != PodPedersenCommitment::zeroed())
-        .map(|commitment| commitment.try_into())
-        .collect::<Result<Vec<PedersenCommitment>, _>>()
-        .map_err(|_| ProofVerificationError::ProofContext)?;
-
-    let bit_lengths: Vec<_> = context
-        .bit_lengths
-        .into_iter()
-        .take(commitments.len())
-        .map(|bit_length| bit_length as usize)
-        .collect();
+        .iter()
+        .take_while(|commitment| **commitment != PodPedersenCommitment::zeroed())
+        .count();
 
     // Ensure at least one commitment exists
-    if commitments.is_empty() {
+    if len == 0 {
         return Err(ProofVerificationError::IllegalCommitmentLength);
     }
 
+    let mut commitments = [PedersenCommitment::default(); MAX_COMMITMENTS];
+    for (commitment, pod_commitment) in commitments.iter_mut().zip(&context.commitments[..len]) {
+        *commitment = (*pod_commitment)
+            .try_into()
+            .map_err(|_| ProofVerificationError::ProofContext)?;
+    }
+
+    let mut bit_lengths = [0; MAX_COMMITMENTS];
+    for (bit_length, pod_bit_length) in bit_lengths.iter_mut().zip(&context.bit_lengths[..len]) {
+        *bit_length = *pod_bit_length as usize;
+    }
+
     // Validate bit lengths (must be > 0 and <= MAX_SINGLE_BIT_LENGTH)
-    if bit_lengths
+    if bit_lengths[..len]
         .iter()
         .any(|&bit_length| bit_length == 0 || bit_length > MAX_SINGLE_BIT_LENGTH)
     {
@@ -104,7 +416,6 @@ pub(crate) fn verify_batched_range_proof_context(
     }
 
     // Ensure that all ignored data in the context (the "tail") is strictly zero.
-    let len = commitments.len();
     let commitments_padding_valid = context.commitments[len..]
         .iter()
         .all(|commitment| *commitment == PodPedersenCommitment::zeroed());
@@ -117,5 +428,398 @@ pub(crate) fn verify_batched_range_proof_context(
         return Err(ProofVerificationError::ProofContext);
     }
 
-    Ok((commitments, bit_lengths))
+    Ok(BatchedRangeProofStatement {
+        commitments,
+        bit_lengths,
+        len,
+    })
+}
+
+/// Appends the verification terms of a batched range proof whose bit lengths must sum to
+/// `batched_bit_length` to a multiscalar multiplication buffer.
+pub(crate) fn append_batched_range_proof_verification_terms(
+    context: &BatchedRangeProofContext,
+    proof: &RangeProof,
+    batched_bit_length: usize,
+    buffer: &mut MultiscalarMulBuffer,
+    statement_context: Option<&[u8]>,
+) -> Result<(), ProofVerificationError> {
+    let statement = decode_batched_range_proof_context(context)?;
+
+    let statement_bit_length = statement
+        .bit_lengths()
+        .iter()
+        .try_fold(0_usize, |acc, &x| acc.checked_add(x))
+        .ok_or(ProofVerificationError::ProofContext)?;
+    if statement_bit_length != batched_bit_length {
+        return Err(ProofVerificationError::IllegalCommitmentLength);
+    }
+
+    let mut commitments = [&statement.commitments[0]; MAX_COMMITMENTS];
+    for (commitment_ref, commitment) in commitments.iter_mut().zip(statement.commitments()) {
+        *commitment_ref = commitment;
+    }
+
+    let mut transcript = batched_range_proof_transcript(context, statement_context);
+    proof
+        .append_verification_terms(
+            &commitments[..statement.len],
+            statement.bit_lengths(),
+            &PedersenGenerators::default(),
+            &mut transcript,
+            buffer,
+        )
+        .map_err(|e| e.into())
+}
+
+/// Verifies a batched range proof whose bit lengths must sum to `batched_bit_length` with the
+/// specified options.
+pub(crate) fn verify_batched_range_proof_data(
+    context: &BatchedRangeProofContext,
+    proof: &RangeProof,
+    batched_bit_length: usize,
+    options: &BatchedRangeProofVerifyOptions,
+) -> Result<(), ProofVerificationError> {
+    let statement = decode_batched_range_proof_context(context)?;
+
+    let statement_bit_length = statement
+        .bit_lengths()
+        .iter()
+        .try_fold(0_usize, |acc, &x| acc.checked_add(x))
+        .ok_or(ProofVerificationError::ProofContext)?;
+    if statement_bit_length != batched_bit_length {
+        return Err(ProofVerificationError::IllegalCommitmentLength);
+    }
+
+    let commitments = statement.commitments().iter().collect();
+    let mut transcript = batched_range_proof_transcript(context, options.statement_context);
+    match options.verifier_context {
+        Some(verifier_context) => {
+            if options.pedersen_generators.is_some()
+                || options.backend.is_some()
+                || statement.bit_lengths() != verifier_context.bit_lengths()
+            {
+                return Err(ProofVerificationError::ProofContext);
+            }
+            proof.verify_with_context(commitments, verifier_context, &mut transcript)
+        }
+        None => {
+            let backend = options.backend.unwrap_or(&CpuMultiscalarMulBackend);
+            let bit_lengths = statement.bit_lengths().to_vec();
+            match options.pedersen_generators {
+                Some(pedersen_generators) => proof.verify_with_generators_and_backend(
+                    commitments,
+                    bit_lengths,
+                    pedersen_generators,
+                    backend,
+                    &mut transcript,
+                ),
+                None => {
+                    proof.verify_with_backend(commitments, bit_lengths, backend, &mut transcript)
+                }
+            }
+        }
+    }
+    .map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            amount::Amount,
+            encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+        },
+        solana_zk_elgamal_proof_interface::proof_data::{
+            BatchedRangeProofU128Data, BatchedRangeProofU256Data, BatchedRangeProofU64Data,
+        },
+    };
+
+    #[test]
+    fn test_split_u128_amount() {
+        let amount = (u128::from(u64::MAX) << 64) | 55;
+        assert_eq!(
+            split_u128_amount(amount, &[64, 64]).unwrap(),
+            vec![55, u64::MAX]
+        );
+        assert_eq!(
+            split_u128_amount(0x1234_5678, &[16, 48]).unwrap(),
+            vec![0x5678, 0x1234]
+        );
+        assert_eq!(
+            split_u128_amount(1 << 64, &[32, 32]).unwrap_err(),
+            ProofGenerationError::IllegalAmountBitLength
+        );
+        assert_eq!(
+            split_u128_amount(1, &[128]).unwrap_err(),
+            ProofGenerationError::IllegalAmountBitLength
+        );
+        assert_eq!(
+            split_u128_amount(1, &[0, 64]).unwrap_err(),
+            ProofGenerationError::IllegalAmountBitLength
+        );
+    }
+
+    #[test]
+    fn test_batched_range_proof_split_u128_correctness() {
+        let limb_bit_lengths = [16, 48, 64];
+        let amount_1 = u128::MAX;
+        let amount_2 = (1_u128 << 100) + 7;
+
+        let limbs: Vec<u64> = [amount_1, amount_2]
+            .iter()
+            .flat_map(|amount| split_u128_amount(*amount, &limb_bit_lengths).unwrap())
+            .collect();
+        let (commitments, openings): (Vec<_>, Vec<_>) =
+            limbs.iter().map(|limb| Pedersen::new(*limb)).unzip();
+
+        let proof_data = build_batched_range_proof_split_u128_data::<BatchedRangeProofU256Data>(
+            commitments.iter().collect(),
+            &[amount_1, amount_2],
+            &limb_bit_lengths,
+            openings.iter().collect(),
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        // the total bit length must match the batched range proof
+        assert_eq!(
+            build_batched_range_proof_split_u128_data::<BatchedRangeProofU128Data>(
+                commitments.iter().collect(),
+                &[amount_1, amount_2],
+                &limb_bit_lengths,
+                openings.iter().collect(),
+            )
+            .unwrap_err(),
+            ProofGenerationError::IllegalAmountBitLength
+        );
+    }
+
+    #[test]
+    fn test_batched_range_proof_from_amounts_correctness() {
+        let amount_1 = Amount::<16>::new(u16::MAX.into()).unwrap();
+        let amount_2 = Amount::<32>::new(77).unwrap();
+        let amount_3 = Amount::<16>::new(99).unwrap();
+
+        let (commitment_1, opening_1) = Pedersen::new(amount_1);
+        let (commitment_2, opening_2) = Pedersen::new(amount_2);
+        let (commitment_3, opening_3) = Pedersen::new(amount_3);
+
+        let proof_data = BatchedRangeProofU64Data::build_from_amounts(
+            vec![&commitment_1, &commitment_2, &commitment_3],
+            &[&amount_1, &amount_2, &amount_3],
+            vec![&opening_1, &opening_2, &opening_3],
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        // the bit lengths of the amounts must sum to the batched bit length
+        assert_eq!(
+            BatchedRangeProofU64Data::build_from_amounts(
+                vec![&commitment_1, &commitment_3],
+                &[&amount_1, &amount_3],
+                vec![&opening_1, &opening_3],
+            )
+            .unwrap_err(),
+            ProofGenerationError::IllegalAmountBitLength
+        );
+    }
+
+    #[test]
+    fn test_signed_amount_range_proof_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        for amount in [-55_i64, 0, 77, -(1 << 31), (1 << 31) - 1] {
+            let opening = PedersenOpening::new_rand();
+            let ciphertext = keypair.pubkey().encrypt_signed_with(amount, &opening);
+
+            // every amount that can be certified can be decrypted
+            assert_eq!(ciphertext.decrypt_signed(keypair.secret()), Some(amount));
+
+            let proof_data =
+                build_signed_amount_range_proof_data(&ciphertext.commitment, amount, &opening)
+                    .unwrap();
+            assert!(
+                verify_signed_amount_range_proof(&proof_data, &ciphertext.commitment, None).is_ok()
+            );
+
+            // the proof does not certify a different commitment
+            let other_ciphertext = keypair.pubkey().encrypt_signed_with(amount + 1, &opening);
+            assert_eq!(
+                verify_signed_amount_range_proof(&proof_data, &other_ciphertext.commitment, None)
+                    .unwrap_err(),
+                ProofVerificationError::ProofContext
+            );
+        }
+
+        // amounts outside of the range cannot be proven
+        for amount in [
+            1_i64 << 31,
+            -(1 << 31) - 1,
+            (1 << 47) - 1,
+            -(1 << 47) + 1,
+            i64::MIN,
+            i64::MAX,
+        ] {
+            let opening = PedersenOpening::new_rand();
+            let ciphertext = keypair.pubkey().encrypt_signed_with(amount, &opening);
+            assert_eq!(
+                build_signed_amount_range_proof_data(&ciphertext.commitment, amount, &opening)
+                    .unwrap_err(),
+                ProofGenerationError::IllegalAmountBitLength
+            );
+        }
+    }
+
+    #[test]
+    fn test_batched_range_proof_with_backend() {
+        use {
+            crate::msm::CpuMultiscalarMulBackend,
+            curve25519_dalek::ristretto::RistrettoPoint,
+            std::sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        #[derive(Default)]
+        struct CountingBackend(AtomicUsize);
+
+        impl MultiscalarMulBackend for CountingBackend {
+            fn multiscalar_mul(
+                &self,
+                scalars: &[Scalar],
+                points: &[RistrettoPoint],
+            ) -> RistrettoPoint {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                CpuMultiscalarMulBackend.multiscalar_mul(scalars, points)
+            }
+        }
+
+        let (commitment_1, opening_1) = Pedersen::new(55_u64);
+        let (commitment_2, opening_2) = Pedersen::new(77_u64);
+
+        let proof_data = BatchedRangeProofU128Data::build(
+            vec![&commitment_1, &commitment_2],
+            vec![55, 77],
+            vec![64, 64],
+            vec![&opening_1, &opening_2],
+        )
+        .unwrap();
+
+        let backend = CountingBackend::default();
+        let options = BatchedRangeProofVerifyOptions {
+            backend: Some(&backend),
+            ..BatchedRangeProofVerifyOptions::default()
+        };
+        assert!(proof_data.verify_proof_with_options(&options).is_ok());
+        assert_eq!(backend.0.load(Ordering::Relaxed), 1);
+
+        // an invalid proof is rejected by the backend computation
+        let mut invalid_proof_data = proof_data;
+        invalid_proof_data.context.commitments.swap(0, 1);
+        assert!(invalid_proof_data
+            .verify_proof_with_options(&options)
+            .is_err());
+        assert_eq!(backend.0.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_batched_range_proof_with_generators_correctness() {
+        let generators = PedersenGenerators::from_label(b"test-protocol");
+        let (commitment_1, opening_1) = generators.commit(55_u64);
+        let (commitment_2, opening_2) = generators.commit(77_u64);
+
+        let proof_data = BatchedRangeProofU128Data::build_with_generators(
+            vec![&commitment_1, &commitment_2],
+            vec![55, 77],
+            vec![64, 64],
+            vec![&opening_1, &opening_2],
+            &generators,
+        )
+        .unwrap();
+
+        let options = BatchedRangeProofVerifyOptions {
+            pedersen_generators: Some(&generators),
+            ..BatchedRangeProofVerifyOptions::default()
+        };
+        assert!(proof_data.verify_proof_with_options(&options).is_ok());
+
+        // the proof is bound to the generators that it was generated with
+        let other_generators = PedersenGenerators::from_label(b"other-protocol");
+        assert!(proof_data
+            .verify_proof_with_options(&BatchedRangeProofVerifyOptions {
+                pedersen_generators: Some(&other_generators),
+                ..BatchedRangeProofVerifyOptions::default()
+            })
+            .is_err());
+        assert!(proof_data.verify_proof().is_err());
+
+        // commitments with respect to the global generators do not verify
+        let (commitment_1, opening_1) = Pedersen::new(55_u64);
+        let (commitment_2, opening_2) = Pedersen::new(77_u64);
+
+        let proof_data = BatchedRangeProofU128Data::build_with_generators(
+            vec![&commitment_1, &commitment_2],
+            vec![55, 77],
+            vec![64, 64],
+            vec![&opening_1, &opening_2],
+            &generators,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof_with_options(&options).is_err());
+    }
+
+    #[test]
+    fn test_batched_range_proof_with_verifier_context() {
+        let bit_lengths = vec![32, 16, 16];
+        let verifier_context = RangeProofVerifierContext::new(bit_lengths.clone()).unwrap();
+        let options = BatchedRangeProofVerifyOptions {
+            verifier_context: Some(&verifier_context),
+            ..BatchedRangeProofVerifyOptions::default()
+        };
+
+        for amounts in [vec![55, 77, 99], vec![u32::MAX.into(), 0, u16::MAX.into()]] {
+            let (commitments, openings): (Vec<_>, Vec<_>) =
+                amounts.iter().map(|amount| Pedersen::new(*amount)).unzip();
+
+            let proof_data = BatchedRangeProofU64Data::build(
+                commitments.iter().collect(),
+                amounts,
+                bit_lengths.clone(),
+                openings.iter().collect(),
+            )
+            .unwrap();
+
+            assert!(proof_data.verify_proof_with_options(&options).is_ok());
+        }
+
+        // the bit lengths of the proof must match the verifier context
+        let (commitment_1, opening_1) = Pedersen::new(55_u64);
+        let (commitment_2, opening_2) = Pedersen::new(77_u64);
+        let proof_data = BatchedRangeProofU64Data::build(
+            vec![&commitment_1, &commitment_2],
+            vec![55, 77],
+            vec![32, 32],
+            vec![&opening_1, &opening_2],
+        )
+        .unwrap();
+        assert_eq!(
+            proof_data.verify_proof_with_options(&options).unwrap_err(),
+            ProofVerificationError::ProofContext
+        );
+
+        // a verifier context cannot be combined with custom generators
+        let generators = PedersenGenerators::from_label(b"test-protocol");
+        assert_eq!(
+            proof_data
+                .verify_proof_with_options(&BatchedRangeProofVerifyOptions {
+                    pedersen_generators: Some(&generators),
+                    ..options
+                })
+                .unwrap_err(),
+            ProofVerificationError::ProofContext
+        );
+    }
 }
@@ -0,0 +1,915 @@
+//! A builder for the proof data of the ZK ElGamal proof program.
+//!
+//! The `build_*_proof_data` functions take the public statement and the secret witness of a proof
+//! as a single list of positional arguments. [`ProofBuilder`] separates the two: the statement
+//! types in this module hold the public inputs that are recorded in the proof context and the
+//! witness types hold the secret inputs that are needed to generate the proof. Each statement type
+//! fixes its witness type and proof data type, so a builder that is missing its statement or its
+//! witness, or that pairs a statement with the wrong witness, fails to compile.
+//!
+//! Statements also have a canonical byte encoding, [`ProofStatement::to_statement_bytes`], that
+//! can be signed with an Ed25519 key so that off-chain agreements about what was proven can be
+//! committed to and audited.
+//!
+//! A statement context, if any, is bound into the proof through [`ProofBuilder::new`].
+//!
+//! ```
+//! use solana_zk_sdk::{
+//!     encryption::elgamal::ElGamalKeypair,
+//!     zk_elgamal_proof_program::{
+//!         builder::{ProofBuilder, ZeroCiphertextStatement, ZeroCiphertextWitness},
+//!         VerifyZkProof,
+//!     },
+//! };
+//!
+//! let keypair = ElGamalKeypair::new_rand();
+//! let ciphertext = keypair.pubkey().encrypt(0_u64);
+//!
+//! let proof_data = ProofBuilder::new(Some(b"example"))
+//!     .statement(ZeroCiphertextStatement {
+//!         pubkey: keypair.pubkey(),
+//!         ciphertext: &ciphertext,
+//!     })
+//!     .witness(ZeroCiphertextWitness { keypair: &keypair })
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(proof_data
+//!     .verify_proof_with_statement_context(Some(b"example"))
+//!     .is_ok());
+//! ```
+
+#[cfg(feature = "unstable")]
+use solana_zk_elgamal_proof_interface::proof_data::{
+    CiphertextCommitmentOpeningEqualityProofData,
+    GroupedCiphertext2HandlesCiphertextEqualityProofData,
+    GroupedCiphertext3HandlesCiphertextEqualityProofData, GroupedZeroCiphertext2HandlesProofData,
+    GroupedZeroCiphertext3HandlesProofData, PercentageWithCapSplitProofData,
+    PercentageWithCapU128ProofData, SplitConsistencyProofData,
+};
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+            grouped_elgamal::GroupedElGamalCiphertext,
+            pedersen::{PedersenCommitment, PedersenOpening},
+        },
+        zk_elgamal_proof_program::{errors::ProofGenerationError, *},
+        TRANSCRIPT_DOMAIN,
+    },
+    solana_address::Address,
+    solana_signature::Signature,
+    solana_signer::{Signer, SignerError},
+    solana_zk_elgamal_proof_interface::proof_data::{
+        BatchedGroupedCiphertext2HandlesValidityProofData,
+        BatchedGroupedCiphertext3HandlesValidityProofData, BatchedRangeProofU128Data,
+        BatchedRangeProofU256Data, BatchedRangeProofU64Data, CiphertextCiphertextEqualityProofData,
+        CiphertextCommitmentEqualityProofData, GroupedCiphertext2HandlesValidityProofData,
+        GroupedCiphertext3HandlesValidityProofData, PercentageWithCapProofData,
+        PubkeyValidityProofData, ZeroCiphertextProofData,
+    },
+};
+
+/// The public statement of a proof.
+pub trait ProofStatement {
+    /// The secret inputs that are needed to prove the statement.
+    type Witness;
+
+    /// The proof data that is generated for the statement.
+    type ProofData: VerifyZkProof;
+
+    /// Generates the proof data for the statement.
+    fn prove(self, witness: Self::Witness) -> Result<Self::ProofData, ProofGenerationError>
+    where
+        Self: Sized,
+    {
+        self.prove_with_statement_context(witness, None)
+    }
+
+    /// Generates the proof data for the statement with `statement_context`, if any, bound into
+    /// the transcript of the proof.
+    fn prove_with_statement_context(
+        self,
+        witness: Self::Witness,
+        statement_context: Option<&[u8]>,
+    ) -> Result<Self::ProofData, ProofGenerationError>;
+
+    /// Returns the canonical byte encoding of the statement.
+    ///
+    /// The encoding consists of the transcript domain and the name of the statement type, each
+    /// prefixed by its length, followed by the fields of the statement in declaration order.
+    /// Group elements are encoded in their compressed form, integers are encoded as little-endian
+    /// `u64` or `u128` values, and sequences are prefixed by their length.
+    fn to_statement_bytes(&self) -> Vec<u8>;
+
+    /// Signs the canonical encoding of the statement with `signer`.
+    fn sign_statement(&self, signer: &dyn Signer) -> Result<Signature, SignerError> {
+        signer.try_sign_message(&self.to_statement_bytes())
+    }
+
+    /// Verifies that `signature` is an Ed25519 signature of `pubkey` on the canonical encoding of
+    /// the statement.
+    fn verify_statement_signature(&self, pubkey: &Address, signature: &Signature) -> bool {
+        signature.verify(pubkey.as_ref(), &self.to_statement_bytes())
+    }
+}
+
+/// The canonical encoding of a statement field.
+trait EncodeStatementField {
+    fn encode(&self, bytes: &mut Vec<u8>);
+}
+
+impl<T: EncodeStatementField + ?Sized> EncodeStatementField for &T {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        (**self).encode(bytes)
+    }
+}
+
+impl<T: EncodeStatementField> EncodeStatementField for [T] {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.len().encode(bytes);
+        for element in self {
+            element.encode(bytes);
+        }
+    }
+}
+
+impl<T: EncodeStatementField> EncodeStatementField for Vec<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.as_slice().encode(bytes)
+    }
+}
+
+impl EncodeStatementField for u8 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self)
+    }
+}
+
+impl EncodeStatementField for usize {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        (*self as u64).encode(bytes)
+    }
+}
+
+impl EncodeStatementField for u64 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+}
+
+impl EncodeStatementField for u128 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes())
+    }
+}
+
+impl EncodeStatementField for ElGamalPubkey {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_bytes())
+    }
+}
+
+impl EncodeStatementField for ElGamalCiphertext {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_bytes())
+    }
+}
+
+impl<const N: usize> EncodeStatementField for GroupedElGamalCiphertext<N> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_bytes())
+    }
+}
+
+impl EncodeStatementField for PedersenCommitment {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_bytes())
+    }
+}
+
+fn encode_statement_header(name: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    TRANSCRIPT_DOMAIN.encode(&mut bytes);
+    name.as_bytes().encode(&mut bytes);
+    bytes
+}
+
+/// Marks a field of a [`ProofBuilder`] that has not been set yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Missing;
+
+/// A builder that generates the proof data for a statement and a witness.
+///
+/// The statement context of the builder, if any, is bound into the transcript of the proof, so
+/// the proof data verifies only with [`VerifyZkProof::verify_proof_with_statement_context`] for
+/// the same statement context.
+#[derive(Clone, Debug)]
+pub struct ProofBuilder<'d, S = Missing, W = Missing> {
+    statement_context: Option<&'d [u8]>,
+    statement: S,
+    witness: W,
+}
+
+impl<'d> ProofBuilder<'d> {
+    /// Creates a builder for proofs that are bound to `statement_context`, if any.
+    pub fn new(statement_context: Option<&'d [u8]>) -> Self {
+        Self {
+            statement_context,
+            statement: Missing,
+            witness: Missing,
+        }
+    }
+}
+
+impl<'d, W> ProofBuilder<'d, Missing, W> {
+    /// Sets the public statement to be proved.
+    pub fn statement<S: ProofStatement>(self, statement: S) -> ProofBuilder<'d, S, W> {
+        ProofBuilder {
+            statement_context: self.statement_context,
+            statement,
+            witness: self.witness,
+        }
+    }
+}
+
+impl<'d, S> ProofBuilder<'d, S, Missing> {
+    /// Sets the secret witness of the statement.
+    pub fn witness<W>(self, witness: W) -> ProofBuilder<'d, S, W> {
+        ProofBuilder {
+            statement_context: self.statement_context,
+            statement: self.statement,
+            witness,
+        }
+    }
+}
+
+impl<S: ProofStatement> ProofBuilder<'_, S, S::Witness> {
+    /// Generates the proof data for the statement.
+    pub fn build(self) -> Result<S::ProofData, ProofGenerationError> {
+        let Self {
+            statement_context,
+            statement,
+            witness,
+        } = self;
+        statement.prove_with_statement_context(witness, statement_context)
+    }
+}
+
+macro_rules! define_proof_statement {
+    (
+        $(#[$statement_meta:meta])*
+        $statement:ident { $($statement_field:ident: $statement_type:ty),* $(,)? }
+        $(#[$witness_meta:meta])*
+        $witness:ident { $($witness_field:ident: $witness_type:ty),* $(,)? }
+        $(where $pubkey:ident == $keypair:ident.pubkey(),)?
+        $proof_data:ty = $build:ident($($argument:expr),* $(,)?)
+    ) => {
+        $(#[$statement_meta])*
+        #[derive(Clone)]
+        pub struct $statement<'a> {
+            $(pub $statement_field: $statement_type,)*
+        }
+
+        $(#[$witness_meta])*
+        #[derive(Clone)]
+        pub struct $witness<'a> {
+            $(pub $witness_field: $witness_type,)*
+        }
+
+        impl<'a> ProofStatement for $statement<'a> {
+            type Witness = $witness<'a>;
+            type ProofData = $proof_data;
+
+            fn prove_with_statement_context(
+                self,
+                witness: Self::Witness,
+                statement_context: Option<&[u8]>,
+            ) -> Result<Self::ProofData, ProofGenerationError> {
+                let $statement { $($statement_field,)* } = self;
+                let $witness { $($witness_field,)* } = witness;
+                $(
+                    if $pubkey != $keypair.pubkey() {
+                        return Err(ProofGenerationError::InconsistentInput);
+                    }
+                )?
+                $build($($argument,)* statement_context)
+            }
+
+            fn to_statement_bytes(&self) -> Vec<u8> {
+                let mut bytes = encode_statement_header(stringify!($statement));
+                $(self.$statement_field.encode(&mut bytes);)*
+                bytes
+            }
+        }
+    };
+}
+
+define_proof_statement! {
+    /// The statement that an ElGamal public key is valid.
+    PubkeyValidityStatement { pubkey: &'a ElGamalPubkey }
+    /// The witness of a [`PubkeyValidityStatement`].
+    PubkeyValidityWitness { keypair: &'a ElGamalKeypair }
+    where pubkey == keypair.pubkey(),
+    PubkeyValidityProofData = build_pubkey_validity_proof_data_with_statement_context(keypair)
+}
+
+define_proof_statement! {
+    /// The statement that an ElGamal ciphertext encrypts zero.
+    ZeroCiphertextStatement {
+        pubkey: &'a ElGamalPubkey,
+        ciphertext: &'a ElGamalCiphertext,
+    }
+    /// The witness of a [`ZeroCiphertextStatement`].
+    ZeroCiphertextWitness { keypair: &'a ElGamalKeypair }
+    where pubkey == keypair.pubkey(),
+    ZeroCiphertextProofData =
+        build_zero_ciphertext_proof_data_with_statement_context(keypair, ciphertext)
+}
+
+define_proof_statement! {
+    /// The statement that an ElGamal ciphertext and a Pedersen commitment hold the same message.
+    CiphertextCommitmentEqualityStatement {
+        pubkey: &'a ElGamalPubkey,
+        ciphertext: &'a ElGamalCiphertext,
+        commitment: &'a PedersenCommitment,
+    }
+    /// The witness of a [`CiphertextCommitmentEqualityStatement`].
+    CiphertextCommitmentEqualityWitness {
+        keypair: &'a ElGamalKeypair,
+        opening: &'a PedersenOpening,
+        amount: u64,
+    }
+    where pubkey == keypair.pubkey(),
+    CiphertextCommitmentEqualityProofData =
+        build_ciphertext_commitment_equality_proof_data_with_statement_context(
+            keypair, ciphertext, commitment, opening, amount,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that an ElGamal ciphertext and a Pedersen commitment hold the same message,
+    /// proved from their openings rather than from the decryption key.
+    CiphertextCommitmentOpeningEqualityStatement {
+        pubkey: &'a ElGamalPubkey,
+        ciphertext: &'a ElGamalCiphertext,
+        commitment: &'a PedersenCommitment,
+    }
+    /// The witness of a [`CiphertextCommitmentOpeningEqualityStatement`].
+    CiphertextCommitmentOpeningEqualityWitness {
+        ciphertext_opening: &'a PedersenOpening,
+        commitment_opening: &'a PedersenOpening,
+        amount: u64,
+    }
+    CiphertextCommitmentOpeningEqualityProofData =
+        build_ciphertext_commitment_opening_equality_proof_data_with_statement_context(
+            pubkey,
+            ciphertext,
+            commitment,
+            ciphertext_opening,
+            commitment_opening,
+            amount,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that two ElGamal ciphertexts encrypt the same message.
+    CiphertextCiphertextEqualityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        first_ciphertext: &'a ElGamalCiphertext,
+        second_ciphertext: &'a ElGamalCiphertext,
+    }
+    /// The witness of a [`CiphertextCiphertextEqualityStatement`].
+    CiphertextCiphertextEqualityWitness {
+        first_keypair: &'a ElGamalKeypair,
+        second_opening: &'a PedersenOpening,
+        amount: u64,
+    }
+    where first_pubkey == first_keypair.pubkey(),
+    CiphertextCiphertextEqualityProofData =
+        build_ciphertext_ciphertext_equality_proof_data_with_statement_context(
+            first_keypair,
+            second_pubkey,
+            first_ciphertext,
+            second_ciphertext,
+            second_opening,
+            amount,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that a handle of a grouped ElGamal ciphertext with 2 handles and an ElGamal
+    /// ciphertext encrypt the same message.
+    GroupedCiphertext2HandlesCiphertextEqualityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<2>,
+        handle_index: usize,
+        second_ciphertext: &'a ElGamalCiphertext,
+    }
+    /// The witness of a [`GroupedCiphertext2HandlesCiphertextEqualityStatement`].
+    GroupedCiphertext2HandlesCiphertextEqualityWitness {
+        first_keypair: &'a ElGamalKeypair,
+        second_opening: &'a PedersenOpening,
+        amount: u64,
+    }
+    where first_pubkey == first_keypair.pubkey(),
+    GroupedCiphertext2HandlesCiphertextEqualityProofData =
+        build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data_with_statement_context(
+            first_keypair,
+            second_pubkey,
+            grouped_ciphertext,
+            handle_index,
+            second_ciphertext,
+            second_opening,
+            amount,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that a handle of a grouped ElGamal ciphertext with 3 handles and an ElGamal
+    /// ciphertext encrypt the same message.
+    GroupedCiphertext3HandlesCiphertextEqualityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<3>,
+        handle_index: usize,
+        second_ciphertext: &'a ElGamalCiphertext,
+    }
+    /// The witness of a [`GroupedCiphertext3HandlesCiphertextEqualityStatement`].
+    GroupedCiphertext3HandlesCiphertextEqualityWitness {
+        first_keypair: &'a ElGamalKeypair,
+        second_opening: &'a PedersenOpening,
+        amount: u64,
+    }
+    where first_pubkey == first_keypair.pubkey(),
+    GroupedCiphertext3HandlesCiphertextEqualityProofData =
+        build_grouped_ciphertext_3_handles_ciphertext_equality_proof_data_with_statement_context(
+            first_keypair,
+            second_pubkey,
+            grouped_ciphertext,
+            handle_index,
+            second_ciphertext,
+            second_opening,
+            amount,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that a grouped ElGamal ciphertext with 2 handles is well-formed.
+    GroupedCiphertext2HandlesValidityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<2>,
+    }
+    /// The witness of a [`GroupedCiphertext2HandlesValidityStatement`].
+    GroupedCiphertext2HandlesValidityWitness {
+        amount: u64,
+        opening: &'a PedersenOpening,
+    }
+    GroupedCiphertext2HandlesValidityProofData =
+        build_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that a grouped ElGamal ciphertext with 3 handles is well-formed.
+    GroupedCiphertext3HandlesValidityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        third_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<3>,
+    }
+    /// The witness of a [`GroupedCiphertext3HandlesValidityStatement`].
+    GroupedCiphertext3HandlesValidityWitness {
+        amount: u64,
+        opening: &'a PedersenOpening,
+    }
+    GroupedCiphertext3HandlesValidityProofData =
+        build_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
+            amount,
+            opening,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that two grouped ElGamal ciphertexts with 2 handles are well-formed.
+    BatchedGroupedCiphertext2HandlesValidityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext_lo: &'a GroupedElGamalCiphertext<2>,
+        grouped_ciphertext_hi: &'a GroupedElGamalCiphertext<2>,
+    }
+    /// The witness of a [`BatchedGroupedCiphertext2HandlesValidityStatement`].
+    BatchedGroupedCiphertext2HandlesValidityWitness {
+        amount_lo: u64,
+        amount_hi: u64,
+        opening_lo: &'a PedersenOpening,
+        opening_hi: &'a PedersenOpening,
+    }
+    BatchedGroupedCiphertext2HandlesValidityProofData =
+        build_batched_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            amount_lo,
+            amount_hi,
+            opening_lo,
+            opening_hi,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that two grouped ElGamal ciphertexts with 3 handles are well-formed.
+    BatchedGroupedCiphertext3HandlesValidityStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        third_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext_lo: &'a GroupedElGamalCiphertext<3>,
+        grouped_ciphertext_hi: &'a GroupedElGamalCiphertext<3>,
+    }
+    /// The witness of a [`BatchedGroupedCiphertext3HandlesValidityStatement`].
+    BatchedGroupedCiphertext3HandlesValidityWitness {
+        amount_lo: u64,
+        amount_hi: u64,
+        opening_lo: &'a PedersenOpening,
+        opening_hi: &'a PedersenOpening,
+    }
+    BatchedGroupedCiphertext3HandlesValidityProofData =
+        build_batched_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext_lo,
+            grouped_ciphertext_hi,
+            amount_lo,
+            amount_hi,
+            opening_lo,
+            opening_hi,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that a grouped ElGamal ciphertext with 2 handles encrypts zero.
+    GroupedZeroCiphertext2HandlesStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<2>,
+    }
+    /// The witness of a [`GroupedZeroCiphertext2HandlesStatement`].
+    GroupedZeroCiphertext2HandlesWitness { opening: &'a PedersenOpening }
+    GroupedZeroCiphertext2HandlesProofData =
+        build_grouped_zero_ciphertext_2_handles_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            grouped_ciphertext,
+            opening,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that a grouped ElGamal ciphertext with 3 handles encrypts zero.
+    GroupedZeroCiphertext3HandlesStatement {
+        first_pubkey: &'a ElGamalPubkey,
+        second_pubkey: &'a ElGamalPubkey,
+        third_pubkey: &'a ElGamalPubkey,
+        grouped_ciphertext: &'a GroupedElGamalCiphertext<3>,
+    }
+    /// The witness of a [`GroupedZeroCiphertext3HandlesStatement`].
+    GroupedZeroCiphertext3HandlesWitness { opening: &'a PedersenOpening }
+    GroupedZeroCiphertext3HandlesProofData =
+        build_grouped_zero_ciphertext_3_handles_proof_data_with_statement_context(
+            first_pubkey,
+            second_pubkey,
+            third_pubkey,
+            grouped_ciphertext,
+            opening,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement that two ElGamal ciphertexts of the low 48 bits and the high 16 bits of an
+    /// amount are consistent with an ElGamal ciphertext of the amount under the same pubkey.
+    SplitConsistencyStatement {
+        pubkey: &'a ElGamalPubkey,
+        ciphertext_lo: &'a ElGamalCiphertext,
+        ciphertext_hi: &'a ElGamalCiphertext,
+        ciphertext: &'a ElGamalCiphertext,
+    }
+    /// The witness of a [`SplitConsistencyStatement`].
+    SplitConsistencyWitness {
+        amount: u64,
+        opening_lo: &'a PedersenOpening,
+        opening_hi: &'a PedersenOpening,
+        opening: &'a PedersenOpening,
+    }
+    SplitConsistencyProofData = build_split_consistency_proof_data_with_statement_context(
+        pubkey,
+        ciphertext_lo,
+        ciphertext_hi,
+        ciphertext,
+        amount,
+        opening_lo,
+        opening_hi,
+        opening,
+    )
+}
+
+define_proof_statement! {
+    /// The statement that the committed amounts are in the ranges of their bit lengths, which sum
+    /// to 64.
+    BatchedRangeProofU64Statement {
+        commitments: Vec<&'a PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+    }
+    /// The witness of a [`BatchedRangeProofU64Statement`].
+    BatchedRangeProofU64Witness {
+        amounts: Vec<u64>,
+        openings: Vec<&'a PedersenOpening>,
+    }
+    BatchedRangeProofU64Data =
+        build_batched_range_proof_u64_data_with_statement_context(
+            commitments, amounts, bit_lengths, openings,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that the committed amounts are in the ranges of their bit lengths, which sum
+    /// to 128.
+    BatchedRangeProofU128Statement {
+        commitments: Vec<&'a PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+    }
+    /// The witness of a [`BatchedRangeProofU128Statement`].
+    BatchedRangeProofU128Witness {
+        amounts: Vec<u64>,
+        openings: Vec<&'a PedersenOpening>,
+    }
+    BatchedRangeProofU128Data =
+        build_batched_range_proof_u128_data_with_statement_context(
+            commitments, amounts, bit_lengths, openings,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that the committed amounts are in the ranges of their bit lengths, which sum
+    /// to 256.
+    BatchedRangeProofU256Statement {
+        commitments: Vec<&'a PedersenCommitment>,
+        bit_lengths: Vec<usize>,
+    }
+    /// The witness of a [`BatchedRangeProofU256Statement`].
+    BatchedRangeProofU256Witness {
+        amounts: Vec<u64>,
+        openings: Vec<&'a PedersenOpening>,
+    }
+    BatchedRangeProofU256Data =
+        build_batched_range_proof_u256_data_with_statement_context(
+            commitments, amounts, bit_lengths, openings,
+        )
+}
+
+define_proof_statement! {
+    /// The statement that a Pedersen commitment holds a signed amount in the range
+    /// `[-2^31, 2^31)`.
+    SignedAmountRangeProofStatement { commitment: &'a PedersenCommitment }
+    /// The witness of a [`SignedAmountRangeProofStatement`].
+    SignedAmountRangeProofWitness {
+        amount: i64,
+        opening: &'a PedersenOpening,
+    }
+    BatchedRangeProofU64Data =
+        build_signed_amount_range_proof_data_with_statement_context(commitment, amount, opening)
+}
+
+define_proof_statement! {
+    /// The statement that a committed amount is the percentage of a committed base amount,
+    /// capped at a maximum value.
+    PercentageWithCapStatement {
+        percentage_commitment: &'a PedersenCommitment,
+        delta_commitment: &'a PedersenCommitment,
+        claimed_commitment: &'a PedersenCommitment,
+        max_value: u64,
+    }
+    /// The witness of a [`PercentageWithCapStatement`].
+    PercentageWithCapWitness {
+        percentage_opening: &'a PedersenOpening,
+        percentage_amount: u64,
+        delta_opening: &'a PedersenOpening,
+        delta_amount: u64,
+        claimed_opening: &'a PedersenOpening,
+    }
+    PercentageWithCapProofData = build_percentage_with_cap_proof_data_with_statement_context(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+    )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement of a [`PercentageWithCapStatement`] with a 128-bit maximum value.
+    PercentageWithCapU128Statement {
+        percentage_commitment: &'a PedersenCommitment,
+        delta_commitment: &'a PedersenCommitment,
+        claimed_commitment: &'a PedersenCommitment,
+        max_value: u128,
+    }
+    /// The witness of a [`PercentageWithCapU128Statement`].
+    PercentageWithCapU128Witness {
+        percentage_opening: &'a PedersenOpening,
+        percentage_amount: u64,
+        delta_opening: &'a PedersenOpening,
+        delta_amount: u64,
+        claimed_opening: &'a PedersenOpening,
+    }
+    PercentageWithCapU128ProofData =
+        build_percentage_with_cap_u128_proof_data_with_statement_context(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+        )
+}
+
+#[cfg(feature = "unstable")]
+define_proof_statement! {
+    /// The statement of a [`PercentageWithCapStatement`] whose capped amount is split into
+    /// committed shares.
+    PercentageWithCapSplitStatement {
+        percentage_commitment: &'a PedersenCommitment,
+        delta_commitment: &'a PedersenCommitment,
+        claimed_commitment: &'a PedersenCommitment,
+        max_value: u64,
+        share_commitments: &'a [&'a PedersenCommitment],
+    }
+    /// The witness of a [`PercentageWithCapSplitStatement`].
+    PercentageWithCapSplitWitness {
+        percentage_opening: &'a PedersenOpening,
+        percentage_amount: u64,
+        delta_opening: &'a PedersenOpening,
+        delta_amount: u64,
+        claimed_opening: &'a PedersenOpening,
+        share_amounts: &'a [u64],
+        share_openings: &'a [&'a PedersenOpening],
+    }
+    PercentageWithCapSplitProofData =
+        build_percentage_with_cap_split_proof_data_with_statement_context(
+            percentage_commitment,
+            percentage_opening,
+            percentage_amount,
+            delta_commitment,
+            delta_opening,
+            delta_amount,
+            claimed_commitment,
+            claimed_opening,
+            max_value,
+            share_commitments,
+            share_amounts,
+            share_openings,
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::pedersen::Pedersen, solana_keypair::Keypair};
+
+    #[test]
+    fn test_proof_builder_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+        let amount = 55_u64;
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+
+        let proof_data = ProofBuilder::new(Some(b"test"))
+            .witness(CiphertextCommitmentEqualityWitness {
+                keypair: &keypair,
+                opening: &opening,
+                amount,
+            })
+            .statement(CiphertextCommitmentEqualityStatement {
+                pubkey: keypair.pubkey(),
+                ciphertext: &ciphertext,
+                commitment: &commitment,
+            })
+            .build()
+            .unwrap();
+
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"test"))
+            .is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"other"))
+            .is_err());
+        assert!(proof_data.verify_proof().is_err());
+
+        let proof_data = ProofBuilder::new(Some(b"test"))
+            .statement(BatchedRangeProofU64Statement {
+                commitments: vec![&commitment],
+                bit_lengths: vec![64],
+            })
+            .witness(BatchedRangeProofU64Witness {
+                amounts: vec![amount],
+                openings: vec![&opening],
+            })
+            .build()
+            .unwrap();
+
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"test"))
+            .is_ok());
+
+        let proof_data = ProofBuilder::new(None)
+            .statement(PubkeyValidityStatement {
+                pubkey: keypair.pubkey(),
+            })
+            .witness(PubkeyValidityWitness { keypair: &keypair })
+            .build()
+            .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"test"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_statement_signature() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let other_ciphertext = keypair.pubkey().encrypt(0_u64);
+        let signer = Keypair::new();
+
+        let statement = ZeroCiphertextStatement {
+            pubkey: keypair.pubkey(),
+            ciphertext: &ciphertext,
+        };
+        let signature = statement.sign_statement(&signer).unwrap();
+        assert!(statement.verify_statement_signature(&signer.pubkey(), &signature));
+        assert!(!statement.verify_statement_signature(&Keypair::new().pubkey(), &signature));
+
+        let other_statement = ZeroCiphertextStatement {
+            pubkey: keypair.pubkey(),
+            ciphertext: &other_ciphertext,
+        };
+        assert!(!other_statement.verify_statement_signature(&signer.pubkey(), &signature));
+
+        // statements of different types with the same fields have different encodings
+        let (commitment, _) = Pedersen::new(0_u64);
+        let u64_statement = BatchedRangeProofU64Statement {
+            commitments: vec![&commitment],
+            bit_lengths: vec![64],
+        };
+        let u128_statement = BatchedRangeProofU128Statement {
+            commitments: vec![&commitment],
+            bit_lengths: vec![64],
+        };
+        assert_ne!(
+            u64_statement.to_statement_bytes(),
+            u128_statement.to_statement_bytes()
+        );
+    }
+
+    #[test]
+    fn test_proof_builder_inconsistent_keypair() {
+        let keypair = ElGamalKeypair::new_rand();
+        let other_keypair = ElGamalKeypair::new_rand();
+
+        let result = ProofBuilder::new(Some(b"test"))
+            .statement(PubkeyValidityStatement {
+                pubkey: other_keypair.pubkey(),
+            })
+            .witness(PubkeyValidityWitness { keypair: &keypair })
+            .build();
+
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
+}
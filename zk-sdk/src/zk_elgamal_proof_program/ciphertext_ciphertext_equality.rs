@@ -27,6 +27,28 @@ pub fn build_ciphertext_ciphertext_equality_proof_data(
     second_ciphertext: &ElGamalCiphertext,
     second_opening: &PedersenOpening,
     amount: u64,
+) -> Result<CiphertextCiphertextEqualityProofData, ProofGenerationError> {
+    build_ciphertext_ciphertext_equality_proof_data_with_statement_context(
+        first_keypair,
+        second_pubkey,
+        first_ciphertext,
+        second_ciphertext,
+        second_opening,
+        amount,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_ciphertext_ciphertext_equality_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_ciphertext_ciphertext_equality_proof_data_with_statement_context(
+    first_keypair: &ElGamalKeypair,
+    second_pubkey: &ElGamalPubkey,
+    first_ciphertext: &ElGamalCiphertext,
+    second_ciphertext: &ElGamalCiphertext,
+    second_opening: &PedersenOpening,
+    amount: u64,
+    statement_context: Option<&[u8]>,
 ) -> Result<CiphertextCiphertextEqualityProofData, ProofGenerationError> {
     // First ciphertext should decrypt to the expected amount
     // D_first = C_first - s * H_first. Should equal amount * G.
@@ -54,8 +76,10 @@ pub fn build_ciphertext_ciphertext_equality_proof_data(
         second_ciphertext: pod_second_ciphertext,
     };
 
-    let mut transcript =
-        Transcript::new_zk_elgamal_transcript(b"ciphertext-ciphertext-equality-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"ciphertext-ciphertext-equality-instruction",
+        statement_context,
+    );
 
     let proof = CiphertextCiphertextEqualityProof::new(
         first_keypair,
@@ -73,8 +97,17 @@ pub fn build_ciphertext_ciphertext_equality_proof_data(
 
 impl VerifyZkProof for CiphertextCiphertextEqualityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript =
-            Transcript::new_zk_elgamal_transcript(b"ciphertext-ciphertext-equality-instruction");
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"ciphertext-ciphertext-equality-instruction",
+            statement_context,
+        );
 
         let first_pubkey = self.context.first_pubkey.try_into()?;
         let second_pubkey = self.context.second_pubkey.try_into()?;
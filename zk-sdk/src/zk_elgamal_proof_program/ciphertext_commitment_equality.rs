@@ -1,14 +1,23 @@
+pub use crate::sigma_proofs::{
+    compressed_equality::{
+        CompressedEqualityProof, CompressedEqualityStatement, CompressedEqualityWitness,
+        MAX_COMPRESSED_EQUALITY_STATEMENTS,
+    },
+    errors::{CompressedEqualityProofGenerationError, EqualityProofVerificationError},
+};
 use {
     crate::{
         encryption::{
             elgamal::{ElGamalCiphertext, ElGamalKeypair},
             pedersen::{Pedersen, PedersenCommitment, PedersenOpening, G},
+            secret_key_handle::{SecretKeyBackend, SecretKeyHandle},
         },
+        msm::MultiscalarMulBuffer,
         sigma_proofs::ciphertext_commitment_equality::CiphertextCommitmentEqualityProof,
         transcript::TranscriptProtocol,
         zk_elgamal_proof_program::{
             errors::{ProofGenerationError, ProofVerificationError},
-            VerifyZkProof,
+            AppendVerificationTerms, VerifyZkProof,
         },
     },
     curve25519_dalek::scalar::Scalar,
@@ -29,6 +38,21 @@ pub fn build_ciphertext_commitment_equality_proof_data(
     commitment: &PedersenCommitment,
     opening: &PedersenOpening,
     amount: u64,
+) -> Result<CiphertextCommitmentEqualityProofData, ProofGenerationError> {
+    build_ciphertext_commitment_equality_proof_data_with_statement_context(
+        keypair, ciphertext, commitment, opening, amount, None,
+    )
+}
+
+/// Generates the proof data of [`build_ciphertext_commitment_equality_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_ciphertext_commitment_equality_proof_data_with_statement_context(
+    keypair: &ElGamalKeypair,
+    ciphertext: &ElGamalCiphertext,
+    commitment: &PedersenCommitment,
+    opening: &PedersenOpening,
+    amount: u64,
+    statement_context: Option<&[u8]>,
 ) -> Result<CiphertextCommitmentEqualityProofData, ProofGenerationError> {
     // Ciphertext should decrypt to amount
     let decrypted_point = ciphertext.decrypt(keypair.secret()).target;
@@ -48,8 +72,10 @@ pub fn build_ciphertext_commitment_equality_proof_data(
         ciphertext: PodElGamalCiphertext(ciphertext.to_bytes()),
         commitment: PodPedersenCommitment(commitment.to_bytes()),
     };
-    let mut transcript =
-        Transcript::new_zk_elgamal_transcript(b"ciphertext-commitment-equality-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"ciphertext-commitment-equality-instruction",
+        statement_context,
+    );
     let proof = CiphertextCommitmentEqualityProof::new(
         keypair,
         ciphertext,
@@ -64,10 +90,62 @@ pub fn build_ciphertext_commitment_equality_proof_data(
     })
 }
 
+pub fn build_ciphertext_commitment_equality_proof_data_with_secret_key_handle<
+    B: SecretKeyBackend,
+>(
+    secret_key_handle: &SecretKeyHandle<B>,
+    ciphertext: &ElGamalCiphertext,
+    commitment: &PedersenCommitment,
+    opening: &PedersenOpening,
+    amount: u64,
+) -> Result<CiphertextCommitmentEqualityProofData, ProofGenerationError> {
+    // Ciphertext should decrypt to amount
+    let decrypted_point = secret_key_handle.decrypt(ciphertext)?.target;
+    let expected_point = Scalar::from(amount) * G;
+    if decrypted_point != expected_point {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    // Commitment should match amount and opening
+    let expected_commitment = Pedersen::with(amount, opening);
+    if *commitment != expected_commitment {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = CiphertextCommitmentEqualityProofContext {
+        pubkey: PodElGamalPubkey((*secret_key_handle.pubkey()).into()),
+        ciphertext: PodElGamalCiphertext(ciphertext.to_bytes()),
+        commitment: PodPedersenCommitment(commitment.to_bytes()),
+    };
+    let mut transcript =
+        Transcript::new_zk_elgamal_transcript(b"ciphertext-commitment-equality-instruction");
+    let proof = CiphertextCommitmentEqualityProof::new_with_secret_key_handle(
+        secret_key_handle,
+        ciphertext,
+        commitment,
+        opening,
+        amount,
+        &mut transcript,
+    )?;
+    Ok(CiphertextCommitmentEqualityProofData {
+        context,
+        proof: proof.into(),
+    })
+}
+
 impl VerifyZkProof for CiphertextCommitmentEqualityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript =
-            Transcript::new_zk_elgamal_transcript(b"ciphertext-commitment-equality-instruction");
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"ciphertext-commitment-equality-instruction",
+            statement_context,
+        );
 
         let pubkey = self.context.pubkey.try_into()?;
         let ciphertext = self.context.ciphertext.try_into()?;
@@ -80,6 +158,30 @@ impl VerifyZkProof for CiphertextCommitmentEqualityProofData {
     }
 }
 
+impl AppendVerificationTerms for CiphertextCommitmentEqualityProofData {
+    const MAX_VERIFICATION_TERMS: usize = CiphertextCommitmentEqualityProof::VERIFICATION_TERMS;
+
+    fn append_verification_terms(
+        &self,
+        buffer: &mut MultiscalarMulBuffer,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"ciphertext-commitment-equality-instruction",
+            statement_context,
+        );
+
+        let pubkey = self.context.pubkey.try_into()?;
+        let ciphertext = self.context.ciphertext.try_into()?;
+        let commitment = self.context.commitment.try_into()?;
+        let proof: CiphertextCommitmentEqualityProof = self.proof.try_into()?;
+
+        proof
+            .append_verification_terms(&pubkey, &ciphertext, &commitment, &mut transcript, buffer)
+            .map_err(|e| e.into())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {
@@ -118,4 +220,34 @@ mod test {
 
         assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
     }
+
+    #[test]
+    fn test_ctxt_comm_equality_proof_with_secret_key_handle() {
+        let keypair = ElGamalKeypair::new_rand();
+        let secret_key_handle = SecretKeyHandle::new(keypair.clone());
+        let amount: u64 = 55;
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+
+        let proof_data = build_ciphertext_commitment_equality_proof_data_with_secret_key_handle(
+            &secret_key_handle,
+            &ciphertext,
+            &commitment,
+            &opening,
+            amount,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        let result = build_ciphertext_commitment_equality_proof_data_with_secret_key_handle(
+            &secret_key_handle,
+            &ciphertext,
+            &commitment,
+            &opening,
+            amount + 1,
+        );
+
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
 }
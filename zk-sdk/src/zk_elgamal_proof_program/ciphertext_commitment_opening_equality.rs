@@ -0,0 +1,166 @@
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
+        },
+        sigma_proofs::ciphertext_commitment_opening_equality::CiphertextCommitmentOpeningEqualityProof,
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            VerifyZkProof,
+        },
+    },
+    merlin::Transcript,
+    solana_zk_elgamal_proof_interface::proof_data::{
+        CiphertextCommitmentOpeningEqualityProofContext,
+        CiphertextCommitmentOpeningEqualityProofData,
+    },
+    solana_zk_sdk_pod::encryption::{
+        elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+        pedersen::PodPedersenCommitment,
+    },
+    std::convert::TryInto,
+};
+
+pub fn build_ciphertext_commitment_opening_equality_proof_data(
+    pubkey: &ElGamalPubkey,
+    ciphertext: &ElGamalCiphertext,
+    commitment: &PedersenCommitment,
+    ciphertext_opening: &PedersenOpening,
+    commitment_opening: &PedersenOpening,
+    amount: u64,
+) -> Result<CiphertextCommitmentOpeningEqualityProofData, ProofGenerationError> {
+    build_ciphertext_commitment_opening_equality_proof_data_with_statement_context(
+        pubkey,
+        ciphertext,
+        commitment,
+        ciphertext_opening,
+        commitment_opening,
+        amount,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_ciphertext_commitment_opening_equality_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_ciphertext_commitment_opening_equality_proof_data_with_statement_context(
+    pubkey: &ElGamalPubkey,
+    ciphertext: &ElGamalCiphertext,
+    commitment: &PedersenCommitment,
+    ciphertext_opening: &PedersenOpening,
+    commitment_opening: &PedersenOpening,
+    amount: u64,
+    statement_context: Option<&[u8]>,
+) -> Result<CiphertextCommitmentOpeningEqualityProofData, ProofGenerationError> {
+    // Ciphertext should match amount and opening
+    let expected_ciphertext = pubkey.encrypt_with(amount, ciphertext_opening);
+    if *ciphertext != expected_ciphertext {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    // Commitment should match amount and opening
+    let expected_commitment = Pedersen::with(amount, commitment_opening);
+    if *commitment != expected_commitment {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = CiphertextCommitmentOpeningEqualityProofContext {
+        pubkey: PodElGamalPubkey((*pubkey).into()),
+        ciphertext: PodElGamalCiphertext(ciphertext.to_bytes()),
+        commitment: PodPedersenCommitment(commitment.to_bytes()),
+    };
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"ciphertext-commitment-opening-equality-instruction",
+        statement_context,
+    );
+    let proof = CiphertextCommitmentOpeningEqualityProof::new(
+        pubkey,
+        ciphertext,
+        commitment,
+        ciphertext_opening,
+        commitment_opening,
+        amount,
+        &mut transcript,
+    );
+    Ok(CiphertextCommitmentOpeningEqualityProofData {
+        context,
+        proof: proof.into(),
+    })
+}
+
+impl VerifyZkProof for CiphertextCommitmentOpeningEqualityProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"ciphertext-commitment-opening-equality-instruction",
+            statement_context,
+        );
+
+        let pubkey = self.context.pubkey.try_into()?;
+        let ciphertext = self.context.ciphertext.try_into()?;
+        let commitment = self.context.commitment.try_into()?;
+        let proof: CiphertextCommitmentOpeningEqualityProof = self.proof.try_into()?;
+
+        proof
+            .verify(&pubkey, &ciphertext, &commitment, &mut transcript)
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_ctxt_comm_opening_equality_proof_correctness() {
+        let pubkey = *ElGamalKeypair::new_rand().pubkey();
+        let amount: u64 = 55;
+        let ciphertext_opening = PedersenOpening::new_rand();
+        let ciphertext = pubkey.encrypt_with(amount, &ciphertext_opening);
+        let (commitment, commitment_opening) = Pedersen::new(amount);
+
+        let proof_data = build_ciphertext_commitment_opening_equality_proof_data(
+            &pubkey,
+            &ciphertext,
+            &commitment,
+            &ciphertext_opening,
+            &commitment_opening,
+            amount,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        let amount_2 = 77_u64;
+        let (commitment_2, commitment_opening_2) = Pedersen::new(amount_2);
+
+        let result = build_ciphertext_commitment_opening_equality_proof_data(
+            &pubkey,
+            &ciphertext,
+            &commitment_2,
+            &ciphertext_opening,
+            &commitment_opening_2,
+            amount,
+        );
+
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+
+        let result = build_ciphertext_commitment_opening_equality_proof_data(
+            &pubkey,
+            &ciphertext,
+            &commitment,
+            &PedersenOpening::new_rand(),
+            &commitment_opening,
+            amount,
+        );
+
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
+}
@@ -1,6 +1,6 @@
 use {
     crate::{
-        errors::ElGamalError,
+        errors::{ElGamalError, RngError, SecretKeyBackendError},
         range_proof::errors::{RangeProofGenerationError, RangeProofVerificationError},
         sigma_proofs::errors::*,
     },
@@ -21,6 +21,10 @@ pub enum ProofGenerationError {
     ProofLength,
     #[error("cryptographic component mismatch")]
     InconsistentInput,
+    #[error("fee calculation failed")]
+    FeeCalculation,
+    #[error("secret key backend failed")]
+    SecretKeyBackend(#[from] SecretKeyBackendError),
 }
 
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
@@ -37,6 +41,14 @@ pub enum ProofVerificationError {
     IllegalCommitmentLength,
     #[error("illegal amount bit length")]
     IllegalAmountBitLength,
+    #[error("combined proof verification failed")]
+    CombinedAlgebraicRelation,
+    #[error("invalid proof batch")]
+    ProofBatch,
+    #[error("invalid proof data")]
+    ProofData,
+    #[error("random number generator failed")]
+    Rng(#[from] RngError),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,6 +58,7 @@ pub enum SigmaProofType {
     PubkeyValidity,
     PercentageWithCap,
     ValidityProof,
+    SplitConsistency,
 }
 
 impl From<ZeroCiphertextProofVerificationError> for ProofVerificationError {
@@ -77,3 +90,15 @@ impl From<ValidityProofVerificationError> for ProofVerificationError {
         Self::SigmaProof(SigmaProofType::ValidityProof, err.0)
     }
 }
+
+impl From<SplitConsistencyProofVerificationError> for ProofVerificationError {
+    fn from(err: SplitConsistencyProofVerificationError) -> Self {
+        Self::SigmaProof(SigmaProofType::SplitConsistency, err.0)
+    }
+}
+
+impl From<KeyPrivateValidityProofVerificationError> for ProofVerificationError {
+    fn from(err: KeyPrivateValidityProofVerificationError) -> Self {
+        Self::SigmaProof(SigmaProofType::ValidityProof, err.0)
+    }
+}
@@ -0,0 +1,217 @@
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair, ElGamalPubkey},
+            grouped_elgamal::GroupedElGamalCiphertext,
+            pedersen::PedersenOpening,
+        },
+        sigma_proofs::ciphertext_ciphertext_equality::CiphertextCiphertextEqualityProof,
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            grouped_ciphertext_ciphertext_equality::{
+                grouped_ciphertext_ciphertext_equality_transcript, verify_first_ciphertext,
+            },
+            VerifyZkProof,
+        },
+    },
+    solana_zk_elgamal_proof_interface::proof_data::{
+        GroupedCiphertext2HandlesCiphertextEqualityProofContext,
+        GroupedCiphertext2HandlesCiphertextEqualityProofData,
+    },
+    solana_zk_sdk_pod::encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+    std::convert::TryInto,
+};
+
+const GROUPED_CIPHERTEXT_2_HANDLES_CIPHERTEXT_EQUALITY_INSTRUCTION_LABEL: &[u8] =
+    b"grouped-ciphertext-2-handles-ciphertext-equality-instruction";
+
+/// Generates a proof that the ciphertext component at `handle_index` of a grouped ciphertext and
+/// a regular ciphertext encrypt the same amount.
+///
+/// The `first_keypair` must be the ElGamal keypair associated with the decrypt handle at
+/// `handle_index` of the grouped ciphertext.
+pub fn build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data(
+    first_keypair: &ElGamalKeypair,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+    handle_index: usize,
+    second_ciphertext: &ElGamalCiphertext,
+    second_opening: &PedersenOpening,
+    amount: u64,
+) -> Result<GroupedCiphertext2HandlesCiphertextEqualityProofData, ProofGenerationError> {
+    build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data_with_statement_context(
+        first_keypair,
+        second_pubkey,
+        grouped_ciphertext,
+        handle_index,
+        second_ciphertext,
+        second_opening,
+        amount,
+        None,
+    )
+}
+
+/// Generates the proof data of
+/// [`build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data`] with `statement_context`,
+/// if any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data_with_statement_context(
+    first_keypair: &ElGamalKeypair,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+    handle_index: usize,
+    second_ciphertext: &ElGamalCiphertext,
+    second_opening: &PedersenOpening,
+    amount: u64,
+    statement_context: Option<&[u8]>,
+) -> Result<GroupedCiphertext2HandlesCiphertextEqualityProofData, ProofGenerationError> {
+    let first_ciphertext = grouped_ciphertext
+        .to_elgamal_ciphertext(handle_index)
+        .map_err(|_| ProofGenerationError::InconsistentInput)?;
+    verify_first_ciphertext(first_keypair, &first_ciphertext, amount)?;
+
+    // Second ciphertext should match encryption of amount with second_opening
+    let expected_second_ciphertext = second_pubkey.encrypt_with(amount, second_opening);
+    if *second_ciphertext != expected_second_ciphertext {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = GroupedCiphertext2HandlesCiphertextEqualityProofContext {
+        first_pubkey: PodElGamalPubkey(first_keypair.pubkey().into()),
+        second_pubkey: PodElGamalPubkey(second_pubkey.into()),
+        grouped_ciphertext: (*grouped_ciphertext).into(),
+        second_ciphertext: PodElGamalCiphertext(second_ciphertext.to_bytes()),
+        // the handle index is bounded by the number of handles
+        handle_index: handle_index as u8,
+    };
+
+    let mut transcript = grouped_ciphertext_ciphertext_equality_transcript(
+        GROUPED_CIPHERTEXT_2_HANDLES_CIPHERTEXT_EQUALITY_INSTRUCTION_LABEL,
+        &context.grouped_ciphertext.0,
+        context.handle_index,
+        statement_context,
+    );
+
+    let proof = CiphertextCiphertextEqualityProof::new(
+        first_keypair,
+        second_pubkey,
+        &first_ciphertext,
+        second_ciphertext,
+        second_opening,
+        amount,
+        &mut transcript,
+    )
+    .into();
+
+    Ok(GroupedCiphertext2HandlesCiphertextEqualityProofData { context, proof })
+}
+
+impl VerifyZkProof for GroupedCiphertext2HandlesCiphertextEqualityProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = grouped_ciphertext_ciphertext_equality_transcript(
+            GROUPED_CIPHERTEXT_2_HANDLES_CIPHERTEXT_EQUALITY_INSTRUCTION_LABEL,
+            &self.context.grouped_ciphertext.0,
+            self.context.handle_index,
+            statement_context,
+        );
+
+        let first_pubkey = self.context.first_pubkey.try_into()?;
+        let second_pubkey = self.context.second_pubkey.try_into()?;
+        let first_ciphertext = self
+            .context
+            .grouped_ciphertext
+            .try_extract_ciphertext(self.context.handle_index as usize)
+            .map_err(|_| ProofVerificationError::ProofContext)?
+            .try_into()?;
+        let second_ciphertext = self.context.second_ciphertext.try_into()?;
+        let proof: CiphertextCiphertextEqualityProof = self.proof.try_into()?;
+
+        proof
+            .verify(
+                &first_pubkey,
+                &second_pubkey,
+                &first_ciphertext,
+                &second_ciphertext,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::grouped_elgamal::GroupedElGamal};
+
+    #[test]
+    fn test_grouped_ciphertext_2_handles_ciphertext_equality_instruction_correctness() {
+        let keypair_0 = ElGamalKeypair::new_rand();
+        let keypair_1 = ElGamalKeypair::new_rand();
+        let second_keypair = ElGamalKeypair::new_rand();
+
+        let amount: u64 = 55;
+        let grouped_ciphertext =
+            GroupedElGamal::encrypt([keypair_0.pubkey(), keypair_1.pubkey()], amount);
+
+        let second_opening = PedersenOpening::new_rand();
+        let second_ciphertext = second_keypair
+            .pubkey()
+            .encrypt_with(amount, &second_opening);
+
+        let proof_data = build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data(
+            &keypair_1,
+            second_keypair.pubkey(),
+            &grouped_ciphertext,
+            1,
+            &second_ciphertext,
+            &second_opening,
+            amount,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        // the proof does not verify with respect to a different handle index
+        let mut modified_proof_data = proof_data;
+        modified_proof_data.context.handle_index = 0;
+        modified_proof_data.context.first_pubkey = PodElGamalPubkey(keypair_0.pubkey().into());
+        assert!(modified_proof_data.verify_proof().is_err());
+
+        // out-of-bounds handle index
+        let mut modified_proof_data = proof_data;
+        modified_proof_data.context.handle_index = 2;
+        assert_eq!(
+            modified_proof_data.verify_proof().unwrap_err(),
+            ProofVerificationError::ProofContext
+        );
+
+        // the keypair must correspond to the handle index
+        let result = build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data(
+            &keypair_0,
+            second_keypair.pubkey(),
+            &grouped_ciphertext,
+            1,
+            &second_ciphertext,
+            &second_opening,
+            amount,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+
+        let result = build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data(
+            &keypair_0,
+            second_keypair.pubkey(),
+            &grouped_ciphertext,
+            2,
+            &second_ciphertext,
+            &second_opening,
+            amount,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
+}
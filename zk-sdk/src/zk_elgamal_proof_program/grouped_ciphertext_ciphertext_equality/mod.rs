@@ -0,0 +1,56 @@
+mod handles_2;
+mod handles_3;
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair},
+            pedersen::G,
+        },
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::errors::ProofGenerationError,
+    },
+    curve25519_dalek::scalar::Scalar,
+    merlin::Transcript,
+};
+pub use {
+    handles_2::build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data,
+    handles_3::build_grouped_ciphertext_3_handles_ciphertext_equality_proof_data,
+};
+pub(crate) use {
+    handles_2::build_grouped_ciphertext_2_handles_ciphertext_equality_proof_data_with_statement_context,
+    handles_3::build_grouped_ciphertext_3_handles_ciphertext_equality_proof_data_with_statement_context,
+};
+
+/// Creates the transcript for a grouped-ciphertext-ciphertext equality proof instruction.
+///
+/// The equality proof itself is defined with respect to the ciphertext that is extracted from the
+/// grouped ciphertext. The full grouped ciphertext and the handle index are hashed into the
+/// transcript so that the proof is bound to the specific grouped ciphertext component.
+fn grouped_ciphertext_ciphertext_equality_transcript(
+    label: &'static [u8],
+    grouped_ciphertext: &[u8],
+    handle_index: u8,
+    statement_context: Option<&[u8]>,
+) -> Transcript {
+    let mut transcript =
+        Transcript::new_zk_elgamal_transcript_with_statement_context(label, statement_context);
+    transcript.append_message(b"grouped-ciphertext", grouped_ciphertext);
+    transcript.append_u64(b"handle-index", handle_index as u64);
+    transcript
+}
+
+/// Checks that the ciphertext extracted from the grouped ciphertext decrypts to `amount`.
+fn verify_first_ciphertext(
+    first_keypair: &ElGamalKeypair,
+    first_ciphertext: &ElGamalCiphertext,
+    amount: u64,
+) -> Result<(), ProofGenerationError> {
+    // D_first = C_first - s * H_first. Should equal amount * G.
+    let decrypted_point = first_ciphertext.decrypt(first_keypair.secret()).target;
+    let expected_point = Scalar::from(amount) * G;
+    if decrypted_point != expected_point {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+    Ok(())
+}
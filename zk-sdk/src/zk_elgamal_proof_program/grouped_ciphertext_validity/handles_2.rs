@@ -25,6 +25,26 @@ pub fn build_grouped_ciphertext_2_handles_validity_proof_data(
     grouped_ciphertext: &GroupedElGamalCiphertext<2>,
     amount: u64,
     opening: &PedersenOpening,
+) -> Result<GroupedCiphertext2HandlesValidityProofData, ProofGenerationError> {
+    build_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+        first_pubkey,
+        second_pubkey,
+        grouped_ciphertext,
+        amount,
+        opening,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_grouped_ciphertext_2_handles_validity_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+    amount: u64,
+    opening: &PedersenOpening,
+    statement_context: Option<&[u8]>,
 ) -> Result<GroupedCiphertext2HandlesValidityProofData, ProofGenerationError> {
     let expected_ciphertext =
         GroupedElGamal::encrypt_with([first_pubkey, second_pubkey], amount, opening);
@@ -42,8 +62,10 @@ pub fn build_grouped_ciphertext_2_handles_validity_proof_data(
         grouped_ciphertext: pod_grouped_ciphertext,
     };
 
-    let mut transcript =
-        Transcript::new_zk_elgamal_transcript(b"grouped-ciphertext-validity-2-handles-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"grouped-ciphertext-validity-2-handles-instruction",
+        statement_context,
+    );
 
     let proof = GroupedCiphertext2HandlesValidityProof::new(
         first_pubkey,
@@ -60,8 +82,16 @@ pub fn build_grouped_ciphertext_2_handles_validity_proof_data(
 
 impl VerifyZkProof for GroupedCiphertext2HandlesValidityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
             b"grouped-ciphertext-validity-2-handles-instruction",
+            statement_context,
         );
 
         let first_pubkey = self.context.first_pubkey.try_into()?;
@@ -4,6 +4,7 @@ use {
             elgamal::ElGamalPubkey,
             grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
             pedersen::PedersenOpening,
+            role::{AuditorElGamalPubkey, ReceiverElGamalPubkey, SenderElGamalPubkey},
         },
         sigma_proofs::grouped_ciphertext_validity::GroupedCiphertext3HandlesValidityProof,
         transcript::TranscriptProtocol,
@@ -26,6 +27,28 @@ pub fn build_grouped_ciphertext_3_handles_validity_proof_data(
     grouped_ciphertext: &GroupedElGamalCiphertext<3>,
     amount: u64,
     opening: &PedersenOpening,
+) -> Result<GroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
+    build_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+        first_pubkey,
+        second_pubkey,
+        third_pubkey,
+        grouped_ciphertext,
+        amount,
+        opening,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_grouped_ciphertext_3_handles_validity_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    third_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<3>,
+    amount: u64,
+    opening: &PedersenOpening,
+    statement_context: Option<&[u8]>,
 ) -> Result<GroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
     let expected_ciphertext =
         GroupedElGamal::encrypt_with([first_pubkey, second_pubkey, third_pubkey], amount, opening);
@@ -45,8 +68,10 @@ pub fn build_grouped_ciphertext_3_handles_validity_proof_data(
         grouped_ciphertext: pod_grouped_ciphertext,
     };
 
-    let mut transcript =
-        Transcript::new_zk_elgamal_transcript(b"grouped-ciphertext-validity-3-handles-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"grouped-ciphertext-validity-3-handles-instruction",
+        statement_context,
+    );
 
     let proof = GroupedCiphertext3HandlesValidityProof::new(
         first_pubkey,
@@ -62,10 +87,40 @@ pub fn build_grouped_ciphertext_3_handles_validity_proof_data(
     Ok(GroupedCiphertext3HandlesValidityProofData { context, proof })
 }
 
+/// Generates the validity proof data for a grouped ciphertext of a transfer amount.
+///
+/// The handles of the grouped ciphertext pertain to the sender, the receiver, and the auditor, in
+/// that order.
+pub fn build_transfer_ciphertext_validity_proof_data(
+    sender_pubkey: &SenderElGamalPubkey,
+    receiver_pubkey: &ReceiverElGamalPubkey,
+    auditor_pubkey: &AuditorElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<3>,
+    amount: u64,
+    opening: &PedersenOpening,
+) -> Result<GroupedCiphertext3HandlesValidityProofData, ProofGenerationError> {
+    build_grouped_ciphertext_3_handles_validity_proof_data(
+        sender_pubkey.pubkey(),
+        receiver_pubkey.pubkey(),
+        auditor_pubkey.pubkey(),
+        grouped_ciphertext,
+        amount,
+        opening,
+    )
+}
+
 impl VerifyZkProof for GroupedCiphertext3HandlesValidityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
             b"grouped-ciphertext-validity-3-handles-instruction",
+            statement_context,
         );
 
         let first_pubkey = self.context.first_pubkey.try_into()?;
@@ -93,7 +148,10 @@ mod test {
     use {
         super::*,
         crate::encryption::{
-            elgamal::ElGamalKeypair, grouped_elgamal::GroupedElGamal, pedersen::PedersenOpening,
+            elgamal::ElGamalKeypair,
+            grouped_elgamal::GroupedElGamal,
+            pedersen::PedersenOpening,
+            role::{AuditorElGamalKeypair, ReceiverElGamalKeypair, SenderElGamalKeypair},
         },
     };
 
@@ -139,4 +197,39 @@ mod test {
         );
         assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
     }
+
+    #[test]
+    fn test_transfer_ciphertext_validity_proof_instruction_correctness() {
+        let sender_keypair = SenderElGamalKeypair::new(ElGamalKeypair::new_rand());
+        let receiver_keypair = ReceiverElGamalKeypair::new(ElGamalKeypair::new_rand());
+        let auditor_keypair = AuditorElGamalKeypair::new(ElGamalKeypair::new_rand());
+
+        let amount: u64 = 55;
+        let opening = PedersenOpening::new_rand();
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(
+            [
+                sender_keypair.keypair().pubkey(),
+                receiver_keypair.keypair().pubkey(),
+                auditor_keypair.keypair().pubkey(),
+            ],
+            amount,
+            &opening,
+        );
+
+        let proof_data = build_transfer_ciphertext_validity_proof_data(
+            &sender_keypair.pubkey(),
+            &receiver_keypair.pubkey(),
+            &auditor_keypair.pubkey(),
+            &grouped_ciphertext,
+            amount,
+            &opening,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+        assert_eq!(
+            proof_data.context.third_pubkey,
+            PodElGamalPubkey(auditor_keypair.keypair().pubkey().into())
+        );
+    }
 }
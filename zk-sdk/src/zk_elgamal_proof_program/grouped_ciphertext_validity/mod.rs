@@ -3,5 +3,12 @@ mod handles_3;
 
 pub use {
     handles_2::build_grouped_ciphertext_2_handles_validity_proof_data,
-    handles_3::build_grouped_ciphertext_3_handles_validity_proof_data,
+    handles_3::{
+        build_grouped_ciphertext_3_handles_validity_proof_data,
+        build_transfer_ciphertext_validity_proof_data,
+    },
+};
+pub(crate) use {
+    handles_2::build_grouped_ciphertext_2_handles_validity_proof_data_with_statement_context,
+    handles_3::build_grouped_ciphertext_3_handles_validity_proof_data_with_statement_context,
 };
@@ -0,0 +1,144 @@
+use {
+    crate::{
+        encryption::{
+            elgamal::ElGamalPubkey,
+            grouped_elgamal::{GroupedElGamal, GroupedElGamalCiphertext},
+            pedersen::PedersenOpening,
+        },
+        sigma_proofs::grouped_zero_ciphertext::GroupedZeroCiphertextProof,
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            VerifyZkProof,
+        },
+    },
+    merlin::Transcript,
+    solana_zk_elgamal_proof_interface::proof_data::{
+        GroupedZeroCiphertext2HandlesProofContext, GroupedZeroCiphertext2HandlesProofData,
+    },
+    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+};
+
+pub fn build_grouped_zero_ciphertext_2_handles_proof_data(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+    opening: &PedersenOpening,
+) -> Result<GroupedZeroCiphertext2HandlesProofData, ProofGenerationError> {
+    build_grouped_zero_ciphertext_2_handles_proof_data_with_statement_context(
+        first_pubkey,
+        second_pubkey,
+        grouped_ciphertext,
+        opening,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_grouped_zero_ciphertext_2_handles_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+pub(crate) fn build_grouped_zero_ciphertext_2_handles_proof_data_with_statement_context(
+    first_pubkey: &ElGamalPubkey,
+    second_pubkey: &ElGamalPubkey,
+    grouped_ciphertext: &GroupedElGamalCiphertext<2>,
+    opening: &PedersenOpening,
+    statement_context: Option<&[u8]>,
+) -> Result<GroupedZeroCiphertext2HandlesProofData, ProofGenerationError> {
+    let expected_ciphertext =
+        GroupedElGamal::encrypt_with([first_pubkey, second_pubkey], 0_u64, opening);
+    if *grouped_ciphertext != expected_ciphertext {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = GroupedZeroCiphertext2HandlesProofContext {
+        first_pubkey: PodElGamalPubkey(first_pubkey.into()),
+        second_pubkey: PodElGamalPubkey(second_pubkey.into()),
+        grouped_ciphertext: (*grouped_ciphertext).into(),
+    };
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"grouped-zero-ciphertext-2-handles-instruction",
+        statement_context,
+    );
+
+    let proof = GroupedZeroCiphertextProof::new(
+        [first_pubkey, second_pubkey],
+        grouped_ciphertext,
+        opening,
+        &mut transcript,
+    )
+    .into();
+
+    Ok(GroupedZeroCiphertext2HandlesProofData { context, proof })
+}
+
+impl VerifyZkProof for GroupedZeroCiphertext2HandlesProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"grouped-zero-ciphertext-2-handles-instruction",
+            statement_context,
+        );
+
+        let first_pubkey = self.context.first_pubkey.try_into()?;
+        let second_pubkey = self.context.second_pubkey.try_into()?;
+        let grouped_ciphertext: GroupedElGamalCiphertext<2> =
+            self.context.grouped_ciphertext.try_into()?;
+
+        let proof: GroupedZeroCiphertextProof<2> = self.proof.try_into()?;
+
+        proof
+            .verify(
+                [&first_pubkey, &second_pubkey],
+                &grouped_ciphertext,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_grouped_zero_ciphertext_2_handles_instruction_correctness() {
+        let first_keypair = ElGamalKeypair::new_rand();
+        let second_keypair = ElGamalKeypair::new_rand();
+
+        let opening = PedersenOpening::new_rand();
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(
+            [first_keypair.pubkey(), second_keypair.pubkey()],
+            0_u64,
+            &opening,
+        );
+
+        let proof_data = build_grouped_zero_ciphertext_2_handles_proof_data(
+            first_keypair.pubkey(),
+            second_keypair.pubkey(),
+            &grouped_ciphertext,
+            &opening,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        let grouped_ciphertext = GroupedElGamal::encrypt_with(
+            [first_keypair.pubkey(), second_keypair.pubkey()],
+            1_u64,
+            &opening,
+        );
+        let result = build_grouped_zero_ciphertext_2_handles_proof_data(
+            first_keypair.pubkey(),
+            second_keypair.pubkey(),
+            &grouped_ciphertext,
+            &opening,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
+}
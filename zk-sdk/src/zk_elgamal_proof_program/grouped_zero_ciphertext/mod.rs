@@ -0,0 +1,11 @@
+mod handles_2;
+mod handles_3;
+
+pub use {
+    handles_2::build_grouped_zero_ciphertext_2_handles_proof_data,
+    handles_3::build_grouped_zero_ciphertext_3_handles_proof_data,
+};
+pub(crate) use {
+    handles_2::build_grouped_zero_ciphertext_2_handles_proof_data_with_statement_context,
+    handles_3::build_grouped_zero_ciphertext_3_handles_proof_data_with_statement_context,
+};
@@ -0,0 +1,97 @@
+//! Key-private ciphertext validity proofs.
+//!
+//! A key-private validity proof certifies that a ciphertext is well-formed under one of the public
+//! keys of an anonymity set without revealing which one, so that a transfer can hide its
+//! recipient among the published keys. See [`KeyPrivateValidityProof`] for details.
+//!
+//! The anonymity set has no fixed size, so these proofs are not supported by the ZK ElGamal proof
+//! program and must be verified by the application that publishes the set.
+
+pub use crate::sigma_proofs::{
+    errors::{KeyPrivateValidityProofGenerationError, KeyPrivateValidityProofVerificationError},
+    key_private_validity::{KeyPrivateValidityProof, MAX_ANONYMITY_SET_SIZE},
+};
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::PedersenOpening,
+        },
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::errors::ProofVerificationError,
+    },
+    merlin::Transcript,
+};
+
+/// Generates a key-private validity proof for a ciphertext that is encrypted under
+/// `anonymity_set[index]`.
+///
+/// The proof is generated over a fresh transcript and verifies with
+/// [`verify_key_private_validity_proof`].
+pub fn build_key_private_validity_proof(
+    anonymity_set: &[ElGamalPubkey],
+    index: usize,
+    ciphertext: &ElGamalCiphertext,
+    amount: u64,
+    opening: &PedersenOpening,
+) -> Result<KeyPrivateValidityProof, KeyPrivateValidityProofGenerationError> {
+    let mut transcript = Transcript::new_zk_elgamal_transcript(b"key-private-validity-instruction");
+    KeyPrivateValidityProof::new(
+        anonymity_set,
+        index,
+        ciphertext,
+        amount,
+        opening,
+        &mut transcript,
+    )
+}
+
+/// Verifies a key-private validity proof that was generated with
+/// [`build_key_private_validity_proof`].
+pub fn verify_key_private_validity_proof(
+    anonymity_set: &[ElGamalPubkey],
+    ciphertext: &ElGamalCiphertext,
+    proof: KeyPrivateValidityProof,
+) -> Result<(), ProofVerificationError> {
+    let mut transcript = Transcript::new_zk_elgamal_transcript(b"key-private-validity-instruction");
+    proof
+        .verify(anonymity_set, ciphertext, &mut transcript)
+        .map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair, zk_elgamal_proof_program::errors::SigmaProofType,
+        },
+    };
+
+    #[test]
+    fn test_key_private_validity_proof() {
+        let keypairs: Vec<_> = (0..3).map(|_| ElGamalKeypair::new_rand()).collect();
+        let anonymity_set: Vec<_> = keypairs.iter().map(|keypair| *keypair.pubkey()).collect();
+
+        let amount = 55_u64;
+        let opening = PedersenOpening::new_rand();
+        let ciphertext = anonymity_set[1].encrypt_with(amount, &opening);
+
+        let proof =
+            build_key_private_validity_proof(&anonymity_set, 1, &ciphertext, amount, &opening)
+                .unwrap();
+        assert!(
+            verify_key_private_validity_proof(&anonymity_set, &ciphertext, proof.clone()).is_ok()
+        );
+
+        // a different ciphertext does not verify
+        let other_ciphertext = anonymity_set[1].encrypt_with(amount, &PedersenOpening::new_rand());
+        assert!(matches!(
+            verify_key_private_validity_proof(&anonymity_set, &other_ciphertext, proof),
+            Err(ProofVerificationError::SigmaProof(
+                SigmaProofType::ValidityProof,
+                _
+            ))
+        ));
+    }
+}
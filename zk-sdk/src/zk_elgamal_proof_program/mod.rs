@@ -1,21 +1,185 @@
-use crate::zk_elgamal_proof_program::errors::ProofVerificationError;
+use {
+    crate::{
+        msm::{MultiscalarMulBackend, MultiscalarMulBuffer},
+        rng::verifier_weight,
+        zk_elgamal_proof_program::errors::ProofVerificationError,
+    },
+    curve25519_dalek::{scalar::Scalar, traits::IsIdentity},
+};
 
+pub mod accumulator;
+pub mod asset;
 pub mod batched_grouped_ciphertext_validity;
 pub mod batched_range_proof;
+pub mod builder;
 pub mod ciphertext_ciphertext_equality;
 pub mod ciphertext_commitment_equality;
+#[cfg(feature = "unstable")]
+pub mod ciphertext_commitment_opening_equality;
 pub mod errors;
+#[cfg(feature = "unstable")]
+pub mod grouped_ciphertext_ciphertext_equality;
 pub mod grouped_ciphertext_validity;
+#[cfg(feature = "unstable")]
+pub mod grouped_zero_ciphertext;
+pub mod key_private_validity;
 pub mod percentage_with_cap;
+#[cfg(feature = "unstable")]
+pub mod percentage_with_cap_split;
+pub mod prepared;
+pub mod proof_batch;
 pub mod pubkey_validity;
+#[cfg(feature = "unstable")]
+pub mod split_consistency;
+pub mod stateless;
 pub mod zero_ciphertext;
 
 pub use {
-    batched_grouped_ciphertext_validity::*, batched_range_proof::*,
+    accumulator::*, asset::*, batched_grouped_ciphertext_validity::*, batched_range_proof::*,
     ciphertext_ciphertext_equality::*, ciphertext_commitment_equality::*,
-    grouped_ciphertext_validity::*, percentage_with_cap::*, pubkey_validity::*, zero_ciphertext::*,
+    grouped_ciphertext_validity::*, key_private_validity::*, percentage_with_cap::*, prepared::*,
+    proof_batch::*, pubkey_validity::*, stateless::*, zero_ciphertext::*,
+};
+#[cfg(feature = "unstable")]
+pub use {
+    ciphertext_commitment_opening_equality::*, grouped_ciphertext_ciphertext_equality::*,
+    grouped_zero_ciphertext::*, percentage_with_cap_split::*, split_consistency::*,
 };
 
 pub trait VerifyZkProof {
     fn verify_proof(&self) -> Result<(), ProofVerificationError>;
+
+    /// Verifies the proof with `statement_context`, if any, bound into the transcript of the
+    /// proof.
+    ///
+    /// Proof data that is generated with a statement context verifies only with the same
+    /// statement context. Proof types that do not support a statement context reject any
+    /// statement context.
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        match statement_context {
+            None => self.verify_proof(),
+            Some(_) => Err(ProofVerificationError::ProofContext),
+        }
+    }
+}
+
+/// Proof data whose verification equation can be checked in a multiscalar multiplication that
+/// is shared with other proofs.
+pub trait AppendVerificationTerms: VerifyZkProof {
+    /// The maximum number of multiscalar multiplication terms in the verification equation.
+    const MAX_VERIFICATION_TERMS: usize;
+
+    /// Appends the terms of the verification equation to `buffer`, with `statement_context`, if
+    /// any, bound into the transcript of the proof.
+    ///
+    /// The proof is valid if and only if the appended terms sum to the identity.
+    fn append_verification_terms(
+        &self,
+        buffer: &mut MultiscalarMulBuffer,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError>;
+}
+
+/// A verifier that checks several proofs with a single multiscalar multiplication.
+///
+/// The verification equation of each appended proof is scaled by a fresh random weight, so the
+/// combined equation holds only if every individual equation holds, except with negligible
+/// probability. The verifier does not allocate; the terms of all proofs are written to the
+/// caller-provided buffer.
+pub struct CombinedProofVerifier<'a> {
+    buffer: MultiscalarMulBuffer<'a>,
+    statement_context: Option<&'a [u8]>,
+}
+
+impl<'a> CombinedProofVerifier<'a> {
+    /// Creates a verifier that collects the verification terms in `buffer`.
+    pub fn new(buffer: MultiscalarMulBuffer<'a>) -> Self {
+        Self::new_with_statement_context(buffer, None)
+    }
+
+    /// Creates a verifier that collects the verification terms in `buffer` and verifies every
+    /// appended proof with `statement_context`, if any.
+    pub fn new_with_statement_context(
+        buffer: MultiscalarMulBuffer<'a>,
+        statement_context: Option<&'a [u8]>,
+    ) -> Self {
+        Self {
+            buffer,
+            statement_context,
+        }
+    }
+
+    /// Appends the verification equation of `proof_data`.
+    ///
+    /// The checks of the proof that do not depend on the multiscalar multiplication, such as the
+    /// validation of the proof context, are performed immediately.
+    ///
+    /// This function is randomized. It uses `OsRng` internally to generate the weight of the
+    /// equation.
+    pub fn append<T: AppendVerificationTerms>(
+        &mut self,
+        proof_data: &T,
+    ) -> Result<(), ProofVerificationError> {
+        self.append_with_statement_context(proof_data, self.statement_context)
+    }
+
+    /// Appends the verification equation of `proof_data` with `statement_context`, if any, in
+    /// place of the statement context of the verifier.
+    ///
+    /// See [`Self::append`].
+    pub fn append_with_statement_context<T: AppendVerificationTerms>(
+        &mut self,
+        proof_data: &T,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let start = self.buffer.len();
+        proof_data.append_verification_terms(&mut self.buffer, statement_context)?;
+
+        // the first equation is left unscaled, as only the relative weights matter
+        if start > 0 {
+            let weight = verifier_weight();
+            self.buffer.scale_from(start, &weight);
+        }
+        Ok(())
+    }
+
+    /// Checks the combined verification equation of all appended proofs.
+    pub fn verify(self, backend: &dyn MultiscalarMulBackend) -> Result<(), ProofVerificationError> {
+        if self.buffer.multiscalar_mul(backend).is_identity() {
+            Ok(())
+        } else {
+            Err(ProofVerificationError::CombinedAlgebraicRelation)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_statement_context() {
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data =
+            build_pubkey_validity_proof_data_with_statement_context(&keypair, Some(b"request-1"))
+                .unwrap();
+
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"request-1"))
+            .is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"request-2"))
+            .is_err());
+        assert!(proof_data.verify_proof().is_err());
+
+        // proof data that is generated without a statement context is unchanged
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+        assert!(proof_data.verify_proof().is_ok());
+        assert!(proof_data
+            .verify_proof_with_statement_context(Some(b"request-1"))
+            .is_err());
+    }
 }
@@ -1,7 +1,11 @@
+#[cfg(feature = "unstable")]
+use solana_zk_elgamal_proof_interface::proof_data::{
+    PercentageWithCapU128ProofContext, PercentageWithCapU128ProofData,
+};
 use {
     crate::{
         encryption::pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
-        sigma_proofs::percentage_with_cap::PercentageWithCapProof,
+        sigma_proofs::percentage_with_cap::{PercentageWithCapProof, PercentageWithCapStatement},
         transcript::TranscriptProtocol,
         zk_elgamal_proof_program::{
             errors::{ProofGenerationError, ProofVerificationError},
@@ -10,7 +14,7 @@ use {
     },
     merlin::Transcript,
     solana_zk_elgamal_proof_interface::proof_data::{
-        PercentageWithCapProofContext, PercentageWithCapProofData,
+        PercentageWithCapProofContext, PercentageWithCapProofData, MAX_PROOF_BATCH_SIZE,
     },
     solana_zk_sdk_pod::encryption::pedersen::PodPedersenCommitment,
     std::convert::TryInto,
@@ -27,6 +31,35 @@ pub fn build_percentage_with_cap_proof_data(
     claimed_commitment: &PedersenCommitment,
     claimed_opening: &PedersenOpening,
     max_value: u64,
+) -> Result<PercentageWithCapProofData, ProofGenerationError> {
+    build_percentage_with_cap_proof_data_with_statement_context(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_percentage_with_cap_proof_data`] with `statement_context`,
+/// if any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_percentage_with_cap_proof_data_with_statement_context(
+    percentage_commitment: &PedersenCommitment,
+    percentage_opening: &PedersenOpening,
+    percentage_amount: u64,
+    delta_commitment: &PedersenCommitment,
+    delta_opening: &PedersenOpening,
+    delta_amount: u64,
+    claimed_commitment: &PedersenCommitment,
+    claimed_opening: &PedersenOpening,
+    max_value: u64,
+    statement_context: Option<&[u8]>,
 ) -> Result<PercentageWithCapProofData, ProofGenerationError> {
     // Verify percentage commitment
     if *percentage_commitment != Pedersen::with(percentage_amount, percentage_opening) {
@@ -50,7 +83,10 @@ pub fn build_percentage_with_cap_proof_data(
         max_value: pod_max_value,
     };
 
-    let mut transcript = Transcript::new_zk_elgamal_transcript(b"percentage-with-cap-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"percentage-with-cap-instruction",
+        statement_context,
+    );
 
     let proof = PercentageWithCapProof::new(
         percentage_commitment,
@@ -71,8 +107,17 @@ pub fn build_percentage_with_cap_proof_data(
 
 impl VerifyZkProof for PercentageWithCapProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript =
-            Transcript::new_zk_elgamal_transcript(b"percentage-with-cap-instruction");
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"percentage-with-cap-instruction",
+            statement_context,
+        );
 
         let percentage_commitment = self.context.percentage_commitment.try_into()?;
         let delta_commitment = self.context.delta_commitment.try_into()?;
@@ -92,6 +137,162 @@ impl VerifyZkProof for PercentageWithCapProofData {
     }
 }
 
+/// Verifies a batch of percentage-with-cap proofs with a single multiscalar multiplication.
+///
+/// All proofs must be with respect to the same maximum cap bound, as is the case for the fee
+/// proofs of transfers of a mint with a fixed fee rate and cap. Returns
+/// [`ProofVerificationError::ProofBatch`] if the batch is empty, exceeds the maximum batch size,
+/// or mixes maximum cap bounds.
+///
+/// This function is randomized. It uses `OsRng` internally to generate the weights that combine
+/// the proofs.
+pub fn verify_percentage_with_cap_proof_batch(
+    proof_data: &[PercentageWithCapProofData],
+) -> Result<(), ProofVerificationError> {
+    let Some(first) = proof_data.first() else {
+        return Err(ProofVerificationError::ProofBatch);
+    };
+    let max_value: u64 = first.context.max_value.into();
+    if proof_data.len() > MAX_PROOF_BATCH_SIZE
+        || proof_data
+            .iter()
+            .any(|proof_data| u64::from(proof_data.context.max_value) != max_value)
+    {
+        return Err(ProofVerificationError::ProofBatch);
+    }
+
+    let mut proofs = Vec::with_capacity(proof_data.len());
+    let mut statements = Vec::with_capacity(proof_data.len());
+    for proof_data in proof_data {
+        proofs.push(PercentageWithCapProof::try_from(proof_data.proof)?);
+        statements.push(PercentageWithCapStatement {
+            percentage_commitment: proof_data.context.percentage_commitment.try_into()?,
+            delta_commitment: proof_data.context.delta_commitment.try_into()?,
+            claimed_commitment: proof_data.context.claimed_commitment.try_into()?,
+        });
+    }
+    let transcript = Transcript::new_zk_elgamal_transcript(b"percentage-with-cap-instruction");
+    let mut transcripts = vec![transcript; proof_data.len()];
+
+    PercentageWithCapProof::verify_batch(&proofs, &statements, max_value, &mut transcripts)
+        .map_err(|e| e.into())
+}
+
+/// Builds the proof data for a percentage-with-cap proof with a 128-bit maximum cap bound.
+#[cfg(feature = "unstable")]
+#[allow(clippy::too_many_arguments)]
+pub fn build_percentage_with_cap_u128_proof_data(
+    percentage_commitment: &PedersenCommitment,
+    percentage_opening: &PedersenOpening,
+    percentage_amount: u64,
+    delta_commitment: &PedersenCommitment,
+    delta_opening: &PedersenOpening,
+    delta_amount: u64,
+    claimed_commitment: &PedersenCommitment,
+    claimed_opening: &PedersenOpening,
+    max_value: u128,
+) -> Result<PercentageWithCapU128ProofData, ProofGenerationError> {
+    build_percentage_with_cap_u128_proof_data_with_statement_context(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_percentage_with_cap_u128_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+#[cfg(feature = "unstable")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_percentage_with_cap_u128_proof_data_with_statement_context(
+    percentage_commitment: &PedersenCommitment,
+    percentage_opening: &PedersenOpening,
+    percentage_amount: u64,
+    delta_commitment: &PedersenCommitment,
+    delta_opening: &PedersenOpening,
+    delta_amount: u64,
+    claimed_commitment: &PedersenCommitment,
+    claimed_opening: &PedersenOpening,
+    max_value: u128,
+    statement_context: Option<&[u8]>,
+) -> Result<PercentageWithCapU128ProofData, ProofGenerationError> {
+    // Verify percentage commitment
+    if *percentage_commitment != Pedersen::with(percentage_amount, percentage_opening) {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+    // Verify claimed commitment
+    if *claimed_commitment != Pedersen::with(delta_amount, claimed_opening) {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = PercentageWithCapU128ProofContext {
+        percentage_commitment: PodPedersenCommitment(percentage_commitment.to_bytes()),
+        delta_commitment: PodPedersenCommitment(delta_commitment.to_bytes()),
+        claimed_commitment: PodPedersenCommitment(claimed_commitment.to_bytes()),
+        max_value: max_value.into(),
+    };
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"percentage-with-cap-u128-instruction",
+        statement_context,
+    );
+
+    let proof = PercentageWithCapProof::new_u128(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+        &mut transcript,
+    )
+    .into();
+
+    Ok(PercentageWithCapU128ProofData { context, proof })
+}
+
+#[cfg(feature = "unstable")]
+impl VerifyZkProof for PercentageWithCapU128ProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"percentage-with-cap-u128-instruction",
+            statement_context,
+        );
+
+        let percentage_commitment = self.context.percentage_commitment.try_into()?;
+        let delta_commitment = self.context.delta_commitment.try_into()?;
+        let claimed_commitment = self.context.claimed_commitment.try_into()?;
+        let max_value = self.context.max_value.into();
+        let proof: PercentageWithCapProof = self.proof.try_into()?;
+
+        proof
+            .verify_u128(
+                &percentage_commitment,
+                &delta_commitment,
+                &claimed_commitment,
+                max_value,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {super::*, crate::encryption::pedersen::Pedersen, curve25519_dalek::scalar::Scalar};
@@ -204,4 +405,100 @@ mod test {
 
         assert!(proof_data.verify_proof().is_ok());
     }
+
+    #[test]
+    fn test_verify_percentage_with_cap_proof_batch() {
+        let max_value: u64 = 3;
+        let proof_data = [1_u64, 2, 3]
+            .into_iter()
+            .map(|percentage_amount| {
+                let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+                let (delta_commitment, delta_opening) = Pedersen::new(0_u64);
+                let (claimed_commitment, claimed_opening) = Pedersen::new(0_u64);
+                build_percentage_with_cap_proof_data(
+                    &percentage_commitment,
+                    &percentage_opening,
+                    percentage_amount,
+                    &delta_commitment,
+                    &delta_opening,
+                    0,
+                    &claimed_commitment,
+                    &claimed_opening,
+                    max_value,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert!(verify_percentage_with_cap_proof_batch(&proof_data).is_ok());
+
+        let mut invalid_proof_data = proof_data.clone();
+        invalid_proof_data[1].context.claimed_commitment =
+            invalid_proof_data[2].context.percentage_commitment;
+        assert!(verify_percentage_with_cap_proof_batch(&invalid_proof_data).is_err());
+
+        // proofs with respect to different maximum cap bounds are not batched
+        let mut mixed_proof_data = proof_data;
+        mixed_proof_data[2].context.max_value = 4_u64.into();
+        assert_eq!(
+            verify_percentage_with_cap_proof_batch(&mixed_proof_data),
+            Err(ProofVerificationError::ProofBatch)
+        );
+        assert_eq!(
+            verify_percentage_with_cap_proof_batch(&[]),
+            Err(ProofVerificationError::ProofBatch)
+        );
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_percentage_with_cap_u128_instruction_correctness() {
+        // percentage amount is capped at a max value that does not fit in 64 bits
+        let max_value: u128 = 1 << 100;
+        let percentage_amount: u64 = u64::MAX;
+        let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+        let delta_amount: u64 = 0;
+        let (delta_commitment, delta_opening) = Pedersen::new(delta_amount);
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+
+        let proof_data = build_percentage_with_cap_u128_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        // percentage amount is equal to a max value that fits in 64 bits
+        let max_value: u128 = 3;
+        let percentage_amount: u64 = 3;
+        let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+        let delta_amount: u64 = 100;
+        let (delta_commitment, delta_opening) = Pedersen::new(delta_amount);
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+
+        let mut proof_data = build_percentage_with_cap_u128_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        proof_data.context.max_value = (max_value + (1 << 64)).into();
+        assert!(proof_data.verify_proof().is_err());
+    }
 }
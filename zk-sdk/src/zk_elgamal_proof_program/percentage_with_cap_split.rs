@@ -0,0 +1,320 @@
+use {
+    crate::{
+        encryption::pedersen::{Pedersen, PedersenCommitment, PedersenOpening},
+        sigma_proofs::percentage_with_cap_split::PercentageWithCapSplitProof,
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            VerifyZkProof,
+        },
+    },
+    bytemuck::Zeroable,
+    curve25519_dalek::traits::IsIdentity,
+    merlin::Transcript,
+    solana_zk_elgamal_proof_interface::proof_data::{
+        PercentageWithCapSplitProofContext, PercentageWithCapSplitProofData, MAX_FEE_SHARES,
+    },
+    solana_zk_sdk_pod::encryption::pedersen::PodPedersenCommitment,
+    std::convert::TryInto,
+};
+
+/// Builds the proof data for a percentage-with-cap fee-split proof.
+///
+/// The fee share commitments, amounts, and openings must have the same length, which must be
+/// between 1 and `MAX_FEE_SHARES`. The fee share amounts must add up to `percentage_amount`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_percentage_with_cap_split_proof_data(
+    percentage_commitment: &PedersenCommitment,
+    percentage_opening: &PedersenOpening,
+    percentage_amount: u64,
+    delta_commitment: &PedersenCommitment,
+    delta_opening: &PedersenOpening,
+    delta_amount: u64,
+    claimed_commitment: &PedersenCommitment,
+    claimed_opening: &PedersenOpening,
+    max_value: u64,
+    share_commitments: &[&PedersenCommitment],
+    share_amounts: &[u64],
+    share_openings: &[&PedersenOpening],
+) -> Result<PercentageWithCapSplitProofData, ProofGenerationError> {
+    build_percentage_with_cap_split_proof_data_with_statement_context(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+        share_commitments,
+        share_amounts,
+        share_openings,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_percentage_with_cap_split_proof_data`] with
+/// `statement_context`, if any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_percentage_with_cap_split_proof_data_with_statement_context(
+    percentage_commitment: &PedersenCommitment,
+    percentage_opening: &PedersenOpening,
+    percentage_amount: u64,
+    delta_commitment: &PedersenCommitment,
+    delta_opening: &PedersenOpening,
+    delta_amount: u64,
+    claimed_commitment: &PedersenCommitment,
+    claimed_opening: &PedersenOpening,
+    max_value: u64,
+    share_commitments: &[&PedersenCommitment],
+    share_amounts: &[u64],
+    share_openings: &[&PedersenOpening],
+    statement_context: Option<&[u8]>,
+) -> Result<PercentageWithCapSplitProofData, ProofGenerationError> {
+    let num_shares = share_commitments.len();
+    if num_shares == 0
+        || num_shares > MAX_FEE_SHARES
+        || num_shares != share_amounts.len()
+        || num_shares != share_openings.len()
+    {
+        return Err(ProofGenerationError::IllegalCommitmentLength);
+    }
+
+    // Verify percentage commitment
+    if *percentage_commitment != Pedersen::with(percentage_amount, percentage_opening) {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+    // Verify claimed commitment
+    if *claimed_commitment != Pedersen::with(delta_amount, claimed_opening) {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+    // Verify share commitments and that the shares add up to the percentage amount
+    let mut share_sum: u64 = 0;
+    for ((commitment, amount), opening) in share_commitments
+        .iter()
+        .zip(share_amounts.iter())
+        .zip(share_openings.iter())
+    {
+        if commitment.get_point().is_identity() {
+            return Err(ProofGenerationError::InvalidCommitment);
+        }
+        if **commitment != Pedersen::with(*amount, opening) {
+            return Err(ProofGenerationError::InconsistentInput);
+        }
+        share_sum = share_sum
+            .checked_add(*amount)
+            .ok_or(ProofGenerationError::InconsistentInput)?;
+    }
+    if share_sum != percentage_amount {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let mut pod_share_commitments = [PodPedersenCommitment::zeroed(); MAX_FEE_SHARES];
+    for (pod_commitment, commitment) in pod_share_commitments
+        .iter_mut()
+        .zip(share_commitments.iter())
+    {
+        *pod_commitment = PodPedersenCommitment(commitment.to_bytes());
+    }
+
+    let context = PercentageWithCapSplitProofContext {
+        percentage_commitment: PodPedersenCommitment(percentage_commitment.to_bytes()),
+        delta_commitment: PodPedersenCommitment(delta_commitment.to_bytes()),
+        claimed_commitment: PodPedersenCommitment(claimed_commitment.to_bytes()),
+        max_value: max_value.into(),
+        share_commitments: pod_share_commitments,
+    };
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"percentage-with-cap-split-instruction",
+        statement_context,
+    );
+
+    let proof = PercentageWithCapSplitProof::new(
+        percentage_commitment,
+        percentage_opening,
+        percentage_amount,
+        delta_commitment,
+        delta_opening,
+        delta_amount,
+        claimed_commitment,
+        claimed_opening,
+        max_value,
+        share_commitments,
+        share_openings,
+        &mut transcript,
+    )
+    .into();
+
+    Ok(PercentageWithCapSplitProofData { context, proof })
+}
+
+impl VerifyZkProof for PercentageWithCapSplitProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"percentage-with-cap-split-instruction",
+            statement_context,
+        );
+
+        let percentage_commitment = self.context.percentage_commitment.try_into()?;
+        let delta_commitment = self.context.delta_commitment.try_into()?;
+        let claimed_commitment = self.context.claimed_commitment.try_into()?;
+        let max_value = self.context.max_value.into();
+
+        let share_commitments = self
+            .context
+            .share_commitments
+            .into_iter()
+            .take_while(|commitment| *commitment != PodPedersenCommitment::zeroed())
+            .map(|commitment| commitment.try_into())
+            .collect::<Result<Vec<PedersenCommitment>, _>>()
+            .map_err(|_| ProofVerificationError::ProofContext)?;
+
+        if share_commitments.is_empty() {
+            return Err(ProofVerificationError::IllegalCommitmentLength);
+        }
+
+        // Ensure that all ignored data in the context (the "tail") is strictly zero.
+        if self.context.share_commitments[share_commitments.len()..]
+            .iter()
+            .any(|commitment| *commitment != PodPedersenCommitment::zeroed())
+        {
+            return Err(ProofVerificationError::ProofContext);
+        }
+
+        let share_commitments: Vec<_> = share_commitments.iter().collect();
+        let proof: PercentageWithCapSplitProof = self.proof.try_into()?;
+
+        proof
+            .verify(
+                &percentage_commitment,
+                &delta_commitment,
+                &claimed_commitment,
+                max_value,
+                &share_commitments,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::pedersen::Pedersen};
+
+    #[test]
+    fn test_percentage_with_cap_split_instruction_correctness() {
+        let transfer_amount: u64 = 10_000;
+        let max_value: u64 = 1_000;
+
+        let percentage_rate: u64 = 300;
+        let percentage_amount: u64 = 300;
+        let delta_amount: u64 = 0;
+
+        let (transfer_commitment, transfer_opening) = Pedersen::new(transfer_amount);
+        let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+
+        let delta_commitment =
+            &percentage_commitment * 10_000 - &transfer_commitment * percentage_rate;
+        let delta_opening = &percentage_opening * 10_000 - &transfer_opening * percentage_rate;
+
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+
+        let (protocol_commitment, protocol_opening) = Pedersen::new(200_u64);
+        let (referrer_commitment, referrer_opening) = Pedersen::new(100_u64);
+
+        let proof_data = build_percentage_with_cap_split_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+            &[&protocol_commitment, &referrer_commitment],
+            &[200, 100],
+            &[&protocol_opening, &referrer_opening],
+        )
+        .unwrap();
+
+        assert!(proof_data.verify_proof().is_ok());
+
+        // shares that do not add up to the fee are rejected by the prover
+        let result = build_percentage_with_cap_split_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+            &[&protocol_commitment],
+            &[200],
+            &[&protocol_opening],
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+
+        // too many shares
+        let result = build_percentage_with_cap_split_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+            &[&protocol_commitment; MAX_FEE_SHARES + 1],
+            &[200; MAX_FEE_SHARES + 1],
+            &[&protocol_opening; MAX_FEE_SHARES + 1],
+        );
+        assert_eq!(result, Err(ProofGenerationError::IllegalCommitmentLength));
+    }
+
+    #[test]
+    fn test_percentage_with_cap_split_instruction_non_zero_padding() {
+        let max_value: u64 = 3;
+        let percentage_amount: u64 = 3;
+        let (percentage_commitment, percentage_opening) = Pedersen::new(percentage_amount);
+        let delta_amount: u64 = 100;
+        let (delta_commitment, delta_opening) = Pedersen::new(delta_amount);
+        let (claimed_commitment, claimed_opening) = Pedersen::new(delta_amount);
+        let (share_commitment, share_opening) = Pedersen::new(percentage_amount);
+
+        let mut proof_data = build_percentage_with_cap_split_proof_data(
+            &percentage_commitment,
+            &percentage_opening,
+            percentage_amount,
+            &delta_commitment,
+            &delta_opening,
+            delta_amount,
+            &claimed_commitment,
+            &claimed_opening,
+            max_value,
+            &[&share_commitment],
+            &[percentage_amount],
+            &[&share_opening],
+        )
+        .unwrap();
+        assert!(proof_data.verify_proof().is_ok());
+
+        proof_data.context.share_commitments[2] = PodPedersenCommitment([1; 32]);
+        assert_eq!(
+            proof_data.verify_proof(),
+            Err(ProofVerificationError::ProofContext)
+        );
+    }
+}
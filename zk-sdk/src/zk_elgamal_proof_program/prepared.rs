@@ -0,0 +1,231 @@
+//! Prepared statements for repeated proof verification.
+//!
+//! Verifying proof data decompresses the points of its context on every call. Services that
+//! re-verify proofs about the same accounts, such as monitors that re-check balances every slot,
+//! can prepare the statement once and verify each new proof against the decompressed points.
+//! Only the points of the proof itself are decompressed on each verification.
+
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::PedersenCommitment,
+        },
+        sigma_proofs::{
+            ciphertext_commitment_equality::CiphertextCommitmentEqualityProof,
+            pubkey_validity::PubkeyValidityProof, zero_ciphertext::ZeroCiphertextProof,
+        },
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::errors::ProofVerificationError,
+    },
+    merlin::Transcript,
+    solana_zk_elgamal_proof_interface::proof_data::{
+        CiphertextCommitmentEqualityProofContext, CiphertextCommitmentEqualityProofData,
+        PubkeyValidityProofContext, PubkeyValidityProofData, ZeroCiphertextProofContext,
+        ZeroCiphertextProofData,
+    },
+    std::convert::TryInto,
+};
+
+/// A [`PubkeyValidityProofContext`] with a decompressed public key.
+#[derive(Clone, Debug)]
+pub struct PreparedPubkeyValidityStatement {
+    context: PubkeyValidityProofContext,
+    pubkey: ElGamalPubkey,
+}
+
+impl PreparedPubkeyValidityStatement {
+    /// Decompresses the points of a proof context.
+    pub fn new(context: &PubkeyValidityProofContext) -> Result<Self, ProofVerificationError> {
+        Ok(Self {
+            context: *context,
+            pubkey: context.pubkey.try_into()?,
+        })
+    }
+
+    /// The proof context of the statement.
+    pub fn context(&self) -> &PubkeyValidityProofContext {
+        &self.context
+    }
+
+    /// Verifies proof data against the statement.
+    ///
+    /// The verification is equivalent to `VerifyZkProof::verify_proof` on `proof_data`. Returns
+    /// [`ProofVerificationError::ProofContext`] if the context of `proof_data` is not the context
+    /// of the statement.
+    pub fn verify_proof(
+        &self,
+        proof_data: &PubkeyValidityProofData,
+    ) -> Result<(), ProofVerificationError> {
+        if proof_data.context != self.context {
+            return Err(ProofVerificationError::ProofContext);
+        }
+
+        let mut transcript = Transcript::new_zk_elgamal_transcript(b"pubkey-validity-instruction");
+        let proof: PubkeyValidityProof = proof_data.proof.try_into()?;
+        proof
+            .verify(&self.pubkey, &mut transcript)
+            .map_err(|e| e.into())
+    }
+}
+
+/// A [`ZeroCiphertextProofContext`] with a decompressed public key and ciphertext.
+#[derive(Clone, Debug)]
+pub struct PreparedZeroCiphertextStatement {
+    context: ZeroCiphertextProofContext,
+    pubkey: ElGamalPubkey,
+    ciphertext: ElGamalCiphertext,
+}
+
+impl PreparedZeroCiphertextStatement {
+    /// Decompresses the points of a proof context.
+    pub fn new(context: &ZeroCiphertextProofContext) -> Result<Self, ProofVerificationError> {
+        Ok(Self {
+            context: *context,
+            pubkey: context.pubkey.try_into()?,
+            ciphertext: context.ciphertext.try_into()?,
+        })
+    }
+
+    /// The proof context of the statement.
+    pub fn context(&self) -> &ZeroCiphertextProofContext {
+        &self.context
+    }
+
+    /// Verifies proof data against the statement.
+    ///
+    /// See [`PreparedPubkeyValidityStatement::verify_proof`] for details.
+    pub fn verify_proof(
+        &self,
+        proof_data: &ZeroCiphertextProofData,
+    ) -> Result<(), ProofVerificationError> {
+        if proof_data.context != self.context {
+            return Err(ProofVerificationError::ProofContext);
+        }
+
+        let mut transcript = Transcript::new_zk_elgamal_transcript(b"zero-ciphertext-instruction");
+        let proof: ZeroCiphertextProof = proof_data.proof.try_into()?;
+        proof
+            .verify(&self.pubkey, &self.ciphertext, &mut transcript)
+            .map_err(|e| e.into())
+    }
+}
+
+/// A [`CiphertextCommitmentEqualityProofContext`] with a decompressed public key, ciphertext, and
+/// commitment.
+#[derive(Clone, Debug)]
+pub struct PreparedCiphertextCommitmentEqualityStatement {
+    context: CiphertextCommitmentEqualityProofContext,
+    pubkey: ElGamalPubkey,
+    ciphertext: ElGamalCiphertext,
+    commitment: PedersenCommitment,
+}
+
+impl PreparedCiphertextCommitmentEqualityStatement {
+    /// Decompresses the points of a proof context.
+    pub fn new(
+        context: &CiphertextCommitmentEqualityProofContext,
+    ) -> Result<Self, ProofVerificationError> {
+        Ok(Self {
+            context: *context,
+            pubkey: context.pubkey.try_into()?,
+            ciphertext: context.ciphertext.try_into()?,
+            commitment: context.commitment.try_into()?,
+        })
+    }
+
+    /// The proof context of the statement.
+    pub fn context(&self) -> &CiphertextCommitmentEqualityProofContext {
+        &self.context
+    }
+
+    /// Verifies proof data against the statement.
+    ///
+    /// See [`PreparedPubkeyValidityStatement::verify_proof`] for details.
+    pub fn verify_proof(
+        &self,
+        proof_data: &CiphertextCommitmentEqualityProofData,
+    ) -> Result<(), ProofVerificationError> {
+        if proof_data.context != self.context {
+            return Err(ProofVerificationError::ProofContext);
+        }
+
+        let mut transcript =
+            Transcript::new_zk_elgamal_transcript(b"ciphertext-commitment-equality-instruction");
+        let proof: CiphertextCommitmentEqualityProof = proof_data.proof.try_into()?;
+        proof
+            .verify(
+                &self.pubkey,
+                &self.ciphertext,
+                &self.commitment,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+            zk_elgamal_proof_program::{
+                build_ciphertext_commitment_equality_proof_data, build_pubkey_validity_proof_data,
+                build_zero_ciphertext_proof_data, VerifyZkProof,
+            },
+        },
+    };
+
+    #[test]
+    fn test_prepared_statements() {
+        let keypair = ElGamalKeypair::new_rand();
+
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+        let statement = PreparedPubkeyValidityStatement::new(&proof_data.context).unwrap();
+        assert!(statement.verify_proof(&proof_data).is_ok());
+
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+        let statement = PreparedZeroCiphertextStatement::new(&proof_data.context).unwrap();
+        assert!(statement.verify_proof(&proof_data).is_ok());
+
+        // the statement is reused across proofs about the same account
+        let amount = 55_u64;
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+        let build = || {
+            build_ciphertext_commitment_equality_proof_data(
+                &keypair,
+                &ciphertext,
+                &commitment,
+                &opening,
+                amount,
+            )
+            .unwrap()
+        };
+        let first_proof_data = build();
+        let statement =
+            PreparedCiphertextCommitmentEqualityStatement::new(&first_proof_data.context).unwrap();
+        assert_eq!(statement.context(), &first_proof_data.context);
+        assert!(statement.verify_proof(&first_proof_data).is_ok());
+        let second_proof_data = build();
+        assert!(second_proof_data.verify_proof().is_ok());
+        assert!(statement.verify_proof(&second_proof_data).is_ok());
+
+        // a proof about a different statement is rejected
+        let (other_commitment, other_opening) = Pedersen::new(amount);
+        let other_proof_data = build_ciphertext_commitment_equality_proof_data(
+            &keypair,
+            &ciphertext,
+            &other_commitment,
+            &other_opening,
+            amount,
+        )
+        .unwrap();
+        assert_eq!(
+            statement.verify_proof(&other_proof_data),
+            Err(ProofVerificationError::ProofContext)
+        );
+    }
+}
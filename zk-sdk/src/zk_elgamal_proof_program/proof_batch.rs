@@ -0,0 +1,189 @@
+use {
+    crate::{
+        msm::{CpuMultiscalarMulBackend, MultiscalarMulBackend, MultiscalarMulBuffer},
+        zk_elgamal_proof_program::{
+            errors::ProofVerificationError, AppendVerificationTerms, CombinedProofVerifier,
+        },
+    },
+    curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::Identity},
+    solana_zk_elgamal_proof_interface::proof_data::MAX_PROOF_BATCH_SIZE,
+};
+#[cfg(feature = "unstable")]
+use {
+    bytemuck::Pod,
+    solana_zk_elgamal_proof_interface::{
+        instruction::ProofInstruction,
+        proof_data::{
+            BatchedGroupedCiphertext3HandlesValidityProofContext,
+            BatchedGroupedCiphertext3HandlesValidityProofData, BatchedRangeProofContext,
+            BatchedRangeProofU128Data, BatchedRangeProofU64Data,
+            CiphertextCommitmentEqualityProofContext, CiphertextCommitmentEqualityProofData,
+            ProofType, ZkProofData,
+        },
+    },
+};
+
+/// Verifies a batch of proofs of the same type with a single multiscalar multiplication.
+///
+/// This function is randomized. It uses `OsRng` internally to generate the weights that combine
+/// the proofs.
+pub fn verify_proof_batch<T: AppendVerificationTerms>(
+    proof_data: &[T],
+) -> Result<(), ProofVerificationError> {
+    verify_proof_batch_with_backend(proof_data, &CpuMultiscalarMulBackend)
+}
+
+/// Verifies a batch of proofs of the same type, computing the multiscalar multiplication with
+/// `backend`.
+pub fn verify_proof_batch_with_backend<T: AppendVerificationTerms>(
+    proof_data: &[T],
+    backend: &dyn MultiscalarMulBackend,
+) -> Result<(), ProofVerificationError> {
+    if proof_data.is_empty() || proof_data.len() > MAX_PROOF_BATCH_SIZE {
+        return Err(ProofVerificationError::ProofBatch);
+    }
+
+    let num_terms = T::MAX_VERIFICATION_TERMS * proof_data.len();
+    let mut scalars = vec![Scalar::ZERO; num_terms];
+    let mut points = vec![RistrettoPoint::identity(); num_terms];
+    let mut verifier =
+        CombinedProofVerifier::new(MultiscalarMulBuffer::new(&mut scalars, &mut points));
+    for proof_data in proof_data {
+        verifier.append(proof_data)?;
+    }
+    verifier.verify(backend)
+}
+
+/// Verifies the proofs in the instruction data of a `VerifyProofBatch` instruction.
+///
+/// Only available with the `unstable` feature; the proof program does not support proof batches
+/// yet.
+#[cfg(feature = "unstable")]
+pub fn verify_proof_batch_instruction_data(input: &[u8]) -> Result<(), ProofVerificationError> {
+    if ProofInstruction::instruction_type(input) != Some(ProofInstruction::VerifyProofBatch) {
+        return Err(ProofVerificationError::ProofBatch);
+    }
+    let header =
+        ProofInstruction::proof_batch_header(input).ok_or(ProofVerificationError::ProofBatch)?;
+
+    match ProofType::try_from(header.proof_type) {
+        Ok(ProofType::CiphertextCommitmentEquality) => verify_proof_batch_data::<
+            CiphertextCommitmentEqualityProofData,
+            CiphertextCommitmentEqualityProofContext,
+        >(input),
+        Ok(ProofType::BatchedGroupedCiphertext3HandlesValidity) => verify_proof_batch_data::<
+            BatchedGroupedCiphertext3HandlesValidityProofData,
+            BatchedGroupedCiphertext3HandlesValidityProofContext,
+        >(input),
+        Ok(ProofType::BatchedRangeProofU64) => {
+            verify_proof_batch_data::<BatchedRangeProofU64Data, BatchedRangeProofContext>(input)
+        }
+        Ok(ProofType::BatchedRangeProofU128) => {
+            verify_proof_batch_data::<BatchedRangeProofU128Data, BatchedRangeProofContext>(input)
+        }
+        _ => Err(ProofVerificationError::ProofBatch),
+    }
+}
+
+#[cfg(feature = "unstable")]
+fn verify_proof_batch_data<T, U>(input: &[u8]) -> Result<(), ProofVerificationError>
+where
+    T: Pod + ZkProofData<U> + AppendVerificationTerms,
+    U: Pod,
+{
+    let proof_data = ProofInstruction::proof_data_batch::<T, U>(input)
+        .ok_or(ProofVerificationError::ProofBatch)?;
+    verify_proof_batch(proof_data)
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "unstable")]
+    use solana_zk_elgamal_proof_interface::instruction::verify_proof_batch as verify_proof_batch_instruction;
+    use {
+        super::*,
+        crate::{
+            encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+            zk_elgamal_proof_program::build_ciphertext_commitment_equality_proof_data,
+        },
+        solana_zk_elgamal_proof_interface::proof_data::CiphertextCommitmentEqualityProofData,
+    };
+
+    fn build_equality_proof_data(amount: u64) -> CiphertextCommitmentEqualityProofData {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(amount);
+        let (commitment, opening) = Pedersen::new(amount);
+        build_ciphertext_commitment_equality_proof_data(
+            &keypair,
+            &ciphertext,
+            &commitment,
+            &opening,
+            amount,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_proof_batch_correctness() {
+        let proof_data: Vec<_> = (0..4).map(build_equality_proof_data).collect();
+        assert!(verify_proof_batch(&proof_data).is_ok());
+
+        // a single invalid proof makes the batch fail
+        let mut tampered = proof_data.clone();
+        tampered[2].context.ciphertext = build_equality_proof_data(2).context.ciphertext;
+        assert_eq!(
+            verify_proof_batch(&tampered).unwrap_err(),
+            ProofVerificationError::CombinedAlgebraicRelation
+        );
+    }
+
+    #[test]
+    fn test_proof_batch_size() {
+        let empty: [CiphertextCommitmentEqualityProofData; 0] = [];
+        assert_eq!(
+            verify_proof_batch(&empty).unwrap_err(),
+            ProofVerificationError::ProofBatch
+        );
+
+        let proof_data = vec![build_equality_proof_data(1); MAX_PROOF_BATCH_SIZE + 1];
+        assert_eq!(
+            verify_proof_batch(&proof_data).unwrap_err(),
+            ProofVerificationError::ProofBatch
+        );
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn test_proof_batch_instruction() {
+        let proof_data: Vec<_> = (0..4).map(build_equality_proof_data).collect();
+        let instruction = verify_proof_batch_instruction(&proof_data).unwrap();
+        assert_eq!(
+            ProofInstruction::proof_data_batch::<
+                CiphertextCommitmentEqualityProofData,
+                CiphertextCommitmentEqualityProofContext,
+            >(&instruction.data),
+            Some(proof_data.as_slice())
+        );
+        assert!(verify_proof_batch_instruction_data(&instruction.data).is_ok());
+
+        // a single invalid proof makes the batch fail
+        let mut tampered = proof_data.clone();
+        tampered[2].context.ciphertext = build_equality_proof_data(2).context.ciphertext;
+        let instruction = verify_proof_batch_instruction(&tampered).unwrap();
+        assert!(verify_proof_batch_instruction_data(&instruction.data).is_err());
+
+        // empty and oversized batches cannot be encoded
+        let empty: [CiphertextCommitmentEqualityProofData; 0] = [];
+        assert!(verify_proof_batch_instruction(&empty).is_none());
+        let oversized = vec![build_equality_proof_data(1); MAX_PROOF_BATCH_SIZE + 1];
+        assert!(verify_proof_batch_instruction(&oversized).is_none());
+
+        // the number of proofs in the header must match the instruction data
+        let mut instruction = verify_proof_batch_instruction(&proof_data[..2]).unwrap();
+        instruction.data[2] = 3;
+        assert_eq!(
+            verify_proof_batch_instruction_data(&instruction.data).unwrap_err(),
+            ProofVerificationError::ProofBatch
+        );
+    }
+}
@@ -1,29 +1,44 @@
 use {
     crate::{
-        encryption::elgamal::ElGamalKeypair,
+        encryption::elgamal::{ElGamalKeypair, ElGamalPubkey},
         sigma_proofs::pubkey_validity::PubkeyValidityProof,
-        transcript::TranscriptProtocol,
+        transcript::{StatementContext, TranscriptProtocol},
         zk_elgamal_proof_program::{
             errors::{ProofGenerationError, ProofVerificationError},
             VerifyZkProof,
         },
     },
     merlin::Transcript,
+    solana_address::Address,
     solana_zk_elgamal_proof_interface::proof_data::{
         PubkeyValidityProofContext, PubkeyValidityProofData,
     },
-    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    solana_zk_sdk_pod::{
+        encryption::elgamal::PodElGamalPubkey, sigma_proofs::PodPubkeyValidityProof,
+    },
     std::convert::TryInto,
 };
 
 pub fn build_pubkey_validity_proof_data(
     keypair: &ElGamalKeypair,
+) -> Result<PubkeyValidityProofData, ProofGenerationError> {
+    build_pubkey_validity_proof_data_with_statement_context(keypair, None)
+}
+
+/// Generates the proof data of [`build_pubkey_validity_proof_data`] with `statement_context`, if
+/// any, bound into the transcript of the proof.
+pub(crate) fn build_pubkey_validity_proof_data_with_statement_context(
+    keypair: &ElGamalKeypair,
+    statement_context: Option<&[u8]>,
 ) -> Result<PubkeyValidityProofData, ProofGenerationError> {
     let pod_pubkey = PodElGamalPubkey(keypair.pubkey().into());
 
     let context = PubkeyValidityProofContext { pubkey: pod_pubkey };
 
-    let mut transcript = Transcript::new_zk_elgamal_transcript(b"pubkey-validity-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"pubkey-validity-instruction",
+        statement_context,
+    );
     let proof = PubkeyValidityProof::new(keypair, &mut transcript).into();
 
     Ok(PubkeyValidityProofData { context, proof })
@@ -31,13 +46,97 @@ pub fn build_pubkey_validity_proof_data(
 
 impl VerifyZkProof for PubkeyValidityProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(b"pubkey-validity-instruction");
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"pubkey-validity-instruction",
+            statement_context,
+        );
         let pubkey = self.context.pubkey.try_into()?;
         let proof: PubkeyValidityProof = self.proof.try_into()?;
         proof.verify(&pubkey, &mut transcript).map_err(|e| e.into())
     }
 }
 
+/// Generates the proof data for a public key validity proof that is bound to a token account.
+///
+/// The owner of the token account and the address of the account are bound into the transcript
+/// of the proof as the statement context of [`StatementContext::for_account`], so the proof data
+/// verifies only with [`verify_pubkey_validity_proof_data_for_account`] for the same owner and
+/// account. The registration of a key for one account therefore cannot be replayed into the
+/// registration of the key for another account.
+pub fn build_pubkey_validity_proof_data_for_account(
+    keypair: &ElGamalKeypair,
+    owner: &Address,
+    token_account: &Address,
+) -> Result<PubkeyValidityProofData, ProofGenerationError> {
+    let context = StatementContext::for_account(*owner, *token_account);
+    build_pubkey_validity_proof_data_with_statement_context(
+        keypair,
+        Some(&context.to_statement_context()),
+    )
+}
+
+/// Verifies the proof data for a public key validity proof that is bound to a token account.
+///
+/// The proof data must have been generated with [`build_pubkey_validity_proof_data_for_account`]
+/// for the same owner and account.
+pub fn verify_pubkey_validity_proof_data_for_account(
+    proof_data: &PubkeyValidityProofData,
+    owner: &Address,
+    token_account: &Address,
+) -> Result<(), ProofVerificationError> {
+    let context = StatementContext::for_account(*owner, *token_account);
+    proof_data.verify_proof_with_statement_context(Some(&context.to_statement_context()))
+}
+
+/// Generates public key validity proofs for a batch of keypairs in a single transcript.
+///
+/// The proofs can only be verified together and in order with
+/// [`verify_pubkey_validity_proof_batch`].
+pub fn build_pubkey_validity_proof_batch(
+    keypairs: &[ElGamalKeypair],
+) -> Vec<PodPubkeyValidityProof> {
+    let mut transcript = Transcript::new_zk_elgamal_transcript(b"pubkey-validity-batch");
+    PubkeyValidityProof::new_batch(keypairs, &mut transcript)
+        .iter()
+        .map(PodPubkeyValidityProof::from)
+        .collect()
+}
+
+/// Verifies a batch of public key validity proofs that were generated with
+/// [`build_pubkey_validity_proof_batch`] with a single multiscalar multiplication.
+///
+/// The `i`-th proof is verified against the `i`-th public key. Returns
+/// [`ProofVerificationError::ProofBatch`] if the batch is empty.
+///
+/// This function is randomized. It uses `OsRng` internally to generate the weights that combine
+/// the proofs.
+pub fn verify_pubkey_validity_proof_batch(
+    pubkeys: &[PodElGamalPubkey],
+    proofs: &[PodPubkeyValidityProof],
+) -> Result<(), ProofVerificationError> {
+    if proofs.is_empty() {
+        return Err(ProofVerificationError::ProofBatch);
+    }
+    let pubkeys = pubkeys
+        .iter()
+        .map(|pubkey| ElGamalPubkey::try_from(*pubkey))
+        .collect::<Result<Vec<_>, _>>()?;
+    let proofs = proofs
+        .iter()
+        .map(PubkeyValidityProof::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript(b"pubkey-validity-batch");
+    PubkeyValidityProof::verify_batch(&proofs, &pubkeys, &mut transcript).map_err(|e| e.into())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -49,4 +148,67 @@ mod test {
         let pubkey_validity_data = build_pubkey_validity_proof_data(&keypair).unwrap();
         assert!(pubkey_validity_data.verify_proof().is_ok());
     }
+
+    #[test]
+    fn test_pubkey_validity_proof_data_for_account() {
+        let keypair = ElGamalKeypair::new_rand();
+        let owner = Address::new_from_array([1; 32]);
+        let token_account = Address::new_from_array([2; 32]);
+
+        let proof_data =
+            build_pubkey_validity_proof_data_for_account(&keypair, &owner, &token_account).unwrap();
+        assert!(
+            verify_pubkey_validity_proof_data_for_account(&proof_data, &owner, &token_account)
+                .is_ok()
+        );
+
+        // the proof data cannot be replayed for another account, another owner, or without an
+        // account
+        let other_address = Address::new_from_array([3; 32]);
+        assert!(
+            verify_pubkey_validity_proof_data_for_account(&proof_data, &owner, &other_address)
+                .is_err()
+        );
+        assert!(verify_pubkey_validity_proof_data_for_account(
+            &proof_data,
+            &other_address,
+            &token_account
+        )
+        .is_err());
+        assert!(
+            verify_pubkey_validity_proof_data_for_account(&proof_data, &token_account, &owner)
+                .is_err()
+        );
+        assert!(proof_data.verify_proof().is_err());
+    }
+
+    #[test]
+    fn test_pubkey_validity_proof_batch() {
+        let keypairs = (0..8)
+            .map(|_| ElGamalKeypair::new_rand())
+            .collect::<Vec<_>>();
+        let pubkeys = keypairs
+            .iter()
+            .map(|keypair| PodElGamalPubkey(keypair.pubkey().into()))
+            .collect::<Vec<_>>();
+
+        let proofs = build_pubkey_validity_proof_batch(&keypairs);
+        assert!(verify_pubkey_validity_proof_batch(&pubkeys, &proofs).is_ok());
+
+        // the proofs are bound to their order in the batch
+        let mut swapped_pubkeys = pubkeys.clone();
+        swapped_pubkeys.swap(0, 1);
+        let mut swapped_proofs = proofs.clone();
+        swapped_proofs.swap(0, 1);
+        assert!(verify_pubkey_validity_proof_batch(&swapped_pubkeys, &swapped_proofs).is_err());
+
+        // a proof of a single public key does not verify in a batch
+        assert!(verify_pubkey_validity_proof_batch(&pubkeys[..1], &proofs[..1]).is_err());
+
+        assert!(verify_pubkey_validity_proof_batch(&pubkeys[1..], &proofs).is_err());
+        assert_eq!(
+            verify_pubkey_validity_proof_batch(&[], &[]),
+            Err(ProofVerificationError::ProofBatch)
+        );
+    }
 }
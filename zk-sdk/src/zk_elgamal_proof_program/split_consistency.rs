@@ -0,0 +1,199 @@
+pub use crate::sigma_proofs::split_consistency::{SPLIT_HI_BIT_LENGTH, SPLIT_LO_BIT_LENGTH};
+use {
+    crate::{
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalPubkey},
+            pedersen::PedersenOpening,
+        },
+        sigma_proofs::split_consistency::SplitConsistencyProof,
+        transcript::TranscriptProtocol,
+        zk_elgamal_proof_program::{
+            errors::{ProofGenerationError, ProofVerificationError},
+            VerifyZkProof,
+        },
+    },
+    merlin::Transcript,
+    solana_zk_elgamal_proof_interface::proof_data::{
+        SplitConsistencyProofContext, SplitConsistencyProofData,
+    },
+    solana_zk_sdk_pod::encryption::elgamal::{PodElGamalCiphertext, PodElGamalPubkey},
+};
+
+/// Splits a 64-bit amount into its low 48 bits and its high 16 bits.
+pub fn split_amount(amount: u64) -> (u64, u64) {
+    let amount_lo = amount & ((1 << SPLIT_LO_BIT_LENGTH) - 1);
+    let amount_hi = amount >> SPLIT_LO_BIT_LENGTH;
+    (amount_lo, amount_hi)
+}
+
+/// Generates the split consistency proof data for ciphertexts of the low 48 bits and the high 16
+/// bits of `amount` and a ciphertext of `amount`, all under `pubkey`.
+///
+/// The proof does not certify the bit lengths of the two halves, so it must be accompanied by a
+/// range proof on the commitments of both halves.
+///
+/// Returns [`ProofGenerationError::InconsistentInput`] if the ciphertexts do not encrypt the split
+/// of `amount` under `pubkey` and the given openings.
+#[allow(clippy::too_many_arguments)]
+pub fn build_split_consistency_proof_data(
+    pubkey: &ElGamalPubkey,
+    ciphertext_lo: &ElGamalCiphertext,
+    ciphertext_hi: &ElGamalCiphertext,
+    ciphertext: &ElGamalCiphertext,
+    amount: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+    opening: &PedersenOpening,
+) -> Result<SplitConsistencyProofData, ProofGenerationError> {
+    build_split_consistency_proof_data_with_statement_context(
+        pubkey,
+        ciphertext_lo,
+        ciphertext_hi,
+        ciphertext,
+        amount,
+        opening_lo,
+        opening_hi,
+        opening,
+        None,
+    )
+}
+
+/// Generates the proof data of [`build_split_consistency_proof_data`] with `statement_context`, if
+/// any, bound into the transcript of the proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_split_consistency_proof_data_with_statement_context(
+    pubkey: &ElGamalPubkey,
+    ciphertext_lo: &ElGamalCiphertext,
+    ciphertext_hi: &ElGamalCiphertext,
+    ciphertext: &ElGamalCiphertext,
+    amount: u64,
+    opening_lo: &PedersenOpening,
+    opening_hi: &PedersenOpening,
+    opening: &PedersenOpening,
+    statement_context: Option<&[u8]>,
+) -> Result<SplitConsistencyProofData, ProofGenerationError> {
+    let (amount_lo, amount_hi) = split_amount(amount);
+    if *ciphertext_lo != pubkey.encrypt_with(amount_lo, opening_lo)
+        || *ciphertext_hi != pubkey.encrypt_with(amount_hi, opening_hi)
+        || *ciphertext != pubkey.encrypt_with(amount, opening)
+    {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = SplitConsistencyProofContext {
+        pubkey: PodElGamalPubkey(pubkey.into()),
+        ciphertext_lo: PodElGamalCiphertext(ciphertext_lo.to_bytes()),
+        ciphertext_hi: PodElGamalCiphertext(ciphertext_hi.to_bytes()),
+        ciphertext: PodElGamalCiphertext(ciphertext.to_bytes()),
+    };
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"split-consistency-instruction",
+        statement_context,
+    );
+    let proof = SplitConsistencyProof::new(
+        pubkey,
+        ciphertext_lo,
+        ciphertext_hi,
+        ciphertext,
+        opening_lo,
+        opening_hi,
+        opening,
+        &mut transcript,
+    )
+    .into();
+
+    Ok(SplitConsistencyProofData { context, proof })
+}
+
+impl VerifyZkProof for SplitConsistencyProofData {
+    fn verify_proof(&self) -> Result<(), ProofVerificationError> {
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"split-consistency-instruction",
+            statement_context,
+        );
+        let pubkey = self.context.pubkey.try_into()?;
+        let ciphertext_lo = self.context.ciphertext_lo.try_into()?;
+        let ciphertext_hi = self.context.ciphertext_hi.try_into()?;
+        let ciphertext = self.context.ciphertext.try_into()?;
+        let proof: SplitConsistencyProof = self.proof.try_into()?;
+        proof
+            .verify(
+                &pubkey,
+                &ciphertext_lo,
+                &ciphertext_hi,
+                &ciphertext,
+                &mut transcript,
+            )
+            .map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::encryption::elgamal::ElGamalKeypair};
+
+    #[test]
+    fn test_split_consistency_instruction_correctness() {
+        let keypair = ElGamalKeypair::new_rand();
+        let pubkey = keypair.pubkey();
+
+        let amount = u64::MAX - 7;
+        let (amount_lo, amount_hi) = split_amount(amount);
+        assert_eq!(amount_lo + (amount_hi << SPLIT_LO_BIT_LENGTH), amount);
+
+        let opening_lo = PedersenOpening::new_rand();
+        let opening_hi = PedersenOpening::new_rand();
+        let opening = PedersenOpening::new_rand();
+        let ciphertext_lo = pubkey.encrypt_with(amount_lo, &opening_lo);
+        let ciphertext_hi = pubkey.encrypt_with(amount_hi, &opening_hi);
+        let ciphertext = pubkey.encrypt_with(amount, &opening);
+
+        let proof_data = build_split_consistency_proof_data(
+            pubkey,
+            &ciphertext_lo,
+            &ciphertext_hi,
+            &ciphertext,
+            amount,
+            &opening_lo,
+            &opening_hi,
+            &opening,
+        )
+        .unwrap();
+        assert!(proof_data.verify_proof().is_ok());
+
+        // the halves are swapped
+        let result = build_split_consistency_proof_data(
+            pubkey,
+            &ciphertext_hi,
+            &ciphertext_lo,
+            &ciphertext,
+            amount,
+            &opening_hi,
+            &opening_lo,
+            &opening,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+
+        // the ciphertexts are encrypted under a different pubkey
+        let other_keypair = ElGamalKeypair::new_rand();
+        let result = build_split_consistency_proof_data(
+            other_keypair.pubkey(),
+            &ciphertext_lo,
+            &ciphertext_hi,
+            &ciphertext,
+            amount,
+            &opening_lo,
+            &opening_hi,
+            &opening,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
+}
@@ -0,0 +1,238 @@
+//! Stateless verification of proof data.
+//!
+//! The functions of this module verify proof data the same way as the proof program, but without
+//! reading sysvars, accounts, or any other state of the runtime. RPC simulators, indexers, and
+//! local test environments can use them to check proofs and to recover the verified contexts
+//! without running the program.
+
+use {
+    crate::zk_elgamal_proof_program::{errors::ProofVerificationError, VerifyZkProof},
+    bytemuck::{bytes_of, Pod},
+    solana_zk_elgamal_proof_interface::{instruction::ProofInstruction, proof_data::*},
+};
+
+/// A proof context that has been verified by [`verify_instruction_data`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedProofContext {
+    /// The proof type of the verified proof
+    pub proof_type: ProofType,
+    /// The bytes of the proof context, as they are written to a context state account
+    pub context: Vec<u8>,
+}
+
+/// Verifies the bytes of a proof data of type `T` and returns its context.
+pub fn verify_proof_data<T, U>(proof_data: &[u8]) -> Result<U, ProofVerificationError>
+where
+    T: Pod + ZkProofData<U> + VerifyZkProof,
+    U: Pod,
+{
+    let proof_data: &T =
+        bytemuck::try_from_bytes(proof_data).map_err(|_| ProofVerificationError::ProofData)?;
+    proof_data.verify_proof()?;
+    Ok(*proof_data.context_data())
+}
+
+/// Verifies the proof in the data of a proof verification instruction and returns its context.
+///
+/// Only instructions that carry the proof in the instruction data are supported. Instructions
+/// that read the proof from an account, `CloseContextState`, and `VerifyProofBatch` are rejected;
+/// a proof batch is verified with `verify_proof_batch_instruction_data`.
+pub fn verify_instruction_data(
+    input: &[u8],
+) -> Result<VerifiedProofContext, ProofVerificationError> {
+    let instruction =
+        ProofInstruction::instruction_type(input).ok_or(ProofVerificationError::ProofData)?;
+    visit_proof_instruction(instruction, VerifyInstructionData(input))
+        .ok_or(ProofVerificationError::ProofData)?
+}
+
+/// A function that is generic over the proof data of a proof verification instruction.
+pub(crate) trait ProofDataVisitor {
+    type Output;
+
+    fn visit<T, U>(self) -> Self::Output
+    where
+        T: Pod + ZkProofData<U> + VerifyZkProof,
+        U: Pod;
+}
+
+/// Calls `visitor` with the proof data type of `instruction`.
+///
+/// Returns `None` if `instruction` is not a proof verification instruction for a single proof.
+pub(crate) fn visit_proof_instruction<V: ProofDataVisitor>(
+    instruction: ProofInstruction,
+    visitor: V,
+) -> Option<V::Output> {
+    let output = match instruction {
+        ProofInstruction::CloseContextState => return None,
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyProofBatch => return None,
+        ProofInstruction::VerifyZeroCiphertext => visitor.visit::<ZeroCiphertextProofData, _>(),
+        ProofInstruction::VerifyCiphertextCiphertextEquality => {
+            visitor.visit::<CiphertextCiphertextEqualityProofData, _>()
+        }
+        ProofInstruction::VerifyCiphertextCommitmentEquality => {
+            visitor.visit::<CiphertextCommitmentEqualityProofData, _>()
+        }
+        ProofInstruction::VerifyPubkeyValidity => visitor.visit::<PubkeyValidityProofData, _>(),
+        ProofInstruction::VerifyPercentageWithCap => {
+            visitor.visit::<PercentageWithCapProofData, _>()
+        }
+        ProofInstruction::VerifyBatchedRangeProofU64 => {
+            visitor.visit::<BatchedRangeProofU64Data, _>()
+        }
+        ProofInstruction::VerifyBatchedRangeProofU128 => {
+            visitor.visit::<BatchedRangeProofU128Data, _>()
+        }
+        ProofInstruction::VerifyBatchedRangeProofU256 => {
+            visitor.visit::<BatchedRangeProofU256Data, _>()
+        }
+        ProofInstruction::VerifyGroupedCiphertext2HandlesValidity => {
+            visitor.visit::<GroupedCiphertext2HandlesValidityProofData, _>()
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext2HandlesValidity => {
+            visitor.visit::<BatchedGroupedCiphertext2HandlesValidityProofData, _>()
+        }
+        ProofInstruction::VerifyGroupedCiphertext3HandlesValidity => {
+            visitor.visit::<GroupedCiphertext3HandlesValidityProofData, _>()
+        }
+        ProofInstruction::VerifyBatchedGroupedCiphertext3HandlesValidity => {
+            visitor.visit::<BatchedGroupedCiphertext3HandlesValidityProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyPercentageWithCapSplit => {
+            visitor.visit::<PercentageWithCapSplitProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyPercentageWithCapU128 => {
+            visitor.visit::<PercentageWithCapU128ProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyGroupedCiphertext2HandlesCiphertextEquality => {
+            visitor.visit::<GroupedCiphertext2HandlesCiphertextEqualityProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyGroupedCiphertext3HandlesCiphertextEquality => {
+            visitor.visit::<GroupedCiphertext3HandlesCiphertextEqualityProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyGroupedZeroCiphertext2Handles => {
+            visitor.visit::<GroupedZeroCiphertext2HandlesProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyGroupedZeroCiphertext3Handles => {
+            visitor.visit::<GroupedZeroCiphertext3HandlesProofData, _>()
+        }
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifySplitConsistency => visitor.visit::<SplitConsistencyProofData, _>(),
+        #[cfg(feature = "unstable")]
+        ProofInstruction::VerifyCiphertextCommitmentOpeningEquality => {
+            visitor.visit::<CiphertextCommitmentOpeningEqualityProofData, _>()
+        }
+    };
+    Some(output)
+}
+
+struct VerifyInstructionData<'a>(&'a [u8]);
+
+impl ProofDataVisitor for VerifyInstructionData<'_> {
+    type Output = Result<VerifiedProofContext, ProofVerificationError>;
+
+    fn visit<T, U>(self) -> Self::Output
+    where
+        T: Pod + ZkProofData<U> + VerifyZkProof,
+        U: Pod,
+    {
+        verify_instruction_proof_data::<T, U>(self.0)
+    }
+}
+
+fn verify_instruction_proof_data<T, U>(
+    input: &[u8],
+) -> Result<VerifiedProofContext, ProofVerificationError>
+where
+    T: Pod + ZkProofData<U> + VerifyZkProof,
+    U: Pod,
+{
+    let proof_data =
+        ProofInstruction::proof_data::<T, U>(input).ok_or(ProofVerificationError::ProofData)?;
+    proof_data.verify_proof()?;
+    Ok(VerifiedProofContext {
+        proof_type: T::PROOF_TYPE,
+        context: bytes_of(proof_data.context_data()).to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            encryption::elgamal::ElGamalKeypair,
+            zk_elgamal_proof_program::{
+                build_pubkey_validity_proof_data, build_zero_ciphertext_proof_data,
+            },
+        },
+    };
+
+    #[test]
+    fn test_verify_proof_data() {
+        let keypair = ElGamalKeypair::new_rand();
+        let proof_data = build_pubkey_validity_proof_data(&keypair).unwrap();
+
+        let context = verify_proof_data::<PubkeyValidityProofData, PubkeyValidityProofContext>(
+            bytes_of(&proof_data),
+        )
+        .unwrap();
+        assert_eq!(context, proof_data.context);
+
+        let mut invalid_data = proof_data;
+        invalid_data.context.pubkey = (*ElGamalKeypair::new_rand().pubkey()).into();
+        assert!(
+            verify_proof_data::<PubkeyValidityProofData, PubkeyValidityProofContext>(bytes_of(
+                &invalid_data
+            ))
+            .is_err()
+        );
+        assert_eq!(
+            verify_proof_data::<PubkeyValidityProofData, PubkeyValidityProofContext>(&[0; 3]),
+            Err(ProofVerificationError::ProofData)
+        );
+    }
+
+    #[test]
+    fn test_verify_instruction_data() {
+        let keypair = ElGamalKeypair::new_rand();
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let proof_data = build_zero_ciphertext_proof_data(&keypair, &ciphertext).unwrap();
+
+        let instruction =
+            ProofInstruction::VerifyZeroCiphertext.encode_verify_proof(None, &proof_data);
+        assert_eq!(
+            verify_instruction_data(&instruction.data),
+            Ok(VerifiedProofContext {
+                proof_type: ProofType::ZeroCiphertext,
+                context: bytes_of(&proof_data.context).to_vec(),
+            })
+        );
+
+        // the proof data must match the instruction type
+        let instruction =
+            ProofInstruction::VerifyPubkeyValidity.encode_verify_proof(None, &proof_data);
+        assert_eq!(
+            verify_instruction_data(&instruction.data),
+            Err(ProofVerificationError::ProofData)
+        );
+
+        // a proof in an account cannot be verified without reading the account
+        let instruction = ProofInstruction::VerifyZeroCiphertext.encode_verify_proof_from_account(
+            None,
+            &solana_address::Address::new_unique(),
+            0,
+        );
+        assert_eq!(
+            verify_instruction_data(&instruction.data),
+            Err(ProofVerificationError::ProofData)
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use {
     crate::{
-        encryption::elgamal::{ElGamalCiphertext, ElGamalKeypair},
+        encryption::{
+            elgamal::{ElGamalCiphertext, ElGamalKeypair},
+            secret_key_handle::{SecretKeyBackend, SecretKeyHandle},
+        },
         sigma_proofs::zero_ciphertext::ZeroCiphertextProof,
         transcript::TranscriptProtocol,
         zk_elgamal_proof_program::{
@@ -20,6 +23,16 @@ use {
 pub fn build_zero_ciphertext_proof_data(
     keypair: &ElGamalKeypair,
     ciphertext: &ElGamalCiphertext,
+) -> Result<ZeroCiphertextProofData, ProofGenerationError> {
+    build_zero_ciphertext_proof_data_with_statement_context(keypair, ciphertext, None)
+}
+
+/// Generates the proof data of [`build_zero_ciphertext_proof_data`] with `statement_context`, if
+/// any, bound into the transcript of the proof.
+pub(crate) fn build_zero_ciphertext_proof_data_with_statement_context(
+    keypair: &ElGamalKeypair,
+    ciphertext: &ElGamalCiphertext,
+    statement_context: Option<&[u8]>,
 ) -> Result<ZeroCiphertextProofData, ProofGenerationError> {
     // Ciphertext should decrypt to Identity
     let decrypted_point = ciphertext.decrypt(keypair.secret()).target;
@@ -35,15 +48,54 @@ pub fn build_zero_ciphertext_proof_data(
         ciphertext: pod_ciphertext,
     };
 
-    let mut transcript = Transcript::new_zk_elgamal_transcript(b"zero-ciphertext-instruction");
+    let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+        b"zero-ciphertext-instruction",
+        statement_context,
+    );
     let proof = ZeroCiphertextProof::new(keypair, ciphertext, &mut transcript).into();
 
     Ok(ZeroCiphertextProofData { context, proof })
 }
 
+pub fn build_zero_ciphertext_proof_data_with_secret_key_handle<B: SecretKeyBackend>(
+    secret_key_handle: &SecretKeyHandle<B>,
+    ciphertext: &ElGamalCiphertext,
+) -> Result<ZeroCiphertextProofData, ProofGenerationError> {
+    // Ciphertext should decrypt to Identity
+    let decrypted_point = secret_key_handle.decrypt(ciphertext)?.target;
+    if !decrypted_point.is_identity() {
+        return Err(ProofGenerationError::InconsistentInput);
+    }
+
+    let context = ZeroCiphertextProofContext {
+        pubkey: PodElGamalPubkey((*secret_key_handle.pubkey()).into()),
+        ciphertext: PodElGamalCiphertext(ciphertext.to_bytes()),
+    };
+
+    let mut transcript = Transcript::new_zk_elgamal_transcript(b"zero-ciphertext-instruction");
+    let proof = ZeroCiphertextProof::new_with_secret_key_handle(
+        secret_key_handle,
+        ciphertext,
+        &mut transcript,
+    )?
+    .into();
+
+    Ok(ZeroCiphertextProofData { context, proof })
+}
+
 impl VerifyZkProof for ZeroCiphertextProofData {
     fn verify_proof(&self) -> Result<(), ProofVerificationError> {
-        let mut transcript = Transcript::new_zk_elgamal_transcript(b"zero-ciphertext-instruction");
+        self.verify_proof_with_statement_context(None)
+    }
+
+    fn verify_proof_with_statement_context(
+        &self,
+        statement_context: Option<&[u8]>,
+    ) -> Result<(), ProofVerificationError> {
+        let mut transcript = Transcript::new_zk_elgamal_transcript_with_statement_context(
+            b"zero-ciphertext-instruction",
+            statement_context,
+        );
         let pubkey = self.context.pubkey.try_into()?;
         let ciphertext = self.context.ciphertext.try_into()?;
         let proof: ZeroCiphertextProof = self.proof.try_into()?;
@@ -72,4 +124,25 @@ mod test {
         let result = build_zero_ciphertext_proof_data(&keypair, &ciphertext);
         assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
     }
+
+    #[test]
+    fn test_zero_ciphertext_proof_with_secret_key_handle() {
+        let keypair = ElGamalKeypair::new_rand();
+        let secret_key_handle = SecretKeyHandle::new(keypair.clone());
+
+        let ciphertext = keypair.pubkey().encrypt(0_u64);
+        let zero_ciphertext_proof_data = build_zero_ciphertext_proof_data_with_secret_key_handle(
+            &secret_key_handle,
+            &ciphertext,
+        )
+        .unwrap();
+        assert!(zero_ciphertext_proof_data.verify_proof().is_ok());
+
+        let ciphertext = keypair.pubkey().encrypt(1_u64);
+        let result = build_zero_ciphertext_proof_data_with_secret_key_handle(
+            &secret_key_handle,
+            &ciphertext,
+        );
+        assert_eq!(result, Err(ProofGenerationError::InconsistentInput));
+    }
 }
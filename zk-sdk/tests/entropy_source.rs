@@ -0,0 +1,50 @@
+//! The entropy source of `DefaultRng` can be registered only once per process, so the
+//! registration is tested in its own test binary.
+
+use {
+    rand::{rngs::OsRng, Error, RngCore},
+    solana_zk_sdk::{
+        encryption::{elgamal::ElGamalKeypair, pedersen::Pedersen},
+        rng::register_entropy_source,
+        zk_elgamal_proof_program::{
+            build_ciphertext_commitment_equality_proof_data, verify_proof_batch,
+        },
+    },
+    std::sync::atomic::{AtomicUsize, Ordering},
+};
+
+static ENTROPY_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+fn counting_entropy_source(dest: &mut [u8]) -> Result<(), Error> {
+    ENTROPY_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    OsRng.try_fill_bytes(dest)
+}
+
+#[test]
+fn test_register_entropy_source() {
+    assert!(register_entropy_source(counting_entropy_source).is_ok());
+    assert!(register_entropy_source(counting_entropy_source).is_err());
+
+    let requests = ENTROPY_REQUESTS.load(Ordering::Relaxed);
+    let keypair = ElGamalKeypair::new_rand();
+    assert!(ENTROPY_REQUESTS.load(Ordering::Relaxed) > requests);
+
+    let requests = ENTROPY_REQUESTS.load(Ordering::Relaxed);
+    let ciphertext = keypair.pubkey().encrypt(55_u64);
+    assert!(ENTROPY_REQUESTS.load(Ordering::Relaxed) > requests);
+    assert_eq!(ciphertext.decrypt_u32(keypair.secret()), Some(55));
+
+    // the weights with which the verifier combines proofs are drawn from the same source
+    let (commitment, opening) = Pedersen::new(55_u64);
+    let proof_data = build_ciphertext_commitment_equality_proof_data(
+        &keypair,
+        &ciphertext,
+        &commitment,
+        &opening,
+        55,
+    )
+    .unwrap();
+    let requests = ENTROPY_REQUESTS.load(Ordering::Relaxed);
+    assert!(verify_proof_batch(&[proof_data, proof_data]).is_ok());
+    assert!(ENTROPY_REQUESTS.load(Ordering::Relaxed) > requests);
+}